@@ -0,0 +1,88 @@
+//! Throughput of the signing path, from raw ed25519 operations up to a full
+//! request through the router. Run with `cargo bench --bench signing`; the
+//! `handler` group against the raw numbers shows what the HTTP stack and the
+//! blocking pool hand-off cost per request.
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use base64::{engine::general_purpose, Engine as _};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use serde_json::{json, Value};
+use solana_sdk::{
+    hash::Hash,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+use solana_system_interface::instruction as system_instruction;
+use tokio::runtime::Runtime;
+use tower::ServiceExt;
+
+use solana_fellowship_server::{config::Config, state::AppState, tx};
+
+const MESSAGE: &[u8] = b"Hello, Solana! Please sign this fixed-size benchmark message.";
+
+fn primitives(c: &mut Criterion) {
+    let keypair = Keypair::new();
+    let signature = keypair.sign_message(MESSAGE);
+    let pubkey = keypair.pubkey();
+
+    c.bench_function("keypair/new", |b| b.iter(Keypair::new));
+    c.bench_function("message/sign", |b| b.iter(|| keypair.sign_message(MESSAGE)));
+    c.bench_function("message/verify", |b| b.iter(|| signature.verify(pubkey.as_ref(), MESSAGE)));
+}
+
+fn instructions(c: &mut Criterion) {
+    let (from, to, mint) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    c.bench_function("instruction/transfer", |b| {
+        b.iter(|| system_instruction::transfer(&from, &to, 1_000))
+    });
+    c.bench_function("instruction/token-transfer", |b| {
+        b.iter(|| spl_token::instruction::transfer_checked(&spl_token::ID, &from, &mint, &to, &from, &[], 1_000, 6))
+    });
+    c.bench_function("transaction/encode", |b| {
+        b.iter(|| {
+            let transaction = tx::unsigned(&[system_instruction::transfer(&from, &to, 1_000)], &from, Hash::default());
+            tx::encode(&transaction)
+        })
+    });
+}
+
+fn post(path: &str, body: &Value) -> Request<Body> {
+    Request::post(path).header(header::CONTENT_TYPE, "application/json").body(Body::from(body.to_string())).unwrap()
+}
+
+/// One request through the whole router, middleware included.
+async fn call(app: Router, request: Request<Body>) {
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+fn handlers(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let app = runtime.block_on(async { solana_fellowship_server::app(AppState::new(Config::default())) });
+    let keypair = Keypair::new();
+    let sign = json!({ "message": "Hello, Solana!", "secret": keypair.to_base58_string() });
+    let signature = keypair.sign_message(b"Hello, Solana!");
+    let verify = json!({
+        "message": "Hello, Solana!",
+        "signature": general_purpose::STANDARD.encode(signature.as_ref()),
+        "pubkey": keypair.pubkey().to_string(),
+    });
+    let send = json!({ "from": keypair.pubkey().to_string(), "to": Pubkey::new_unique().to_string(), "lamports": 1 });
+
+    let mut group = c.benchmark_group("handler");
+    let requests = [("/keypair", json!({})), ("/message/sign", sign), ("/message/verify", verify), ("/send/sol", send)];
+    for (path, body) in requests {
+        group.bench_function(path.trim_start_matches('/'), |b| {
+            let request = || post(path, &body);
+            b.to_async(&runtime).iter_batched(request, |request| call(app.clone(), request), BatchSize::SmallInput)
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, primitives, instructions, handlers);
+criterion_main!(benches);
@@ -0,0 +1,742 @@
+//! Async client for the Superdev API, typed with the same request and
+//! response structs the server uses.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), superdev_client::Error> {
+//! use superdev_client::Client;
+//! use superdev_client::models::SignMessageRequest;
+//!
+//! let client = Client::new("http://localhost:3000").with_api_key("alice-key");
+//! let keypair = client.generate_keypair().await?;
+//! let request = SignMessageRequest {
+//!     message: "hi".to_string(),
+//!     secret: None,
+//!     key_id: keypair.key_id,
+//!     signature_encoding: Default::default(),
+//! };
+//! let signed = client.sign_message(&request).await?;
+//! println!("{}", signed.signature);
+//! # Ok(())
+//! # }
+//! ```
+
+use reqwest::{Method, RequestBuilder, Response};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+pub use superdev_models as models;
+
+use models::account::{CleanupRequest, CleanupResponse};
+use models::address_book::{AddressBookEntry, CreateAddressRequest, UpdateAddressRequest};
+use models::admin::{AdminStatus, FeaturesRequest, Health, RevokeRequest, RotateResponse, RuntimeStatus};
+use models::airdrop::{AirdropDryRun, BulkAirdropRequest, BulkAirdropResponse};
+use models::alt::{AltPlanRequest, AltPlanResponse};
+use models::anchor::{
+    AnchorInstructionRequest, ParseLogsRequest, ParseLogsResponse, RegisterIdlRequest, RegisterIdlResponse,
+};
+use models::assets::{Asset, AssetsByOwnerQuery, AssetsByOwnerResponse};
+use models::audit::AuditEntry;
+use models::block::{BlockQuery, BlockResponse};
+use models::borsh::{BorshDecodeRequest, BorshDecodeResponse, BorshEncodeRequest, BorshEncodeResponse};
+use models::cluster::{EpochInfoResponse, SlotResponse, ValidatorsQuery, ValidatorsResponse, VersionResponse};
+use models::compose::ComposeRequest;
+use models::convert::{SolConversion, SolQuery, TokenConversion, TokenQuery};
+use models::decode::{AccountSource, MintLayout, NonceLayout, StakeLayout, TokenAccountLayout};
+use models::derive::{AtaQuery, AtaResponse, PdaQuery, PdaResponse};
+use models::jobs::{Job, ScheduleRequest};
+use models::keys::{KeyInfo, KeyMetadata, KeysQuery};
+use models::merkle_tree::{
+    AppendLeafRequest, CreateMerkleTreeRequest, CreateMerkleTreeResponse, MerkleTreeCost, MerkleTreeCostQuery,
+};
+use models::nft::{BurnNftRequest, NftMetadataResponse, PrintEditionRequest, PrintEditionResponse};
+use models::nonce_pool::{
+    AcquireNonceRequest, AddNonceAccountsRequest, AddNonceAccountsResponse, NonceLease, NoncePoolStatus,
+    ReleaseNonceRequest,
+};
+use models::program::{
+    CloseBufferRequest, DeployProgramRequest, DeployProgramResponse, ProgramAccountsQuery, ProgramAccountsResponse,
+    ProgramUpload, ProgramUploadQuery, ProgramUploadRequest, ProgramUploadResponse, SetProgramAuthorityRequest,
+    UpgradeProgramRequest, WriteBufferRequest, WriteBufferResponse,
+};
+use models::qr::{PayQrQuery, QrQuery};
+use models::relayer::{RelayRequest, RelaySignResponse, RelaySubmitResponse, RelayerInfo};
+use models::solana_pay::{
+    DecodeQuery, DecodeResponse, EncodeRequest, EncodeResponse, PayRequestInfo, PayTransactionRequest,
+    PayTransactionResponse,
+};
+use models::squads::{
+    ApproveProposalRequest, CreateMultisigRequest, CreateMultisigResponse, ExecuteProposalRequest,
+    ProposalInstructionsResponse, VaultTransactionRequest, VaultTransactionResponse,
+};
+use models::stake::{RewardsQuery, RewardsResponse};
+use models::templates::{RenderTemplateRequest, RenderTemplateResponse, StoredTemplate, TemplateRequest};
+use models::token::{CreateMetadataRequest, HoldersQuery, HoldersResponse, TokenInfoResponse};
+use models::transaction::{
+    BuildTransactionRequest, BuildTransactionResponse, BundleTipInfo, InspectRequest, InspectResponse, PreviewRequest,
+    PreviewResponse, SendBundleRequest, SendBundleResponse, SendTransactionRequest, SendTransactionResponse,
+    SignTransactionMessageRequest, SignTransactionMessageResponse, TransactionSummary, VerifySignaturesRequest,
+    VerifySignaturesResponse,
+};
+use models::transfers::{ApproveRequest, Proposal, ProposeRequest};
+use models::vote::{VoteAuthorizeRequest, VoteWithdrawRequest};
+use models::{
+    BatchVerifyRequest, BatchVerifyResponse, CreateTokenRequest, DeterministicKeypairRequest,
+    DeterministicKeypairResponse, EncryptedKeypair, ExportKeypairRequest, ImportKeypairRequest, InstructionResponse,
+    KeypairQuery, KeypairResponse, MintTokenRequest, SendSolBatchRequest,
+    SendSolBatchResponse, SendSolRequest, SendSolResponse, SendTokenRequest, SendTokenResponse, SignMessageRequest,
+    SignMessageResponse, VerifyMessageRequest, VerifyMessageResponse,
+};
+
+#[derive(Error, Debug)]
+pub enum Error {
+    /// The request never got a usable response: connection, TLS or body
+    /// decoding failure.
+    #[error("request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    /// The server refused the request with its usual error body.
+    #[error("{message} ({code})")]
+    Api {
+        status: u16,
+        /// Stable error code, e.g. `INVALID_PUBKEY`.
+        code: String,
+        message: String,
+        /// Request field at fault, when the error is about one.
+        field: Option<String>,
+        /// Per-field violations of `VALIDATION_FAILED` errors.
+        details: Option<Value>,
+    },
+    /// The server failed without an error body, e.g. 404 for a hidden route
+    /// group.
+    #[error("server answered {0} without an error body")]
+    Status(u16),
+}
+
+/// Result of `POST /airdrop/bulk`, whose shape depends on `dry_run`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum AirdropOutcome {
+    Transactions(BulkAirdropResponse),
+    DryRun(AirdropDryRun),
+}
+
+#[derive(Deserialize)]
+struct Envelope<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: String,
+    code: String,
+    field: Option<String>,
+    details: Option<Value>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl Client {
+    /// Client for the server at `base_url`, e.g. `https://superdev.example.com`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_http_client(reqwest::Client::new(), base_url)
+    }
+
+    /// Uses a preconfigured `reqwest` client, e.g. one carrying a client
+    /// certificate for mutual TLS.
+    pub fn with_http_client(http: reqwest::Client, base_url: impl Into<String>) -> Self {
+        Self { http, base_url: base_url.into().trim_end_matches('/').to_string(), api_key: None }
+    }
+
+    /// Sends `key` as `X-Api-Key` with every request.
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    pub async fn generate_keypair(&self) -> Result<KeypairResponse, Error> {
+        self.post_empty("/keypair").await
+    }
+
+    /// `count` keypairs from one request.
+    pub async fn generate_keypairs(&self, count: usize) -> Result<Vec<KeypairResponse>, Error> {
+        let query = KeypairQuery { count: Some(count) };
+        let response = self.request(Method::POST, "/keypair").query(&query).send().await?;
+        data(response).await
+    }
+
+    pub async fn export_encrypted(&self, request: &ExportKeypairRequest) -> Result<EncryptedKeypair, Error> {
+        self.post("/keypair/export-encrypted", request).await
+    }
+
+    pub async fn import_encrypted(&self, request: &ImportKeypairRequest) -> Result<KeypairResponse, Error> {
+        self.post("/keypair/import-encrypted", request).await
+    }
+
+    /// Only available from servers built with the `dev-tools` feature.
+    pub async fn deterministic_keypair(
+        &self,
+        request: &DeterministicKeypairRequest,
+    ) -> Result<DeterministicKeypairResponse, Error> {
+        self.post("/keypair/deterministic", request).await
+    }
+
+    pub async fn create_token(&self, request: &CreateTokenRequest) -> Result<InstructionResponse, Error> {
+        self.post("/token/create", request).await
+    }
+
+    /// `CreateMetadataAccountV3` naming an existing fungible mint.
+    pub async fn create_token_metadata(&self, request: &CreateMetadataRequest) -> Result<InstructionResponse, Error> {
+        self.post("/token/create-metadata", request).await
+    }
+
+    pub async fn mint_token(&self, request: &MintTokenRequest) -> Result<InstructionResponse, Error> {
+        self.post("/token/mint", request).await
+    }
+
+    pub async fn sign_message(&self, request: &SignMessageRequest) -> Result<SignMessageResponse, Error> {
+        self.post("/message/sign", request).await
+    }
+
+    pub async fn verify_message(&self, request: &VerifyMessageRequest) -> Result<VerifyMessageResponse, Error> {
+        self.post("/message/verify", request).await
+    }
+
+    pub async fn verify_message_batch(&self, request: &BatchVerifyRequest) -> Result<BatchVerifyResponse, Error> {
+        self.post("/message/verify-batch", request).await
+    }
+
+    pub async fn send_sol(&self, request: &SendSolRequest) -> Result<InstructionResponse, Error> {
+        self.post("/send/sol", request).await
+    }
+
+    /// `send_sol` in the shape it had before it returned account metas.
+    pub async fn send_sol_v1(&self, request: &SendSolRequest) -> Result<SendSolResponse, Error> {
+        self.post("/v1/send/sol", request).await
+    }
+
+    pub async fn send_sol_batch(&self, request: &SendSolBatchRequest) -> Result<SendSolBatchResponse, Error> {
+        self.post("/send/sol-batch", request).await
+    }
+
+    pub async fn send_token(&self, request: &SendTokenRequest) -> Result<SendTokenResponse, Error> {
+        self.post("/send/token", request).await
+    }
+
+    pub async fn account_cleanup(&self, owner: &str, request: &CleanupRequest) -> Result<CleanupResponse, Error> {
+        self.post(&format!("/account/{owner}/cleanup"), request).await
+    }
+
+    pub async fn health(&self) -> Result<Health, Error> {
+        self.get("/health").await
+    }
+
+    pub async fn admin_status(&self) -> Result<AdminStatus, Error> {
+        self.get("/admin").await
+    }
+
+    pub async fn admin_reload(&self) -> Result<AdminStatus, Error> {
+        self.post_empty("/admin/reload").await
+    }
+
+    pub async fn debug_runtime(&self) -> Result<RuntimeStatus, Error> {
+        self.get("/debug/runtime").await
+    }
+
+    pub async fn rotate_master_key(&self) -> Result<RotateResponse, Error> {
+        self.post_empty("/admin/keystore/rotate").await
+    }
+
+    pub async fn revoke_api_key(&self, request: &RevokeRequest) -> Result<AdminStatus, Error> {
+        self.post("/admin/api-keys/revoke", request).await
+    }
+
+    pub async fn set_features(&self, request: &FeaturesRequest) -> Result<AdminStatus, Error> {
+        self.post("/admin/features", request).await
+    }
+
+    pub async fn bulk_airdrop(&self, request: &BulkAirdropRequest) -> Result<AirdropOutcome, Error> {
+        self.post("/airdrop/bulk", request).await
+    }
+
+    pub async fn plan_lookup_table(&self, request: &AltPlanRequest) -> Result<AltPlanResponse, Error> {
+        self.post("/alt/plan", request).await
+    }
+
+    pub async fn audit_log(&self) -> Result<Vec<AuditEntry>, Error> {
+        self.get("/audit").await
+    }
+
+    pub async fn register_idl(&self, request: &RegisterIdlRequest) -> Result<RegisterIdlResponse, Error> {
+        self.post("/anchor/idl", request).await
+    }
+
+    pub async fn anchor_instruction(&self, request: &AnchorInstructionRequest) -> Result<InstructionResponse, Error> {
+        self.post("/anchor/instruction", request).await
+    }
+
+    pub async fn parse_anchor_logs(&self, request: &ParseLogsRequest) -> Result<ParseLogsResponse, Error> {
+        self.post("/anchor/parse-logs", request).await
+    }
+
+    pub async fn borsh_encode(&self, request: &BorshEncodeRequest) -> Result<BorshEncodeResponse, Error> {
+        self.post("/borsh/encode", request).await
+    }
+
+    pub async fn borsh_decode(&self, request: &BorshDecodeRequest) -> Result<BorshDecodeResponse, Error> {
+        self.post("/borsh/decode", request).await
+    }
+
+    pub async fn compose(&self, request: &ComposeRequest) -> Result<BuildTransactionResponse, Error> {
+        self.post("/compose", request).await
+    }
+
+    pub async fn decode_mint(&self, source: &AccountSource) -> Result<MintLayout, Error> {
+        self.post("/decode/mint", source).await
+    }
+
+    pub async fn decode_nonce(&self, source: &AccountSource) -> Result<NonceLayout, Error> {
+        self.post("/decode/nonce", source).await
+    }
+
+    pub async fn decode_stake(&self, source: &AccountSource) -> Result<StakeLayout, Error> {
+        self.post("/decode/stake", source).await
+    }
+
+    pub async fn decode_token_account(&self, source: &AccountSource) -> Result<TokenAccountLayout, Error> {
+        self.post("/decode/token-account", source).await
+    }
+
+    pub async fn derive_ata(&self, query: &AtaQuery) -> Result<AtaResponse, Error> {
+        self.get_query("/derive/ata", query).await
+    }
+
+    pub async fn derive_pda(&self, query: &PdaQuery) -> Result<PdaResponse, Error> {
+        self.get_query("/derive/pda", query).await
+    }
+
+    pub async fn convert_sol(&self, query: &SolQuery) -> Result<SolConversion, Error> {
+        self.get_query("/convert/sol", query).await
+    }
+
+    pub async fn convert_token(&self, query: &TokenQuery) -> Result<TokenConversion, Error> {
+        self.get_query("/convert/token", query).await
+    }
+
+    pub async fn schedule_job(&self, request: &ScheduleRequest) -> Result<Job, Error> {
+        self.post("/jobs", request).await
+    }
+
+    pub async fn job(&self, id: &str) -> Result<Job, Error> {
+        self.get(&format!("/jobs/{id}")).await
+    }
+
+    pub async fn cancel_job(&self, id: &str) -> Result<Job, Error> {
+        let response = self.request(Method::DELETE, &format!("/jobs/{id}")).send().await?;
+        data(response).await
+    }
+
+    pub async fn keys(&self, query: &KeysQuery) -> Result<Vec<KeyInfo>, Error> {
+        self.get_query("/keys", query).await
+    }
+
+    pub async fn update_key(&self, key_id: &str, request: &KeyMetadata) -> Result<KeyInfo, Error> {
+        let response = self.request(Method::PUT, &format!("/keys/{key_id}")).json(request).send().await?;
+        data(response).await
+    }
+
+    pub async fn address_book(&self) -> Result<Vec<AddressBookEntry>, Error> {
+        self.get("/address-book").await
+    }
+
+    pub async fn address(&self, label: &str) -> Result<AddressBookEntry, Error> {
+        self.get(&format!("/address-book/{label}")).await
+    }
+
+    pub async fn create_address(&self, request: &CreateAddressRequest) -> Result<AddressBookEntry, Error> {
+        self.post("/address-book", request).await
+    }
+
+    pub async fn update_address(&self, label: &str, request: &UpdateAddressRequest) -> Result<AddressBookEntry, Error> {
+        let response = self.request(Method::PUT, &format!("/address-book/{label}")).json(request).send().await?;
+        data(response).await
+    }
+
+    pub async fn delete_address(&self, label: &str) -> Result<AddressBookEntry, Error> {
+        let response = self.request(Method::DELETE, &format!("/address-book/{label}")).send().await?;
+        data(response).await
+    }
+
+    pub async fn create_template(&self, request: &TemplateRequest) -> Result<StoredTemplate, Error> {
+        self.post("/templates", request).await
+    }
+
+    pub async fn templates(&self) -> Result<Vec<StoredTemplate>, Error> {
+        self.get("/templates").await
+    }
+
+    pub async fn template(&self, id: &str) -> Result<StoredTemplate, Error> {
+        self.get(&format!("/templates/{id}")).await
+    }
+
+    pub async fn update_template(&self, id: &str, request: &TemplateRequest) -> Result<StoredTemplate, Error> {
+        let response = self.request(Method::PUT, &format!("/templates/{id}")).json(request).send().await?;
+        data(response).await
+    }
+
+    pub async fn delete_template(&self, id: &str) -> Result<StoredTemplate, Error> {
+        let response = self.request(Method::DELETE, &format!("/templates/{id}")).send().await?;
+        data(response).await
+    }
+
+    pub async fn render_template(
+        &self,
+        id: &str,
+        request: &RenderTemplateRequest,
+    ) -> Result<RenderTemplateResponse, Error> {
+        self.post(&format!("/templates/{id}/render"), request).await
+    }
+
+    pub async fn relayer_info(&self) -> Result<RelayerInfo, Error> {
+        self.get("/relayer").await
+    }
+
+    pub async fn relayer_sign(&self, request: &RelayRequest) -> Result<RelaySignResponse, Error> {
+        self.post("/relayer/sign", request).await
+    }
+
+    pub async fn relayer_submit(&self, request: &RelayRequest) -> Result<RelaySubmitResponse, Error> {
+        self.post("/relayer/submit", request).await
+    }
+
+    pub async fn solana_pay_encode(&self, request: &EncodeRequest) -> Result<EncodeResponse, Error> {
+        self.post("/solana-pay/encode", request).await
+    }
+
+    pub async fn solana_pay_decode(&self, query: &DecodeQuery) -> Result<DecodeResponse, Error> {
+        self.get_query("/solana-pay/decode", query).await
+    }
+
+    /// PNG or SVG bytes, as `query.format` asks.
+    pub async fn solana_pay_qr(&self, query: &PayQrQuery) -> Result<Vec<u8>, Error> {
+        self.image("/solana-pay/qr", query).await
+    }
+
+    /// Spec-defined body, without the usual `success`/`data` envelope.
+    pub async fn solana_pay_request_info(&self, id: &str) -> Result<PayRequestInfo, Error> {
+        let response = self.request(Method::GET, &format!("/solana-pay/tx/{id}")).send().await?;
+        Ok(checked(response).await?.json().await?)
+    }
+
+    /// Spec-defined body, without the usual `success`/`data` envelope.
+    pub async fn solana_pay_request_transaction(
+        &self,
+        id: &str,
+        request: &PayTransactionRequest,
+    ) -> Result<PayTransactionResponse, Error> {
+        let response = self.request(Method::POST, &format!("/solana-pay/tx/{id}")).json(request).send().await?;
+        Ok(checked(response).await?.json().await?)
+    }
+
+    pub async fn squads_create_multisig(
+        &self,
+        request: &CreateMultisigRequest,
+    ) -> Result<CreateMultisigResponse, Error> {
+        self.post("/squads/multisig", request).await
+    }
+
+    pub async fn squads_vault_transaction(
+        &self,
+        request: &VaultTransactionRequest,
+    ) -> Result<VaultTransactionResponse, Error> {
+        self.post("/squads/vault-transaction", request).await
+    }
+
+    pub async fn squads_approve(
+        &self,
+        request: &ApproveProposalRequest,
+    ) -> Result<ProposalInstructionsResponse, Error> {
+        self.post("/squads/approve", request).await
+    }
+
+    pub async fn squads_execute(
+        &self,
+        request: &ExecuteProposalRequest,
+    ) -> Result<ProposalInstructionsResponse, Error> {
+        self.post("/squads/execute", request).await
+    }
+
+    pub async fn token_holders(&self, mint: &str, query: &HoldersQuery) -> Result<HoldersResponse, Error> {
+        self.get_query(&format!("/token/{mint}/holders"), query).await
+    }
+
+    pub async fn token_info(&self, mint: &str) -> Result<TokenInfoResponse, Error> {
+        self.get(&format!("/token/{mint}/info")).await
+    }
+
+    pub async fn nft_metadata(&self, mint: &str) -> Result<NftMetadataResponse, Error> {
+        self.get(&format!("/nft/{mint}/metadata")).await
+    }
+
+    pub async fn burn_nft(&self, request: &BurnNftRequest) -> Result<InstructionResponse, Error> {
+        self.post("/nft/burn", request).await
+    }
+
+    pub async fn print_edition(&self, request: &PrintEditionRequest) -> Result<PrintEditionResponse, Error> {
+        self.post("/nft/print-edition", request).await
+    }
+
+    pub async fn nonce_pool(&self) -> Result<NoncePoolStatus, Error> {
+        self.get("/nonce-pool").await
+    }
+
+    pub async fn add_nonce_accounts(&self, request: &AddNonceAccountsRequest) -> Result<AddNonceAccountsResponse, Error> {
+        self.post("/nonce-pool/accounts", request).await
+    }
+
+    pub async fn acquire_nonce(&self, request: &AcquireNonceRequest) -> Result<NonceLease, Error> {
+        self.post("/nonce-pool/acquire", request).await
+    }
+
+    pub async fn release_nonce(&self, request: &ReleaseNonceRequest) -> Result<NoncePoolStatus, Error> {
+        self.post("/nonce-pool/release", request).await
+    }
+
+    pub async fn epoch_info(&self) -> Result<EpochInfoResponse, Error> {
+        self.get("/cluster/epoch-info").await
+    }
+
+    pub async fn slot(&self) -> Result<SlotResponse, Error> {
+        self.get("/cluster/slot").await
+    }
+
+    pub async fn cluster_version(&self) -> Result<VersionResponse, Error> {
+        self.get("/cluster/version").await
+    }
+
+    pub async fn validators(&self, query: &ValidatorsQuery) -> Result<ValidatorsResponse, Error> {
+        self.get_query("/cluster/validators", query).await
+    }
+
+    pub async fn stake_rewards(&self, account: &str, query: &RewardsQuery) -> Result<RewardsResponse, Error> {
+        self.get_query(&format!("/stake/{account}/rewards"), query).await
+    }
+
+    pub async fn block(&self, slot: u64, query: &BlockQuery) -> Result<BlockResponse, Error> {
+        self.get_query(&format!("/block/{slot}"), query).await
+    }
+
+    pub async fn program_accounts(
+        &self,
+        program_id: &str,
+        query: &ProgramAccountsQuery,
+    ) -> Result<ProgramAccountsResponse, Error> {
+        self.get_query(&format!("/program/{program_id}/accounts"), query).await
+    }
+
+    pub async fn program_write_buffer(&self, request: &WriteBufferRequest) -> Result<WriteBufferResponse, Error> {
+        self.post("/program/buffer", request).await
+    }
+
+    pub async fn upload_program(&self, request: &ProgramUploadRequest) -> Result<ProgramUploadResponse, Error> {
+        self.post("/program/uploads", request).await
+    }
+
+    /// The upload's transactions re-packed, by default with the latest
+    /// blockhash.
+    pub async fn program_upload(&self, id: &str, query: &ProgramUploadQuery) -> Result<ProgramUploadResponse, Error> {
+        self.get_query(&format!("/program/uploads/{id}"), query).await
+    }
+
+    pub async fn delete_program_upload(&self, id: &str) -> Result<ProgramUpload, Error> {
+        let response = self.request(Method::DELETE, &format!("/program/uploads/{id}")).send().await?;
+        data(response).await
+    }
+
+    pub async fn program_deploy(&self, request: &DeployProgramRequest) -> Result<DeployProgramResponse, Error> {
+        self.post("/program/deploy", request).await
+    }
+
+    pub async fn program_upgrade(&self, request: &UpgradeProgramRequest) -> Result<InstructionResponse, Error> {
+        self.post("/program/upgrade", request).await
+    }
+
+    pub async fn program_set_authority(
+        &self,
+        request: &SetProgramAuthorityRequest,
+    ) -> Result<InstructionResponse, Error> {
+        self.post("/program/set-authority", request).await
+    }
+
+    pub async fn program_close_buffer(&self, request: &CloseBufferRequest) -> Result<InstructionResponse, Error> {
+        self.post("/program/close-buffer", request).await
+    }
+
+    pub async fn create_merkle_tree(
+        &self,
+        request: &CreateMerkleTreeRequest,
+    ) -> Result<CreateMerkleTreeResponse, Error> {
+        self.post("/merkle-tree/create", request).await
+    }
+
+    pub async fn merkle_tree_cost(&self, query: &MerkleTreeCostQuery) -> Result<MerkleTreeCost, Error> {
+        self.get_query("/merkle-tree/cost", query).await
+    }
+
+    pub async fn append_merkle_leaf(&self, request: &AppendLeafRequest) -> Result<InstructionResponse, Error> {
+        self.post("/merkle-tree/append", request).await
+    }
+
+    pub async fn asset(&self, id: &str) -> Result<Asset, Error> {
+        self.get(&format!("/assets/{id}")).await
+    }
+
+    pub async fn assets_by_owner(&self, query: &AssetsByOwnerQuery) -> Result<AssetsByOwnerResponse, Error> {
+        self.get_query("/assets/by-owner", query).await
+    }
+
+    pub async fn propose_transfer(&self, request: &ProposeRequest) -> Result<Proposal, Error> {
+        self.post("/transfers/propose", request).await
+    }
+
+    pub async fn approve_transfer(&self, request: &ApproveRequest) -> Result<Proposal, Error> {
+        self.post("/transfers/approve", request).await
+    }
+
+    pub async fn transfer(&self, id: &str) -> Result<Proposal, Error> {
+        self.get(&format!("/transfers/{id}")).await
+    }
+
+    pub async fn vote_withdraw(&self, request: &VoteWithdrawRequest) -> Result<InstructionResponse, Error> {
+        self.post("/vote/withdraw", request).await
+    }
+
+    pub async fn vote_authorize(&self, request: &VoteAuthorizeRequest) -> Result<InstructionResponse, Error> {
+        self.post("/vote/authorize", request).await
+    }
+
+    pub async fn build_transaction(
+        &self,
+        request: &BuildTransactionRequest,
+    ) -> Result<BuildTransactionResponse, Error> {
+        self.post("/transaction/build", request).await
+    }
+
+    pub async fn inspect_transaction(&self, request: &InspectRequest) -> Result<InspectResponse, Error> {
+        self.post("/transaction/inspect", request).await
+    }
+
+    pub async fn verify_transaction_signatures(
+        &self,
+        request: &VerifySignaturesRequest,
+    ) -> Result<VerifySignaturesResponse, Error> {
+        self.post("/transaction/verify-signatures", request).await
+    }
+
+    pub async fn sign_transaction_message(
+        &self,
+        request: &SignTransactionMessageRequest,
+    ) -> Result<SignTransactionMessageResponse, Error> {
+        self.post("/transaction/sign-message", request).await
+    }
+
+    pub async fn preview_transaction(&self, request: &PreviewRequest) -> Result<PreviewResponse, Error> {
+        self.post("/transaction/preview", request).await
+    }
+
+    pub async fn bundle_tip(&self) -> Result<BundleTipInfo, Error> {
+        self.get("/transaction/bundle-tip").await
+    }
+
+    pub async fn send_bundle(&self, request: &SendBundleRequest) -> Result<SendBundleResponse, Error> {
+        self.post("/transaction/send-bundle", request).await
+    }
+
+    pub async fn send_transaction(&self, request: &SendTransactionRequest) -> Result<SendTransactionResponse, Error> {
+        self.post("/transaction/send", request).await
+    }
+
+    pub async fn parse_transaction(&self, signature: &str) -> Result<TransactionSummary, Error> {
+        self.get(&format!("/transaction/parse/{signature}")).await
+    }
+
+    /// PNG or SVG bytes, as `query.format` asks.
+    pub async fn qr(&self, query: &QrQuery) -> Result<Vec<u8>, Error> {
+        self.image("/qr", query).await
+    }
+
+    /// JSON Schema of every request and response body.
+    pub async fn schemas(&self) -> Result<Value, Error> {
+        let response = self.request(Method::GET, "/schemas").send().await?;
+        Ok(checked(response).await?.json().await?)
+    }
+
+    /// Prometheus text exposition format.
+    pub async fn metrics(&self) -> Result<String, Error> {
+        let response = self.request(Method::GET, "/metrics").send().await?;
+        Ok(checked(response).await?.text().await?)
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self.http.request(method, format!("{}{path}", self.base_url));
+        match &self.api_key {
+            Some(key) => request.header("x-api-key", key),
+            None => request,
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, Error> {
+        let response = self.request(Method::GET, path).send().await?;
+        data(response).await
+    }
+
+    async fn get_query<Q: Serialize + ?Sized, T: DeserializeOwned>(&self, path: &str, query: &Q) -> Result<T, Error> {
+        let response = self.request(Method::GET, path).query(query).send().await?;
+        data(response).await
+    }
+
+    async fn post<B: Serialize + ?Sized, T: DeserializeOwned>(&self, path: &str, body: &B) -> Result<T, Error> {
+        let response = self.request(Method::POST, path).json(body).send().await?;
+        data(response).await
+    }
+
+    async fn post_empty<T: DeserializeOwned>(&self, path: &str) -> Result<T, Error> {
+        let response = self.request(Method::POST, path).send().await?;
+        data(response).await
+    }
+
+    async fn image<Q: Serialize + ?Sized>(&self, path: &str, query: &Q) -> Result<Vec<u8>, Error> {
+        let response = self.request(Method::GET, path).query(query).send().await?;
+        Ok(checked(response).await?.bytes().await?.to_vec())
+    }
+}
+
+/// Unwraps the `data` of a successful response.
+async fn data<T: DeserializeOwned>(response: Response) -> Result<T, Error> {
+    let Envelope { data } = checked(response).await?.json().await?;
+    Ok(data)
+}
+
+/// Turns error statuses into `Error::Api`, or `Error::Status` when the body
+/// isn't the server's error JSON.
+async fn checked(response: Response) -> Result<Response, Error> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let bytes = response.bytes().await?;
+    match serde_json::from_slice::<ErrorBody>(&bytes) {
+        Ok(body) => Err(Error::Api {
+            status: status.as_u16(),
+            code: body.code,
+            message: body.error,
+            field: body.field,
+            details: body.details,
+        }),
+        Err(_) => Err(Error::Status(status.as_u16())),
+    }
+}
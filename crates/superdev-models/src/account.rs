@@ -0,0 +1,48 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::types::{HashStr, PubkeyStr};
+
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct CleanupRequest {
+    /// Mints whose empty accounts stay open, e.g. ones about to be refilled.
+    #[serde(default)]
+    pub keep_mints: Vec<PubkeyStr>,
+    /// Receives the reclaimed rent; defaults to the owner.
+    #[serde(default)]
+    pub destination: Option<PubkeyStr>,
+    /// Defaults to the cluster's latest blockhash.
+    #[serde(default)]
+    pub recent_blockhash: Option<HashStr>,
+}
+
+/// Empty token accounts the owner can close, and unsigned transactions
+/// closing them. Accounts that are frozen, hold withheld fees or have
+/// another close authority are left out.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CleanupResponse {
+    pub owner: String,
+    pub closed: Vec<ClosedAccount>,
+    pub reclaimed_lamports: u64,
+    /// Absent when there is nothing to close.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recent_blockhash: Option<String>,
+    pub transactions: Vec<CleanupTransaction>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ClosedAccount {
+    pub address: String,
+    pub mint: String,
+    pub token_program: String,
+    pub lamports: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CleanupTransaction {
+    pub index: usize,
+    /// Base64 bincode of the unsigned transaction; only the owner must sign.
+    pub transaction: String,
+    /// Indices into `closed` covered by this transaction, `[start, end)`.
+    pub accounts: [usize; 2],
+    pub size: usize,
+}
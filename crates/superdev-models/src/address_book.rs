@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::PubkeyStr;
+
+/// Names `address` so send endpoints can take `label:<label>` in its place.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CreateAddressRequest {
+    pub label: String,
+    pub address: PubkeyStr,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UpdateAddressRequest {
+    pub address: PubkeyStr,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AddressBookEntry {
+    pub label: String,
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,112 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Production keeps secret keys out of the API: generated keypairs stay in
+/// the keystore and signing endpoints only take `key_id`s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    #[default]
+    Development,
+    Production,
+}
+
+/// Switches for the optional services, all on unless disabled here or
+/// through `/admin/features`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct FeaturesConfig {
+    /// Fee-payer signing and submission under `/relayer`.
+    pub relayer: bool,
+    /// Jito bundle submission.
+    pub bundles: bool,
+    /// Scheduling new jobs; jobs already queued still run.
+    pub jobs: bool,
+}
+
+impl Default for FeaturesConfig {
+    fn default() -> Self {
+        Self {
+            relayer: true,
+            bundles: true,
+            jobs: true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AdminStatus {
+    pub mode: Mode,
+    pub features: FeaturesConfig,
+    /// Version of the keystore master key, bumped by each rotation.
+    pub master_key_version: u32,
+    /// Identities whose API keys were revoked, sorted.
+    pub revoked: Vec<String>,
+}
+
+/// Liveness of the process, for load balancer and orchestrator probes.
+/// Answered without calling the RPC node.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Health {
+    pub mode: Mode,
+    /// Whether RPC-backed endpoints have a node (or the mock) to use.
+    pub rpc: bool,
+}
+
+/// Diagnostics from `/debug/runtime`.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RuntimeStatus {
+    pub runtime: RuntimeTasks,
+    pub jobs: JobQueueDepth,
+    /// Age of the cached blockhash; `None` until one is fetched or after the
+    /// cache is invalidated.
+    pub blockhash_age_ms: Option<u64>,
+    pub rpc: RpcHealth,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RuntimeTasks {
+    pub workers: usize,
+    /// Spawned tasks that haven't completed, jobs and background sends
+    /// included.
+    pub alive_tasks: usize,
+    /// Tasks waiting in the runtime's shared queue for a worker.
+    pub global_queue_depth: usize,
+}
+
+/// Jobs by state, across tenants. Finished jobs aren't counted.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct JobQueueDepth {
+    pub scheduled: usize,
+    pub running: usize,
+}
+
+/// Outcome of a `getSlot` probe of the RPC node.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RpcHealth {
+    pub configured: bool,
+    pub slot: Option<u64>,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RotateResponse {
+    pub master_key_version: u32,
+    /// Keystore entries re-encrypted under the new master key.
+    pub reencrypted: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RevokeRequest {
+    /// Identity whose API key stops authenticating.
+    pub identity: String,
+}
+
+/// Features to switch; omitted ones keep their current state.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FeaturesRequest {
+    pub relayer: Option<bool>,
+    pub bundles: Option<bool>,
+    pub jobs: Option<bool>,
+}
@@ -0,0 +1,66 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::types::{HashStr, PubkeyStr};
+use crate::ItemError;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BulkAirdropRequest {
+    /// Fee payer and source of the funds. Token airdrops send from its ATA.
+    pub payer: PubkeyStr,
+    /// Token to distribute; SOL when omitted, with amounts in lamports.
+    #[serde(default)]
+    pub mint: Option<PubkeyStr>,
+    pub recipients: Vec<AirdropRecipient>,
+    /// Defaults to the cluster's latest blockhash.
+    #[serde(default)]
+    pub recent_blockhash: Option<HashStr>,
+    /// Validate and report totals without building any transactions.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AirdropRecipient {
+    pub recipient: PubkeyStr,
+    /// Base units of the mint, or lamports for SOL.
+    pub amount: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BulkAirdropResponse {
+    /// `"SOL"` or the mint address.
+    pub asset: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_program: Option<String>,
+    pub recipient_count: usize,
+    pub total_amount: u64,
+    pub recent_blockhash: String,
+    pub transactions: Vec<AirdropTransaction>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AirdropTransaction {
+    pub index: usize,
+    /// Base64 bincode of the unsigned transaction; only `payer` must sign.
+    pub transaction: String,
+    /// Indices into `recipients` covered by this transaction, `[start, end)`.
+    pub recipients: [usize; 2],
+    pub size: usize,
+}
+
+/// One line of an airdrop streamed as NDJSON: a transaction, or the error
+/// that stopped the rest from being built.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum AirdropLine {
+    Transaction(AirdropTransaction),
+    Failed { error: ItemError },
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AirdropDryRun {
+    pub asset: String,
+    pub recipient_count: usize,
+    pub unique_recipients: usize,
+    pub total_amount: u64,
+}
@@ -0,0 +1,40 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::jobs::InstructionTemplate;
+use crate::types::PubkeyStr;
+use crate::InstructionResponse;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AltPlanRequest {
+    /// Pays for the table and, in the estimate, for the transaction.
+    pub payer: PubkeyStr,
+    /// May extend and close the table; defaults to `payer`.
+    #[serde(default)]
+    pub authority: Option<PubkeyStr>,
+    pub instructions: Vec<InstructionTemplate>,
+    /// Recent slot the table address derives from; the current slot when
+    /// omitted.
+    #[serde(default)]
+    pub recent_slot: Option<u64>,
+}
+
+/// A lookup table holding every account the instructions don't need
+/// static, and what moving them saves.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AltPlanResponse {
+    pub lookup_table: String,
+    pub authority: String,
+    pub recent_slot: u64,
+    /// Accounts to store in the table, in the order they're added.
+    pub addresses: Vec<String>,
+    /// Signers and invoked programs, which must stay in the message.
+    pub static_accounts: Vec<String>,
+    /// Create, then extend in batches; send each in order.
+    pub instructions: Vec<InstructionResponse>,
+    /// Serialized size with placeholder signatures, in bytes.
+    pub legacy_size: usize,
+    pub v0_size: usize,
+    /// Whether the v0 transaction fits in one packet.
+    pub fits: bool,
+}
@@ -0,0 +1,67 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use crate::types::PubkeyStr;
+use crate::OutputFormat;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RegisterIdlRequest {
+    /// Overrides the address recorded in the IDL, e.g. for a devnet deploy.
+    pub program_id: Option<PubkeyStr>,
+    pub idl: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RegisterIdlResponse {
+    pub program_id: String,
+    pub name: String,
+    pub instructions: Vec<String>,
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AnchorInstructionRequest {
+    pub program_id: PubkeyStr,
+    /// Instruction name as written in the IDL; snake_case and camelCase both match.
+    pub method: String,
+    #[serde(default)]
+    pub args: Map<String, Value>,
+    /// Account name to pubkey. Nested account groups are nested objects.
+    #[serde(default)]
+    pub accounts: Map<String, Value>,
+    #[serde(default)]
+    pub remaining_accounts: Vec<RemainingAccount>,
+    #[serde(default)]
+    pub output_format: OutputFormat,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RemainingAccount {
+    pub pubkey: PubkeyStr,
+    #[serde(default)]
+    pub is_signer: bool,
+    #[serde(default)]
+    pub is_writable: bool,
+}
+
+/// Exactly one of `logs` or `signature`.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ParseLogsRequest {
+    pub program_id: PubkeyStr,
+    pub logs: Option<Vec<String>>,
+    /// Base58 transaction signature whose logs are fetched over RPC.
+    pub signature: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ParseLogsResponse {
+    pub events: Vec<AnchorEvent>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AnchorEvent {
+    pub name: String,
+    /// Index of the `Program data:` line within the logs.
+    pub log_index: usize,
+    pub data: Value,
+}
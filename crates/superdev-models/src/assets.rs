@@ -0,0 +1,49 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::PubkeyStr;
+
+/// A digital asset as reported by the configured DAS provider, flattened
+/// to the fields regular and compressed NFTs have in common.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Asset {
+    pub id: String,
+    /// DAS interface, e.g. `V1_NFT`, `ProgrammableNFT` or `FungibleToken`.
+    pub interface: String,
+    /// Compressed assets have no mint or token account; they live as leaves
+    /// of `tree`.
+    pub compressed: bool,
+    pub owner: Option<String>,
+    pub delegate: Option<String>,
+    pub frozen: bool,
+    pub burnt: bool,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub image: Option<String>,
+    pub collection: Option<String>,
+    pub royalty_basis_points: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tree: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leaf_id: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AssetsByOwnerQuery {
+    pub owner: PubkeyStr,
+    /// 1-based page number; defaults to 1.
+    pub page: Option<u32>,
+    /// Page size; defaults to 100.
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AssetsByOwnerResponse {
+    pub owner: String,
+    pub page: u32,
+    pub limit: u32,
+    /// Assets on this page, as counted by the provider.
+    pub total: u32,
+    pub items: Vec<Asset>,
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One decision about an operation that signs or sends funds.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AuditEntry {
+    pub time: DateTime<Utc>,
+    /// What was attempted, e.g. `relayer.sign` or `jobs.run`.
+    pub action: String,
+    /// Required signers of the transaction in question, fee payer first.
+    pub signers: Vec<String>,
+    /// Caller, by client certificate or API key, when it authenticated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity: Option<String>,
+    pub allowed: bool,
+    /// Error code when the operation was refused.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// JSON body of the HTTP request behind the operation, secrets replaced
+    /// by `[REDACTED]`. Absent for background work such as scheduled jobs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request: Option<Value>,
+}
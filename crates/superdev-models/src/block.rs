@@ -0,0 +1,31 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::transaction::TransactionSummary;
+use crate::types::PubkeyStr;
+
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct BlockQuery {
+    /// Only transactions that reference this account, directly or through
+    /// a lookup table.
+    pub address: Option<PubkeyStr>,
+    /// Page size; defaults to 100.
+    pub limit: Option<usize>,
+    /// Transactions to skip, e.g. the previous response's `next_offset`.
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BlockResponse {
+    pub slot: u64,
+    pub blockhash: String,
+    pub parent_slot: u64,
+    pub block_time: Option<i64>,
+    pub block_height: Option<u64>,
+    /// Transactions matching `address`, across all pages, in block order.
+    pub transaction_count: usize,
+    pub transactions: Vec<TransactionSummary>,
+    /// Offset of the next page; absent on the last one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<usize>,
+}
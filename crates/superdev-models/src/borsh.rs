@@ -0,0 +1,39 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::idl::{Type, TypeDecl};
+
+/// Root type plus any named types it refers to, in Anchor IDL syntax.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SchemaDescription {
+    #[serde(rename = "type")]
+    pub ty: Type,
+    #[serde(default)]
+    pub types: Vec<TypeDecl>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BorshEncodeRequest {
+    pub schema: SchemaDescription,
+    pub value: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BorshEncodeResponse {
+    /// Base64 of the encoded bytes.
+    pub data: String,
+    pub hex: String,
+    pub size: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BorshDecodeRequest {
+    pub schema: SchemaDescription,
+    /// Base64 of the bytes to decode.
+    pub data: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BorshDecodeResponse {
+    pub value: Value,
+}
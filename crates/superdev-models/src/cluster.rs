@@ -0,0 +1,75 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct EpochInfoResponse {
+    pub epoch: u64,
+    /// Slot within the epoch, from 0.
+    pub slot_index: u64,
+    pub slots_in_epoch: u64,
+    pub slots_remaining: u64,
+    pub absolute_slot: u64,
+    pub block_height: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_count: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SlotResponse {
+    pub slot: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VersionResponse {
+    pub solana_core: String,
+    /// Identifier of the node's enabled feature set.
+    pub feature_set: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorSort {
+    /// Largest activated stake first.
+    #[default]
+    Stake,
+    /// Lowest commission first, then largest stake.
+    Commission,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ValidatorsQuery {
+    /// Only delinquent (`true`) or only voting (`false`) validators.
+    pub delinquent: Option<bool>,
+    /// Commission percentage bound, inclusive.
+    pub max_commission: Option<u8>,
+    /// Activated stake bound in lamports, inclusive.
+    pub min_stake: Option<u64>,
+    #[serde(default)]
+    pub sort: ValidatorSort,
+    /// Page size; all matching validators when unset.
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ValidatorsResponse {
+    /// Activated stake of every vote account, filters aside.
+    pub total_stake: u64,
+    pub delinquent_stake: u64,
+    /// Validators matching the filters, across all pages.
+    pub validator_count: usize,
+    pub validators: Vec<Validator>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Validator {
+    pub vote_pubkey: String,
+    /// Identity of the node voting with this account.
+    pub node_pubkey: String,
+    pub activated_stake: u64,
+    pub activated_stake_sol: String,
+    pub commission: u8,
+    pub last_vote: u64,
+    pub root_slot: u64,
+    pub delinquent: bool,
+}
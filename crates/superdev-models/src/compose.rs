@@ -0,0 +1,50 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{AddressRef, HashStr, PubkeyStr};
+use crate::{SendSolRequest, SendTokenRequest};
+
+/// Builds each operation the way its own endpoint would and compiles them,
+/// in order, into one unsigned transaction paid by `payer`. The response is
+/// that of `/transaction/build`.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ComposeRequest {
+    pub payer: PubkeyStr,
+    pub operations: Vec<ComposeOperation>,
+    /// Defaults to the cluster's latest blockhash.
+    #[serde(default)]
+    pub recent_blockhash: Option<HashStr>,
+}
+
+/// One operation, tagged by `type`; the other fields are its endpoint's
+/// request body. `output_format` is ignored.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ComposeOperation {
+    /// As `/send/sol`.
+    SendSol(SendSolRequest),
+    /// As `/send/token`.
+    SendToken(SendTokenRequest),
+    Memo(MemoOperation),
+    CreateAta(CreateAtaOperation),
+}
+
+/// An SPL memo, signed by no one.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MemoOperation {
+    pub memo: String,
+}
+
+/// Creates `owner`'s associated token account for `mint` unless it already
+/// exists, so a transfer to it later in the transaction can't fail.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CreateAtaOperation {
+    pub owner: AddressRef,
+    pub mint: PubkeyStr,
+    /// Defaults to the classic spl-token program.
+    #[serde(default)]
+    pub token_program: Option<PubkeyStr>,
+    /// Pays the account's rent; defaults to the transaction's payer.
+    #[serde(default)]
+    pub payer: Option<PubkeyStr>,
+}
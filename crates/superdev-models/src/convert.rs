@@ -0,0 +1,37 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::types::PubkeyStr;
+
+/// Exactly one of `lamports` or `sol`.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SolQuery {
+    pub lamports: Option<u64>,
+    pub sol: Option<String>,
+}
+
+/// Raw amounts are strings: lamport and base-unit counts routinely exceed
+/// the 2^53 integers JavaScript numbers hold exactly.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SolConversion {
+    pub lamports: String,
+    pub sol: String,
+}
+
+/// Exactly one of `amount` (raw) or `ui_amount`. `decimals` is read from
+/// `mint` when not given.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TokenQuery {
+    pub mint: Option<PubkeyStr>,
+    pub decimals: Option<u8>,
+    pub amount: Option<u64>,
+    pub ui_amount: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TokenConversion {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mint: Option<String>,
+    pub decimals: u8,
+    pub amount: String,
+    pub ui_amount: String,
+}
@@ -0,0 +1,96 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::types::PubkeyStr;
+
+/// Exactly one of `data` (base64 account data) or `pubkey` (fetched over RPC).
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AccountSource {
+    pub data: Option<String>,
+    pub pubkey: Option<PubkeyStr>,
+}
+
+/// `address` and `program_id` are only known when the account was fetched.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MintLayout {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program_id: Option<String>,
+    pub mint_authority: Option<String>,
+    pub supply: String,
+    pub decimals: u8,
+    pub is_initialized: bool,
+    pub freeze_authority: Option<String>,
+    pub extensions: Vec<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TokenAccountLayout {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program_id: Option<String>,
+    pub mint: String,
+    pub owner: String,
+    pub amount: String,
+    pub delegate: Option<String>,
+    pub delegated_amount: String,
+    pub state: String,
+    pub is_native: bool,
+    /// Lamports held back for rent on wrapped-SOL accounts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rent_exempt_reserve: Option<String>,
+    pub close_authority: Option<String>,
+    pub extensions: Vec<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NonceLayout {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// `legacy` accounts predate durable nonce domain separation.
+    pub version: String,
+    pub state: String,
+    pub authority: Option<String>,
+    /// The durable nonce, i.e. the blockhash to sign nonce transactions with.
+    pub nonce: Option<String>,
+    pub lamports_per_signature: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StakeLayout {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    pub state: String,
+    pub meta: Option<StakeMeta>,
+    pub delegation: Option<StakeDelegation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StakeMeta {
+    pub rent_exempt_reserve: String,
+    pub staker: String,
+    pub withdrawer: String,
+    pub lockup: StakeLockup,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StakeLockup {
+    pub unix_timestamp: i64,
+    pub epoch: u64,
+    pub custodian: String,
+    /// Lockups with every field zeroed impose no restriction.
+    pub in_force: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StakeDelegation {
+    pub voter: String,
+    pub stake: String,
+    /// `None` for bootstrap stakes, active since genesis.
+    pub activation_epoch: Option<u64>,
+    /// `None` while the stake is not deactivating.
+    pub deactivation_epoch: Option<u64>,
+    pub credits_observed: u64,
+}
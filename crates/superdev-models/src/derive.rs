@@ -0,0 +1,32 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::types::{PubkeyStr, SeedList};
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AtaQuery {
+    pub owner: PubkeyStr,
+    pub mint: PubkeyStr,
+    /// Defaults to the classic spl-token program.
+    pub token_program: Option<PubkeyStr>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AtaResponse {
+    pub address: String,
+    pub owner: String,
+    pub mint: String,
+    pub token_program: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PdaQuery {
+    pub program_id: PubkeyStr,
+    #[serde(default)]
+    pub seeds: SeedList,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PdaResponse {
+    pub address: String,
+    pub bump: u8,
+}
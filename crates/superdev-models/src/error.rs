@@ -0,0 +1,211 @@
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+/// Validation failure for a single request field. These are raised both by
+/// handlers and from inside `Deserialize` impls, where serde can only carry
+/// them as text, so the Display strings double as the key `parse` matches on.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FieldError {
+    #[error("Invalid public key")]
+    InvalidPubkey,
+    #[error("Invalid base58 secret key")]
+    SecretInvalidBase58,
+    #[error("Secret key must be 64 bytes, got {0}")]
+    SecretWrongLength(usize),
+    #[error("Invalid secret key format")]
+    SecretInvalid,
+    #[error("Invalid base64 signature")]
+    SignatureInvalidBase64,
+    #[error("Invalid signature format")]
+    SignatureInvalid,
+    #[error("Amount must be greater than 0")]
+    AmountZero,
+    #[error("Message must be at most {0} bytes")]
+    MessageTooLong(usize),
+    #[error("Decimals must be at most {0}")]
+    DecimalsOutOfRange(u8),
+    #[error("Source and destination must differ")]
+    SelfTransfer,
+    #[error("Source and destination token accounts are the same")]
+    SameTokenAccount,
+    #[error("Amount is below the dust threshold of {0} lamports")]
+    BelowDustThreshold(u64),
+    #[error("Delegate must differ from the owner")]
+    DelegateIsOwner,
+    #[error("Signers must be distinct and must not include the authority they sign for")]
+    DuplicateSigner,
+    #[error("At most {0} multisig signers")]
+    TooManySigners(usize),
+    #[error("Members must be distinct")]
+    DuplicateMember,
+    #[error("At least one member needs the {0} permission")]
+    MissingPermission(&'static str),
+    #[error("Threshold must be between 1 and {0}, the number of voting members")]
+    ThresholdRange(usize),
+    #[error("Invalid seeds: expected comma-separated utf8:, hex:, base58: or base64: values of at most 32 bytes each")]
+    InvalidSeeds,
+    #[error("Token program must be spl-token or token-2022")]
+    UnsupportedTokenProgram,
+    #[error("Batch must contain between 1 and {0} items")]
+    BatchSize(usize),
+    #[error("Invalid base58 blockhash")]
+    InvalidBlockhash,
+    #[error("Total amount overflows u64")]
+    AmountOverflow,
+    #[error("Amount must be a whole number of base units")]
+    InvalidAmount,
+    #[error("Expected true or false")]
+    InvalidBool,
+    #[error("Invalid Solana Pay URL: {0}")]
+    InvalidPaymentUrl(String),
+    #[error("Amount must be a non-negative decimal number")]
+    DecimalAmount,
+    #[error("Amount has more than {0} decimal places")]
+    AmountPrecision(u8),
+    #[error("Link must be an absolute https URL")]
+    InvalidLink,
+    #[error("Provide either a recipient (transfer request) or a link (transaction request)")]
+    RecipientOrLink,
+    #[error("Only allowed in transfer requests")]
+    TransferOnly,
+    #[error("Size must be between {0} and {1} pixels")]
+    QrSize(u32, u32),
+    #[error("Data is too long for a QR code at this error-correction level")]
+    QrDataTooLong,
+    #[error("Amount exceeds the u64 range of base units")]
+    AmountTooLarge,
+    #[error("Provide exactly one of {0}")]
+    ExactlyOne(&'static str),
+    #[error("Percentage must be between 1 and 100")]
+    PercentOutOfRange,
+    #[error("Must not be empty")]
+    Empty,
+    #[error("Raw secret keys are disabled in production mode; use a key_id")]
+    RawSecretDisabled,
+    #[error("Unknown key_id")]
+    UnknownKeyId,
+    #[error("Key expired at {0}")]
+    KeyExpired(DateTime<Utc>),
+    #[error("At most {0} tags")]
+    TooManyTags(usize),
+    #[error("Refusing to sign bytes that parse as a transaction message")]
+    TransactionMessage,
+    #[error("Passphrase must be at least {0} characters")]
+    PassphraseTooShort(usize),
+    #[error("Wrong passphrase or corrupted backup")]
+    DecryptionFailed,
+    #[error("Invalid cron expression: {0}")]
+    InvalidCron(String),
+    #[error("Max attempts must be between 1 and {0}")]
+    AttemptsOutOfRange(u32),
+    #[error("Epochs must be completed, in order, and span at most {0} epochs")]
+    EpochRange(u64),
+    #[error("More than {0} accounts match; narrow the filters")]
+    TooManyAccounts(usize),
+    #[error("Initial price must be greater than 0 and at most max_micro_lamports")]
+    PriorityFeeRange,
+    #[error("Must be greater than 0")]
+    NotPositive,
+    #[error("At most {0} rebuilds")]
+    TooManyRebuilds(u32),
+    #[error("Not allowed together with {0}")]
+    NotAllowedWith(&'static str),
+    #[error("Required with {0}")]
+    RequiredWith(&'static str),
+    #[error("Label must be 1 to {0} letters, digits, '-', '_' or '.'")]
+    InvalidLabel(usize),
+    #[error("No address-book entry has this label")]
+    UnknownLabel,
+    #[error("Unsupported max_depth and max_buffer_size pair")]
+    UnsupportedTreeSize,
+    #[error("Canopy depth must be at most max_depth and at most {0}")]
+    CanopyDepth(u32),
+    #[error("Must be at most {0} bytes")]
+    TooLong(usize),
+}
+
+impl FieldError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            FieldError::InvalidPubkey => "INVALID_PUBKEY",
+            FieldError::SecretInvalidBase58 => "SECRET_INVALID_BASE58",
+            FieldError::SecretWrongLength(_) => "SECRET_WRONG_LENGTH",
+            FieldError::SecretInvalid => "SECRET_INVALID",
+            FieldError::SignatureInvalidBase64 => "SIGNATURE_INVALID_BASE64",
+            FieldError::SignatureInvalid => "SIGNATURE_INVALID",
+            FieldError::AmountZero => "AMOUNT_ZERO",
+            FieldError::MessageTooLong(_) => "MESSAGE_TOO_LONG",
+            FieldError::DecimalsOutOfRange(_) => "DECIMALS_OUT_OF_RANGE",
+            FieldError::SelfTransfer => "SELF_TRANSFER",
+            FieldError::SameTokenAccount => "SAME_TOKEN_ACCOUNT",
+            FieldError::BelowDustThreshold(_) => "BELOW_DUST_THRESHOLD",
+            FieldError::DelegateIsOwner => "DELEGATE_IS_OWNER",
+            FieldError::DuplicateSigner => "DUPLICATE_SIGNER",
+            FieldError::TooManySigners(_) => "TOO_MANY_SIGNERS",
+            FieldError::DuplicateMember => "DUPLICATE_MEMBER",
+            FieldError::MissingPermission(_) => "MISSING_PERMISSION",
+            FieldError::ThresholdRange(_) => "THRESHOLD_RANGE",
+            FieldError::InvalidSeeds => "INVALID_SEEDS",
+            FieldError::UnsupportedTokenProgram => "UNSUPPORTED_TOKEN_PROGRAM",
+            FieldError::BatchSize(_) => "BATCH_SIZE",
+            FieldError::InvalidBlockhash => "INVALID_BLOCKHASH",
+            FieldError::AmountOverflow => "AMOUNT_OVERFLOW",
+            FieldError::InvalidAmount => "INVALID_AMOUNT",
+            FieldError::InvalidBool => "INVALID_BOOL",
+            FieldError::InvalidPaymentUrl(_) => "INVALID_PAYMENT_URL",
+            FieldError::DecimalAmount => "INVALID_DECIMAL_AMOUNT",
+            FieldError::AmountPrecision(_) => "AMOUNT_PRECISION",
+            FieldError::InvalidLink => "INVALID_LINK",
+            FieldError::RecipientOrLink => "RECIPIENT_OR_LINK",
+            FieldError::TransferOnly => "TRANSFER_ONLY",
+            FieldError::QrSize(..) => "QR_SIZE",
+            FieldError::QrDataTooLong => "QR_DATA_TOO_LONG",
+            FieldError::AmountTooLarge => "AMOUNT_TOO_LARGE",
+            FieldError::ExactlyOne(_) => "EXACTLY_ONE",
+            FieldError::PercentOutOfRange => "PERCENT_OUT_OF_RANGE",
+            FieldError::Empty => "EMPTY",
+            FieldError::RawSecretDisabled => "RAW_SECRET_DISABLED",
+            FieldError::UnknownKeyId => "UNKNOWN_KEY_ID",
+            FieldError::KeyExpired(_) => "KEY_EXPIRED",
+            FieldError::TooManyTags(_) => "TOO_MANY_TAGS",
+            FieldError::TransactionMessage => "TRANSACTION_MESSAGE",
+            FieldError::PassphraseTooShort(_) => "PASSPHRASE_TOO_SHORT",
+            FieldError::DecryptionFailed => "DECRYPTION_FAILED",
+            FieldError::InvalidCron(_) => "INVALID_CRON",
+            FieldError::AttemptsOutOfRange(_) => "ATTEMPTS_OUT_OF_RANGE",
+            FieldError::EpochRange(_) => "EPOCH_RANGE",
+            FieldError::TooManyAccounts(_) => "TOO_MANY_ACCOUNTS",
+            FieldError::PriorityFeeRange => "PRIORITY_FEE_RANGE",
+            FieldError::NotPositive => "NOT_POSITIVE",
+            FieldError::TooManyRebuilds(_) => "TOO_MANY_REBUILDS",
+            FieldError::NotAllowedWith(_) => "NOT_ALLOWED_WITH",
+            FieldError::RequiredWith(_) => "REQUIRED_WITH",
+            FieldError::InvalidLabel(_) => "INVALID_LABEL",
+            FieldError::UnknownLabel => "UNKNOWN_LABEL",
+            FieldError::UnsupportedTreeSize => "UNSUPPORTED_TREE_SIZE",
+            FieldError::CanopyDepth(_) => "CANOPY_DEPTH",
+            FieldError::TooLong(_) => "TOO_LONG",
+        }
+    }
+
+    /// The error whose Display text is `message`, for errors serde only
+    /// passed along as text.
+    pub fn parse(message: &str) -> Option<Self> {
+        if let Some(len) = message.strip_prefix("Secret key must be 64 bytes, got ") {
+            return len.parse().ok().map(FieldError::SecretWrongLength);
+        }
+
+        [
+            FieldError::InvalidPubkey,
+            FieldError::SecretInvalidBase58,
+            FieldError::SecretInvalid,
+            FieldError::SignatureInvalidBase64,
+            FieldError::SignatureInvalid,
+            FieldError::AmountZero,
+            FieldError::InvalidSeeds,
+            FieldError::InvalidBlockhash,
+        ]
+        .into_iter()
+        .find(|error| error.to_string() == message)
+    }
+}
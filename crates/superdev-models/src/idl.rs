@@ -0,0 +1,301 @@
+//! Borsh type descriptions in Anchor IDL syntax.
+
+use std::borrow::Cow;
+
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{json, Value};
+
+/// A Borsh field type, written the way Anchor IDLs spell them: `"u64"`,
+/// `{"vec": "u8"}`, `{"option": "pubkey"}`, `{"array": ["u8", 32]}`,
+/// `{"defined": "Name"}` or `{"defined": {"name": "Name"}}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    U128,
+    I128,
+    F32,
+    F64,
+    String,
+    Bytes,
+    Pubkey,
+    Vec(Box<Type>),
+    Option(Box<Type>),
+    /// `COption`: a 4-byte tag instead of Borsh's 1-byte one.
+    COption(Box<Type>),
+    Array(Box<Type>, usize),
+    Defined(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fields {
+    Named(Vec<(String, Type)>),
+    Tuple(Vec<Type>),
+    Unit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeDef {
+    Struct(Fields),
+    Enum(Vec<(String, Fields)>),
+    Alias(Type),
+}
+
+/// A named field, `{"name": "amount", "type": "u64"}`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct Field {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: Type,
+}
+
+/// A named type definition in IDL syntax, e.g.
+/// `{"name": "Point", "type": {"kind": "struct", "fields": [...]}}`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct TypeDecl {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub def: TypeDef,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub generics: Vec<Value>,
+}
+
+impl Type {
+    pub fn from_json(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::String(name) => Self::primitive(name).ok_or_else(|| format!("unknown type `{name}`")),
+            Value::Object(map) if map.len() == 1 => {
+                let (kind, inner) = map.iter().next().unwrap();
+                match kind.as_str() {
+                    "vec" => Ok(Type::Vec(Box::new(Self::from_json(inner)?))),
+                    "option" => Ok(Type::Option(Box::new(Self::from_json(inner)?))),
+                    "coption" => Ok(Type::COption(Box::new(Self::from_json(inner)?))),
+                    "array" => match inner.as_array().map(Vec::as_slice) {
+                        Some([element, Value::Number(len)]) => {
+                            let len = len.as_u64().ok_or("array length must be a non-negative integer")?;
+                            Ok(Type::Array(Box::new(Self::from_json(element)?), len as usize))
+                        }
+                        _ => Err("array must be [type, length]".to_string()),
+                    },
+                    "defined" => match inner {
+                        Value::String(name) => Ok(Type::Defined(name.clone())),
+                        Value::Object(defined) => match defined.get("name") {
+                            Some(Value::String(name)) if !has_generics(defined) => Ok(Type::Defined(name.clone())),
+                            Some(Value::String(name)) => Err(format!("generic type `{name}` is not supported")),
+                            _ => Err("defined type needs a name".to_string()),
+                        },
+                        _ => Err("defined type needs a name".to_string()),
+                    },
+                    other => Err(format!("unknown type `{other}`")),
+                }
+            }
+            _ => Err(format!("invalid type {value}")),
+        }
+    }
+
+    /// The IDL spelling `from_json` reads back.
+    pub fn to_json(&self) -> Value {
+        let nested = |kind: &str, inner: Value| json!({ kind: inner });
+        match self {
+            Type::Vec(inner) => nested("vec", inner.to_json()),
+            Type::Option(inner) => nested("option", inner.to_json()),
+            Type::COption(inner) => nested("coption", inner.to_json()),
+            Type::Array(inner, len) => nested("array", json!([inner.to_json(), len])),
+            Type::Defined(name) => nested("defined", Value::from(name.as_str())),
+            primitive => Value::from(primitive.primitive_name()),
+        }
+    }
+
+    fn primitive_name(&self) -> &'static str {
+        match self {
+            Type::Bool => "bool",
+            Type::U8 => "u8",
+            Type::I8 => "i8",
+            Type::U16 => "u16",
+            Type::I16 => "i16",
+            Type::U32 => "u32",
+            Type::I32 => "i32",
+            Type::U64 => "u64",
+            Type::I64 => "i64",
+            Type::U128 => "u128",
+            Type::I128 => "i128",
+            Type::F32 => "f32",
+            Type::F64 => "f64",
+            Type::String => "string",
+            Type::Bytes => "bytes",
+            Type::Pubkey => "pubkey",
+            Type::Vec(_) | Type::Option(_) | Type::COption(_) | Type::Array(..) | Type::Defined(_) => {
+                unreachable!("not a primitive")
+            }
+        }
+    }
+
+    fn primitive(name: &str) -> Option<Self> {
+        Some(match name {
+            "bool" => Type::Bool,
+            "u8" => Type::U8,
+            "i8" => Type::I8,
+            "u16" => Type::U16,
+            "i16" => Type::I16,
+            "u32" => Type::U32,
+            "i32" => Type::I32,
+            "u64" => Type::U64,
+            "i64" => Type::I64,
+            "u128" => Type::U128,
+            "i128" => Type::I128,
+            "f32" => Type::F32,
+            "f64" => Type::F64,
+            "string" => Type::String,
+            "bytes" => Type::Bytes,
+            "pubkey" | "publicKey" => Type::Pubkey,
+            _ => return None,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Type {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        Type::from_json(&value).map_err(de::Error::custom)
+    }
+}
+
+impl JsonSchema for Type {
+    fn schema_name() -> Cow<'static, str> {
+        "Type".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        let inner = generator.subschema_for::<Type>().to_value();
+        let nested = |kind: &str, schema: Value| {
+            json!({ "type": "object", "properties": { kind: schema }, "required": [kind], "additionalProperties": false })
+        };
+        let primitives = [
+            "bool", "u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64", "u128", "i128", "f32", "f64", "string",
+            "bytes", "pubkey", "publicKey",
+        ];
+        let name = json!({ "type": "string" });
+        json_schema!({
+            "description": "Borsh field type in Anchor IDL syntax",
+            "oneOf": [
+                { "type": "string", "enum": primitives },
+                nested("vec", inner.clone()),
+                nested("option", inner.clone()),
+                nested("coption", inner.clone()),
+                nested("array", json!({ "type": "array", "prefixItems": [inner, { "type": "integer", "minimum": 0 }] })),
+                nested("defined", json!({ "oneOf": [name, nested("name", name.clone())] })),
+            ]
+        })
+    }
+}
+
+impl Serialize for Type {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json().serialize(serializer)
+    }
+}
+
+fn has_generics(defined: &serde_json::Map<String, Value>) -> bool {
+    defined.get("generics").and_then(Value::as_array).is_some_and(|generics| !generics.is_empty())
+}
+
+#[derive(Deserialize, Serialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum RawTypeBody {
+    Struct {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        fields: Option<RawFields>,
+    },
+    Enum {
+        variants: Vec<RawVariant>,
+    },
+    Type {
+        alias: Type,
+    },
+}
+
+#[derive(Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+#[schemars(rename = "Fields")]
+enum RawFields {
+    Named(Vec<Field>),
+    Tuple(Vec<Type>),
+}
+
+#[derive(Deserialize, Serialize, JsonSchema)]
+#[schemars(rename = "Variant")]
+struct RawVariant {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fields: Option<RawFields>,
+}
+
+impl From<Option<RawFields>> for Fields {
+    fn from(fields: Option<RawFields>) -> Self {
+        match fields {
+            None => Fields::Unit,
+            // Legacy IDLs spell unit variants with an empty field list.
+            Some(RawFields::Named(named)) if named.is_empty() => Fields::Unit,
+            Some(RawFields::Named(named)) => Fields::Named(named.into_iter().map(|field| (field.name, field.ty)).collect()),
+            Some(RawFields::Tuple(types)) => Fields::Tuple(types),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TypeDef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match RawTypeBody::deserialize(deserializer)? {
+            RawTypeBody::Struct { fields } => TypeDef::Struct(fields.into()),
+            RawTypeBody::Enum { variants } => {
+                TypeDef::Enum(variants.into_iter().map(|variant| (variant.name, variant.fields.into())).collect())
+            }
+            RawTypeBody::Type { alias } => TypeDef::Alias(alias),
+        })
+    }
+}
+
+impl From<&Fields> for Option<RawFields> {
+    fn from(fields: &Fields) -> Self {
+        match fields {
+            Fields::Unit => None,
+            Fields::Named(named) => Some(RawFields::Named(
+                named.iter().map(|(name, ty)| Field { name: name.clone(), ty: ty.clone() }).collect(),
+            )),
+            Fields::Tuple(types) => Some(RawFields::Tuple(types.clone())),
+        }
+    }
+}
+
+impl JsonSchema for TypeDef {
+    fn schema_name() -> Cow<'static, str> {
+        "TypeDef".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        RawTypeBody::json_schema(generator)
+    }
+}
+
+impl Serialize for TypeDef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let body = match self {
+            TypeDef::Struct(fields) => RawTypeBody::Struct { fields: fields.into() },
+            TypeDef::Enum(variants) => RawTypeBody::Enum {
+                variants: variants
+                    .iter()
+                    .map(|(name, fields)| RawVariant { name: name.clone(), fields: fields.into() })
+                    .collect(),
+            },
+            TypeDef::Alias(alias) => RawTypeBody::Type { alias: alias.clone() },
+        };
+        body.serialize(serializer)
+    }
+}
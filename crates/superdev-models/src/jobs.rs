@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{PubkeyStr, SignerRef};
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ScheduleRequest {
+    /// Every signer the instructions need, fee payer first. Raw secrets are
+    /// held in the keystore until the job finishes or is cancelled.
+    pub signers: Vec<SignerRef>,
+    pub instructions: Vec<InstructionTemplate>,
+    /// One-off run at this RFC 3339 time; past times run immediately.
+    #[serde(default)]
+    pub execute_at: Option<DateTime<Utc>>,
+    /// Recurring run on a five-field UTC cron expression.
+    #[serde(default)]
+    pub cron: Option<String>,
+    #[serde(default)]
+    pub retry: RetryRequest,
+    /// Resubmit at a rising compute-unit price until the transaction
+    /// confirms. Without it, a run ends as soon as the node accepts a send.
+    #[serde(default)]
+    pub priority_fee: Option<PriorityFeeRequest>,
+}
+
+/// Instruction replayed on every run; only the blockhash changes.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct InstructionTemplate {
+    pub program_id: PubkeyStr,
+    pub accounts: Vec<AccountMetaTemplate>,
+    /// Base64 instruction data. Also read from `instruction_data`, so
+    /// instructions this service built can be passed back as they are.
+    #[serde(default, alias = "instruction_data")]
+    pub data: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AccountMetaTemplate {
+    pub pubkey: PubkeyStr,
+    #[serde(default)]
+    pub is_signer: bool,
+    #[serde(default)]
+    pub is_writable: bool,
+}
+
+/// Overrides for the configured retry policy.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct RetryRequest {
+    /// Sends per run, first included.
+    pub max_attempts: Option<u32>,
+    /// Wait before the first retry; doubles after each further failure.
+    pub backoff_ms: Option<u64>,
+}
+
+/// Escalating compute-unit price for sends dropped during congestion. Each
+/// bump is a new transaction with a fresh blockhash, signed only once the
+/// previous send's blockhash has expired, so one run never lands twice.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PriorityFeeRequest {
+    /// Compute-unit price of the first send, in micro-lamports.
+    pub initial_micro_lamports: u64,
+    /// Highest price to send at. A send at this price that doesn't confirm
+    /// fails the run.
+    pub max_micro_lamports: u64,
+    /// Percent added to the price after each send that expires unconfirmed.
+    #[serde(default)]
+    pub bump_percent: Option<u8>,
+    /// How often a pending send is checked for confirmation.
+    #[serde(default)]
+    pub interval_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Scheduled,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn is_finished(self) -> bool {
+        matches!(self, JobStatus::Succeeded | JobStatus::Failed | JobStatus::Cancelled)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Job {
+    pub id: String,
+    pub status: JobStatus,
+    pub fee_payer: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cron: Option<String>,
+    /// `None` once the job has finished.
+    pub next_run: Option<DateTime<Utc>>,
+    /// Completed runs, successful or not.
+    pub runs: u32,
+    /// Sends made by the latest run.
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    /// Signatures of every transaction sent, oldest first. Jobs with a
+    /// `priority_fee` list only the send of each run that confirmed.
+    pub signatures: Vec<String>,
+    /// Compute-unit price of the latest run's confirmed send, or of its last
+    /// send while none has confirmed, in micro-lamports.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_fee: Option<u64>,
+    /// Total fee of the latest run's confirmed transaction, in lamports.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_paid: Option<u64>,
+}
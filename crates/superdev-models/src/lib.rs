@@ -0,0 +1,411 @@
+//! Request and response bodies of the Superdev API. The server
+//! deserializes requests and serializes responses with these types, and
+//! `superdev-client` does the reverse, so the two can't drift apart.
+
+pub mod account;
+pub mod admin;
+pub mod address_book;
+pub mod airdrop;
+pub mod alt;
+pub mod anchor;
+pub mod assets;
+pub mod audit;
+pub mod block;
+pub mod borsh;
+pub mod cluster;
+pub mod compose;
+pub mod convert;
+pub mod decode;
+pub mod derive;
+mod error;
+pub mod idl;
+pub mod jobs;
+pub mod keys;
+pub mod merkle_tree;
+pub mod nft;
+pub mod nonce_pool;
+pub mod parse;
+pub mod program;
+pub mod qr;
+pub mod schema;
+pub mod relayer;
+pub mod reporting;
+pub mod solana_pay;
+pub mod squads;
+pub mod stake;
+pub mod templates;
+pub mod token;
+pub mod transaction;
+pub mod transfers;
+pub mod types;
+pub mod vote;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::types::{AddressRef, HashStr, PubkeyStr, Redacted, SecretKeyStr, SignatureEncoding, SignatureStr};
+
+pub use error::FieldError;
+
+/// Query of `POST /keypair`.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct KeypairQuery {
+    /// Generate this many keypairs and answer with an array, or stream them
+    /// as NDJSON when `Accept: application/x-ndjson` is sent. Bounded by
+    /// `keypair.max_count`.
+    pub count: Option<usize>,
+}
+
+/// In production mode the secret stays in the keystore and only its
+/// `key_id` is returned.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct KeypairResponse {
+    pub pubkey: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<Redacted<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+}
+
+/// One line of a keypair batch streamed as NDJSON: a keypair, or the error
+/// that stopped a chunk of them from being generated.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum KeypairLine {
+    Keypair(KeypairResponse),
+    Failed { error: ItemError },
+}
+
+/// Only served by builds with the `dev-tools` feature. The same seed and
+/// index always give the same keypair, so anyone who knows them has the key.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DeterministicKeypairRequest {
+    pub seed: String,
+    #[serde(default)]
+    pub index: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DeterministicKeypairResponse {
+    pub pubkey: String,
+    pub secret: Redacted<String>,
+    pub seed: String,
+    pub index: u32,
+    /// Always true: a reminder that the key is derivable from public inputs.
+    pub insecure: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CreateTokenRequest {
+    #[serde(rename = "mintAuthority")]
+    pub mint_authority: PubkeyStr,
+    pub mint: PubkeyStr,
+    pub decimals: u8,
+    #[serde(default)]
+    pub output_format: OutputFormat,
+}
+
+/// Shape of the instruction a builder endpoint returns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// The endpoint's own response body.
+    #[default]
+    Superdev,
+    /// [`Web3Instruction`].
+    Web3js,
+}
+
+/// `TransactionInstruction` JSON as web3.js takes it, so a response can go
+/// straight into `new Transaction().add()` once `programId` and the key
+/// pubkeys are wrapped in `PublicKey` and `data` in a `Buffer`.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Web3Instruction {
+    pub program_id: String,
+    pub keys: Vec<Web3AccountMeta>,
+    /// Base64.
+    pub data: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Web3AccountMeta {
+    pub pubkey: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl From<InstructionResponse> for Web3Instruction {
+    fn from(response: InstructionResponse) -> Self {
+        Web3Instruction {
+            program_id: response.program_id,
+            keys: response
+                .accounts
+                .into_iter()
+                .map(|account| Web3AccountMeta {
+                    pubkey: account.pubkey,
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: response.instruction_data,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct InstructionResponse {
+    pub program_id: String,
+    pub accounts: Vec<AccountMeta>,
+    pub instruction_data: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AccountMeta {
+    pub pubkey: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MintTokenRequest {
+    pub mint: PubkeyStr,
+    pub destination: PubkeyStr,
+    pub authority: PubkeyStr,
+    /// Raw base units; exactly one of `amount` and `ui_amount`.
+    #[serde(default)]
+    pub amount: Option<u64>,
+    /// Decimal amount, e.g. `"1.5"`; finer than a base unit is rejected.
+    #[serde(default)]
+    pub ui_amount: Option<String>,
+    /// The mint's decimals. When given, or read from the mint to convert a
+    /// `ui_amount`, the `*_checked` instruction is built.
+    #[serde(default)]
+    pub decimals: Option<u8>,
+    #[serde(default)]
+    pub output_format: OutputFormat,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExportKeypairRequest {
+    /// Exactly one of `secret` and `key_id`; production mode only takes `key_id`.
+    pub secret: Option<SecretKeyStr>,
+    pub key_id: Option<String>,
+    pub passphrase: Redacted<String>,
+}
+
+/// Secret key sealed with AES-256-GCM under a key argon2id derives from a
+/// passphrase. The public key is authenticated as associated data, so the
+/// ciphertext can't be relabelled with another key.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EncryptedKeypair {
+    pub version: u8,
+    pub pubkey: PubkeyStr,
+    pub kdf: KdfParams,
+    /// Base64 argon2 salt.
+    pub salt: String,
+    /// Base64 AES-GCM nonce.
+    pub nonce: String,
+    /// Base64 ciphertext and tag.
+    pub ciphertext: String,
+}
+
+/// argon2id costs, recorded so they can be raised without breaking old backups.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ImportKeypairRequest {
+    pub encrypted: EncryptedKeypair,
+    pub passphrase: Redacted<String>,
+    #[serde(flatten)]
+    pub metadata: keys::KeyMetadata,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignMessageRequest {
+    pub message: String,
+    /// Exactly one of `secret` and `key_id`; production mode only takes `key_id`.
+    pub secret: Option<SecretKeyStr>,
+    pub key_id: Option<String>,
+    #[serde(default)]
+    pub signature_encoding: SignatureEncoding,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignMessageResponse {
+    pub signature: String,
+    pub public_key: String,
+    pub message: String,
+    /// Tag the server prepended before signing, when it's configured with
+    /// one; verifiers must prepend it too.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VerifyMessageRequest {
+    pub message: String,
+    pub signature: SignatureStr,
+    pub pubkey: PubkeyStr,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VerifyMessageResponse {
+    pub valid: bool,
+    pub message: String,
+    pub pubkey: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BatchVerifyRequest {
+    pub items: Vec<BatchVerifyItem>,
+}
+
+/// Left as raw strings so one malformed entry fails only its own result.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BatchVerifyItem {
+    pub message: String,
+    pub signature: String,
+    pub pubkey: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BatchVerifyResult {
+    pub index: usize,
+    pub valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ItemError>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ItemError {
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BatchVerifyResponse {
+    pub total: usize,
+    pub valid_count: usize,
+    pub results: Vec<BatchVerifyResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SendSolRequest {
+    pub from: PubkeyStr,
+    pub to: AddressRef,
+    /// Exactly one of `lamports` and `amount_sol`.
+    #[serde(default)]
+    pub lamports: Option<u64>,
+    /// Decimal SOL, e.g. `"1.5"`; finer than a lamport is rejected.
+    #[serde(default)]
+    pub amount_sol: Option<String>,
+    #[serde(default)]
+    pub output_format: OutputFormat,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SendSolBatchRequest {
+    /// Source of every transfer, and fee payer of packed transactions.
+    pub from: PubkeyStr,
+    pub transfers: Vec<SolTransfer>,
+    /// Pack the transfers into unsigned transactions instead of returning
+    /// bare instructions.
+    #[serde(default)]
+    pub pack: bool,
+    /// For packed transactions; defaults to the cluster's latest blockhash.
+    #[serde(default)]
+    pub recent_blockhash: Option<HashStr>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SolTransfer {
+    pub to: AddressRef,
+    pub lamports: u64,
+}
+
+/// Carries `instructions`, or `transactions` and their `recent_blockhash`
+/// when the request asked for them packed.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SendSolBatchResponse {
+    pub transfer_count: usize,
+    pub total_lamports: u64,
+    pub total_sol: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub instructions: Vec<InstructionResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recent_blockhash: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transactions: Vec<SolBatchTransaction>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SolBatchTransaction {
+    pub index: usize,
+    /// Base64 bincode of the unsigned transaction; only `from` must sign.
+    pub transaction: String,
+    /// Indices into `transfers` covered by this transaction, `[start, end)`.
+    pub transfers: [usize; 2],
+    pub size: usize,
+}
+
+/// The `/send/sol` response from before it carried full account metas,
+/// still served at `/v1/send/sol` for clients that read it.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SendSolResponse {
+    pub program_id: String,
+    pub accounts: Vec<String>,
+    pub instruction_data: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SendTokenRequest {
+    pub destination: AddressRef,
+    pub mint: PubkeyStr,
+    pub owner: PubkeyStr,
+    /// Approved delegate that signs in place of `owner`; tokens still leave
+    /// the owner's associated token account.
+    #[serde(default)]
+    pub delegate: Option<PubkeyStr>,
+    /// Signers of the authority (owner or delegate) when it is an spl-token
+    /// multisig account.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub multisig_signers: Vec<PubkeyStr>,
+    /// Raw base units; exactly one of `amount` and `ui_amount`.
+    #[serde(default)]
+    pub amount: Option<u64>,
+    /// Decimal amount, e.g. `"1.5"`; finer than a base unit is rejected.
+    #[serde(default)]
+    pub ui_amount: Option<String>,
+    /// The mint's decimals. When given, or read from the mint to convert a
+    /// `ui_amount`, the `*_checked` instruction is built.
+    #[serde(default)]
+    pub decimals: Option<u8>,
+    #[serde(default)]
+    pub output_format: OutputFormat,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SendTokenResponse {
+    pub program_id: String,
+    pub accounts: Vec<SendTokenAccount>,
+    pub instruction_data: String,
+    /// Owner's associated token account the tokens leave from.
+    pub source_ata: String,
+    /// Destination's associated token account; it must exist before the transfer lands.
+    pub destination_ata: String,
+    /// Keys that must sign a transaction carrying this instruction.
+    pub required_signers: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SendTokenAccount {
+    pub pubkey: String,
+    #[serde(rename = "isSigner")]
+    pub is_signer: bool,
+}
\ No newline at end of file
@@ -0,0 +1,63 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::PubkeyStr;
+use crate::{InstructionResponse, OutputFormat};
+
+/// Builds the instructions creating an empty SPL account compression tree:
+/// allocating the account, then `init_empty_merkle_tree`.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CreateMerkleTreeRequest {
+    /// Funds the tree account's rent.
+    pub payer: PubkeyStr,
+    /// A new keypair's address, which signs its own creation.
+    pub tree: PubkeyStr,
+    /// May append to and replace leaves in the tree; defaults to `payer`.
+    #[serde(default)]
+    pub authority: Option<PubkeyStr>,
+    /// The tree holds up to 2^max_depth leaves.
+    pub max_depth: u32,
+    /// How many concurrent changes to the tree can land in one slot.
+    pub max_buffer_size: u32,
+    /// Upper levels cached on chain, so proofs can leave them out.
+    #[serde(default)]
+    pub canopy_depth: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CreateMerkleTreeResponse {
+    pub tree: String,
+    pub authority: String,
+    pub cost: MerkleTreeCost,
+    pub instructions: Vec<InstructionResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MerkleTreeCostQuery {
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    #[serde(default)]
+    pub canopy_depth: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MerkleTreeCost {
+    /// Bytes the tree account is allocated.
+    pub space: u64,
+    /// Rent-exempt balance for `space`.
+    pub lamports: u64,
+    pub max_leaves: u64,
+    /// Proof nodes passed with each change, those the canopy doesn't cache.
+    pub proof_length: u32,
+}
+
+/// Builds `append`, adding `leaf` as the tree's next leaf.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AppendLeafRequest {
+    pub tree: PubkeyStr,
+    pub authority: PubkeyStr,
+    /// The 32-byte leaf hash, base58.
+    pub leaf: String,
+    #[serde(default)]
+    pub output_format: OutputFormat,
+}
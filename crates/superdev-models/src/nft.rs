@@ -0,0 +1,118 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::types::PubkeyStr;
+use crate::{InstructionResponse, OutputFormat};
+
+/// An NFT's Metaplex metadata account merged with the JSON its `uri`
+/// points to. Display fields come from the account where it has them and
+/// from the off-chain JSON otherwise; both layers are included as read.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NftMetadataResponse {
+    pub mint: String,
+    pub metadata_address: String,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub animation_url: Option<String>,
+    pub external_url: Option<String>,
+    pub attributes: Vec<Value>,
+    pub on_chain: OnChainMetadata,
+    /// The JSON at `uri`; absent when there is none or it couldn't be read.
+    pub off_chain: Option<Value>,
+    /// Why `off_chain` couldn't be read. Such responses aren't cached.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub off_chain_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OnChainMetadata {
+    pub update_authority: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<NftCreator>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NftCreator {
+    pub address: String,
+    pub verified: bool,
+    /// Percentage of royalties paid to this creator.
+    pub share: u8,
+}
+
+/// Builds the Token Metadata program's `Burn` for an NFT, which closes its
+/// metadata, edition and token accounts and returns their rent to the
+/// owner, unlike a plain SPL burn.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BurnNftRequest {
+    /// Holds the NFT and signs the burn.
+    pub owner: PubkeyStr,
+    pub mint: PubkeyStr,
+    /// Defaults to the owner's associated token account.
+    #[serde(default)]
+    pub token_account: Option<PubkeyStr>,
+    /// Required when the NFT is a verified member of a collection.
+    #[serde(default)]
+    pub collection_mint: Option<PubkeyStr>,
+    /// Programmable NFTs (pNFTs) also close their token record.
+    #[serde(default)]
+    pub programmable: bool,
+    /// Set when burning a print edition.
+    #[serde(default)]
+    pub print_of: Option<PrintOf>,
+    #[serde(default)]
+    pub output_format: OutputFormat,
+}
+
+/// The master edition a print was made from.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PrintOf {
+    pub master_mint: PubkeyStr,
+    /// A token account holding the master edition.
+    pub master_token_account: PubkeyStr,
+    /// The print's edition number, starting at 1.
+    pub edition: u64,
+}
+
+/// Builds `MintNewEditionFromMasterEditionViaToken`, printing a numbered
+/// edition of a master edition into a fresh mint.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PrintEditionRequest {
+    pub master_mint: PubkeyStr,
+    /// Holds the master edition and signs for it.
+    pub owner: PubkeyStr,
+    /// Defaults to the owner's associated token account.
+    #[serde(default)]
+    pub master_token_account: Option<PubkeyStr>,
+    /// A mint with 0 decimals and one token already minted; it becomes the
+    /// print.
+    pub new_mint: PubkeyStr,
+    /// Mint authority of `new_mint`, which signs.
+    pub new_mint_authority: PubkeyStr,
+    /// Funds the print's accounts; defaults to `owner`.
+    #[serde(default)]
+    pub payer: Option<PubkeyStr>,
+    /// Defaults to `owner`. The print inherits the master's update
+    /// authority either way.
+    #[serde(default)]
+    pub update_authority: Option<PubkeyStr>,
+    /// The print's edition number, starting at 1 and at most the master's
+    /// max supply.
+    pub edition: u64,
+    #[serde(default)]
+    pub output_format: OutputFormat,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PrintEditionResponse {
+    pub metadata: String,
+    pub edition: String,
+    /// Records which edition numbers near this one are taken.
+    pub edition_marker: String,
+    pub instruction: InstructionResponse,
+}
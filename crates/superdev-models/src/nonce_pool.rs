@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{PubkeyStr, SignerRef};
+use crate::InstructionResponse;
+
+/// Grows the pool: either creates `count` new nonce accounts funded by
+/// `payer`, or adopts existing `accounts` the pool authority controls.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AddNonceAccountsRequest {
+    #[serde(default)]
+    pub count: Option<u8>,
+    /// Funds the new accounts' rent; required with `count`.
+    #[serde(default)]
+    pub payer: Option<SignerRef>,
+    #[serde(default)]
+    pub accounts: Vec<PubkeyStr>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AddNonceAccountsResponse {
+    pub added: Vec<String>,
+    /// Signatures of the transactions creating new accounts.
+    pub signatures: Vec<String>,
+    /// Accounts in the pool afterwards.
+    pub total: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NoncePoolStatus {
+    pub authority: String,
+    pub total: usize,
+    pub available: usize,
+    pub accounts: Vec<PooledNonce>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PooledNonce {
+    pub address: String,
+    pub leased: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lease_expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct AcquireNonceRequest {
+    /// Lease length; capped at the configured default.
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
+}
+
+/// A nonce account reserved for one transaction. Sign the transaction with
+/// `nonce` as its recent blockhash and `advance_instruction` first, then
+/// send it through `/transaction/send` with the `lease_id`, which adds the
+/// authority's signature and releases the lease once it confirms.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NonceLease {
+    pub lease_id: String,
+    pub nonce_account: String,
+    pub nonce: String,
+    pub authority: String,
+    pub advance_instruction: InstructionResponse,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ReleaseNonceRequest {
+    pub lease_id: String,
+}
@@ -0,0 +1,91 @@
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::keypair::Keypair;
+use std::str::FromStr;
+use base64::{Engine as _, engine::general_purpose};
+use zeroize::Zeroizing;
+use crate::error::FieldError;
+
+pub fn parse_pubkey(pubkey_str: &str) -> Result<Pubkey, FieldError> {
+    Pubkey::from_str(pubkey_str).map_err(|_| FieldError::InvalidPubkey)
+}
+
+pub fn parse_hash(hash_str: &str) -> Result<Hash, FieldError> {
+    Hash::from_str(hash_str).map_err(|_| FieldError::InvalidBlockhash)
+}
+
+/// Reads a 64-byte secret key written as base58 (solana-cli, Phantom),
+/// hex or base64. Their 64-byte forms never overlap: base58 is tried first,
+/// and when nothing fits the base58 error is reported.
+pub fn parse_secret_key(secret_str: &str) -> Result<Keypair, FieldError> {
+    let base58 = bs58::decode(secret_str)
+        .into_vec()
+        .map(Zeroizing::new)
+        .map_err(|_| FieldError::SecretInvalidBase58)
+        .and_then(|bytes| parse_secret_bytes(&bytes));
+    base58.or_else(|error| {
+        let bytes = hex::decode(secret_str.strip_prefix("0x").unwrap_or(secret_str))
+            .or_else(|_| general_purpose::STANDARD.decode(secret_str))
+            .map(Zeroizing::new)
+            .ok()
+            .filter(|bytes| bytes.len() == 64)
+            .ok_or(error)?;
+        parse_secret_bytes(&bytes)
+    })
+}
+
+/// A secret key given as raw bytes, e.g. a solana-cli keypair file's JSON
+/// array.
+pub fn parse_secret_bytes(secret_bytes: &[u8]) -> Result<Keypair, FieldError> {
+    if secret_bytes.len() != 64 {
+        return Err(FieldError::SecretWrongLength(secret_bytes.len()));
+    }
+
+    Keypair::try_from(secret_bytes).map_err(|_| FieldError::SecretInvalid)
+}
+
+/// Takes base64 or base58, the encoding explorers and solana-cli print.
+/// Base64 of 64 bytes always ends in `==`, which base58 can't contain, so
+/// the two never overlap; errors describe the base64 attempt.
+pub fn parse_signature(signature_str: &str) -> Result<Signature, FieldError> {
+    let base64 = general_purpose::STANDARD
+        .decode(signature_str)
+        .map_err(|_| FieldError::SignatureInvalidBase64)
+        .and_then(|bytes| Signature::try_from(bytes.as_slice()).map_err(|_| FieldError::SignatureInvalid));
+    base64.or_else(|error| {
+        let bytes = bs58::decode(signature_str).into_vec().map_err(|_| error.clone())?;
+        Signature::try_from(bytes.as_slice()).map_err(|_| error)
+    })
+}
+
+/// Parses PDA seeds written as comma-separated `encoding:value` pairs, e.g.
+/// `utf8:metadata,base58:<pubkey>,hex:01ff`.
+pub fn parse_seeds(seeds_str: &str) -> Result<Vec<Vec<u8>>, FieldError> {
+    if seeds_str.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let seeds = seeds_str
+        .split(',')
+        .map(|seed| {
+            let (encoding, value) = seed.split_once(':').ok_or(FieldError::InvalidSeeds)?;
+            let bytes = match encoding {
+                "utf8" => value.as_bytes().to_vec(),
+                "hex" => hex::decode(value).map_err(|_| FieldError::InvalidSeeds)?,
+                "base58" => bs58::decode(value).into_vec().map_err(|_| FieldError::InvalidSeeds)?,
+                "base64" => general_purpose::STANDARD.decode(value).map_err(|_| FieldError::InvalidSeeds)?,
+                _ => return Err(FieldError::InvalidSeeds),
+            };
+            if bytes.len() > MAX_SEED_LEN {
+                return Err(FieldError::InvalidSeeds);
+            }
+            Ok(bytes)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if seeds.len() > MAX_SEEDS {
+        return Err(FieldError::InvalidSeeds);
+    }
+    Ok(seeds)
+}
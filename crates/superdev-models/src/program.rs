@@ -0,0 +1,209 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::types::{HashStr, PubkeyStr};
+use crate::InstructionResponse;
+
+/// Filters for `/program/{id}/accounts`; at least one is required. `mint`
+/// and `owner` are presets for token accounts of either token program.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ProgramAccountsQuery {
+    /// Token accounts of this mint.
+    pub mint: Option<PubkeyStr>,
+    /// Token accounts owned by this wallet.
+    pub owner: Option<PubkeyStr>,
+    /// Raw byte match as `<offset>:<base58 bytes>`, e.g. `8:3xqY...`.
+    pub memcmp: Option<String>,
+    /// Exact account data length.
+    pub data_size: Option<u64>,
+    /// Most accounts to return; more matches are an error rather than cut
+    /// off. Defaults to 100.
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ProgramAccountsResponse {
+    pub program_id: String,
+    pub count: usize,
+    pub accounts: Vec<ProgramAccount>,
+}
+
+/// An account with its data decoded when the layout is known, and as
+/// base64 otherwise.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ProgramAccount {
+    pub pubkey: String,
+    pub lamports: u64,
+    pub data_len: usize,
+    /// `mint`, `token_account`, `nonce` or `stake`, as in `/decode`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parsed: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+/// Builds the instructions creating a buffer for `program` and writing it
+/// in, for deploys and upgrades. As `multipart/form-data`, the `file` part
+/// holds the raw `.so` and text fields carry the rest.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WriteBufferRequest {
+    pub payer: PubkeyStr,
+    /// A fresh keypair's public key; it signs the buffer's creation.
+    pub buffer: PubkeyStr,
+    /// May write to, deploy from and close the buffer. Defaults to `payer`.
+    #[serde(default)]
+    pub authority: Option<PubkeyStr>,
+    /// The program's ELF bytes, base64.
+    pub program: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WriteBufferResponse {
+    pub buffer: String,
+    pub program_len: usize,
+    /// Rent the payer funds the buffer with; closing it refunds them.
+    pub lamports: u64,
+    /// Creates the buffer. Send these before any of `writes`.
+    pub instructions: Vec<InstructionResponse>,
+    /// One transaction each, sized to fit with the payer and authority
+    /// signing. They may land in any order.
+    pub writes: Vec<InstructionResponse>,
+}
+
+/// Stores a program binary for an hour and packs the transactions writing
+/// it into a new buffer. As `multipart/form-data`, the `file` part holds the
+/// raw `.so` and text fields carry the rest.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ProgramUploadRequest {
+    pub payer: PubkeyStr,
+    /// A fresh keypair's public key; it signs the buffer's creation.
+    pub buffer: PubkeyStr,
+    /// Defaults to `payer`.
+    #[serde(default)]
+    pub authority: Option<PubkeyStr>,
+    /// The program's ELF bytes, base64.
+    pub program: String,
+    /// Defaults to the cluster's latest blockhash.
+    #[serde(default)]
+    pub recent_blockhash: Option<HashStr>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ProgramUpload {
+    pub id: String,
+    /// SHA-256 of the binary, hex.
+    pub sha256: String,
+    pub program_len: usize,
+    pub payer: String,
+    pub buffer: String,
+    pub authority: String,
+    pub cost: DeployCost,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// What writing the buffer and deploying from it costs the payer, at the
+/// base fee and with `max_data_len` equal to the program's length.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DeployCost {
+    /// Refunded when the deploy closes the buffer.
+    pub buffer_lamports: u64,
+    pub program_lamports: u64,
+    pub program_data_lamports: u64,
+    /// Transactions to send, the deploy included.
+    pub transactions: usize,
+    pub fee_lamports: u64,
+    /// Balance the payer needs before starting.
+    pub total_lamports: u64,
+    /// Spent once the buffer's rent is refunded.
+    pub net_lamports: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ProgramUploadResponse {
+    #[serde(flatten)]
+    pub upload: ProgramUpload,
+    pub recent_blockhash: String,
+    /// Unsigned, base64: the buffer's creation, then its writes.
+    pub transactions: Vec<String>,
+}
+
+/// Re-packs an upload's transactions, e.g. once their blockhash expired.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ProgramUploadQuery {
+    /// Defaults to the cluster's latest blockhash.
+    pub recent_blockhash: Option<HashStr>,
+}
+
+/// Builds the instructions deploying a new program from a written buffer,
+/// which the deploy closes.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DeployProgramRequest {
+    pub payer: PubkeyStr,
+    /// A fresh keypair's public key; it signs the deploy and becomes the
+    /// program id.
+    pub program_id: PubkeyStr,
+    pub buffer: PubkeyStr,
+    /// The buffer's authority, which becomes the upgrade authority.
+    /// Defaults to `payer`.
+    #[serde(default)]
+    pub authority: Option<PubkeyStr>,
+    /// Largest program upgrades may grow to. Defaults to the buffered
+    /// program's length, read from the buffer.
+    #[serde(default)]
+    pub max_data_len: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DeployProgramResponse {
+    pub program_id: String,
+    pub program_data: String,
+    pub max_data_len: usize,
+    pub instructions: Vec<InstructionResponse>,
+}
+
+/// Builds an upgrade replacing the program with a written buffer's contents.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UpgradeProgramRequest {
+    pub program_id: PubkeyStr,
+    pub buffer: PubkeyStr,
+    /// The upgrade authority, which must also be the buffer's.
+    pub authority: PubkeyStr,
+    /// Receives the buffer's lamports. Defaults to `authority`.
+    #[serde(default)]
+    pub spill: Option<PubkeyStr>,
+}
+
+/// Builds a change of a program's upgrade authority or a buffer's authority.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SetProgramAuthorityRequest {
+    pub account: PubkeyStr,
+    pub account_type: LoaderAccountType,
+    pub authority: PubkeyStr,
+    /// Leaving it out makes a program immutable; buffers always need one.
+    #[serde(default)]
+    pub new_authority: Option<PubkeyStr>,
+    /// Also require the new authority's signature.
+    #[serde(default)]
+    pub checked: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LoaderAccountType {
+    Program,
+    Buffer,
+}
+
+/// Builds the close of a buffer, refunding its rent, e.g. after a failed
+/// deploy.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CloseBufferRequest {
+    pub buffer: PubkeyStr,
+    pub authority: PubkeyStr,
+    pub recipient: PubkeyStr,
+}
@@ -0,0 +1,62 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageKind {
+    #[default]
+    Png,
+    Svg,
+}
+
+/// Error-correction level; higher levels survive more damage (or a logo
+/// pasted over the middle) at the cost of a denser code.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
+pub enum Correction {
+    L,
+    #[default]
+    M,
+    Q,
+    H,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QrOptions {
+    pub format: ImageKind,
+    /// Minimum width and height in pixels; the quiet zone is included.
+    pub size: Option<u32>,
+    pub ec: Correction,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct QrQuery {
+    pub data: String,
+    #[serde(default)]
+    pub format: ImageKind,
+    pub size: Option<u32>,
+    #[serde(default)]
+    pub ec: Correction,
+}
+
+/// Like `QrQuery`, but `url` must be a valid `solana:` URL.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PayQrQuery {
+    pub url: String,
+    #[serde(default)]
+    pub format: ImageKind,
+    pub size: Option<u32>,
+    #[serde(default)]
+    pub ec: Correction,
+}
+
+impl QrQuery {
+    pub fn options(&self) -> QrOptions {
+        QrOptions { format: self.format, size: self.size, ec: self.ec }
+    }
+}
+
+impl PayQrQuery {
+    pub fn options(&self) -> QrOptions {
+        QrOptions { format: self.format, size: self.size, ec: self.ec }
+    }
+}
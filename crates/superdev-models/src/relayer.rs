@@ -0,0 +1,39 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RelayerInfo {
+    pub fee_payer: String,
+    pub fee: Option<RelayerFeeInfo>,
+    pub max_signatures: u8,
+    /// Empty when any program may be invoked.
+    pub allowed_programs: Vec<String>,
+}
+
+/// The token fee a relayed transaction must include, as a transfer of at
+/// least `amount` base units of `mint` into `account`.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RelayerFeeInfo {
+    pub mint: String,
+    pub amount: String,
+    pub account: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RelayRequest {
+    /// Base64 wire-format transaction with the relayer as fee payer and every
+    /// other required signature already present.
+    pub transaction: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RelaySignResponse {
+    /// Fully signed, ready for `sendTransaction`.
+    pub transaction: String,
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RelaySubmitResponse {
+    pub signature: String,
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// What the server POSTs to `error_reporting.webhook_url` when a request
+/// panics or ends in a 5xx response.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ErrorReport {
+    pub time: DateTime<Utc>,
+    /// Also returned to the caller in `X-Request-Id`, so a report can be
+    /// matched to a client's complaint.
+    pub request_id: String,
+    /// The caller's `X-Correlation-Id`, or the one made up for the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    /// `error_reporting.environment`, e.g. `production` or `staging`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+    pub method: String,
+    /// Path without its query string, which may carry secrets.
+    pub path: String,
+    pub status: u16,
+    /// Error code of the response body, or `PANIC` for a handler that
+    /// panicked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub tenant: String,
+    /// Caller, by client certificate or API key, when it authenticated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity: Option<String>,
+    /// JSON body of the request, secrets replaced by `[REDACTED]`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request: Option<Value>,
+}
@@ -0,0 +1,195 @@
+use schemars::generate::SchemaSettings;
+use serde_json::{json, Value};
+
+use crate::{account, address_book, admin, airdrop, alt, anchor, assets, audit, block, borsh, cluster, compose};
+use crate::{convert, decode, derive, jobs, keys, merkle_tree, nft, nonce_pool, program, qr, relayer};
+use crate::{reporting, solana_pay, squads, stake, templates, token, transaction, transfers, vote};
+
+/// JSON Schema (draft 2020-12) of every request and response body, keyed
+/// by type name under `$defs`. Types they contain are included too.
+pub fn document() -> Value {
+    let mut generator = SchemaSettings::draft2020_12().into_generator();
+    macro_rules! add {
+        ($($ty:ty),* $(,)?) => {$(
+            generator.subschema_for::<$ty>();
+        )*};
+    }
+    add!(
+        crate::KeypairQuery,
+        crate::KeypairResponse,
+        crate::KeypairLine,
+        crate::DeterministicKeypairRequest,
+        crate::DeterministicKeypairResponse,
+        crate::CreateTokenRequest,
+        crate::InstructionResponse,
+        crate::MintTokenRequest,
+        crate::ExportKeypairRequest,
+        crate::EncryptedKeypair,
+        crate::ImportKeypairRequest,
+        crate::SignMessageRequest,
+        crate::SignMessageResponse,
+        crate::VerifyMessageRequest,
+        crate::VerifyMessageResponse,
+        crate::BatchVerifyRequest,
+        crate::BatchVerifyResponse,
+        crate::SendSolRequest,
+        crate::SendSolResponse,
+        crate::SendSolBatchRequest,
+        crate::SendSolBatchResponse,
+        crate::SendTokenRequest,
+        crate::SendTokenResponse,
+        crate::Web3Instruction,
+        account::CleanupRequest,
+        account::CleanupResponse,
+        address_book::CreateAddressRequest,
+        address_book::UpdateAddressRequest,
+        address_book::AddressBookEntry,
+        admin::AdminStatus,
+        admin::Health,
+        admin::RuntimeStatus,
+        admin::RuntimeTasks,
+        admin::JobQueueDepth,
+        admin::RpcHealth,
+        admin::RotateResponse,
+        admin::RevokeRequest,
+        admin::FeaturesRequest,
+        airdrop::BulkAirdropRequest,
+        airdrop::BulkAirdropResponse,
+        airdrop::AirdropLine,
+        airdrop::AirdropDryRun,
+        alt::AltPlanRequest,
+        alt::AltPlanResponse,
+        anchor::RegisterIdlRequest,
+        anchor::RegisterIdlResponse,
+        anchor::AnchorInstructionRequest,
+        anchor::ParseLogsRequest,
+        anchor::ParseLogsResponse,
+        assets::Asset,
+        assets::AssetsByOwnerQuery,
+        assets::AssetsByOwnerResponse,
+        audit::AuditEntry,
+        block::BlockQuery,
+        block::BlockResponse,
+        borsh::BorshEncodeRequest,
+        borsh::BorshEncodeResponse,
+        borsh::BorshDecodeRequest,
+        borsh::BorshDecodeResponse,
+        cluster::EpochInfoResponse,
+        cluster::SlotResponse,
+        cluster::VersionResponse,
+        cluster::ValidatorsQuery,
+        cluster::ValidatorsResponse,
+        compose::ComposeRequest,
+        compose::ComposeOperation,
+        compose::MemoOperation,
+        compose::CreateAtaOperation,
+        convert::SolQuery,
+        convert::SolConversion,
+        convert::TokenQuery,
+        convert::TokenConversion,
+        decode::AccountSource,
+        decode::MintLayout,
+        decode::TokenAccountLayout,
+        decode::NonceLayout,
+        decode::StakeLayout,
+        derive::AtaQuery,
+        derive::AtaResponse,
+        derive::PdaQuery,
+        derive::PdaResponse,
+        jobs::ScheduleRequest,
+        jobs::Job,
+        keys::KeyMetadata,
+        keys::KeysQuery,
+        keys::KeyInfo,
+        merkle_tree::CreateMerkleTreeRequest,
+        merkle_tree::CreateMerkleTreeResponse,
+        merkle_tree::MerkleTreeCostQuery,
+        merkle_tree::MerkleTreeCost,
+        merkle_tree::AppendLeafRequest,
+        nft::NftMetadataResponse,
+        nft::BurnNftRequest,
+        nft::PrintEditionRequest,
+        nft::PrintEditionResponse,
+        nonce_pool::AddNonceAccountsRequest,
+        nonce_pool::AddNonceAccountsResponse,
+        nonce_pool::NoncePoolStatus,
+        nonce_pool::AcquireNonceRequest,
+        nonce_pool::NonceLease,
+        nonce_pool::ReleaseNonceRequest,
+        program::ProgramAccountsQuery,
+        program::ProgramAccountsResponse,
+        program::WriteBufferRequest,
+        program::WriteBufferResponse,
+        program::ProgramUploadRequest,
+        program::ProgramUpload,
+        program::ProgramUploadResponse,
+        program::ProgramUploadQuery,
+        program::DeployProgramRequest,
+        program::DeployProgramResponse,
+        program::UpgradeProgramRequest,
+        program::SetProgramAuthorityRequest,
+        program::CloseBufferRequest,
+        qr::QrQuery,
+        qr::PayQrQuery,
+        relayer::RelayerInfo,
+        relayer::RelayRequest,
+        relayer::RelaySignResponse,
+        relayer::RelaySubmitResponse,
+        reporting::ErrorReport,
+        solana_pay::EncodeRequest,
+        solana_pay::EncodeResponse,
+        solana_pay::DecodeQuery,
+        solana_pay::DecodeResponse,
+        solana_pay::PayRequestInfo,
+        solana_pay::PayTransactionRequest,
+        solana_pay::PayTransactionResponse,
+        squads::CreateMultisigRequest,
+        squads::CreateMultisigResponse,
+        squads::VaultTransactionRequest,
+        squads::VaultTransactionResponse,
+        squads::ApproveProposalRequest,
+        squads::ExecuteProposalRequest,
+        squads::ProposalInstructionsResponse,
+        stake::RewardsQuery,
+        stake::RewardsResponse,
+        stake::EpochReward,
+        templates::TemplateRequest,
+        templates::StoredTemplate,
+        templates::RenderTemplateRequest,
+        templates::RenderTemplateResponse,
+        token::CreateMetadataRequest,
+        token::HoldersLine,
+        token::HoldersQuery,
+        token::HoldersResponse,
+        token::HoldersSummary,
+        token::TokenInfoResponse,
+        transaction::TransactionSummary,
+        transaction::InspectRequest,
+        transaction::InspectResponse,
+        transaction::VerifySignaturesRequest,
+        transaction::VerifySignaturesResponse,
+        transaction::SignTransactionMessageRequest,
+        transaction::SignTransactionMessageResponse,
+        transaction::BuildTransactionRequest,
+        transaction::BuildTransactionResponse,
+        transaction::PreviewRequest,
+        transaction::PreviewResponse,
+        transaction::SendBundleRequest,
+        transaction::SendBundleResponse,
+        transaction::BundleTipInfo,
+        transaction::SendTransactionRequest,
+        transaction::SendTransactionResponse,
+        transaction::RebuildHint,
+        transaction::SimulateQuery,
+        transaction::DryRun,
+        transfers::ProposeRequest,
+        transfers::ApproveRequest,
+        transfers::Proposal,
+        vote::VoteWithdrawRequest,
+        vote::VoteAuthorizeRequest,
+    );
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$defs": generator.take_definitions(true),
+    })
+}
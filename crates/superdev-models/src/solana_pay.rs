@@ -0,0 +1,70 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::types::PubkeyStr;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct EncodeRequest {
+    /// Transfer request recipient. Mutually exclusive with `link`.
+    pub recipient: Option<PubkeyStr>,
+    /// Transaction request endpoint. Mutually exclusive with `recipient`.
+    pub link: Option<String>,
+    /// Decimal amount in SOL or whole tokens, e.g. `"1.5"`.
+    pub amount: Option<String>,
+    pub spl_token: Option<PubkeyStr>,
+    #[serde(default)]
+    pub reference: Vec<PubkeyStr>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+    pub memo: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct EncodeResponse {
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DecodeQuery {
+    pub url: String,
+}
+
+/// Flattened view of a `PayUrl`, tagged by `kind`.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DecodeResponse {
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spl_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reference: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+}
+
+/// `GET /solana-pay/tx/{id}` body, exactly as the spec defines it.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PayRequestInfo {
+    pub label: String,
+    pub icon: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PayTransactionRequest {
+    pub account: PubkeyStr,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PayTransactionResponse {
+    pub transaction: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
@@ -0,0 +1,108 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::jobs::InstructionTemplate;
+use crate::types::PubkeyStr;
+use crate::InstructionResponse;
+
+/// Builds the instruction creating a Squads v4 multisig. `create_key` is
+/// any fresh keypair; it only seeds the multisig address and must sign.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CreateMultisigRequest {
+    pub creator: PubkeyStr,
+    pub create_key: PubkeyStr,
+    pub members: Vec<MultisigMember>,
+    /// Approvals a proposal needs; at most the number of voting members.
+    pub threshold: u16,
+    /// Seconds between approval and execution.
+    #[serde(default)]
+    pub time_lock: u32,
+    /// Lets this key change the configuration without a vote. Leave unset
+    /// for an autonomous multisig.
+    #[serde(default)]
+    pub config_authority: Option<PubkeyStr>,
+    #[serde(default)]
+    pub rent_collector: Option<PubkeyStr>,
+    #[serde(default)]
+    pub memo: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MultisigMember {
+    pub key: PubkeyStr,
+    pub permissions: Vec<MemberPermission>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MemberPermission {
+    /// Create vault transactions and proposals.
+    Initiate,
+    Vote,
+    Execute,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CreateMultisigResponse {
+    pub multisig: String,
+    /// The default vault, index 0, which holds the multisig's funds.
+    pub vault: String,
+    pub instructions: Vec<InstructionResponse>,
+}
+
+/// Builds the instructions storing `instructions` as a vault transaction and
+/// opening a proposal for it. The vault is the only signer the instructions
+/// may need.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VaultTransactionRequest {
+    pub multisig: PubkeyStr,
+    /// A member with the initiate permission.
+    pub creator: PubkeyStr,
+    /// Pays rent for the new accounts; defaults to `creator`.
+    #[serde(default)]
+    pub rent_payer: Option<PubkeyStr>,
+    #[serde(default)]
+    pub vault_index: u8,
+    pub instructions: Vec<InstructionTemplate>,
+    #[serde(default)]
+    pub memo: Option<String>,
+    /// Defaults to the next index, read from the multisig account.
+    #[serde(default)]
+    pub transaction_index: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VaultTransactionResponse {
+    pub transaction_index: u64,
+    pub vault: String,
+    pub transaction: String,
+    pub proposal: String,
+    pub instructions: Vec<InstructionResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ApproveProposalRequest {
+    pub multisig: PubkeyStr,
+    pub transaction_index: u64,
+    /// A member with the vote permission.
+    pub member: PubkeyStr,
+    #[serde(default)]
+    pub memo: Option<String>,
+}
+
+/// Builds the instruction executing an approved vault transaction, with the
+/// accounts its stored message needs.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExecuteProposalRequest {
+    pub multisig: PubkeyStr,
+    pub transaction_index: u64,
+    /// A member with the execute permission.
+    pub member: PubkeyStr,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ProposalInstructionsResponse {
+    pub transaction_index: u64,
+    pub proposal: String,
+    pub instructions: Vec<InstructionResponse>,
+}
@@ -0,0 +1,33 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Epochs to report, inclusive. Defaults to the last 10 completed epochs.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct RewardsQuery {
+    pub start_epoch: Option<u64>,
+    pub end_epoch: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RewardsResponse {
+    pub account: String,
+    pub start_epoch: u64,
+    pub end_epoch: u64,
+    /// Lamports earned over the range.
+    pub total_rewards: u64,
+    pub total_rewards_sol: String,
+    /// Epochs the account earned a reward in, oldest first.
+    pub rewards: Vec<EpochReward>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct EpochReward {
+    pub epoch: u64,
+    pub effective_slot: u64,
+    pub amount: u64,
+    pub amount_sol: String,
+    pub post_balance: u64,
+    /// The reward as a fraction of the balance that earned it.
+    pub rate: String,
+    pub commission: Option<u8>,
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::borsh::SchemaDescription;
+use crate::idl::Type;
+use crate::types::PubkeyStr;
+use crate::InstructionResponse;
+
+/// A reusable set of instructions with `{{name}}` placeholders, filled in at
+/// render time. Programs are fixed when the template is saved; callers only
+/// supply the declared parameters.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TemplateRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Every placeholder used must be declared here.
+    #[serde(default)]
+    pub parameters: Vec<TemplateParameter>,
+    pub instructions: Vec<TemplateInstruction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TemplateParameter {
+    pub name: String,
+    /// Type in Anchor IDL syntax, e.g. `"pubkey"` or `"u64"`; values are
+    /// checked against it before rendering.
+    #[serde(rename = "type")]
+    pub ty: Type,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Used when a render omits the parameter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TemplateInstruction {
+    pub program_id: PubkeyStr,
+    pub accounts: Vec<TemplateAccount>,
+    /// Base64 bytes the data starts with, such as an Anchor discriminator.
+    #[serde(default)]
+    pub data: String,
+    /// Borsh-encoded after `data`. Strings in `value` that are exactly
+    /// `{{name}}` take the parameter's value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<TemplateArgs>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TemplateAccount {
+    /// A base58 public key or `{{name}}` of a `pubkey` parameter.
+    pub pubkey: String,
+    #[serde(default)]
+    pub is_signer: bool,
+    #[serde(default)]
+    pub is_writable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TemplateArgs {
+    pub schema: SchemaDescription,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StoredTemplate {
+    pub id: String,
+    #[serde(flatten)]
+    pub template: TemplateRequest,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct RenderTemplateRequest {
+    #[serde(default)]
+    pub parameters: Map<String, Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RenderTemplateResponse {
+    pub template_id: String,
+    pub instructions: Vec<InstructionResponse>,
+}
@@ -0,0 +1,125 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::types::PubkeyStr;
+use crate::OutputFormat;
+
+/// One page of holders, largest balance first.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct HoldersQuery {
+    /// Leave out owners holding fewer base units than this.
+    pub min_balance: Option<u64>,
+    /// Page size; defaults to 1000.
+    pub limit: Option<usize>,
+    /// Holders to skip, e.g. the previous response's `next_offset`.
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct HoldersResponse {
+    pub mint: String,
+    pub token_program: String,
+    pub decimals: u8,
+    /// Owners matching `min_balance`, across all pages.
+    pub holder_count: usize,
+    /// Base units those owners hold, across all pages.
+    pub total_balance: u64,
+    pub holders: Vec<Holder>,
+    /// Offset of the next page; absent on the last one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<usize>,
+}
+
+/// One line of a holders page streamed as NDJSON: a holder, or the summary
+/// that closes the page.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum HoldersLine {
+    Holder(Holder),
+    Summary(HoldersSummary),
+}
+
+/// The last line of a streamed page: what `HoldersResponse` carries
+/// besides the holders themselves.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct HoldersSummary {
+    pub mint: String,
+    pub token_program: String,
+    pub decimals: u8,
+    pub holder_count: usize,
+    pub total_balance: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<usize>,
+}
+
+/// An owner's balance summed over all its token accounts of the mint.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Holder {
+    pub owner: String,
+    pub balance: u64,
+    pub ui_balance: String,
+    pub accounts: usize,
+}
+
+/// A mint's current supply and configuration, plus its display metadata
+/// when it has any.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TokenInfoResponse {
+    pub mint: String,
+    pub token_program: String,
+    /// Base units in circulation, as reported by `getTokenSupply`.
+    pub supply: u64,
+    pub ui_supply: String,
+    pub decimals: u8,
+    pub mint_authority: Option<String>,
+    pub freeze_authority: Option<String>,
+    /// Token-2022 extensions, as in `/decode/mint`.
+    pub extensions: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<TokenMetadata>,
+}
+
+/// Name, symbol and URI from the mint's Metaplex metadata account or,
+/// failing that, its Token-2022 metadata extension.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TokenMetadata {
+    /// `metaplex` or `token-2022`.
+    pub source: String,
+    /// The account the metadata was read from.
+    pub address: String,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub update_authority: Option<String>,
+}
+
+/// Builds `CreateMetadataAccountV3` for an existing fungible mint, so
+/// wallets can show its name, symbol and logo.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CreateMetadataRequest {
+    pub mint: PubkeyStr,
+    /// Signs for the mint; the program checks it against the mint account.
+    pub mint_authority: PubkeyStr,
+    /// Funds the metadata account's rent; defaults to `mint_authority`.
+    #[serde(default)]
+    pub payer: Option<PubkeyStr>,
+    /// May change the metadata later; defaults to `mint_authority`.
+    #[serde(default)]
+    pub update_authority: Option<PubkeyStr>,
+    /// At most 32 bytes.
+    pub name: String,
+    /// At most 10 bytes.
+    pub symbol: String,
+    /// The off-chain JSON document, at most 200 bytes.
+    pub uri: String,
+    /// Whether the update authority may change the metadata later.
+    #[serde(default = "default_mutable")]
+    pub is_mutable: bool,
+    #[serde(default)]
+    pub output_format: OutputFormat,
+}
+
+fn default_mutable() -> bool {
+    true
+}
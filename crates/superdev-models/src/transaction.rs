@@ -0,0 +1,304 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::jobs::InstructionTemplate;
+use crate::types::{HashStr, PubkeyStr, SecretKeyStr, SignatureEncoding, SignerRef};
+
+/// Normalized view of a confirmed transaction: what moved, who paid and
+/// whether it landed. Raw amounts are strings; changes are signed.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionSummary {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    /// `success` or `failed`; failed transactions still charge the fee.
+    pub status: String,
+    pub error: Option<String>,
+    pub fee_payer: String,
+    pub fee: String,
+    pub sol_changes: Vec<SolChange>,
+    pub token_changes: Vec<TokenChange>,
+    pub memos: Vec<String>,
+}
+
+/// Lamport balance movement of one account, fee included for the payer.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SolChange {
+    pub account: String,
+    pub pre: String,
+    pub post: String,
+    pub change: String,
+}
+
+/// Net movement of one mint for one owner, summed over the owner's token
+/// accounts in the transaction.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TokenChange {
+    pub owner: Option<String>,
+    pub mint: String,
+    pub decimals: u8,
+    pub change: String,
+    pub ui_change: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct InspectRequest {
+    /// Base64 wire-format transaction, signed or not.
+    pub transaction: String,
+    /// Programs the caller vouches for beyond the built-in and registered ones.
+    #[serde(default)]
+    pub trusted_programs: Vec<PubkeyStr>,
+    /// Delegates an approval may name without being flagged.
+    #[serde(default)]
+    pub trusted_delegates: Vec<PubkeyStr>,
+    /// Share of an account's current balance a transfer may move before
+    /// it's reported as draining it.
+    #[serde(default = "default_drain_threshold")]
+    pub drain_threshold_percent: u8,
+}
+
+fn default_drain_threshold() -> u8 {
+    50
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Risk {
+    None,
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Finding {
+    /// Stable identifier, e.g. `OWNER_CHANGE`; never reworded.
+    pub code: String,
+    pub severity: Risk,
+    /// Absent for findings about the transaction as a whole.
+    pub instruction_index: Option<usize>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct InspectResponse {
+    /// Highest severity among the findings.
+    pub risk: Risk,
+    pub fee_payer: String,
+    /// False when no RPC is configured, so balance drains went unchecked.
+    pub balances_checked: bool,
+    pub findings: Vec<Finding>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VerifySignaturesRequest {
+    /// Base64 wire-format transaction, fully or partially signed.
+    pub transaction: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VerifySignaturesResponse {
+    /// Every required signature is present and valid.
+    pub complete: bool,
+    pub valid_count: usize,
+    /// One per required signer, in message order; the first is the fee payer.
+    pub signers: Vec<SignerSignature>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignerSignature {
+    pub pubkey: String,
+    pub status: SignatureStatus,
+    /// Base58, absent while the signature is missing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureStatus {
+    Valid,
+    /// Still the all-zero placeholder; the signer hasn't signed yet.
+    Missing,
+    /// Present but doesn't verify, e.g. made over a different message.
+    Invalid,
+}
+
+/// Signs serialized message bytes without a transaction around them, for
+/// coordinators that collect signatures out of band.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignTransactionMessageRequest {
+    /// Base64 wire-format message, legacy or v0.
+    pub message: String,
+    /// Exactly one of `secret` and `key_id`; the key must be a required
+    /// signer of the message.
+    pub secret: Option<SecretKeyStr>,
+    pub key_id: Option<String>,
+    #[serde(default)]
+    pub signature_encoding: SignatureEncoding,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SignTransactionMessageResponse {
+    pub signature: String,
+    pub public_key: String,
+    /// Position of the signature in the transaction's signature list.
+    pub signer_index: usize,
+}
+
+/// Compiles `instructions` into one unsigned legacy transaction paid by
+/// `payer`. Instructions may target any program: ones this service built
+/// and ones built elsewhere compose the same way.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BuildTransactionRequest {
+    pub payer: PubkeyStr,
+    pub instructions: Vec<InstructionTemplate>,
+    /// Defaults to the cluster's latest blockhash.
+    #[serde(default)]
+    pub recent_blockhash: Option<HashStr>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BuildTransactionResponse {
+    /// Base64 wire-format transaction with empty signatures.
+    pub transaction: String,
+    /// Base64 message bytes, for signing detached.
+    pub message: String,
+    pub recent_blockhash: String,
+    /// Keys that must sign, in signature order; the payer first.
+    pub signers: Vec<String>,
+    /// Serialized size in bytes, signatures included.
+    pub size: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PreviewRequest {
+    /// Base64 wire-format transaction; signatures aren't verified.
+    pub transaction: String,
+}
+
+/// Raw amounts are strings; `ui_change` is absent when a mint's decimals
+/// couldn't be read.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BalanceChange {
+    pub pre: String,
+    pub post: String,
+    pub change: String,
+    pub ui_change: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TokenPreview {
+    pub mint: String,
+    pub owner: String,
+    pub decimals: Option<u8>,
+    pub amount: BalanceChange,
+}
+
+/// One writable account of the transaction, before and after simulation.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AccountPreview {
+    pub address: String,
+    pub lamports: BalanceChange,
+    /// Present when the account is a token account before or after.
+    pub token: Option<TokenPreview>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PreviewResponse {
+    /// `success` or `failed`; a failed simulation previews no balances.
+    pub status: String,
+    pub error: Option<String>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    pub accounts: Vec<AccountPreview>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SendBundleRequest {
+    /// Fully signed base64 wire-format transactions, executed in this order.
+    pub transactions: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SendBundleResponse {
+    pub bundle_id: String,
+    /// First signature of each transaction, in bundle order.
+    pub signatures: Vec<String>,
+    pub tip_lamports: String,
+}
+
+/// How to tip: transfer at least `min_tip_lamports` to any of `tip_accounts`
+/// from one of the bundle's transactions.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BundleTipInfo {
+    pub min_tip_lamports: String,
+    pub tip_accounts: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SendTransactionRequest {
+    /// Base64 wire-format transaction. Fully signed unless `signers` are
+    /// given, in which case the server signs it.
+    pub transaction: String,
+    /// Every required signer, fee payer first. Lets the server re-sign on a
+    /// fresh blockhash when the transaction's expires unconfirmed.
+    #[serde(default)]
+    pub signers: Vec<SignerRef>,
+    /// Last block height at which the transaction's blockhash is valid, as
+    /// `getLatestBlockhash` returned it. Looked up when omitted.
+    #[serde(default)]
+    pub last_valid_block_height: Option<u64>,
+    /// Fresh-blockhash rebuilds allowed when the server holds the signers.
+    #[serde(default)]
+    pub max_rebuilds: Option<u32>,
+    /// Lease from `/nonce-pool/acquire` whose nonce the transaction uses as
+    /// its blockhash. The server adds the nonce authority's signature and
+    /// frees the lease once the transaction confirms.
+    #[serde(default)]
+    pub nonce_lease: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SendTransactionResponse {
+    /// Signature of the send that confirmed.
+    pub signature: String,
+    pub slot: u64,
+    /// Why the transaction failed on chain, if it did.
+    pub error: Option<String>,
+    pub fee_lamports: u64,
+    /// Times the server re-signed on a fresh blockhash.
+    pub rebuilds: u32,
+    /// `None` for durable-nonce transactions, which don't expire.
+    pub last_valid_block_height: Option<u64>,
+}
+
+/// Returned with `BLOCKHASH_EXPIRED`: the expired send, and a fresh
+/// blockhash to rebuild and re-sign it on.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RebuildHint {
+    pub signature: String,
+    pub expired_blockhash: String,
+    /// `None` when the blockhash had expired before it was sent.
+    pub last_valid_block_height: Option<u64>,
+    pub block_height: u64,
+    pub recent_blockhash: String,
+    pub recent_last_valid_block_height: u64,
+}
+
+/// `?simulate=true` on a builder endpoint: `simulation` is added next to
+/// `data`, from simulating the built instructions in one transaction.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SimulateQuery {
+    #[serde(default)]
+    pub simulate: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DryRun {
+    /// The instructions' first writable signer, or a placeholder when none
+    /// is; a placeholder holds no SOL, so such simulations fail on fees.
+    pub fee_payer: String,
+    pub error: Option<String>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::jobs::InstructionTemplate;
+use crate::types::SignerRef;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ProposeRequest {
+    /// Every signer the instructions need, fee payer first. Raw secrets are
+    /// held in the keystore while the proposal waits for approvals.
+    pub signers: Vec<SignerRef>,
+    pub instructions: Vec<InstructionTemplate>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ApproveRequest {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ProposalStatus {
+    Pending,
+    Submitting,
+    Submitted,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Proposal {
+    pub id: String,
+    pub status: ProposalStatus,
+    pub proposer: String,
+    pub fee_payer: String,
+    /// Identities that approved, in order.
+    pub approvals: Vec<String>,
+    pub required: usize,
+    pub expires_at: DateTime<Utc>,
+    pub signature: Option<String>,
+    pub error: Option<String>,
+}
@@ -0,0 +1,423 @@
+use std::fmt;
+use std::ops::Deref;
+use std::borrow::Cow;
+
+use base64::{engine::general_purpose, Engine as _};
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use serde::{de, ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
+use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signature, signer::keypair::Keypair};
+use zeroize::{Zeroize, Zeroizing};
+use crate::parse::{parse_hash, parse_pubkey, parse_secret_bytes, parse_secret_key, parse_seeds, parse_signature};
+
+/// Base58 public key, validated while the request body is deserialized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PubkeyStr(pub Pubkey);
+
+impl Deref for PubkeyStr {
+    type Target = Pubkey;
+
+    fn deref(&self) -> &Pubkey {
+        &self.0
+    }
+}
+
+impl fmt::Display for PubkeyStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'de> Deserialize<'de> for PubkeyStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_pubkey(&value).map(PubkeyStr).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for PubkeyStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+/// Prefix that marks an address-book label where a public key is expected.
+pub const LABEL_PREFIX: &str = "label:";
+
+/// A base58 public key, or `label:<name>` for an entry in the caller's
+/// address book, which the server resolves after deserializing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AddressRef {
+    Pubkey(Pubkey),
+    Label(String),
+}
+
+impl AddressRef {
+    /// The key itself, unless it's a label still to resolve.
+    pub fn pubkey(&self) -> Option<Pubkey> {
+        match self {
+            AddressRef::Pubkey(pubkey) => Some(*pubkey),
+            AddressRef::Label(_) => None,
+        }
+    }
+}
+
+impl From<PubkeyStr> for AddressRef {
+    fn from(pubkey: PubkeyStr) -> Self {
+        AddressRef::Pubkey(pubkey.0)
+    }
+}
+
+impl fmt::Display for AddressRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressRef::Pubkey(pubkey) => pubkey.fmt(f),
+            AddressRef::Label(label) => write!(f, "{LABEL_PREFIX}{label}"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AddressRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        match value.strip_prefix(LABEL_PREFIX) {
+            Some("") => Err(de::Error::custom("empty address-book label")),
+            Some(label) => Ok(AddressRef::Label(label.to_string())),
+            None => parse_pubkey(&value).map(AddressRef::Pubkey).map_err(de::Error::custom),
+        }
+    }
+}
+
+impl Serialize for AddressRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Secret material on its way in or out of the service, e.g. an encoded
+/// secret key. `Debug` prints `[REDACTED]` so it can't leak into logs or
+/// error messages, and the value is zeroized when dropped.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Redacted<T: Zeroize>(pub T);
+
+impl<T: Zeroize> Deref for Redacted<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Zeroize> fmt::Debug for Redacted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+impl<T: Zeroize> Drop for Redacted<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<'de, T: Zeroize + Deserialize<'de>> Deserialize<'de> for Redacted<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Redacted)
+    }
+}
+
+impl<T: Zeroize + Serialize> Serialize for Redacted<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// 64-byte secret key, decoded into a `Keypair` during deserialization.
+/// `Serialize` writes base58 for clients sending it; the server never
+/// writes one out. Redacted in `Debug`, and the keypair zeroizes its
+/// secret half on drop.
+pub struct SecretKeyStr(pub Keypair);
+
+impl fmt::Debug for SecretKeyStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+impl Deref for SecretKeyStr {
+    type Target = Keypair;
+
+    fn deref(&self) -> &Keypair {
+        &self.0
+    }
+}
+
+/// Accepts the encodings `parse_secret_key` reads, or a JSON array of the
+/// 64 bytes as in a solana-cli keypair file.
+impl<'de> Deserialize<'de> for SecretKeyStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SecretVisitor;
+
+        impl<'de> de::Visitor<'de> for SecretVisitor {
+            type Value = SecretKeyStr;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a secret key string or byte array")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<SecretKeyStr, E> {
+                parse_secret_key(value).map(SecretKeyStr).map_err(E::custom)
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<SecretKeyStr, A::Error> {
+                secret_from_seq(seq).map(SecretKeyStr)
+            }
+        }
+
+        deserializer.deserialize_any(SecretVisitor)
+    }
+}
+
+fn secret_from_seq<'de, A: de::SeqAccess<'de>>(mut seq: A) -> Result<Keypair, A::Error> {
+    let mut bytes = Zeroizing::new(Vec::with_capacity(64));
+    while let Some(byte) = seq.next_element::<u8>()? {
+        bytes.push(byte);
+    }
+    parse_secret_bytes(&bytes).map_err(de::Error::custom)
+}
+
+impl Serialize for SecretKeyStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = Zeroizing::new(self.0.to_bytes());
+        let encoded = Zeroizing::new(bs58::encode(bytes.as_slice()).into_string());
+        serializer.serialize_str(&encoded)
+    }
+}
+
+/// A signer named in a request: either a raw secret key or
+/// `{"key_id": ...}` for a key already in the keystore.
+#[derive(Debug)]
+pub enum SignerRef {
+    Secret(SecretKeyStr),
+    KeyId(String),
+}
+
+impl<'de> Deserialize<'de> for SignerRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SignerVisitor;
+
+        impl<'de> de::Visitor<'de> for SignerVisitor {
+            type Value = SignerRef;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a secret key or an object with a key_id")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<SignerRef, E> {
+                parse_secret_key(value).map(|keypair| SignerRef::Secret(SecretKeyStr(keypair))).map_err(E::custom)
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<SignerRef, A::Error> {
+                secret_from_seq(seq).map(|keypair| SignerRef::Secret(SecretKeyStr(keypair)))
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<SignerRef, A::Error> {
+                #[derive(Deserialize)]
+                #[serde(deny_unknown_fields)]
+                struct KeyRef {
+                    key_id: String,
+                }
+                let KeyRef { key_id } = KeyRef::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                Ok(SignerRef::KeyId(key_id))
+            }
+        }
+
+        deserializer.deserialize_any(SignerVisitor)
+    }
+}
+
+impl Serialize for SignerRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            SignerRef::Secret(secret) => secret.serialize(serializer),
+            SignerRef::KeyId(key_id) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("key_id", key_id)?;
+                map.end()
+            }
+        }
+    }
+}
+
+/// Base64-encoded ed25519 signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignatureStr(pub Signature);
+
+impl Deref for SignatureStr {
+    type Target = Signature;
+
+    fn deref(&self) -> &Signature {
+        &self.0
+    }
+}
+
+impl<'de> Deserialize<'de> for SignatureStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_signature(&value).map(SignatureStr).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for SignatureStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&general_purpose::STANDARD.encode(self.0))
+    }
+}
+
+/// How `/message/sign` writes its signature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureEncoding {
+    #[default]
+    Base64,
+    /// As shown by explorers and solana-cli.
+    Base58,
+}
+
+impl SignatureEncoding {
+    pub fn encode(self, signature: &Signature) -> String {
+        match self {
+            SignatureEncoding::Base64 => general_purpose::STANDARD.encode(signature),
+            SignatureEncoding::Base58 => bs58::encode(signature).into_string(),
+        }
+    }
+}
+
+/// Base58 blockhash supplied by clients that manage their own recency.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HashStr(pub Hash);
+
+impl Deref for HashStr {
+    type Target = Hash;
+
+    fn deref(&self) -> &Hash {
+        &self.0
+    }
+}
+
+impl<'de> Deserialize<'de> for HashStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_hash(&value).map(HashStr).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for HashStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+/// PDA seeds in the `encoding:value,...` form accepted by `parse_seeds`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SeedList(pub Vec<Vec<u8>>);
+
+impl<'de> Deserialize<'de> for SeedList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_seeds(&value).map(SeedList).map_err(de::Error::custom)
+    }
+}
+
+/// Written back with every seed as `base64:`, which `parse_seeds` reads
+/// byte for byte.
+impl Serialize for SeedList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let seeds: Vec<_> = self.0.iter().map(|seed| format!("base64:{}", general_purpose::STANDARD.encode(seed))).collect();
+        serializer.serialize_str(&seeds.join(","))
+    }
+}
+
+/// Schema of the string newtypes above: a string whose format the server
+/// checks while deserializing.
+fn string_schema(description: &str) -> Schema {
+    json_schema!({ "type": "string", "description": description })
+}
+
+macro_rules! string_schemas {
+    ($($ty:ty => $name:literal, $description:literal;)*) => {$(
+        impl JsonSchema for $ty {
+            fn schema_name() -> Cow<'static, str> {
+                $name.into()
+            }
+
+            fn json_schema(_: &mut SchemaGenerator) -> Schema {
+                string_schema($description)
+            }
+        }
+    )*};
+}
+
+string_schemas! {
+    PubkeyStr => "Pubkey", "Base58 public key";
+    AddressRef => "Address", "Base58 public key, or `label:<name>` for an address-book entry";
+    SignatureStr => "Signature", "Base64 or base58 ed25519 signature";
+    HashStr => "Blockhash", "Base58 blockhash";
+    SeedList => "Seeds", "Comma-separated `encoding:value` seeds; encodings are utf8, hex, base58 and base64";
+}
+
+impl JsonSchema for SecretKeyStr {
+    fn schema_name() -> Cow<'static, str> {
+        "SecretKey".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "64-byte secret key as base58, hex or base64, or an array of its bytes",
+            "oneOf": [
+                { "type": "string" },
+                {
+                    "type": "array",
+                    "items": { "type": "integer", "minimum": 0, "maximum": 255 },
+                    "minItems": 64,
+                    "maxItems": 64,
+                },
+            ],
+        })
+    }
+}
+
+impl<T: Zeroize + JsonSchema> JsonSchema for Redacted<T> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        T::schema_name()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        T::json_schema(generator)
+    }
+}
+
+impl JsonSchema for SignerRef {
+    fn schema_name() -> Cow<'static, str> {
+        "SignerRef".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        let secret = generator.subschema_for::<SecretKeyStr>();
+        json_schema!({
+            "description": "A raw secret key, or the `key_id` of a key in the keystore",
+            "oneOf": [
+                secret,
+                {
+                    "type": "object",
+                    "properties": { "key_id": { "type": "string" } },
+                    "required": ["key_id"],
+                    "additionalProperties": false
+                }
+            ]
+        })
+    }
+}
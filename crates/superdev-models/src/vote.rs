@@ -0,0 +1,43 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{AddressRef, PubkeyStr};
+use crate::OutputFormat;
+
+/// Builds a vote program `Withdraw`. The account must stay rent exempt
+/// unless its whole balance is withdrawn, which closes it and is only
+/// allowed once it has stopped voting.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VoteWithdrawRequest {
+    pub vote_account: PubkeyStr,
+    pub authorized_withdrawer: PubkeyStr,
+    pub to: AddressRef,
+    pub lamports: u64,
+    #[serde(default)]
+    pub output_format: OutputFormat,
+}
+
+/// Builds a vote program `Authorize`, or `AuthorizeChecked` when `checked`
+/// is set, which also needs the new authority's signature so a mistyped key
+/// can't take over.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct VoteAuthorizeRequest {
+    pub vote_account: PubkeyStr,
+    /// The current authority of `authority_type`; the withdrawer may also
+    /// change the voter.
+    pub authority: PubkeyStr,
+    pub new_authority: PubkeyStr,
+    pub authority_type: VoteAuthorityType,
+    #[serde(default)]
+    pub checked: bool,
+    #[serde(default)]
+    pub output_format: OutputFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum VoteAuthorityType {
+    /// Takes effect from the next epoch.
+    Voter,
+    Withdrawer,
+}
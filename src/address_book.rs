@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::Utc;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::errors::{AppError, FieldError};
+use crate::models::address_book::AddressBookEntry;
+use crate::tenant;
+use crate::types::AddressRef;
+
+/// Longest label accepted.
+pub const MAX_LABEL_LEN: usize = 64;
+
+/// Labelled addresses per tenant, so send endpoints can take
+/// `label:treasury` instead of a pasted key. Kept in memory like templates
+/// and jobs.
+#[derive(Default)]
+pub struct AddressBook {
+    /// Keyed by tenant, then label.
+    entries: Mutex<HashMap<(String, String), AddressBookEntry>>,
+}
+
+pub fn valid_label(label: &str) -> bool {
+    (1..=MAX_LABEL_LEN).contains(&label.len())
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+fn not_found(label: &str) -> AppError {
+    AppError::NotFound(format!("Address-book entry {label}"))
+}
+
+impl AddressBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, label: String, address: Pubkey, note: Option<String>) -> Result<AddressBookEntry, AppError> {
+        let mut entries = self.entries.lock().unwrap();
+        let key = (tenant::current(), label);
+        if entries.contains_key(&key) {
+            return Err(AppError::Conflict(format!("label {} is already in use", key.1)));
+        }
+        let now = Utc::now();
+        let entry = AddressBookEntry {
+            label: key.1.clone(),
+            address: address.to_string(),
+            note,
+            created_at: now,
+            updated_at: now,
+        };
+        entries.insert(key, entry.clone());
+        Ok(entry)
+    }
+
+    /// The current tenant's entries, by label.
+    pub fn list(&self) -> Vec<AddressBookEntry> {
+        let entries = self.entries.lock().unwrap();
+        let tenant = tenant::current();
+        let mut list: Vec<AddressBookEntry> =
+            entries.iter().filter(|((owner, _), _)| *owner == tenant).map(|(_, entry)| entry.clone()).collect();
+        list.sort_by(|a, b| a.label.cmp(&b.label));
+        list
+    }
+
+    pub fn get(&self, label: &str) -> Result<AddressBookEntry, AppError> {
+        let entries = self.entries.lock().unwrap();
+        entries.get(&(tenant::current(), label.to_string())).cloned().ok_or_else(|| not_found(label))
+    }
+
+    pub fn update(&self, label: &str, address: Pubkey, note: Option<String>) -> Result<AddressBookEntry, AppError> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get_mut(&(tenant::current(), label.to_string())).ok_or_else(|| not_found(label))?;
+        entry.address = address.to_string();
+        entry.note = note;
+        entry.updated_at = Utc::now();
+        Ok(entry.clone())
+    }
+
+    pub fn remove(&self, label: &str) -> Result<AddressBookEntry, AppError> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.remove(&(tenant::current(), label.to_string())).ok_or_else(|| not_found(label))
+    }
+
+    /// The key `address` stands for, looking labels up in the current
+    /// tenant's book. `field` names the request field in errors.
+    pub fn resolve(&self, address: &AddressRef, field: &str) -> Result<Pubkey, AppError> {
+        let label = match address {
+            AddressRef::Pubkey(pubkey) => return Ok(*pubkey),
+            AddressRef::Label(label) => label,
+        };
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(&(tenant::current(), label.clone()))
+            .and_then(|entry| entry.address.parse().ok())
+            .ok_or_else(|| AppError::Field { field: field.to_string(), error: FieldError::UnknownLabel })
+    }
+}
@@ -0,0 +1,69 @@
+use rust_decimal::Decimal;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use crate::errors::FieldError;
+
+/// Lamports per SOL expressed as decimal places.
+pub const SOL_DECIMALS: u8 = LAMPORTS_PER_SOL.ilog10() as u8;
+/// Most decimal places a conversion supports; `Decimal`'s maximum scale.
+pub const MAX_DECIMALS: u8 = Decimal::MAX_SCALE as u8;
+
+/// UI amounts are plain non-negative decimals: no sign, exponent or
+/// separators. Stricter than `Decimal`'s own parser on purpose.
+pub fn check_format(amount: &str) -> Result<(), FieldError> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+
+    if whole.is_empty() || !digits(whole) || !digits(fraction) || (amount.contains('.') && fraction.is_empty()) {
+        return Err(FieldError::DecimalAmount);
+    }
+    Ok(())
+}
+
+/// Raw base units to a normalized UI amount, e.g. `(1_500_000_000, 9)` is `1.5`.
+pub fn to_ui(raw: u64, decimals: u8) -> Result<Decimal, FieldError> {
+    check_decimals(decimals)?;
+    Ok(Decimal::from_i128_with_scale(raw.into(), decimals.into()).normalize())
+}
+
+/// Signed difference of raw amounts, e.g. a balance change, as a UI amount.
+pub fn change_to_ui(change: i128, decimals: u8) -> Result<Decimal, FieldError> {
+    check_decimals(decimals)?;
+    Decimal::try_from_i128_with_scale(change, decimals.into())
+        .map(|value| value.normalize())
+        .map_err(|_| FieldError::AmountTooLarge)
+}
+
+/// UI amount to raw base units, exactly: input finer than `decimals` is an
+/// error rather than silently rounded.
+pub fn from_ui(amount: &str, decimals: u8) -> Result<u64, FieldError> {
+    check_decimals(decimals)?;
+    check_format(amount)?;
+
+    let fraction = amount.split_once('.').map_or("", |(_, fraction)| fraction.trim_end_matches('0'));
+    if fraction.len() > usize::from(decimals) {
+        return Err(FieldError::AmountPrecision(decimals));
+    }
+
+    let value = Decimal::from_str_exact(amount).map_err(|_| FieldError::AmountTooLarge)?.normalize();
+    let factor = 10i128.pow(u32::from(decimals) - value.scale());
+    value
+        .mantissa()
+        .checked_mul(factor)
+        .and_then(|raw| u64::try_from(raw).ok())
+        .ok_or(FieldError::AmountTooLarge)
+}
+
+pub fn lamports_to_sol(lamports: u64) -> Decimal {
+    to_ui(lamports, SOL_DECIMALS).expect("SOL decimals are in range")
+}
+
+pub fn sol_to_lamports(sol: &str) -> Result<u64, FieldError> {
+    from_ui(sol, SOL_DECIMALS)
+}
+
+fn check_decimals(decimals: u8) -> Result<(), FieldError> {
+    if decimals > MAX_DECIMALS {
+        return Err(FieldError::DecimalsOutOfRange(MAX_DECIMALS));
+    }
+    Ok(())
+}
@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use serde::Deserialize;
+use serde_json::Value;
+use solana_sdk::{hash::hash, pubkey::Pubkey};
+use crate::borsh::{same_name, BorshError, Field, Fields, Schema, Type, TypeDecl, TypeDef};
+use crate::errors::AppError;
+use crate::utils::parse_pubkey;
+
+/// An Anchor IDL reduced to what we need to build instructions. Both the
+/// legacy format (Anchor < 0.30, camelCase names, no discriminators) and the
+/// 0.30+ spec format are accepted.
+#[derive(Debug)]
+pub struct Idl {
+    pub name: String,
+    pub address: Option<Pubkey>,
+    pub instructions: Vec<IdlInstruction>,
+    pub events: Vec<IdlEvent>,
+    pub schema: Schema,
+}
+
+#[derive(Debug)]
+pub struct IdlInstruction {
+    pub name: String,
+    pub discriminator: [u8; 8],
+    /// Nested account groups flattened in declaration order.
+    pub accounts: Vec<IdlAccount>,
+    pub args: Vec<(String, Type)>,
+}
+
+#[derive(Debug)]
+pub struct IdlEvent {
+    pub name: String,
+    pub discriminator: [u8; 8],
+    pub ty: Type,
+}
+
+#[derive(Debug)]
+pub struct IdlAccount {
+    /// Group names followed by the account name, e.g. `["vault", "authority"]`.
+    pub path: Vec<String>,
+    pub writable: bool,
+    pub signer: bool,
+    pub optional: bool,
+    /// Fixed address declared in the IDL, used when the caller omits it.
+    pub address: Option<Pubkey>,
+}
+
+impl Idl {
+    pub fn parse(value: Value) -> Result<Self, AppError> {
+        let raw: RawIdl = serde_path_to_error::deserialize(value).map_err(|err| AppError::InvalidField {
+            field: format!("idl.{}", err.path()),
+            message: err.into_inner().to_string(),
+        })?;
+        let invalid = |message: String| AppError::InvalidField { field: "idl".to_string(), message };
+
+        let name = raw
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.name.clone())
+            .or(raw.name)
+            .unwrap_or_default();
+        let address = raw
+            .address
+            .or_else(|| raw.metadata.and_then(|metadata| metadata.address))
+            .map(|address| parse_pubkey(&address).map_err(|_| invalid(format!("`{address}` is not a valid program id"))))
+            .transpose()?;
+
+        let mut schema = Schema::from_decls(raw.types).map_err(invalid)?;
+
+        let instructions = raw
+            .instructions
+            .into_iter()
+            .map(|ix| {
+                let mut accounts = Vec::new();
+                flatten(ix.accounts, &[], &mut accounts)?;
+                Ok(IdlInstruction {
+                    discriminator: ix.discriminator.unwrap_or_else(|| sighash("global", &snake_case(&ix.name))),
+                    name: ix.name,
+                    accounts,
+                    args: ix.args.into_iter().map(|arg| (arg.name, arg.ty)).collect(),
+                })
+            })
+            .collect::<Result<_, AppError>>()?;
+
+        // Legacy IDLs declare event fields inline; the spec format points at
+        // a type of the same name.
+        let mut events = Vec::with_capacity(raw.events.len());
+        for event in raw.events {
+            if let Some(fields) = event.fields {
+                let fields = Fields::Named(fields.into_iter().map(|field| (field.name, field.ty)).collect());
+                schema.types.entry(event.name.clone()).or_insert(TypeDef::Struct(fields));
+            } else if !schema.types.contains_key(&event.name) {
+                return Err(invalid(format!("event `{}` has no type definition", event.name)));
+            }
+            events.push(IdlEvent {
+                discriminator: event.discriminator.unwrap_or_else(|| sighash("event", &event.name)),
+                ty: Type::Defined(event.name.clone()),
+                name: event.name,
+            });
+        }
+
+        Ok(Self { name, address, instructions, events, schema })
+    }
+
+    pub fn instruction(&self, method: &str) -> Option<&IdlInstruction> {
+        self.instructions.iter().find(|ix| same_name(&ix.name, method))
+    }
+
+    /// Decodes `data` as one of this program's events, or `None` if the
+    /// discriminator matches none of them.
+    pub fn decode_event(&self, data: &[u8], path: &str) -> Option<Result<(&IdlEvent, Value), BorshError>> {
+        let event = self.events.iter().find(|event| data.starts_with(&event.discriminator))?;
+        let mut body = &data[8..];
+        Some(self.schema.decode(&event.ty, &mut body, path).map(|value| (event, value)))
+    }
+}
+
+/// Prefix of the log line `sol_log_data` writes, which is how `emit!` publishes events.
+pub const PROGRAM_DATA: &str = "Program data: ";
+
+/// The `Program data:` payloads logged while `program_id` was the executing
+/// program, with their line index. CPIs are tracked through the
+/// invoke/success/failed lines so data logged by callees isn't attributed
+/// to the caller.
+pub fn program_data<'a>(logs: &'a [String], program_id: &Pubkey) -> Vec<(usize, &'a str)> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut found = Vec::new();
+
+    for (index, line) in logs.iter().enumerate() {
+        if let Some(data) = line.strip_prefix(PROGRAM_DATA) {
+            if stack.last() == Some(&program_id.as_str()) {
+                found.push((index, data));
+            }
+            continue;
+        }
+
+        // `Program log:` and `Program return:` lines share the prefix but
+        // never name a program.
+        let mut words = line.split_whitespace();
+        if let (Some("Program"), Some(program), Some(action)) = (words.next(), words.next(), words.next())
+            && !program.ends_with(':')
+        {
+            match action {
+                "invoke" => stack.push(program),
+                "success" | "failed:" => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    found
+}
+
+/// Anchor's discriminator: the first 8 bytes of `sha256("<namespace>:<name>")`.
+pub fn sighash(namespace: &str, name: &str) -> [u8; 8] {
+    let digest = hash(format!("{namespace}:{name}").as_bytes());
+    digest.to_bytes()[..8].try_into().unwrap()
+}
+
+/// Same conversion Anchor applies to legacy camelCase method names before
+/// hashing: `initializeMint` and `createNFTMint` become `initialize_mint`
+/// and `create_nft_mint`.
+pub fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+fn flatten(items: Vec<RawAccountItem>, prefix: &[String], out: &mut Vec<IdlAccount>) -> Result<(), AppError> {
+    for item in items {
+        match item {
+            RawAccountItem::Group { name, accounts } => {
+                let mut path = prefix.to_vec();
+                path.push(name);
+                flatten(accounts, &path, out)?;
+            }
+            RawAccountItem::Single(account) => {
+                let mut path = prefix.to_vec();
+                path.push(account.name);
+                let address = account
+                    .address
+                    .map(|address| {
+                        parse_pubkey(&address).map_err(|_| AppError::InvalidField {
+                            field: "idl".to_string(),
+                            message: format!("account `{}` has invalid address `{address}`", path.join(".")),
+                        })
+                    })
+                    .transpose()?;
+                out.push(IdlAccount {
+                    path,
+                    writable: account.writable,
+                    signer: account.signer,
+                    optional: account.optional,
+                    address,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Registered IDLs, keyed by program id. Held in memory only; clients
+/// re-register after a restart.
+#[derive(Default)]
+pub struct IdlRegistry {
+    idls: RwLock<HashMap<Pubkey, Arc<Idl>>>,
+}
+
+impl IdlRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `idl`, replacing any previous IDL for the program.
+    pub fn insert(&self, program_id: Pubkey, idl: Idl) -> Arc<Idl> {
+        let idl = Arc::new(idl);
+        self.idls.write().unwrap().insert(program_id, idl.clone());
+        idl
+    }
+
+    pub fn get(&self, program_id: &Pubkey) -> Option<Arc<Idl>> {
+        self.idls.read().unwrap().get(program_id).cloned()
+    }
+}
+
+#[derive(Deserialize)]
+struct RawIdl {
+    #[serde(default)]
+    address: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    metadata: Option<RawMetadata>,
+    instructions: Vec<RawInstruction>,
+    #[serde(default)]
+    types: Vec<TypeDecl>,
+    #[serde(default)]
+    events: Vec<RawEvent>,
+}
+
+#[derive(Deserialize)]
+struct RawMetadata {
+    #[serde(default)]
+    name: Option<String>,
+    /// Where legacy IDLs put the deployed program id.
+    #[serde(default)]
+    address: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawInstruction {
+    name: String,
+    #[serde(default)]
+    discriminator: Option<[u8; 8]>,
+    #[serde(default)]
+    accounts: Vec<RawAccountItem>,
+    #[serde(default)]
+    args: Vec<Field>,
+}
+
+#[derive(Deserialize)]
+struct RawEvent {
+    name: String,
+    #[serde(default)]
+    discriminator: Option<[u8; 8]>,
+    #[serde(default)]
+    fields: Option<Vec<Field>>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawAccountItem {
+    Group { name: String, accounts: Vec<RawAccountItem> },
+    Single(RawAccount),
+}
+
+#[derive(Deserialize)]
+struct RawAccount {
+    name: String,
+    #[serde(default, alias = "isMut")]
+    writable: bool,
+    #[serde(default, alias = "isSigner")]
+    signer: bool,
+    #[serde(default, alias = "isOptional")]
+    optional: bool,
+    #[serde(default)]
+    address: Option<String>,
+}
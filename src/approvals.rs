@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{TimeDelta, Utc};
+use solana_sdk::signature::Signature;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::jobs::Template;
+use crate::keystore::Keystore;
+use crate::models::transfers::{Proposal, ProposalStatus};
+use crate::tenant;
+
+/// Transfers waiting for enough distinct identities to approve them. Their
+/// signers stay in the keystore until the proposal is submitted or expires.
+/// Proposals are visible to, and approved by, their proposer's tenant only,
+/// and are dropped once past their expiry.
+pub struct Approvals {
+    keystore: Arc<Keystore>,
+    proposals: Mutex<HashMap<String, Entry>>,
+}
+
+struct Entry {
+    tenant: String,
+    proposal: Proposal,
+    template: Template,
+}
+
+impl Approvals {
+    pub fn new(keystore: Arc<Keystore>) -> Self {
+        Self { keystore, proposals: Mutex::new(HashMap::new()) }
+    }
+
+    /// Stores a new proposal. `Submitting` ones are being sent by the caller
+    /// straight away and must be passed to `finish`.
+    pub fn insert(
+        &self,
+        proposer: String,
+        template: Template,
+        required: usize,
+        ttl_secs: u64,
+        status: ProposalStatus,
+    ) -> Proposal {
+        let proposal = Proposal {
+            id: Uuid::new_v4().to_string(),
+            status,
+            proposer,
+            fee_payer: self.keystore.pubkey(&template.key_ids[0]).map(|key| key.to_string()).unwrap_or_default(),
+            approvals: Vec::new(),
+            required,
+            expires_at: Utc::now() + TimeDelta::seconds(i64::try_from(ttl_secs).unwrap_or(i64::MAX)),
+            signature: None,
+            error: None,
+        };
+        let mut proposals = self.proposals.lock().unwrap();
+        self.sweep_locked(&mut proposals);
+        let entry = Entry { tenant: tenant::current(), proposal: proposal.clone(), template };
+        proposals.insert(proposal.id.clone(), entry);
+        proposal
+    }
+
+    pub fn get(&self, id: &str) -> Option<Proposal> {
+        let mut proposals = self.proposals.lock().unwrap();
+        self.sweep_locked(&mut proposals);
+        proposals.get(id).filter(|entry| entry.tenant == tenant::current()).map(|entry| entry.proposal.clone())
+    }
+
+    /// Records `approver`'s approval. Once enough have been collected the
+    /// proposal moves to `Submitting` and its template is returned for the
+    /// caller to send, exactly once.
+    pub fn approve(&self, id: &str, approver: &str) -> Result<(Proposal, Option<Template>), AppError> {
+        let mut proposals = self.proposals.lock().unwrap();
+        self.sweep_locked(&mut proposals);
+        let entry = proposals
+            .get_mut(id)
+            .filter(|entry| entry.tenant == tenant::current())
+            .ok_or_else(|| AppError::NotFound(format!("Proposal {id}")))?;
+
+        let proposal = &mut entry.proposal;
+        if proposal.status != ProposalStatus::Pending {
+            return Err(AppError::Conflict(format!("Proposal {id} is no longer pending")));
+        }
+        if proposal.proposer == approver {
+            return Err(AppError::Conflict("Proposers can't approve their own proposals".to_string()));
+        }
+        if proposal.approvals.iter().any(|existing| existing == approver) {
+            return Err(AppError::Conflict(format!("{approver} already approved proposal {id}")));
+        }
+
+        proposal.approvals.push(approver.to_string());
+        if proposal.approvals.len() < proposal.required {
+            return Ok((proposal.clone(), None));
+        }
+        proposal.status = ProposalStatus::Submitting;
+        Ok((proposal.clone(), Some(entry.template.clone())))
+    }
+
+    /// Records the outcome of sending an approved proposal and drops its signers.
+    pub fn finish(&self, id: &str, outcome: Result<Signature, &AppError>) -> Option<Proposal> {
+        let mut proposals = self.proposals.lock().unwrap();
+        let entry = proposals.get_mut(id)?;
+        match outcome {
+            Ok(signature) => {
+                entry.proposal.status = ProposalStatus::Submitted;
+                entry.proposal.signature = Some(signature.to_string());
+            }
+            Err(err) => {
+                entry.proposal.status = ProposalStatus::Failed;
+                entry.proposal.error = Some(err.to_string());
+            }
+        }
+        self.forget(&entry.template);
+        Some(entry.proposal.clone())
+    }
+
+    /// Drops proposals past their expiry, with the signers of those still
+    /// pending. Ones being submitted stay until `finish`.
+    pub fn sweep(&self) {
+        self.sweep_locked(&mut self.proposals.lock().unwrap());
+    }
+
+    fn sweep_locked(&self, proposals: &mut HashMap<String, Entry>) {
+        let now = Utc::now();
+        proposals.retain(|_, entry| {
+            let keep = now < entry.proposal.expires_at || entry.proposal.status == ProposalStatus::Submitting;
+            if !keep && entry.proposal.status == ProposalStatus::Pending {
+                self.forget(&entry.template);
+            }
+            keep
+        });
+    }
+
+    fn forget(&self, template: &Template) {
+        for key_id in &template.key_ids {
+            self.keystore.remove(key_id);
+        }
+    }
+}
+
+/// Sweeps `approvals` every `period`, so expired signers leave the keystore
+/// even when no request touches the proposals.
+pub fn sweep_every(approvals: Arc<Approvals>, period: Duration) {
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(period);
+        loop {
+            ticks.tick().await;
+            approvals.sweep();
+        }
+    });
+}
@@ -0,0 +1,135 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::{Request, State},
+    http::{header, Method},
+    middleware::Next,
+    response::Response,
+};
+use chrono::Utc;
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::errors::AppError;
+use crate::extract::Identity;
+use crate::state::AppState;
+use crate::tenant;
+
+pub use superdev_models::audit::AuditEntry;
+
+/// Entries kept per tenant before the oldest are dropped.
+pub const AUDIT_CAPACITY: usize = 1_000;
+
+/// Largest request body copied into audit entries.
+pub const AUDIT_BODY_LIMIT: usize = 64 * 1024;
+
+/// Request fields that carry secret keys, at any depth. Every field typed
+/// `SignerRef` or `SecretKeyStr` belongs here, as both accept raw secrets.
+const SECRET_FIELDS: [&str; 4] = ["secret", "signers", "passphrase", "payer"];
+
+tokio::task_local! {
+    /// What `AuditLog::record` knows of the request being handled.
+    static REQUEST: Captured;
+}
+
+struct Captured {
+    identity: Option<String>,
+    body: Option<Value>,
+}
+
+/// Most recent policy decisions, in memory only, kept apart per tenant so
+/// one tenant's traffic can't evict or reveal another's history.
+#[derive(Default)]
+pub struct AuditLog {
+    entries: Mutex<HashMap<String, VecDeque<AuditEntry>>>,
+}
+
+impl AuditLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the outcome of `action` to the current tenant's log, evicting
+    /// its oldest entry when full.
+    pub fn record(&self, action: &'static str, signers: &[Pubkey], outcome: Result<(), &AppError>) {
+        let (identity, request) = captured();
+        let entry = AuditEntry {
+            time: Utc::now(),
+            action: action.to_string(),
+            signers: signers.iter().map(ToString::to_string).collect(),
+            identity,
+            allowed: outcome.is_ok(),
+            code: outcome.err().map(|err| err.code().to_string()),
+            message: outcome.err().map(ToString::to_string),
+            request,
+        };
+        let mut entries = self.entries.lock().unwrap();
+        let entries = entries.entry(tenant::current()).or_default();
+        if entries.len() == AUDIT_CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// `tenant`'s entries, oldest first.
+    pub fn entries(&self, tenant: &str) -> Vec<AuditEntry> {
+        let entries = self.entries.lock().unwrap();
+        entries.get(tenant).map(|entries| entries.iter().cloned().collect()).unwrap_or_default()
+    }
+}
+
+/// Middleware that notes the caller's identity and keeps a scrubbed copy of
+/// JSON request bodies for the audit entries recorded while the request is
+/// handled. Bodies of unknown or excessive length pass through uncaptured.
+pub async fn capture(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let (parts, body) = request.into_parts();
+    let identity = Identity::resolve(&parts, &state).map(|Identity(name)| name);
+    let request = Request::from_parts(parts, body);
+
+    let is_json = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    let small = request.body().size_hint().upper().is_some_and(|length| length <= AUDIT_BODY_LIMIT as u64);
+    if request.method() != Method::POST || !is_json || !small {
+        return REQUEST.scope(Captured { identity, body: None }, next.run(request)).await;
+    }
+
+    let (parts, body) = request.into_parts();
+    let Ok(bytes) = to_bytes(body, AUDIT_BODY_LIMIT).await else {
+        let request = Request::from_parts(parts, Body::empty());
+        return REQUEST.scope(Captured { identity, body: None }, next.run(request)).await;
+    };
+    let request = Request::from_parts(parts, Body::from(bytes.clone()));
+    let body = serde_json::from_slice::<Value>(&bytes).ok().map(|mut body| {
+        scrub(&mut body);
+        body
+    });
+    REQUEST.scope(Captured { identity, body }, next.run(request)).await
+}
+
+/// Identity and scrubbed body of the request being handled, as `capture`
+/// noted them.
+pub(crate) fn captured() -> (Option<String>, Option<Value>) {
+    REQUEST.try_with(|request| (request.identity.clone(), request.body.clone())).unwrap_or_default()
+}
+
+/// Replaces every value under a secret-bearing key with `[REDACTED]`.
+pub fn scrub(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields.iter_mut() {
+                if SECRET_FIELDS.contains(&key.as_str()) {
+                    *field = Value::from("[REDACTED]");
+                } else {
+                    scrub(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(scrub),
+        _ => {}
+    }
+}
@@ -0,0 +1,153 @@
+//! Load generator for a running server, for capacity planning: keeps
+//! `--concurrency` requests in flight for `--seconds` and prints throughput
+//! and latency percentiles.
+//!
+//! ```text
+//! cargo run --release --bin loadgen -- http://localhost:3000 --concurrency 64 --seconds 30 --endpoint sign
+//! ```
+//!
+//! Endpoints: `sign` (`/message/sign` with an inline secret, so the server
+//! must be in development mode), `verify`, `keypair` and `send-sol`.
+
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+use reqwest::header::CONTENT_TYPE;
+use tokio::task::JoinSet;
+
+struct Options {
+    url: String,
+    concurrency: usize,
+    duration: Duration,
+    endpoint: String,
+}
+
+fn options() -> Result<Options, String> {
+    let mut options = Options {
+        url: "http://localhost:3000".to_string(),
+        concurrency: 32,
+        duration: Duration::from_secs(10),
+        endpoint: "sign".to_string(),
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{name} needs a value"));
+        match arg.as_str() {
+            "--concurrency" => {
+                options.concurrency = value(&arg)?.parse().map_err(|_| "--concurrency must be a number")?;
+            }
+            "--seconds" => {
+                let seconds = value(&arg)?.parse().map_err(|_| "--seconds must be a number")?;
+                options.duration = Duration::from_secs(seconds);
+            }
+            "--endpoint" => options.endpoint = value(&arg)?,
+            url if !url.starts_with("--") => options.url = url.trim_end_matches('/').to_string(),
+            other => return Err(format!("unknown option {other}")),
+        }
+    }
+    if options.concurrency == 0 {
+        return Err("--concurrency must be at least 1".to_string());
+    }
+    Ok(options)
+}
+
+/// Path and body of each request to `endpoint`.
+fn request(endpoint: &str) -> Result<(&'static str, Value), String> {
+    let keypair = Keypair::new();
+    let message = "Hello, Solana!";
+    Ok(match endpoint {
+        "sign" => ("/message/sign", json!({ "message": message, "secret": keypair.to_base58_string() })),
+        "verify" => {
+            let signature = general_purpose::STANDARD.encode(keypair.sign_message(message.as_bytes()).as_ref());
+            let pubkey = keypair.pubkey().to_string();
+            ("/message/verify", json!({ "message": message, "signature": signature, "pubkey": pubkey }))
+        }
+        "keypair" => ("/keypair", json!({})),
+        "send-sol" => {
+            let to = Pubkey::new_unique().to_string();
+            ("/send/sol", json!({ "from": keypair.pubkey().to_string(), "to": to, "lamports": 1 }))
+        }
+        other => return Err(format!("unknown endpoint {other}")),
+    })
+}
+
+#[derive(Default)]
+struct Tally {
+    latencies: Vec<Duration>,
+    errors: usize,
+}
+
+async fn worker(client: reqwest::Client, url: Arc<str>, body: Arc<str>, until: Instant) -> Tally {
+    let mut tally = Tally::default();
+    while Instant::now() < until {
+        let started = Instant::now();
+        let request = client.post(&*url).header(CONTENT_TYPE, "application/json").body(body.to_string());
+        let response = request.send().await;
+        match response {
+            Ok(response) if response.status().is_success() => {
+                // Read the body so the connection is reused.
+                let _ = response.bytes().await;
+                tally.latencies.push(started.elapsed());
+            }
+            _ => tally.errors += 1,
+        }
+    }
+    tally
+}
+
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let index = ((sorted.len() as f64 * p).ceil() as usize).clamp(1, sorted.len()) - 1;
+    sorted[index]
+}
+
+async fn run() -> Result<(), String> {
+    let options = options()?;
+    let (path, body) = request(&options.endpoint)?;
+    let url: Arc<str> = format!("{}{path}", options.url).into();
+    let body: Arc<str> = body.to_string().into();
+    let client = reqwest::Client::new();
+
+    let started = Instant::now();
+    let until = started + options.duration;
+    let mut workers = JoinSet::new();
+    for _ in 0..options.concurrency {
+        workers.spawn(worker(client.clone(), url.clone(), body.clone(), until));
+    }
+    let mut total = Tally::default();
+    while let Some(tally) = workers.join_next().await {
+        let tally = tally.expect("worker panicked");
+        total.latencies.extend(tally.latencies);
+        total.errors += tally.errors;
+    }
+    let elapsed = started.elapsed().as_secs_f64();
+
+    let latencies = &mut total.latencies;
+    println!("{url}: {} concurrent for {elapsed:.1}s", options.concurrency);
+    println!("ok {}, errors {}, {:.0} req/s", latencies.len(), total.errors, latencies.len() as f64 / elapsed);
+    if latencies.is_empty() {
+        return Err("no request succeeded".to_string());
+    }
+    latencies.sort();
+    for (name, p) in [("p50", 0.5), ("p90", 0.9), ("p99", 0.99), ("max", 1.0)] {
+        println!("{name} {:.2}ms", percentile(latencies, p).as_secs_f64() * 1000.0);
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("loadgen: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
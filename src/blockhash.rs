@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+use crate::rpc::{LatestBlockhash, RpcError, SolanaRpc};
+
+/// Caches `getLatestBlockhash` for a short TTL so bursts of transaction builds
+/// share one RPC round trip. The lock is held across the refresh, so
+/// concurrent callers wait for a single request instead of stampeding.
+pub struct BlockhashCache {
+    ttl: Duration,
+    cached: Mutex<Option<(Instant, LatestBlockhash)>>,
+}
+
+impl BlockhashCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, cached: Mutex::new(None) }
+    }
+
+    pub async fn get(&self, rpc: &dyn SolanaRpc) -> Result<LatestBlockhash, RpcError> {
+        let mut cached = self.cached.lock().await;
+        if let Some((fetched_at, blockhash)) = *cached
+            && fetched_at.elapsed() < self.ttl
+        {
+            return Ok(blockhash);
+        }
+
+        let blockhash = rpc.get_latest_blockhash().await?;
+        *cached = Some((Instant::now(), blockhash));
+        Ok(blockhash)
+    }
+
+    /// How long ago the cached blockhash was fetched, if one is cached.
+    pub async fn age(&self) -> Option<Duration> {
+        self.cached.lock().await.map(|(fetched_at, _)| fetched_at.elapsed())
+    }
+
+    /// Drops the cached value, e.g. after the RPC backend is swapped.
+    pub async fn invalidate(&self) {
+        *self.cached.lock().await = None;
+    }
+}
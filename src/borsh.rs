@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use base64::{Engine as _, engine::general_purpose};
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+use crate::errors::AppError;
+
+pub use superdev_models::idl::{Field, Fields, Type, TypeDecl, TypeDef};
+
+/// Named type definitions that `Type::Defined` refers to.
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    pub types: HashMap<String, TypeDef>,
+}
+
+/// Where in the value an encoding failure happened, e.g. `args.params.amount`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BorshError {
+    pub path: String,
+    pub message: String,
+}
+
+impl From<BorshError> for AppError {
+    fn from(error: BorshError) -> Self {
+        AppError::InvalidField { field: error.path, message: error.message }
+    }
+}
+
+impl Schema {
+    pub fn from_decls(decls: Vec<TypeDecl>) -> Result<Self, String> {
+        let mut schema = Schema::default();
+        for decl in decls {
+            if !decl.generics.is_empty() {
+                return Err(format!("generic type `{}` is not supported", decl.name));
+            }
+            if schema.types.insert(decl.name.clone(), decl.def).is_some() {
+                return Err(format!("type `{}` is defined twice", decl.name));
+            }
+        }
+        Ok(schema)
+    }
+
+    /// Appends the Borsh encoding of `value` as `ty` to `out`.
+    pub fn encode(&self, ty: &Type, value: &Value, path: &str, out: &mut Vec<u8>) -> Result<(), BorshError> {
+        self.write(ty, value, path, out, 0)
+    }
+
+    fn write(&self, ty: &Type, value: &Value, path: &str, out: &mut Vec<u8>, depth: usize) -> Result<(), BorshError> {
+        check_depth(depth, path)?;
+        let fail = |message: String| BorshError { path: path.to_string(), message };
+
+        match ty {
+            Type::Bool => out.push(value.as_bool().ok_or_else(|| fail("expected a boolean".into()))? as u8),
+            Type::U8 => out.extend(integer::<u8>(value).ok_or_else(|| fail("expected a u8".into()))?.to_le_bytes()),
+            Type::I8 => out.extend(integer::<i8>(value).ok_or_else(|| fail("expected an i8".into()))?.to_le_bytes()),
+            Type::U16 => out.extend(integer::<u16>(value).ok_or_else(|| fail("expected a u16".into()))?.to_le_bytes()),
+            Type::I16 => out.extend(integer::<i16>(value).ok_or_else(|| fail("expected an i16".into()))?.to_le_bytes()),
+            Type::U32 => out.extend(integer::<u32>(value).ok_or_else(|| fail("expected a u32".into()))?.to_le_bytes()),
+            Type::I32 => out.extend(integer::<i32>(value).ok_or_else(|| fail("expected an i32".into()))?.to_le_bytes()),
+            Type::U64 => out.extend(integer::<u64>(value).ok_or_else(|| fail("expected a u64".into()))?.to_le_bytes()),
+            Type::I64 => out.extend(integer::<i64>(value).ok_or_else(|| fail("expected an i64".into()))?.to_le_bytes()),
+            Type::U128 => out.extend(integer::<u128>(value).ok_or_else(|| fail("expected a u128".into()))?.to_le_bytes()),
+            Type::I128 => out.extend(integer::<i128>(value).ok_or_else(|| fail("expected an i128".into()))?.to_le_bytes()),
+            Type::F32 => out.extend((float(value).ok_or_else(|| fail("expected a number".into()))? as f32).to_le_bytes()),
+            Type::F64 => out.extend(float(value).ok_or_else(|| fail("expected a number".into()))?.to_le_bytes()),
+            Type::String => {
+                let string = value.as_str().ok_or_else(|| fail("expected a string".into()))?;
+                write_len(string.len(), out);
+                out.extend(string.as_bytes());
+            }
+            Type::Bytes => {
+                let bytes = byte_string(value).ok_or_else(|| fail("expected base64 or an array of bytes".into()))?;
+                write_len(bytes.len(), out);
+                out.extend(bytes);
+            }
+            Type::Pubkey => {
+                let key = value
+                    .as_str()
+                    .and_then(|key| Pubkey::from_str(key).ok())
+                    .ok_or_else(|| fail("expected a base58 public key".into()))?;
+                out.extend(key.to_bytes());
+            }
+            Type::Vec(element) => {
+                let items = value.as_array().ok_or_else(|| fail("expected an array".into()))?;
+                write_len(items.len(), out);
+                for (i, item) in items.iter().enumerate() {
+                    self.write(element, item, &format!("{path}[{i}]"), out, depth + 1)?;
+                }
+            }
+            Type::Array(element, len) => {
+                let items = value.as_array().ok_or_else(|| fail(format!("expected an array of {len}")))?;
+                if items.len() != *len {
+                    return Err(fail(format!("expected {len} items, got {}", items.len())));
+                }
+                for (i, item) in items.iter().enumerate() {
+                    self.write(element, item, &format!("{path}[{i}]"), out, depth + 1)?;
+                }
+            }
+            Type::Option(inner) | Type::COption(inner) => {
+                let wide = matches!(ty, Type::COption(_));
+                let tag = |present: u8| if wide { vec![present, 0, 0, 0] } else { vec![present] };
+                if value.is_null() {
+                    out.extend(tag(0));
+                } else {
+                    out.extend(tag(1));
+                    self.write(inner, value, path, out, depth + 1)?;
+                }
+            }
+            Type::Defined(name) => match self.types.get(name) {
+                Some(TypeDef::Struct(fields)) => self.write_fields(fields, value, path, out, depth + 1)?,
+                Some(TypeDef::Alias(inner)) => self.write(inner, value, path, out, depth + 1)?,
+                Some(TypeDef::Enum(variants)) => {
+                    // Unit variants may be given as a bare string; others as
+                    // a single-key object `{"Variant": fields}`.
+                    let (variant, fields_value) = match value {
+                        Value::String(variant) => (variant.as_str(), &Value::Null),
+                        Value::Object(map) if map.len() == 1 => {
+                            let (variant, fields) = map.iter().next().unwrap();
+                            (variant.as_str(), fields)
+                        }
+                        _ => return Err(fail(format!("expected a `{name}` variant"))),
+                    };
+                    let index = variants
+                        .iter()
+                        .position(|(candidate, _)| same_name(candidate, variant))
+                        .ok_or_else(|| fail(format!("`{name}` has no variant `{variant}`")))?;
+                    out.push(index as u8);
+                    self.write_fields(&variants[index].1, fields_value, &format!("{path}.{variant}"), out, depth + 1)?;
+                }
+                None => return Err(fail(format!("unknown type `{name}`"))),
+            },
+        }
+        Ok(())
+    }
+
+    fn write_fields(
+        &self,
+        fields: &Fields,
+        value: &Value,
+        path: &str,
+        out: &mut Vec<u8>,
+        depth: usize,
+    ) -> Result<(), BorshError> {
+        match fields {
+            Fields::Unit => Ok(()),
+            Fields::Tuple(types) => {
+                let items = value.as_array().ok_or_else(|| BorshError {
+                    path: path.to_string(),
+                    message: format!("expected an array of {}", types.len()),
+                })?;
+                if items.len() != types.len() {
+                    return Err(BorshError {
+                        path: path.to_string(),
+                        message: format!("expected {} items, got {}", types.len(), items.len()),
+                    });
+                }
+                for (i, (ty, item)) in types.iter().zip(items).enumerate() {
+                    self.write(ty, item, &format!("{path}[{i}]"), out, depth + 1)?;
+                }
+                Ok(())
+            }
+            Fields::Named(named) => {
+                let map = value.as_object().ok_or_else(|| BorshError {
+                    path: path.to_string(),
+                    message: "expected an object".to_string(),
+                })?;
+                for (name, ty) in named {
+                    let field_path = format!("{path}.{name}");
+                    let field = lookup(map, name).ok_or_else(|| BorshError {
+                        path: field_path.clone(),
+                        message: "missing field".to_string(),
+                    })?;
+                    self.write(ty, field, &field_path, out, depth + 1)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Schema {
+    /// Reads one `ty` from the front of `input`, advancing it. Integers wider
+    /// than 32 bits come back as strings, bytes as base64 and enums as either
+    /// the variant name or `{"Variant": fields}`, mirroring what `encode` takes.
+    pub fn decode(&self, ty: &Type, input: &mut &[u8], path: &str) -> Result<Value, BorshError> {
+        self.read(ty, input, path, 0)
+    }
+
+    fn read(&self, ty: &Type, input: &mut &[u8], path: &str, depth: usize) -> Result<Value, BorshError> {
+        check_depth(depth, path)?;
+        let value = match ty {
+            Type::Bool => match take::<1>(input, path)? {
+                [0] => Value::Bool(false),
+                [1] => Value::Bool(true),
+                [other] => return Err(BorshError { path: path.to_string(), message: format!("invalid bool byte {other}") }),
+            },
+            Type::U8 => u8::from_le_bytes(take(input, path)?).into(),
+            Type::I8 => i8::from_le_bytes(take(input, path)?).into(),
+            Type::U16 => u16::from_le_bytes(take(input, path)?).into(),
+            Type::I16 => i16::from_le_bytes(take(input, path)?).into(),
+            Type::U32 => u32::from_le_bytes(take(input, path)?).into(),
+            Type::I32 => i32::from_le_bytes(take(input, path)?).into(),
+            Type::U64 => u64::from_le_bytes(take(input, path)?).to_string().into(),
+            Type::I64 => i64::from_le_bytes(take(input, path)?).to_string().into(),
+            Type::U128 => u128::from_le_bytes(take(input, path)?).to_string().into(),
+            Type::I128 => i128::from_le_bytes(take(input, path)?).to_string().into(),
+            Type::F32 => f32::from_le_bytes(take(input, path)?).into(),
+            Type::F64 => f64::from_le_bytes(take(input, path)?).into(),
+            Type::String => {
+                let len = read_len(input, path)?;
+                let bytes = take_slice(input, len, path)?;
+                String::from_utf8(bytes.to_vec())
+                    .map_err(|_| BorshError { path: path.to_string(), message: "string is not UTF-8".to_string() })?
+                    .into()
+            }
+            Type::Bytes => {
+                let len = read_len(input, path)?;
+                let bytes = take_slice(input, len, path)?;
+                general_purpose::STANDARD.encode(bytes).into()
+            }
+            Type::Pubkey => Pubkey::new_from_array(take(input, path)?).to_string().into(),
+            Type::Vec(element) => {
+                // Elements take at least a byte each, so a longer length is
+                // corrupt; checking first avoids allocating for it.
+                let len = read_len(input, path)?;
+                if len > input.len() {
+                    return Err(BorshError { path: path.to_string(), message: format!("length {len} exceeds the input") });
+                }
+                self.read_items(element, len, input, path, depth + 1)?
+            }
+            Type::Array(element, len) => self.read_items(element, *len, input, path, depth + 1)?,
+            Type::Option(inner) | Type::COption(inner) => {
+                let present = if matches!(ty, Type::COption(_)) {
+                    u32::from_le_bytes(take(input, path)?)
+                } else {
+                    u8::from_le_bytes(take(input, path)?) as u32
+                };
+                match present {
+                    0 => Value::Null,
+                    1 => self.read(inner, input, path, depth + 1)?,
+                    other => {
+                        return Err(BorshError { path: path.to_string(), message: format!("invalid option tag {other}") });
+                    }
+                }
+            }
+            Type::Defined(name) => match self.types.get(name) {
+                Some(TypeDef::Struct(fields)) => self.read_fields(fields, input, path, depth + 1)?,
+                Some(TypeDef::Alias(inner)) => self.read(inner, input, path, depth + 1)?,
+                Some(TypeDef::Enum(variants)) => {
+                    let [index] = take::<1>(input, path)?;
+                    let (variant, fields) = variants.get(index as usize).ok_or_else(|| BorshError {
+                        path: path.to_string(),
+                        message: format!("`{name}` has no variant {index}"),
+                    })?;
+                    match fields {
+                        Fields::Unit => Value::String(variant.clone()),
+                        fields => {
+                            let value = self.read_fields(fields, input, &format!("{path}.{variant}"), depth + 1)?;
+                            Value::Object([(variant.clone(), value)].into_iter().collect())
+                        }
+                    }
+                }
+                None => return Err(BorshError { path: path.to_string(), message: format!("unknown type `{name}`") }),
+            },
+        };
+        Ok(value)
+    }
+
+    fn read_items(
+        &self,
+        element: &Type,
+        len: usize,
+        input: &mut &[u8],
+        path: &str,
+        depth: usize,
+    ) -> Result<Value, BorshError> {
+        (0..len)
+            .map(|i| self.read(element, input, &format!("{path}[{i}]"), depth))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array)
+    }
+
+    fn read_fields(&self, fields: &Fields, input: &mut &[u8], path: &str, depth: usize) -> Result<Value, BorshError> {
+        match fields {
+            Fields::Unit => Ok(Value::Null),
+            Fields::Tuple(types) => types
+                .iter()
+                .enumerate()
+                .map(|(i, ty)| self.read(ty, input, &format!("{path}[{i}]"), depth))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array),
+            Fields::Named(named) => named
+                .iter()
+                .map(|(name, ty)| Ok((name.clone(), self.read(ty, input, &format!("{path}.{name}"), depth)?)))
+                .collect::<Result<serde_json::Map<_, _>, _>>()
+                .map(Value::Object),
+        }
+    }
+}
+
+/// IDLs mix snake_case and camelCase for the same names, so lookups ignore
+/// underscores and case.
+pub fn same_name(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| s.chars().filter(|c| *c != '_').flat_map(char::to_lowercase).collect::<String>();
+    normalize(a) == normalize(b)
+}
+
+pub fn lookup<'a>(map: &'a serde_json::Map<String, Value>, name: &str) -> Option<&'a Value> {
+    map.get(name).or_else(|| map.iter().find(|(key, _)| same_name(key, name)).map(|(_, value)| value))
+}
+
+/// Recursive type definitions would otherwise let a small request overflow
+/// the stack.
+const MAX_DEPTH: usize = 64;
+
+fn check_depth(depth: usize, path: &str) -> Result<(), BorshError> {
+    if depth > MAX_DEPTH {
+        return Err(BorshError { path: path.to_string(), message: format!("nested deeper than {MAX_DEPTH} levels") });
+    }
+    Ok(())
+}
+
+fn take<const N: usize>(input: &mut &[u8], path: &str) -> Result<[u8; N], BorshError> {
+    Ok(take_slice(input, N, path)?.try_into().unwrap())
+}
+
+fn take_slice<'a>(input: &mut &'a [u8], len: usize, path: &str) -> Result<&'a [u8], BorshError> {
+    if input.len() < len {
+        return Err(BorshError { path: path.to_string(), message: "unexpected end of data".to_string() });
+    }
+    let (head, tail) = input.split_at(len);
+    *input = tail;
+    Ok(head)
+}
+
+fn read_len(input: &mut &[u8], path: &str) -> Result<usize, BorshError> {
+    Ok(u32::from_le_bytes(take(input, path)?) as usize)
+}
+
+fn write_len(len: usize, out: &mut Vec<u8>) {
+    out.extend((len as u32).to_le_bytes());
+}
+
+/// Integers may be JSON numbers or strings, since 64- and 128-bit values
+/// don't survive a round trip through JavaScript numbers.
+fn integer<T: FromStr>(value: &Value) -> Option<T> {
+    match value {
+        Value::Number(number) => number.to_string().parse().ok(),
+        Value::String(string) => string.parse().ok(),
+        _ => None,
+    }
+}
+
+fn float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(string) => string.parse().ok(),
+        _ => None,
+    }
+}
+
+fn byte_string(value: &Value) -> Option<Vec<u8>> {
+    match value {
+        Value::String(encoded) => general_purpose::STANDARD.decode(encoded).ok(),
+        Value::Array(items) => items.iter().map(integer::<u8>).collect(),
+        _ => None,
+    }
+}
+
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use moka::future::Cache;
+use solana_address_lookup_table_interface::state::AddressLookupTable;
+use solana_sdk::{account::Account, message::VersionedMessage, program_pack::Pack, pubkey::Pubkey};
+use spl_token::state::Mint;
+
+use crate::config::CacheConfig;
+use crate::errors::AppError;
+use crate::rpc::{RpcError, RpcHandle};
+
+/// Decoded base mint layout, shared by spl-token and token-2022.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MintInfo {
+    /// Owning token program, i.e. which program instructions must target.
+    pub program_id: Pubkey,
+    pub decimals: u8,
+    pub supply: u64,
+    pub mint_authority: Option<Pubkey>,
+    pub freeze_authority: Option<Pubkey>,
+}
+
+#[derive(Debug, Clone)]
+enum Lookup {
+    NotFound,
+    Rpc(RpcError),
+}
+
+/// TTL cache of raw accounts in front of the RPC backend, used by builders
+/// that need mint decimals, token program detection, lookup table contents
+/// or metadata. Concurrent misses for the same key share a single fetch;
+/// missing accounts are not cached so newly created ones show up at once.
+pub struct AccountCache {
+    rpc: RpcHandle,
+    accounts: Cache<Pubkey, Arc<Account>>,
+}
+
+impl AccountCache {
+    pub fn new(rpc: RpcHandle, config: &CacheConfig) -> Self {
+        let accounts = Cache::builder()
+            .max_capacity(config.max_accounts)
+            .time_to_live(Duration::from_secs(config.account_ttl_secs))
+            .build();
+
+        Self { rpc, accounts }
+    }
+
+    pub async fn account(&self, pubkey: &Pubkey) -> Result<Arc<Account>, AppError> {
+        let rpc = self.rpc.get().ok_or(AppError::RpcUnavailable)?;
+
+        self.accounts
+            .try_get_with(*pubkey, async move {
+                match rpc.get_account(pubkey).await {
+                    Ok(Some(account)) => Ok(Arc::new(account)),
+                    Ok(None) => Err(Lookup::NotFound),
+                    Err(err) => Err(Lookup::Rpc(err)),
+                }
+            })
+            .await
+            .map_err(|err| match &*err {
+                Lookup::NotFound => AppError::AccountNotFound(*pubkey),
+                Lookup::Rpc(err) => AppError::Rpc(err.clone()),
+            })
+    }
+
+    pub async fn mint(&self, mint: &Pubkey) -> Result<MintInfo, AppError> {
+        let account = self.account(mint).await?;
+        let invalid = || AppError::InvalidAccount { pubkey: *mint, expected: "token mint" };
+
+        if account.owner != spl_token::id() && account.owner != spl_token_2022::id() {
+            return Err(invalid());
+        }
+        // Token-2022 mints append extensions after the base layout.
+        let base = account.data.get(..Mint::LEN).ok_or_else(invalid)?;
+        let state = Mint::unpack(base).map_err(|_| invalid())?;
+
+        Ok(MintInfo {
+            program_id: account.owner,
+            decimals: state.decimals,
+            supply: state.supply,
+            mint_authority: state.mint_authority.into(),
+            freeze_authority: state.freeze_authority.into(),
+        })
+    }
+
+    pub async fn token_program(&self, mint: &Pubkey) -> Result<Pubkey, AppError> {
+        self.mint(mint).await.map(|info| info.program_id)
+    }
+
+    pub async fn lookup_table(&self, address: &Pubkey) -> Result<Vec<Pubkey>, AppError> {
+        let account = self.account(address).await?;
+        let table = AddressLookupTable::deserialize(&account.data).map_err(|_| AppError::InvalidAccount {
+            pubkey: *address,
+            expected: "address lookup table",
+        })?;
+        Ok(table.addresses.to_vec())
+    }
+
+    /// Static keys followed by lookup-table addresses, writable before
+    /// readonly, matching how the runtime indexes a message's accounts.
+    pub async fn account_keys(&self, message: &VersionedMessage) -> Result<Vec<Pubkey>, AppError> {
+        let mut keys = message.static_account_keys().to_vec();
+        let Some(lookups) = message.address_table_lookups() else {
+            return Ok(keys);
+        };
+
+        let mut tables = Vec::with_capacity(lookups.len());
+        for lookup in lookups {
+            tables.push(self.lookup_table(&lookup.account_key).await?);
+        }
+        for writable in [true, false] {
+            for (lookup, table) in lookups.iter().zip(&tables) {
+                let indexes = if writable { &lookup.writable_indexes } else { &lookup.readonly_indexes };
+                for &index in indexes {
+                    let address = table.get(usize::from(index)).ok_or_else(|| AppError::InvalidField {
+                        field: "transaction".to_string(),
+                        message: format!("lookup table {} has no entry {index}", lookup.account_key),
+                    })?;
+                    keys.push(*address);
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Drops every entry, e.g. after the RPC backend is swapped.
+    pub fn invalidate_all(&self) {
+        self.accounts.invalidate_all();
+    }
+}
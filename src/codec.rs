@@ -0,0 +1,153 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use crate::errors::AppError;
+
+/// Wire formats a request or response body can use. Every format maps onto
+/// the same serde models, so handlers never see the difference.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Cbor,
+    MessagePack,
+}
+
+impl Format {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::Cbor => "application/cbor",
+            Format::MessagePack => "application/msgpack",
+        }
+    }
+
+    fn from_mime(mime: &str) -> Option<Self> {
+        let mime = mime.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        match mime.as_str() {
+            "application/json" => Some(Format::Json),
+            "application/cbor" => Some(Format::Cbor),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(Format::MessagePack)
+            }
+            _ if mime.starts_with("application/") && mime.ends_with("+json") => Some(Format::Json),
+            _ => None,
+        }
+    }
+
+    /// Format of a request body, or `None` if its `Content-Type` is missing or
+    /// not one we decode.
+    pub fn from_content_type(headers: &HeaderMap) -> Option<Self> {
+        headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Self::from_mime)
+    }
+
+    /// First format listed in `Accept` that we can produce; JSON otherwise.
+    pub fn from_accept(headers: &HeaderMap) -> Self {
+        headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .find_map(Self::from_mime)
+            .unwrap_or(Format::Json)
+    }
+
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, AppError> {
+        match self {
+            Format::Json => crate::extract::from_json_slice(bytes),
+            Format::Cbor => from_cbor_slice(bytes),
+            Format::MessagePack => from_msgpack_slice(bytes),
+        }
+    }
+
+    pub fn encode<T: Serialize>(self, value: &T) -> Result<Vec<u8>, AppError> {
+        match self {
+            Format::Json => serde_json::to_vec(value).map_err(|e| AppError::Internal(e.to_string())),
+            Format::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes).map_err(|e| AppError::Internal(e.to_string()))?;
+                Ok(bytes)
+            }
+            Format::MessagePack => rmp_serde::to_vec_named(value).map_err(|e| AppError::Internal(e.to_string())),
+        }
+    }
+}
+
+/// ciborium doesn't expose its deserializer, so CBOR goes through a JSON value
+/// first. Our models are JSON-shaped anyway and this keeps field paths in
+/// error messages.
+fn from_cbor_slice<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, AppError> {
+    let mut reader = bytes;
+    let value: serde_json::Value =
+        ciborium::from_reader(&mut reader).map_err(|e| AppError::MalformedBody(e.to_string()))?;
+
+    if !reader.is_empty() {
+        return Err(AppError::MalformedBody("trailing bytes after CBOR value".to_string()));
+    }
+
+    serde_path_to_error::deserialize(value)
+        .map_err(|err| AppError::from_deserialize_error(err.path().to_string(), err.into_inner().to_string()))
+}
+
+fn from_msgpack_slice<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, AppError> {
+    use rmp_serde::decode::Error;
+
+    let mut reader = bytes;
+    let mut de = rmp_serde::Deserializer::new(&mut reader);
+    let value = serde_path_to_error::deserialize(&mut de).map_err(|err| {
+        let path = err.path().to_string();
+        match err.into_inner() {
+            Error::Syntax(message) => AppError::from_deserialize_error(path, message),
+            other => AppError::MalformedBody(other.to_string()),
+        }
+    })?;
+
+    if !reader.is_empty() {
+        return Err(AppError::MalformedBody("trailing bytes after MessagePack value".to_string()));
+    }
+    Ok(value)
+}
+
+/// Re-encodes JSON responses into the format the client asked for via
+/// `Accept`. Other content types (NDJSON streams, metrics) pass through.
+pub async fn negotiate(request: Request, next: Next) -> Response {
+    let format = Format::from_accept(request.headers());
+    let mut response = next.run(request).await;
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes() == b"application/json");
+    if !is_json {
+        return response;
+    }
+
+    response.headers_mut().insert(header::VARY, HeaderValue::from_static("accept"));
+    if format == Format::Json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+
+    let encoded = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .map_err(|e| AppError::Internal(e.to_string()))
+        .and_then(|value| format.encode(&value));
+    let encoded = match encoded {
+        Ok(encoded) => encoded,
+        Err(err) => return err.into_response(),
+    };
+
+    parts.headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(format.content_type()));
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(encoded))
+}
@@ -0,0 +1,689 @@
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use serde::Deserialize;
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+use crate::jito::TIP_ACCOUNTS;
+use crate::routes::{Availability, RouteGroup};
+use crate::tenant::DEFAULT_TENANT;
+use crate::types::{PubkeyStr, Redacted};
+use crate::utils::parse_secret_key;
+
+pub use superdev_models::admin::{FeaturesConfig, Mode};
+
+/// Service configuration. Read from the TOML file named by `SUPERDEV_CONFIG`
+/// (if set), then overridden by individual `SUPERDEV_*` environment variables.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub mode: Mode,
+    pub rpc: RpcConfig,
+    pub cache: CacheConfig,
+    pub dev: DevConfig,
+    pub solana_pay: SolanaPayConfig,
+    pub relayer: RelayerConfig,
+    pub jito: JitoConfig,
+    pub das: DasConfig,
+    pub nonce_pool: NoncePoolConfig,
+    pub jobs: JobsConfig,
+    pub policy: PolicyConfig,
+    pub transfers: TransfersConfig,
+    pub nft: NftConfig,
+    pub approvals: ApprovalsConfig,
+    pub runtime: RuntimeConfig,
+    pub keypair: KeypairConfig,
+    pub listener: ListenerConfig,
+    pub tls: TlsConfig,
+    pub messages: MessagesConfig,
+    pub admin: AdminConfig,
+    pub error_reporting: ErrorReportingConfig,
+    pub features: FeaturesConfig,
+    /// Identities by tenant. Each tenant sees only its own keystore entries,
+    /// jobs, proposals and audit log; anonymous callers and identities no
+    /// tenant lists share the `default` tenant.
+    pub tenants: HashMap<String, Vec<String>>,
+    /// Availability of each route group, e.g. `keypair = "hidden"` to keep
+    /// key generation off a public instance.
+    pub routes: HashMap<RouteGroup, Availability>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RpcConfig {
+    /// JSON-RPC endpoint; RPC-backed endpoints are unavailable when unset.
+    pub url: Option<String>,
+    /// Serve RPC-backed endpoints from the in-memory `MockRpc` instead of a
+    /// cluster. Takes precedence over `url`.
+    pub mock: bool,
+    /// How long a fetched blockhash is reused before asking the node again.
+    pub blockhash_ttl_ms: u64,
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            mock: false,
+            blockhash_ttl_ms: 5_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Lifetime of cached accounts (mints, lookup tables, metadata).
+    pub account_ttl_secs: u64,
+    pub max_accounts: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            account_ttl_secs: 30,
+            max_accounts: 10_000,
+        }
+    }
+}
+
+/// Settings for the `dev-tools` feature; ignored when it is compiled out.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DevConfig {
+    pub validator_bin: String,
+    pub ledger_dir: String,
+    pub rpc_port: u16,
+    /// How long `/dev/validator/start` waits for the RPC port to answer.
+    pub startup_timeout_secs: u64,
+}
+
+impl Default for DevConfig {
+    fn default() -> Self {
+        Self {
+            validator_bin: "solana-test-validator".to_string(),
+            ledger_dir: "test-ledger".to_string(),
+            rpc_port: 8899,
+            startup_timeout_secs: 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SolanaPayConfig {
+    /// Transaction-request templates served at `/solana-pay/tx/{id}`.
+    pub requests: HashMap<String, PayTemplate>,
+}
+
+/// A payment the service builds for whichever wallet connects. The wallet
+/// pays the fees and the transfer; `amount` is in lamports or token base units.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PayTemplate {
+    pub label: String,
+    pub icon: String,
+    pub recipient: PubkeyStr,
+    pub amount: u64,
+    #[serde(default)]
+    pub spl_token: Option<PubkeyStr>,
+    #[serde(default)]
+    pub reference: Vec<PubkeyStr>,
+    #[serde(default)]
+    pub memo: Option<String>,
+    /// Shown by the wallet alongside the transaction.
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Fee-payer-as-a-service. Disabled unless `secret` is set.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RelayerConfig {
+    /// Base58 secret of the fee payer; loaded into the keystore at startup.
+    pub secret: Option<Redacted<String>>,
+    /// Token fee users must pay the relayer inside each relayed transaction.
+    pub fee: Option<RelayerFee>,
+    /// Cap on required signatures, and so on the base fee the relayer pays.
+    pub max_signatures: u8,
+    /// Programs relayed transactions may invoke; empty allows any.
+    pub allowed_programs: Vec<PubkeyStr>,
+}
+
+impl Default for RelayerConfig {
+    fn default() -> Self {
+        Self {
+            secret: None,
+            fee: None,
+            max_signatures: 2,
+            allowed_programs: Vec::new(),
+        }
+    }
+}
+
+/// Paid in base units of `mint` to the relayer's associated token account.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RelayerFee {
+    pub mint: PubkeyStr,
+    pub amount: u64,
+}
+
+/// Jito bundle submission. Disabled unless `block_engine_url` is set.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct JitoConfig {
+    /// Block engine base URL, e.g. `https://mainnet.block-engine.jito.wtf`.
+    pub block_engine_url: Option<String>,
+    /// Smallest total tip a bundle must pay; the block engine drops less.
+    pub min_tip_lamports: u64,
+    pub tip_accounts: Vec<PubkeyStr>,
+}
+
+impl Default for JitoConfig {
+    fn default() -> Self {
+        Self {
+            block_engine_url: None,
+            min_tip_lamports: 1_000,
+            tip_accounts: TIP_ACCOUNTS.map(PubkeyStr).to_vec(),
+        }
+    }
+}
+
+/// Digital asset queries under `/assets`. Disabled unless `url` is set.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DasConfig {
+    /// DAS JSON-RPC endpoint, e.g. a Helius or Triton RPC URL with its key.
+    pub url: Option<Redacted<String>>,
+}
+
+/// Durable nonce accounts leased out for offline signing. Disabled unless
+/// `authority` is set.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct NoncePoolConfig {
+    /// Base58 secret of the authority of every pooled nonce account; loaded
+    /// into the keystore at startup.
+    pub authority: Option<Redacted<String>>,
+    /// Most accounts the pool tracks.
+    pub max_accounts: usize,
+    /// How long a lease lasts unless the request asks for less.
+    pub lease_ttl_secs: u64,
+}
+
+impl Default for NoncePoolConfig {
+    fn default() -> Self {
+        Self {
+            authority: None,
+            max_accounts: 256,
+            lease_ttl_secs: 120,
+        }
+    }
+}
+
+/// Default retry policy for scheduled transactions; requests may override it.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct JobsConfig {
+    /// Sends per run, first included.
+    pub max_attempts: u32,
+    /// Wait before the first retry; doubles after each further failure.
+    pub backoff_ms: u64,
+    /// Percent a priority fee rises by after each unconfirmed send.
+    pub bump_percent: u8,
+    /// How often a pending priority-fee send is checked for confirmation.
+    pub bump_interval_ms: u64,
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff_ms: 2_000,
+            bump_percent: 25,
+            bump_interval_ms: 1_000,
+        }
+    }
+}
+
+/// Rules checked before the service signs or sends anything that moves
+/// funds. Empty lists and unset limits impose nothing; once any rule is set,
+/// instructions the policy can't read are refused when a signer is involved.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PolicyConfig {
+    /// Lamports any one key may move per UTC day.
+    pub daily_lamport_limit: Option<u64>,
+    /// Lamports one key may move in a single operation without approval.
+    pub approval_threshold_lamports: Option<u64>,
+    /// Per-mint limits, in base units.
+    pub tokens: Vec<TokenPolicy>,
+    /// Mints that may be transferred; empty allows any.
+    pub allowed_mints: Vec<PubkeyStr>,
+    /// Wallets that may receive funds, directly or via their associated
+    /// token accounts; empty allows any.
+    pub allowed_destinations: Vec<PubkeyStr>,
+    pub denied_destinations: Vec<PubkeyStr>,
+}
+
+/// Sanity checks on the transfers builder endpoints produce.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TransfersConfig {
+    /// Smallest SOL transfer `/send/sol` builds, in lamports; unset allows any.
+    pub dust_threshold_lamports: Option<u64>,
+}
+
+/// Off-chain JSON fetched by `/nft/{mint}/metadata`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct NftConfig {
+    /// Whole-request limit, body included.
+    pub fetch_timeout_ms: u64,
+    /// Larger documents are rejected rather than read.
+    pub max_json_bytes: usize,
+    /// How long merged metadata is served from memory.
+    pub cache_ttl_secs: u64,
+    pub max_cached: u64,
+    /// Prefix `ipfs://` URIs are rewritten to.
+    pub ipfs_gateway: String,
+}
+
+impl Default for NftConfig {
+    fn default() -> Self {
+        Self {
+            fetch_timeout_ms: 5_000,
+            max_json_bytes: 1 << 20,
+            cache_ttl_secs: 300,
+            max_cached: 10_000,
+            ipfs_gateway: "https://ipfs.io/ipfs/".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenPolicy {
+    pub mint: PubkeyStr,
+    #[serde(default)]
+    pub daily_limit: Option<u64>,
+    #[serde(default)]
+    pub approval_threshold: Option<u64>,
+}
+
+/// Two-step signing for transfers over a policy approval threshold.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ApprovalsConfig {
+    /// Distinct identities, proposer excluded, that must approve.
+    pub required: usize,
+    /// How long a proposal may wait for approvals.
+    pub ttl_secs: u64,
+    /// API keys (sent as `X-Api-Key`) by identity name.
+    pub identities: HashMap<String, Redacted<String>>,
+}
+
+impl Default for ApprovalsConfig {
+    fn default() -> Self {
+        Self {
+            required: 2,
+            ttl_secs: 86_400,
+            identities: HashMap::new(),
+        }
+    }
+}
+
+/// Key generation under `/keypair`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct KeypairConfig {
+    /// Largest `count` one request may ask for.
+    pub max_count: usize,
+}
+
+impl Default for KeypairConfig {
+    fn default() -> Self {
+        Self { max_count: 1_000 }
+    }
+}
+
+/// Thread pools, sized at startup. Signing-heavy deployments want more
+/// `crypto_threads`; RPC-read-heavy ones, more `worker_threads`. Measure with
+/// `loadgen` before changing them.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RuntimeConfig {
+    /// Async worker threads; one per CPU core when unset.
+    pub worker_threads: Option<usize>,
+    /// Cap on tokio's blocking pool, which grows on demand.
+    pub max_blocking_threads: usize,
+    /// Threads dedicated to keypair generation, signing and verification.
+    /// Unset, that work shares the blocking pool.
+    pub crypto_threads: Option<usize>,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self { worker_threads: None, max_blocking_threads: 512, crypto_threads: None }
+    }
+}
+
+/// The API's listening socket.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ListenerConfig {
+    /// Socket address, e.g. `0.0.0.0:3000` or `[::]:3000`. Ignored when
+    /// systemd passes a socket in (`LISTEN_FDS`).
+    pub address: String,
+    /// Bind with `SO_REUSEPORT`, so a new release can start accepting on
+    /// the same port while the old one drains.
+    pub reuse_port: bool,
+    /// How long in-flight requests get to finish after SIGTERM or Ctrl-C
+    /// before the remaining connections are dropped.
+    pub drain_timeout_secs: u64,
+    /// Route groups served on `address`; all of them when empty.
+    pub groups: Vec<RouteGroup>,
+    /// Further addresses to listen on, e.g. `127.0.0.1:3001` serving only
+    /// `admin` and `keys`, so those stay on a private interface.
+    pub extra: Vec<BindConfig>,
+    /// Address serving `/metrics`, `/health`, `/admin` and `/debug`, e.g.
+    /// `127.0.0.1:9090`. When set, listeners without a `groups` list stop
+    /// serving those, so they can be firewalled apart from the public API.
+    pub admin_address: Option<String>,
+}
+
+impl Default for ListenerConfig {
+    fn default() -> Self {
+        Self {
+            address: "0.0.0.0:3000".to_string(),
+            reuse_port: false,
+            drain_timeout_secs: 30,
+            groups: Vec::new(),
+            extra: Vec::new(),
+            admin_address: None,
+        }
+    }
+}
+
+/// An additional listening address. Shares `reuse_port` and TLS with the
+/// main one.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BindConfig {
+    pub address: String,
+    /// Route groups served on `address`; all of them when empty.
+    pub groups: Vec<RouteGroup>,
+}
+
+/// HTTPS on the listener. Plain HTTP is served unless `cert` and `key` are set.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TlsConfig {
+    /// PEM certificate chain presented to clients, leaf first.
+    pub cert: Option<String>,
+    /// PEM private key for `cert`.
+    pub key: Option<String>,
+    /// PEM bundle of CAs trusted to issue client certificates. When set,
+    /// connections without a certificate from one of them are refused.
+    pub client_ca: Option<String>,
+    /// Identity names by client certificate subject common name. These
+    /// authenticate like `approvals.identities` and take precedence over
+    /// `X-Api-Key` on the same request.
+    pub subjects: HashMap<String, String>,
+}
+
+/// Off-chain message signing under `/message`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct MessagesConfig {
+    /// Opt-in tag, e.g. `"superdev:v1\n"`, prepended to every message
+    /// before it's signed or verified. While set, bytes that parse as a
+    /// transaction message are refused, so a leaked API key can't be used to
+    /// sign transactions through `/message/sign`.
+    pub domain: Option<String>,
+}
+
+/// Runtime administration under `/admin`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AdminConfig {
+    /// Identities, by API key or client certificate, allowed to use `/admin`.
+    pub identities: Vec<String>,
+}
+
+/// Reports of panics and 5xx responses, POSTed as JSON so operators hear of
+/// failures without reading logs. Disabled unless `webhook_url` is set.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ErrorReportingConfig {
+    /// Receiver of `ErrorReport`s, e.g. an alerting relay's ingest URL with
+    /// its token.
+    pub webhook_url: Option<Redacted<String>>,
+    /// Included in each report to tell deployments apart.
+    pub environment: Option<String>,
+    /// Reports are sent in the background and dropped after this long.
+    pub timeout_ms: u64,
+}
+
+impl Default for ErrorReportingConfig {
+    fn default() -> Self {
+        Self { webhook_url: None, environment: None, timeout_ms: 5_000 }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("failed to read config file {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+    /// Only the parser's message: its default rendering quotes the offending
+    /// line, which may hold a secret.
+    #[error("failed to parse config file: {0}")]
+    Parse(String),
+    #[error("invalid value for {name}: {value}")]
+    Env { name: &'static str, value: String },
+    #[error("invalid {0}")]
+    Invalid(&'static str),
+}
+
+impl Config {
+    pub fn load() -> Result<Self, ConfigError> {
+        let mut config = match std::env::var("SUPERDEV_CONFIG") {
+            Ok(path) => Self::from_file(&path)?,
+            Err(_) => Self::default(),
+        };
+        config.apply_env()?;
+        config.check()?;
+        Ok(config)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.display().to_string(),
+            source,
+        })?;
+        let contents = Zeroizing::new(contents);
+        toml::from_str(&contents).map_err(|error| ConfigError::Parse(error.message().to_string()))
+    }
+
+    fn apply_env(&mut self) -> Result<(), ConfigError> {
+        if let Ok(value) = std::env::var("SUPERDEV_MODE") {
+            self.mode = match value.as_str() {
+                "development" => Mode::Development,
+                "production" => Mode::Production,
+                _ => return Err(ConfigError::Env { name: "SUPERDEV_MODE", value }),
+            };
+        }
+        if let Ok(url) = std::env::var("SUPERDEV_RPC_URL") {
+            self.rpc.url = Some(url);
+        }
+        if let Ok(value) = std::env::var("SUPERDEV_RPC_MOCK") {
+            self.rpc.mock = parse_bool("SUPERDEV_RPC_MOCK", value)?;
+        }
+        if let Ok(url) = std::env::var("SUPERDEV_JITO_URL") {
+            self.jito.block_engine_url = Some(url);
+        }
+        if let Ok(url) = std::env::var("SUPERDEV_DAS_URL") {
+            self.das.url = Some(Redacted(url));
+        }
+        if let Ok(secret) = std::env::var("SUPERDEV_RELAYER_SECRET") {
+            self.relayer.secret = Some(Redacted(secret));
+        }
+        if let Ok(secret) = std::env::var("SUPERDEV_NONCE_AUTHORITY") {
+            self.nonce_pool.authority = Some(Redacted(secret));
+        }
+        if let Ok(url) = std::env::var("SUPERDEV_ERROR_WEBHOOK_URL") {
+            self.error_reporting.webhook_url = Some(Redacted(url));
+        }
+        if let Ok(address) = std::env::var("SUPERDEV_LISTEN_ADDRESS") {
+            self.listener.address = address;
+        }
+        if let Ok(address) = std::env::var("SUPERDEV_ADMIN_LISTEN_ADDRESS") {
+            self.listener.admin_address = Some(address);
+        }
+        if let Ok(path) = std::env::var("SUPERDEV_TLS_CERT") {
+            self.tls.cert = Some(path);
+        }
+        if let Ok(path) = std::env::var("SUPERDEV_TLS_KEY") {
+            self.tls.key = Some(path);
+        }
+        if let Ok(path) = std::env::var("SUPERDEV_TLS_CLIENT_CA") {
+            self.tls.client_ca = Some(path);
+        }
+        Ok(())
+    }
+
+    /// Rejects values that parse but can't be used, so a bad deployment
+    /// fails at startup rather than on first use.
+    fn check(&self) -> Result<(), ConfigError> {
+        if let Some(secret) = &self.relayer.secret
+            && parse_secret_key(secret).is_err()
+        {
+            return Err(ConfigError::Invalid("relayer.secret"));
+        }
+        if self.jobs.max_attempts == 0 {
+            return Err(ConfigError::Invalid("jobs.max_attempts"));
+        }
+        if self.nonce_pool.lease_ttl_secs == 0 {
+            return Err(ConfigError::Invalid("nonce_pool.lease_ttl_secs"));
+        }
+        if !(1..=100).contains(&self.jobs.bump_percent) {
+            return Err(ConfigError::Invalid("jobs.bump_percent"));
+        }
+        if self.jobs.bump_interval_ms == 0 {
+            return Err(ConfigError::Invalid("jobs.bump_interval_ms"));
+        }
+        if self.error_reporting.timeout_ms == 0 {
+            return Err(ConfigError::Invalid("error_reporting.timeout_ms"));
+        }
+        // The proposer can't approve, so someone else must be able to.
+        let approvals = &self.approvals;
+        let identities = self.identities().len();
+        if approvals.required == 0 || (identities > 0 && approvals.required >= identities) {
+            return Err(ConfigError::Invalid("approvals.required"));
+        }
+        if self.keypair.max_count == 0 {
+            return Err(ConfigError::Invalid("keypair.max_count"));
+        }
+        let runtime = &self.runtime;
+        if runtime.worker_threads == Some(0) {
+            return Err(ConfigError::Invalid("runtime.worker_threads"));
+        }
+        if runtime.max_blocking_threads == 0 {
+            return Err(ConfigError::Invalid("runtime.max_blocking_threads"));
+        }
+        if runtime.crypto_threads == Some(0) {
+            return Err(ConfigError::Invalid("runtime.crypto_threads"));
+        }
+        if self.listener.address.parse::<SocketAddr>().is_err() {
+            return Err(ConfigError::Invalid("listener.address"));
+        }
+        if self.listener.extra.iter().any(|bind| bind.address.parse::<SocketAddr>().is_err()) {
+            return Err(ConfigError::Invalid("listener.extra"));
+        }
+        if self.listener.admin_address.as_ref().is_some_and(|address| address.parse::<SocketAddr>().is_err()) {
+            return Err(ConfigError::Invalid("listener.admin_address"));
+        }
+        let tls = &self.tls;
+        if tls.cert.is_some() && tls.key.is_none() {
+            return Err(ConfigError::Invalid("tls.key"));
+        }
+        if tls.cert.is_none() && (tls.key.is_some() || tls.client_ca.is_some()) {
+            return Err(ConfigError::Invalid("tls.cert"));
+        }
+        if !tls.subjects.is_empty() && tls.client_ca.is_none() {
+            return Err(ConfigError::Invalid("tls.subjects"));
+        }
+        if self.messages.domain.as_deref() == Some("") {
+            return Err(ConfigError::Invalid("messages.domain"));
+        }
+        let identities = self.identities();
+        if self.admin.identities.iter().any(|name| !identities.contains(name.as_str())) {
+            return Err(ConfigError::Invalid("admin.identities"));
+        }
+        let mut members = HashSet::new();
+        for name in self.tenants.values().flatten() {
+            if !identities.contains(name.as_str()) || !members.insert(name) {
+                return Err(ConfigError::Invalid("tenants"));
+            }
+        }
+        Ok(())
+    }
+
+    /// The tenant `identity` belongs to.
+    pub fn tenant_of(&self, identity: &str) -> &str {
+        self.tenants
+            .iter()
+            .find(|(_, members)| members.iter().any(|member| member == identity))
+            .map_or(DEFAULT_TENANT, |(tenant, _)| tenant.as_str())
+    }
+
+    /// Every identity name a caller can authenticate as, by API key or
+    /// client certificate.
+    pub fn identities(&self) -> HashSet<&str> {
+        let keys = self.approvals.identities.keys();
+        keys.chain(self.tls.subjects.values()).map(String::as_str).collect()
+    }
+}
+
+/// Shared, swappable reference to the active configuration. Cloning shares
+/// the slot, so a reload through `/admin/reload` or SIGHUP is seen by every
+/// holder.
+#[derive(Clone)]
+pub struct ConfigHandle(Arc<RwLock<Arc<Config>>>);
+
+impl ConfigHandle {
+    pub fn new(config: Config) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(config))))
+    }
+
+    pub fn get(&self) -> Arc<Config> {
+        self.0.read().unwrap().clone()
+    }
+
+    /// Installs `config` and returns the one it replaced.
+    pub fn replace(&self, config: Config) -> Arc<Config> {
+        std::mem::replace(&mut *self.0.write().unwrap(), Arc::new(config))
+    }
+
+    /// Installs a copy of the active configuration changed by `change`.
+    pub fn update(&self, change: impl FnOnce(&mut Config)) -> Arc<Config> {
+        let mut slot = self.0.write().unwrap();
+        let mut config = Config::clone(&slot);
+        change(&mut config);
+        *slot = Arc::new(config);
+        slot.clone()
+    }
+}
+
+fn parse_bool(name: &'static str, value: String) -> Result<bool, ConfigError> {
+    match value.as_str() {
+        "1" | "true" => Ok(true),
+        "0" | "false" => Ok(false),
+        _ => Err(ConfigError::Env { name, value }),
+    }
+}
@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use solana_sdk::{hash::Hash, signature::Signature};
+
+use crate::rpc::{ConfirmedTransaction, RpcError, SolanaRpc};
+
+/// Blocks after its own in which a blockhash can still be used.
+pub const MAX_PROCESSING_AGE: u64 = 150;
+
+/// How often a submitted transaction is checked for confirmation.
+const POLL: Duration = Duration::from_millis(500);
+
+/// Last block height at which `blockhash` is valid, or `None` if it already
+/// expired. Exact for the latest blockhash; for older ones an upper bound,
+/// as the node doesn't say which block a hash came from.
+pub async fn last_valid_block_height(rpc: &dyn SolanaRpc, blockhash: &Hash) -> Result<Option<u64>, RpcError> {
+    let latest = rpc.get_latest_blockhash().await?;
+    if latest.blockhash == *blockhash {
+        return Ok(Some(latest.last_valid_block_height));
+    }
+    if !rpc.is_blockhash_valid(blockhash).await? {
+        return Ok(None);
+    }
+    Ok(Some(rpc.get_block_height().await? + MAX_PROCESSING_AGE))
+}
+
+/// Waits for `signature` to confirm, or returns `None` once the chain has
+/// passed `last_valid_block_height` without it, after which it never can.
+pub async fn track(
+    rpc: &dyn SolanaRpc,
+    signature: &Signature,
+    last_valid_block_height: u64,
+) -> Result<Option<ConfirmedTransaction>, RpcError> {
+    track_every(rpc, signature, last_valid_block_height, POLL).await
+}
+
+/// `track`, checking every `poll`.
+pub async fn track_every(
+    rpc: &dyn SolanaRpc,
+    signature: &Signature,
+    last_valid_block_height: u64,
+    poll: Duration,
+) -> Result<Option<ConfirmedTransaction>, RpcError> {
+    loop {
+        // Height first, so a landing in the last valid block is still seen.
+        let height = rpc.get_block_height().await?;
+        if let Some(confirmed) = rpc.get_transaction(signature).await? {
+            return Ok(Some(confirmed));
+        }
+        if height > last_valid_block_height {
+            return Ok(None);
+        }
+        tokio::time::sleep(poll).await;
+    }
+}
+
+/// Waits for `signature` to confirm, or returns `None` at `deadline`. For
+/// durable-nonce transactions, which have no last valid block height.
+pub async fn track_until(
+    rpc: &dyn SolanaRpc,
+    signature: &Signature,
+    deadline: DateTime<Utc>,
+) -> Result<Option<ConfirmedTransaction>, RpcError> {
+    loop {
+        if let Some(confirmed) = rpc.get_transaction(signature).await? {
+            return Ok(Some(confirmed));
+        }
+        if Utc::now() >= deadline {
+            return Ok(None);
+        }
+        tokio::time::sleep(POLL).await;
+    }
+}
@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use reqwest_middleware::{ClientBuilder, RequestBuilder, RequestInitialiser};
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_client::RpcClientConfig};
+use solana_rpc_client::http_sender::HttpSender;
+use solana_sdk::commitment_config::CommitmentConfig;
+use uuid::Uuid;
+
+use crate::request_id::plausible;
+
+pub const CORRELATION_ID: HeaderName = HeaderName::from_static("x-correlation-id");
+
+/// How long an outbound JSON-RPC call may take, as with `RpcClient::new`.
+const RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+tokio::task_local! {
+    static CURRENT: String;
+}
+
+/// Correlation id of the request being handled; `None` in background work
+/// such as scheduled jobs.
+pub fn current() -> Option<String> {
+    CURRENT.try_with(String::clone).ok()
+}
+
+/// Middleware that takes the caller's `X-Correlation-Id`, or makes one up,
+/// and handles the request under it: it's echoed in the response header and
+/// put in error bodies, log lines, error reports and outbound RPC calls.
+/// Unlike the request id, one correlation id may span many requests.
+pub async fn assign(request: Request, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(&CORRELATION_ID)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| plausible(id))
+        .map_or_else(|| Uuid::new_v4().to_string(), str::to_string);
+
+    let mut response = CURRENT.scope(id.clone(), next.run(request)).await;
+    let id = HeaderValue::from_str(&id).expect("correlation ids are visible ASCII");
+    response.headers_mut().insert(CORRELATION_ID, id);
+    response
+}
+
+/// `RpcClient` for `url` that forwards the current correlation id as
+/// `X-Correlation-Id`, for providers that log or trace by it; the rest
+/// ignore the header.
+pub fn rpc_client(url: String) -> RpcClient {
+    let client = reqwest::Client::builder()
+        .default_headers(HttpSender::default_headers())
+        .timeout(RPC_TIMEOUT)
+        .pool_idle_timeout(RPC_TIMEOUT)
+        .build()
+        .expect("HTTP client builds");
+    let client = ClientBuilder::new(client).with_init(Propagate).build();
+    let sender = HttpSender::new_with_client_with_middleware(url, client);
+    RpcClient::new_sender(sender, RpcClientConfig::with_commitment(CommitmentConfig::default()))
+}
+
+struct Propagate;
+
+impl RequestInitialiser for Propagate {
+    fn init(&self, request: RequestBuilder) -> RequestBuilder {
+        match current() {
+            Some(id) => request.header(CORRELATION_ID, id),
+            None => request,
+        }
+    }
+}
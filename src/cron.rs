@@ -0,0 +1,135 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Days, NaiveDate, TimeDelta, Timelike, Utc};
+
+/// How far ahead `next_after` looks before deciding an expression never
+/// fires. Eight years covers `0 0 29 2 *` across a skipped leap year.
+const SEARCH_DAYS: i64 = 8 * 366;
+
+/// Standard five-field cron expression (minute, hour, day of month, month,
+/// day of week), evaluated in UTC. Each field takes `*`, values, `a-b`
+/// ranges, `/n` steps and comma-separated lists; Sunday is 0 or 7.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cron {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether day of month / day of week were `*`. When both are
+    /// restricted a day matching either one fires, as in Vixie cron.
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl FromStr for Cron {
+    type Err = String;
+
+    fn from_str(expression: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!("expected 5 fields, got {}", fields.len()));
+        };
+
+        let mut weekdays = field(weekday, 0, 7, "day of week")?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            minutes: field(minute, 0, 59, "minute")?,
+            hours: field(hour, 0, 23, "hour")?,
+            days: field(day, 1, 31, "day of month")?,
+            months: field(month, 1, 12, "month")?,
+            weekdays,
+            any_day: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+}
+
+/// Parses one field into a bitmask of the values it matches.
+fn field(text: &str, min: u32, max: u32, name: &str) -> Result<u64, String> {
+    let invalid = || format!("invalid {name} field `{text}`");
+    let mut mask = 0;
+    for part in text.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step.parse::<u32>().ok().filter(|&step| step > 0).ok_or_else(invalid)?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (
+                    value(start, min, max).ok_or_else(invalid)?,
+                    value(end, min, max).ok_or_else(invalid)?,
+                ),
+                None => {
+                    let start = value(range, min, max).ok_or_else(invalid)?;
+                    // `5/15` means every 15th value starting at 5.
+                    (start, if step > 1 { max } else { start })
+                }
+            },
+        };
+        if start > end {
+            return Err(invalid());
+        }
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+fn value(text: &str, min: u32, max: u32) -> Option<u32> {
+    text.parse().ok().filter(|value| (min..=max).contains(value))
+}
+
+fn matches(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+impl Cron {
+    /// First whole minute strictly after `after` that the expression matches,
+    /// or `None` if it never fires (e.g. `0 0 31 2 *`).
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut time = after.with_second(0)?.with_nanosecond(0)? + TimeDelta::minutes(1);
+        let limit = time + TimeDelta::days(SEARCH_DAYS);
+
+        while time < limit {
+            if !matches(self.months, time.month()) {
+                let (year, month) = match time.month() {
+                    12 => (time.year() + 1, 1),
+                    month => (time.year(), month + 1),
+                };
+                time = start_of_day(NaiveDate::from_ymd_opt(year, month, 1)?);
+            } else if !self.day_matches(time) {
+                time = start_of_day(time.date_naive().checked_add_days(Days::new(1))?);
+            } else if !matches(self.hours, time.hour()) {
+                time = time.with_minute(0)? + TimeDelta::hours(1);
+            } else if !matches(self.minutes, time.minute()) {
+                time += TimeDelta::minutes(1);
+            } else {
+                return Some(time);
+            }
+        }
+        None
+    }
+
+    fn day_matches(&self, time: DateTime<Utc>) -> bool {
+        let day = matches(self.days, time.day());
+        let weekday = matches(self.weekdays, time.weekday().num_days_from_sunday());
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (false, true) => day,
+            (true, false) => weekday,
+            (false, false) => day || weekday,
+        }
+    }
+}
+
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).expect("midnight exists").and_utc()
+}
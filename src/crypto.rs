@@ -0,0 +1,144 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Mutex};
+
+use aes_gcm::aead::{Aead, Generate, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose, Engine as _};
+use solana_sdk::signer::{keypair::Keypair, Signer};
+use tokio::sync::oneshot;
+use zeroize::Zeroizing;
+
+use crate::errors::{AppError, FieldError};
+use crate::types::PubkeyStr;
+
+pub use superdev_models::{EncryptedKeypair, KdfParams};
+
+/// Format version written into every `EncryptedKeypair`.
+pub const BACKUP_VERSION: u8 = 1;
+/// Upper bound on the argon2 memory cost accepted on import, in KiB, so a
+/// crafted backup can't make the server allocate without limit.
+pub const MAX_KDF_MEMORY_KIB: u32 = 256 * 1024;
+const MAX_KDF_ITERATIONS: u32 = 16;
+
+type Task = Box<dyn FnOnce() + Send>;
+
+/// Runs CPU-bound crypto (keypair generation, ed25519 signing and
+/// verification) off the async workers, so bursts of signing requests don't
+/// stall everything else. Uses tokio's blocking pool unless
+/// `runtime.crypto_threads` gives it threads of its own.
+pub struct CryptoPool {
+    /// `None` when work goes to the blocking pool.
+    sender: Option<mpsc::Sender<Task>>,
+}
+
+impl CryptoPool {
+    /// Starts `threads` dedicated threads, which exit once the pool is
+    /// dropped; with `None`, work goes to tokio's blocking pool.
+    pub fn new(threads: Option<usize>) -> Self {
+        let Some(threads) = threads else {
+            return Self { sender: None };
+        };
+        let (sender, receiver) = mpsc::channel::<Task>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for index in 0..threads {
+            let receiver = receiver.clone();
+            std::thread::Builder::new()
+                .name(format!("crypto-{index}"))
+                .spawn(move || loop {
+                    // The lock is held only while waiting for a task.
+                    let task = receiver.lock().unwrap().recv();
+                    let Ok(task) = task else { return };
+                    task();
+                })
+                .expect("failed to start a crypto thread");
+        }
+        Self { sender: Some(sender) }
+    }
+
+    pub async fn run<F, T>(&self, work: F) -> Result<T, AppError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let Some(sender) = &self.sender else {
+            return tokio::task::spawn_blocking(work)
+                .await
+                .map_err(|e| AppError::Internal(format!("crypto task failed: {e}")));
+        };
+        let (done, result) = oneshot::channel();
+        // A panic fails only its own task, not the thread running it.
+        let task: Task = Box::new(move || {
+            let _ = done.send(panic::catch_unwind(AssertUnwindSafe(work)));
+        });
+        sender.send(task).map_err(|_| AppError::Internal("crypto pool stopped".to_string()))?;
+        match result.await {
+            Ok(Ok(value)) => Ok(value),
+            _ => Err(AppError::Internal("crypto task failed: panicked".to_string())),
+        }
+    }
+}
+
+pub fn encrypt_keypair(keypair: &Keypair, passphrase: &str) -> Result<EncryptedKeypair, AppError> {
+    let kdf = KdfParams {
+        memory_kib: Params::DEFAULT_M_COST,
+        iterations: Params::DEFAULT_T_COST,
+        parallelism: Params::DEFAULT_P_COST,
+    };
+    let salt = <[u8; 16]>::generate();
+    let cipher = cipher(&kdf, passphrase.as_bytes(), &salt).ok_or_else(|| internal("key derivation failed"))?;
+    let nonce = Nonce::generate();
+    let pubkey = keypair.pubkey();
+    let secret = Zeroizing::new(keypair.to_bytes());
+    let ciphertext = cipher
+        .encrypt(&nonce, Payload { msg: secret.as_slice(), aad: pubkey.as_ref() })
+        .map_err(|_| internal("encryption failed"))?;
+
+    Ok(EncryptedKeypair {
+        version: BACKUP_VERSION,
+        pubkey: PubkeyStr(pubkey),
+        kdf,
+        salt: general_purpose::STANDARD.encode(salt),
+        nonce: general_purpose::STANDARD.encode(nonce),
+        ciphertext: general_purpose::STANDARD.encode(ciphertext),
+    })
+}
+
+/// Any failure, from a wrong passphrase to a truncated field, is reported
+/// the same way so the error doesn't help guess the passphrase.
+pub fn decrypt_keypair(encrypted: &EncryptedKeypair, passphrase: &str) -> Result<Keypair, FieldError> {
+    let kdf = &encrypted.kdf;
+    if encrypted.version != BACKUP_VERSION
+        || kdf.memory_kib > MAX_KDF_MEMORY_KIB
+        || kdf.iterations > MAX_KDF_ITERATIONS
+    {
+        return Err(FieldError::DecryptionFailed);
+    }
+
+    let decode = |value: &str| general_purpose::STANDARD.decode(value).map_err(|_| FieldError::DecryptionFailed);
+    let salt = decode(&encrypted.salt)?;
+    let nonce = Nonce::try_from(decode(&encrypted.nonce)?.as_slice()).map_err(|_| FieldError::DecryptionFailed)?;
+    let ciphertext = decode(&encrypted.ciphertext)?;
+
+    let cipher = cipher(kdf, passphrase.as_bytes(), &salt).ok_or(FieldError::DecryptionFailed)?;
+    let secret = cipher
+        .decrypt(&nonce, Payload { msg: &ciphertext, aad: encrypted.pubkey.as_ref() })
+        .map(Zeroizing::new)
+        .map_err(|_| FieldError::DecryptionFailed)?;
+    let keypair = Keypair::try_from(secret.as_slice()).map_err(|_| FieldError::DecryptionFailed)?;
+    if keypair.pubkey() != *encrypted.pubkey {
+        return Err(FieldError::DecryptionFailed);
+    }
+    Ok(keypair)
+}
+
+fn cipher(kdf: &KdfParams, passphrase: &[u8], salt: &[u8]) -> Option<Aes256Gcm> {
+    let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, None).ok()?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params).hash_password_into(passphrase, salt, key.as_mut()).ok()?;
+    Aes256Gcm::new_from_slice(key.as_ref()).ok()
+}
+
+fn internal(message: &str) -> AppError {
+    AppError::Internal(message.to_string())
+}
@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::RpcRequest};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::correlation;
+use crate::config::DasConfig;
+use crate::models::assets::Asset;
+use crate::rpc::RpcError;
+
+/// One page of `getAssetsByOwner`, items as the provider returned them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AssetPage {
+    pub total: u32,
+    pub items: Vec<Value>,
+}
+
+/// Digital Asset Standard API, as served by Helius, Triton and others. It
+/// indexes compressed NFTs, which have no accounts to read over plain RPC.
+#[async_trait]
+pub trait DasProvider: Send + Sync {
+    /// `None` when the provider knows no asset `id`.
+    async fn get_asset(&self, id: &Pubkey) -> Result<Option<Value>, RpcError>;
+
+    async fn get_assets_by_owner(&self, owner: &Pubkey, page: u32, limit: u32) -> Result<AssetPage, RpcError>;
+}
+
+/// Builds the configured DAS provider, or `None` when none is configured.
+/// Mock RPC deployments get a `MockDas` so asset endpoints work offline.
+pub fn connect(config: &DasConfig, mock: bool) -> Option<Arc<dyn DasProvider>> {
+    if mock {
+        return Some(Arc::new(MockDas::new()));
+    }
+
+    config.url.as_ref().map(|url| Arc::new(HttpDas::new(String::clone(url))) as Arc<dyn DasProvider>)
+}
+
+/// DAS provider spoken to over JSON-RPC.
+pub struct HttpDas {
+    client: RpcClient,
+}
+
+impl HttpDas {
+    pub fn new(url: String) -> Self {
+        Self { client: correlation::rpc_client(url) }
+    }
+}
+
+#[async_trait]
+impl DasProvider for HttpDas {
+    async fn get_asset(&self, id: &Pubkey) -> Result<Option<Value>, RpcError> {
+        let result = self
+            .client
+            .send::<Value>(RpcRequest::Custom { method: "getAsset" }, json!({ "id": id.to_string() }))
+            .await;
+        match result {
+            Ok(asset) => Ok(Some(asset)),
+            // Providers report unknown ids as a JSON-RPC error rather than null.
+            Err(err) if err.to_string().to_ascii_lowercase().contains("not found") => Ok(None),
+            Err(err) => Err(RpcError(err.to_string())),
+        }
+    }
+
+    async fn get_assets_by_owner(&self, owner: &Pubkey, page: u32, limit: u32) -> Result<AssetPage, RpcError> {
+        let params = json!({ "ownerAddress": owner.to_string(), "page": page, "limit": limit });
+        let response: Value = self
+            .client
+            .send(RpcRequest::Custom { method: "getAssetsByOwner" }, params)
+            .await
+            .map_err(|err| RpcError(err.to_string()))?;
+        let items = response["items"].as_array().cloned().unwrap_or_default();
+        let total = response["total"].as_u64().map_or(items.len() as u32, |total| total as u32);
+        Ok(AssetPage { total, items })
+    }
+}
+
+/// In-memory provider for tests and mock deployments. Assets are listed by
+/// owner in id order.
+#[derive(Default)]
+pub struct MockDas {
+    assets: Mutex<BTreeMap<String, Value>>,
+}
+
+impl MockDas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores `asset`, a DAS asset object, under its `id`.
+    pub fn set_asset(&self, asset: Value) {
+        let id = asset["id"].as_str().expect("DAS assets have an id").to_string();
+        self.assets.lock().unwrap().insert(id, asset);
+    }
+}
+
+#[async_trait]
+impl DasProvider for MockDas {
+    async fn get_asset(&self, id: &Pubkey) -> Result<Option<Value>, RpcError> {
+        Ok(self.assets.lock().unwrap().get(&id.to_string()).cloned())
+    }
+
+    async fn get_assets_by_owner(&self, owner: &Pubkey, page: u32, limit: u32) -> Result<AssetPage, RpcError> {
+        let owner = owner.to_string();
+        let items: Vec<Value> = self
+            .assets
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|asset| asset["ownership"]["owner"] == owner.as_str())
+            .skip(page.saturating_sub(1) as usize * limit as usize)
+            .take(limit as usize)
+            .cloned()
+            .collect();
+        Ok(AssetPage { total: items.len() as u32, items })
+    }
+}
+
+/// Flattens a DAS asset object. Missing fields come out empty rather than
+/// failing, as providers differ in which optional parts they fill in.
+pub fn normalize(asset: &Value) -> Asset {
+    let text = |value: &Value| value.as_str().map(str::to_string);
+    let flag = |value: &Value| value.as_bool().unwrap_or(false);
+    let content = &asset["content"];
+    let compression = &asset["compression"];
+    let compressed = flag(&compression["compressed"]);
+
+    Asset {
+        id: text(&asset["id"]).unwrap_or_default(),
+        interface: text(&asset["interface"]).unwrap_or_default(),
+        compressed,
+        owner: text(&asset["ownership"]["owner"]),
+        delegate: text(&asset["ownership"]["delegate"]),
+        frozen: flag(&asset["ownership"]["frozen"]),
+        burnt: flag(&asset["burnt"]),
+        name: text(&content["metadata"]["name"]).unwrap_or_default(),
+        symbol: text(&content["metadata"]["symbol"]).unwrap_or_default(),
+        uri: text(&content["json_uri"]).unwrap_or_default(),
+        image: text(&content["links"]["image"]).or_else(|| text(&content["files"][0]["uri"])),
+        collection: asset["grouping"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|group| group["group_key"] == "collection")
+            .and_then(|group| text(&group["group_value"])),
+        royalty_basis_points: asset["royalty"]["basis_points"].as_u64().map_or(0, |points| points as u16),
+        tree: compressed.then(|| text(&compression["tree"])).flatten(),
+        leaf_id: compressed.then(|| compression["leaf_id"].as_u64()).flatten(),
+    }
+}
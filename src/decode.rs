@@ -0,0 +1,315 @@
+use serde_json::{json, Value};
+use solana_sdk::{program_error::ProgramError, program_pack::Pack, pubkey::Pubkey};
+use spl_token_2022::extension::{
+    cpi_guard::CpiGuard,
+    default_account_state::DefaultAccountState,
+    group_member_pointer::GroupMemberPointer,
+    group_pointer::GroupPointer,
+    interest_bearing_mint::InterestBearingConfig,
+    memo_transfer::MemoTransfer,
+    metadata_pointer::MetadataPointer,
+    mint_close_authority::MintCloseAuthority,
+    pausable::PausableConfig,
+    permanent_delegate::PermanentDelegate,
+    scaled_ui_amount::ScaledUiAmountConfig,
+    transfer_fee::{TransferFee, TransferFeeAmount, TransferFeeConfig},
+    transfer_hook::{TransferHook, TransferHookAccount},
+    BaseState, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use spl_token_2022::state::{Account, AccountState, Mint};
+use spl_token_group_interface::state::{TokenGroup, TokenGroupMember};
+use spl_token_metadata_interface::state::TokenMetadata;
+use solana_nonce::{state::State as NonceState, versions::Versions};
+use solana_stake_interface::state::{Meta, StakeStateV2};
+use crate::models::decode::{
+    MintLayout, NonceLayout, StakeDelegation, StakeLayout, StakeLockup, StakeMeta, TokenAccountLayout,
+};
+
+/// Parses a mint owned by either token program. Legacy SPL Token mints are
+/// the bare base layout, so they decode the same way with no extensions.
+pub fn mint(data: &[u8]) -> Result<MintLayout, ProgramError> {
+    let state = StateWithExtensions::<Mint>::unpack(data)?;
+    let base = state.base;
+
+    Ok(MintLayout {
+        address: None,
+        program_id: None,
+        mint_authority: key(base.mint_authority),
+        supply: base.supply.to_string(),
+        decimals: base.decimals,
+        is_initialized: base.is_initialized,
+        freeze_authority: key(base.freeze_authority),
+        extensions: extensions(&state)?,
+    })
+}
+
+pub fn token_account(data: &[u8]) -> Result<TokenAccountLayout, ProgramError> {
+    let state = StateWithExtensions::<Account>::unpack(data)?;
+    let base = state.base;
+
+    Ok(TokenAccountLayout {
+        address: None,
+        program_id: None,
+        mint: base.mint.to_string(),
+        owner: base.owner.to_string(),
+        amount: base.amount.to_string(),
+        delegate: key(base.delegate),
+        delegated_amount: base.delegated_amount.to_string(),
+        state: match base.state {
+            AccountState::Uninitialized => "uninitialized",
+            AccountState::Initialized => "initialized",
+            AccountState::Frozen => "frozen",
+        }
+        .to_string(),
+        is_native: base.is_native.is_some(),
+        rent_exempt_reserve: Option::<u64>::from(base.is_native).map(|reserve| reserve.to_string()),
+        close_authority: key(base.close_authority),
+        extensions: extensions(&state)?,
+    })
+}
+
+pub fn nonce(data: &[u8]) -> Option<NonceLayout> {
+    let versions: Versions = bincode::deserialize(data).ok()?;
+    let version = match versions {
+        Versions::Legacy(_) => "legacy",
+        Versions::Current(_) => "current",
+    };
+
+    Some(match versions.state() {
+        NonceState::Uninitialized => NonceLayout {
+            address: None,
+            version: version.to_string(),
+            state: "uninitialized".to_string(),
+            authority: None,
+            nonce: None,
+            lamports_per_signature: None,
+        },
+        NonceState::Initialized(data) => NonceLayout {
+            address: None,
+            version: version.to_string(),
+            state: "initialized".to_string(),
+            authority: Some(data.authority.to_string()),
+            nonce: Some(data.blockhash().to_string()),
+            lamports_per_signature: Some(data.get_lamports_per_signature()),
+        },
+    })
+}
+
+pub fn stake(data: &[u8]) -> Option<StakeLayout> {
+    let state: StakeStateV2 = bincode::deserialize(data).ok()?;
+    let meta = |meta: &Meta| StakeMeta {
+        rent_exempt_reserve: meta.rent_exempt_reserve.to_string(),
+        staker: meta.authorized.staker.to_string(),
+        withdrawer: meta.authorized.withdrawer.to_string(),
+        lockup: StakeLockup {
+            unix_timestamp: meta.lockup.unix_timestamp,
+            epoch: meta.lockup.epoch,
+            custodian: meta.lockup.custodian.to_string(),
+            in_force: meta.lockup != Default::default(),
+        },
+    };
+
+    // `u64::MAX` is the "never" sentinel for both epochs.
+    let epoch = |epoch: u64| (epoch != u64::MAX).then_some(epoch);
+    let (name, meta, delegation) = match &state {
+        StakeStateV2::Uninitialized => ("uninitialized", None, None),
+        StakeStateV2::RewardsPool => ("rewards_pool", None, None),
+        StakeStateV2::Initialized(m) => ("initialized", Some(meta(m)), None),
+        StakeStateV2::Stake(m, stake, _) => {
+            let delegation = &stake.delegation;
+            let delegation = StakeDelegation {
+                voter: delegation.voter_pubkey.to_string(),
+                stake: delegation.stake.to_string(),
+                activation_epoch: epoch(delegation.activation_epoch),
+                deactivation_epoch: epoch(delegation.deactivation_epoch),
+                credits_observed: stake.credits_observed,
+            };
+            ("delegated", Some(meta(m)), Some(delegation))
+        }
+    };
+
+    Some(StakeLayout { address: None, state: name.to_string(), meta, delegation })
+}
+
+fn key(key: impl Into<Option<Pubkey>>) -> Option<String> {
+    key.into().map(|key| key.to_string())
+}
+
+fn extensions<S: BaseState + Pack>(state: &StateWithExtensions<S>) -> Result<Vec<Value>, ProgramError> {
+    state
+        .get_extension_types()?
+        .into_iter()
+        .map(|extension_type| extension(state, extension_type))
+        .collect()
+}
+
+/// One extension as `{"extension": "<name>", ...fields}`. Confidential
+/// transfer extensions hold ciphertexts that mean nothing without the
+/// owner's keys, so only their presence is reported.
+fn extension<S: BaseState + Pack>(
+    state: &StateWithExtensions<S>,
+    extension_type: ExtensionType,
+) -> Result<Value, ProgramError> {
+    let (name, mut fields) = match extension_type {
+        ExtensionType::Uninitialized => ("uninitialized", json!({})),
+        ExtensionType::TransferFeeConfig => {
+            let config = state.get_extension::<TransferFeeConfig>()?;
+            let fee = |fee: &TransferFee| {
+                json!({
+                    "epoch": u64::from(fee.epoch),
+                    "maximum_fee": u64::from(fee.maximum_fee).to_string(),
+                    "transfer_fee_basis_points": u16::from(fee.transfer_fee_basis_points),
+                })
+            };
+            (
+                "transfer_fee_config",
+                json!({
+                    "transfer_fee_config_authority": key(config.transfer_fee_config_authority),
+                    "withdraw_withheld_authority": key(config.withdraw_withheld_authority),
+                    "withheld_amount": u64::from(config.withheld_amount).to_string(),
+                    "older_transfer_fee": fee(&config.older_transfer_fee),
+                    "newer_transfer_fee": fee(&config.newer_transfer_fee),
+                }),
+            )
+        }
+        ExtensionType::TransferFeeAmount => {
+            let amount = state.get_extension::<TransferFeeAmount>()?;
+            ("transfer_fee_amount", json!({ "withheld_amount": u64::from(amount.withheld_amount).to_string() }))
+        }
+        ExtensionType::MintCloseAuthority => {
+            let authority = state.get_extension::<MintCloseAuthority>()?;
+            ("mint_close_authority", json!({ "close_authority": key(authority.close_authority) }))
+        }
+        ExtensionType::ConfidentialTransferMint => ("confidential_transfer_mint", json!({})),
+        ExtensionType::ConfidentialTransferAccount => ("confidential_transfer_account", json!({})),
+        ExtensionType::ConfidentialTransferFeeConfig => ("confidential_transfer_fee_config", json!({})),
+        ExtensionType::ConfidentialTransferFeeAmount => ("confidential_transfer_fee_amount", json!({})),
+        ExtensionType::ConfidentialMintBurn => ("confidential_mint_burn", json!({})),
+        ExtensionType::DefaultAccountState => {
+            let default = state.get_extension::<DefaultAccountState>()?;
+            let account_state = match AccountState::try_from(default.state) {
+                Ok(AccountState::Initialized) => "initialized",
+                Ok(AccountState::Frozen) => "frozen",
+                _ => "uninitialized",
+            };
+            ("default_account_state", json!({ "state": account_state }))
+        }
+        ExtensionType::ImmutableOwner => ("immutable_owner", json!({})),
+        ExtensionType::MemoTransfer => {
+            let memo = state.get_extension::<MemoTransfer>()?;
+            (
+                "memo_transfer",
+                json!({ "require_incoming_transfer_memos": bool::from(memo.require_incoming_transfer_memos) }),
+            )
+        }
+        ExtensionType::NonTransferable => ("non_transferable", json!({})),
+        ExtensionType::NonTransferableAccount => ("non_transferable_account", json!({})),
+        ExtensionType::InterestBearingConfig => {
+            let config = state.get_extension::<InterestBearingConfig>()?;
+            (
+                "interest_bearing_config",
+                json!({
+                    "rate_authority": key(config.rate_authority),
+                    "initialization_timestamp": i64::from(config.initialization_timestamp),
+                    "pre_update_average_rate": i16::from(config.pre_update_average_rate),
+                    "last_update_timestamp": i64::from(config.last_update_timestamp),
+                    "current_rate": i16::from(config.current_rate),
+                }),
+            )
+        }
+        ExtensionType::CpiGuard => {
+            let guard = state.get_extension::<CpiGuard>()?;
+            ("cpi_guard", json!({ "lock_cpi": bool::from(guard.lock_cpi) }))
+        }
+        ExtensionType::PermanentDelegate => {
+            let delegate = state.get_extension::<PermanentDelegate>()?;
+            ("permanent_delegate", json!({ "delegate": key(delegate.delegate) }))
+        }
+        ExtensionType::TransferHook => {
+            let hook = state.get_extension::<TransferHook>()?;
+            ("transfer_hook", json!({ "authority": key(hook.authority), "program_id": key(hook.program_id) }))
+        }
+        ExtensionType::TransferHookAccount => {
+            let hook = state.get_extension::<TransferHookAccount>()?;
+            ("transfer_hook_account", json!({ "transferring": bool::from(hook.transferring) }))
+        }
+        ExtensionType::MetadataPointer => {
+            let pointer = state.get_extension::<MetadataPointer>()?;
+            (
+                "metadata_pointer",
+                json!({ "authority": key(pointer.authority), "metadata_address": key(pointer.metadata_address) }),
+            )
+        }
+        ExtensionType::TokenMetadata => {
+            let metadata = state.get_variable_len_extension::<TokenMetadata>()?;
+            (
+                "token_metadata",
+                json!({
+                    "update_authority": key(metadata.update_authority),
+                    "mint": metadata.mint.to_string(),
+                    "name": metadata.name,
+                    "symbol": metadata.symbol,
+                    "uri": metadata.uri,
+                    "additional_metadata": metadata.additional_metadata,
+                }),
+            )
+        }
+        ExtensionType::GroupPointer => {
+            let pointer = state.get_extension::<GroupPointer>()?;
+            (
+                "group_pointer",
+                json!({ "authority": key(pointer.authority), "group_address": key(pointer.group_address) }),
+            )
+        }
+        ExtensionType::TokenGroup => {
+            let group = state.get_extension::<TokenGroup>()?;
+            (
+                "token_group",
+                json!({
+                    "update_authority": key(group.update_authority),
+                    "mint": group.mint.to_string(),
+                    "size": u64::from(group.size),
+                    "max_size": u64::from(group.max_size),
+                }),
+            )
+        }
+        ExtensionType::GroupMemberPointer => {
+            let pointer = state.get_extension::<GroupMemberPointer>()?;
+            (
+                "group_member_pointer",
+                json!({ "authority": key(pointer.authority), "member_address": key(pointer.member_address) }),
+            )
+        }
+        ExtensionType::TokenGroupMember => {
+            let member = state.get_extension::<TokenGroupMember>()?;
+            (
+                "token_group_member",
+                json!({
+                    "mint": member.mint.to_string(),
+                    "group": member.group.to_string(),
+                    "member_number": u64::from(member.member_number),
+                }),
+            )
+        }
+        ExtensionType::ScaledUiAmount => {
+            let config = state.get_extension::<ScaledUiAmountConfig>()?;
+            (
+                "scaled_ui_amount",
+                json!({
+                    "authority": key(config.authority),
+                    "multiplier": f64::from(config.multiplier),
+                    "new_multiplier_effective_timestamp": i64::from(config.new_multiplier_effective_timestamp),
+                    "new_multiplier": f64::from(config.new_multiplier),
+                }),
+            )
+        }
+        ExtensionType::Pausable => {
+            let config = state.get_extension::<PausableConfig>()?;
+            ("pausable", json!({ "authority": key(config.authority), "paused": bool::from(config.paused) }))
+        }
+        ExtensionType::PausableAccount => ("pausable_account", json!({})),
+    };
+
+    fields["extension"] = Value::from(name);
+    Ok(fields)
+}
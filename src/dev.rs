@@ -0,0 +1,185 @@
+//! Development-only endpoints that manage a local `solana-test-validator`
+//! and point the service's RPC handle at it, plus seed-derived keypairs for
+//! fixtures. Compiled only with the `dev-tools` feature; never enable it on a
+//! shared deployment.
+
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{extract::State, routing::post, Router};
+use serde::Serialize;
+use solana_sdk::hash::hashv;
+use solana_sdk::signer::{keypair::Keypair, Signer};
+use tokio::process::{Child, Command};
+use tokio::sync::Mutex;
+
+use crate::config::DevConfig;
+use crate::errors::AppError;
+use crate::extract::{Json, ValidJson};
+use crate::handlers::success;
+use crate::models::{DeterministicKeypairRequest, DeterministicKeypairResponse};
+use crate::rpc::{ClusterRpc, SolanaRpc};
+use crate::state::AppState;
+use crate::types::Redacted;
+
+/// Mixed into every derivation so fixture keys can't collide with keys
+/// hashed from the same seed by other tools.
+const KEYPAIR_DOMAIN: &[u8] = b"superdev-insecure-fixture-keypair";
+
+#[derive(Clone)]
+struct DevState {
+    app: AppState,
+    validator: Arc<Mutex<Option<RunningValidator>>>,
+}
+
+struct RunningValidator {
+    child: Child,
+    /// Backend that was active before the validator took over, restored on stop.
+    previous_rpc: Option<Arc<dyn SolanaRpc>>,
+}
+
+#[derive(Serialize)]
+struct ValidatorStatus {
+    running: bool,
+    pid: Option<u32>,
+    rpc_url: String,
+    ledger_dir: String,
+}
+
+pub fn routes(app: AppState) -> Router {
+    let state = DevState {
+        app,
+        validator: Arc::new(Mutex::new(None)),
+    };
+
+    Router::new()
+        .route("/validator/start", post(start_validator))
+        .route("/validator/stop", post(stop_validator))
+        .route("/validator/reset", post(reset_validator))
+        .with_state(state)
+}
+
+fn rpc_url(config: &DevConfig) -> String {
+    format!("http://127.0.0.1:{}", config.rpc_port)
+}
+
+fn status(config: &DevConfig, pid: Option<u32>) -> Json<serde_json::Value> {
+    success(ValidatorStatus {
+        running: pid.is_some(),
+        pid,
+        rpc_url: rpc_url(config),
+        ledger_dir: config.ledger_dir.clone(),
+    })
+}
+
+async fn start_validator(State(state): State<DevState>) -> Result<Json<serde_json::Value>, AppError> {
+    let mut validator = state.validator.lock().await;
+    if validator.is_some() {
+        return Err(AppError::Conflict("Validator is already running".to_string()));
+    }
+
+    let running = spawn(&state, false).await?;
+    let pid = running.child.id();
+    *validator = Some(running);
+
+    Ok(status(&state.app.config().dev, pid))
+}
+
+async fn stop_validator(State(state): State<DevState>) -> Result<Json<serde_json::Value>, AppError> {
+    let mut validator = state.validator.lock().await;
+    let running = validator
+        .take()
+        .ok_or_else(|| AppError::Conflict("Validator is not running".to_string()))?;
+
+    shutdown(&state, running).await?;
+
+    Ok(status(&state.app.config().dev, None))
+}
+
+/// Restarts the validator on a wiped ledger, starting it if it wasn't running.
+async fn reset_validator(State(state): State<DevState>) -> Result<Json<serde_json::Value>, AppError> {
+    let mut validator = state.validator.lock().await;
+    if let Some(running) = validator.take() {
+        shutdown(&state, running).await?;
+    }
+
+    let running = spawn(&state, true).await?;
+    let pid = running.child.id();
+    *validator = Some(running);
+
+    Ok(status(&state.app.config().dev, pid))
+}
+
+async fn spawn(state: &DevState, reset: bool) -> Result<RunningValidator, AppError> {
+    let config = &state.app.config().dev;
+    let mut command = Command::new(&config.validator_bin);
+    command
+        .arg("--ledger")
+        .arg(&config.ledger_dir)
+        .arg("--rpc-port")
+        .arg(config.rpc_port.to_string())
+        .arg("--quiet")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true);
+    if reset {
+        command.arg("--reset");
+    }
+
+    let mut child = command
+        .spawn()
+        .map_err(|e| AppError::Internal(format!("failed to spawn {}: {e}", config.validator_bin)))?;
+
+    let rpc: Arc<dyn SolanaRpc> = Arc::new(ClusterRpc::new(rpc_url(config)));
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(config.startup_timeout_secs);
+    loop {
+        if rpc.get_latest_blockhash().await.is_ok() {
+            break;
+        }
+        if let Ok(Some(exit)) = child.try_wait() {
+            return Err(AppError::Internal(format!("validator exited during startup: {exit}")));
+        }
+        if tokio::time::Instant::now() >= deadline {
+            let _ = child.kill().await;
+            return Err(AppError::Internal("validator did not become ready in time".to_string()));
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+
+    let previous_rpc = state.app.rpc.replace(Some(rpc));
+    state.app.blockhash.invalidate().await;
+    state.app.accounts.invalidate_all();
+    Ok(RunningValidator { child, previous_rpc })
+}
+
+async fn shutdown(state: &DevState, mut running: RunningValidator) -> Result<(), AppError> {
+    state.app.rpc.replace(running.previous_rpc.take());
+    state.app.blockhash.invalidate().await;
+    state.app.accounts.invalidate_all();
+    running
+        .child
+        .kill()
+        .await
+        .map_err(|e| AppError::Internal(format!("failed to stop validator: {e}")))
+}
+
+/// The keypair for `seed` and `index`, identical on every run and machine so
+/// tests and demos get stable addresses. Anyone with the seed has the key.
+pub async fn deterministic_keypair(
+    ValidJson(request): ValidJson<DeterministicKeypairRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let DeterministicKeypairRequest { seed, index } = request;
+    let secret = hashv(&[KEYPAIR_DOMAIN, seed.as_bytes(), &index.to_le_bytes()]).to_bytes();
+    let keypair = Keypair::new_from_array(secret);
+    let response = DeterministicKeypairResponse {
+        pubkey: keypair.pubkey().to_string(),
+        secret: Redacted(keypair.to_base58_string()),
+        seed,
+        index,
+        insecure: true,
+    };
+
+    Ok(success(response))
+}
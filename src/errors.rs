@@ -1,29 +1,234 @@
 use axum::{
-    http::StatusCode,
+    http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
-use serde_json::json;
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
 use thiserror::Error;
+use crate::config::ConfigError;
+use crate::correlation;
+pub use superdev_models::FieldError;
+use crate::models::transaction::RebuildHint;
+use crate::policy::PolicyError;
+use crate::rpc::RpcError;
+use crate::validation::Violation;
 
 #[derive(Error, Debug)]
 pub enum AppError {
+    #[error("Missing required field: {field}")]
+    MissingField { field: String },
+    #[error("Invalid value for field `{field}`: {message}")]
+    InvalidField { field: String, message: String },
+    #[error("Malformed JSON: {0}")]
+    MalformedJson(String),
+    #[error("Malformed request body: {0}")]
+    MalformedBody(String),
+    #[error("Invalid request body: {0}")]
+    InvalidBody(String),
+    #[error("Expected request with `Content-Type: application/json`, `application/cbor` or `application/msgpack`")]
+    UnsupportedMediaType,
     #[allow(dead_code)]
-    #[error("Missing required fields")]
-    MissingFields,
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+    #[allow(dead_code)]
     #[error("Cryptographic error: {0}")]
     CryptoError(String),
+    #[error("{error}")]
+    Field { field: String, error: FieldError },
+    #[error("{0}")]
+    Conflict(String),
+    #[error("No RPC endpoint is configured")]
+    RpcUnavailable,
+    #[error("No relayer fee payer is configured")]
+    RelayerUnavailable,
+    #[error("No Jito block engine is configured")]
+    BlockEngineUnavailable,
+    #[error("No DAS provider is configured")]
+    DasUnavailable,
+    #[error("No nonce pool authority is configured")]
+    NoncePoolUnavailable,
+    #[error("Policy violation: {0}")]
+    PolicyViolation(String),
+    #[error("Missing or unknown API key or client certificate")]
+    Unauthorized,
+    #[error("{0}")]
+    Forbidden(String),
+    #[error("The {0} feature is disabled")]
+    FeatureDisabled(&'static str),
+    #[error("Configuration rejected: {0}")]
+    InvalidConfig(#[from] ConfigError),
+    #[error(transparent)]
+    Policy(#[from] PolicyError),
+    #[error(transparent)]
+    Rpc(#[from] RpcError),
+    #[error("Account {0} not found")]
+    AccountNotFound(Pubkey),
+    #[error("{0} not found")]
+    NotFound(String),
+    #[error("Method {method} not allowed")]
+    MethodNotAllowed { method: String, allowed: Vec<String> },
+    #[error("Account {pubkey} is not a valid {expected}")]
+    InvalidAccount { pubkey: Pubkey, expected: &'static str },
+    #[error("Internal error: {0}")]
+    Internal(String),
+    #[error("Request validation failed")]
+    Validation(Vec<Violation>),
+    #[error("Failed to create {0} instruction")]
+    InstructionBuild(&'static str),
+    #[error("Blockhash expired before transaction {} confirmed", .0.signature)]
+    BlockhashExpired(Box<RebuildHint>),
+}
+
+impl AppError {
+    /// Stable identifier clients can branch on; never reword these.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::MissingField { .. } => "MISSING_FIELD",
+            AppError::InvalidField { .. } => "INVALID_FIELD",
+            AppError::MalformedJson(_) => "MALFORMED_JSON",
+            AppError::MalformedBody(_) => "MALFORMED_BODY",
+            AppError::InvalidBody(_) => "INVALID_BODY",
+            AppError::UnsupportedMediaType => "UNSUPPORTED_MEDIA_TYPE",
+            AppError::InvalidInput(_) => "INVALID_INPUT",
+            AppError::CryptoError(_) => "CRYPTO_ERROR",
+            AppError::Field { error, .. } => error.code(),
+            AppError::Conflict(_) => "CONFLICT",
+            AppError::RpcUnavailable => "RPC_UNAVAILABLE",
+            AppError::RelayerUnavailable => "RELAYER_UNAVAILABLE",
+            AppError::BlockEngineUnavailable => "BLOCK_ENGINE_UNAVAILABLE",
+            AppError::DasUnavailable => "DAS_UNAVAILABLE",
+            AppError::NoncePoolUnavailable => "NONCE_POOL_UNAVAILABLE",
+            AppError::PolicyViolation(_) => "POLICY_VIOLATION",
+            AppError::Unauthorized => "UNAUTHORIZED",
+            AppError::Forbidden(_) => "FORBIDDEN",
+            AppError::FeatureDisabled(_) => "FEATURE_DISABLED",
+            AppError::InvalidConfig(_) => "INVALID_CONFIG",
+            AppError::Policy(error) => error.code(),
+            AppError::Rpc(_) => "RPC_ERROR",
+            AppError::AccountNotFound(_) => "ACCOUNT_NOT_FOUND",
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::MethodNotAllowed { .. } => "METHOD_NOT_ALLOWED",
+            AppError::InvalidAccount { .. } => "INVALID_ACCOUNT",
+            AppError::Internal(_) => "INTERNAL",
+            AppError::Validation(_) => "VALIDATION_FAILED",
+            AppError::InstructionBuild(_) => "INSTRUCTION_BUILD_FAILED",
+            AppError::BlockhashExpired(_) => "BLOCKHASH_EXPIRED",
+        }
+    }
+
+    /// Request field (wire name) the error refers to, if any.
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            AppError::MissingField { field }
+            | AppError::InvalidField { field, .. }
+            | AppError::Field { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+
+    /// The same error, its field (if any) reported under `prefix`.
+    pub fn within(mut self, prefix: &str) -> Self {
+        if let AppError::MissingField { field } | AppError::InvalidField { field, .. } | AppError::Field { field, .. } =
+            &mut self
+        {
+            *field = format!("{prefix}.{field}");
+        }
+        self
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AppError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::Conflict(_) | AppError::BlockhashExpired(_) => StatusCode::CONFLICT,
+            AppError::RpcUnavailable
+            | AppError::RelayerUnavailable
+            | AppError::BlockEngineUnavailable
+            | AppError::DasUnavailable
+            | AppError::NoncePoolUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::PolicyViolation(_) | AppError::Policy(_) => StatusCode::FORBIDDEN,
+            AppError::Unauthorized => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(_) | AppError::FeatureDisabled(_) => StatusCode::FORBIDDEN,
+            AppError::Rpc(_) => StatusCode::BAD_GATEWAY,
+            AppError::AccountNotFound(_) | AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::MethodNotAllowed { .. } => StatusCode::METHOD_NOT_ALLOWED,
+            AppError::Internal(_) | AppError::InvalidConfig(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    /// Classifies a body deserialization failure, keeping the JSON path of the
+    /// offending field so clients don't have to parse serde's message.
+    pub fn from_json_error(err: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        let path = err.path().to_string();
+        let inner = err.into_inner();
+        let message = strip_position(&inner);
+
+        if !inner.is_data() {
+            return AppError::MalformedJson(message);
+        }
+
+        Self::from_deserialize_error(path, message)
+    }
+
+    /// Maps a data-level serde failure at `path` ("." for the root) to the
+    /// matching field error. Shared by every extractor, whatever the format.
+    pub fn from_deserialize_error(path: String, message: String) -> Self {
+        if let Some(name) = message
+            .strip_prefix("missing field `")
+            .and_then(|rest| rest.strip_suffix('`'))
+        {
+            let field = if path == "." { name.to_string() } else { format!("{path}.{name}") };
+            return AppError::MissingField { field };
+        }
+
+        if path == "." {
+            return AppError::InvalidBody(message);
+        }
+
+        if let Some(error) = FieldError::parse(&message) {
+            return AppError::Field { field: path, error };
+        }
+
+        AppError::InvalidField { field: path, message }
+    }
+}
+
+/// serde_json appends " at line X column Y", which is noise for API clients.
+fn strip_position(err: &serde_json::Error) -> String {
+    let message = err.to_string();
+    match message.rfind(" at line ") {
+        Some(idx) => message[..idx].to_string(),
+        None => message,
+    }
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let body = Json(json!({
+        let mut body = json!({
             "success": false,
-            "error": self.to_string()
-        }));
-        
-        (StatusCode::BAD_REQUEST, body).into_response()
+            "error": self.to_string(),
+            "code": self.code()
+        });
+        if let Some(field) = self.field() {
+            body["field"] = Value::from(field);
+        }
+        if let AppError::Validation(violations) = &self {
+            body["details"] = json!(violations);
+        }
+        if let AppError::BlockhashExpired(hint) = &self {
+            body["rebuild"] = json!(hint);
+        }
+        if let Some(id) = correlation::current() {
+            body["correlation_id"] = Value::from(id);
+        }
+        if let AppError::MethodNotAllowed { allowed, .. } = &self {
+            body["allowed"] = json!(allowed);
+            let allow = HeaderValue::from_str(&allowed.join(", ")).expect("method names are visible ASCII");
+            return (self.status(), [(header::ALLOW, allow)], Json(body)).into_response();
+        }
+
+        (self.status(), Json(body)).into_response()
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,63 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use solana_sdk::hash::hash;
+
+/// Conditional-request middleware for read endpoints whose output depends
+/// only on their input. Tags successful GET/HEAD responses with a strong ETag
+/// over the body and answers a matching `If-None-Match` with 304.
+pub async fn conditional(request: Request, next: Next) -> Response {
+    if request.method() != Method::GET && request.method() != Method::HEAD {
+        return next.run(request).await;
+    }
+
+    let if_none_match = request.headers().get(header::IF_NONE_MATCH).cloned();
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+
+    let etag = etag_for(&bytes);
+    parts.headers.insert(header::ETAG, etag.clone());
+
+    if if_none_match.is_some_and(|header| matches(&header, &etag)) {
+        parts.status = StatusCode::NOT_MODIFIED;
+        strip_entity_headers(&mut parts.headers);
+        return Response::from_parts(parts, Body::empty());
+    }
+
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+fn etag_for(body: &[u8]) -> HeaderValue {
+    let digest = hash(body);
+    let hex: String = digest.as_ref()[..16].iter().map(|b| format!("{b:02x}")).collect();
+    HeaderValue::from_str(&format!("\"{hex}\"")).unwrap()
+}
+
+/// `If-None-Match` uses weak comparison, so `W/` prefixes are ignored.
+fn matches(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let Ok(candidates) = if_none_match.to_str() else {
+        return false;
+    };
+    let etag = etag.to_str().unwrap_or_default();
+
+    candidates
+        .split(',')
+        .map(|candidate| candidate.trim())
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+fn strip_entity_headers(headers: &mut HeaderMap) {
+    headers.remove(header::CONTENT_LENGTH);
+    headers.remove(header::CONTENT_TYPE);
+}
@@ -0,0 +1,175 @@
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, FromRequestParts, Multipart, Request},
+    http::{header, request::Parts},
+    response::{IntoResponse, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use crate::codec::Format;
+use crate::errors::AppError;
+use crate::state::AppState;
+use crate::tls::ClientSubject;
+use crate::upload::{FromUpload, Upload};
+use crate::validation::{Validate, Violations};
+
+/// Drop-in replacement for `axum::Json` whose rejections use the standard
+/// `{success: false, ...}` envelope and name the offending field. Bodies sent
+/// as CBOR or MessagePack decode into the same `T` (see `codec::Format`).
+pub struct Json<T>(pub T);
+
+impl<T, S> FromRequest<S> for Json<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Some(format) = Format::from_content_type(req.headers()) else {
+            return Err(AppError::UnsupportedMediaType);
+        };
+
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(|rejection| AppError::InvalidBody(rejection.body_text()))?;
+
+        format.decode(&bytes).map(Json)
+    }
+}
+
+/// `Json<T>` followed by the request's `Validate` rules.
+pub struct ValidJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state).await?;
+
+        let mut violations = Violations::default();
+        value.validate(&mut violations);
+        violations.into_result()?;
+
+        Ok(ValidJson(value))
+    }
+}
+
+/// Body of a bulk endpoint: anything `ValidJson` accepts, or a
+/// `multipart/form-data` CSV upload converted through `FromUpload`.
+pub struct Bulk<T>(pub T);
+
+impl<T, S> FromRequest<S> for Bulk<T>
+where
+    T: DeserializeOwned + Validate + FromUpload,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let multipart = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.to_ascii_lowercase().starts_with("multipart/form-data"));
+        if !multipart {
+            let ValidJson(value) = ValidJson::<T>::from_request(req, state).await?;
+            return Ok(Bulk(value));
+        }
+
+        let multipart = Multipart::from_request(req, state)
+            .await
+            .map_err(|rejection| AppError::InvalidBody(rejection.body_text()))?;
+        let value = T::from_upload(Upload::read(multipart).await?)?;
+
+        let mut violations = Violations::default();
+        value.validate(&mut violations);
+        violations.into_result()?;
+
+        Ok(Bulk(value))
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+/// Query-string counterpart of `Json<T>`, with the same error envelope.
+pub struct Query<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let query = parts.uri.query().unwrap_or_default();
+        let de = serde_urlencoded::Deserializer::new(form_urlencoded::parse(query.as_bytes()));
+
+        serde_path_to_error::deserialize(de)
+            .map(Query)
+            .map_err(|err| AppError::from_deserialize_error(err.path().to_string(), err.into_inner().to_string()))
+    }
+}
+
+/// Name of the calling identity: the one mapped to the connection's client
+/// certificate under mTLS, else the one whose key was sent in `X-Api-Key`.
+pub struct Identity(pub String);
+
+impl Identity {
+    /// `None` for anonymous callers, unknown certificates or keys, and
+    /// revoked keys.
+    pub fn resolve(parts: &Parts, state: &AppState) -> Option<Self> {
+        let config = state.config();
+        if let Some(ClientSubject(subject)) = parts.extensions.get::<ClientSubject>()
+            && let Some(name) = config.tls.subjects.get(subject)
+        {
+            return Some(Identity(name.clone()));
+        }
+        let key = parts.headers.get("x-api-key").and_then(|value| value.to_str().ok())?;
+        config
+            .approvals
+            .identities
+            .iter()
+            .find(|(_, expected)| expected.as_str() == key)
+            .map(|(name, _)| Identity(name.clone()))
+            .filter(|Identity(name)| !state.revoked.read().unwrap().contains(name))
+    }
+}
+
+impl FromRequestParts<AppState> for Identity {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        Identity::resolve(parts, state).ok_or(AppError::Unauthorized)
+    }
+}
+
+/// `Identity` of a caller listed in `admin.identities`.
+pub struct Admin(pub String);
+
+impl FromRequestParts<AppState> for Admin {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let Identity(name) = Identity::from_request_parts(parts, state).await?;
+        if !state.config().admin.identities.contains(&name) {
+            return Err(AppError::Forbidden(format!("Identity `{name}` may not use the admin API")));
+        }
+        Ok(Admin(name))
+    }
+}
+
+pub fn from_json_slice<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, AppError> {
+    let de = &mut serde_json::Deserializer::from_slice(bytes);
+    let value = serde_path_to_error::deserialize(&mut *de).map_err(AppError::from_json_error)?;
+    de.end().map_err(|e| AppError::MalformedJson(e.to_string()))?;
+    Ok(value)
+}
@@ -1,445 +1,613 @@
-use axum::{http::StatusCode, Json};
-use solana_sdk::{pubkey::Pubkey, signer::{keypair::Keypair, Signer}, signature::Signature};
+pub mod account;
+pub mod address_book;
+pub mod admin;
+pub mod airdrop;
+pub mod alt;
+pub mod anchor;
+pub mod assets;
+pub mod audit;
+pub mod block;
+pub mod borsh;
+pub mod cluster;
+pub mod compose;
+pub mod convert;
+pub mod decode;
+pub mod derive;
+pub mod jobs;
+pub mod keys;
+pub mod merkle_tree;
+pub mod nft;
+pub mod nonce_pool;
+pub mod program;
+pub mod qr;
+pub mod relayer;
+pub mod schemas;
+pub mod solana_pay;
+pub mod squads;
+pub mod stake;
+pub mod templates;
+pub mod token;
+pub mod transaction;
+pub mod transfers;
+pub mod vote;
+
+use std::sync::Arc;
+use axum::{extract::State, http::HeaderMap, response::{IntoResponse, Response}};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::{keypair::Keypair, Signer};
 use solana_system_interface::instruction as system_instruction;
 use spl_token::instruction::{initialize_mint, mint_to, transfer};
-use spl_associated_token_account::get_associated_token_address;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
 use base64::{Engine as _, engine::general_purpose};
+use zeroize::Zeroizing;
+use serde::Serialize;
 use serde_json::json;
+use crate::amount;
+use crate::config::Mode;
+use crate::crypto::{self, CryptoPool};
+use crate::errors::{AppError, FieldError};
+use crate::extract::{Admin, Json, Query, ValidJson};
+use crate::ndjson;
 use crate::models::{
-    KeypairResponse, CreateTokenRequest, InstructionResponse, 
-    AccountMeta, MintTokenRequest, SignMessageRequest, SignMessageResponse,
-    VerifyMessageRequest, VerifyMessageResponse, SendSolRequest, SendTokenRequest,
-    SendSolResponse, SendTokenResponse
+    KeypairLine, KeypairQuery, KeypairResponse, ExportKeypairRequest, ImportKeypairRequest, CreateTokenRequest,
+    InstructionResponse, AccountMeta, MintTokenRequest, SignMessageRequest, SignMessageResponse,
+    VerifyMessageRequest, VerifyMessageResponse, BatchVerifyRequest, BatchVerifyItem,
+    BatchVerifyResult, BatchVerifyResponse, ItemError, SendSolRequest, SendSolResponse, SendTokenRequest,
+    SendTokenResponse, OutputFormat, Web3Instruction, SendSolBatchRequest, SendSolBatchResponse,
+    SolBatchTransaction,
 };
+use crate::state::AppState;
+use crate::tx;
+use crate::tenant;
+use crate::types::{AddressRef, Redacted, SecretKeyStr, SignerRef};
+use crate::utils::{parse_pubkey, parse_signature};
+use crate::validation::MAX_MESSAGE_LEN;
 
-pub async fn generate_keypair() -> Json<serde_json::Value> {
-    let keypair = Keypair::new();
-    let pubkey = keypair.pubkey().to_string();
-    let secret = bs58::encode(&keypair.to_bytes()).into_string();
-    let response = KeypairResponse { pubkey, secret };
-    
+pub(crate) fn success<T: Serialize>(data: T) -> Json<serde_json::Value> {
     Json(json!({
         "success": true,
-        "data": response
+        "data": data
     }))
 }
 
-pub async fn create_token(
-    Json(payload): Json<CreateTokenRequest>,
-) -> (StatusCode, Json<serde_json::Value>) {
-    let mint_authority = match payload.mint_authority.parse::<Pubkey>() {
-        Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid mint authority address"
-                })),
-            );
-        }
+/// One keypair, or `count` of them as an array. With
+/// `Accept: application/x-ndjson` a batch streams one keypair per line as
+/// they're generated.
+pub async fn generate_keypair(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<KeypairQuery>,
+) -> Result<Response, AppError> {
+    let production = state.config().mode == Mode::Production;
+    let Some(count) = query.count else {
+        let keypair = state.crypto.run(Keypair::new).await?;
+        return Ok(success(keypair_response(&state, &tenant::current(), production, keypair)).into_response());
     };
+    let max = state.config().keypair.max_count;
+    if !(1..=max).contains(&count) {
+        return Err(AppError::Field { field: "count".to_string(), error: FieldError::BatchSize(max) });
+    }
 
-    let mint = match payload.mint.parse::<Pubkey>() {
-        Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid mint address"
-                })),
-            );
+    let keypairs = generate_batch(state, count, production);
+    if ndjson::accepts(&headers) {
+        // The status has already gone, so a chunk that fails becomes an
+        // error line in place of its keypairs.
+        let lines = keypairs.flat_map(|chunk| {
+            stream::iter(match chunk {
+                Ok(keypairs) => keypairs.into_iter().map(KeypairLine::Keypair).collect(),
+                Err(err) => {
+                    let error = ItemError { code: err.code().to_string(), message: err.to_string() };
+                    vec![KeypairLine::Failed { error }]
+                }
+            })
+        });
+        return Ok(ndjson::stream(lines));
+    }
+    let keypairs: Vec<Vec<KeypairResponse>> = keypairs.try_collect().await?;
+    Ok(success(keypairs.into_iter().flatten().collect::<Vec<_>>()).into_response())
+}
+
+fn keypair_response(state: &AppState, tenant: &str, production: bool, keypair: Keypair) -> KeypairResponse {
+    let pubkey = keypair.pubkey().to_string();
+    if production {
+        return KeypairResponse { pubkey, secret: None, key_id: Some(state.keystore.insert(tenant, keypair)) };
+    }
+    let bytes = Zeroizing::new(keypair.to_bytes());
+    let secret = Redacted(bs58::encode(bytes.as_slice()).into_string());
+    KeypairResponse { pubkey, secret: Some(secret), key_id: None }
+}
+
+const KEYPAIR_CHUNK: usize = 64;
+
+/// Keypairs generated on the crypto pool a chunk at a time, like
+/// `verify_batch_stream`. The tenant is read up front: the stream may be
+/// polled after the request's tenant scope has ended.
+fn generate_batch(
+    state: AppState,
+    count: usize,
+    production: bool,
+) -> impl Stream<Item = Result<Vec<KeypairResponse>, AppError>> + Send + 'static {
+    let tenant = tenant::current();
+    let chunks = (0..count).step_by(KEYPAIR_CHUNK).map(move |start| KEYPAIR_CHUNK.min(count - start));
+    stream::iter(chunks).then(move |size| {
+        let (state, tenant) = (state.clone(), tenant.clone());
+        async move {
+            let keypairs = state.crypto.run(move || (0..size).map(|_| Keypair::new()).collect::<Vec<_>>()).await?;
+            Ok(keypairs.into_iter().map(|keypair| keypair_response(&state, &tenant, production, keypair)).collect())
         }
-    };
-    
-    let instruction = match initialize_mint(
+    })
+}
+
+/// Returns the keypair's secret sealed under `passphrase`, for backups. In
+/// production mode only admins may export, as a key id alone must not be
+/// enough to take a key out of the keystore.
+pub async fn export_encrypted(
+    State(state): State<AppState>,
+    admin: Result<Admin, AppError>,
+    ValidJson(payload): ValidJson<ExportKeypairRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    if state.config().mode == Mode::Production {
+        admin?;
+    }
+    let ExportKeypairRequest { secret, key_id, passphrase } = payload;
+    let keypair = signer(&state, secret, key_id)?;
+    let encrypted = state.crypto.run(move || crypto::encrypt_keypair(&keypair, &passphrase)).await??;
+
+    Ok(success(encrypted))
+}
+
+/// Loads a backup made by `export_encrypted` into the keystore.
+pub async fn import_encrypted(
+    State(state): State<AppState>,
+    ValidJson(payload): ValidJson<ImportKeypairRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let ImportKeypairRequest { encrypted, passphrase, metadata } = payload;
+    let keypair = state.crypto.run(move || crypto::decrypt_keypair(&encrypted, &passphrase))
+        .await?
+        .map_err(|error| AppError::Field { field: "passphrase".to_string(), error })?;
+    let pubkey = keypair.pubkey().to_string();
+    let key_id = state.keystore.insert_with(&tenant::current(), keypair, metadata.into());
+    let response = KeypairResponse { pubkey, secret: None, key_id: Some(key_id) };
+
+    Ok(success(response))
+}
+
+/// The signer named by a request's `secret` or `key_id` field; validation
+/// guarantees exactly one is present.
+fn signer(state: &AppState, secret: Option<SecretKeyStr>, key_id: Option<String>) -> Result<Arc<Keypair>, AppError> {
+    match (secret, key_id) {
+        (Some(secret), _) => state.signer("secret".to_string(), SignerRef::Secret(secret)),
+        (None, Some(key_id)) => state.signer("key_id".to_string(), SignerRef::KeyId(key_id)),
+        (None, None) => unreachable!("validated: exactly one of secret or key_id"),
+    }
+}
+
+fn instruction_response(instruction: &Instruction) -> InstructionResponse {
+    InstructionResponse {
+        program_id: instruction.program_id.to_string(),
+        accounts: instruction
+            .accounts
+            .iter()
+            .map(|acc| AccountMeta {
+                pubkey: acc.pubkey.to_string(),
+                is_signer: acc.is_signer,
+                is_writable: acc.is_writable,
+            })
+            .collect(),
+        instruction_data: general_purpose::STANDARD.encode(&instruction.data),
+    }
+}
+
+/// A builder's `response`, or `instruction` as web3.js JSON if the request
+/// asked for that instead.
+fn built<T: Serialize>(
+    format: OutputFormat,
+    instruction: &Instruction,
+    response: impl FnOnce() -> T,
+) -> Json<serde_json::Value> {
+    match format {
+        OutputFormat::Superdev => success(response()),
+        OutputFormat::Web3js => success(Web3Instruction::from(instruction_response(instruction))),
+    }
+}
+
+pub async fn create_token(
+    ValidJson(payload): ValidJson<CreateTokenRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let instruction = initialize_mint(
         &spl_token::id(),
-        &mint,
-        &mint_authority,
+        &payload.mint,
+        &payload.mint_authority,
         None,
         payload.decimals,
-    ) {
-        Ok(inst) => inst,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Failed to create mint instruction"
-                })),
-            );
-        }
-    };
-    
-    let accounts: Vec<AccountMeta> = instruction
-        .accounts
-        .iter()
-        .map(|acc| AccountMeta {
-            pubkey: acc.pubkey.to_string(),
-            is_signer: acc.is_signer,
-            is_writable: acc.is_writable,
-        })
-        .collect();
-    
-    let response = InstructionResponse {
-        program_id: instruction.program_id.to_string(),
-        accounts,
-        instruction_data: general_purpose::STANDARD.encode(&instruction.data),
-    };
-    
-    (StatusCode::OK, Json(json!({
-        "success": true,
-        "data": response
-    })))
+    )
+    .map_err(|_| AppError::InstructionBuild("mint"))?;
+
+    Ok(built(payload.output_format, &instruction, || instruction_response(&instruction)))
 }
 
-pub async fn mint_token(
-    Json(payload): Json<MintTokenRequest>,
-) -> (StatusCode, Json<serde_json::Value>) {
-    let mint = match payload.mint.parse::<Pubkey>() {
-        Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid mint address"
-                })),
-            );
-        }
-    };
+/// What a token instruction for `mint` needs beyond the request itself.
+struct TokenTarget {
+    program_id: Pubkey,
+    amount: u64,
+    /// Set when the `*_checked` instruction should be built.
+    decimals: Option<u8>,
+}
 
-    let destination = match payload.destination.parse::<Pubkey>() {
-        Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid destination address"
-                })),
-            );
-        }
+/// With RPC configured the mint is read (through the account cache) so
+/// token-2022 mints get token-2022 instructions; without it spl-token is
+/// assumed and only a `ui_amount` without `decimals` needs the mint.
+/// Token-2022 always gets the checked variants, which it requires of mints
+/// with extensions such as transfer fees.
+async fn token_target(
+    state: &AppState,
+    mint: &Pubkey,
+    amount: Option<u64>,
+    ui_amount: Option<&str>,
+    decimals: Option<u8>,
+) -> Result<TokenTarget, AppError> {
+    let info = if state.rpc.get().is_some() || (ui_amount.is_some() && decimals.is_none()) {
+        Some(state.accounts.mint(mint).await?)
+    } else {
+        None
     };
-
-    let authority = match payload.authority.parse::<Pubkey>() {
-        Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid authority address"
-                })),
-            );
+    let program_id = info.map_or_else(spl_token::id, |info| info.program_id);
+    let decimals = match info {
+        Some(info) if decimals.is_none() && (ui_amount.is_some() || program_id == spl_token_2022::id()) => {
+            Some(info.decimals)
         }
+        _ => decimals,
     };
-    
-    let instruction = match mint_to(
-        &spl_token::id(),
-        &mint,
-        &destination,
-        &authority,
-        &[],
-        payload.amount,
-    ) {
-        Ok(inst) => inst,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Failed to create mint instruction"
-                })),
-            );
+
+    let amount = match (ui_amount, decimals) {
+        (Some(ui_amount), Some(decimals)) => {
+            let field = |error| AppError::Field { field: "ui_amount".to_string(), error };
+            let raw = amount::from_ui(ui_amount, decimals).map_err(field)?;
+            if raw == 0 {
+                return Err(field(FieldError::AmountZero));
+            }
+            raw
         }
+        _ => amount.expect("validated: exactly one of amount or ui_amount"),
     };
-    
-    let accounts: Vec<AccountMeta> = instruction
-        .accounts
-        .iter()
-        .map(|acc| AccountMeta {
-            pubkey: acc.pubkey.to_string(),
-            is_signer: acc.is_signer,
-            is_writable: acc.is_writable,
-        })
-        .collect();
-    
-    let response = InstructionResponse {
-        program_id: instruction.program_id.to_string(),
-        accounts,
-        instruction_data: general_purpose::STANDARD.encode(&instruction.data),
-    };
-    
-    (StatusCode::OK, Json(json!({
-        "success": true,
-        "data": response
-    })))
+    Ok(TokenTarget { program_id, amount, decimals })
 }
 
-pub async fn sign_message(
-    Json(payload): Json<SignMessageRequest>,
-) -> (StatusCode, Json<serde_json::Value>) {
-    let secret_bytes = match bs58::decode(&payload.secret).into_vec() {
-        Ok(bytes) => bytes,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid base58 secret key"
-                })),
-            );
-        }
-    };
+pub async fn mint_token(
+    State(state): State<AppState>,
+    ValidJson(payload): ValidJson<MintTokenRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let target =
+        token_target(&state, &payload.mint, payload.amount, payload.ui_amount.as_deref(), payload.decimals).await?;
+    let instruction = match target.decimals {
+        Some(decimals) => spl_token_2022::instruction::mint_to_checked(
+            &target.program_id,
+            &payload.mint,
+            &payload.destination,
+            &payload.authority,
+            &[],
+            target.amount,
+            decimals,
+        ),
+        None => mint_to(&target.program_id, &payload.mint, &payload.destination, &payload.authority, &[], target.amount),
+    }
+    .map_err(|_| AppError::InstructionBuild("mint"))?;
+
+    Ok(built(payload.output_format, &instruction, || instruction_response(&instruction)))
+}
 
-    if secret_bytes.len() != 64 {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "success": false,
-                "error": "Secret key must be 64 bytes"
-            })),
-        );
+pub async fn sign_message(
+    State(state): State<AppState>,
+    ValidJson(payload): ValidJson<SignMessageRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let SignMessageRequest { message, secret, key_id, signature_encoding } = payload;
+    let domain = state.config().messages.domain.clone();
+    let bytes = message_bytes(domain.as_deref(), &message);
+    if domain.is_some() && tx::is_message(&bytes) {
+        return Err(AppError::Field { field: "message".to_string(), error: FieldError::TransactionMessage });
     }
+    let secret = signer(&state, secret, key_id)?;
+    let public_key = secret.pubkey().to_string();
 
-    let keypair = match Keypair::try_from(&secret_bytes[..]) {
-        Ok(kp) => kp,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid secret key format"
-                })),
-            );
-        }
-    };
+    let signature = state.crypto.run(move || secret.sign_message(&bytes)).await?;
 
-    let message_bytes = payload.message.as_bytes();
-    let signature = keypair.sign_message(message_bytes);
-    
     let response = SignMessageResponse {
-        signature: general_purpose::STANDARD.encode(signature.as_ref()),
-        public_key: keypair.pubkey().to_string(),
-        message: payload.message,
+        signature: signature_encoding.encode(&signature),
+        public_key,
+        message,
+        domain,
     };
-    
-    (StatusCode::OK, Json(json!({
-        "success": true,
-        "data": response
-    })))
+
+    Ok(success(response))
 }
 
 pub async fn verify_message(
-    Json(payload): Json<VerifyMessageRequest>,
-) -> (StatusCode, Json<serde_json::Value>) {
-    let pubkey = match payload.pubkey.parse::<Pubkey>() {
-        Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid public key"
-                })),
-            );
-        }
-    };
-    
-    let signature_bytes = match general_purpose::STANDARD.decode(&payload.signature) {
-        Ok(bytes) => bytes,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid base64 signature"
-                })),
-            );
-        }
-    };
-    
-    let signature = match Signature::try_from(signature_bytes.as_slice()) {
-        Ok(sig) => sig,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid signature format"
-                })),
-            );
-        }
-    };
-    
-    let message_bytes = payload.message.as_bytes();
-    let is_valid = signature.verify(&pubkey.to_bytes(), message_bytes);
-    
+    State(state): State<AppState>,
+    ValidJson(payload): ValidJson<VerifyMessageRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let VerifyMessageRequest { message, signature, pubkey } = payload;
+    let bytes = message_bytes(state.config().messages.domain.as_deref(), &message);
+
+    let is_valid = state.crypto.run(move || signature.verify(&pubkey.to_bytes(), &bytes)).await?;
+
     let response = VerifyMessageResponse {
         valid: is_valid,
-        message: payload.message,
-        pubkey: payload.pubkey,
+        message,
+        pubkey: pubkey.to_string(),
     };
-    
-    (StatusCode::OK, Json(json!({
-        "success": true,
-        "data": response
-    })))
+
+    Ok(success(response))
 }
 
-pub async fn send_sol(
-    Json(payload): Json<SendSolRequest>,
-) -> (StatusCode, Json<serde_json::Value>) {
-    let from_pubkey = match payload.from.parse::<Pubkey>() {
-        Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid sender address"
-                })),
-            );
-        }
+/// Verifies many signatures at once. A malformed entry fails only its own
+/// result. With `Accept: application/x-ndjson` results stream as they are
+/// verified instead of being collected into one response.
+pub async fn verify_message_batch(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    ValidJson(payload): ValidJson<BatchVerifyRequest>,
+) -> Result<Response, AppError> {
+    let results = verify_batch_stream(state.crypto.clone(), payload.items, state.config().messages.domain.clone());
+    if ndjson::accepts(&headers) {
+        return Ok(ndjson::stream(results));
+    }
+
+    let results: Vec<BatchVerifyResult> = results.collect().await;
+    let valid_count = results.iter().filter(|result| result.valid).count();
+    let response = BatchVerifyResponse {
+        total: results.len(),
+        valid_count,
+        results,
     };
 
-    let to_pubkey = match payload.to.parse::<Pubkey>() {
-        Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid recipient address"
-                })),
-            );
+    Ok(success(response).into_response())
+}
+
+const VERIFY_CHUNK: usize = 64;
+
+/// Verification runs on the crypto pool a chunk at a time, keeping the
+/// per-task overhead low without holding up the stream for the whole batch.
+fn verify_batch_stream(
+    crypto: Arc<CryptoPool>,
+    items: Vec<BatchVerifyItem>,
+    domain: Option<String>,
+) -> impl Stream<Item = BatchVerifyResult> + Send + 'static {
+    let mut chunks: Vec<Vec<(usize, BatchVerifyItem)>> = Vec::new();
+    for (index, item) in items.into_iter().enumerate() {
+        match chunks.last_mut() {
+            Some(chunk) if chunk.len() < VERIFY_CHUNK => chunk.push((index, item)),
+            _ => chunks.push(vec![(index, item)]),
         }
-    };
-    
-    if payload.lamports == 0 {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "success": false,
-                "error": "Amount must be greater than 0"
-            })),
-        );
     }
-    
-    let instruction = system_instruction::transfer(&from_pubkey, &to_pubkey, payload.lamports);
-    
-    let accounts: Vec<String> = instruction
-        .accounts
-        .iter()
-        .map(|acc| acc.pubkey.to_string())
-        .collect();
-    
-    let response = SendSolResponse {
+
+    stream::iter(chunks)
+        .then(move |chunk| {
+            let (crypto, domain) = (crypto.clone(), domain.clone());
+            async move {
+                let indices: Vec<usize> = chunk.iter().map(|(index, _)| *index).collect();
+                let verified = crypto.run(move || {
+                    chunk
+                        .into_iter()
+                        .map(|(index, item)| batch_result(index, verify_item(&item, domain.as_deref())))
+                        .collect::<Vec<_>>()
+                })
+                .await;
+
+                verified.unwrap_or_else(|err| {
+                    indices
+                        .into_iter()
+                        .map(|index| BatchVerifyResult {
+                            index,
+                            valid: false,
+                            error: Some(ItemError { code: err.code().to_string(), message: err.to_string() }),
+                        })
+                        .collect()
+                })
+            }
+        })
+        .flat_map(stream::iter)
+}
+
+fn verify_item(item: &BatchVerifyItem, domain: Option<&str>) -> Result<bool, FieldError> {
+    if item.message.len() > MAX_MESSAGE_LEN {
+        return Err(FieldError::MessageTooLong(MAX_MESSAGE_LEN));
+    }
+    let pubkey = parse_pubkey(&item.pubkey)?;
+    let signature = parse_signature(&item.signature)?;
+    Ok(signature.verify(pubkey.as_ref(), &message_bytes(domain, &item.message)))
+}
+
+/// The bytes signed for an off-chain `message`: the configured domain tag,
+/// if any, then the message itself.
+fn message_bytes(domain: Option<&str>, message: &str) -> Vec<u8> {
+    [domain.unwrap_or_default().as_bytes(), message.as_bytes()].concat()
+}
+
+fn batch_result(index: usize, outcome: Result<bool, FieldError>) -> BatchVerifyResult {
+    match outcome {
+        Ok(valid) => BatchVerifyResult { index, valid, error: None },
+        Err(err) => BatchVerifyResult {
+            index,
+            valid: false,
+            error: Some(ItemError { code: err.code().to_string(), message: err.to_string() }),
+        },
+    }
+}
+
+pub async fn send_sol(
+    State(state): State<AppState>,
+    ValidJson(payload): ValidJson<SendSolRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let instruction = send_sol_instruction(&state, &payload)?;
+    Ok(built(payload.output_format, &instruction, || instruction_response(&instruction)))
+}
+
+/// `/send/sol` answering in its old shape, with bare account addresses.
+pub async fn send_sol_v1(
+    State(state): State<AppState>,
+    ValidJson(payload): ValidJson<SendSolRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let instruction = send_sol_instruction(&state, &payload)?;
+    Ok(built(payload.output_format, &instruction, || SendSolResponse {
         program_id: instruction.program_id.to_string(),
-        accounts,
+        accounts: instruction.accounts.iter().map(|meta| meta.pubkey.to_string()).collect(),
         instruction_data: general_purpose::STANDARD.encode(&instruction.data),
-    };
-    
-    (StatusCode::OK, Json(json!({
-        "success": true,
-        "data": response
-    })))
+    }))
 }
 
-pub async fn send_token(
-    Json(payload): Json<SendTokenRequest>,
-) -> (StatusCode, Json<serde_json::Value>) {
-    let destination = match payload.destination.parse::<Pubkey>() {
-        Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid destination address"
-                })),
-            );
-        }
+/// The transfer `/send/sol` builds, shared with `/compose`.
+fn send_sol_instruction(state: &AppState, payload: &SendSolRequest) -> Result<Instruction, AppError> {
+    let (field, lamports) = match (payload.lamports, payload.amount_sol.as_deref()) {
+        (Some(lamports), _) => ("lamports", lamports),
+        (None, Some(sol)) => (
+            "amount_sol",
+            amount::sol_to_lamports(sol)
+                .map_err(|error| AppError::Field { field: "amount_sol".to_string(), error })?,
+        ),
+        (None, None) => unreachable!("validated: exactly one of lamports or amount_sol"),
     };
+    check_dust(state, field, lamports)?;
+    let to = recipient(state, &payload.from, &payload.to, "to")?;
+    Ok(system_instruction::transfer(&payload.from, &to, lamports))
+}
 
-    let mint = match payload.mint.parse::<Pubkey>() {
-        Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid mint address"
-                })),
-            );
-        }
-    };
+/// `to` with address-book labels resolved. Validation only caught transfers
+/// to self given as a literal key.
+fn recipient(state: &AppState, from: &Pubkey, to: &AddressRef, field: &str) -> Result<Pubkey, AppError> {
+    let to = state.address_book.resolve(to, field)?;
+    if to == *from {
+        return Err(AppError::Field { field: field.to_string(), error: FieldError::SelfTransfer });
+    }
+    Ok(to)
+}
 
-    let owner = match payload.owner.parse::<Pubkey>() {
-        Ok(pk) => pk,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid owner address"
-                })),
-            );
-        }
+fn check_dust(state: &AppState, field: &str, lamports: u64) -> Result<(), AppError> {
+    match state.config().transfers.dust_threshold_lamports {
+        Some(threshold) if lamports < threshold => Err(AppError::Field {
+            field: field.to_string(),
+            error: FieldError::BelowDustThreshold(threshold),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// One system transfer per entry, all from `from`. With `pack` they come
+/// back as unsigned transactions, as few as the size limit allows, rather
+/// than as instructions for the caller to assemble.
+pub async fn send_sol_batch(
+    State(state): State<AppState>,
+    ValidJson(payload): ValidJson<SendSolBatchRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    for (i, transfer) in payload.transfers.iter().enumerate() {
+        check_dust(&state, &format!("transfers[{i}].lamports"), transfer.lamports)?;
+    }
+    let total_lamports = payload.transfers.iter().map(|transfer| transfer.lamports).sum();
+    let instructions: Vec<Instruction> = payload
+        .transfers
+        .iter()
+        .enumerate()
+        .map(|(i, transfer)| {
+            let to = recipient(&state, &payload.from, &transfer.to, &format!("transfers[{i}].to"))?;
+            Ok(system_instruction::transfer(&payload.from, &to, transfer.lamports))
+        })
+        .collect::<Result<_, AppError>>()?;
+
+    let mut response = SendSolBatchResponse {
+        transfer_count: instructions.len(),
+        total_lamports,
+        total_sol: amount::lamports_to_sol(total_lamports).to_string(),
+        instructions: Vec::new(),
+        recent_blockhash: None,
+        transactions: Vec::new(),
     };
-    
-    if payload.amount == 0 {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "success": false,
-                "error": "Amount must be greater than 0"
-            })),
-        );
+    if !payload.pack {
+        response.instructions = instructions.iter().map(instruction_response).collect();
+        return Ok(success(response));
     }
-    
-    let source_ata = get_associated_token_address(&owner, &mint);
-    let destination_ata = get_associated_token_address(&destination, &mint);
-    
-    let instruction = match transfer(
-        &spl_token::id(),
-        &source_ata,
-        &destination_ata,
-        &owner,
-        &[],
-        payload.amount,
-    ) {
-        Ok(inst) => inst,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Failed to create transfer instruction"
-                })),
-            );
-        }
+
+    let blockhash = match payload.recent_blockhash {
+        Some(hash) => *hash,
+        None => state.latest_blockhash().await?.blockhash,
     };
-    
-    let accounts: Vec<crate::models::SendTokenAccount> = instruction
-        .accounts
-        .iter()
-        .map(|acc| crate::models::SendTokenAccount {
-            pubkey: acc.pubkey.to_string(),
-            is_signer: acc.is_signer,
+    let groups = instructions.into_iter().map(|instruction| vec![instruction]).collect();
+    response.transactions = tx::pack(groups, &payload.from, blockhash)?
+        .into_iter()
+        .enumerate()
+        .map(|(index, packed)| SolBatchTransaction {
+            index,
+            transaction: tx::encode(&packed.transaction),
+            transfers: [packed.groups.start, packed.groups.end],
+            size: packed.size,
         })
         .collect();
-    
-    let response = SendTokenResponse {
+    response.recent_blockhash = Some(blockhash.to_string());
+
+    Ok(success(response))
+}
+
+pub async fn send_token(
+    State(state): State<AppState>,
+    ValidJson(payload): ValidJson<SendTokenRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let TokenTransfer { instruction, source_ata, destination_ata } = send_token_instruction(&state, &payload).await?;
+
+    Ok(built(payload.output_format, &instruction, || SendTokenResponse {
         program_id: instruction.program_id.to_string(),
-        accounts,
+        accounts: instruction
+            .accounts
+            .iter()
+            .map(|acc| crate::models::SendTokenAccount {
+                pubkey: acc.pubkey.to_string(),
+                is_signer: acc.is_signer,
+            })
+            .collect(),
         instruction_data: general_purpose::STANDARD.encode(&instruction.data),
-    };
-    
-    (StatusCode::OK, Json(json!({
-        "success": true,
-        "data": response
-    })))
-}
\ No newline at end of file
+        source_ata: source_ata.to_string(),
+        destination_ata: destination_ata.to_string(),
+        required_signers: instruction
+            .accounts
+            .iter()
+            .filter(|acc| acc.is_signer)
+            .map(|acc| acc.pubkey.to_string())
+            .collect(),
+    }))
+}
+
+struct TokenTransfer {
+    instruction: Instruction,
+    source_ata: Pubkey,
+    destination_ata: Pubkey,
+}
+
+/// The transfer `/send/token` builds, shared with `/compose`.
+async fn send_token_instruction(state: &AppState, payload: &SendTokenRequest) -> Result<TokenTransfer, AppError> {
+    let target =
+        token_target(state, &payload.mint, payload.amount, payload.ui_amount.as_deref(), payload.decimals).await?;
+    let destination = state.address_book.resolve(&payload.destination, "destination")?;
+    if destination == *payload.owner {
+        return Err(AppError::Field { field: "destination".to_string(), error: FieldError::SameTokenAccount });
+    }
+    let source_ata = get_associated_token_address_with_program_id(&payload.owner, &payload.mint, &target.program_id);
+    let destination_ata =
+        get_associated_token_address_with_program_id(&destination, &payload.mint, &target.program_id);
+
+    let authority = payload.delegate.as_deref().unwrap_or(&payload.owner);
+    let signers: Vec<&Pubkey> = payload.multisig_signers.iter().map(|signer| &signer.0).collect();
+    let instruction = match target.decimals {
+        Some(decimals) => spl_token_2022::instruction::transfer_checked(
+            &target.program_id,
+            &source_ata,
+            &payload.mint,
+            &destination_ata,
+            authority,
+            &signers,
+            target.amount,
+            decimals,
+        ),
+        None => transfer(&target.program_id, &source_ata, &destination_ata, authority, &signers, target.amount),
+    }
+    .map_err(|_| AppError::InstructionBuild("transfer"))?;
+    Ok(TokenTransfer { instruction, source_ata, destination_ata })
+}
@@ -0,0 +1,99 @@
+use axum::extract::{Path, State};
+use solana_sdk::{account::Account, instruction::Instruction, pubkey::Pubkey};
+use spl_token_2022::extension::{transfer_fee::TransferFeeAmount, BaseStateWithExtensions, StateWithExtensions};
+use spl_token_2022::state::{Account as TokenAccount, AccountState};
+
+use super::success;
+use crate::errors::AppError;
+use crate::extract::Json;
+use crate::models::account::{CleanupRequest, CleanupResponse, CleanupTransaction, ClosedAccount};
+use crate::state::AppState;
+use crate::tx;
+use crate::utils::parse_pubkey;
+
+/// Finds `owner`'s empty token accounts under both token programs and
+/// builds unsigned transactions closing them, paid for by the owner, to
+/// reclaim their rent.
+pub async fn cleanup(
+    State(state): State<AppState>,
+    Path(owner): Path<String>,
+    Json(request): Json<CleanupRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let owner = parse_pubkey(&owner).map_err(|error| AppError::Field { field: "owner".to_string(), error })?;
+    let destination = request.destination.map_or(owner, |destination| destination.0);
+    let rpc = state.rpc()?;
+
+    let mut closable = Vec::new();
+    for program_id in [spl_token::id(), spl_token_2022::id()] {
+        for (address, account) in rpc.get_token_accounts_by_owner(&owner, &program_id).await? {
+            if let Some(mint) = closable_mint(&account, &owner)
+                && !request.keep_mints.iter().any(|keep| keep.0 == mint)
+            {
+                closable.push((address, mint, account));
+            }
+        }
+    }
+
+    let groups = closable
+        .iter()
+        .map(|(address, _, account)| close(&account.owner, address, &destination, &owner).map(|ix| vec![ix]))
+        .collect::<Result<Vec<_>, _>>()?;
+    let closed: Vec<ClosedAccount> = closable
+        .iter()
+        .map(|(address, mint, account)| ClosedAccount {
+            address: address.to_string(),
+            mint: mint.to_string(),
+            token_program: account.owner.to_string(),
+            lamports: account.lamports,
+        })
+        .collect();
+
+    let (recent_blockhash, transactions) = if groups.is_empty() {
+        (None, Vec::new())
+    } else {
+        let blockhash = match request.recent_blockhash {
+            Some(hash) => *hash,
+            None => state.latest_blockhash().await?.blockhash,
+        };
+        let transactions = tx::pack(groups, &owner, blockhash)?
+            .into_iter()
+            .enumerate()
+            .map(|(index, packed)| CleanupTransaction {
+                index,
+                transaction: tx::encode(&packed.transaction),
+                accounts: [packed.groups.start, packed.groups.end],
+                size: packed.size,
+            })
+            .collect();
+        (Some(blockhash.to_string()), transactions)
+    };
+
+    Ok(success(CleanupResponse {
+        owner: owner.to_string(),
+        reclaimed_lamports: closed.iter().map(|account| account.lamports).sum(),
+        closed,
+        recent_blockhash,
+        transactions,
+    }))
+}
+
+/// The mint of a token account `owner` can close right now: empty, not
+/// frozen, closable by the owner and holding no withheld transfer fees.
+fn closable_mint(account: &Account, owner: &Pubkey) -> Option<Pubkey> {
+    let state = StateWithExtensions::<TokenAccount>::unpack(&account.data).ok()?;
+    let base = state.base;
+    let withheld = state
+        .get_extension::<TransferFeeAmount>()
+        .map_or(0, |fees| u64::from(fees.withheld_amount));
+
+    let closable = base.amount == 0
+        && base.state == AccountState::Initialized
+        && Option::<Pubkey>::from(base.close_authority).is_none_or(|authority| authority == *owner)
+        && withheld == 0;
+    closable.then_some(base.mint)
+}
+
+fn close(program_id: &Pubkey, account: &Pubkey, destination: &Pubkey, owner: &Pubkey) -> Result<Instruction, AppError> {
+    spl_token_2022::instruction::close_account(program_id, account, destination, owner, &[])
+        .map_err(|_| AppError::InstructionBuild("close_account"))
+}
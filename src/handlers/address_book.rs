@@ -0,0 +1,40 @@
+use axum::extract::{Path, State};
+
+use super::success;
+use crate::errors::AppError;
+use crate::extract::{Json, ValidJson};
+use crate::models::address_book::{CreateAddressRequest, UpdateAddressRequest};
+use crate::state::AppState;
+
+pub async fn create(
+    State(state): State<AppState>,
+    ValidJson(request): ValidJson<CreateAddressRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    Ok(success(state.address_book.insert(request.label, *request.address, request.note)?))
+}
+
+pub async fn list(State(state): State<AppState>) -> Result<Json<serde_json::Value>, AppError> {
+    Ok(success(state.address_book.list()))
+}
+
+pub async fn get(
+    State(state): State<AppState>,
+    Path(label): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    Ok(success(state.address_book.get(&label)?))
+}
+
+pub async fn update(
+    State(state): State<AppState>,
+    Path(label): Path<String>,
+    Json(request): Json<UpdateAddressRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    Ok(success(state.address_book.update(&label, *request.address, request.note)?))
+}
+
+pub async fn delete(
+    State(state): State<AppState>,
+    Path(label): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    Ok(success(state.address_book.remove(&label)?))
+}
@@ -0,0 +1,119 @@
+use std::time::{Duration, Instant};
+
+use axum::extract::State;
+
+use super::success;
+use crate::errors::AppError;
+use crate::extract::{Admin, Json};
+use crate::models::admin::{
+    AdminStatus, FeaturesRequest, Health, RevokeRequest, RotateResponse, RpcHealth, RuntimeStatus, RuntimeTasks,
+};
+use crate::state::AppState;
+
+fn status(state: &AppState) -> AdminStatus {
+    let config = state.config();
+    let mut revoked: Vec<String> = state.revoked.read().unwrap().iter().cloned().collect();
+    revoked.sort();
+    AdminStatus {
+        mode: config.mode,
+        features: config.features,
+        master_key_version: state.keystore.version(),
+        revoked,
+    }
+}
+
+/// Records an admin action, which moves no funds, in the audit log.
+fn audited<T>(state: &AppState, action: &'static str, outcome: Result<T, AppError>) -> Result<T, AppError> {
+    state.audit.record(action, &[], outcome.as_ref().map(|_| ()));
+    outcome
+}
+
+/// Unauthenticated, unlike the rest of `/admin`, so probes need no key.
+pub async fn health(State(state): State<AppState>) -> Json<serde_json::Value> {
+    success(Health { mode: state.config().mode, rpc: state.rpc.get().is_some() })
+}
+
+/// How long `/debug/runtime` waits on the RPC node before reporting it
+/// unhealthy.
+const RPC_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Task counts, job queue depth, blockhash cache age and a live probe of the
+/// RPC node, for diagnosing a stuck or slow instance.
+pub async fn runtime(State(state): State<AppState>, _: Admin) -> Json<serde_json::Value> {
+    let metrics = tokio::runtime::Handle::current().metrics();
+    let runtime = RuntimeTasks {
+        workers: metrics.num_workers(),
+        alive_tasks: metrics.num_alive_tasks(),
+        global_queue_depth: metrics.global_queue_depth(),
+    };
+    let blockhash_age_ms = state.blockhash.age().await.map(|age| age.as_millis() as u64);
+    let rpc = match state.rpc.get() {
+        None => RpcHealth { configured: false, slot: None, latency_ms: None, error: None },
+        Some(rpc) => {
+            let started = Instant::now();
+            let probe = tokio::time::timeout(RPC_PROBE_TIMEOUT, rpc.get_slot()).await;
+            let latency_ms = Some(started.elapsed().as_millis() as u64);
+            match probe {
+                Ok(Ok(slot)) => RpcHealth { configured: true, slot: Some(slot), latency_ms, error: None },
+                Ok(Err(err)) => RpcHealth { configured: true, slot: None, latency_ms, error: Some(err.to_string()) },
+                Err(_) => RpcHealth { configured: true, slot: None, latency_ms, error: Some("timed out".to_string()) },
+            }
+        }
+    };
+    success(RuntimeStatus { runtime, jobs: state.jobs.depth(), blockhash_age_ms, rpc })
+}
+
+pub async fn get(State(state): State<AppState>, _: Admin) -> Result<Json<serde_json::Value>, AppError> {
+    Ok(success(status(&state)))
+}
+
+/// Re-reads the config file and environment, as SIGHUP does; see
+/// `AppState::reload` for what takes effect.
+pub async fn reload(State(state): State<AppState>, _: Admin) -> Result<Json<serde_json::Value>, AppError> {
+    audited(&state, "admin.reload", state.reload().await.map_err(AppError::from))?;
+    Ok(success(status(&state)))
+}
+
+/// Replaces the keystore master key, re-encrypting every stored key.
+pub async fn rotate_master_key(
+    State(state): State<AppState>,
+    _: Admin,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let (master_key_version, reencrypted) = state.keystore.rotate();
+    audited(&state, "admin.rotate_master_key", Ok(()))?;
+    Ok(success(RotateResponse { master_key_version, reencrypted }))
+}
+
+/// Stops an identity's API key from authenticating, across reloads, until
+/// restart. Client certificates mapped to the same identity still work.
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    _: Admin,
+    Json(request): Json<RevokeRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let known = state.config().approvals.identities.contains_key(&request.identity);
+    let outcome = if known {
+        state.revoked.write().unwrap().insert(request.identity);
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!("API key identity `{}`", request.identity)))
+    };
+    audited(&state, "admin.revoke_api_key", outcome)?;
+    Ok(success(status(&state)))
+}
+
+/// Switches optional services on or off until the next reload.
+pub async fn features(
+    State(state): State<AppState>,
+    _: Admin,
+    Json(request): Json<FeaturesRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    state.config.update(|config| {
+        let features = &mut config.features;
+        features.relayer = request.relayer.unwrap_or(features.relayer);
+        features.bundles = request.bundles.unwrap_or(features.bundles);
+        features.jobs = request.jobs.unwrap_or(features.jobs);
+    });
+    audited(&state, "admin.features", Ok(()))?;
+    Ok(success(status(&state)))
+}
@@ -0,0 +1,127 @@
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashSet;
+
+use futures::stream;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use solana_system_interface::instruction as system_instruction;
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+
+use super::success;
+use crate::cache::MintInfo;
+use crate::errors::AppError;
+use crate::extract::Bulk;
+use crate::models::airdrop::{
+    AirdropDryRun, AirdropLine, AirdropRecipient, AirdropTransaction, BulkAirdropRequest, BulkAirdropResponse,
+};
+use crate::models::ItemError;
+use crate::ndjson;
+use crate::state::AppState;
+use crate::tx;
+
+/// Builds unsigned transactions paying every recipient from `payer`, packed
+/// as tightly as the size limit allows. Token airdrops create each
+/// recipient's ATA idempotently in the same transaction as its transfer.
+/// With `Accept: application/x-ndjson` each transaction is built and sent on
+/// its own line as the stream is read, and a recipient that can't be packed
+/// ends the stream with an error line. Recipients may also be uploaded as
+/// CSV; `dry_run` stops after validation and only reports totals.
+pub async fn bulk(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Bulk(request): Bulk<BulkAirdropRequest>,
+) -> Result<Response, AppError> {
+    let total_amount = request.recipients.iter().map(|entry| entry.amount).sum();
+    if request.dry_run {
+        let unique: HashSet<_> = request.recipients.iter().map(|entry| entry.recipient).collect();
+        return Ok(success(AirdropDryRun {
+            asset: request.mint.map_or_else(|| "SOL".to_string(), |mint| mint.to_string()),
+            recipient_count: request.recipients.len(),
+            unique_recipients: unique.len(),
+            total_amount,
+        })
+        .into_response());
+    }
+
+    let payer = *request.payer;
+    let blockhash = match request.recent_blockhash {
+        Some(hash) => *hash,
+        None => state.latest_blockhash().await?.blockhash,
+    };
+    let token = match request.mint {
+        Some(mint) => Some((*mint, state.accounts.mint(&mint).await?)),
+        None => None,
+    };
+    let asset = token.map_or_else(|| "SOL".to_string(), |(mint, _)| mint.to_string());
+    let token_program = token.map(|(_, info)| info.program_id.to_string());
+    let recipient_count = request.recipients.len();
+
+    // Nothing is built up front: each recipient's instructions are made as
+    // the packer reaches them, and each transaction as it is read.
+    let groups = request.recipients.into_iter().map(move |entry| match &token {
+        None => Ok(vec![system_instruction::transfer(&payer, &entry.recipient, entry.amount)]),
+        Some((mint, info)) => token_airdrop(&payer, info, mint, &entry),
+    });
+    let transactions = tx::packer(groups, &payer, blockhash).enumerate().map(|(index, packed)| {
+        packed.map(|packed| AirdropTransaction {
+            index,
+            transaction: tx::encode(&packed.transaction),
+            recipients: [packed.groups.start, packed.groups.end],
+            size: packed.size,
+        })
+    });
+
+    if ndjson::accepts(&headers) {
+        let lines = transactions.map(|transaction| match transaction {
+            Ok(transaction) => AirdropLine::Transaction(transaction),
+            Err(err) => {
+                let error = ItemError { code: err.code().to_string(), message: err.to_string() };
+                AirdropLine::Failed { error }
+            }
+        });
+        return Ok(ndjson::stream(stream::iter(lines)));
+    }
+
+    let response = BulkAirdropResponse {
+        asset,
+        token_program,
+        recipient_count,
+        total_amount,
+        recent_blockhash: blockhash.to_string(),
+        transactions: transactions.collect::<Result<_, _>>()?,
+    };
+
+    Ok(success(response).into_response())
+}
+
+fn token_airdrop(
+    payer: &Pubkey,
+    mint: &MintInfo,
+    mint_address: &Pubkey,
+    entry: &AirdropRecipient,
+) -> Result<Vec<Instruction>, AppError> {
+    let program_id = mint.program_id;
+    let source = get_associated_token_address_with_program_id(payer, mint_address, &program_id);
+    let destination = get_associated_token_address_with_program_id(&entry.recipient, mint_address, &program_id);
+
+    let create = create_associated_token_account_idempotent(payer, &entry.recipient, mint_address, &program_id);
+    let transfer = spl_token_2022::instruction::transfer_checked(
+        &program_id,
+        &source,
+        mint_address,
+        &destination,
+        payer,
+        &[],
+        entry.amount,
+        mint.decimals,
+    )
+    .map_err(|_| AppError::InstructionBuild("transfer_checked"))?;
+
+    Ok(vec![create, transfer])
+}
@@ -0,0 +1,103 @@
+use axum::extract::State;
+use solana_address_lookup_table_interface::{instruction as alt_instruction, state::LOOKUP_TABLE_MAX_ADDRESSES};
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, AddressLookupTableAccount, Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+
+use super::jobs::instructions;
+use super::{instruction_response, success};
+use crate::errors::AppError;
+use crate::extract::{Json, ValidJson};
+use crate::models::alt::{AltPlanRequest, AltPlanResponse};
+use crate::state::AppState;
+use crate::tx::{self, MAX_TRANSACTION_SIZE};
+
+/// Addresses per extend instruction, few enough that each fits a
+/// transaction of its own.
+const EXTEND_BATCH: usize = 20;
+
+/// Plans a lookup table for `instructions`: every account that isn't a
+/// signer or an invoked program moves into it. Returns the instructions
+/// creating and filling the table, and the transaction's size before and
+/// after.
+pub async fn plan(
+    State(state): State<AppState>,
+    ValidJson(request): ValidJson<AltPlanRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let payer = *request.payer;
+    let authority = request.authority.map_or(payer, |authority| *authority);
+    let instructions = instructions(&request.instructions)?;
+    let recent_slot = match request.recent_slot {
+        Some(slot) => slot,
+        None => state.rpc()?.get_slot().await?,
+    };
+
+    let message = Message::new_with_blockhash(&instructions, Some(&payer), &Hash::default());
+    // Programs must be invoked through static keys, and signatures are
+    // checked against static keys only.
+    let movable = |index: usize| {
+        !message.is_signer(index) && !message.instructions.iter().any(|ix| usize::from(ix.program_id_index) == index)
+    };
+    let mut addresses = Vec::new();
+    let mut static_accounts = Vec::new();
+    for (index, key) in message.account_keys.iter().enumerate() {
+        if movable(index) {
+            addresses.push(*key);
+        } else {
+            static_accounts.push(*key);
+        }
+    }
+    if addresses.is_empty() {
+        return Err(AppError::InvalidField {
+            field: "instructions".to_string(),
+            message: "every account is a signer or invoked program, so none can move into a lookup table".to_string(),
+        });
+    }
+    if addresses.len() > LOOKUP_TABLE_MAX_ADDRESSES {
+        return Err(AppError::InvalidField {
+            field: "instructions".to_string(),
+            message: format!("a lookup table holds at most {LOOKUP_TABLE_MAX_ADDRESSES} addresses"),
+        });
+    }
+
+    let (create, table) = alt_instruction::create_lookup_table(authority, payer, recent_slot);
+    let mut setup = vec![create];
+    setup.extend(
+        addresses
+            .chunks(EXTEND_BATCH)
+            .map(|batch| alt_instruction::extend_lookup_table(table, authority, Some(payer), batch.to_vec())),
+    );
+
+    let legacy_size = tx::serialized_size(&tx::unsigned(&instructions, &payer, Hash::default()));
+    let lookup = AddressLookupTableAccount { key: table, addresses: addresses.clone() };
+    let v0_size = v0_size(&payer, &instructions, lookup)?;
+    let response = AltPlanResponse {
+        lookup_table: table.to_string(),
+        authority: authority.to_string(),
+        recent_slot,
+        addresses: addresses.iter().map(ToString::to_string).collect(),
+        static_accounts: static_accounts.iter().map(ToString::to_string).collect(),
+        instructions: setup.iter().map(instruction_response).collect(),
+        legacy_size,
+        v0_size,
+        fits: v0_size <= MAX_TRANSACTION_SIZE,
+    };
+    Ok(success(response))
+}
+
+/// Wire size of `instructions` as a v0 transaction using `table`, with
+/// placeholder signatures.
+fn v0_size(payer: &Pubkey, instructions: &[Instruction], table: AddressLookupTableAccount) -> Result<usize, AppError> {
+    let message = v0::Message::try_compile(payer, instructions, &[table], Hash::default())
+        .map_err(|err| AppError::InvalidInput(format!("cannot compile a v0 message: {err}")))?;
+    let transaction = VersionedTransaction {
+        signatures: vec![Signature::default(); usize::from(message.header.num_required_signatures)],
+        message: VersionedMessage::V0(message),
+    };
+    Ok(bincode::serialized_size(&transaction).expect("transactions serialize") as usize)
+}
@@ -0,0 +1,172 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::State;
+use base64::{Engine as _, engine::general_purpose};
+use serde_json::{Map, Value};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+use super::success;
+use crate::anchor::{self, Idl, IdlAccount};
+use crate::borsh::{lookup, same_name, Type};
+use crate::errors::{AppError, FieldError};
+use crate::extract::Json;
+use crate::models::anchor::{
+    AnchorEvent, AnchorInstructionRequest, ParseLogsRequest, ParseLogsResponse, RegisterIdlRequest,
+    RegisterIdlResponse,
+};
+use crate::models::{AccountMeta, InstructionResponse, OutputFormat, Web3Instruction};
+use crate::state::AppState;
+use crate::utils::parse_pubkey;
+
+pub async fn register_idl(
+    State(state): State<AppState>,
+    Json(request): Json<RegisterIdlRequest>,
+) -> Result<Json<Value>, AppError> {
+    let idl = Idl::parse(request.idl)?;
+    let program_id = request
+        .program_id
+        .map(|program_id| *program_id)
+        .or(idl.address)
+        .ok_or_else(|| AppError::MissingField { field: "program_id".to_string() })?;
+
+    let idl = state.idls.insert(program_id, idl);
+    Ok(success(RegisterIdlResponse {
+        program_id: program_id.to_string(),
+        name: idl.name.clone(),
+        instructions: idl.instructions.iter().map(|ix| ix.name.clone()).collect(),
+        events: idl.events.iter().map(|event| event.name.clone()).collect(),
+    }))
+}
+
+/// Builds an instruction from a registered IDL: the discriminator followed
+/// by the Borsh-encoded args, with accounts in IDL order.
+pub async fn instruction(
+    State(state): State<AppState>,
+    Json(request): Json<AnchorInstructionRequest>,
+) -> Result<Json<Value>, AppError> {
+    let program_id = *request.program_id;
+    let idl = registered(&state, &program_id)?;
+    let ix = idl.instruction(&request.method).ok_or_else(|| AppError::InvalidField {
+        field: "method".to_string(),
+        message: format!("`{}` has no instruction `{}`", idl.name, request.method),
+    })?;
+
+    if let Some(unknown) = request.args.keys().find(|key| lookup_arg(&ix.args, key).is_none()) {
+        return Err(AppError::InvalidField {
+            field: format!("args.{unknown}"),
+            message: format!("`{}` takes no such argument", ix.name),
+        });
+    }
+
+    let mut data = ix.discriminator.to_vec();
+    for (name, ty) in &ix.args {
+        let path = format!("args.{name}");
+        let value = lookup(&request.args, name).ok_or_else(|| AppError::MissingField { field: path.clone() })?;
+        idl.schema.encode(ty, value, &path, &mut data)?;
+    }
+
+    let mut accounts = ix
+        .accounts
+        .iter()
+        .map(|account| resolve(account, &request.accounts, &program_id))
+        .collect::<Result<Vec<_>, _>>()?;
+    accounts.extend(request.remaining_accounts.iter().map(|account| AccountMeta {
+        pubkey: account.pubkey.to_string(),
+        is_signer: account.is_signer,
+        is_writable: account.is_writable,
+    }));
+
+    let response = InstructionResponse {
+        program_id: program_id.to_string(),
+        accounts,
+        instruction_data: general_purpose::STANDARD.encode(&data),
+    };
+    Ok(match request.output_format {
+        OutputFormat::Superdev => success(response),
+        OutputFormat::Web3js => success(Web3Instruction::from(response)),
+    })
+}
+
+/// Decodes the Anchor events `program_id` emitted, from logs passed in or
+/// fetched for a transaction signature.
+pub async fn parse_logs(
+    State(state): State<AppState>,
+    Json(request): Json<ParseLogsRequest>,
+) -> Result<Json<Value>, AppError> {
+    let program_id = *request.program_id;
+    let idl = registered(&state, &program_id)?;
+
+    let logs = match (request.logs, request.signature) {
+        (Some(logs), None) => logs,
+        (None, Some(signature)) => {
+            let signature = Signature::from_str(&signature).map_err(|_| AppError::InvalidField {
+                field: "signature".to_string(),
+                message: "expected a base58 transaction signature".to_string(),
+            })?;
+            state
+                .rpc()?
+                .get_transaction_logs(&signature)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Transaction {signature}")))?
+        }
+        _ => {
+            return Err(AppError::Field {
+                field: "logs".to_string(),
+                error: FieldError::ExactlyOne("logs or signature"),
+            });
+        }
+    };
+
+    let mut events = Vec::new();
+    for (index, data) in anchor::program_data(&logs, &program_id) {
+        let path = format!("logs[{index}]");
+        let Ok(bytes) = general_purpose::STANDARD.decode(data) else {
+            return Err(AppError::InvalidField { field: path, message: "program data is not base64".to_string() });
+        };
+        // Programs may log data that isn't an event; only known discriminators count.
+        if let Some(decoded) = idl.decode_event(&bytes, &path) {
+            let (event, data) = decoded?;
+            events.push(AnchorEvent { name: event.name.clone(), log_index: index, data });
+        }
+    }
+
+    Ok(success(ParseLogsResponse { events }))
+}
+
+fn registered(state: &AppState, program_id: &Pubkey) -> Result<Arc<Idl>, AppError> {
+    state
+        .idls
+        .get(program_id)
+        .ok_or_else(|| AppError::NotFound(format!("IDL for program {program_id}")))
+}
+
+fn lookup_arg<'a>(args: &'a [(String, Type)], key: &str) -> Option<&'a Type> {
+    args.iter().find(|(name, _)| same_name(name, key)).map(|(_, ty)| ty)
+}
+
+/// Finds the caller's pubkey for an IDL account. Omitted optional accounts
+/// become the program id, which is how Anchor encodes `None`.
+fn resolve(account: &IdlAccount, given: &Map<String, Value>, program_id: &Pubkey) -> Result<AccountMeta, AppError> {
+    let field = format!("accounts.{}", account.path.join("."));
+    let mut value = None;
+    let mut scope = Some(given);
+    for segment in &account.path {
+        value = scope.and_then(|map| lookup(map, segment));
+        scope = value.and_then(Value::as_object);
+    }
+
+    let pubkey = match (value, account.address) {
+        (Some(Value::String(key)), _) => parse_pubkey(key).map_err(|error| AppError::Field { field, error })?,
+        (Some(Value::Null) | None, Some(address)) => address,
+        (Some(Value::Null) | None, None) if account.optional => {
+            return Ok(AccountMeta { pubkey: program_id.to_string(), is_signer: false, is_writable: false });
+        }
+        (Some(Value::Null) | None, None) => return Err(AppError::MissingField { field }),
+        (Some(_), _) => {
+            return Err(AppError::InvalidField { field, message: "expected a base58 public key".to_string() });
+        }
+    };
+
+    Ok(AccountMeta { pubkey: pubkey.to_string(), is_signer: account.signer, is_writable: account.writable })
+}
@@ -0,0 +1,46 @@
+use axum::extract::{Path, State};
+
+use super::success;
+use crate::das;
+use crate::errors::{AppError, FieldError};
+use crate::extract::{Json, Query};
+use crate::models::assets::{AssetsByOwnerQuery, AssetsByOwnerResponse};
+use crate::state::AppState;
+use crate::utils::parse_pubkey;
+
+const DEFAULT_ASSETS_PAGE: u32 = 100;
+/// The largest page DAS providers serve.
+const MAX_ASSETS_PAGE: u32 = 1_000;
+
+/// One asset by id (its mint, or for compressed NFTs its asset id).
+pub async fn asset(State(state): State<AppState>, Path(id): Path<String>) -> Result<Json<serde_json::Value>, AppError> {
+    let id = parse_pubkey(&id).map_err(|error| AppError::Field { field: "id".to_string(), error })?;
+    let asset = state.das()?.get_asset(&id).await?.ok_or_else(|| AppError::NotFound(format!("Asset {id}")))?;
+    Ok(success(das::normalize(&asset)))
+}
+
+/// Regular and compressed assets held by `owner`, one provider page at a
+/// time.
+pub async fn by_owner(
+    State(state): State<AppState>,
+    Query(query): Query<AssetsByOwnerQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let page = query.page.unwrap_or(1);
+    if page == 0 {
+        return Err(AppError::InvalidField { field: "page".to_string(), message: "pages start at 1".to_string() });
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_ASSETS_PAGE);
+    if !(1..=MAX_ASSETS_PAGE).contains(&limit) {
+        let error = FieldError::BatchSize(MAX_ASSETS_PAGE as usize);
+        return Err(AppError::Field { field: "limit".to_string(), error });
+    }
+
+    let assets = state.das()?.get_assets_by_owner(&query.owner, page, limit).await?;
+    Ok(success(AssetsByOwnerResponse {
+        owner: query.owner.to_string(),
+        page,
+        limit,
+        total: assets.total,
+        items: assets.items.iter().map(das::normalize).collect(),
+    }))
+}
@@ -0,0 +1,12 @@
+use axum::extract::State;
+
+use super::success;
+use crate::errors::AppError;
+use crate::extract::Json;
+use crate::state::AppState;
+use crate::tenant;
+
+/// The caller's tenant's recent policy decisions, oldest first.
+pub async fn list(State(state): State<AppState>) -> Result<Json<serde_json::Value>, AppError> {
+    Ok(success(state.audit.entries(&tenant::current())))
+}
@@ -0,0 +1,71 @@
+use axum::extract::{Path, State};
+use solana_sdk::pubkey::Pubkey;
+
+use super::success;
+use crate::errors::{AppError, FieldError};
+use crate::extract::{Json, Query};
+use crate::models::block::{BlockQuery, BlockResponse};
+use crate::rpc::ConfirmedTransaction;
+use crate::state::AppState;
+use crate::summary;
+
+const DEFAULT_BLOCK_PAGE: usize = 100;
+const MAX_BLOCK_PAGE: usize = 1_000;
+
+/// A confirmed block's transactions, each summarized like
+/// `/transaction/parse/{signature}`.
+pub async fn block(
+    State(state): State<AppState>,
+    Path(slot): Path<String>,
+    Query(query): Query<BlockQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let slot: u64 = slot.parse().map_err(|_| AppError::InvalidField {
+        field: "slot".to_string(),
+        message: "expected a slot number".to_string(),
+    })?;
+    let limit = query.limit.unwrap_or(DEFAULT_BLOCK_PAGE);
+    if !(1..=MAX_BLOCK_PAGE).contains(&limit) {
+        return Err(AppError::Field { field: "limit".to_string(), error: FieldError::BatchSize(MAX_BLOCK_PAGE) });
+    }
+
+    let block = state
+        .rpc()?
+        .get_block(slot)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Block {slot}")))?;
+
+    let matching: Vec<_> = block
+        .transactions
+        .iter()
+        .filter(|confirmed| query.address.as_ref().is_none_or(|address| involves(confirmed, address)))
+        .collect();
+    let transaction_count = matching.len();
+    let offset = query.offset.unwrap_or(0);
+    let transactions = matching
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .filter_map(|confirmed| {
+            let signature = confirmed.transaction.signatures.first()?;
+            Some(summary::summarize(signature, confirmed))
+        })
+        .collect();
+
+    let next_offset = offset.saturating_add(limit);
+    Ok(success(BlockResponse {
+        slot: block.slot,
+        blockhash: block.blockhash.to_string(),
+        parent_slot: block.parent_slot,
+        block_time: block.block_time,
+        block_height: block.block_height,
+        transaction_count,
+        transactions,
+        next_offset: (next_offset < transaction_count).then_some(next_offset),
+    }))
+}
+
+fn involves(confirmed: &ConfirmedTransaction, address: &Pubkey) -> bool {
+    confirmed.transaction.message.static_account_keys().contains(address)
+        || confirmed.meta.loaded_writable.contains(address)
+        || confirmed.meta.loaded_readonly.contains(address)
+}
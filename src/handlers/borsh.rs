@@ -0,0 +1,48 @@
+use base64::{Engine as _, engine::general_purpose};
+
+use super::success;
+use crate::borsh::{Schema, Type};
+use crate::errors::AppError;
+use crate::extract::Json;
+use crate::models::borsh::{
+    BorshDecodeRequest, BorshDecodeResponse, BorshEncodeRequest, BorshEncodeResponse, SchemaDescription,
+};
+
+fn schema(description: SchemaDescription) -> Result<(Schema, Type), AppError> {
+    let schema = Schema::from_decls(description.types)
+        .map_err(|message| AppError::InvalidField { field: "schema.types".to_string(), message })?;
+    Ok((schema, description.ty))
+}
+
+pub async fn encode(Json(request): Json<BorshEncodeRequest>) -> Result<Json<serde_json::Value>, AppError> {
+    let (schema, ty) = schema(request.schema)?;
+    let mut data = Vec::new();
+    schema.encode(&ty, &request.value, "value", &mut data)?;
+
+    Ok(success(BorshEncodeResponse {
+        data: general_purpose::STANDARD.encode(&data),
+        hex: hex::encode(&data),
+        size: data.len(),
+    }))
+}
+
+/// Decodes exactly one value; leftover bytes usually mean the schema is
+/// wrong, so they are an error rather than silently dropped.
+pub async fn decode(Json(request): Json<BorshDecodeRequest>) -> Result<Json<serde_json::Value>, AppError> {
+    let (schema, ty) = schema(request.schema)?;
+    let bytes = general_purpose::STANDARD.decode(&request.data).map_err(|_| AppError::InvalidField {
+        field: "data".to_string(),
+        message: "expected base64".to_string(),
+    })?;
+
+    let mut input = bytes.as_slice();
+    let value = schema.decode(&ty, &mut input, "data")?;
+    if !input.is_empty() {
+        return Err(AppError::InvalidField {
+            field: "data".to_string(),
+            message: format!("{} trailing bytes after the value", input.len()),
+        });
+    }
+
+    Ok(success(BorshDecodeResponse { value }))
+}
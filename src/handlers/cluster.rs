@@ -0,0 +1,81 @@
+use axum::extract::State;
+
+use super::success;
+use crate::amount;
+use crate::errors::AppError;
+use crate::extract::{Json, Query};
+use crate::models::cluster::{
+    EpochInfoResponse, SlotResponse, Validator, ValidatorSort, ValidatorsQuery, ValidatorsResponse, VersionResponse,
+};
+use crate::state::AppState;
+
+pub async fn epoch_info(State(state): State<AppState>) -> Result<Json<serde_json::Value>, AppError> {
+    let info = state.rpc()?.get_epoch_info().await?;
+    Ok(success(EpochInfoResponse {
+        epoch: info.epoch,
+        slot_index: info.slot_index,
+        slots_in_epoch: info.slots_in_epoch,
+        slots_remaining: info.slots_in_epoch.saturating_sub(info.slot_index),
+        absolute_slot: info.absolute_slot,
+        block_height: info.block_height,
+        transaction_count: info.transaction_count,
+    }))
+}
+
+pub async fn slot(State(state): State<AppState>) -> Result<Json<serde_json::Value>, AppError> {
+    let slot = state.rpc()?.get_slot().await?;
+    Ok(success(SlotResponse { slot }))
+}
+
+pub async fn version(State(state): State<AppState>) -> Result<Json<serde_json::Value>, AppError> {
+    let version = state.rpc()?.get_version().await?;
+    Ok(success(VersionResponse { solana_core: version.solana_core, feature_set: version.feature_set }))
+}
+
+/// Vote accounts with their stake and commission, for picking a validator
+/// to delegate to.
+pub async fn validators(
+    State(state): State<AppState>,
+    Query(query): Query<ValidatorsQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let accounts = state.rpc()?.get_vote_accounts().await?;
+    let total_stake = accounts.iter().fold(0u64, |total, account| total.saturating_add(account.activated_stake));
+    let delinquent_stake = accounts
+        .iter()
+        .filter(|account| account.delinquent)
+        .fold(0u64, |total, account| total.saturating_add(account.activated_stake));
+
+    let mut matching: Vec<_> = accounts
+        .into_iter()
+        .filter(|account| query.delinquent.is_none_or(|delinquent| account.delinquent == delinquent))
+        .filter(|account| query.max_commission.is_none_or(|max| account.commission <= max))
+        .filter(|account| query.min_stake.is_none_or(|min| account.activated_stake >= min))
+        .collect();
+    matching.sort_by(|a, b| {
+        let by_stake = b.activated_stake.cmp(&a.activated_stake);
+        match query.sort {
+            ValidatorSort::Stake => by_stake,
+            ValidatorSort::Commission => a.commission.cmp(&b.commission).then(by_stake),
+        }
+        .then(a.vote_pubkey.cmp(&b.vote_pubkey))
+    });
+
+    let validator_count = matching.len();
+    let validators = matching
+        .into_iter()
+        .skip(query.offset.unwrap_or(0))
+        .take(query.limit.unwrap_or(usize::MAX))
+        .map(|account| Validator {
+            vote_pubkey: account.vote_pubkey.to_string(),
+            node_pubkey: account.node_pubkey.to_string(),
+            activated_stake: account.activated_stake,
+            activated_stake_sol: amount::lamports_to_sol(account.activated_stake).to_string(),
+            commission: account.commission,
+            last_vote: account.last_vote,
+            root_slot: account.root_slot,
+            delinquent: account.delinquent,
+        })
+        .collect();
+
+    Ok(success(ValidatorsResponse { total_stake, delinquent_stake, validator_count, validators }))
+}
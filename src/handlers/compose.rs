@@ -0,0 +1,49 @@
+use axum::extract::State;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+use super::transaction::compile;
+use super::{send_sol_instruction, send_token_instruction, success};
+use crate::errors::{AppError, FieldError};
+use crate::extract::{Json, ValidJson};
+use crate::models::compose::{ComposeOperation, ComposeRequest, CreateAtaOperation};
+use crate::solana_pay::memo_instruction;
+use crate::state::AppState;
+
+/// Builds each operation with the same code as its own endpoint, so a
+/// composed transfer is checked exactly like a standalone one.
+pub async fn compose(
+    State(state): State<AppState>,
+    ValidJson(request): ValidJson<ComposeRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let mut instructions = Vec::with_capacity(request.operations.len());
+    for (index, operation) in request.operations.iter().enumerate() {
+        let instruction = build(&state, &request.payer, operation).await;
+        instructions.push(instruction.map_err(|error| error.within(&format!("operations[{index}]")))?);
+    }
+    let blockhash = match request.recent_blockhash {
+        Some(hash) => *hash,
+        None => state.latest_blockhash().await?.blockhash,
+    };
+
+    Ok(success(compile(&instructions, &request.payer, blockhash, "operations")?))
+}
+
+async fn build(state: &AppState, payer: &Pubkey, operation: &ComposeOperation) -> Result<Instruction, AppError> {
+    match operation {
+        ComposeOperation::SendSol(payload) => send_sol_instruction(state, payload),
+        ComposeOperation::SendToken(payload) => Ok(send_token_instruction(state, payload).await?.instruction),
+        ComposeOperation::Memo(memo) => Ok(memo_instruction(&memo.memo)),
+        ComposeOperation::CreateAta(create) => create_ata(state, payer, create),
+    }
+}
+
+fn create_ata(state: &AppState, payer: &Pubkey, create: &CreateAtaOperation) -> Result<Instruction, AppError> {
+    let token_program = create.token_program.as_ref().map_or_else(spl_token::id, |program| program.0);
+    if token_program != spl_token::id() && token_program != spl_token_2022::id() {
+        return Err(AppError::Field { field: "token_program".to_string(), error: FieldError::UnsupportedTokenProgram });
+    }
+    let owner = state.address_book.resolve(&create.owner, "owner")?;
+    let payer = create.payer.as_deref().unwrap_or(payer);
+    Ok(create_associated_token_account_idempotent(payer, &owner, &create.mint, &token_program))
+}
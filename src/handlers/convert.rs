@@ -0,0 +1,50 @@
+use axum::extract::State;
+
+use super::success;
+use crate::amount;
+use crate::errors::{AppError, FieldError};
+use crate::extract::{Json, Query};
+use crate::models::convert::{SolConversion, SolQuery, TokenConversion, TokenQuery};
+use crate::state::AppState;
+
+fn field(field: &str) -> impl FnOnce(FieldError) -> AppError + '_ {
+    move |error| AppError::Field { field: field.to_string(), error }
+}
+
+pub async fn sol(Query(query): Query<SolQuery>) -> Result<Json<serde_json::Value>, AppError> {
+    let lamports = match (query.lamports, query.sol.as_deref()) {
+        (Some(lamports), None) => lamports,
+        (None, Some(sol)) => amount::sol_to_lamports(sol).map_err(field("sol"))?,
+        _ => return Err(field("lamports")(FieldError::ExactlyOne("lamports or sol"))),
+    };
+
+    Ok(success(SolConversion {
+        lamports: lamports.to_string(),
+        sol: amount::lamports_to_sol(lamports).to_string(),
+    }))
+}
+
+pub async fn token(
+    State(state): State<AppState>,
+    Query(query): Query<TokenQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let decimals = match (query.decimals, query.mint) {
+        (Some(decimals), _) => decimals,
+        (None, Some(mint)) => state.accounts.mint(&mint).await?.decimals,
+        (None, None) => return Err(field("mint")(FieldError::ExactlyOne("mint or decimals"))),
+    };
+
+    let raw = match (query.amount, query.ui_amount.as_deref()) {
+        (Some(raw), None) => raw,
+        (None, Some(ui_amount)) => amount::from_ui(ui_amount, decimals).map_err(field("ui_amount"))?,
+        _ => return Err(field("amount")(FieldError::ExactlyOne("amount or ui_amount"))),
+    };
+    let ui_amount = amount::to_ui(raw, decimals).map_err(field("decimals"))?;
+
+    Ok(success(TokenConversion {
+        mint: query.mint.map(|mint| mint.to_string()),
+        decimals,
+        amount: raw.to_string(),
+        ui_amount: ui_amount.to_string(),
+    }))
+}
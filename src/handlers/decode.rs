@@ -0,0 +1,110 @@
+use axum::extract::State;
+use base64::{Engine as _, engine::general_purpose};
+use solana_sdk::pubkey::Pubkey;
+
+use super::success;
+use crate::decode;
+use crate::errors::{AppError, FieldError};
+use crate::extract::Json;
+use crate::models::decode::AccountSource;
+use crate::state::AppState;
+
+/// Account bytes to decode, plus where they came from when fetched.
+struct Loaded {
+    address: Option<Pubkey>,
+    owner: Option<Pubkey>,
+    data: Vec<u8>,
+}
+
+impl Loaded {
+    /// Bad inline data is a field error; a fetched account of the wrong
+    /// kind is reported against its address.
+    fn invalid(&self, expected: &'static str) -> AppError {
+        match self.address {
+            Some(pubkey) => AppError::InvalidAccount { pubkey, expected },
+            None => AppError::InvalidField { field: "data".to_string(), message: format!("not a valid {expected}") },
+        }
+    }
+
+    fn require_owner(&self, programs: &[Pubkey], expected: &'static str) -> Result<(), AppError> {
+        match self.owner {
+            Some(owner) if !programs.contains(&owner) => Err(self.invalid(expected)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Always reads through to RPC rather than the account cache: balances and
+/// authorities are exactly what callers are inspecting.
+async fn load(state: &AppState, source: AccountSource) -> Result<Loaded, AppError> {
+    match (source.data, source.pubkey) {
+        (Some(data), None) => {
+            let data = general_purpose::STANDARD.decode(data).map_err(|_| AppError::InvalidField {
+                field: "data".to_string(),
+                message: "expected base64".to_string(),
+            })?;
+            Ok(Loaded { address: None, owner: None, data })
+        }
+        (None, Some(pubkey)) => {
+            let account = state
+                .rpc()?
+                .get_account(&pubkey)
+                .await?
+                .ok_or(AppError::AccountNotFound(*pubkey))?;
+            Ok(Loaded { address: Some(*pubkey), owner: Some(account.owner), data: account.data })
+        }
+        _ => Err(AppError::Field { field: "data".to_string(), error: FieldError::ExactlyOne("data or pubkey") }),
+    }
+}
+
+const TOKEN_PROGRAMS: [Pubkey; 2] = [spl_token::ID, spl_token_2022::ID];
+
+pub async fn mint(
+    State(state): State<AppState>,
+    Json(source): Json<AccountSource>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let loaded = load(&state, source).await?;
+    loaded.require_owner(&TOKEN_PROGRAMS, "token mint")?;
+
+    let mut layout = decode::mint(&loaded.data).map_err(|_| loaded.invalid("token mint"))?;
+    layout.address = loaded.address.map(|address| address.to_string());
+    layout.program_id = loaded.owner.map(|owner| owner.to_string());
+    Ok(success(layout))
+}
+
+pub async fn token_account(
+    State(state): State<AppState>,
+    Json(source): Json<AccountSource>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let loaded = load(&state, source).await?;
+    loaded.require_owner(&TOKEN_PROGRAMS, "token account")?;
+
+    let mut layout = decode::token_account(&loaded.data).map_err(|_| loaded.invalid("token account"))?;
+    layout.address = loaded.address.map(|address| address.to_string());
+    layout.program_id = loaded.owner.map(|owner| owner.to_string());
+    Ok(success(layout))
+}
+
+pub async fn nonce(
+    State(state): State<AppState>,
+    Json(source): Json<AccountSource>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let loaded = load(&state, source).await?;
+    loaded.require_owner(&[solana_system_interface::program::ID], "nonce account")?;
+
+    let mut layout = decode::nonce(&loaded.data).ok_or_else(|| loaded.invalid("nonce account"))?;
+    layout.address = loaded.address.map(|address| address.to_string());
+    Ok(success(layout))
+}
+
+pub async fn stake(
+    State(state): State<AppState>,
+    Json(source): Json<AccountSource>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let loaded = load(&state, source).await?;
+    loaded.require_owner(&[solana_stake_interface::program::ID], "stake account")?;
+
+    let mut layout = decode::stake(&loaded.data).ok_or_else(|| loaded.invalid("stake account"))?;
+    layout.address = loaded.address.map(|address| address.to_string());
+    Ok(success(layout))
+}
@@ -0,0 +1,37 @@
+use solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+use super::success;
+use crate::errors::{AppError, FieldError};
+use crate::extract::{Json, Query};
+use crate::models::derive::{AtaQuery, AtaResponse, PdaQuery, PdaResponse};
+
+pub async fn ata(Query(query): Query<AtaQuery>) -> Result<Json<serde_json::Value>, AppError> {
+    let token_program = query.token_program.map_or_else(spl_token::id, |program| program.0);
+    if token_program != spl_token::id() && token_program != spl_token_2022::id() {
+        return Err(AppError::Field {
+            field: "token_program".to_string(),
+            error: FieldError::UnsupportedTokenProgram,
+        });
+    }
+
+    let address = get_associated_token_address_with_program_id(&query.owner, &query.mint, &token_program);
+
+    Ok(success(AtaResponse {
+        address: address.to_string(),
+        owner: query.owner.to_string(),
+        mint: query.mint.to_string(),
+        token_program: token_program.to_string(),
+    }))
+}
+
+pub async fn pda(Query(query): Query<PdaQuery>) -> Result<Json<serde_json::Value>, AppError> {
+    let seeds: Vec<&[u8]> = query.seeds.0.iter().map(Vec::as_slice).collect();
+    let (address, bump) = Pubkey::try_find_program_address(&seeds, &query.program_id)
+        .ok_or_else(|| AppError::InvalidInput("No viable bump seed for these seeds".to_string()))?;
+
+    Ok(success(PdaResponse {
+        address: address.to_string(),
+        bump,
+    }))
+}
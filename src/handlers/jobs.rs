@@ -0,0 +1,157 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::{Path, State};
+use base64::{Engine as _, engine::general_purpose};
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::VersionedMessage,
+    pubkey::Pubkey,
+    signer::keypair::Keypair,
+};
+
+use super::success;
+use crate::errors::AppError;
+use crate::extract::{Json, ValidJson};
+use crate::jobs::{self, FeeBump, RetryPolicy, Schedule, Template};
+use crate::models::jobs::{InstructionTemplate, ScheduleRequest};
+use crate::policy::{self, Transfer};
+use crate::state::AppState;
+use crate::tenant;
+use crate::tx::{self, MAX_TRANSACTION_SIZE};
+
+pub(super) fn instructions(templates: &[InstructionTemplate]) -> Result<Vec<Instruction>, AppError> {
+    templates
+        .iter()
+        .enumerate()
+        .map(|(index, template)| {
+            let data = general_purpose::STANDARD.decode(&template.data).map_err(|_| AppError::InvalidField {
+                field: format!("instructions[{index}].data"),
+                message: "expected base64".to_string(),
+            })?;
+            let accounts = template
+                .accounts
+                .iter()
+                .map(|meta| AccountMeta {
+                    pubkey: *meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect();
+            Ok(Instruction { program_id: *template.program_id, accounts, data })
+        })
+        .collect()
+}
+
+/// Instructions accepted for signing later.
+pub(super) struct Prepared {
+    pub instructions: Vec<Instruction>,
+    /// Required signers, fee payer first.
+    pub signers: Vec<Pubkey>,
+    pub transfers: Vec<Transfer>,
+}
+
+/// Decodes `templates` and trial-signs them with `signers`, so a missing
+/// signer or oversized transaction fails the request rather than the later
+/// send.
+pub(super) fn prepare(signers: &[Arc<Keypair>], templates: &[InstructionTemplate]) -> Result<Prepared, AppError> {
+    let instructions = instructions(templates)?;
+    let signers: Vec<&Keypair> = signers.iter().map(AsRef::as_ref).collect();
+    let transaction = jobs::sign(&instructions, &signers, Hash::default())?;
+    if tx::serialized_size(&transaction) > MAX_TRANSACTION_SIZE {
+        return Err(AppError::InvalidField {
+            field: "instructions".to_string(),
+            message: format!("transaction exceeds the {MAX_TRANSACTION_SIZE}-byte limit"),
+        });
+    }
+
+    let message = VersionedMessage::Legacy(transaction.message);
+    let keys = message.static_account_keys();
+    let transfers = policy::transfers(&message, keys);
+    let signers = keys[..usize::from(message.header().num_required_signatures)].to_vec();
+    Ok(Prepared { instructions, signers, transfers })
+}
+
+/// Checks that `instructions` still fit once prefixed with a compute-unit
+/// price, and don't set one of their own that would conflict with it.
+fn check_priced(signers: &[Arc<Keypair>], instructions: &[Instruction], max: u64) -> Result<(), AppError> {
+    // `SetComputeUnitPrice` is instruction 3 of the compute budget program.
+    if instructions.iter().any(|instruction| {
+        instruction.program_id == solana_sdk::compute_budget::ID && instruction.data.first() == Some(&3)
+    }) {
+        return Err(AppError::InvalidField {
+            field: "instructions".to_string(),
+            message: "must not set a compute-unit price when priority_fee is given".to_string(),
+        });
+    }
+
+    let signers: Vec<&Keypair> = signers.iter().map(AsRef::as_ref).collect();
+    let transaction = jobs::sign(&jobs::priced(instructions, max), &signers, Hash::default())?;
+    if tx::serialized_size(&transaction) > MAX_TRANSACTION_SIZE {
+        return Err(AppError::InvalidField {
+            field: "instructions".to_string(),
+            message: format!("transaction with a priority fee exceeds the {MAX_TRANSACTION_SIZE}-byte limit"),
+        });
+    }
+    Ok(())
+}
+
+/// Schedules instructions to be signed and sent later, once at `execute_at`
+/// or on every match of `cron`. Each run fetches a fresh blockhash; with a
+/// `priority_fee`, each run also resends at rising prices until confirmed.
+pub async fn schedule(
+    State(state): State<AppState>,
+    ValidJson(request): ValidJson<ScheduleRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    state.require_feature("jobs", |features| features.jobs)?;
+    state.rpc()?;
+    let signers = state.signers(request.signers)?;
+    let prepared = prepare(&signers, &request.instructions)?;
+    state.review_policy("jobs.schedule", &prepared.signers, &prepared.transfers)?;
+
+    let schedule = match (request.execute_at, request.cron) {
+        (Some(at), _) => Schedule::At(at),
+        (None, Some(expression)) => {
+            let cron = expression
+                .parse()
+                .map_err(|message| AppError::InvalidField { field: "cron".to_string(), message })?;
+            Schedule::Cron { expression, cron }
+        }
+        (None, None) => unreachable!("validated: exactly one of execute_at or cron"),
+    };
+    let config = &state.config().jobs;
+    let fee_bump = request.priority_fee.map(|fee| FeeBump {
+        initial: fee.initial_micro_lamports,
+        max: fee.max_micro_lamports,
+        percent: fee.bump_percent.unwrap_or(config.bump_percent),
+        interval: Duration::from_millis(fee.interval_ms.unwrap_or(config.bump_interval_ms)),
+    });
+    if let Some(bump) = &fee_bump {
+        check_priced(&signers, &prepared.instructions, bump.max)?;
+    }
+    let retry = RetryPolicy {
+        max_attempts: request.retry.max_attempts.unwrap_or(config.max_attempts),
+        backoff: Duration::from_millis(request.retry.backoff_ms.unwrap_or(config.backoff_ms)),
+        fee_bump,
+    };
+
+    let tenant = tenant::current();
+    let key_ids = signers.into_iter().map(|signer| state.keystore.insert(&tenant, signer)).collect();
+    let template = Template { instructions: prepared.instructions, key_ids };
+    let job = state.jobs.schedule(state.clone(), schedule, template, retry);
+    Ok(success(job))
+}
+
+pub async fn get(State(state): State<AppState>, Path(id): Path<String>) -> Result<Json<serde_json::Value>, AppError> {
+    let job = state.jobs.get(&id).ok_or_else(|| AppError::NotFound(format!("Job {id}")))?;
+    Ok(success(job))
+}
+
+/// Cancels a job that hasn't finished; a cron job stops recurring.
+pub async fn cancel(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    Ok(success(state.jobs.cancel(&id, &state)?))
+}
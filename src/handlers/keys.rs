@@ -0,0 +1,29 @@
+use axum::extract::{Path, State};
+
+use super::success;
+use crate::errors::AppError;
+use crate::extract::{Json, Query, ValidJson};
+use crate::models::keys::{KeyMetadata, KeysQuery};
+use crate::state::AppState;
+use crate::tenant;
+
+/// The caller's keystore keys, optionally only those tagged `tag`.
+pub async fn list(
+    State(state): State<AppState>,
+    Query(query): Query<KeysQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    Ok(success(state.keystore.list(&tenant::current(), query.tag.as_deref())))
+}
+
+/// Replaces a key's label, tags and expiry.
+pub async fn update(
+    State(state): State<AppState>,
+    Path(key_id): Path<String>,
+    ValidJson(request): ValidJson<KeyMetadata>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let key = state
+        .keystore
+        .update(&tenant::current(), &key_id, request.into())
+        .ok_or_else(|| AppError::NotFound(format!("Key {key_id}")))?;
+    Ok(success(key))
+}
@@ -0,0 +1,67 @@
+use solana_sdk::rent::Rent;
+use solana_system_interface::instruction as system_instruction;
+
+use super::{built, instruction_response, success};
+use crate::errors::AppError;
+use crate::extract::{Json, Query, ValidJson};
+use crate::merkle_tree;
+use crate::models::merkle_tree::{
+    AppendLeafRequest, CreateMerkleTreeRequest, CreateMerkleTreeResponse, MerkleTreeCost, MerkleTreeCostQuery,
+};
+use crate::validation::{Validate, Violations};
+
+/// Allocates the tree account, owned by the compression program, and
+/// initializes it empty. Both instructions go in one transaction signed by
+/// the payer, the tree keypair and the authority.
+pub async fn create(
+    ValidJson(request): ValidJson<CreateMerkleTreeRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let payer = *request.payer;
+    let tree = *request.tree;
+    let authority = request.authority.map_or(payer, |key| *key);
+    let cost = cost(request.max_depth, request.max_buffer_size, request.canopy_depth);
+
+    let instructions = [
+        system_instruction::create_account(&payer, &tree, cost.lamports, cost.space, &merkle_tree::PROGRAM_ID),
+        merkle_tree::init_empty_merkle_tree(&tree, &authority, request.max_depth, request.max_buffer_size),
+    ];
+    Ok(success(CreateMerkleTreeResponse {
+        tree: tree.to_string(),
+        authority: authority.to_string(),
+        cost,
+        instructions: instructions.iter().map(instruction_response).collect(),
+    }))
+}
+
+/// Rent for a tree of the given shape, before choosing one.
+pub async fn tree_cost(Query(query): Query<MerkleTreeCostQuery>) -> Result<Json<serde_json::Value>, AppError> {
+    let mut violations = Violations::default();
+    query.validate(&mut violations);
+    violations.into_result()?;
+
+    Ok(success(cost(query.max_depth, query.max_buffer_size, query.canopy_depth)))
+}
+
+pub async fn append(Json(request): Json<AppendLeafRequest>) -> Result<Json<serde_json::Value>, AppError> {
+    let leaf: [u8; 32] = bs58::decode(&request.leaf)
+        .into_vec()
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| AppError::InvalidField {
+            field: "leaf".to_string(),
+            message: "expected 32 base58-encoded bytes".to_string(),
+        })?;
+    let instruction = merkle_tree::append(&request.tree, &request.authority, &leaf);
+
+    Ok(built(request.output_format, &instruction, || instruction_response(&instruction)))
+}
+
+fn cost(max_depth: u32, max_buffer_size: u32, canopy_depth: u32) -> MerkleTreeCost {
+    let space = merkle_tree::account_size(max_depth, max_buffer_size, canopy_depth);
+    MerkleTreeCost {
+        space: space as u64,
+        lamports: Rent::default().minimum_balance(space),
+        max_leaves: 1 << max_depth,
+        proof_length: max_depth - canopy_depth,
+    }
+}
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use serde_json::Value;
+use spl_associated_token_account::get_associated_token_address;
+
+use super::{built, instruction_response, success};
+use crate::errors::AppError;
+use crate::extract::{Json, ValidJson};
+use crate::metaplex::{self, EditionPrint, Metadata, NftBurn, PrintSource, METADATA_PROGRAM_ID};
+use crate::models::nft::{
+    BurnNftRequest, NftCreator, NftMetadataResponse, OnChainMetadata, PrintEditionRequest, PrintEditionResponse,
+};
+use crate::state::AppState;
+use crate::utils::parse_pubkey;
+
+/// The mint's Metaplex metadata account merged with the JSON at its `uri`.
+/// A `uri` that can't be read still returns the on-chain layer, with the
+/// reason in `off_chain_error`; only complete results are cached.
+pub async fn metadata(State(state): State<AppState>, Path(mint): Path<String>) -> Result<Json<Value>, AppError> {
+    let mint = parse_pubkey(&mint).map_err(|error| AppError::Field { field: "mint".to_string(), error })?;
+    if let Some(cached) = state.nft.get(&mint).await {
+        return Ok(success(&*cached));
+    }
+
+    let address = metaplex::metadata_address(&mint);
+    let account = state.accounts.account(&address).await?;
+    let metadata = (account.owner == METADATA_PROGRAM_ID)
+        .then(|| metaplex::decode(&account.data))
+        .flatten()
+        .filter(|metadata| metadata.mint == mint)
+        .ok_or(AppError::InvalidAccount { pubkey: address, expected: "metadata" })?;
+
+    let off_chain = match metadata.uri.as_str() {
+        "" => Ok(None),
+        uri => state.nft.fetch_json(uri).await.map(Some),
+    };
+    let complete = off_chain.is_ok();
+    let response = Arc::new(merge(mint.to_string(), address.to_string(), metadata, off_chain));
+    if complete {
+        state.nft.insert(mint, response.clone()).await;
+    }
+    Ok(success(&*response))
+}
+
+/// The Token Metadata program's `Burn` for one NFT, master or print
+/// edition, programmable or not.
+pub async fn burn(ValidJson(request): ValidJson<BurnNftRequest>) -> Result<Json<Value>, AppError> {
+    let (owner, mint) = (*request.owner, *request.mint);
+    let burn = NftBurn {
+        owner,
+        mint,
+        token: request.token_account.map_or_else(|| get_associated_token_address(&owner, &mint), |key| *key),
+        collection_metadata: request.collection_mint.map(|collection| metaplex::metadata_address(&collection)),
+        programmable: request.programmable,
+        print_of: request.print_of.map(|print| PrintSource {
+            master_mint: *print.master_mint,
+            master_token: *print.master_token_account,
+            edition: print.edition,
+        }),
+    };
+    let instruction = burn.instruction();
+
+    Ok(built(request.output_format, &instruction, || instruction_response(&instruction)))
+}
+
+/// `MintNewEditionFromMasterEditionViaToken` for a numbered print of a
+/// master edition, returned with the print's derived accounts.
+pub async fn print_edition(ValidJson(request): ValidJson<PrintEditionRequest>) -> Result<Json<Value>, AppError> {
+    let (owner, master_mint) = (*request.owner, *request.master_mint);
+    let print = EditionPrint {
+        master_mint,
+        owner,
+        master_token: request
+            .master_token_account
+            .map_or_else(|| get_associated_token_address(&owner, &master_mint), |key| *key),
+        new_mint: *request.new_mint,
+        new_mint_authority: *request.new_mint_authority,
+        payer: request.payer.map_or(owner, |key| *key),
+        update_authority: request.update_authority.map_or(owner, |key| *key),
+        edition: request.edition,
+    };
+    let instruction = print.instruction();
+
+    Ok(built(request.output_format, &instruction, || PrintEditionResponse {
+        metadata: metaplex::metadata_address(&print.new_mint).to_string(),
+        edition: metaplex::edition_address(&print.new_mint).to_string(),
+        edition_marker: print.edition_marker().to_string(),
+        instruction: instruction_response(&instruction),
+    }))
+}
+
+fn merge(
+    mint: String,
+    metadata_address: String,
+    metadata: Metadata,
+    off_chain: Result<Option<Value>, String>,
+) -> NftMetadataResponse {
+    let (off_chain, off_chain_error) = match off_chain {
+        Ok(document) => (document, None),
+        Err(error) => (None, Some(error)),
+    };
+    let text = |key: &str| off_chain.as_ref()?.get(key)?.as_str().map(str::to_string);
+    // The account's name and symbol are authoritative; the JSON only fills
+    // them in when the account leaves them blank.
+    let name = Some(metadata.name).filter(|name| !name.is_empty()).or_else(|| text("name")).unwrap_or_default();
+    let symbol = Some(metadata.symbol).filter(|symbol| !symbol.is_empty()).or_else(|| text("symbol")).unwrap_or_default();
+
+    NftMetadataResponse {
+        mint,
+        metadata_address,
+        name,
+        symbol,
+        description: text("description"),
+        image: text("image"),
+        animation_url: text("animation_url"),
+        external_url: text("external_url"),
+        attributes: off_chain
+            .as_ref()
+            .and_then(|document| document.get("attributes")?.as_array().cloned())
+            .unwrap_or_default(),
+        uri: metadata.uri,
+        on_chain: OnChainMetadata {
+            update_authority: metadata.update_authority.to_string(),
+            seller_fee_basis_points: metadata.seller_fee_basis_points,
+            creators: metadata
+                .creators
+                .unwrap_or_default()
+                .into_iter()
+                .map(|creator| NftCreator {
+                    address: creator.address.to_string(),
+                    verified: creator.verified,
+                    share: creator.share,
+                })
+                .collect(),
+            primary_sale_happened: metadata.primary_sale_happened,
+            is_mutable: metadata.is_mutable,
+        },
+        off_chain,
+        off_chain_error,
+    }
+}
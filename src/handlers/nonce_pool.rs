@@ -0,0 +1,145 @@
+use axum::extract::State;
+use solana_nonce::{state::State as NonceState, versions::Versions};
+use solana_sdk::{
+    hash::Hash,
+    pubkey::Pubkey,
+    rent::Rent,
+    signer::{Signer, keypair::Keypair},
+    transaction::{Transaction, VersionedTransaction},
+};
+use solana_system_interface::instruction::{advance_nonce_account, create_nonce_account};
+
+use super::{instruction_response, success};
+use crate::confirm;
+use crate::errors::AppError;
+use crate::extract::{Json, ValidJson};
+use crate::models::nonce_pool::{
+    AcquireNonceRequest, AddNonceAccountsRequest, AddNonceAccountsResponse, NonceLease, ReleaseNonceRequest,
+};
+use crate::nonce_pool::NoncePool;
+use crate::policy;
+use crate::rpc::SolanaRpc;
+use crate::state::AppState;
+
+/// The durable nonce stored in `account`, if it's an initialized nonce
+/// account controlled by `authority`.
+async fn durable_nonce(rpc: &dyn SolanaRpc, account: &Pubkey, authority: &Pubkey) -> Result<Hash, AppError> {
+    let data = rpc.get_account(account).await?.ok_or(AppError::AccountNotFound(*account))?;
+    let versions: Versions = bincode::deserialize(&data.data)
+        .map_err(|_| AppError::InvalidAccount { pubkey: *account, expected: "nonce account" })?;
+    match versions.state() {
+        NonceState::Initialized(data) if data.authority == *authority => Ok(data.blockhash()),
+        _ => Err(AppError::InvalidAccount { pubkey: *account, expected: "nonce account of the pool authority" }),
+    }
+}
+
+pub async fn status(State(state): State<AppState>) -> Result<Json<serde_json::Value>, AppError> {
+    Ok(success(state.nonce_pool()?.status()))
+}
+
+/// Grows the pool by creating nonce accounts or adopting existing ones.
+/// Created accounts join once their transaction confirms; ones that don't
+/// are left out of `added`.
+pub async fn add(
+    State(state): State<AppState>,
+    ValidJson(request): ValidJson<AddNonceAccountsRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let pool = state.nonce_pool()?;
+    let rpc = state.rpc()?;
+    let (accounts, signatures) = match (request.count, request.payer) {
+        (Some(count), Some(payer)) => {
+            let payer = state.signer("payer".to_string(), payer)?;
+            create(&state, rpc.as_ref(), &pool, &payer, count).await?
+        }
+        _ => {
+            let accounts: Vec<Pubkey> = request.accounts.iter().map(|account| account.0).collect();
+            for account in &accounts {
+                durable_nonce(rpc.as_ref(), account, &pool.authority()).await?;
+            }
+            (accounts, Vec::new())
+        }
+    };
+
+    let added = pool.add(&accounts)?;
+    let response = AddNonceAccountsResponse {
+        added: added.iter().map(ToString::to_string).collect(),
+        signatures,
+        total: pool.status().total,
+    };
+    Ok(success(response))
+}
+
+/// Creates `count` nonce accounts for the pool authority, one transaction
+/// each, and returns the confirmed ones with every signature sent.
+async fn create(
+    state: &AppState,
+    rpc: &dyn SolanaRpc,
+    pool: &NoncePool,
+    payer: &Keypair,
+    count: u8,
+) -> Result<(Vec<Pubkey>, Vec<String>), AppError> {
+    let lamports = Rent::default().minimum_balance(NonceState::size());
+    let blockhash = state.latest_blockhash().await?;
+    let mut sent = Vec::new();
+    for _ in 0..count {
+        let nonce = Keypair::new();
+        let instructions = create_nonce_account(&payer.pubkey(), &nonce.pubkey(), &pool.authority(), lamports);
+        let transaction = VersionedTransaction::from(Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[payer, &nonce],
+            blockhash.blockhash,
+        ));
+        let keys = transaction.message.static_account_keys();
+        state.enforce_policy("nonce_pool.create", &[payer.pubkey()], &policy::transfers(&transaction.message, keys))?;
+        sent.push((nonce.pubkey(), rpc.send_transaction(&transaction).await?));
+    }
+
+    let mut accounts = Vec::new();
+    for (account, signature) in &sent {
+        let confirmed = confirm::track(rpc, signature, blockhash.last_valid_block_height).await?;
+        if confirmed.is_some_and(|confirmed| confirmed.meta.err.is_none()) {
+            accounts.push(*account);
+        }
+    }
+    Ok((accounts, sent.iter().map(|(_, signature)| signature.to_string()).collect()))
+}
+
+/// Leases a free nonce account and returns its current nonce with the
+/// advance instruction the transaction must start with.
+pub async fn acquire(
+    State(state): State<AppState>,
+    Json(request): Json<AcquireNonceRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let pool = state.nonce_pool()?;
+    let rpc = state.rpc()?;
+    let lease = pool.reserve(request.ttl_secs)?;
+    let nonce = match durable_nonce(rpc.as_ref(), &lease.account, &pool.authority()).await {
+        Ok(nonce) => nonce,
+        Err(err) => {
+            pool.release(&lease.id)?;
+            return Err(err);
+        }
+    };
+    pool.set_nonce(&lease.id, nonce);
+
+    let response = NonceLease {
+        lease_id: lease.id,
+        nonce_account: lease.account.to_string(),
+        nonce: nonce.to_string(),
+        authority: pool.authority().to_string(),
+        advance_instruction: instruction_response(&advance_nonce_account(&lease.account, &pool.authority())),
+        expires_at: lease.expires_at,
+    };
+    Ok(success(response))
+}
+
+/// Frees a lease whose transaction won't be sent.
+pub async fn release(
+    State(state): State<AppState>,
+    Json(request): Json<ReleaseNonceRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let pool = state.nonce_pool()?;
+    pool.release(&request.lease_id)?;
+    Ok(success(pool.status()))
+}
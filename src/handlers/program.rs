@@ -0,0 +1,360 @@
+use axum::extract::{Path, State};
+use base64::{Engine as _, engine::general_purpose};
+use chrono::Utc;
+use serde_json::Value;
+use solana_loader_v3_interface::{get_program_data_address, instruction as loader, state::UpgradeableLoaderState};
+use solana_sdk::{
+    account::Account,
+    hash::{hash, Hash},
+    instruction::Instruction,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    transaction::Transaction,
+};
+use solana_system_interface::MAX_PERMITTED_DATA_LENGTH;
+use spl_token_2022::extension::AccountType;
+use spl_token_2022::state::{Account as TokenAccount, Mint};
+
+use super::{instruction_response, success};
+use crate::decode;
+use crate::errors::{AppError, FieldError};
+use crate::extract::{Bulk, Json, Query, ValidJson};
+use crate::models::program::{
+    CloseBufferRequest, DeployCost, DeployProgramRequest, DeployProgramResponse, LoaderAccountType, ProgramAccount,
+    ProgramAccountsQuery, ProgramAccountsResponse, ProgramUpload, ProgramUploadQuery, ProgramUploadRequest,
+    ProgramUploadResponse, SetProgramAuthorityRequest, UpgradeProgramRequest, WriteBufferRequest, WriteBufferResponse,
+};
+use crate::program_uploads::StoredProgram;
+use crate::rpc::AccountFilter;
+use crate::state::AppState;
+use crate::tx::{self, MAX_TRANSACTION_SIZE};
+use crate::utils::parse_pubkey;
+
+const DEFAULT_ACCOUNTS_LIMIT: usize = 100;
+const MAX_ACCOUNTS_LIMIT: usize = 1_000;
+/// The most bytes a node compares in one memcmp filter.
+const MAX_MEMCMP_BYTES: usize = 128;
+/// Where the owner sits in a token account's base layout, after the mint.
+const TOKEN_OWNER_OFFSET: usize = 32;
+/// Request body limit for buffer writes and uploads: room for the largest
+/// program, base64 encoded.
+pub const MAX_BUFFER_BODY: usize = 16 * 1024 * 1024;
+/// The base fee; priority fees come on top.
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// `getProgramAccounts` with friendly filters. The node returns every match
+/// at once, so a filter set matching more than `limit` accounts is refused
+/// rather than truncated.
+pub async fn accounts(
+    State(state): State<AppState>,
+    Path(program_id): Path<String>,
+    Query(query): Query<ProgramAccountsQuery>,
+) -> Result<Json<Value>, AppError> {
+    let program_id = parse_pubkey(&program_id).map_err(|error| AppError::Field { field: "id".to_string(), error })?;
+    let limit = query.limit.unwrap_or(DEFAULT_ACCOUNTS_LIMIT);
+    if !(1..=MAX_ACCOUNTS_LIMIT).contains(&limit) {
+        return Err(AppError::Field { field: "limit".to_string(), error: FieldError::BatchSize(MAX_ACCOUNTS_LIMIT) });
+    }
+    let filters = filters(&program_id, &query)?;
+
+    let accounts = state.rpc()?.get_program_accounts(&program_id, &filters).await?;
+    if accounts.len() > limit {
+        return Err(AppError::Field { field: "limit".to_string(), error: FieldError::TooManyAccounts(limit) });
+    }
+
+    let accounts: Vec<_> = accounts.into_iter().map(|(pubkey, account)| program_account(pubkey, &account)).collect();
+    Ok(success(ProgramAccountsResponse { program_id: program_id.to_string(), count: accounts.len(), accounts }))
+}
+
+fn filters(program_id: &Pubkey, query: &ProgramAccountsQuery) -> Result<Vec<AccountFilter>, AppError> {
+    let token_program = *program_id == spl_token::ID || *program_id == spl_token_2022::ID;
+    let mut filters = Vec::new();
+    for (field, key, offset) in [("mint", &query.mint, 0), ("owner", &query.owner, TOKEN_OWNER_OFFSET)] {
+        let Some(key) = key else {
+            continue;
+        };
+        if !token_program {
+            return Err(AppError::Field { field: field.to_string(), error: FieldError::UnsupportedTokenProgram });
+        }
+        filters.push(AccountFilter::Memcmp { offset, bytes: key.to_bytes().to_vec() });
+    }
+    // Token-2022 accounts vary in size with their extensions, but legacy
+    // ones are all the same size, which also keeps mints out.
+    if !filters.is_empty() && *program_id == spl_token::ID {
+        filters.push(AccountFilter::DataSize(TokenAccount::LEN as u64));
+    }
+    if let Some(memcmp) = &query.memcmp {
+        filters.push(memcmp_filter(memcmp)?);
+    }
+    if let Some(size) = query.data_size {
+        filters.push(AccountFilter::DataSize(size));
+    }
+
+    if filters.is_empty() {
+        return Err(AppError::InvalidField {
+            field: "filters".to_string(),
+            message: "provide at least one of mint, owner, memcmp or data_size".to_string(),
+        });
+    }
+    Ok(filters)
+}
+
+fn memcmp_filter(memcmp: &str) -> Result<AccountFilter, AppError> {
+    let invalid = |message: &str| AppError::InvalidField { field: "memcmp".to_string(), message: message.to_string() };
+    let (offset, bytes) = memcmp.split_once(':').ok_or_else(|| invalid("expected <offset>:<base58 bytes>"))?;
+    let offset = offset.parse().map_err(|_| invalid("offset must be a byte offset"))?;
+    let bytes = bs58::decode(bytes).into_vec().map_err(|_| invalid("bytes must be base58"))?;
+    if bytes.is_empty() || bytes.len() > MAX_MEMCMP_BYTES {
+        return Err(invalid("bytes must be 1 to 128 bytes long"));
+    }
+    Ok(AccountFilter::Memcmp { offset, bytes })
+}
+
+fn program_account(pubkey: Pubkey, account: &Account) -> ProgramAccount {
+    let (layout, parsed) = match parse(account) {
+        Some((layout, parsed)) => (Some(layout.to_string()), Some(parsed)),
+        None => (None, None),
+    };
+    ProgramAccount {
+        pubkey: pubkey.to_string(),
+        lamports: account.lamports,
+        data_len: account.data.len(),
+        data: parsed.is_none().then(|| general_purpose::STANDARD.encode(&account.data)),
+        layout,
+        parsed,
+    }
+}
+
+/// Decodes the layouts `/decode` knows, by owning program and size.
+fn parse(account: &Account) -> Option<(&'static str, Value)> {
+    let data = &account.data;
+    let parsed = if account.owner == spl_token::ID || account.owner == spl_token_2022::ID {
+        // Token-2022 tags extended accounts with their type right after the
+        // base token account layout.
+        let is_mint = match data.len() {
+            Mint::LEN => true,
+            TokenAccount::LEN => false,
+            _ => *data.get(TokenAccount::LEN)? == AccountType::Mint as u8,
+        };
+        if is_mint {
+            ("mint", serde_json::to_value(decode::mint(data).ok()?))
+        } else {
+            ("token_account", serde_json::to_value(decode::token_account(data).ok()?))
+        }
+    } else if account.owner == solana_system_interface::program::ID {
+        ("nonce", serde_json::to_value(decode::nonce(data)?))
+    } else if account.owner == solana_stake_interface::program::ID {
+        ("stake", serde_json::to_value(decode::stake(data)?))
+    } else {
+        return None;
+    };
+    Some((parsed.0, parsed.1.ok()?))
+}
+
+/// Creates a buffer sized for the program and splits it into writes, each
+/// as large as a transaction signed by the payer and authority allows.
+pub async fn write_buffer(Bulk(request): Bulk<WriteBufferRequest>) -> Result<Json<Value>, AppError> {
+    let payer = *request.payer;
+    let buffer = *request.buffer;
+    let authority = request.authority.map_or(payer, |authority| *authority);
+    let program = decode_program(&request.program)?;
+    let plan = buffer_plan(&payer, &buffer, &authority, &program)?;
+
+    Ok(success(WriteBufferResponse {
+        buffer: buffer.to_string(),
+        program_len: program.len(),
+        lamports: plan.lamports,
+        instructions: plan.create.iter().map(instruction_response).collect(),
+        writes: plan.writes.iter().map(instruction_response).collect(),
+    }))
+}
+
+/// Keeps the binary for an hour and packs its buffer transactions, with
+/// what the whole deploy will cost.
+pub async fn upload(
+    State(state): State<AppState>,
+    Bulk(request): Bulk<ProgramUploadRequest>,
+) -> Result<Json<Value>, AppError> {
+    let payer = *request.payer;
+    let authority = request.authority.map_or(payer, |authority| *authority);
+    let program = decode_program(&request.program)?;
+    let stored = StoredProgram { payer, buffer: *request.buffer, authority, program };
+    let blockhash = match request.recent_blockhash {
+        Some(hash) => *hash,
+        None => state.latest_blockhash().await?.blockhash,
+    };
+    let transactions = pack_upload(&stored, blockhash)?;
+
+    let now = Utc::now();
+    let upload = ProgramUpload {
+        id: String::new(),
+        sha256: hex::encode(hash(&stored.program).to_bytes()),
+        program_len: stored.program.len(),
+        payer: payer.to_string(),
+        buffer: stored.buffer.to_string(),
+        authority: authority.to_string(),
+        cost: deploy_cost(&stored, &transactions),
+        created_at: now,
+        expires_at: now,
+    };
+    let upload = state.program_uploads.insert(upload, stored)?;
+    Ok(success(ProgramUploadResponse {
+        upload,
+        recent_blockhash: blockhash.to_string(),
+        transactions: transactions.iter().map(tx::encode).collect(),
+    }))
+}
+
+/// The upload re-packed with a fresh blockhash.
+pub async fn get_upload(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ProgramUploadQuery>,
+) -> Result<Json<Value>, AppError> {
+    let (upload, stored) = state.program_uploads.get(&id)?;
+    let blockhash = match query.recent_blockhash {
+        Some(hash) => *hash,
+        None => state.latest_blockhash().await?.blockhash,
+    };
+    let transactions = pack_upload(&stored, blockhash)?;
+    Ok(success(ProgramUploadResponse {
+        upload,
+        recent_blockhash: blockhash.to_string(),
+        transactions: transactions.iter().map(tx::encode).collect(),
+    }))
+}
+
+pub async fn delete_upload(State(state): State<AppState>, Path(id): Path<String>) -> Result<Json<Value>, AppError> {
+    Ok(success(state.program_uploads.remove(&id)?))
+}
+
+fn decode_program(encoded: &str) -> Result<Vec<u8>, AppError> {
+    let invalid = |message: String| AppError::InvalidField { field: "program".to_string(), message };
+    let program = general_purpose::STANDARD.decode(encoded).map_err(|_| invalid("expected base64".to_string()))?;
+    if UpgradeableLoaderState::size_of_programdata(program.len()) as u64 > MAX_PERMITTED_DATA_LENGTH {
+        return Err(invalid(format!("programs are at most {MAX_PERMITTED_DATA_LENGTH} bytes with their metadata")));
+    }
+    Ok(program)
+}
+
+/// The rent funding a program's buffer, and the instructions creating and
+/// filling it.
+struct BufferPlan {
+    lamports: u64,
+    create: Vec<Instruction>,
+    writes: Vec<Instruction>,
+}
+
+fn buffer_plan(payer: &Pubkey, buffer: &Pubkey, authority: &Pubkey, program: &[u8]) -> Result<BufferPlan, AppError> {
+    let lamports = Rent::default().minimum_balance(UpgradeableLoaderState::size_of_buffer(program.len()));
+    let create = loader::create_buffer(payer, buffer, authority, lamports, program.len())
+        .map_err(|_| AppError::InstructionBuild("buffer"))?;
+    let chunk = write_chunk_size(payer, buffer, authority);
+    let writes = program
+        .chunks(chunk)
+        .enumerate()
+        .map(|(i, bytes)| loader::write(buffer, authority, (i * chunk) as u32, bytes.to_vec()))
+        .collect();
+    Ok(BufferPlan { lamports, create, writes })
+}
+
+/// Largest write whose transaction, with `payer` and `authority` signing,
+/// stays within the size limit.
+fn write_chunk_size(payer: &Pubkey, buffer: &Pubkey, authority: &Pubkey) -> usize {
+    let empty = tx::unsigned(&[loader::write(buffer, authority, 0, Vec::new())], payer, Hash::default());
+    // Instruction data over 127 bytes takes a second byte of length.
+    MAX_TRANSACTION_SIZE - tx::serialized_size(&empty) - 1
+}
+
+fn pack_upload(stored: &StoredProgram, blockhash: Hash) -> Result<Vec<Transaction>, AppError> {
+    let plan = buffer_plan(&stored.payer, &stored.buffer, &stored.authority, &stored.program)?;
+    let groups = std::iter::once(plan.create).chain(plan.writes.into_iter().map(|write| vec![write])).collect();
+    Ok(tx::pack(groups, &stored.payer, blockhash)?.into_iter().map(|packed| packed.transaction).collect())
+}
+
+/// Rent and base fees for `transactions` and the deploy after them, which
+/// the payer, the new program's keypair and the authority sign.
+fn deploy_cost(stored: &StoredProgram, transactions: &[Transaction]) -> DeployCost {
+    let rent = Rent::default();
+    let buffer_lamports = rent.minimum_balance(UpgradeableLoaderState::size_of_buffer(stored.program.len()));
+    let program_lamports = rent.minimum_balance(UpgradeableLoaderState::size_of_program());
+    let program_data_lamports =
+        rent.minimum_balance(UpgradeableLoaderState::size_of_programdata(stored.program.len()));
+    let deploy_signatures = if stored.authority == stored.payer { 2 } else { 3 };
+    let signatures: u64 = transactions
+        .iter()
+        .map(|transaction| u64::from(transaction.message.header.num_required_signatures))
+        .sum::<u64>()
+        + deploy_signatures;
+    let fee_lamports = signatures * LAMPORTS_PER_SIGNATURE;
+    let total_lamports = buffer_lamports + program_lamports + program_data_lamports + fee_lamports;
+    DeployCost {
+        buffer_lamports,
+        program_lamports,
+        program_data_lamports,
+        transactions: transactions.len() + 1,
+        fee_lamports,
+        total_lamports,
+        net_lamports: total_lamports - buffer_lamports,
+    }
+}
+
+/// Deploys a new program from `buffer`. Without `max_data_len` the buffer
+/// is read over RPC for the program's length.
+pub async fn deploy(
+    State(state): State<AppState>,
+    Json(request): Json<DeployProgramRequest>,
+) -> Result<Json<Value>, AppError> {
+    let payer = *request.payer;
+    let program_id = *request.program_id;
+    let buffer = *request.buffer;
+    let authority = request.authority.map_or(payer, |authority| *authority);
+    let max_data_len = match request.max_data_len {
+        Some(len) => len,
+        None => {
+            let account = state.rpc()?.get_account(&buffer).await?.ok_or(AppError::AccountNotFound(buffer))?;
+            (account.owner == solana_sdk::bpf_loader_upgradeable::ID)
+                .then(|| account.data.len().checked_sub(UpgradeableLoaderState::size_of_buffer_metadata()))
+                .flatten()
+                .ok_or(AppError::InvalidAccount { pubkey: buffer, expected: "program buffer" })?
+        }
+    };
+
+    let lamports = Rent::default().minimum_balance(UpgradeableLoaderState::size_of_program());
+    // Deprecated in favour of loader-v4, which clusters don't deploy with yet.
+    #[allow(deprecated)]
+    let instructions =
+        loader::deploy_with_max_program_len(&payer, &program_id, &buffer, &authority, lamports, max_data_len)
+            .map_err(|_| AppError::InstructionBuild("deploy"))?;
+    Ok(success(DeployProgramResponse {
+        program_id: program_id.to_string(),
+        program_data: get_program_data_address(&program_id).to_string(),
+        max_data_len,
+        instructions: instructions.iter().map(instruction_response).collect(),
+    }))
+}
+
+pub async fn upgrade(Json(request): Json<UpgradeProgramRequest>) -> Result<Json<Value>, AppError> {
+    let spill = request.spill.map_or(*request.authority, |spill| *spill);
+    let instruction = loader::upgrade(&request.program_id, &request.buffer, &request.authority, &spill);
+    Ok(success(instruction_response(&instruction)))
+}
+
+pub async fn set_authority(ValidJson(request): ValidJson<SetProgramAuthorityRequest>) -> Result<Json<Value>, AppError> {
+    let (account, authority) = (&*request.account, &*request.authority);
+    let new_authority = request.new_authority.as_deref();
+    let instruction = match (request.account_type, request.checked, new_authority) {
+        (LoaderAccountType::Program, false, new) => loader::set_upgrade_authority(account, authority, new),
+        (LoaderAccountType::Program, true, Some(new)) => loader::set_upgrade_authority_checked(account, authority, new),
+        (LoaderAccountType::Buffer, false, Some(new)) => loader::set_buffer_authority(account, authority, new),
+        (LoaderAccountType::Buffer, true, Some(new)) => loader::set_buffer_authority_checked(account, authority, new),
+        (_, _, None) => unreachable!("validated: new_authority is required for buffers and checked changes"),
+    };
+    Ok(success(instruction_response(&instruction)))
+}
+
+pub async fn close_buffer(Json(request): Json<CloseBufferRequest>) -> Result<Json<Value>, AppError> {
+    let instruction = loader::close_any(&request.buffer, &request.recipient, Some(&request.authority), None);
+    Ok(success(instruction_response(&instruction)))
+}
@@ -0,0 +1,10 @@
+use axum::response::Response;
+
+use crate::errors::AppError;
+use crate::extract::Query;
+use crate::models::qr::QrQuery;
+use crate::qr;
+
+pub async fn encode(Query(query): Query<QrQuery>) -> Result<Response, AppError> {
+    qr::render(&query.data, query.options())
+}
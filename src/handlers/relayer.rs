@@ -0,0 +1,77 @@
+use axum::extract::State;
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+use super::success;
+use crate::errors::AppError;
+use crate::extract::Json;
+use crate::models::relayer::{RelayRequest, RelayerFeeInfo, RelayerInfo, RelaySignResponse, RelaySubmitResponse};
+use crate::policy;
+use crate::relayer::Relayer;
+use crate::state::AppState;
+use crate::tx;
+
+/// The relayer's associated token account for the configured fee mint.
+async fn fee_account(state: &AppState, relayer: &Relayer) -> Result<Option<Pubkey>, AppError> {
+    let Some(fee) = &relayer.config().fee else {
+        return Ok(None);
+    };
+    let program_id = state.accounts.token_program(&fee.mint).await?;
+    Ok(Some(get_associated_token_address_with_program_id(&relayer.fee_payer(), &fee.mint, &program_id)))
+}
+
+/// What clients need to build a transaction the relayer will accept.
+pub async fn info(State(state): State<AppState>) -> Result<Json<serde_json::Value>, AppError> {
+    let relayer = state.relayer()?;
+    let account = fee_account(&state, &relayer).await?;
+    let config = relayer.config();
+
+    let response = RelayerInfo {
+        fee_payer: relayer.fee_payer().to_string(),
+        fee: config.fee.as_ref().zip(account).map(|(fee, account)| RelayerFeeInfo {
+            mint: fee.mint.to_string(),
+            amount: fee.amount.to_string(),
+            account: account.to_string(),
+        }),
+        max_signatures: config.max_signatures,
+        allowed_programs: config.allowed_programs.iter().map(ToString::to_string).collect(),
+    };
+    Ok(success(response))
+}
+
+async fn cosign(
+    state: &AppState,
+    action: &'static str,
+    request: RelayRequest,
+) -> Result<(VersionedTransaction, Signature), AppError> {
+    let relayer = state.relayer()?;
+    let mut transaction = tx::decode(&request.transaction, "transaction")?;
+    let keys = state.accounts.account_keys(&transaction.message).await?;
+    let fee_account = fee_account(state, &relayer).await?;
+
+    relayer.check(&transaction, &keys, fee_account)?;
+    let signers = &keys[..usize::from(transaction.message.header().num_required_signatures)];
+    state.enforce_policy(action, signers, &policy::transfers(&transaction.message, &keys))?;
+    let signature = relayer.cosign(&state.keystore, &mut transaction)?;
+    Ok((transaction, signature))
+}
+
+/// Co-signs as fee payer and hands the transaction back for the client to send.
+pub async fn sign(
+    State(state): State<AppState>,
+    Json(request): Json<RelayRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let (transaction, signature) = cosign(&state, "relayer.sign", request).await?;
+    Ok(success(RelaySignResponse { transaction: tx::encode(&transaction), signature: signature.to_string() }))
+}
+
+/// Co-signs as fee payer and submits through the configured RPC.
+pub async fn submit(
+    State(state): State<AppState>,
+    Json(request): Json<RelayRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let rpc = state.rpc()?;
+    let (transaction, _) = cosign(&state, "relayer.submit", request).await?;
+    let signature = rpc.send_transaction(&transaction).await?;
+    Ok(success(RelaySubmitResponse { signature: signature.to_string() }))
+}
@@ -0,0 +1,34 @@
+use std::sync::LazyLock;
+
+use serde_json::{json, Value};
+
+use crate::extract::Json;
+use crate::models::schema;
+
+/// Response bodies pinned by the golden tests, embedded when the server is
+/// compiled so the schema examples can't drift from what the API returns.
+const EXAMPLES: [(&str, &str); 5] = [
+    ("InstructionResponse", include_str!("../../tests/golden/create_token.json")),
+    ("InstructionResponse", include_str!("../../tests/golden/mint_token.json")),
+    ("InstructionResponse", include_str!("../../tests/golden/send_sol.json")),
+    ("SendTokenResponse", include_str!("../../tests/golden/send_token.json")),
+    ("SignMessageResponse", include_str!("../../tests/golden/sign_message.json")),
+];
+
+static DOCUMENT: LazyLock<Value> = LazyLock::new(|| {
+    let mut document = schema::document();
+    for (name, golden) in EXAMPLES {
+        let body: Value = serde_json::from_str(golden).expect("golden files are JSON");
+        let Some(schema) = document["$defs"][name].as_object_mut() else { continue };
+        let examples = schema.entry("examples").or_insert_with(|| json!([]));
+        examples.as_array_mut().expect("examples is an array").push(body["data"].clone());
+    }
+    document
+});
+
+/// JSON Schema of every request and response body, for generating clients
+/// in other languages. Served without the `success`/`data` envelope so
+/// schema tooling can read it as is.
+pub async fn list() -> Json<Value> {
+    Json(DOCUMENT.clone())
+}
@@ -0,0 +1,122 @@
+use axum::{
+    extract::{Path, State},
+    response::Response,
+};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_system_interface::instruction as system_instruction;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+use super::success;
+use crate::config::PayTemplate;
+use crate::errors::{AppError, FieldError};
+use crate::extract::{Json, Query, ValidJson};
+use crate::models::qr::PayQrQuery;
+use crate::models::solana_pay::{
+    DecodeQuery, DecodeResponse, EncodeRequest, EncodeResponse, PayRequestInfo, PayTransactionRequest,
+    PayTransactionResponse,
+};
+use crate::solana_pay::{memo_instruction, PayUrl, TransferRequest};
+use crate::qr;
+use crate::state::AppState;
+use crate::tx;
+
+pub async fn encode(ValidJson(request): ValidJson<EncodeRequest>) -> Result<Json<serde_json::Value>, AppError> {
+    let url = match (request.recipient, request.link) {
+        (_, Some(link)) => PayUrl::Transaction { link },
+        (Some(recipient), None) => PayUrl::Transfer(TransferRequest {
+            recipient: *recipient,
+            amount: request.amount,
+            spl_token: request.spl_token.map(|mint| *mint),
+            reference: request.reference.iter().map(|key| **key).collect(),
+            label: request.label,
+            message: request.message,
+            memo: request.memo,
+        }),
+        // Validation guarantees one of the two is present.
+        (None, None) => return Err(AppError::Field { field: "recipient".to_string(), error: FieldError::RecipientOrLink }),
+    };
+
+    Ok(success(EncodeResponse { url: url.encode() }))
+}
+
+pub async fn decode(Query(query): Query<DecodeQuery>) -> Result<Json<serde_json::Value>, AppError> {
+    let url = PayUrl::parse(&query.url).map_err(|error| AppError::Field { field: "url".to_string(), error })?;
+    Ok(success(DecodeResponse::from(url)))
+}
+
+/// Checkout QR code. The URL is validated first so a typo can't end up
+/// printed at a till.
+pub async fn qr(Query(query): Query<PayQrQuery>) -> Result<Response, AppError> {
+    PayUrl::parse(&query.url).map_err(|error| AppError::Field { field: "url".to_string(), error })?;
+    qr::render(&query.url, query.options())
+}
+
+fn template(state: &AppState, id: &str) -> Result<PayTemplate, AppError> {
+    state
+        .config()
+        .solana_pay
+        .requests
+        .get(id)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound(format!("Payment request `{id}`")))
+}
+
+/// Transaction-request GET. The body is the bare spec shape, not our usual
+/// envelope, since wallets consume it directly.
+pub async fn request_info(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<PayRequestInfo>, AppError> {
+    let template = template(&state, &id)?;
+    Ok(Json(PayRequestInfo { label: template.label, icon: template.icon }))
+}
+
+/// Transaction-request POST: builds the template's payment with the
+/// connecting wallet as fee payer and sender.
+pub async fn request_transaction(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<PayTransactionRequest>,
+) -> Result<Json<PayTransactionResponse>, AppError> {
+    let template = template(&state, &id)?;
+    let account = *request.account;
+    if account == *template.recipient {
+        return Err(AppError::Field { field: "account".to_string(), error: FieldError::SelfTransfer });
+    }
+
+    let mut transfer = match template.spl_token {
+        None => system_instruction::transfer(&account, &template.recipient, template.amount),
+        Some(mint) => {
+            let info = state.accounts.mint(&mint).await?;
+            let source = get_associated_token_address_with_program_id(&account, &mint, &info.program_id);
+            let destination = get_associated_token_address_with_program_id(&template.recipient, &mint, &info.program_id);
+            spl_token_2022::instruction::transfer_checked(
+                &info.program_id,
+                &source,
+                &mint,
+                &destination,
+                &account,
+                &[],
+                template.amount,
+                info.decimals,
+            )
+            .map_err(|_| AppError::InstructionBuild("transfer_checked"))?
+        }
+    };
+    // References ride along as read-only keys so the merchant can find the
+    // transaction with getSignaturesForAddress.
+    transfer
+        .accounts
+        .extend(template.reference.iter().map(|key| AccountMeta::new_readonly(**key, false)));
+
+    let mut instructions: Vec<Instruction> = template.memo.as_deref().map(memo_instruction).into_iter().collect();
+    instructions.push(transfer);
+
+    let blockhash = state.latest_blockhash().await?.blockhash;
+    let transaction = tx::unsigned(&instructions, &account, blockhash);
+
+    Ok(Json(PayTransactionResponse {
+        transaction: tx::encode(&transaction),
+        message: template.message,
+    }))
+}
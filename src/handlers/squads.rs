@@ -0,0 +1,158 @@
+use axum::extract::State;
+use solana_sdk::pubkey::Pubkey;
+
+use super::jobs::instructions;
+use super::{instruction_response, success};
+use crate::errors::AppError;
+use crate::extract::{Json, ValidJson};
+use crate::models::squads::{
+    ApproveProposalRequest, CreateMultisigRequest, CreateMultisigResponse, ExecuteProposalRequest, MemberPermission,
+    ProposalInstructionsResponse, VaultTransactionRequest, VaultTransactionResponse,
+};
+use crate::squads::{self, CreateMultisig, Member, VaultMessage};
+use crate::state::AppState;
+
+/// Builds `multisig_create_v2`. The treasury it pays the creation fee to is
+/// read from the program config, so this needs RPC.
+pub async fn create_multisig(
+    State(state): State<AppState>,
+    ValidJson(request): ValidJson<CreateMultisigRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let config = squads::program_config_address();
+    let account = state.accounts.account(&config).await?;
+    let treasury = (account.owner == squads::PROGRAM_ID)
+        .then(|| squads::decode_treasury(&account.data))
+        .flatten()
+        .ok_or(AppError::InvalidAccount { pubkey: config, expected: "Squads program config" })?;
+
+    let members: Vec<Member> = request
+        .members
+        .iter()
+        .map(|member| Member { key: *member.key, mask: mask(&member.permissions) })
+        .collect();
+    let create_key = *request.create_key;
+    let instruction = squads::create_multisig(&CreateMultisig {
+        create_key,
+        creator: *request.creator,
+        treasury,
+        config_authority: request.config_authority.map(|key| *key),
+        threshold: request.threshold,
+        members: &members,
+        time_lock: request.time_lock,
+        rent_collector: request.rent_collector.map(|key| *key),
+        memo: request.memo.as_deref(),
+    });
+
+    let multisig = squads::multisig_address(&create_key);
+    Ok(success(CreateMultisigResponse {
+        multisig: multisig.to_string(),
+        vault: squads::vault_address(&multisig, 0).to_string(),
+        instructions: vec![instruction_response(&instruction)],
+    }))
+}
+
+/// Builds `vault_transaction_create` for `instructions`, run by the vault,
+/// followed by `proposal_create` so members can start voting.
+pub async fn create_vault_transaction(
+    State(state): State<AppState>,
+    ValidJson(request): ValidJson<VaultTransactionRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let multisig = *request.multisig;
+    let creator = *request.creator;
+    let rent_payer = request.rent_payer.map_or(creator, |key| *key);
+    let vault = squads::vault_address(&multisig, request.vault_index);
+
+    let message = VaultMessage::compile(&vault, &instructions(&request.instructions)?).map_err(|signer| {
+        AppError::InvalidField {
+            field: "instructions".to_string(),
+            message: format!("only the vault {vault} can sign, but {signer} would need to"),
+        }
+    })?;
+    if !message.fits() {
+        return Err(AppError::InvalidField {
+            field: "instructions".to_string(),
+            message: "at most 255 accounts and instructions, and 65535 data bytes per instruction".to_string(),
+        });
+    }
+    let index = match request.transaction_index {
+        Some(index) => index,
+        None => read_multisig(&state, &multisig).await?.transaction_index + 1,
+    };
+
+    let instructions = [
+        squads::create_vault_transaction(
+            &multisig,
+            index,
+            &creator,
+            &rent_payer,
+            request.vault_index,
+            &message.to_bytes(),
+            request.memo.as_deref(),
+        ),
+        squads::create_proposal(&multisig, index, &creator, &rent_payer),
+    ];
+    Ok(success(VaultTransactionResponse {
+        transaction_index: index,
+        vault: vault.to_string(),
+        transaction: squads::transaction_address(&multisig, index).to_string(),
+        proposal: squads::proposal_address(&multisig, index).to_string(),
+        instructions: instructions.iter().map(instruction_response).collect(),
+    }))
+}
+
+pub async fn approve(Json(request): Json<ApproveProposalRequest>) -> Result<Json<serde_json::Value>, AppError> {
+    let multisig = *request.multisig;
+    let index = request.transaction_index;
+    let instruction = squads::approve_proposal(&multisig, index, &request.member, request.memo.as_deref());
+    Ok(success(ProposalInstructionsResponse {
+        transaction_index: index,
+        proposal: squads::proposal_address(&multisig, index).to_string(),
+        instructions: vec![instruction_response(&instruction)],
+    }))
+}
+
+/// Builds `vault_transaction_execute`. The stored message is read over RPC
+/// to list the accounts it touches.
+pub async fn execute(
+    State(state): State<AppState>,
+    Json(request): Json<ExecuteProposalRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let multisig = *request.multisig;
+    let index = request.transaction_index;
+    let address = squads::transaction_address(&multisig, index);
+    let account = state.rpc()?.get_account(&address).await?.ok_or(AppError::AccountNotFound(address))?;
+    let message = (account.owner == squads::PROGRAM_ID)
+        .then(|| squads::decode_vault_transaction(&account.data))
+        .flatten()
+        .ok_or(AppError::InvalidAccount {
+            pubkey: address,
+            expected: "Squads vault transaction without lookup tables",
+        })?;
+
+    let instruction = squads::execute_vault_transaction(&multisig, index, &request.member, &message);
+    Ok(success(ProposalInstructionsResponse {
+        transaction_index: index,
+        proposal: squads::proposal_address(&multisig, index).to_string(),
+        instructions: vec![instruction_response(&instruction)],
+    }))
+}
+
+/// Reads the multisig uncached: its transaction index moves with every
+/// proposal.
+async fn read_multisig(state: &AppState, multisig: &Pubkey) -> Result<squads::Multisig, AppError> {
+    let account = state.rpc()?.get_account(multisig).await?.ok_or(AppError::AccountNotFound(*multisig))?;
+    (account.owner == squads::PROGRAM_ID)
+        .then(|| squads::decode_multisig(&account.data))
+        .flatten()
+        .ok_or(AppError::InvalidAccount { pubkey: *multisig, expected: "Squads multisig" })
+}
+
+fn mask(permissions: &[MemberPermission]) -> u8 {
+    permissions.iter().fold(0, |mask, permission| {
+        mask | match permission {
+            MemberPermission::Initiate => squads::PERMISSION_INITIATE,
+            MemberPermission::Vote => squads::PERMISSION_VOTE,
+            MemberPermission::Execute => squads::PERMISSION_EXECUTE,
+        }
+    })
+}
@@ -0,0 +1,73 @@
+use axum::extract::{Path, State};
+use futures::future;
+use rust_decimal::Decimal;
+
+use super::success;
+use crate::amount;
+use crate::errors::{AppError, FieldError};
+use crate::extract::{Json, Query};
+use crate::models::stake::{EpochReward, RewardsQuery, RewardsResponse};
+use crate::state::AppState;
+use crate::utils::parse_pubkey;
+
+const DEFAULT_REWARD_EPOCHS: u64 = 10;
+/// Each epoch is its own `getInflationReward` call.
+const MAX_REWARD_EPOCHS: u64 = 50;
+
+/// Inflation rewards `account` (a stake or vote account) earned per epoch.
+/// Rewards for an epoch are paid at the start of the next, so the range
+/// ends at the last completed epoch.
+pub async fn rewards(
+    State(state): State<AppState>,
+    Path(account): Path<String>,
+    Query(query): Query<RewardsQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let account = parse_pubkey(&account).map_err(|error| AppError::Field { field: "account".to_string(), error })?;
+    let rpc = state.rpc()?;
+
+    let range_error = |field: &str| AppError::Field {
+        field: field.to_string(),
+        error: FieldError::EpochRange(MAX_REWARD_EPOCHS),
+    };
+    let completed = rpc.get_epoch_info().await?.epoch.checked_sub(1).ok_or_else(|| range_error("end_epoch"))?;
+    let end_epoch = query.end_epoch.unwrap_or(completed);
+    if end_epoch > completed {
+        return Err(range_error("end_epoch"));
+    }
+    let start_epoch = query.start_epoch.unwrap_or(end_epoch.saturating_sub(DEFAULT_REWARD_EPOCHS - 1));
+    if start_epoch > end_epoch || end_epoch - start_epoch >= MAX_REWARD_EPOCHS {
+        return Err(range_error("start_epoch"));
+    }
+
+    let rewards = future::try_join_all((start_epoch..=end_epoch).map(|epoch| rpc.get_inflation_reward(&account, epoch)))
+        .await?
+        .into_iter()
+        .flatten()
+        .map(|reward| {
+            let earned_on = reward.post_balance.saturating_sub(reward.amount);
+            let rate = match earned_on {
+                0 => Decimal::ZERO,
+                _ => (Decimal::from(reward.amount) / Decimal::from(earned_on)).round_dp(10).normalize(),
+            };
+            EpochReward {
+                epoch: reward.epoch,
+                effective_slot: reward.effective_slot,
+                amount: reward.amount,
+                amount_sol: amount::lamports_to_sol(reward.amount).to_string(),
+                post_balance: reward.post_balance,
+                rate: rate.to_string(),
+                commission: reward.commission,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let total_rewards = rewards.iter().fold(0u64, |total, reward| total.saturating_add(reward.amount));
+    Ok(success(RewardsResponse {
+        account: account.to_string(),
+        start_epoch,
+        end_epoch,
+        total_rewards,
+        total_rewards_sol: amount::lamports_to_sol(total_rewards).to_string(),
+        rewards,
+    }))
+}
@@ -0,0 +1,56 @@
+use axum::extract::{Path, State};
+
+use super::{instruction_response, success};
+use crate::errors::AppError;
+use crate::extract::{Json, ValidJson};
+use crate::models::templates::{RenderTemplateRequest, RenderTemplateResponse, TemplateRequest};
+use crate::state::AppState;
+use crate::templates;
+
+pub async fn create(
+    State(state): State<AppState>,
+    ValidJson(request): ValidJson<TemplateRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    templates::check(&request)?;
+    Ok(success(state.templates.insert(request)))
+}
+
+pub async fn list(State(state): State<AppState>) -> Result<Json<serde_json::Value>, AppError> {
+    Ok(success(state.templates.list()))
+}
+
+pub async fn get(State(state): State<AppState>, Path(id): Path<String>) -> Result<Json<serde_json::Value>, AppError> {
+    Ok(success(state.templates.get(&id)?))
+}
+
+pub async fn update(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    ValidJson(request): ValidJson<TemplateRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    templates::check(&request)?;
+    Ok(success(state.templates.update(&id, request)?))
+}
+
+pub async fn delete(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    Ok(success(state.templates.remove(&id)?))
+}
+
+/// Fills in a template's parameters and returns its instructions, ready to
+/// sign; nothing is signed or sent.
+pub async fn render(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<RenderTemplateRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let template = state.templates.get(&id)?;
+    let instructions = templates::render(&template.template, &request.parameters)?;
+    let response = RenderTemplateResponse {
+        template_id: template.id,
+        instructions: instructions.iter().map(instruction_response).collect(),
+    };
+    Ok(success(response))
+}
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+};
+use futures::stream;
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use spl_token_2022::state::Mint;
+use spl_token_metadata_interface::state::TokenMetadata as MetadataExtension;
+
+use super::{built, instruction_response, success};
+use crate::amount;
+use crate::decode;
+use crate::errors::{AppError, FieldError};
+use crate::extract::{Json, Query, ValidJson};
+use crate::metaplex::{self, FungibleMetadata};
+use crate::models::token::{
+    CreateMetadataRequest, Holder, HoldersLine, HoldersQuery, HoldersResponse, HoldersSummary, TokenInfoResponse,
+    TokenMetadata,
+};
+use crate::ndjson;
+use crate::state::AppState;
+use crate::utils::parse_pubkey;
+
+const DEFAULT_HOLDERS_PAGE: usize = 1_000;
+const MAX_HOLDERS_PAGE: usize = 10_000;
+
+fn mint_path(mint: &str) -> Result<Pubkey, AppError> {
+    parse_pubkey(mint).map_err(|error| AppError::Field { field: "mint".to_string(), error })
+}
+
+/// `CreateMetadataAccountV3` giving an existing mint the Metaplex metadata
+/// wallets read its name, symbol and logo from. The payer and update
+/// authority default to the mint authority.
+pub async fn create_metadata(ValidJson(request): ValidJson<CreateMetadataRequest>) -> Result<Json<Value>, AppError> {
+    let mint_authority = *request.mint_authority;
+    let metadata = FungibleMetadata {
+        mint: *request.mint,
+        mint_authority,
+        payer: request.payer.map_or(mint_authority, |key| *key),
+        update_authority: request.update_authority.map_or(mint_authority, |key| *key),
+        name: &request.name,
+        symbol: &request.symbol,
+        uri: &request.uri,
+        is_mutable: request.is_mutable,
+    };
+    let instruction = metadata.create_instruction();
+
+    Ok(built(request.output_format, &instruction, || instruction_response(&instruction)))
+}
+
+/// Snapshot of who holds `mint`: every token account of it, summed per
+/// owner and sorted by balance. Only each account's owner and amount are
+/// fetched, in one call. With `Accept: application/x-ndjson` the page's
+/// holders stream one per line, followed by a summary line carrying the
+/// totals and `next_offset` the JSON body has.
+pub async fn holders(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(mint): Path<String>,
+    Query(query): Query<HoldersQuery>,
+) -> Result<Response, AppError> {
+    let mint = mint_path(&mint)?;
+    let limit = query.limit.unwrap_or(DEFAULT_HOLDERS_PAGE);
+    if !(1..=MAX_HOLDERS_PAGE).contains(&limit) {
+        return Err(AppError::Field { field: "limit".to_string(), error: FieldError::BatchSize(MAX_HOLDERS_PAGE) });
+    }
+
+    let info = state.accounts.mint(&mint).await?;
+    let accounts = state.rpc()?.get_token_balances_by_mint(&mint, &info.program_id).await?;
+
+    let mut balances: HashMap<Pubkey, (u64, usize)> = HashMap::new();
+    for (owner, amount) in accounts {
+        let entry = balances.entry(owner).or_default();
+        entry.0 = entry.0.saturating_add(amount);
+        entry.1 += 1;
+    }
+
+    let min_balance = query.min_balance.unwrap_or(0);
+    let mut owners: Vec<_> = balances.into_iter().filter(|(_, (balance, _))| *balance >= min_balance).collect();
+    owners.sort_by(|(a, (a_balance, _)), (b, (b_balance, _))| b_balance.cmp(a_balance).then(a.cmp(b)));
+
+    let holder_count = owners.len();
+    let total_balance = owners.iter().fold(0u64, |total, (_, (balance, _))| total.saturating_add(*balance));
+    let offset = query.offset.unwrap_or(0);
+    let holders = owners
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .map(|(owner, (balance, accounts))| {
+            Ok(Holder {
+                owner: owner.to_string(),
+                balance,
+                ui_balance: amount::to_ui(balance, info.decimals)
+                    .map_err(|error| AppError::Field { field: "mint".to_string(), error })?
+                    .to_string(),
+                accounts,
+            })
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    let next_offset = offset.saturating_add(limit);
+    let summary = HoldersSummary {
+        mint: mint.to_string(),
+        token_program: info.program_id.to_string(),
+        decimals: info.decimals,
+        holder_count,
+        total_balance,
+        next_offset: (next_offset < holder_count).then_some(next_offset),
+    };
+
+    if ndjson::accepts(&headers) {
+        let lines = holders.into_iter().map(HoldersLine::Holder).chain([HoldersLine::Summary(summary)]);
+        return Ok(ndjson::stream(stream::iter(lines)));
+    }
+
+    let HoldersSummary { mint, token_program, decimals, holder_count, total_balance, next_offset } = summary;
+    Ok(success(HoldersResponse { mint, token_program, decimals, holder_count, total_balance, holders, next_offset })
+        .into_response())
+}
+
+/// Supply, authorities and extensions of `mint`, read fresh rather than
+/// from the account cache since supply moves with every mint and burn.
+/// Metadata comes from the Metaplex account when there is one.
+pub async fn info(State(state): State<AppState>, Path(mint): Path<String>) -> Result<Json<Value>, AppError> {
+    let mint = mint_path(&mint)?;
+    let metadata_address = metaplex::metadata_address(&mint);
+    let rpc = state.rpc()?;
+    let (account, metadata_account) = tokio::join!(rpc.get_account(&mint), rpc.get_account(&metadata_address));
+
+    let account = account?.ok_or(AppError::AccountNotFound(mint))?;
+    let invalid = || AppError::InvalidAccount { pubkey: mint, expected: "mint" };
+    if account.owner != spl_token::ID && account.owner != spl_token_2022::ID {
+        return Err(invalid());
+    }
+    let layout = decode::mint(&account.data).map_err(|_| invalid())?;
+    let base = StateWithExtensions::<Mint>::unpack(&account.data).map_err(|_| invalid())?;
+
+    let metadata = match metadata_account? {
+        Some(metadata_account) if metadata_account.owner == metaplex::METADATA_PROGRAM_ID => {
+            metaplex::decode(&metadata_account.data).map(|metadata| TokenMetadata {
+                source: "metaplex".to_string(),
+                address: metadata_address.to_string(),
+                name: metadata.name,
+                symbol: metadata.symbol,
+                uri: metadata.uri,
+                update_authority: Some(metadata.update_authority.to_string()),
+            })
+        }
+        _ => None,
+    };
+    let metadata = metadata.or_else(|| {
+        let extension = base.get_variable_len_extension::<MetadataExtension>().ok()?;
+        Some(TokenMetadata {
+            source: "token-2022".to_string(),
+            address: mint.to_string(),
+            name: extension.name,
+            symbol: extension.symbol,
+            uri: extension.uri,
+            update_authority: Option::<Pubkey>::from(extension.update_authority).map(|key| key.to_string()),
+        })
+    });
+
+    let supply = base.base.supply;
+    Ok(success(TokenInfoResponse {
+        mint: mint.to_string(),
+        token_program: account.owner.to_string(),
+        supply,
+        ui_supply: amount::to_ui(supply, layout.decimals)
+            .map_err(|error| AppError::Field { field: "mint".to_string(), error })?
+            .to_string(),
+        decimals: layout.decimals,
+        mint_authority: layout.mint_authority,
+        freeze_authority: layout.freeze_authority,
+        extensions: layout.extensions,
+        metadata,
+    }))
+}
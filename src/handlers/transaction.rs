@@ -0,0 +1,504 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use base64::{engine::general_purpose, Engine as _};
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    instruction::Instruction,
+    message::VersionedMessage,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::{keypair::Keypair, Signer},
+    transaction::VersionedTransaction,
+};
+use solana_system_interface::instruction::advance_nonce_account;
+use spl_token::state::Account as TokenAccount;
+
+use super::jobs::instructions;
+use super::{signer, success};
+use crate::amount::{self, SOL_DECIMALS};
+use crate::confirm;
+use crate::errors::AppError;
+use crate::extract::{Json, ValidJson};
+use crate::inspect::{self, Outflow};
+use crate::jito;
+use crate::models::transaction::{
+    AccountPreview, BalanceChange, BuildTransactionRequest, BuildTransactionResponse, BundleTipInfo, InspectRequest,
+    InspectResponse, PreviewRequest, PreviewResponse, RebuildHint, Risk, SendBundleRequest, SendBundleResponse,
+    SendTransactionRequest, SendTransactionResponse, SignTransactionMessageRequest, SignTransactionMessageResponse,
+    SignatureStatus, SignerSignature, TokenPreview, VerifySignaturesRequest, VerifySignaturesResponse,
+};
+use crate::nonce_pool::Lease;
+use crate::policy;
+use crate::rpc::SolanaRpc;
+use crate::state::AppState;
+use crate::summary;
+use crate::tx::{self, MAX_TRANSACTION_SIZE};
+
+/// Fetches a confirmed transaction and summarizes the balances it moved, for
+/// crediting deposits without walking instructions.
+pub async fn parse(
+    State(state): State<AppState>,
+    Path(signature): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let signature = Signature::from_str(&signature).map_err(|_| AppError::InvalidField {
+        field: "signature".to_string(),
+        message: "expected a base58 transaction signature".to_string(),
+    })?;
+    let confirmed = state
+        .rpc()?
+        .get_transaction(&signature)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Transaction {signature}")))?;
+
+    Ok(success(summary::summarize(&signature, &confirmed)))
+}
+
+/// Base token account state for accounts owned by either token program.
+fn token_state(account: &Account) -> Option<TokenAccount> {
+    if account.owner != spl_token::ID && account.owner != spl_token_2022::ID {
+        return None;
+    }
+    // Token-2022 accounts append extensions after the base layout.
+    TokenAccount::unpack(account.data.get(..TokenAccount::LEN)?).ok()
+}
+
+/// Current balance of each outflow's source: lamports, or the token amount
+/// for token accounts. Read fresh from RPC; unreadable accounts are left out.
+async fn balances(state: &AppState, outflows: &[Outflow]) -> Result<HashMap<Pubkey, u64>, AppError> {
+    let rpc = state.rpc()?;
+    let mut balances = HashMap::new();
+    for outflow in outflows {
+        if balances.contains_key(&outflow.source) {
+            continue;
+        }
+        let Some(account) = rpc.get_account(&outflow.source).await? else {
+            continue;
+        };
+        let balance = if outflow.token {
+            match token_state(&account) {
+                Some(token) => token.amount,
+                None => continue,
+            }
+        } else {
+            account.lamports
+        };
+        balances.insert(outflow.source, balance);
+    }
+    Ok(balances)
+}
+
+/// Lints a transaction before it's signed and reports anything a wallet
+/// should warn about. Balance drains are only checked when RPC is available.
+pub async fn inspect(
+    State(state): State<AppState>,
+    ValidJson(request): ValidJson<InspectRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let transaction = tx::decode(&request.transaction, "transaction")?;
+    let message = &transaction.message;
+    let keys = state.accounts.account_keys(message).await?;
+
+    let trusted_programs: Vec<Pubkey> = request.trusted_programs.iter().map(|program| **program).collect();
+    let trusted_delegates: Vec<Pubkey> = request.trusted_delegates.iter().map(|delegate| **delegate).collect();
+    let trusted = |program: &Pubkey| trusted_programs.contains(program) || state.idls.get(program).is_some();
+    let mut scan = inspect::scan(message, &keys, trusted, &trusted_delegates);
+
+    let balances_checked = match balances(&state, &scan.outflows).await {
+        Ok(balances) => {
+            let drains = inspect::drains(&scan.outflows, request.drain_threshold_percent, |outflow| {
+                balances.get(&outflow.source).copied()
+            });
+            scan.findings.extend(drains);
+            true
+        }
+        Err(AppError::RpcUnavailable) => false,
+        Err(err) => return Err(err),
+    };
+    scan.findings.sort_by_key(|finding| finding.instruction_index);
+
+    let response = InspectResponse {
+        risk: scan.findings.iter().map(|finding| finding.severity).max().unwrap_or(Risk::None),
+        fee_payer: keys.first().map(Pubkey::to_string).unwrap_or_default(),
+        balances_checked,
+        findings: scan.findings,
+    };
+    Ok(success(response))
+}
+
+/// Reports which required signers have validly signed, so a partially
+/// signed multisig transaction can be checked before it's passed on.
+pub async fn verify_signatures(
+    Json(request): Json<VerifySignaturesRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let transaction = tx::decode(&request.transaction, "transaction")?;
+    // Signers are always static keys, in signature order.
+    let keys = transaction.message.static_account_keys();
+    let signers: Vec<SignerSignature> = transaction
+        .signatures
+        .iter()
+        .zip(transaction.verify_with_results())
+        .zip(keys)
+        .map(|((signature, valid), pubkey)| {
+            let status = match (*signature == Signature::default(), valid) {
+                (true, _) => SignatureStatus::Missing,
+                (false, true) => SignatureStatus::Valid,
+                (false, false) => SignatureStatus::Invalid,
+            };
+            SignerSignature {
+                pubkey: pubkey.to_string(),
+                status,
+                signature: (status != SignatureStatus::Missing).then(|| signature.to_string()),
+            }
+        })
+        .collect();
+
+    let valid_count = signers.iter().filter(|signer| signer.status == SignatureStatus::Valid).count();
+    let response = VerifySignaturesResponse { complete: valid_count == signers.len(), valid_count, signers };
+    Ok(success(response))
+}
+
+/// Signs bare message bytes for a coordinator assembling signatures out of
+/// band. The key must be a required signer, and the message passes the same
+/// transfer policy a send would.
+pub async fn sign_message(
+    State(state): State<AppState>,
+    ValidJson(request): ValidJson<SignTransactionMessageRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let SignTransactionMessageRequest { message, secret, key_id, signature_encoding } = request;
+    let message = tx::decode_message(&message, "message")?;
+    let keypair = signer(&state, secret, key_id)?;
+    let public_key = keypair.pubkey();
+    let keys = state.accounts.account_keys(&message).await?;
+    let required = &keys[..usize::from(message.header().num_required_signatures)];
+    let signer_index = required.iter().position(|key| *key == public_key).ok_or_else(|| AppError::InvalidField {
+        field: "message".to_string(),
+        message: format!("{public_key} is not a required signer of this message"),
+    })?;
+    state.enforce_policy("transaction.sign_message", &[public_key], &policy::transfers(&message, &keys))?;
+
+    let signature = state.crypto.run(move || keypair.sign_message(&message.serialize())).await?;
+    let response = SignTransactionMessageResponse {
+        signature: signature_encoding.encode(&signature),
+        public_key: public_key.to_string(),
+        signer_index,
+    };
+    Ok(success(response))
+}
+
+fn balance_change(pre: u64, post: u64, decimals: Option<u8>) -> BalanceChange {
+    let change = i128::from(post) - i128::from(pre);
+    BalanceChange {
+        pre: pre.to_string(),
+        post: post.to_string(),
+        change: change.to_string(),
+        ui_change: decimals.and_then(|decimals| amount::change_to_ui(change, decimals).ok()).map(|ui| ui.to_string()),
+    }
+}
+
+/// Compiles instructions from any source into one unsigned transaction.
+pub async fn build(
+    State(state): State<AppState>,
+    ValidJson(request): ValidJson<BuildTransactionRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let instructions = instructions(&request.instructions)?;
+    let blockhash = match request.recent_blockhash {
+        Some(hash) => *hash,
+        None => state.latest_blockhash().await?.blockhash,
+    };
+    Ok(success(compile(&instructions, &request.payer, blockhash, "instructions")?))
+}
+
+/// `instructions` as an unsigned transaction, or an error against `field`
+/// when they don't fit in one.
+pub(super) fn compile(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    blockhash: Hash,
+    field: &str,
+) -> Result<BuildTransactionResponse, AppError> {
+    let transaction = tx::unsigned(instructions, payer, blockhash);
+    let size = tx::serialized_size(&transaction);
+    if size > MAX_TRANSACTION_SIZE {
+        return Err(AppError::InvalidField {
+            field: field.to_string(),
+            message: format!("transaction would be {size} bytes, over the {MAX_TRANSACTION_SIZE} byte limit"),
+        });
+    }
+
+    let message = &transaction.message;
+    let signers = &message.account_keys[..usize::from(message.header.num_required_signatures)];
+    Ok(BuildTransactionResponse {
+        transaction: tx::encode(&transaction),
+        message: general_purpose::STANDARD.encode(message.serialize()),
+        recent_blockhash: blockhash.to_string(),
+        signers: signers.iter().map(ToString::to_string).collect(),
+        size,
+    })
+}
+
+/// Simulates a transaction and reports how each writable account's SOL and
+/// token balances would change, for "you will send / receive" prompts.
+pub async fn preview(
+    State(state): State<AppState>,
+    Json(request): Json<PreviewRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let transaction = tx::decode(&request.transaction, "transaction")?;
+    let message = &transaction.message;
+    let addresses: Vec<Pubkey> = state.accounts.account_keys(message)
+        .await?
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| message.is_maybe_writable(*index, None))
+        .map(|(_, address)| address)
+        .collect();
+
+    let rpc = state.rpc()?;
+    let mut before = Vec::with_capacity(addresses.len());
+    for address in &addresses {
+        before.push(rpc.get_account(address).await?);
+    }
+    let simulation = rpc.simulate_with_accounts(&transaction, &addresses).await?;
+
+    let mut accounts = Vec::new();
+    if simulation.err.is_none() {
+        for ((address, pre), post) in addresses.iter().zip(&before).zip(&simulation.accounts) {
+            let lamports = |account: &Option<Account>| account.as_ref().map_or(0, |account| account.lamports);
+            let tokens = |account: &Option<Account>| account.as_ref().and_then(token_state);
+            let (pre_token, post_token) = (tokens(pre), tokens(post));
+
+            let token = match pre_token.or(post_token) {
+                Some(base) => {
+                    let decimals = state.accounts.mint(&base.mint).await.ok().map(|mint| mint.decimals);
+                    let amount = |token: Option<TokenAccount>| token.map_or(0, |token| token.amount);
+                    Some(TokenPreview {
+                        mint: base.mint.to_string(),
+                        owner: base.owner.to_string(),
+                        decimals,
+                        amount: balance_change(amount(pre_token), amount(post_token), decimals),
+                    })
+                }
+                None => None,
+            };
+            accounts.push(AccountPreview {
+                address: address.to_string(),
+                lamports: balance_change(lamports(pre), lamports(post), Some(SOL_DECIMALS)),
+                token,
+            });
+        }
+    }
+
+    let response = PreviewResponse {
+        status: if simulation.err.is_none() { "success" } else { "failed" }.to_string(),
+        error: simulation.err,
+        logs: simulation.logs,
+        units_consumed: simulation.units_consumed,
+        accounts,
+    };
+    Ok(success(response))
+}
+
+/// Tip accounts and the minimum tip `send_bundle` enforces.
+pub async fn bundle_tip(State(state): State<AppState>) -> Result<Json<serde_json::Value>, AppError> {
+    let config = &state.config().jito;
+    let response = BundleTipInfo {
+        min_tip_lamports: config.min_tip_lamports.to_string(),
+        tip_accounts: config.tip_accounts.iter().map(ToString::to_string).collect(),
+    };
+    Ok(success(response))
+}
+
+/// Submits signed transactions as one Jito bundle. The bundle must carry a
+/// tip, since the block engine silently drops bundles that don't.
+pub async fn send_bundle(
+    State(state): State<AppState>,
+    ValidJson(request): ValidJson<SendBundleRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let block_engine = state.block_engine()?;
+    let config = &state.config().jito;
+    let tip_accounts: Vec<Pubkey> = config.tip_accounts.iter().map(|account| **account).collect();
+
+    let mut transactions = Vec::with_capacity(request.transactions.len());
+    let mut signers = Vec::new();
+    let mut transfers = Vec::new();
+    let mut tip: u64 = 0;
+    for (index, encoded) in request.transactions.iter().enumerate() {
+        let field = format!("transactions[{index}]");
+        let transaction = tx::decode(encoded, &field)?;
+        if !transaction.verify_with_results().into_iter().all(|valid| valid) {
+            return Err(AppError::InvalidField { field, message: "transaction is not fully signed".to_string() });
+        }
+        let keys = state.accounts.account_keys(&transaction.message).await?;
+        tip = tip.saturating_add(jito::tip_lamports(&transaction.message, &keys, &tip_accounts));
+        for signer in &keys[..usize::from(transaction.message.header().num_required_signatures)] {
+            if !signers.contains(signer) {
+                signers.push(*signer);
+            }
+        }
+        transfers.extend(policy::transfers(&transaction.message, &keys));
+        transactions.push(transaction);
+    }
+    if tip < config.min_tip_lamports {
+        return Err(AppError::InvalidField {
+            field: "transactions".to_string(),
+            message: format!("bundle must tip at least {} lamports to a Jito tip account", config.min_tip_lamports),
+        });
+    }
+
+    state.enforce_policy("bundle.send", &signers, &transfers)?;
+
+    let bundle_id = block_engine.send_bundle(&transactions).await?;
+    let response = SendBundleResponse {
+        bundle_id,
+        signatures: transactions.iter().map(|transaction| transaction.signatures[0].to_string()).collect(),
+        tip_lamports: tip.to_string(),
+    };
+    Ok(success(response))
+}
+
+/// Rebuilds allowed when the request doesn't set `max_rebuilds`.
+const DEFAULT_REBUILDS: u32 = 2;
+
+/// Signs `message` with exactly its required `signers`.
+fn resign(message: VersionedMessage, signers: &[Arc<Keypair>]) -> Result<VersionedTransaction, AppError> {
+    let signers: Vec<&Keypair> = signers.iter().map(AsRef::as_ref).collect();
+    VersionedTransaction::try_new(message, &signers)
+        .map_err(|err| AppError::InvalidField { field: "signers".to_string(), message: err.to_string() })
+}
+
+/// Submits a transaction and waits until it confirms or its blockhash
+/// expires. With `signers`, expired sends are re-signed on a fresh
+/// blockhash; otherwise expiry fails with `BLOCKHASH_EXPIRED` and a fresh
+/// blockhash to rebuild on.
+pub async fn send(
+    State(state): State<AppState>,
+    ValidJson(request): ValidJson<SendTransactionRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let rpc = state.rpc()?;
+    let mut transaction = tx::decode(&request.transaction, "transaction")?;
+    if let Some(lease_id) = &request.nonce_lease {
+        return send_on_nonce(&state, rpc.as_ref(), transaction, lease_id).await;
+    }
+    let signers = state.signers(request.signers)?;
+    if !signers.is_empty() {
+        transaction = resign(transaction.message, &signers)?;
+    } else if !transaction.verify_with_results().into_iter().all(|valid| valid) {
+        return Err(AppError::InvalidField {
+            field: "transaction".to_string(),
+            message: "transaction is not fully signed".to_string(),
+        });
+    }
+    let keys = state.accounts.account_keys(&transaction.message).await?;
+    let required = &keys[..usize::from(transaction.message.header().num_required_signatures)];
+    state.enforce_policy("transaction.send", required, &policy::transfers(&transaction.message, &keys))?;
+
+    let max_rebuilds = if signers.is_empty() { 0 } else { request.max_rebuilds.unwrap_or(DEFAULT_REBUILDS) };
+    let mut last_valid = match request.last_valid_block_height {
+        Some(height) => Some(height),
+        None => confirm::last_valid_block_height(rpc.as_ref(), transaction.message.recent_blockhash()).await?,
+    };
+    let mut rebuilds = 0;
+    loop {
+        let signature = transaction.signatures[0];
+        // A blockhash known to have expired can't land, so isn't sent.
+        if let Some(height) = last_valid {
+            rpc.send_transaction(&transaction).await?;
+            if let Some(confirmed) = confirm::track(rpc.as_ref(), &signature, height).await? {
+                let response = SendTransactionResponse {
+                    signature: signature.to_string(),
+                    slot: confirmed.slot,
+                    error: confirmed.meta.err,
+                    fee_lamports: confirmed.meta.fee,
+                    rebuilds,
+                    last_valid_block_height: Some(height),
+                };
+                return Ok(success(response));
+            }
+        }
+
+        let fresh = rpc.get_latest_blockhash().await?;
+        if rebuilds >= max_rebuilds {
+            return Err(AppError::BlockhashExpired(Box::new(RebuildHint {
+                signature: signature.to_string(),
+                expired_blockhash: transaction.message.recent_blockhash().to_string(),
+                last_valid_block_height: last_valid,
+                block_height: rpc.get_block_height().await?,
+                recent_blockhash: fresh.blockhash.to_string(),
+                recent_last_valid_block_height: fresh.last_valid_block_height,
+            })));
+        }
+        let mut message = transaction.message;
+        message.set_recent_blockhash(fresh.blockhash);
+        transaction = resign(message, &signers)?;
+        last_valid = Some(fresh.last_valid_block_height);
+        rebuilds += 1;
+    }
+}
+
+/// Sends `transaction` on the durable nonce leased as `lease_id`, adding the
+/// nonce authority's signature. The transaction can't expire, so it's
+/// tracked until the lease does, and the lease is freed once it lands.
+async fn send_on_nonce(
+    state: &AppState,
+    rpc: &dyn SolanaRpc,
+    mut transaction: VersionedTransaction,
+    lease_id: &str,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let pool = state.nonce_pool()?;
+    let lease = pool.lease(lease_id)?;
+    check_advance(&transaction.message, &lease, &pool.authority())?;
+    pool.cosign(&state.keystore, &mut transaction)?;
+    if !transaction.verify_with_results().into_iter().all(|valid| valid) {
+        return Err(AppError::InvalidField {
+            field: "transaction".to_string(),
+            message: "transaction is not fully signed".to_string(),
+        });
+    }
+    let keys = state.accounts.account_keys(&transaction.message).await?;
+    let required = &keys[..usize::from(transaction.message.header().num_required_signatures)];
+    state.enforce_policy("transaction.send", required, &policy::transfers(&transaction.message, &keys))?;
+
+    let signature = transaction.signatures[0];
+    rpc.send_transaction(&transaction).await?;
+    let Some(confirmed) = confirm::track_until(rpc, &signature, lease.expires_at).await? else {
+        return Err(AppError::Conflict(format!(
+            "transaction {signature} did not confirm before nonce lease {lease_id} expired"
+        )));
+    };
+    pool.release(lease_id)?;
+    let response = SendTransactionResponse {
+        signature: signature.to_string(),
+        slot: confirmed.slot,
+        error: confirmed.meta.err,
+        fee_lamports: confirmed.meta.fee,
+        rebuilds: 0,
+        last_valid_block_height: None,
+    };
+    Ok(success(response))
+}
+
+/// A durable-nonce transaction must use the leased nonce as its blockhash
+/// and advance that account, as the pool authority, in its first
+/// instruction.
+fn check_advance(message: &VersionedMessage, lease: &Lease, authority: &Pubkey) -> Result<(), AppError> {
+    let invalid = |message: String| Err(AppError::InvalidField { field: "transaction".to_string(), message });
+    if *message.recent_blockhash() != lease.nonce {
+        return invalid(format!("blockhash must be the leased nonce {}", lease.nonce));
+    }
+    let expected = advance_nonce_account(&lease.account, authority);
+    let keys = message.static_account_keys();
+    let key = |index: u8| keys.get(usize::from(index)).copied();
+    let advances = message.instructions().first().is_some_and(|instruction| {
+        key(instruction.program_id_index) == Some(expected.program_id)
+            && instruction.data == expected.data
+            && instruction.accounts.first().and_then(|&index| key(index)) == Some(lease.account)
+            && instruction.accounts.get(2).and_then(|&index| key(index)) == Some(*authority)
+    });
+    if !advances {
+        return invalid(format!("first instruction must advance nonce account {}", lease.account));
+    }
+    Ok(())
+}
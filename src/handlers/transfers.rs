@@ -0,0 +1,69 @@
+use axum::extract::{Path, State};
+
+use super::jobs::prepare;
+use super::success;
+use crate::errors::AppError;
+use crate::extract::{Identity, Json, ValidJson};
+use crate::jobs::{self, Template};
+use crate::models::transfers::{ApproveRequest, ProposalStatus, ProposeRequest};
+use crate::policy::PolicyError;
+use crate::state::AppState;
+use crate::tenant;
+
+/// Signs and submits a transfer, unless it crosses a policy approval
+/// threshold: then it is stored pending until enough other identities
+/// approve it through `/transfers/approve`.
+pub async fn propose(
+    State(state): State<AppState>,
+    Identity(proposer): Identity,
+    ValidJson(request): ValidJson<ProposeRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    state.rpc()?;
+    let signers = state.signers(request.signers)?;
+    let prepared = prepare(&signers, &request.instructions)?;
+    let tenant = tenant::current();
+    let key_ids = signers.into_iter().map(|signer| state.keystore.insert(&tenant, signer)).collect();
+    let template = Template { instructions: prepared.instructions, key_ids };
+    let config = &state.config().approvals;
+
+    let (status, signature) = match jobs::send(&state, &template, "transfers.propose", false).await {
+        Ok(signature) => (ProposalStatus::Submitting, Some(signature)),
+        Err(AppError::Policy(PolicyError::ApprovalRequired { .. })) => (ProposalStatus::Pending, None),
+        Err(err) => {
+            for key_id in &template.key_ids {
+                state.keystore.remove(key_id);
+            }
+            return Err(err);
+        }
+    };
+
+    let proposal = state.approvals.insert(proposer, template, config.required, config.ttl_secs, status);
+    let proposal = match signature {
+        Some(signature) => state.approvals.finish(&proposal.id, Ok(signature)).unwrap_or(proposal),
+        None => proposal,
+    };
+    Ok(success(proposal))
+}
+
+/// Adds the caller's approval; the one that completes the set signs and
+/// submits the transfer with a fresh blockhash.
+pub async fn approve(
+    State(state): State<AppState>,
+    Identity(approver): Identity,
+    Json(request): Json<ApproveRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let (proposal, template) = state.approvals.approve(&request.id, &approver)?;
+    let Some(template) = template else {
+        return Ok(success(proposal));
+    };
+
+    let outcome = jobs::send(&state, &template, "transfers.approve", true).await;
+    let proposal = state.approvals.finish(&request.id, outcome.as_ref().copied()).unwrap_or(proposal);
+    outcome?;
+    Ok(success(proposal))
+}
+
+pub async fn get(State(state): State<AppState>, Path(id): Path<String>) -> Result<Json<serde_json::Value>, AppError> {
+    let proposal = state.approvals.get(&id).ok_or_else(|| AppError::NotFound(format!("Proposal {id}")))?;
+    Ok(success(proposal))
+}
@@ -0,0 +1,33 @@
+use axum::extract::State;
+use solana_vote_interface::{instruction as vote_instruction, state::VoteAuthorize};
+
+use super::{built, instruction_response};
+use crate::errors::{AppError, FieldError};
+use crate::extract::{Json, ValidJson};
+use crate::models::vote::{VoteAuthorityType, VoteAuthorizeRequest, VoteWithdrawRequest};
+use crate::state::AppState;
+
+pub async fn withdraw(
+    State(state): State<AppState>,
+    ValidJson(request): ValidJson<VoteWithdrawRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let to = state.address_book.resolve(&request.to, "to")?;
+    if to == *request.vote_account {
+        return Err(AppError::Field { field: "to".to_string(), error: FieldError::SelfTransfer });
+    }
+    let instruction =
+        vote_instruction::withdraw(&request.vote_account, &request.authorized_withdrawer, request.lamports, &to);
+
+    Ok(built(request.output_format, &instruction, || instruction_response(&instruction)))
+}
+
+pub async fn authorize(Json(request): Json<VoteAuthorizeRequest>) -> Result<Json<serde_json::Value>, AppError> {
+    let authority_type = match request.authority_type {
+        VoteAuthorityType::Voter => VoteAuthorize::Voter,
+        VoteAuthorityType::Withdrawer => VoteAuthorize::Withdrawer,
+    };
+    let build = if request.checked { vote_instruction::authorize_checked } else { vote_instruction::authorize };
+    let instruction = build(&request.vote_account, &request.authority, &request.new_authority, authority_type);
+
+    Ok(built(request.output_format, &instruction, || instruction_response(&instruction)))
+}
@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+
+use solana_sdk::{
+    hash::Hash,
+    instruction::CompiledInstruction,
+    message::VersionedMessage,
+    pubkey::Pubkey,
+};
+use solana_system_interface::instruction::SystemInstruction;
+use spl_token_2022::instruction::{AuthorityType, TokenInstruction};
+
+use crate::models::transaction::{Finding, Risk};
+use crate::solana_pay::MEMO_PROGRAM_ID;
+use crate::summary::MEMO_V1_PROGRAM_ID;
+
+/// Programs every wallet already understands; anything else needs trust
+/// from the caller or a registered IDL.
+pub const KNOWN_PROGRAMS: [Pubkey; 9] = [
+    solana_system_interface::program::ID,
+    solana_sdk::compute_budget::ID,
+    solana_address_lookup_table_interface::program::ID,
+    solana_stake_interface::program::ID,
+    spl_token::ID,
+    spl_token_2022::ID,
+    spl_associated_token_account::ID,
+    MEMO_PROGRAM_ID,
+    MEMO_V1_PROGRAM_ID,
+];
+
+/// Funds an instruction moves out of `source`: lamports, or base units for a
+/// token account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outflow {
+    pub instruction_index: usize,
+    pub source: Pubkey,
+    pub amount: u64,
+    pub token: bool,
+}
+
+/// What `scan` found without looking at any balances.
+#[derive(Debug, Default)]
+pub struct Scan {
+    pub findings: Vec<Finding>,
+    pub outflows: Vec<Outflow>,
+}
+
+/// Flags risky instructions in `message`, whose full account list
+/// (lookup tables resolved) is `keys`. `trusted` decides whether a program
+/// outside `KNOWN_PROGRAMS` is acceptable.
+pub fn scan(
+    message: &VersionedMessage,
+    keys: &[Pubkey],
+    trusted: impl Fn(&Pubkey) -> bool,
+    trusted_delegates: &[Pubkey],
+) -> Scan {
+    let mut scan = Scan::default();
+    if *message.recent_blockhash() == Hash::default() {
+        scan.findings.push(Finding {
+            code: "MISSING_BLOCKHASH".to_string(),
+            severity: Risk::Low,
+            instruction_index: None,
+            message: "Transaction has no recent blockhash and cannot land as is".to_string(),
+        });
+    }
+
+    let mut flagged_programs = Vec::new();
+    for (index, instruction) in message.instructions().iter().enumerate() {
+        let Some(&program) = keys.get(usize::from(instruction.program_id_index)) else {
+            continue;
+        };
+        let account = |position: usize| {
+            instruction.accounts.get(position).and_then(|&key| keys.get(usize::from(key))).copied()
+        };
+
+        if program == solana_system_interface::program::ID {
+            system(&mut scan, index, instruction, account);
+        } else if program == spl_token::ID || program == spl_token_2022::ID {
+            token(&mut scan, index, instruction, account, trusted_delegates);
+        } else if !KNOWN_PROGRAMS.contains(&program) && !trusted(&program) && !flagged_programs.contains(&program) {
+            flagged_programs.push(program);
+            scan.findings.push(Finding {
+                code: "UNKNOWN_PROGRAM".to_string(),
+                severity: Risk::Medium,
+                instruction_index: Some(index),
+                message: format!("Invokes unrecognized program {program}"),
+            });
+        }
+    }
+    scan
+}
+
+fn name(key: Option<Pubkey>) -> String {
+    key.map(|key| key.to_string()).unwrap_or_default()
+}
+
+fn system(scan: &mut Scan, index: usize, instruction: &CompiledInstruction, account: impl Fn(usize) -> Option<Pubkey>) {
+    let Ok(decoded) = bincode::deserialize::<SystemInstruction>(&instruction.data) else {
+        return;
+    };
+    let (source, amount) = match decoded {
+        SystemInstruction::Transfer { lamports } => (account(0), lamports),
+        SystemInstruction::TransferWithSeed { lamports, .. } => (account(0), lamports),
+        SystemInstruction::Assign { owner } | SystemInstruction::AssignWithSeed { owner, .. } => {
+            scan.findings.push(Finding {
+                code: "OWNER_CHANGE".to_string(),
+                severity: Risk::High,
+                instruction_index: Some(index),
+                message: format!("Reassigns account {} to program {owner}", name(account(0))),
+            });
+            return;
+        }
+        _ => return,
+    };
+    if let Some(source) = source {
+        scan.outflows.push(Outflow { instruction_index: index, source, amount, token: false });
+    }
+}
+
+fn token(
+    scan: &mut Scan,
+    index: usize,
+    instruction: &CompiledInstruction,
+    account: impl Fn(usize) -> Option<Pubkey>,
+    trusted_delegates: &[Pubkey],
+) {
+    let Ok(decoded) = TokenInstruction::unpack(&instruction.data) else {
+        return;
+    };
+    match decoded {
+        #[allow(deprecated)]
+        TokenInstruction::Transfer { amount } => {
+            if let Some(source) = account(0) {
+                scan.outflows.push(Outflow { instruction_index: index, source, amount, token: true });
+            }
+        }
+        TokenInstruction::TransferChecked { amount, .. } => {
+            if let Some(source) = account(0) {
+                scan.outflows.push(Outflow { instruction_index: index, source, amount, token: true });
+            }
+        }
+        TokenInstruction::Approve { .. } => approval(scan, index, account(0), account(1), trusted_delegates),
+        TokenInstruction::ApproveChecked { .. } => approval(scan, index, account(0), account(2), trusted_delegates),
+        TokenInstruction::SetAuthority { authority_type, new_authority } => {
+            let new_authority = Option::<Pubkey>::from(new_authority);
+            let new_authority = new_authority.map_or("nobody".to_string(), |key| key.to_string());
+            let (code, message) = match authority_type {
+                AuthorityType::AccountOwner => (
+                    "OWNER_CHANGE",
+                    format!("Transfers ownership of token account {} to {new_authority}", name(account(0))),
+                ),
+                other => (
+                    "SET_AUTHORITY",
+                    format!("Sets the {other:?} authority of {} to {new_authority}", name(account(0))),
+                ),
+            };
+            let code = code.to_string();
+            scan.findings.push(Finding { code, severity: Risk::High, instruction_index: Some(index), message });
+        }
+        _ => {}
+    }
+}
+
+fn approval(scan: &mut Scan, index: usize, source: Option<Pubkey>, delegate: Option<Pubkey>, trusted: &[Pubkey]) {
+    if delegate.is_some_and(|delegate| trusted.contains(&delegate)) {
+        return;
+    }
+    scan.findings.push(Finding {
+        code: "TOKEN_DELEGATE".to_string(),
+        severity: Risk::Medium,
+        instruction_index: Some(index),
+        message: format!("Lets {} spend from token account {}", name(delegate), name(source)),
+    });
+}
+
+/// Reports sources whose combined outflow reaches `threshold_percent` of
+/// their current balance. `balance` returns `None` for accounts it couldn't
+/// read, which are skipped.
+pub fn drains(outflows: &[Outflow], threshold_percent: u8, balance: impl Fn(&Outflow) -> Option<u64>) -> Vec<Finding> {
+    let mut totals: BTreeMap<Pubkey, (Outflow, u128)> = BTreeMap::new();
+    for outflow in outflows {
+        totals.entry(outflow.source).or_insert((*outflow, 0)).1 += u128::from(outflow.amount);
+    }
+
+    totals
+        .into_values()
+        .filter_map(|(first, total)| {
+            let balance = u128::from(balance(&first)?);
+            (balance > 0 && total * 100 >= balance * u128::from(threshold_percent)).then(|| {
+                let unit = if first.token { "base units" } else { "lamports" };
+                Finding {
+                    code: "BALANCE_DRAIN".to_string(),
+                    severity: Risk::High,
+                    instruction_index: Some(first.instruction_index),
+                    message: format!(
+                        "Moves {total} of the {balance} {unit} held by {} ({}%)",
+                        first.source,
+                        total * 100 / balance
+                    ),
+                }
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,126 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose};
+use serde_json::json;
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::RpcRequest};
+use solana_sdk::{
+    message::VersionedMessage,
+    pubkey,
+    pubkey::Pubkey,
+    transaction::VersionedTransaction,
+};
+use solana_system_interface::instruction::SystemInstruction;
+
+use crate::correlation;
+use crate::config::JitoConfig;
+use crate::rpc::RpcError;
+
+/// Most transactions a block engine accepts in one bundle.
+pub const MAX_BUNDLE_TRANSACTIONS: usize = 5;
+
+/// Mainnet tip accounts; a bundle lands only if it pays one of them.
+pub const TIP_ACCOUNTS: [Pubkey; 8] = [
+    pubkey!("96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"),
+    pubkey!("HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe"),
+    pubkey!("Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY"),
+    pubkey!("ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49"),
+    pubkey!("DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh"),
+    pubkey!("ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt"),
+    pubkey!("DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL"),
+    pubkey!("3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT"),
+];
+
+/// Submits bundles: all of the transactions land in order in one slot, or
+/// none do.
+#[async_trait]
+pub trait BlockEngine: Send + Sync {
+    /// Returns the bundle id assigned by the block engine.
+    async fn send_bundle(&self, transactions: &[VersionedTransaction]) -> Result<String, RpcError>;
+}
+
+/// Builds the configured block engine, or `None` when bundles are disabled.
+/// Mock RPC deployments get a `MockBlockEngine` so bundles work offline.
+pub fn connect(config: &JitoConfig, mock: bool) -> Option<Arc<dyn BlockEngine>> {
+    if mock {
+        return Some(Arc::new(MockBlockEngine::new()));
+    }
+
+    config.block_engine_url.as_ref().map(|url| {
+        let endpoint = format!("{}/api/v1/bundles", url.trim_end_matches('/'));
+        Arc::new(JitoBlockEngine::new(endpoint)) as Arc<dyn BlockEngine>
+    })
+}
+
+/// Block engine spoken to over its JSON-RPC bundle endpoint.
+pub struct JitoBlockEngine {
+    client: RpcClient,
+}
+
+impl JitoBlockEngine {
+    pub fn new(url: String) -> Self {
+        Self { client: correlation::rpc_client(url) }
+    }
+}
+
+#[async_trait]
+impl BlockEngine for JitoBlockEngine {
+    async fn send_bundle(&self, transactions: &[VersionedTransaction]) -> Result<String, RpcError> {
+        let encoded = transactions
+            .iter()
+            .map(|transaction| {
+                bincode::serialize(transaction)
+                    .map(|bytes| general_purpose::STANDARD.encode(bytes))
+                    .map_err(|err| RpcError(err.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.client
+            .send(RpcRequest::Custom { method: "sendBundle" }, json!([encoded, { "encoding": "base64" }]))
+            .await
+            .map_err(|err| RpcError(err.to_string()))
+    }
+}
+
+/// Records bundles instead of sending them; ids count up from `bundle-1`.
+#[derive(Default)]
+pub struct MockBlockEngine {
+    bundles: Mutex<Vec<Vec<VersionedTransaction>>>,
+}
+
+impl MockBlockEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every bundle sent so far, oldest first.
+    pub fn bundles(&self) -> Vec<Vec<VersionedTransaction>> {
+        self.bundles.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl BlockEngine for MockBlockEngine {
+    async fn send_bundle(&self, transactions: &[VersionedTransaction]) -> Result<String, RpcError> {
+        let mut bundles = self.bundles.lock().unwrap();
+        bundles.push(transactions.to_vec());
+        Ok(format!("bundle-{}", bundles.len()))
+    }
+}
+
+/// Lamports `message` transfers to any of `tip_accounts` through the system
+/// program. `keys` is its full account list.
+pub fn tip_lamports(message: &VersionedMessage, keys: &[Pubkey], tip_accounts: &[Pubkey]) -> u64 {
+    message
+        .instructions()
+        .iter()
+        .filter(|instruction| keys[usize::from(instruction.program_id_index)] == solana_system_interface::program::ID)
+        .filter_map(|instruction| match bincode::deserialize(&instruction.data) {
+            Ok(SystemInstruction::Transfer { lamports }) => {
+                let to = instruction.accounts.get(1).map(|&index| keys[usize::from(index)])?;
+                tip_accounts.contains(&to).then_some(lamports)
+            }
+            _ => None,
+        })
+        .fold(0, u64::saturating_add)
+}
@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::Instruction,
+    message::{Message, VersionedMessage},
+    signature::Signature,
+    signer::{keypair::Keypair, Signer},
+    transaction::{Transaction, VersionedTransaction},
+};
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use crate::confirm;
+use crate::cron::Cron;
+use crate::errors::AppError;
+use crate::models::admin::JobQueueDepth;
+use crate::models::jobs::{Job, JobStatus};
+use crate::policy;
+use crate::state::AppState;
+use crate::tenant;
+
+/// When a job runs: once, or on every match of a cron expression.
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    At(DateTime<Utc>),
+    Cron { expression: String, cron: Cron },
+}
+
+impl Schedule {
+    /// First run strictly after `now`, except that one-off jobs whose time
+    /// has passed run straight away.
+    fn next(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Schedule::At(at) => Some(*at),
+            Schedule::Cron { cron, .. } => cron.next_after(now),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Sends per run, first included.
+    pub max_attempts: u32,
+    /// Wait before the first retry; doubles after each further failure.
+    pub backoff: Duration,
+    /// Resubmit at a rising priority fee until confirmed, rather than
+    /// stopping once a send is accepted.
+    pub fee_bump: Option<FeeBump>,
+}
+
+impl RetryPolicy {
+    fn delay(&self, failures: u32) -> Duration {
+        self.backoff.saturating_mul(1 << (failures - 1).min(16))
+    }
+}
+
+/// Compute-unit prices to try, in micro-lamports: `initial`, then `percent`
+/// more after each send whose blockhash expires without it confirming, up
+/// to `max`.
+#[derive(Debug, Clone, Copy)]
+pub struct FeeBump {
+    pub initial: u64,
+    pub max: u64,
+    pub percent: u8,
+    /// How often a pending send is checked for confirmation.
+    pub interval: Duration,
+}
+
+impl FeeBump {
+    /// The price after `price`, or `None` once `max` has been tried.
+    fn next(&self, price: u64) -> Option<u64> {
+        let step = (u128::from(price) * u128::from(self.percent) / 100).max(1);
+        (price < self.max).then(|| u64::try_from(u128::from(price) + step).unwrap_or(u64::MAX).min(self.max))
+    }
+}
+
+/// A fee-bumped send that confirmed.
+struct Landed {
+    signature: Signature,
+    price: u64,
+    fee: u64,
+    /// Why the transaction failed on chain, if it did.
+    err: Option<String>,
+}
+
+/// Instructions to build and sign later. The signers live in the keystore
+/// under `key_ids`, fee payer first.
+#[derive(Clone)]
+pub struct Template {
+    pub instructions: Vec<Instruction>,
+    pub key_ids: Vec<String>,
+}
+
+/// Transactions scheduled for later sending. Each job is a tokio task that
+/// sleeps until its next run, then builds the template with a fresh
+/// blockhash, signs it and sends it, retrying per its policy.
+#[derive(Default)]
+pub struct JobQueue {
+    jobs: Mutex<HashMap<String, Entry>>,
+}
+
+struct Entry {
+    tenant: String,
+    job: Arc<Mutex<Job>>,
+    task: AbortHandle,
+    key_ids: Vec<String>,
+}
+
+impl JobQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a job for the current tenant and returns its initial state.
+    /// Must be called from within the tokio runtime.
+    pub fn schedule(&self, state: AppState, schedule: Schedule, template: Template, retry: RetryPolicy) -> Job {
+        let fee_payer = state.keystore.pubkey(&template.key_ids[0]).map(|key| key.to_string()).unwrap_or_default();
+        let job = Job {
+            id: Uuid::new_v4().to_string(),
+            status: JobStatus::Scheduled,
+            fee_payer,
+            cron: match &schedule {
+                Schedule::Cron { expression, .. } => Some(expression.clone()),
+                Schedule::At(_) => None,
+            },
+            next_run: schedule.next(Utc::now()),
+            runs: 0,
+            attempts: 0,
+            last_error: None,
+            signatures: Vec::new(),
+            priority_fee: None,
+            fee_paid: None,
+        };
+        let snapshot = job.clone();
+        let job = Arc::new(Mutex::new(job));
+        let key_ids = template.key_ids.clone();
+
+        let tenant = tenant::current();
+
+        let mut jobs = self.jobs.lock().unwrap();
+        let run = tenant::scope(tenant.clone(), run(state, job.clone(), schedule, template, retry));
+        let task = tokio::spawn(run).abort_handle();
+        jobs.insert(snapshot.id.clone(), Entry { tenant, job, task, key_ids });
+        snapshot
+    }
+
+    /// The current tenant's job `id`.
+    pub fn get(&self, id: &str) -> Option<Job> {
+        let jobs = self.jobs.lock().unwrap();
+        jobs.get(id).filter(|entry| entry.tenant == tenant::current()).map(|entry| entry.job.lock().unwrap().clone())
+    }
+
+    /// Stops one of the current tenant's jobs that hasn't finished and
+    /// forgets its signers. A send
+    /// already in flight may still land.
+    pub fn cancel(&self, id: &str, state: &AppState) -> Result<Job, AppError> {
+        let jobs = self.jobs.lock().unwrap();
+        let entry = jobs
+            .get(id)
+            .filter(|entry| entry.tenant == tenant::current())
+            .ok_or_else(|| AppError::NotFound(format!("Job {id}")))?;
+        let mut job = entry.job.lock().unwrap();
+        if job.status.is_finished() {
+            return Err(AppError::Conflict(format!("Job {id} has already finished")));
+        }
+
+        entry.task.abort();
+        for key_id in &entry.key_ids {
+            state.keystore.remove(key_id);
+        }
+        job.status = JobStatus::Cancelled;
+        job.next_run = None;
+        Ok(job.clone())
+    }
+
+    /// Unfinished jobs of every tenant, by state.
+    pub fn depth(&self) -> JobQueueDepth {
+        let jobs = self.jobs.lock().unwrap();
+        let mut depth = JobQueueDepth::default();
+        for entry in jobs.values() {
+            match entry.job.lock().unwrap().status {
+                JobStatus::Scheduled => depth.scheduled += 1,
+                JobStatus::Running => depth.running += 1,
+                _ => {}
+            }
+        }
+        depth
+    }
+}
+
+async fn run(state: AppState, job: Arc<Mutex<Job>>, schedule: Schedule, template: Template, retry: RetryPolicy) {
+    let mut next = schedule.next(Utc::now());
+    while let Some(at) = next {
+        tokio::time::sleep((at - Utc::now()).to_std().unwrap_or_default()).await;
+        job.lock().unwrap().status = JobStatus::Running;
+
+        let outcome = match retry.fee_bump {
+            None => send_with_retries(&state, &job, &template, retry).await.map(|signature| (signature, None)),
+            Some(bump) => send_bumped(&state, &job, &template, retry, bump).await.map(|landed| {
+                let mut job = job.lock().unwrap();
+                job.priority_fee = Some(landed.price);
+                job.fee_paid = Some(landed.fee);
+                (landed.signature, landed.err)
+            }),
+        };
+
+        next = match &schedule {
+            Schedule::At(_) => None,
+            Schedule::Cron { cron, .. } => cron.next_after(Utc::now()),
+        };
+        let mut job = job.lock().unwrap();
+        job.runs += 1;
+        job.next_run = next;
+        match outcome {
+            Ok((signature, err)) => {
+                job.signatures.push(signature.to_string());
+                job.last_error = err.map(|err| format!("transaction {signature} failed: {err}"));
+            }
+            Err(err) => job.last_error = Some(err),
+        }
+        job.status = match (next, &job.last_error) {
+            (Some(_), _) => JobStatus::Scheduled,
+            (None, None) => JobStatus::Succeeded,
+            (None, Some(_)) => JobStatus::Failed,
+        };
+    }
+
+    for key_id in &template.key_ids {
+        state.keystore.remove(key_id);
+    }
+}
+
+/// Sends `template` until the node accepts it, backing off per `retry`.
+async fn send_with_retries(
+    state: &AppState,
+    job: &Mutex<Job>,
+    template: &Template,
+    retry: RetryPolicy,
+) -> Result<Signature, String> {
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        job.lock().unwrap().attempts = attempts;
+        match send(state, template, "jobs.run", false).await {
+            Ok(signature) => return Ok(signature),
+            // Retrying can't change a policy decision.
+            Err(err @ AppError::Policy(_)) => return Err(err.to_string()),
+            Err(err) if attempts >= retry.max_attempts => return Err(err.to_string()),
+            Err(_) => tokio::time::sleep(retry.delay(attempts)).await,
+        }
+    }
+}
+
+/// Sends `template` at `bump.initial` and again at each higher price while
+/// no send so far has confirmed. A bump is only signed once the previous
+/// send's blockhash has expired, so at most one send of a run can ever land.
+/// Failed sends are retried at the same price per `retry`; every send counts
+/// as an attempt.
+async fn send_bumped(
+    state: &AppState,
+    job: &Mutex<Job>,
+    template: &Template,
+    retry: RetryPolicy,
+    bump: FeeBump,
+) -> Result<Landed, String> {
+    let rpc = state.rpc().map_err(|err| err.to_string())?;
+    // Every send carries the same transfers, so the run is counted once.
+    let payer = state.keystore.pubkey(&template.key_ids[0]).ok_or("signer missing from keystore")?;
+    let message = VersionedMessage::Legacy(Message::new(&template.instructions, Some(&payer)));
+    check_policy(state, &message, "jobs.run", Check::Enforce).map_err(|err| err.to_string())?;
+
+    let mut price = bump.initial;
+    let mut failures = 0;
+    {
+        let mut job = job.lock().unwrap();
+        job.attempts = 0;
+        job.fee_paid = None;
+    }
+    loop {
+        {
+            let mut job = job.lock().unwrap();
+            job.attempts += 1;
+            job.priority_fee = Some(price);
+        }
+        let (transaction, last_valid_block_height) =
+            match signed(state, template, "jobs.run", Check::Counted, Some(price)).await {
+                Ok(signed) => signed,
+                Err(err @ AppError::Policy(_)) => return Err(err.to_string()),
+                Err(err) => {
+                    failures += 1;
+                    if failures >= retry.max_attempts {
+                        return Err(err.to_string());
+                    }
+                    tokio::time::sleep(retry.delay(failures)).await;
+                    continue;
+                }
+            };
+
+        // Even a send the node refused may have reached a leader, so it is
+        // watched until it lands or can't, and nothing else is signed before.
+        let signature = transaction.signatures[0];
+        let sent = rpc.send_transaction(&transaction).await;
+        let confirmed = loop {
+            match confirm::track_every(rpc.as_ref(), &signature, last_valid_block_height, bump.interval).await {
+                Ok(confirmed) => break confirmed,
+                Err(err) => {
+                    failures += 1;
+                    if failures >= retry.max_attempts {
+                        return Err(err.to_string());
+                    }
+                    tokio::time::sleep(retry.delay(failures)).await;
+                }
+            }
+        };
+        if let Some(confirmed) = confirmed {
+            return Ok(Landed { signature, price, fee: confirmed.meta.fee, err: confirmed.meta.err });
+        }
+        if let Err(err) = sent {
+            failures += 1;
+            if failures >= retry.max_attempts {
+                return Err(err.to_string());
+            }
+            tokio::time::sleep(retry.delay(failures)).await;
+            continue;
+        }
+        price = bump
+            .next(price)
+            .ok_or_else(|| format!("not confirmed at the maximum priority fee of {} micro-lamports", bump.max))?;
+    }
+}
+
+/// Which transfer-policy rules a send is checked against.
+#[derive(Debug, Clone, Copy)]
+enum Check {
+    /// Every rule, counting the transfers towards daily usage.
+    Enforce,
+    /// `Enforce` without approval thresholds, for approved transfers.
+    Approved,
+    /// The rules that don't depend on usage, for transfers already counted.
+    Counted,
+}
+
+fn check_policy(
+    state: &AppState,
+    message: &VersionedMessage,
+    action: &'static str,
+    check: Check,
+) -> Result<(), AppError> {
+    let keys = message.static_account_keys();
+    let signer_keys = &keys[..usize::from(message.header().num_required_signatures)];
+    let transfers = policy::transfers(message, keys);
+    match check {
+        Check::Enforce => state.enforce_policy(action, signer_keys, &transfers),
+        Check::Approved => state.enforce_approved_policy(action, signer_keys, &transfers),
+        Check::Counted => state.review_policy(action, signer_keys, &transfers),
+    }
+}
+
+/// Builds, signs and submits one transaction from `template` after checking
+/// the transfer policy under `action`. `approved` transactions skip the
+/// approval thresholds.
+pub async fn send(
+    state: &AppState,
+    template: &Template,
+    action: &'static str,
+    approved: bool,
+) -> Result<Signature, AppError> {
+    let check = if approved { Check::Approved } else { Check::Enforce };
+    let (transaction, _) = signed(state, template, action, check, None).await?;
+    Ok(state.rpc()?.send_transaction(&transaction).await?)
+}
+
+/// `template` signed with a fresh blockhash and checked against the policy,
+/// prefixing `price` as the compute-unit price when given. Returns the last
+/// block height the transaction can land at too.
+async fn signed(
+    state: &AppState,
+    template: &Template,
+    action: &'static str,
+    check: Check,
+    price: Option<u64>,
+) -> Result<(VersionedTransaction, u64), AppError> {
+    // Bypasses the shared cache: a retry after a dropped send needs a newer hash.
+    let latest = state.rpc()?.get_latest_blockhash().await?;
+    let signers = template
+        .key_ids
+        .iter()
+        .map(|key_id| state.keystore.get(key_id))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| AppError::Internal("signer missing from keystore".to_string()))?;
+
+    let signers: Vec<&Keypair> = signers.iter().map(AsRef::as_ref).collect();
+    let instructions = match price {
+        Some(price) => priced(&template.instructions, price),
+        None => template.instructions.clone(),
+    };
+    let transaction = VersionedTransaction::from(sign(&instructions, &signers, latest.blockhash)?);
+    check_policy(state, &transaction.message, action, check)?;
+    Ok((transaction, latest.last_valid_block_height))
+}
+
+/// Signs `instructions` with `signers`, the first paying fees. Fails unless
+/// `signers` are exactly the required signers.
+pub fn sign(instructions: &[Instruction], signers: &[&Keypair], blockhash: Hash) -> Result<Transaction, AppError> {
+    let payer = signers[0].pubkey();
+    let message = Message::new_with_blockhash(instructions, Some(&payer), &blockhash);
+    let mut transaction = Transaction::new_unsigned(message);
+    transaction
+        .try_sign(signers, blockhash)
+        .map_err(|err| AppError::InvalidField { field: "signers".to_string(), message: err.to_string() })?;
+    Ok(transaction)
+}
+
+/// `instructions` preceded by one setting the compute-unit price.
+pub fn priced(instructions: &[Instruction], micro_lamports: u64) -> Vec<Instruction> {
+    let mut priced = vec![ComputeBudgetInstruction::set_compute_unit_price(micro_lamports)];
+    priced.extend_from_slice(instructions);
+    priced
+}
@@ -0,0 +1,214 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, RwLock};
+
+use aes_gcm::aead::{Aead, Generate, KeyInit, Nonce, Payload};
+use aes_gcm::Aes256Gcm;
+use chrono::{DateTime, Utc};
+use solana_sdk::{pubkey::Pubkey, signer::{keypair::Keypair, Signer}};
+use uuid::Uuid;
+use zeroize::Zeroizing;
+
+use crate::models::keys::{KeyInfo, KeyMetadata};
+
+/// Server-held signing keys, addressed by an opaque `key_id` so callers never
+/// have to handle the secret after it is stored. Secrets are kept sealed
+/// under an in-memory master key and only opened for the caller of `get`.
+/// Each entry belongs to the tenant that stored it.
+pub struct Keystore {
+    inner: RwLock<Inner>,
+}
+
+struct Inner {
+    master: Aes256Gcm,
+    /// Bumped by every `rotate`.
+    version: u32,
+    keys: HashMap<String, Entry>,
+}
+
+struct Entry {
+    sealed: Sealed,
+    meta: KeyMeta,
+}
+
+/// Most tags one key can carry.
+pub const MAX_KEY_TAGS: usize = 16;
+
+/// What operators attach to a key to find and retire it in a large fleet.
+#[derive(Debug, Clone, Default)]
+pub struct KeyOptions {
+    pub label: Option<String>,
+    pub tags: BTreeSet<String>,
+    /// After this the key can no longer be used through its `key_id`.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<KeyMetadata> for KeyOptions {
+    fn from(metadata: KeyMetadata) -> Self {
+        KeyOptions { label: metadata.label, tags: metadata.tags.into_iter().collect(), expires_at: metadata.expires_at }
+    }
+}
+
+struct KeyMeta {
+    options: KeyOptions,
+    created_at: DateTime<Utc>,
+    uses: u64,
+    last_used_at: Option<DateTime<Utc>>,
+}
+
+/// Why `get_in` refused a `key_id`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyError {
+    /// No such key, or another tenant's.
+    Unknown,
+    Expired(DateTime<Utc>),
+}
+
+/// A secret key encrypted under the master key, with its public key as
+/// associated data.
+struct Sealed {
+    tenant: String,
+    pubkey: Pubkey,
+    nonce: Nonce<Aes256Gcm>,
+    ciphertext: Vec<u8>,
+}
+
+impl Default for Keystore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Keystore {
+    pub fn new() -> Self {
+        let inner = Inner { master: master_key(), version: 1, keys: HashMap::new() };
+        Self { inner: RwLock::new(inner) }
+    }
+
+    /// Stores `keypair` for `tenant` and returns its newly assigned id. A
+    /// keypair already stored under another id can be added again; removing
+    /// either id leaves the other in place.
+    pub fn insert(&self, tenant: &str, keypair: impl Into<Arc<Keypair>>) -> String {
+        self.insert_with(tenant, keypair, KeyOptions::default())
+    }
+
+    /// `insert` with a label, tags and expiry.
+    pub fn insert_with(&self, tenant: &str, keypair: impl Into<Arc<Keypair>>, options: KeyOptions) -> String {
+        let keypair = keypair.into();
+        let key_id = Uuid::new_v4().to_string();
+        let mut inner = self.inner.write().unwrap();
+        let sealed = seal(&inner.master, tenant.to_string(), &keypair);
+        let meta = KeyMeta { options, created_at: Utc::now(), uses: 0, last_used_at: None };
+        inner.keys.insert(key_id.clone(), Entry { sealed, meta });
+        key_id
+    }
+
+    /// Any tenant's key, for ids the service itself stored.
+    pub fn get(&self, key_id: &str) -> Option<Arc<Keypair>> {
+        let inner = self.inner.read().unwrap();
+        let entry = inner.keys.get(key_id)?;
+        Some(Arc::new(open(&inner.master, &entry.sealed)))
+    }
+
+    /// `get` for an id a caller in `tenant` supplied, counted as a use.
+    /// Other tenants' keys look absent; expired keys are refused.
+    pub fn get_in(&self, tenant: &str, key_id: &str) -> Result<Arc<Keypair>, KeyError> {
+        let mut guard = self.inner.write().unwrap();
+        let inner = &mut *guard;
+        let entry = inner.keys.get_mut(key_id).filter(|entry| entry.sealed.tenant == tenant).ok_or(KeyError::Unknown)?;
+        let now = Utc::now();
+        if let Some(expires_at) = entry.meta.options.expires_at.filter(|expires_at| *expires_at <= now) {
+            return Err(KeyError::Expired(expires_at));
+        }
+        entry.meta.uses += 1;
+        entry.meta.last_used_at = Some(now);
+        Ok(Arc::new(open(&inner.master, &entry.sealed)))
+    }
+
+    pub fn pubkey(&self, key_id: &str) -> Option<Pubkey> {
+        self.inner.read().unwrap().keys.get(key_id).map(|entry| entry.sealed.pubkey)
+    }
+
+    /// `tenant`'s keys, oldest first, optionally only those carrying `tag`.
+    pub fn list(&self, tenant: &str, tag: Option<&str>) -> Vec<KeyInfo> {
+        let inner = self.inner.read().unwrap();
+        let mut keys: Vec<KeyInfo> = inner
+            .keys
+            .iter()
+            .filter(|(_, entry)| entry.sealed.tenant == tenant)
+            .filter(|(_, entry)| tag.is_none_or(|tag| entry.meta.options.tags.contains(tag)))
+            .map(|(key_id, entry)| info(key_id, entry))
+            .collect();
+        keys.sort_by_key(|key| key.created_at);
+        keys
+    }
+
+    /// Replaces the label, tags and expiry of one of `tenant`'s keys.
+    pub fn update(&self, tenant: &str, key_id: &str, options: KeyOptions) -> Option<KeyInfo> {
+        let mut inner = self.inner.write().unwrap();
+        let entry = inner.keys.get_mut(key_id).filter(|entry| entry.sealed.tenant == tenant)?;
+        entry.meta.options = options;
+        Some(info(key_id, entry))
+    }
+
+    pub fn remove(&self, key_id: &str) -> bool {
+        self.inner.write().unwrap().keys.remove(key_id).is_some()
+    }
+
+    /// Replaces the master key, re-encrypting every entry under the new one.
+    /// Returns the new key's version and the number of entries re-encrypted.
+    pub fn rotate(&self) -> (u32, usize) {
+        let mut guard = self.inner.write().unwrap();
+        let inner = &mut *guard;
+        let master = master_key();
+        for entry in inner.keys.values_mut() {
+            entry.sealed = seal(&master, entry.sealed.tenant.clone(), &open(&inner.master, &entry.sealed));
+        }
+        inner.master = master;
+        inner.version += 1;
+        (inner.version, inner.keys.len())
+    }
+
+    pub fn version(&self) -> u32 {
+        self.inner.read().unwrap().version
+    }
+}
+
+fn info(key_id: &str, entry: &Entry) -> KeyInfo {
+    let options = &entry.meta.options;
+    KeyInfo {
+        key_id: key_id.to_string(),
+        pubkey: entry.sealed.pubkey.to_string(),
+        label: options.label.clone(),
+        tags: options.tags.iter().cloned().collect(),
+        created_at: entry.meta.created_at,
+        expires_at: options.expires_at,
+        expired: options.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()),
+        uses: entry.meta.uses,
+        last_used_at: entry.meta.last_used_at,
+    }
+}
+
+fn master_key() -> Aes256Gcm {
+    let key = Zeroizing::new(<[u8; 32]>::generate());
+    Aes256Gcm::new_from_slice(key.as_ref()).expect("AES-256 key is 32 bytes")
+}
+
+fn seal(master: &Aes256Gcm, tenant: String, keypair: &Keypair) -> Sealed {
+    let pubkey = keypair.pubkey();
+    let nonce = Nonce::<Aes256Gcm>::generate();
+    let secret = Zeroizing::new(keypair.to_bytes());
+    let ciphertext = master
+        .encrypt(&nonce, Payload { msg: secret.as_slice(), aad: pubkey.as_ref() })
+        .expect("AES-GCM encryption of a keypair can't fail");
+    Sealed { tenant, pubkey, nonce, ciphertext }
+}
+
+/// Entries are only ever sealed by this keystore under its current master
+/// key, so failing to open one is a bug rather than bad input.
+fn open(master: &Aes256Gcm, sealed: &Sealed) -> Keypair {
+    let secret = master
+        .decrypt(&sealed.nonce, Payload { msg: &sealed.ciphertext, aad: sealed.pubkey.as_ref() })
+        .map(Zeroizing::new)
+        .expect("keystore entry sealed under the current master key");
+    Keypair::try_from(secret.as_slice()).expect("keystore entry holds a valid keypair")
+}
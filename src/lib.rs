@@ -0,0 +1,229 @@
+pub mod address_book;
+pub mod amount;
+pub mod anchor;
+pub mod approvals;
+pub mod audit;
+pub mod blockhash;
+pub mod borsh;
+pub mod cache;
+pub mod codec;
+pub mod config;
+pub mod confirm;
+pub mod cron;
+pub mod correlation;
+pub mod crypto;
+pub mod das;
+pub mod decode;
+#[cfg(feature = "dev-tools")]
+pub mod dev;
+pub mod errors;
+pub mod etag;
+pub mod extract;
+pub mod handlers;
+pub mod inspect;
+pub mod jito;
+pub mod jobs;
+pub mod keystore;
+pub mod listener;
+pub mod merkle_tree;
+pub mod metaplex;
+pub mod metrics;
+pub mod nft;
+pub mod ndjson;
+pub mod nonce_pool;
+pub mod pipe;
+pub mod policy;
+pub mod program_uploads;
+pub mod qr;
+pub mod recover;
+pub mod relayer;
+pub mod reporting;
+pub mod request_id;
+pub mod routes;
+pub mod rpc;
+pub mod simulate;
+pub mod solana_pay;
+pub mod squads;
+pub mod state;
+pub mod summary;
+pub mod templates;
+pub mod tenant;
+pub mod tls;
+pub mod tx;
+pub mod upload;
+pub mod validation;
+
+pub use superdev_models as models;
+pub use superdev_models::{parse as utils, types};
+
+use axum::{
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{get, post, put},
+    Router,
+};
+use tower_http::compression::CompressionLayer;
+
+use state::AppState;
+
+pub fn app(state: AppState) -> Router {
+    // Deterministic reads: responses carry an ETag and honour If-None-Match.
+    // The ETag layer sits outside negotiation and compression so each
+    // encoding gets its own tag.
+    let reads = Router::new()
+        .route("/derive/ata", get(handlers::derive::ata))
+        .route("/derive/pda", get(handlers::derive::pda))
+        .route("/convert/sol", get(handlers::convert::sol))
+        .route("/convert/token", get(handlers::convert::token))
+        .route("/solana-pay/decode", get(handlers::solana_pay::decode))
+        .route("/solana-pay/qr", get(handlers::solana_pay::qr))
+        .route("/qr", get(handlers::qr::encode))
+        .route("/merkle-tree/cost", get(handlers::merkle_tree::tree_cost))
+        .route("/schemas", get(handlers::schemas::list))
+        .layer(middleware::from_fn_with_state(state.clone(), recover::catch_panic))
+        .layer(middleware::from_fn_with_state(state.clone(), reporting::report))
+        .layer(middleware::from_fn(codec::negotiate))
+        .layer(CompressionLayer::new())
+        .layer(middleware::from_fn(etag::conditional));
+
+    let router = Router::new()
+        .route("/keypair", post(handlers::generate_keypair))
+        .route("/keypair/export-encrypted", post(handlers::export_encrypted))
+        .route("/keypair/import-encrypted", post(handlers::import_encrypted))
+        .route("/keys", get(handlers::keys::list))
+        .route("/keys/{key_id}", put(handlers::keys::update))
+        .route("/token/create", post(handlers::create_token))
+        .route("/token/create-metadata", post(handlers::token::create_metadata))
+        .route("/token/mint", post(handlers::mint_token))
+        .route("/message/sign", post(handlers::sign_message))
+        .route("/message/verify", post(handlers::verify_message))
+        .route("/message/verify-batch", post(handlers::verify_message_batch))
+        .route("/send/sol", post(handlers::send_sol))
+        .route("/v1/send/sol", post(handlers::send_sol_v1))
+        .route("/send/sol-batch", post(handlers::send_sol_batch))
+        .route("/send/token", post(handlers::send_token))
+        .route("/account/{owner}/cleanup", post(handlers::account::cleanup))
+        .route("/admin", get(handlers::admin::get))
+        .route("/admin/reload", post(handlers::admin::reload))
+        .route("/admin/keystore/rotate", post(handlers::admin::rotate_master_key))
+        .route("/admin/api-keys/revoke", post(handlers::admin::revoke_api_key))
+        .route("/admin/features", post(handlers::admin::features))
+        .route("/debug/runtime", get(handlers::admin::runtime))
+        .route("/address-book", get(handlers::address_book::list).post(handlers::address_book::create))
+        .route(
+            "/address-book/{label}",
+            get(handlers::address_book::get).put(handlers::address_book::update).delete(handlers::address_book::delete),
+        )
+        .route("/airdrop/bulk", post(handlers::airdrop::bulk))
+        .route("/alt/plan", post(handlers::alt::plan))
+        .route("/audit", get(handlers::audit::list))
+        .route("/anchor/idl", post(handlers::anchor::register_idl))
+        .route("/anchor/instruction", post(handlers::anchor::instruction))
+        .route("/anchor/parse-logs", post(handlers::anchor::parse_logs))
+        .route("/borsh/encode", post(handlers::borsh::encode))
+        .route("/borsh/decode", post(handlers::borsh::decode))
+        .route("/compose", post(handlers::compose::compose))
+        .route("/decode/mint", post(handlers::decode::mint))
+        .route("/decode/nonce", post(handlers::decode::nonce))
+        .route("/decode/stake", post(handlers::decode::stake))
+        .route("/decode/token-account", post(handlers::decode::token_account))
+        .route("/jobs", post(handlers::jobs::schedule))
+        .route("/jobs/{id}", get(handlers::jobs::get).delete(handlers::jobs::cancel))
+        .route("/nonce-pool", get(handlers::nonce_pool::status))
+        .route("/nonce-pool/accounts", post(handlers::nonce_pool::add))
+        .route("/nonce-pool/acquire", post(handlers::nonce_pool::acquire))
+        .route("/nonce-pool/release", post(handlers::nonce_pool::release))
+        .route("/relayer", get(handlers::relayer::info))
+        .route("/relayer/sign", post(handlers::relayer::sign))
+        .route("/relayer/submit", post(handlers::relayer::submit))
+        .route("/solana-pay/encode", post(handlers::solana_pay::encode))
+        .route(
+            "/solana-pay/tx/{id}",
+            get(handlers::solana_pay::request_info).post(handlers::solana_pay::request_transaction),
+        )
+        .route("/squads/multisig", post(handlers::squads::create_multisig))
+        .route("/squads/vault-transaction", post(handlers::squads::create_vault_transaction))
+        .route("/squads/approve", post(handlers::squads::approve))
+        .route("/squads/execute", post(handlers::squads::execute))
+        .route("/templates", get(handlers::templates::list).post(handlers::templates::create))
+        .route(
+            "/templates/{id}",
+            get(handlers::templates::get).put(handlers::templates::update).delete(handlers::templates::delete),
+        )
+        .route("/templates/{id}/render", post(handlers::templates::render))
+        .route("/token/{mint}/holders", get(handlers::token::holders))
+        .route("/token/{mint}/info", get(handlers::token::info))
+        .route("/nft/{mint}/metadata", get(handlers::nft::metadata))
+        .route("/nft/burn", post(handlers::nft::burn))
+        .route("/nft/print-edition", post(handlers::nft::print_edition))
+        .route("/assets/by-owner", get(handlers::assets::by_owner))
+        .route("/assets/{id}", get(handlers::assets::asset))
+        .route("/cluster/epoch-info", get(handlers::cluster::epoch_info))
+        .route("/cluster/slot", get(handlers::cluster::slot))
+        .route("/cluster/version", get(handlers::cluster::version))
+        .route("/cluster/validators", get(handlers::cluster::validators))
+        .route("/stake/{account}/rewards", get(handlers::stake::rewards))
+        .route("/block/{slot}", get(handlers::block::block))
+        .route("/program/{id}/accounts", get(handlers::program::accounts))
+        .route(
+            "/program/buffer",
+            post(handlers::program::write_buffer).layer(DefaultBodyLimit::max(handlers::program::MAX_BUFFER_BODY)),
+        )
+        .route(
+            "/program/uploads",
+            post(handlers::program::upload).layer(DefaultBodyLimit::max(handlers::program::MAX_BUFFER_BODY)),
+        )
+        .route("/program/uploads/{id}", get(handlers::program::get_upload).delete(handlers::program::delete_upload))
+        .route("/program/deploy", post(handlers::program::deploy))
+        .route("/program/upgrade", post(handlers::program::upgrade))
+        .route("/program/set-authority", post(handlers::program::set_authority))
+        .route("/program/close-buffer", post(handlers::program::close_buffer))
+        .route("/merkle-tree/create", post(handlers::merkle_tree::create))
+        .route("/merkle-tree/append", post(handlers::merkle_tree::append))
+        .route("/transfers/propose", post(handlers::transfers::propose))
+        .route("/transfers/approve", post(handlers::transfers::approve))
+        .route("/transfers/{id}", get(handlers::transfers::get))
+        .route("/vote/withdraw", post(handlers::vote::withdraw))
+        .route("/vote/authorize", post(handlers::vote::authorize))
+        .route("/transaction/build", post(handlers::transaction::build))
+        .route("/transaction/inspect", post(handlers::transaction::inspect))
+        .route("/transaction/preview", post(handlers::transaction::preview))
+        .route("/transaction/verify-signatures", post(handlers::transaction::verify_signatures))
+        .route("/transaction/sign-message", post(handlers::transaction::sign_message))
+        .route("/transaction/bundle-tip", get(handlers::transaction::bundle_tip))
+        .route("/transaction/send", post(handlers::transaction::send))
+        .route("/transaction/send-bundle", post(handlers::transaction::send_bundle))
+        .route("/transaction/parse/{signature}", get(handlers::transaction::parse))
+        .layer(middleware::from_fn_with_state(state.clone(), recover::catch_panic))
+        .layer(middleware::from_fn_with_state(state.clone(), simulate::dry_run))
+        .layer(middleware::from_fn_with_state(state.clone(), reporting::report))
+        .layer(middleware::from_fn_with_state(state.clone(), audit::capture))
+        .layer(middleware::from_fn_with_state(state.clone(), tenant::assign))
+        .layer(middleware::from_fn(codec::negotiate))
+        .route("/metrics", get(metrics::export))
+        .route("/health", get(handlers::admin::health))
+        .merge(reads)
+        .with_state(state.clone());
+
+    #[cfg(feature = "dev-tools")]
+    let router = router
+        .nest("/dev", dev::routes(state.clone()))
+        .route("/keypair/deterministic", post(dev::deterministic_keypair));
+
+    // Panics in the groups above are caught inside `reporting::report`, so
+    // they're reported; this catches the rest, e.g. in `/metrics`.
+    let router = router
+        .fallback(routes::not_found)
+        .layer(middleware::from_fn_with_state(state.clone(), recover::catch_panic))
+        .layer(middleware::from_fn_with_state(state.clone(), routes::gate))
+        .layer(middleware::from_fn(request_id::assign))
+        .layer(middleware::from_fn_with_state(state, metrics::track));
+
+    // Responses are compressed as `Accept-Encoding` allows; ones compressed
+    // under the ETag layer are left alone.
+    Router::new()
+        .fallback_service(router)
+        .layer(middleware::from_fn(routes::method_not_allowed))
+        .layer(middleware::from_fn(correlation::assign))
+        .layer(CompressionLayer::new())
+}
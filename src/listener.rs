@@ -0,0 +1,147 @@
+use std::future::{Future, IntoFuture};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{Extension, Router};
+use tokio::net::{TcpListener, TcpSocket};
+use tokio::sync::Notify;
+
+use crate::config::ListenerConfig;
+use crate::routes::{RouteGroup, Served};
+
+/// Pending connections the kernel queues before `accept`.
+const BACKLOG: u32 = 1024;
+
+/// A listening socket and the route groups served on it.
+#[derive(Debug)]
+pub struct Listener {
+    pub socket: TcpListener,
+    /// All groups when empty.
+    pub groups: Vec<RouteGroup>,
+}
+
+/// Route groups served on `admin_address` when that's set, and then no
+/// longer on listeners that serve every group.
+pub const OPERATIONS: [RouteGroup; 4] =
+    [RouteGroup::Admin, RouteGroup::Debug, RouteGroup::Health, RouteGroup::Metrics];
+
+/// The configured listeners: `address` first, then each of `extra`, then
+/// `admin_address`. Under systemd socket activation the passed sockets are
+/// taken in that order instead of binding.
+pub fn listeners(config: &ListenerConfig) -> io::Result<Vec<Listener>> {
+    let mut binds = vec![(config.address.as_str(), config.groups.clone())];
+    binds.extend(config.extra.iter().map(|bind| (bind.address.as_str(), bind.groups.clone())));
+    if let Some(admin) = &config.admin_address {
+        for (_, groups) in binds.iter_mut().filter(|(_, groups)| groups.is_empty()) {
+            groups.extend(RouteGroup::ALL.into_iter().filter(|group| !OPERATIONS.contains(group)));
+        }
+        binds.push((admin.as_str(), OPERATIONS.to_vec()));
+    }
+    let mut activated = activated(binds.len())?.into_iter();
+    binds
+        .into_iter()
+        .map(|(address, groups)| {
+            let socket = match activated.next() {
+                Some(socket) => socket,
+                None => bind(address, config.reuse_port)?,
+            };
+            Ok(Listener { socket, groups })
+        })
+        .collect()
+}
+
+/// A new socket listening on `address`. With `reuse_port`, several
+/// processes share the port and the kernel spreads connections among them,
+/// so a deploy can start the new release before stopping the old one.
+pub fn bind(address: &str, reuse_port: bool) -> io::Result<TcpListener> {
+    let address: SocketAddr = address.parse().map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    let socket = if address.is_ipv6() { TcpSocket::new_v6()? } else { TcpSocket::new_v4()? };
+    socket.set_reuseaddr(true)?;
+    #[cfg(unix)]
+    socket.set_reuseport(reuse_port)?;
+    #[cfg(not(unix))]
+    let _ = reuse_port;
+    socket.bind(address)?;
+    socket.listen(BACKLOG)
+}
+
+/// Up to `wanted` sockets passed by systemd's socket activation protocol,
+/// if `LISTEN_PID` names this process.
+#[cfg(unix)]
+fn activated(wanted: usize) -> io::Result<Vec<TcpListener>> {
+    use std::os::fd::{FromRawFd, RawFd};
+
+    /// `SD_LISTEN_FDS_START`: passed sockets are numbered from here.
+    const FIRST: RawFd = 3;
+
+    let var = |name| std::env::var(name).ok().and_then(|value| value.parse::<u32>().ok());
+    if var("LISTEN_PID") != Some(std::process::id()) {
+        return Ok(Vec::new());
+    }
+    let passed = var("LISTEN_FDS").unwrap_or(0) as usize;
+    (FIRST..)
+        .take(passed.min(wanted))
+        .map(|fd| {
+            // SAFETY: under socket activation the process owns the passed
+            // descriptors and nothing else has taken them.
+            let listener = unsafe { std::net::TcpListener::from_raw_fd(fd) };
+            listener.set_nonblocking(true)?;
+            TcpListener::from_std(listener)
+        })
+        .collect()
+}
+
+#[cfg(not(unix))]
+fn activated(_wanted: usize) -> io::Result<Vec<TcpListener>> {
+    Ok(Vec::new())
+}
+
+/// `app` serving only `groups`, or everything when that's empty. Other
+/// groups answer as unknown paths.
+pub fn restrict(app: Router, groups: &[RouteGroup]) -> Router {
+    if groups.is_empty() {
+        return app;
+    }
+    app.layer(Extension(Served(groups.into())))
+}
+
+/// Resolves on SIGTERM (what systemd and Kubernetes send) or Ctrl-C.
+pub async fn shutdown_signal() {
+    #[cfg(unix)]
+    let terminate = async {
+        let mut signal = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install the SIGTERM handler");
+        signal.recv().await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = terminate => {}
+    }
+}
+
+/// `axum::serve` that stops accepting once `shutdown` resolves, then gives
+/// in-flight requests up to `drain` to finish.
+pub async fn serve(
+    listener: TcpListener,
+    app: Router,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+    drain: Duration,
+) -> io::Result<()> {
+    let stopping = Arc::new(Notify::new());
+    let signal = {
+        let stopping = stopping.clone();
+        async move {
+            shutdown.await;
+            stopping.notify_one();
+        }
+    };
+    let server = axum::serve(listener, app).with_graceful_shutdown(signal).into_future();
+    tokio::select! {
+        result = server => result,
+        _ = async { stopping.notified().await; tokio::time::sleep(drain).await } => Ok(()),
+    }
+}
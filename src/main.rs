@@ -1,27 +1,57 @@
-mod handlers;
-mod models;
+use std::time::Duration;
 
-use axum::{
-    routing::post,
-    Router,
-};
+use solana_fellowship_server::{approvals, config::Config, listener, pipe, state::AppState, tls};
+use tokio::io::{stdin, stdout, BufReader};
+use tokio::task::JoinSet;
 
-#[tokio::main]
-async fn main() {
-    let app = Router::new()
-        .route("/keypair", post(handlers::generate_keypair))
-        .route("/token/create", post(handlers::create_token))
-        .route("/token/mint", post(handlers::mint_token))
-        .route("/message/sign", post(handlers::sign_message))
-        .route("/message/verify", post(handlers::verify_message))
-        .route("/send/sol", post(handlers::send_sol))
-        .route("/send/token", post(handlers::send_token));
+fn main() {
+    let config = Config::load().expect("invalid configuration");
+    let mut runtime = tokio::runtime::Builder::new_multi_thread();
+    runtime.enable_all().max_blocking_threads(config.runtime.max_blocking_threads);
+    if let Some(threads) = config.runtime.worker_threads {
+        runtime.worker_threads(threads);
+    }
+    runtime.build().expect("failed to start the tokio runtime").block_on(run(config));
+}
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000")
-        .await
-        .unwrap();
-        
-    println!("Server running on http://0.0.0.0:3000");
-    
-    axum::serve(listener, app).await.unwrap();
-}
\ No newline at end of file
+async fn run(config: Config) {
+    #[cfg(feature = "tokio-console")]
+    console_subscriber::init();
+
+    let tls = tls::server_config(&config.tls).expect("invalid TLS configuration");
+    let listening = config.listener.clone();
+    let state = AppState::new(config);
+    #[cfg(unix)]
+    state.reload_on_hangup().expect("failed to install the SIGHUP handler");
+    approvals::sweep_every(state.approvals.clone(), Duration::from_secs(60));
+    let app = solana_fellowship_server::app(state);
+
+    // Newline-delimited JSON on stdin/stdout instead of a listener.
+    if std::env::args().skip(1).any(|arg| arg == "--pipe") {
+        pipe::run(app, BufReader::new(stdin()), stdout()).await.unwrap();
+        return;
+    }
+
+    let drain = Duration::from_secs(listening.drain_timeout_secs);
+    let mut servers = JoinSet::new();
+    for bound in listener::listeners(&listening).expect("failed to bind the listeners") {
+        let address = bound.socket.local_addr().unwrap();
+        let app = listener::restrict(app.clone(), &bound.groups);
+        match tls.clone() {
+            Some(tls) => {
+                println!("Server running on https://{address}");
+                servers.spawn(async move {
+                    tls::serve(bound.socket, tls, app, listener::shutdown_signal(), drain).await;
+                    Ok(())
+                });
+            }
+            None => {
+                println!("Server running on http://{address}");
+                servers.spawn(listener::serve(bound.socket, app, listener::shutdown_signal(), drain));
+            }
+        }
+    }
+    while let Some(server) = servers.join_next().await {
+        server.unwrap().unwrap();
+    }
+}
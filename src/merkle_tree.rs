@@ -0,0 +1,101 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+};
+
+use crate::anchor::sighash;
+
+/// The SPL account compression program.
+pub const PROGRAM_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+/// The noop program the compression program logs changelogs through, so
+/// indexers can rebuild the tree from transactions.
+pub const NOOP_PROGRAM_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+/// The `(max_depth, max_buffer_size)` pairs the program is compiled for;
+/// any other pair fails to initialize.
+pub const DEPTH_SIZE_PAIRS: [(u32, u32); 34] = [
+    (3, 8),
+    (5, 8),
+    (6, 16),
+    (7, 16),
+    (8, 16),
+    (9, 16),
+    (10, 32),
+    (11, 32),
+    (12, 32),
+    (13, 32),
+    (14, 64),
+    (14, 256),
+    (14, 1024),
+    (14, 2048),
+    (15, 64),
+    (16, 64),
+    (17, 64),
+    (18, 64),
+    (19, 64),
+    (20, 64),
+    (20, 256),
+    (20, 1024),
+    (20, 2048),
+    (24, 64),
+    (24, 256),
+    (24, 512),
+    (24, 1024),
+    (24, 2048),
+    (26, 512),
+    (26, 1024),
+    (26, 2048),
+    (30, 512),
+    (30, 1024),
+    (30, 2048),
+];
+
+/// A deeper canopy no longer fits in an account alongside the largest tree.
+pub const MAX_CANOPY_DEPTH: u32 = 17;
+
+/// Account type and version bytes, then `max_buffer_size`, `max_depth`,
+/// the authority, the creation slot and padding.
+const HEADER_SIZE: usize = 2 + 4 + 4 + 32 + 8 + 6;
+
+pub fn supported(max_depth: u32, max_buffer_size: u32) -> bool {
+    DEPTH_SIZE_PAIRS.contains(&(max_depth, max_buffer_size))
+}
+
+/// Size of a tree account: the header, the concurrent tree (its sequence
+/// number, active index and buffer size, a changelog per buffer slot and
+/// the rightmost path) and the canopy's cached upper nodes.
+pub fn account_size(max_depth: u32, max_buffer_size: u32, canopy_depth: u32) -> usize {
+    let depth = max_depth as usize;
+    // A root, a path of `depth` nodes and a u32 index with padding.
+    let changelog = 32 + 32 * depth + 8;
+    // A proof of `depth` nodes, the leaf and a u32 index with padding.
+    let path = 32 * depth + 32 + 8;
+    let tree = 3 * 8 + max_buffer_size as usize * changelog + path;
+    let canopy = ((1usize << (canopy_depth + 1)) - 2) * 32;
+    HEADER_SIZE + tree + canopy
+}
+
+/// `init_empty_merkle_tree` for an account already allocated with
+/// `account_size` bytes and owned by the program.
+pub fn init_empty_merkle_tree(tree: &Pubkey, authority: &Pubkey, max_depth: u32, max_buffer_size: u32) -> Instruction {
+    let mut data = sighash("global", "init_empty_merkle_tree").to_vec();
+    data.extend_from_slice(&max_depth.to_le_bytes());
+    data.extend_from_slice(&max_buffer_size.to_le_bytes());
+    Instruction { program_id: PROGRAM_ID, accounts: accounts(tree, authority), data }
+}
+
+/// `append`, adding `leaf` as the tree's next leaf.
+pub fn append(tree: &Pubkey, authority: &Pubkey, leaf: &[u8; 32]) -> Instruction {
+    let mut data = sighash("global", "append").to_vec();
+    data.extend_from_slice(leaf);
+    Instruction { program_id: PROGRAM_ID, accounts: accounts(tree, authority), data }
+}
+
+fn accounts(tree: &Pubkey, authority: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*tree, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+    ]
+}
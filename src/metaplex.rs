@@ -0,0 +1,310 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+    sysvar,
+};
+
+/// The Metaplex Token Metadata program.
+pub const METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// `Key::MetadataV1`, the first byte of every metadata account.
+const METADATA_V1: u8 = 4;
+/// Instruction discriminators, the first byte of instruction data.
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+const MINT_NEW_EDITION_FROM_MASTER_EDITION_VIA_TOKEN: u8 = 11;
+/// `Burn`, followed by the `BurnArgs::V1` variant byte.
+const BURN: u8 = 41;
+/// Print editions mark which numbers are taken in markers of this many bits.
+const EDITIONS_PER_MARKER: u64 = 248;
+
+/// Longest `name`, `symbol` and `uri` the program accepts, in bytes.
+pub const MAX_NAME_LEN: usize = 32;
+pub const MAX_SYMBOL_LEN: usize = 10;
+pub const MAX_URI_LEN: usize = 200;
+
+/// The metadata account of `mint`: `["metadata", program, mint]`.
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"metadata", METADATA_PROGRAM_ID.as_ref(), mint.as_ref()], &METADATA_PROGRAM_ID).0
+}
+
+/// The mint's master edition or, for a print, its edition account:
+/// `["metadata", program, mint, "edition"]`.
+pub fn edition_address(mint: &Pubkey) -> Pubkey {
+    let seeds: &[&[u8]] = &[b"metadata", METADATA_PROGRAM_ID.as_ref(), mint.as_ref(), b"edition"];
+    Pubkey::find_program_address(seeds, &METADATA_PROGRAM_ID).0
+}
+
+/// The marker recording which prints of `master_mint` around `edition`
+/// exist: `["metadata", program, master_mint, "edition", edition / 248]`.
+pub fn edition_marker_address(master_mint: &Pubkey, edition: u64) -> Pubkey {
+    let marker = (edition / EDITIONS_PER_MARKER).to_string();
+    let seeds: &[&[u8]] =
+        &[b"metadata", METADATA_PROGRAM_ID.as_ref(), master_mint.as_ref(), b"edition", marker.as_bytes()];
+    Pubkey::find_program_address(seeds, &METADATA_PROGRAM_ID).0
+}
+
+/// The single marker programmable master editions record all their prints
+/// in: `["metadata", program, master_mint, "edition", "marker"]`.
+pub fn edition_marker_v2_address(master_mint: &Pubkey) -> Pubkey {
+    let seeds: &[&[u8]] = &[b"metadata", METADATA_PROGRAM_ID.as_ref(), master_mint.as_ref(), b"edition", b"marker"];
+    Pubkey::find_program_address(seeds, &METADATA_PROGRAM_ID).0
+}
+
+/// A programmable NFT's per-token-account state:
+/// `["metadata", program, mint, "token_record", token]`.
+pub fn token_record_address(mint: &Pubkey, token: &Pubkey) -> Pubkey {
+    let seeds: &[&[u8]] =
+        &[b"metadata", METADATA_PROGRAM_ID.as_ref(), mint.as_ref(), b"token_record", token.as_ref()];
+    Pubkey::find_program_address(seeds, &METADATA_PROGRAM_ID).0
+}
+
+/// An NFT to burn through the program rather than SPL Token, so its
+/// metadata and edition accounts are closed with it.
+pub struct NftBurn {
+    /// Owner of `token`, who signs.
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub token: Pubkey,
+    /// Metadata of the verified collection the NFT belongs to, whose size
+    /// the burn decrements.
+    pub collection_metadata: Option<Pubkey>,
+    /// Programmable NFTs also close their token record.
+    pub programmable: bool,
+    /// Set when the NFT is a print rather than a master edition.
+    pub print_of: Option<PrintSource>,
+}
+
+/// The master edition a print was made from.
+pub struct PrintSource {
+    pub master_mint: Pubkey,
+    /// A token account holding the master edition.
+    pub master_token: Pubkey,
+    /// The print's edition number.
+    pub edition: u64,
+}
+
+impl NftBurn {
+    /// `BurnV1` of the one token. Accounts that don't apply are filled with
+    /// the program's own id, as the program expects.
+    pub fn instruction(&self) -> Instruction {
+        let optional = |account: Option<Pubkey>, writable: bool| match account {
+            Some(account) if writable => AccountMeta::new(account, false),
+            Some(account) => AccountMeta::new_readonly(account, false),
+            None => AccountMeta::new_readonly(METADATA_PROGRAM_ID, false),
+        };
+        let print = self.print_of.as_ref();
+        let marker = print.map(|print| match self.programmable {
+            true => edition_marker_v2_address(&print.master_mint),
+            false => edition_marker_address(&print.master_mint, print.edition),
+        });
+        let token_record = self.programmable.then(|| token_record_address(&self.mint, &self.token));
+
+        let mut data = vec![BURN, 0];
+        data.extend_from_slice(&1u64.to_le_bytes());
+        Instruction {
+            program_id: METADATA_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.owner, true),
+                optional(self.collection_metadata, true),
+                AccountMeta::new(metadata_address(&self.mint), false),
+                AccountMeta::new(edition_address(&self.mint), false),
+                AccountMeta::new(self.mint, false),
+                AccountMeta::new(self.token, false),
+                optional(print.map(|print| edition_address(&print.master_mint)), true),
+                optional(print.map(|print| print.master_mint), false),
+                optional(print.map(|print| print.master_token), false),
+                optional(marker, true),
+                optional(token_record, true),
+                AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+                AccountMeta::new_readonly(sysvar::instructions::ID, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ],
+            data,
+        }
+    }
+}
+
+/// A print of a master edition, authorized by holding the master edition's
+/// token.
+pub struct EditionPrint {
+    pub master_mint: Pubkey,
+    /// Owner of `master_token`, who signs.
+    pub owner: Pubkey,
+    /// A token account holding the master edition.
+    pub master_token: Pubkey,
+    /// A mint with no decimals and one token already minted, which becomes
+    /// the print.
+    pub new_mint: Pubkey,
+    pub new_mint_authority: Pubkey,
+    pub payer: Pubkey,
+    pub update_authority: Pubkey,
+    /// The print's edition number, at most the master's max supply.
+    pub edition: u64,
+}
+
+impl EditionPrint {
+    /// The marker `edition` is recorded in.
+    pub fn edition_marker(&self) -> Pubkey {
+        edition_marker_address(&self.master_mint, self.edition)
+    }
+
+    /// `MintNewEditionFromMasterEditionViaToken`, creating the print's
+    /// metadata and edition accounts and marking `edition` as taken.
+    pub fn instruction(&self) -> Instruction {
+        let mut data = vec![MINT_NEW_EDITION_FROM_MASTER_EDITION_VIA_TOKEN];
+        data.extend_from_slice(&self.edition.to_le_bytes());
+        Instruction {
+            program_id: METADATA_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(metadata_address(&self.new_mint), false),
+                AccountMeta::new(edition_address(&self.new_mint), false),
+                AccountMeta::new(edition_address(&self.master_mint), false),
+                AccountMeta::new(self.new_mint, false),
+                AccountMeta::new(self.edition_marker(), false),
+                AccountMeta::new_readonly(self.new_mint_authority, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.owner, true),
+                AccountMeta::new_readonly(self.master_token, false),
+                AccountMeta::new_readonly(self.update_authority, false),
+                AccountMeta::new_readonly(metadata_address(&self.master_mint), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+            ],
+            data,
+        }
+    }
+}
+
+/// Metadata for a fungible mint: no creators, collection or uses, and no
+/// seller fee.
+pub struct FungibleMetadata<'a> {
+    pub mint: Pubkey,
+    pub mint_authority: Pubkey,
+    pub payer: Pubkey,
+    pub update_authority: Pubkey,
+    pub name: &'a str,
+    pub symbol: &'a str,
+    pub uri: &'a str,
+    pub is_mutable: bool,
+}
+
+impl FungibleMetadata<'_> {
+    /// `CreateMetadataAccountV3`, creating the mint's metadata account. The
+    /// update authority only signs when it is also the mint authority.
+    pub fn create_instruction(&self) -> Instruction {
+        let mut data = vec![CREATE_METADATA_ACCOUNT_V3];
+        for text in [self.name, self.symbol, self.uri] {
+            data.extend_from_slice(&(text.len() as u32).to_le_bytes());
+            data.extend_from_slice(text.as_bytes());
+        }
+        // Seller fee, then no creators, collection or uses.
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&[0, 0, 0]);
+        data.push(u8::from(self.is_mutable));
+        // No collection details: a fungible mint is never a collection.
+        data.push(0);
+
+        Instruction {
+            program_id: METADATA_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(metadata_address(&self.mint), false),
+                AccountMeta::new_readonly(self.mint, false),
+                AccountMeta::new_readonly(self.mint_authority, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.update_authority, self.update_authority == self.mint_authority),
+                AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+            ],
+            data,
+        }
+    }
+}
+
+/// The fixed leading fields of a metadata account. Later fields (editions,
+/// collections, uses, programmable config) are versioned and not decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+/// Decodes a metadata account, or `None` if `data` isn't one. Strings are
+/// stored padded with NULs to their maximum length; the padding is dropped.
+pub fn decode(data: &[u8]) -> Option<Metadata> {
+    let mut input = data;
+    if take::<1>(&mut input)?[0] != METADATA_V1 {
+        return None;
+    }
+    let update_authority = Pubkey::new_from_array(take(&mut input)?);
+    let mint = Pubkey::new_from_array(take(&mut input)?);
+    let name = string(&mut input)?;
+    let symbol = string(&mut input)?;
+    let uri = string(&mut input)?;
+    let seller_fee_basis_points = u16::from_le_bytes(take(&mut input)?);
+    let creators = match flag(&mut input)? {
+        false => None,
+        true => {
+            let len = u32::from_le_bytes(take(&mut input)?);
+            let mut creators = Vec::new();
+            for _ in 0..len {
+                creators.push(Creator {
+                    address: Pubkey::new_from_array(take(&mut input)?),
+                    verified: flag(&mut input)?,
+                    share: take::<1>(&mut input)?[0],
+                });
+            }
+            Some(creators)
+        }
+    };
+
+    Some(Metadata {
+        update_authority,
+        mint,
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points,
+        creators,
+        primary_sale_happened: flag(&mut input)?,
+        is_mutable: flag(&mut input)?,
+    })
+}
+
+fn take<const N: usize>(input: &mut &[u8]) -> Option<[u8; N]> {
+    let (head, rest) = input.split_first_chunk::<N>()?;
+    *input = rest;
+    Some(*head)
+}
+
+fn flag(input: &mut &[u8]) -> Option<bool> {
+    match take::<1>(input)?[0] {
+        0 => Some(false),
+        1 => Some(true),
+        _ => None,
+    }
+}
+
+fn string(input: &mut &[u8]) -> Option<String> {
+    let len = usize::try_from(u32::from_le_bytes(take(input)?)).ok()?;
+    if input.len() < len {
+        return None;
+    }
+    let (bytes, rest) = input.split_at(len);
+    *input = rest;
+    let text = std::str::from_utf8(bytes).ok()?;
+    Some(text.trim_end_matches('\0').to_string())
+}
@@ -1,100 +0,0 @@
-use serde::{Deserialize, Serialize};
-
-#[derive(Serialize)]
-pub struct KeypairResponse {
-    pub pubkey: String,
-    pub secret: String,
-}
-
-#[derive(Deserialize)]
-pub struct CreateTokenRequest {
-    #[serde(rename = "mintAuthority")]
-    pub mint_authority: String,
-    pub mint: String,
-    pub decimals: u8,
-}
-
-#[derive(Serialize)]
-pub struct InstructionResponse {
-    pub program_id: String,
-    pub accounts: Vec<AccountMeta>,
-    pub instruction_data: String,
-}
-
-#[derive(Serialize)]
-pub struct AccountMeta {
-    pub pubkey: String,
-    pub is_signer: bool,
-    pub is_writable: bool,
-}
-
-#[derive(Deserialize)]
-pub struct MintTokenRequest {
-    pub mint: String,
-    pub destination: String,
-    pub authority: String,
-    pub amount: u64,
-}
-
-#[derive(Deserialize)]
-pub struct SignMessageRequest {
-    pub message: String,
-    pub secret: String,
-}
-
-#[derive(Serialize)]
-pub struct SignMessageResponse {
-    pub signature: String,
-    pub public_key: String,
-    pub message: String,
-}
-
-#[derive(Deserialize)]
-pub struct VerifyMessageRequest {
-    pub message: String,
-    pub signature: String,
-    pub pubkey: String,
-}
-
-#[derive(Serialize)]
-pub struct VerifyMessageResponse {
-    pub valid: bool,
-    pub message: String,
-    pub pubkey: String,
-}
-
-#[derive(Deserialize)]
-pub struct SendSolRequest {
-    pub from: String,
-    pub to: String,
-    pub lamports: u64,
-}
-
-#[derive(Serialize)]
-pub struct SendSolResponse {
-    pub program_id: String,
-    pub accounts: Vec<String>,
-    pub instruction_data: String,
-}
-
-#[derive(Deserialize)]
-pub struct SendTokenRequest {
-    pub destination: String,
-    pub mint: String,
-    pub owner: String,
-    pub amount: u64,
-}
-
-#[derive(Serialize)]
-pub struct SendTokenResponse {
-    pub program_id: String,
-    pub accounts: Vec<SendTokenAccount>,
-    pub instruction_data: String,
-}
-
-#[derive(Serialize)]
-pub struct SendTokenAccount {
-    pub pubkey: String,
-    #[serde(rename = "isSigner")]
-    pub is_signer: bool,
-}
\ No newline at end of file
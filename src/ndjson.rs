@@ -0,0 +1,41 @@
+use std::convert::Infallible;
+
+use axum::{
+    body::{Body, Bytes},
+    http::{header, HeaderMap, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use futures::{Stream, StreamExt};
+use serde::Serialize;
+
+pub const CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// True when the client asked for newline-delimited JSON via `Accept`.
+pub fn accepts(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media| media.split(';').next().unwrap_or_default().trim() == CONTENT_TYPE)
+}
+
+/// Streams each item as one JSON line as soon as it is produced, so bulk
+/// responses never have to be buffered in full.
+pub fn stream<S, T>(items: S) -> Response
+where
+    S: Stream<Item = T> + Send + 'static,
+    T: Serialize,
+{
+    let body = items.map(|item| {
+        let mut line = serde_json::to_vec(&item).expect("response items serialize");
+        line.push(b'\n');
+        Ok::<_, Infallible>(Bytes::from(line))
+    });
+
+    (
+        [(header::CONTENT_TYPE, HeaderValue::from_static(CONTENT_TYPE))],
+        Body::from_stream(body),
+    )
+        .into_response()
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use moka::future::Cache;
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::config::NftConfig;
+use crate::models::nft::NftMetadataResponse;
+
+/// Fetches the off-chain half of NFT metadata and caches merged results by
+/// mint. URIs come from arbitrary on-chain accounts, so every fetch is
+/// bounded in time and size.
+pub struct NftMetadataCache {
+    client: reqwest::Client,
+    max_bytes: usize,
+    ipfs_gateway: String,
+    merged: Cache<Pubkey, Arc<NftMetadataResponse>>,
+}
+
+impl NftMetadataCache {
+    pub fn new(config: &NftConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.fetch_timeout_ms))
+            .build()
+            .expect("HTTP client builds");
+        let merged = Cache::builder()
+            .max_capacity(config.max_cached)
+            .time_to_live(Duration::from_secs(config.cache_ttl_secs))
+            .build();
+
+        Self { client, max_bytes: config.max_json_bytes, ipfs_gateway: config.ipfs_gateway.clone(), merged }
+    }
+
+    pub async fn get(&self, mint: &Pubkey) -> Option<Arc<NftMetadataResponse>> {
+        self.merged.get(mint).await
+    }
+
+    pub async fn insert(&self, mint: Pubkey, metadata: Arc<NftMetadataResponse>) {
+        self.merged.insert(mint, metadata).await;
+    }
+
+    /// The JSON document at `uri`. Errors are messages for the caller, as
+    /// an unreadable URI is the metadata's problem rather than the request's.
+    pub async fn fetch_json(&self, uri: &str) -> Result<Value, String> {
+        let url = match uri.strip_prefix("ipfs://") {
+            Some(path) => format!("{}{}", self.ipfs_gateway, path.trim_start_matches("ipfs/")),
+            None if uri.starts_with("https://") || uri.starts_with("http://") => uri.to_string(),
+            None => return Err("unsupported URI scheme".to_string()),
+        };
+
+        let mut response = self.client.get(&url).send().await.map_err(|err| err.to_string())?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("HTTP {status}"));
+        }
+        let too_large = || format!("document exceeds {} bytes", self.max_bytes);
+        if response.content_length().is_some_and(|len| len > self.max_bytes as u64) {
+            return Err(too_large());
+        }
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|err| err.to_string())? {
+            if body.len() + chunk.len() > self.max_bytes {
+                return Err(too_large());
+            }
+            body.extend_from_slice(&chunk);
+        }
+        serde_json::from_slice(&body).map_err(|err| format!("invalid JSON: {err}"))
+    }
+}
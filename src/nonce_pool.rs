@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signature, signer::Signer, transaction::VersionedTransaction};
+use uuid::Uuid;
+
+use crate::config::NoncePoolConfig;
+use crate::errors::AppError;
+use crate::keystore::Keystore;
+use crate::models::nonce_pool::{NoncePoolStatus, PooledNonce};
+use crate::tenant::{self, DEFAULT_TENANT};
+use crate::utils::parse_secret_key;
+
+/// Durable nonce accounts the service controls, each leased to at most one
+/// transaction at a time. Transactions on a durable nonce never expire, so
+/// offline signers can take their time, and separate leases never race for
+/// one blockhash. Accounts are tracked in memory; after a restart they must
+/// be added again.
+pub struct NoncePool {
+    key_id: String,
+    authority: Pubkey,
+    config: NoncePoolConfig,
+    accounts: Mutex<BTreeMap<Pubkey, Option<Lease>>>,
+}
+
+/// One account reserved for the tenant that acquired it, until released or
+/// `expires_at`.
+#[derive(Debug, Clone)]
+pub struct Lease {
+    pub id: String,
+    pub tenant: String,
+    pub account: Pubkey,
+    /// The account's nonce when leased; the transaction's blockhash.
+    pub nonce: Hash,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Lease {
+    fn is_live(&self) -> bool {
+        self.expires_at > Utc::now()
+    }
+}
+
+impl NoncePool {
+    /// Loads the configured authority into `keystore`, or `None` when the
+    /// pool is disabled.
+    pub fn load(config: &NoncePoolConfig, keystore: &Keystore) -> Option<Self> {
+        let keypair = parse_secret_key(config.authority.as_deref()?).ok()?;
+        let authority = keypair.pubkey();
+        let key_id = keystore.insert(DEFAULT_TENANT, keypair);
+        Some(Self { key_id, authority, config: config.clone(), accounts: Mutex::default() })
+    }
+
+    pub fn authority(&self) -> Pubkey {
+        self.authority
+    }
+
+    /// Starts tracking `accounts`, which must already be nonce accounts the
+    /// authority controls. Returns those not tracked before.
+    pub fn add(&self, accounts: &[Pubkey]) -> Result<Vec<Pubkey>, AppError> {
+        let mut tracked = self.accounts.lock().unwrap();
+        let added: Vec<Pubkey> = accounts.iter().filter(|account| !tracked.contains_key(account)).copied().collect();
+        if tracked.len() + added.len() > self.config.max_accounts {
+            return Err(AppError::Conflict(format!("the pool holds at most {} accounts", self.config.max_accounts)));
+        }
+        tracked.extend(added.iter().map(|account| (*account, None)));
+        Ok(added)
+    }
+
+    /// Reserves a free account for the current tenant for up to `ttl_secs`,
+    /// capped at the configured lease length. Its nonce is unknown until
+    /// `set_nonce`; a caller that can't read it must `release` the lease.
+    pub fn reserve(&self, ttl_secs: Option<u64>) -> Result<Lease, AppError> {
+        let mut accounts = self.accounts.lock().unwrap();
+        let (account, slot) = accounts
+            .iter_mut()
+            .find(|(_, lease)| !lease.as_ref().is_some_and(Lease::is_live))
+            .ok_or_else(|| AppError::Conflict("every pooled nonce account is leased".to_string()))?;
+        let ttl = ttl_secs.map_or(self.config.lease_ttl_secs, |ttl| ttl.min(self.config.lease_ttl_secs));
+        let lease = Lease {
+            id: Uuid::new_v4().to_string(),
+            tenant: tenant::current(),
+            account: *account,
+            nonce: Hash::default(),
+            expires_at: Utc::now() + TimeDelta::seconds(i64::try_from(ttl).unwrap_or(i64::MAX)),
+        };
+        *slot = Some(lease.clone());
+        Ok(lease)
+    }
+
+    pub fn set_nonce(&self, lease_id: &str, nonce: Hash) {
+        let mut accounts = self.accounts.lock().unwrap();
+        if let Some(lease) = accounts.values_mut().flatten().find(|lease| lease.id == lease_id) {
+            lease.nonce = nonce;
+        }
+    }
+
+    /// The current tenant's live lease `lease_id`.
+    pub fn lease(&self, lease_id: &str) -> Result<Lease, AppError> {
+        let accounts = self.accounts.lock().unwrap();
+        accounts
+            .values()
+            .flatten()
+            .find(|lease| lease.id == lease_id && lease.tenant == tenant::current() && lease.is_live())
+            .cloned()
+            .ok_or_else(|| AppError::NotFound(format!("Nonce lease {lease_id}")))
+    }
+
+    /// Frees the current tenant's lease `lease_id`. The next lease of the
+    /// account reads its nonce afresh, so this is safe whether or not a
+    /// transaction used it.
+    pub fn release(&self, lease_id: &str) -> Result<(), AppError> {
+        let mut accounts = self.accounts.lock().unwrap();
+        let slot = accounts
+            .values_mut()
+            .find(|lease| {
+                lease.as_ref().is_some_and(|lease| lease.id == lease_id && lease.tenant == tenant::current())
+            })
+            .ok_or_else(|| AppError::NotFound(format!("Nonce lease {lease_id}")))?;
+        *slot = None;
+        Ok(())
+    }
+
+    pub fn status(&self) -> NoncePoolStatus {
+        let accounts = self.accounts.lock().unwrap();
+        let accounts: Vec<PooledNonce> = accounts
+            .iter()
+            .map(|(address, lease)| {
+                let lease = lease.as_ref().filter(|lease| lease.is_live());
+                PooledNonce {
+                    address: address.to_string(),
+                    leased: lease.is_some(),
+                    lease_expires_at: lease.map(|lease| lease.expires_at),
+                }
+            })
+            .collect();
+        NoncePoolStatus {
+            authority: self.authority.to_string(),
+            total: accounts.len(),
+            available: accounts.iter().filter(|account| !account.leased).count(),
+            accounts,
+        }
+    }
+
+    /// Adds the authority's signature to `transaction`, which must list it
+    /// as a signer.
+    pub fn cosign(&self, keystore: &Keystore, transaction: &mut VersionedTransaction) -> Result<Signature, AppError> {
+        let keypair = keystore
+            .get(&self.key_id)
+            .ok_or_else(|| AppError::Internal("nonce authority missing from keystore".to_string()))?;
+        let signers = usize::from(transaction.message.header().num_required_signatures);
+        let index = transaction.message.static_account_keys()[..signers]
+            .iter()
+            .position(|key| *key == self.authority)
+            .ok_or_else(|| AppError::InvalidInput(format!("nonce authority {} must sign", self.authority)))?;
+        let signature = keypair.sign_message(&transaction.message.serialize());
+        transaction.signatures[index] = signature;
+        Ok(signature)
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::io;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request},
+    response::{IntoResponse, Response},
+    Router,
+};
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+use tower::ServiceExt;
+
+use crate::errors::AppError;
+use crate::extract::from_json_slice;
+
+/// One request per input line, e.g.
+/// `{"id": 1, "path": "/send/sol", "body": {...}}`.
+#[derive(Deserialize)]
+struct Command {
+    /// Echoed back so callers can match responses to commands.
+    #[serde(default)]
+    id: Option<Value>,
+    #[serde(default = "default_method")]
+    method: String,
+    path: String,
+    #[serde(default)]
+    body: Option<Value>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+}
+
+fn default_method() -> String {
+    "POST".to_string()
+}
+
+/// One response per output line, in input order.
+#[derive(Serialize)]
+struct Reply {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<Value>,
+    status: u16,
+    /// The JSON response body. Other bodies (QR images, metrics) arrive as
+    /// a string: UTF-8 text as is, anything else base64-encoded.
+    body: Value,
+}
+
+/// Serves `app` over newline-delimited JSON instead of HTTP: reads commands
+/// from `input` until it closes and writes each reply to `output` before
+/// reading the next. Requests go through the full router, so validation,
+/// errors and policy are exactly those of the HTTP API.
+pub async fn run<R, W>(app: Router, input: R, mut output: W) -> io::Result<()>
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut lines = input.lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let reply = handle(&app, line.as_bytes()).await;
+        let mut line = serde_json::to_vec(&reply).expect("replies serialize");
+        line.push(b'\n');
+        output.write_all(&line).await?;
+        output.flush().await?;
+    }
+    Ok(())
+}
+
+async fn handle(app: &Router, line: &[u8]) -> Reply {
+    let command: Command = match from_json_slice(line) {
+        Ok(command) => command,
+        Err(err) => return reply(None, err.into_response()).await,
+    };
+    let id = command.id.clone();
+    match request(command) {
+        Ok(request) => {
+            let response = app.clone().oneshot(request).await.unwrap_or_else(|never| match never {});
+            reply(id, response).await
+        }
+        Err(err) => reply(id, err.into_response()).await,
+    }
+}
+
+fn request(command: Command) -> Result<Request<Body>, AppError> {
+    let invalid = |field: &str, message: String| AppError::InvalidField { field: field.to_string(), message };
+    let method = Method::from_bytes(command.method.to_ascii_uppercase().as_bytes())
+        .map_err(|err| invalid("method", err.to_string()))?;
+    if !command.path.starts_with('/') {
+        return Err(invalid("path", "expected a path starting with `/`".to_string()));
+    }
+
+    let mut request = Request::builder().method(method).uri(&command.path);
+    for (name, value) in &command.headers {
+        request = request.header(name, value);
+    }
+    let body = match command.body {
+        Some(body) => {
+            request = request.header(header::CONTENT_TYPE, "application/json");
+            Body::from(body.to_string())
+        }
+        None => Body::empty(),
+    };
+    request.body(body).map_err(|err| invalid("headers", err.to_string()))
+}
+
+async fn reply(id: Option<Value>, response: Response) -> Reply {
+    let status = response.status().as_u16();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap_or_default();
+    let body = if bytes.is_empty() {
+        Value::Null
+    } else if let Ok(value) = serde_json::from_slice(&bytes) {
+        value
+    } else {
+        match std::str::from_utf8(&bytes) {
+            Ok(text) => Value::from(text),
+            Err(_) => Value::from(general_purpose::STANDARD.encode(&bytes)),
+        }
+    };
+    Reply { id, status, body }
+}
@@ -0,0 +1,324 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, RwLock};
+
+use chrono::{NaiveDate, Utc};
+use solana_sdk::{message::VersionedMessage, pubkey::Pubkey};
+use solana_system_interface::instruction::SystemInstruction;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::instruction::TokenInstruction;
+use thiserror::Error;
+
+use crate::config::PolicyConfig;
+use crate::solana_pay::MEMO_PROGRAM_ID;
+use crate::summary::MEMO_V1_PROGRAM_ID;
+
+/// Funds one instruction moves, as the policy sees them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transfer {
+    pub instruction_index: usize,
+    /// Signer spending the funds: the SOL sender or the token owner/delegate.
+    pub authority: Pubkey,
+    /// Recipient wallet for SOL, token account for tokens.
+    pub destination: Pubkey,
+    /// Lamports, or base units of the mint.
+    pub amount: u64,
+    pub asset: Asset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Asset {
+    Sol,
+    /// `mint` is unknown for unchecked `Transfer` and `Approve` instructions.
+    Token { program: Pubkey, mint: Option<Pubkey> },
+    /// Whatever an instruction of `program` the policy can't read may move;
+    /// its `destination` is the program and its `amount` zero.
+    Unknown { program: Pubkey },
+}
+
+impl Asset {
+    fn mint(&self) -> Option<Pubkey> {
+        match self {
+            Asset::Token { mint, .. } => *mint,
+            Asset::Sol | Asset::Unknown { .. } => None,
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Asset::Sol => "lamports".to_string(),
+            Asset::Token { mint: Some(mint), .. } => format!("base units of {mint}"),
+            Asset::Token { mint: None, .. } => "base units".to_string(),
+            Asset::Unknown { program } => format!("funds moved by {program}"),
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    #[error("Destination {0} is denied")]
+    DestinationDenied(Pubkey),
+    #[error("Destination {0} is not on the allowlist")]
+    DestinationNotAllowed(Pubkey),
+    #[error("Mint {0} is not on the allowlist")]
+    MintNotAllowed(Pubkey),
+    #[error("Instruction {0} is an unchecked token transfer; use TransferChecked so its mint can be checked")]
+    UnknownMint(usize),
+    #[error("Instruction {0} may move funds in a way the policy can't check")]
+    Unclassified(usize),
+    #[error("{key} would exceed its daily limit of {limit} {asset}")]
+    DailyLimit { key: Pubkey, asset: String, limit: u64 },
+    #[error("Moving {amount} {asset} from {key} needs approval above {threshold}")]
+    ApprovalRequired { key: Pubkey, asset: String, amount: u128, threshold: u64 },
+}
+
+impl PolicyError {
+    /// Stable identifier clients can branch on; never reword these.
+    pub fn code(&self) -> &'static str {
+        match self {
+            PolicyError::DestinationDenied(_) => "DESTINATION_DENIED",
+            PolicyError::DestinationNotAllowed(_) => "DESTINATION_NOT_ALLOWED",
+            PolicyError::MintNotAllowed(_) => "MINT_NOT_ALLOWED",
+            PolicyError::UnknownMint(_) => "UNKNOWN_MINT",
+            PolicyError::Unclassified(_) => "UNCLASSIFIED_INSTRUCTION",
+            PolicyError::DailyLimit { .. } => "DAILY_LIMIT_EXCEEDED",
+            PolicyError::ApprovalRequired { .. } => "APPROVAL_REQUIRED",
+        }
+    }
+}
+
+/// SOL and token transfers in `message`, whose full account list is `keys`.
+/// Anything else that funds or empties an account counts as a transfer too:
+/// creating or funding an account, withdrawing from a nonce account, and
+/// approving a delegate, who can then move up to the approved amount. An
+/// instruction that can't be read that way but involves a signer comes back
+/// as an `Asset::Unknown` transfer, for `review` to refuse.
+pub fn transfers(message: &VersionedMessage, keys: &[Pubkey]) -> Vec<Transfer> {
+    let mut transfers = Vec::new();
+    for (index, instruction) in message.instructions().iter().enumerate() {
+        let Some(&program) = keys.get(usize::from(instruction.program_id_index)) else {
+            continue;
+        };
+        let account = |position: usize| instruction.accounts.get(position).and_then(|&key| keys.get(usize::from(key)));
+        let transfer = |authority: Option<&Pubkey>, destination: Option<&Pubkey>, amount, asset| {
+            let (authority, destination) = (*authority?, *destination?);
+            Some(Transfer { instruction_index: index, authority, destination, amount, asset })
+        };
+        // With no signer among its accounts, an instruction can't spend
+        // anything a signer holds.
+        let unknown = || {
+            let signer = instruction.accounts.iter().find(|&&key| message.is_signer(usize::from(key)))?;
+            transfer(keys.get(usize::from(*signer)), Some(&program), 0, Asset::Unknown { program })
+        };
+
+        let found = if program == solana_system_interface::program::ID {
+            match bincode::deserialize(&instruction.data) {
+                Ok(SystemInstruction::Transfer { lamports }) => transfer(account(0), account(1), lamports, Asset::Sol),
+                Ok(SystemInstruction::TransferWithSeed { lamports, .. }) => {
+                    transfer(account(1), account(2), lamports, Asset::Sol)
+                }
+                Ok(
+                    SystemInstruction::CreateAccount { lamports, .. }
+                    | SystemInstruction::CreateAccountWithSeed { lamports, .. },
+                ) => transfer(account(0), account(1), lamports, Asset::Sol),
+                Ok(SystemInstruction::WithdrawNonceAccount(lamports)) => {
+                    transfer(account(4), account(1), lamports, Asset::Sol)
+                }
+                Ok(SystemInstruction::AdvanceNonceAccount) => continue,
+                _ => unknown(),
+            }
+        } else if program == spl_token::ID || program == spl_token_2022::ID {
+            match TokenInstruction::unpack(&instruction.data) {
+                #[allow(deprecated)]
+                Ok(TokenInstruction::Transfer { amount }) => {
+                    transfer(account(2), account(1), amount, Asset::Token { program, mint: None })
+                }
+                Ok(TokenInstruction::TransferChecked { amount, .. }) => {
+                    let mint = account(1).copied();
+                    transfer(account(3), account(2), amount, Asset::Token { program, mint })
+                }
+                Ok(TokenInstruction::Approve { amount }) => {
+                    transfer(account(2), account(1), amount, Asset::Token { program, mint: None })
+                }
+                Ok(TokenInstruction::ApproveChecked { amount, .. }) => {
+                    let mint = account(1).copied();
+                    transfer(account(3), account(2), amount, Asset::Token { program, mint })
+                }
+                // Closing into the owner's own wallet only reclaims its rent.
+                Ok(TokenInstruction::CloseAccount) if account(1).is_some() && account(1) == account(2) => continue,
+                Ok(
+                    TokenInstruction::Burn { .. }
+                    | TokenInstruction::BurnChecked { .. }
+                    | TokenInstruction::MintTo { .. }
+                    | TokenInstruction::MintToChecked { .. }
+                    | TokenInstruction::Revoke
+                    | TokenInstruction::SyncNative
+                    | TokenInstruction::InitializeAccount
+                    | TokenInstruction::InitializeAccount2 { .. }
+                    | TokenInstruction::InitializeAccount3 { .. }
+                    | TokenInstruction::InitializeImmutableOwner,
+                ) => continue,
+                _ => unknown(),
+            }
+        } else if program == spl_associated_token_account::ID {
+            // Creating an associated token account only funds its rent;
+            // recovering a nested one moves tokens.
+            match instruction.data.as_slice() {
+                [] | [0] | [1] => continue,
+                _ => unknown(),
+            }
+        } else if program == MEMO_PROGRAM_ID || program == MEMO_V1_PROGRAM_ID {
+            continue;
+        } else {
+            unknown()
+        };
+        transfers.extend(found);
+    }
+    transfers
+}
+
+/// Limits on what the service signs or sends. Daily usage is kept in memory
+/// per authority and asset, resets at UTC midnight, and counts as soon as
+/// an operation is allowed, whether or not its transaction lands.
+pub struct Policy {
+    config: RwLock<Arc<PolicyConfig>>,
+    usage: Mutex<Usage>,
+}
+
+/// Amount moved per authority and asset, and the UTC day it was moved on.
+type Usage = HashMap<(Pubkey, Asset), (NaiveDate, u128)>;
+
+impl Policy {
+    pub fn new(config: PolicyConfig) -> Self {
+        Self { config: RwLock::new(Arc::new(config)), usage: Mutex::new(HashMap::new()) }
+    }
+
+    /// Swaps in reloaded rules. Today's usage carries over.
+    pub fn set_config(&self, config: PolicyConfig) {
+        *self.config.write().unwrap() = Arc::new(config);
+    }
+
+    /// Checks the destination and mint rules, which don't depend on history.
+    pub fn review(&self, transfers: &[Transfer]) -> Result<(), PolicyError> {
+        review(&self.config(), transfers)
+    }
+
+    /// Runs every rule and, if all pass, adds `transfers` to today's usage.
+    pub fn enforce(&self, transfers: &[Transfer]) -> Result<(), PolicyError> {
+        self.check(transfers, false)
+    }
+
+    /// `enforce` for a proposal that collected its approvals: thresholds no
+    /// longer apply, but daily limits still do.
+    pub fn enforce_approved(&self, transfers: &[Transfer]) -> Result<(), PolicyError> {
+        self.check(transfers, true)
+    }
+
+    fn config(&self) -> Arc<PolicyConfig> {
+        self.config.read().unwrap().clone()
+    }
+
+    fn check(&self, transfers: &[Transfer], approved: bool) -> Result<(), PolicyError> {
+        let config = self.config();
+        review(&config, transfers)?;
+
+        let mut totals: BTreeMap<(Pubkey, Asset), u128> = BTreeMap::new();
+        for transfer in transfers {
+            *totals.entry((transfer.authority, transfer.asset)).or_default() += u128::from(transfer.amount);
+        }
+        for (&(key, asset), &amount) in &totals {
+            if let Some(threshold) = limits(&config, asset).1
+                && !approved
+                && amount > u128::from(threshold)
+            {
+                return Err(PolicyError::ApprovalRequired { key, asset: asset.name(), amount, threshold });
+            }
+        }
+
+        let today = Utc::now().date_naive();
+        let mut usage = self.usage.lock().unwrap();
+        let used = |usage: &Usage, entry: &(Pubkey, Asset)| match usage.get(entry) {
+            Some(&(day, used)) if day == today => used,
+            _ => 0,
+        };
+        for (&(key, asset), &amount) in &totals {
+            if let Some(limit) = limits(&config, asset).0
+                && used(&usage, &(key, asset)) + amount > u128::from(limit)
+            {
+                return Err(PolicyError::DailyLimit { key, asset: asset.name(), limit });
+            }
+        }
+        for (entry, amount) in totals {
+            let total = used(&usage, &entry) + amount;
+            usage.insert(entry, (today, total));
+        }
+        Ok(())
+    }
+}
+
+fn review(config: &PolicyConfig, transfers: &[Transfer]) -> Result<(), PolicyError> {
+    for transfer in transfers {
+        // Any rule at all would be void if funds could leave unread.
+        if let Asset::Unknown { .. } = transfer.asset
+            && restricts(config)
+        {
+            return Err(PolicyError::Unclassified(transfer.instruction_index));
+        }
+        let mint = transfer.asset.mint();
+        if let Asset::Token { mint: None, .. } = transfer.asset
+            && (!config.allowed_mints.is_empty() || !config.tokens.is_empty())
+        {
+            return Err(PolicyError::UnknownMint(transfer.instruction_index));
+        }
+        if let Some(mint) = mint
+            && !config.allowed_mints.is_empty()
+            && !config.allowed_mints.iter().any(|allowed| **allowed == mint)
+        {
+            return Err(PolicyError::MintNotAllowed(mint));
+        }
+
+        let destination = transfer.destination;
+        if config.denied_destinations.iter().any(|wallet| receives(wallet, transfer)) {
+            return Err(PolicyError::DestinationDenied(destination));
+        }
+        if !config.allowed_destinations.is_empty()
+            && !config.allowed_destinations.iter().any(|wallet| receives(wallet, transfer))
+        {
+            return Err(PolicyError::DestinationNotAllowed(destination));
+        }
+    }
+    Ok(())
+}
+
+/// Daily limit and approval threshold for `asset`.
+fn limits(config: &PolicyConfig, asset: Asset) -> (Option<u64>, Option<u64>) {
+    match asset {
+        Asset::Sol => (config.daily_lamport_limit, config.approval_threshold_lamports),
+        // Only reachable with no token rules configured; see `review`.
+        Asset::Token { mint: None, .. } => (None, None),
+        // Only reachable with no rules configured at all.
+        Asset::Unknown { .. } => (None, None),
+        Asset::Token { mint: Some(mint), .. } => config
+            .tokens
+            .iter()
+            .find(|token| *token.mint == mint)
+            .map_or((None, None), |token| (token.daily_limit, token.approval_threshold)),
+    }
+}
+
+/// Whether `config` sets any rule.
+fn restricts(config: &PolicyConfig) -> bool {
+    config.daily_lamport_limit.is_some()
+        || config.approval_threshold_lamports.is_some()
+        || !config.tokens.is_empty()
+        || !config.allowed_mints.is_empty()
+        || !config.allowed_destinations.is_empty()
+        || !config.denied_destinations.is_empty()
+}
+
+/// Whether `transfer` pays `wallet`, directly or through its associated
+/// token account for the transferred mint.
+fn receives(wallet: &Pubkey, transfer: &Transfer) -> bool {
+    transfer.destination == *wallet
+        || matches!(transfer.asset, Asset::Token { program, mint: Some(mint) }
+            if get_associated_token_address_with_program_id(wallet, &mint, &program) == transfer.destination)
+}
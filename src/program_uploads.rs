@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{TimeDelta, Utc};
+use solana_sdk::pubkey::Pubkey;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::program::ProgramUpload;
+use crate::tenant;
+
+/// How long an upload is kept after it's made.
+pub const UPLOAD_TTL_SECS: i64 = 60 * 60;
+/// Uploads a tenant may hold at once; binaries are up to 10 MiB each.
+pub const MAX_UPLOADS: usize = 4;
+
+/// Program binaries uploaded through `/program/uploads`, visible to their
+/// tenant only, so write transactions can be re-packed with a fresh
+/// blockhash when earlier ones expire before they land.
+#[derive(Default)]
+pub struct ProgramUploads {
+    uploads: Mutex<HashMap<String, Entry>>,
+}
+
+/// What an upload's transactions are re-packed from.
+pub struct StoredProgram {
+    pub payer: Pubkey,
+    pub buffer: Pubkey,
+    pub authority: Pubkey,
+    pub program: Vec<u8>,
+}
+
+struct Entry {
+    tenant: String,
+    upload: ProgramUpload,
+    stored: Arc<StoredProgram>,
+}
+
+impl ProgramUploads {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores `stored` under a new id, which `upload` gets along with its
+    /// timestamps.
+    pub fn insert(&self, mut upload: ProgramUpload, stored: StoredProgram) -> Result<ProgramUpload, AppError> {
+        let tenant = tenant::current();
+        let mut uploads = self.uploads.lock().unwrap();
+        let now = Utc::now();
+        uploads.retain(|_, entry| entry.upload.expires_at > now);
+        if uploads.values().filter(|entry| entry.tenant == tenant).count() >= MAX_UPLOADS {
+            return Err(AppError::Conflict(format!(
+                "At most {MAX_UPLOADS} program uploads are kept at once; delete one first"
+            )));
+        }
+
+        upload.id = Uuid::new_v4().to_string();
+        upload.created_at = now;
+        upload.expires_at = now + TimeDelta::seconds(UPLOAD_TTL_SECS);
+        let entry = Entry { tenant, upload: upload.clone(), stored: Arc::new(stored) };
+        uploads.insert(upload.id.clone(), entry);
+        Ok(upload)
+    }
+
+    pub fn get(&self, id: &str) -> Result<(ProgramUpload, Arc<StoredProgram>), AppError> {
+        let uploads = self.uploads.lock().unwrap();
+        uploads
+            .get(id)
+            .filter(|entry| entry.tenant == tenant::current() && entry.upload.expires_at > Utc::now())
+            .map(|entry| (entry.upload.clone(), entry.stored.clone()))
+            .ok_or_else(|| AppError::NotFound(format!("Program upload {id}")))
+    }
+
+    pub fn remove(&self, id: &str) -> Result<ProgramUpload, AppError> {
+        let mut uploads = self.uploads.lock().unwrap();
+        match uploads.get(id) {
+            Some(entry) if entry.tenant == tenant::current() => Ok(uploads.remove(id).unwrap().upload),
+            _ => Err(AppError::NotFound(format!("Program upload {id}"))),
+        }
+    }
+}
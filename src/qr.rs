@@ -0,0 +1,52 @@
+use std::io::Cursor;
+
+use axum::{
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use image::{ImageFormat, Luma};
+use qrcode::{render::svg, EcLevel, QrCode};
+use crate::errors::{AppError, FieldError};
+
+pub use superdev_models::qr::{Correction, ImageKind, QrOptions};
+
+pub const MIN_SIZE: u32 = 64;
+pub const MAX_SIZE: u32 = 2048;
+const DEFAULT_SIZE: u32 = 256;
+
+fn ec_level(level: Correction) -> EcLevel {
+    match level {
+        Correction::L => EcLevel::L,
+        Correction::M => EcLevel::M,
+        Correction::Q => EcLevel::Q,
+        Correction::H => EcLevel::H,
+    }
+}
+
+/// Renders `data` as a PNG or SVG image response.
+pub fn render(data: &str, options: QrOptions) -> Result<Response, AppError> {
+    let size = options.size.unwrap_or(DEFAULT_SIZE);
+    if !(MIN_SIZE..=MAX_SIZE).contains(&size) {
+        return Err(AppError::Field { field: "size".to_string(), error: FieldError::QrSize(MIN_SIZE, MAX_SIZE) });
+    }
+
+    let code = QrCode::with_error_correction_level(data, ec_level(options.ec))
+        .map_err(|_| AppError::Field { field: "data".to_string(), error: FieldError::QrDataTooLong })?;
+
+    let (content_type, body) = match options.format {
+        ImageKind::Svg => {
+            let svg = code.render::<svg::Color>().min_dimensions(size, size).build();
+            ("image/svg+xml", svg.into_bytes())
+        }
+        ImageKind::Png => {
+            let image = code.render::<Luma<u8>>().min_dimensions(size, size).build();
+            let mut png = Vec::new();
+            image
+                .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+            ("image/png", png)
+        }
+    };
+
+    Ok(([(header::CONTENT_TYPE, HeaderValue::from_static(content_type))], body).into_response())
+}
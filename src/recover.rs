@@ -0,0 +1,65 @@
+use std::any::Any;
+use std::panic::AssertUnwindSafe;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use futures::FutureExt;
+use serde_json::json;
+
+use crate::correlation;
+use crate::errors::AppError;
+use crate::extract::Json;
+use crate::request_id::RequestId;
+use crate::state::AppState;
+
+/// Marks a response `catch_panic` made up, with the panic's message.
+#[derive(Debug, Clone)]
+pub struct Panicked(pub String);
+
+/// Middleware that answers a panicking handler with the standard `INTERNAL`
+/// error envelope, plus the request and correlation ids, instead of dropping
+/// the connection. Each panic is counted in `http_panics_total` and logged.
+pub async fn catch_panic(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| "unmatched".to_string(), |path| path.as_str().to_string());
+    let request_id = request.extensions().get::<RequestId>().map(|RequestId(id)| id.clone());
+
+    let panic = match AssertUnwindSafe(next.run(request)).catch_unwind().await {
+        Ok(response) => return response,
+        Err(panic) => panic,
+    };
+    let message = panic_message(&*panic);
+    state.metrics.panicked(&method, &route);
+    let correlation_id = correlation::current();
+    eprintln!(
+        "panic in {method} {route} (request {}, correlation {}): {message}",
+        request_id.as_deref().unwrap_or("-"),
+        correlation_id.as_deref().unwrap_or("-"),
+    );
+
+    let error = AppError::Internal("request handler panicked".to_string());
+    let mut body = json!({ "success": false, "error": error.to_string(), "code": error.code() });
+    if let Some(id) = request_id {
+        body["request_id"] = id.into();
+    }
+    if let Some(id) = correlation_id {
+        body["correlation_id"] = id.into();
+    }
+    let mut response = (error.status(), Json(body)).into_response();
+    response.extensions_mut().insert(Panicked(message));
+    response
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    match (panic.downcast_ref::<&str>(), panic.downcast_ref::<String>()) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => "panicked".to_string(),
+    }
+}
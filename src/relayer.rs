@@ -0,0 +1,122 @@
+use solana_sdk::{
+    instruction::CompiledInstruction,
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::Signer,
+    transaction::VersionedTransaction,
+};
+use spl_token_2022::instruction::TokenInstruction;
+
+use crate::config::{RelayerConfig, RelayerFee};
+use crate::errors::AppError;
+use crate::keystore::Keystore;
+use crate::tenant::DEFAULT_TENANT;
+use crate::utils::parse_secret_key;
+
+/// Fee payer the service lends to user transactions. The keypair lives in
+/// the keystore; this only remembers which entry and the policy to enforce.
+pub struct Relayer {
+    key_id: String,
+    fee_payer: Pubkey,
+    config: RelayerConfig,
+}
+
+impl Relayer {
+    /// Loads the configured fee payer into `keystore`, or `None` when the
+    /// relayer is disabled.
+    pub fn load(config: &RelayerConfig, keystore: &Keystore) -> Option<Self> {
+        let keypair = parse_secret_key(config.secret.as_deref()?).ok()?;
+        let fee_payer = keypair.pubkey();
+        let key_id = keystore.insert(DEFAULT_TENANT, keypair);
+        Some(Self { key_id, fee_payer, config: config.clone() })
+    }
+
+    pub fn fee_payer(&self) -> Pubkey {
+        self.fee_payer
+    }
+
+    pub fn config(&self) -> &RelayerConfig {
+        &self.config
+    }
+
+    /// Checks `transaction` against the relay policy. `keys` is its full
+    /// account list; `fee_account` is where the token fee must land, when
+    /// one is charged.
+    pub fn check(
+        &self,
+        transaction: &VersionedTransaction,
+        keys: &[Pubkey],
+        fee_account: Option<Pubkey>,
+    ) -> Result<(), AppError> {
+        let violation = |message: String| Err(AppError::PolicyViolation(message));
+        let message = &transaction.message;
+        let header = message.header();
+
+        if keys.first() != Some(&self.fee_payer) {
+            return violation(format!("fee payer must be the relayer {}", self.fee_payer));
+        }
+        if header.num_required_signatures > self.config.max_signatures {
+            return violation(format!("at most {} signatures may be required", self.config.max_signatures));
+        }
+
+        let mut fee_paid: u128 = 0;
+        for (index, instruction) in message.instructions().iter().enumerate() {
+            let program = keys[usize::from(instruction.program_id_index)];
+            // The relayer only pays fees: it can't authorize or fund anything.
+            if instruction.accounts.iter().any(|&account| keys[usize::from(account)] == self.fee_payer) {
+                return violation(format!("instruction {index} uses the relayer's account"));
+            }
+            let allowed = &self.config.allowed_programs;
+            if !allowed.is_empty() && !allowed.iter().any(|allowed| **allowed == program) {
+                return violation(format!("instruction {index} invokes disallowed program {program}"));
+            }
+            if let (Some(fee), Some(destination)) = (&self.config.fee, fee_account)
+                && (program == spl_token::ID || program == spl_token_2022::ID)
+            {
+                fee_paid += fee_transfer(instruction, keys, fee, destination);
+            }
+        }
+
+        if let Some(fee) = &self.config.fee
+            && fee_paid < u128::from(fee.amount)
+        {
+            return violation(format!("transaction must pay a fee of {} base units of {}", fee.amount, fee.mint));
+        }
+
+        let data = message.serialize();
+        for (index, signature) in transaction.signatures.iter().enumerate().skip(1) {
+            if !signature.verify(keys[index].as_ref(), &data) {
+                return violation(format!("missing or invalid signature for {}", keys[index]));
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the fee payer's signature. Call only after `check` passed.
+    pub fn cosign(&self, keystore: &Keystore, transaction: &mut VersionedTransaction) -> Result<Signature, AppError> {
+        let keypair = keystore
+            .get(&self.key_id)
+            .ok_or_else(|| AppError::Internal("relayer key missing from keystore".to_string()))?;
+        let signature = keypair.sign_message(&transaction.message.serialize());
+        transaction.signatures[0] = signature;
+        Ok(signature)
+    }
+}
+
+/// Amount a token instruction moves into `destination` in the fee mint.
+/// Unchecked transfers carry no mint, but the fee account only holds one.
+fn fee_transfer(instruction: &CompiledInstruction, keys: &[Pubkey], fee: &RelayerFee, destination: Pubkey) -> u128 {
+    let account = |position: usize| instruction.accounts.get(position).map(|&key| keys[usize::from(key)]);
+    let (amount, to) = match TokenInstruction::unpack(&instruction.data) {
+        #[allow(deprecated)]
+        Ok(TokenInstruction::Transfer { amount }) => (amount, account(1)),
+        Ok(TokenInstruction::TransferChecked { amount, .. }) => {
+            if account(1) != Some(*fee.mint) {
+                return 0;
+            }
+            (amount, account(2))
+        }
+        _ => return 0,
+    };
+    if to == Some(destination) { u128::from(amount) } else { 0 }
+}
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::Response,
+};
+use chrono::Utc;
+use serde_json::Value;
+
+use crate::audit;
+use crate::correlation;
+use crate::recover::Panicked;
+use crate::request_id::RequestId;
+use crate::state::AppState;
+use crate::tenant;
+
+pub use superdev_models::reporting::ErrorReport;
+
+/// Largest error body read for its code and message.
+const ERROR_BODY_LIMIT: usize = 64 * 1024;
+
+/// Delivers `ErrorReport`s to the configured webhook.
+#[derive(Default)]
+pub struct ErrorReporter {
+    client: reqwest::Client,
+}
+
+impl ErrorReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends `report` in the background. A failed delivery is only printed,
+    /// without the URL, which holds the webhook's token.
+    pub fn send(&self, url: &str, timeout: Duration, report: ErrorReport) {
+        let body = serde_json::to_vec(&report).expect("reports serialize");
+        let request = self
+            .client
+            .post(url)
+            .timeout(timeout)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(body);
+        tokio::spawn(async move {
+            if let Err(err) = request.send().await.and_then(reqwest::Response::error_for_status) {
+                let correlation = report.correlation_id.as_deref().unwrap_or("-");
+                let err = err.without_url();
+                eprintln!("error report {} (correlation {correlation}) not delivered: {err}", report.request_id);
+            }
+        });
+    }
+}
+
+/// Middleware that reports 5xx responses, panics caught by
+/// `recover::catch_panic` included, to `error_reporting.webhook_url`.
+pub async fn report(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let request_id = request.extensions().get::<RequestId>().map(|RequestId(id)| id.clone());
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+    let response = next.run(request).await;
+
+    let config = state.config();
+    let reporting = &config.error_reporting;
+    let Some(url) = reporting.webhook_url.as_ref().filter(|_| response.status().is_server_error()) else {
+        return response;
+    };
+    let (response, code, message) = match response.extensions().get::<Panicked>() {
+        Some(Panicked(message)) => {
+            let message = message.clone();
+            (response, Some("PANIC".to_string()), Some(message))
+        }
+        None => error_of(response).await,
+    };
+    let (identity, body) = audit::captured();
+    let report = ErrorReport {
+        time: Utc::now(),
+        request_id: request_id.unwrap_or_default(),
+        correlation_id: correlation::current(),
+        environment: reporting.environment.clone(),
+        method,
+        path,
+        status: response.status().as_u16(),
+        code,
+        message,
+        tenant: tenant::current(),
+        identity,
+        request: body,
+    };
+    state.reporter.send(url, Duration::from_millis(reporting.timeout_ms), report);
+    response
+}
+
+/// `code` and `error` of a JSON error body, read without losing it. Bodies
+/// of unknown or excessive length are left unread.
+async fn error_of(response: Response) -> (Response, Option<String>, Option<String>) {
+    let small = response.body().size_hint().upper().is_some_and(|length| length <= ERROR_BODY_LIMIT as u64);
+    if !small {
+        return (response, None, None);
+    }
+    let (parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, ERROR_BODY_LIMIT).await else {
+        return (Response::from_parts(parts, Body::empty()), None, None);
+    };
+    let error = serde_json::from_slice::<Value>(&bytes).unwrap_or_default();
+    let field = |name: &str| error[name].as_str().map(str::to_string);
+    let (code, message) = (field("code"), field("error"));
+    (Response::from_parts(parts, Body::from(bytes)), code, message)
+}
@@ -0,0 +1,40 @@
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+pub const REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest caller-supplied id that's kept.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Id of the request being handled, in its extensions.
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+/// Middleware that gives each request an id, available to inner layers as a
+/// `RequestId` extension and returned in `X-Request-Id`. A caller's own id
+/// is kept when it looks like one, so it lines up with the caller's logs.
+pub async fn assign(mut request: Request, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(&REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| plausible(id))
+        .map_or_else(|| Uuid::new_v4().to_string(), str::to_string);
+    request.extensions_mut().insert(RequestId(id.clone()));
+
+    let mut response = next.run(request).await;
+    let id = HeaderValue::from_str(&id).expect("request ids are visible ASCII");
+    response.headers_mut().insert(REQUEST_ID, id);
+    response
+}
+
+/// Whether a caller-supplied id is safe to echo and log.
+pub(crate) fn plausible(id: &str) -> bool {
+    (1..=MAX_REQUEST_ID_LEN).contains(&id.len())
+        && id.bytes().all(|byte| byte.is_ascii_alphanumeric() || b"-_.:".contains(&byte))
+}
@@ -0,0 +1,221 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AppError;
+use crate::state::AppState;
+
+/// Endpoints sharing a first path segment, switched on or off together
+/// through the `routes` config table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RouteGroup {
+    Account,
+    AddressBook,
+    Admin,
+    Airdrop,
+    Alt,
+    Anchor,
+    Assets,
+    Audit,
+    Block,
+    Borsh,
+    Cluster,
+    Compose,
+    Convert,
+    Debug,
+    Decode,
+    Derive,
+    Health,
+    Jobs,
+    Keys,
+    Keypair,
+    MerkleTree,
+    Message,
+    Metrics,
+    Nft,
+    NoncePool,
+    Program,
+    Qr,
+    Relayer,
+    Schemas,
+    Send,
+    SolanaPay,
+    Squads,
+    Stake,
+    Templates,
+    Token,
+    Transaction,
+    Transfers,
+    Vote,
+}
+
+impl RouteGroup {
+    pub const ALL: [RouteGroup; 38] = [
+        RouteGroup::Account,
+        RouteGroup::AddressBook,
+        RouteGroup::Admin,
+        RouteGroup::Airdrop,
+        RouteGroup::Alt,
+        RouteGroup::Anchor,
+        RouteGroup::Assets,
+        RouteGroup::Audit,
+        RouteGroup::Block,
+        RouteGroup::Borsh,
+        RouteGroup::Cluster,
+        RouteGroup::Compose,
+        RouteGroup::Convert,
+        RouteGroup::Debug,
+        RouteGroup::Decode,
+        RouteGroup::Derive,
+        RouteGroup::Health,
+        RouteGroup::Jobs,
+        RouteGroup::Keys,
+        RouteGroup::Keypair,
+        RouteGroup::MerkleTree,
+        RouteGroup::Message,
+        RouteGroup::Metrics,
+        RouteGroup::Nft,
+        RouteGroup::NoncePool,
+        RouteGroup::Program,
+        RouteGroup::Qr,
+        RouteGroup::Relayer,
+        RouteGroup::Schemas,
+        RouteGroup::Send,
+        RouteGroup::SolanaPay,
+        RouteGroup::Squads,
+        RouteGroup::Stake,
+        RouteGroup::Templates,
+        RouteGroup::Token,
+        RouteGroup::Transaction,
+        RouteGroup::Transfers,
+        RouteGroup::Vote,
+    ];
+
+    /// The path segment the group's routes start with.
+    pub fn name(self) -> &'static str {
+        match self {
+            RouteGroup::Account => "account",
+            RouteGroup::AddressBook => "address-book",
+            RouteGroup::Admin => "admin",
+            RouteGroup::Airdrop => "airdrop",
+            RouteGroup::Alt => "alt",
+            RouteGroup::Anchor => "anchor",
+            RouteGroup::Assets => "assets",
+            RouteGroup::Audit => "audit",
+            RouteGroup::Block => "block",
+            RouteGroup::Borsh => "borsh",
+            RouteGroup::Cluster => "cluster",
+            RouteGroup::Compose => "compose",
+            RouteGroup::Convert => "convert",
+            RouteGroup::Debug => "debug",
+            RouteGroup::Decode => "decode",
+            RouteGroup::Derive => "derive",
+            RouteGroup::Health => "health",
+            RouteGroup::Jobs => "jobs",
+            RouteGroup::Keys => "keys",
+            RouteGroup::Keypair => "keypair",
+            RouteGroup::MerkleTree => "merkle-tree",
+            RouteGroup::Message => "message",
+            RouteGroup::Metrics => "metrics",
+            RouteGroup::Nft => "nft",
+            RouteGroup::NoncePool => "nonce-pool",
+            RouteGroup::Program => "program",
+            RouteGroup::Qr => "qr",
+            RouteGroup::Relayer => "relayer",
+            RouteGroup::Schemas => "schemas",
+            RouteGroup::Send => "send",
+            RouteGroup::SolanaPay => "solana-pay",
+            RouteGroup::Squads => "squads",
+            RouteGroup::Stake => "stake",
+            RouteGroup::Templates => "templates",
+            RouteGroup::Token => "token",
+            RouteGroup::Transaction => "transaction",
+            RouteGroup::Transfers => "transfers",
+            RouteGroup::Vote => "vote",
+        }
+    }
+
+    /// Group serving `path`, if any. Routes kept under `/v1` for old
+    /// clients belong to the same group as their current path.
+    pub fn of(path: &str) -> Option<Self> {
+        let path = path.trim_start_matches('/');
+        let segment = path.strip_prefix("v1/").unwrap_or(path).split('/').next()?;
+        Self::ALL.into_iter().find(|group| group.name() == segment)
+    }
+}
+
+/// How a route group answers. Groups the config doesn't mention are enabled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Availability {
+    #[default]
+    Enabled,
+    /// Refused with 403 `FEATURE_DISABLED`, so clients learn why.
+    Forbidden,
+    /// Answered with the 404 of an unknown path, as if the routes didn't
+    /// exist.
+    Hidden,
+}
+
+/// Route groups served by the listener a request arrived on, inserted by
+/// `listener::restrict`. The rest are hidden there, whatever their
+/// availability.
+#[derive(Debug, Clone)]
+pub struct Served(pub Arc<[RouteGroup]>);
+
+/// Middleware applying the configured availability of each route group.
+/// Read per request, so a reload takes effect immediately.
+pub async fn gate(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let Some(group) = RouteGroup::of(request.uri().path()) else {
+        return next.run(request).await;
+    };
+    if request.extensions().get::<Served>().is_some_and(|Served(groups)| !groups.contains(&group)) {
+        return unknown_route(request.uri().path()).into_response();
+    }
+    match state.config().routes.get(&group).copied().unwrap_or_default() {
+        Availability::Enabled => next.run(request).await,
+        Availability::Forbidden => AppError::FeatureDisabled(group.name()).into_response(),
+        Availability::Hidden => unknown_route(request.uri().path()).into_response(),
+    }
+}
+
+/// Fallback for paths no route matches.
+pub async fn not_found(uri: Uri) -> AppError {
+    unknown_route(uri.path())
+}
+
+/// Middleware giving axum's bare 405s the standard error envelope, with the
+/// methods the path does accept. Wraps the whole router, as axum only adds
+/// `Allow` after route layers have run.
+pub async fn method_not_allowed(request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let response = next.run(request).await;
+    if response.status() != StatusCode::METHOD_NOT_ALLOWED {
+        return response;
+    }
+    let allowed = response
+        .headers()
+        .get(header::ALLOW)
+        .and_then(|value| value.to_str().ok())
+        .map(|allow| allow.split(',').map(str::trim).filter(|method| !method.is_empty()).map(str::to_string).collect())
+        .unwrap_or_default();
+    // Keeps what outer layers added, such as `X-Request-Id`.
+    let mut envelope = AppError::MethodNotAllowed { method, allowed }.into_response();
+    for (name, value) in response.headers() {
+        if name != header::CONTENT_LENGTH {
+            envelope.headers_mut().entry(name).or_insert_with(|| value.clone());
+        }
+    }
+    envelope
+}
+
+fn unknown_route(path: &str) -> AppError {
+    AppError::NotFound(format!("Route {path}"))
+}
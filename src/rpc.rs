@@ -0,0 +1,248 @@
+pub mod client;
+pub mod mock;
+
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use solana_sdk::{
+    account::Account,
+    epoch_info::EpochInfo,
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+use thiserror::Error;
+
+use crate::config::RpcConfig;
+
+pub use client::ClusterRpc;
+pub use mock::MockRpc;
+
+#[derive(Error, Debug, Clone)]
+#[error("RPC request failed: {0}")]
+pub struct RpcError(pub String);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatestBlockhash {
+    pub blockhash: Hash,
+    pub last_valid_block_height: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Simulation {
+    pub err: Option<String>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    /// Post-simulation state of the addresses asked for, in request order;
+    /// `None` for accounts that don't exist afterwards.
+    pub accounts: Vec<Option<Account>>,
+}
+
+/// A transaction as confirmed on chain, with the status metadata needed to
+/// account for what it moved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfirmedTransaction {
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub transaction: VersionedTransaction,
+    pub meta: TransactionMeta,
+}
+
+/// A confirmed block with its transactions, each carrying the block's slot
+/// and time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfirmedBlock {
+    pub slot: u64,
+    pub blockhash: Hash,
+    pub parent_slot: u64,
+    pub block_time: Option<i64>,
+    pub block_height: Option<u64>,
+    pub transactions: Vec<ConfirmedTransaction>,
+}
+
+/// Balances are indexed like the message's account keys followed by any
+/// addresses loaded from lookup tables, writable before readonly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransactionMeta {
+    pub err: Option<String>,
+    pub fee: u64,
+    pub pre_balances: Vec<u64>,
+    pub post_balances: Vec<u64>,
+    pub pre_token_balances: Vec<TokenBalance>,
+    pub post_token_balances: Vec<TokenBalance>,
+    pub loaded_writable: Vec<Pubkey>,
+    pub loaded_readonly: Vec<Pubkey>,
+    pub logs: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenBalance {
+    pub account_index: usize,
+    pub mint: Pubkey,
+    pub owner: Option<Pubkey>,
+    pub amount: u64,
+    pub decimals: u8,
+}
+
+/// Software the node runs, from `getVersion`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClusterVersion {
+    pub solana_core: String,
+    pub feature_set: Option<u32>,
+}
+
+/// A vote account from `getVoteAccounts`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoteAccount {
+    pub vote_pubkey: Pubkey,
+    pub node_pubkey: Pubkey,
+    /// Lamports delegated to the account and active this epoch.
+    pub activated_stake: u64,
+    pub commission: u8,
+    pub last_vote: u64,
+    pub root_slot: u64,
+    /// Listed by the node as delinquent, i.e. not voting recently.
+    pub delinquent: bool,
+}
+
+/// An account's inflation reward for one epoch, from `getInflationReward`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InflationReward {
+    pub epoch: u64,
+    /// Slot the reward was credited in.
+    pub effective_slot: u64,
+    pub amount: u64,
+    pub post_balance: u64,
+    /// Vote account commission when the reward was credited, for stake
+    /// accounts.
+    pub commission: Option<u8>,
+}
+
+/// A `getProgramAccounts` filter; accounts must pass all of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountFilter {
+    DataSize(u64),
+    /// Data holds `bytes` starting at `offset`.
+    Memcmp { offset: usize, bytes: Vec<u8> },
+}
+
+impl AccountFilter {
+    pub fn matches(&self, data: &[u8]) -> bool {
+        match self {
+            AccountFilter::DataSize(size) => data.len() as u64 == *size,
+            AccountFilter::Memcmp { offset, bytes } => {
+                data.get(*offset..).is_some_and(|data| data.starts_with(bytes))
+            }
+        }
+    }
+}
+
+/// The subset of JSON-RPC the service depends on. Handlers only ever see this
+/// trait so they can run against a live cluster or `MockRpc` unchanged.
+#[async_trait]
+pub trait SolanaRpc: Send + Sync {
+    async fn get_latest_blockhash(&self) -> Result<LatestBlockhash, RpcError>;
+
+    async fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>, RpcError>;
+
+    /// Token accounts `owner` holds under `program_id`, spl-token or
+    /// token-2022, with their addresses.
+    async fn get_token_accounts_by_owner(
+        &self,
+        owner: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<Vec<(Pubkey, Account)>, RpcError>;
+
+    /// Owner and amount of every token account of `mint`, which
+    /// `program_id` owns. Only those bytes of each account are fetched, so
+    /// even mints with many holders come back small.
+    async fn get_token_balances_by_mint(
+        &self,
+        mint: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<Vec<(Pubkey, u64)>, RpcError>;
+
+    /// Accounts `program_id` owns that pass every filter, with their
+    /// addresses.
+    async fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: &[AccountFilter],
+    ) -> Result<Vec<(Pubkey, Account)>, RpcError>;
+
+    async fn simulate_transaction(&self, transaction: &VersionedTransaction) -> Result<Simulation, RpcError>;
+
+    /// Submits a fully signed transaction and returns its signature.
+    async fn send_transaction(&self, transaction: &VersionedTransaction) -> Result<Signature, RpcError>;
+
+    /// Like `simulate_transaction`, also returning the resulting state of
+    /// `addresses`.
+    async fn simulate_with_accounts(
+        &self,
+        transaction: &VersionedTransaction,
+        addresses: &[Pubkey],
+    ) -> Result<Simulation, RpcError>;
+
+    /// Log messages of a confirmed transaction, or `None` if the node doesn't
+    /// know the signature.
+    async fn get_transaction_logs(&self, signature: &Signature) -> Result<Option<Vec<String>>, RpcError>;
+
+    /// A confirmed transaction with its metadata, or `None` if the node
+    /// doesn't know the signature.
+    async fn get_transaction(&self, signature: &Signature) -> Result<Option<ConfirmedTransaction>, RpcError>;
+
+    /// `None` for skipped slots and blocks the node no longer stores.
+    async fn get_block(&self, slot: u64) -> Result<Option<ConfirmedBlock>, RpcError>;
+
+    async fn get_epoch_info(&self) -> Result<EpochInfo, RpcError>;
+
+    async fn get_slot(&self) -> Result<u64, RpcError>;
+
+    async fn get_block_height(&self) -> Result<u64, RpcError>;
+
+    /// Whether transactions using `blockhash` can still land.
+    async fn is_blockhash_valid(&self, blockhash: &Hash) -> Result<bool, RpcError>;
+
+    async fn get_version(&self) -> Result<ClusterVersion, RpcError>;
+
+    /// Current and delinquent vote accounts together.
+    async fn get_vote_accounts(&self) -> Result<Vec<VoteAccount>, RpcError>;
+
+    /// `None` when `address` earned nothing in `epoch`.
+    async fn get_inflation_reward(&self, address: &Pubkey, epoch: u64) -> Result<Option<InflationReward>, RpcError>;
+}
+
+/// Builds the configured backend, or `None` when no RPC is configured and
+/// only the offline builders are available.
+pub fn connect(config: &RpcConfig) -> Option<Arc<dyn SolanaRpc>> {
+    if config.mock {
+        return Some(Arc::new(MockRpc::new()));
+    }
+
+    config
+        .url
+        .as_ref()
+        .map(|url| Arc::new(ClusterRpc::new(url.clone())) as Arc<dyn SolanaRpc>)
+}
+
+/// Shared, swappable reference to the active backend. Cloning shares the slot,
+/// so replacing the backend (e.g. when dev tools start a local validator) is
+/// seen by every holder.
+#[derive(Clone, Default)]
+pub struct RpcHandle(Arc<RwLock<Option<Arc<dyn SolanaRpc>>>>);
+
+impl RpcHandle {
+    pub fn new(rpc: Option<Arc<dyn SolanaRpc>>) -> Self {
+        Self(Arc::new(RwLock::new(rpc)))
+    }
+
+    pub fn get(&self) -> Option<Arc<dyn SolanaRpc>> {
+        self.0.read().unwrap().clone()
+    }
+
+    /// Installs `rpc` and returns the backend it replaced.
+    pub fn replace(&self, rpc: Option<Arc<dyn SolanaRpc>>) -> Option<Arc<dyn SolanaRpc>> {
+        std::mem::replace(&mut *self.0.write().unwrap(), rpc)
+    }
+}
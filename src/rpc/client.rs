@@ -0,0 +1,531 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::RpcSimulateTransactionConfig,
+    rpc_request::RpcRequest,
+};
+use solana_sdk::{
+    account::Account,
+    epoch_info::EpochInfo,
+    hash::Hash,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+
+use super::{
+    AccountFilter, ClusterVersion, ConfirmedBlock, ConfirmedTransaction, InflationReward, LatestBlockhash, RpcError,
+    Simulation, SolanaRpc, TokenBalance, TransactionMeta, VoteAccount,
+};
+use crate::correlation;
+
+/// `SolanaRpc` backed by a real cluster through the nonblocking `RpcClient`.
+pub struct ClusterRpc {
+    client: RpcClient,
+}
+
+impl ClusterRpc {
+    pub fn new(url: String) -> Self {
+        Self { client: correlation::rpc_client(url) }
+    }
+
+    fn transaction_params(&self, signature: &Signature, encoding: &str) -> Value {
+        json!([
+            signature.to_string(),
+            {
+                "encoding": encoding,
+                "commitment": self.client.commitment().commitment,
+                "maxSupportedTransactionVersion": 0,
+            }
+        ])
+    }
+}
+
+fn rpc_error(err: solana_client::client_error::ClientError) -> RpcError {
+    RpcError(err.to_string())
+}
+
+/// `getTransaction` with base64 encoding, read loosely rather than through
+/// the transaction-status crate's typed response.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawConfirmedTransaction {
+    slot: u64,
+    block_time: Option<i64>,
+    transaction: (String, String),
+    meta: Option<RawMeta>,
+}
+
+/// `getBlock` with full base64 transactions and no rewards.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawBlock {
+    blockhash: String,
+    parent_slot: u64,
+    block_time: Option<i64>,
+    block_height: Option<u64>,
+    #[serde(default)]
+    transactions: Vec<RawBlockTransaction>,
+}
+
+#[derive(Deserialize)]
+struct RawBlockTransaction {
+    transaction: (String, String),
+    meta: Option<RawMeta>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct RawMeta {
+    err: Option<Value>,
+    fee: u64,
+    pre_balances: Vec<u64>,
+    post_balances: Vec<u64>,
+    pre_token_balances: Vec<RawTokenBalance>,
+    post_token_balances: Vec<RawTokenBalance>,
+    loaded_addresses: RawLoadedAddresses,
+    log_messages: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RawLoadedAddresses {
+    writable: Vec<String>,
+    readonly: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTokenBalance {
+    account_index: usize,
+    mint: String,
+    owner: Option<String>,
+    ui_token_amount: RawTokenAmount,
+}
+
+#[derive(Deserialize)]
+struct RawTokenAmount {
+    amount: String,
+    decimals: u8,
+}
+
+#[derive(Deserialize)]
+struct RawResponse<T> {
+    value: T,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSimulation {
+    err: Option<Value>,
+    logs: Option<Vec<String>>,
+    units_consumed: Option<u64>,
+    accounts: Option<Vec<Option<RawAccount>>>,
+}
+
+#[derive(Deserialize)]
+struct RawKeyedAccount {
+    pubkey: String,
+    account: RawAccount,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawAccount {
+    lamports: u64,
+    owner: String,
+    data: (String, String),
+    executable: bool,
+    rent_epoch: u64,
+}
+
+impl TryFrom<RawAccount> for Account {
+    type Error = RpcError;
+
+    fn try_from(raw: RawAccount) -> Result<Self, RpcError> {
+        Ok(Self {
+            lamports: raw.lamports,
+            owner: pubkey(&raw.owner)?,
+            data: general_purpose::STANDARD.decode(&raw.data.0).map_err(|_| malformed("account data is not base64"))?,
+            executable: raw.executable,
+            rent_epoch: raw.rent_epoch,
+        })
+    }
+}
+
+fn malformed(what: &str) -> RpcError {
+    RpcError(format!("malformed RPC response: {what}"))
+}
+
+fn pubkey(value: &str) -> Result<Pubkey, RpcError> {
+    Pubkey::from_str(value).map_err(|_| malformed("invalid address"))
+}
+
+fn pubkeys(values: &[String]) -> Result<Vec<Pubkey>, RpcError> {
+    values.iter().map(|value| pubkey(value)).collect()
+}
+
+fn token_balances(raw: Vec<RawTokenBalance>) -> Result<Vec<TokenBalance>, RpcError> {
+    raw.into_iter()
+        .map(|balance| {
+            Ok(TokenBalance {
+                account_index: balance.account_index,
+                mint: pubkey(&balance.mint)?,
+                owner: balance.owner.as_deref().map(pubkey).transpose()?,
+                amount: balance.ui_token_amount.amount.parse().map_err(|_| malformed("invalid token amount"))?,
+                decimals: balance.ui_token_amount.decimals,
+            })
+        })
+        .collect()
+}
+
+impl TryFrom<RawConfirmedTransaction> for ConfirmedTransaction {
+    type Error = RpcError;
+
+    fn try_from(raw: RawConfirmedTransaction) -> Result<Self, RpcError> {
+        confirmed_transaction(raw.slot, raw.block_time, &raw.transaction.0, raw.meta)
+    }
+}
+
+impl ConfirmedBlock {
+    fn from_raw(slot: u64, raw: RawBlock) -> Result<Self, RpcError> {
+        let transactions = raw
+            .transactions
+            .into_iter()
+            .map(|entry| confirmed_transaction(slot, raw.block_time, &entry.transaction.0, entry.meta))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            slot,
+            blockhash: Hash::from_str(&raw.blockhash).map_err(|_| malformed("invalid blockhash"))?,
+            parent_slot: raw.parent_slot,
+            block_time: raw.block_time,
+            block_height: raw.block_height,
+            transactions,
+        })
+    }
+}
+
+fn confirmed_transaction(
+    slot: u64,
+    block_time: Option<i64>,
+    transaction: &str,
+    meta: Option<RawMeta>,
+) -> Result<ConfirmedTransaction, RpcError> {
+    let bytes = general_purpose::STANDARD.decode(transaction).map_err(|_| malformed("transaction is not base64"))?;
+    let transaction = bincode::deserialize(&bytes).map_err(|_| malformed("undecodable transaction"))?;
+    let meta = meta.unwrap_or_default();
+
+    Ok(ConfirmedTransaction {
+        slot,
+        block_time,
+        transaction,
+        meta: TransactionMeta {
+            err: meta.err.filter(|err| !err.is_null()).map(|err| err.to_string()),
+            fee: meta.fee,
+            pre_balances: meta.pre_balances,
+            post_balances: meta.post_balances,
+            pre_token_balances: token_balances(meta.pre_token_balances)?,
+            post_token_balances: token_balances(meta.post_token_balances)?,
+            loaded_writable: pubkeys(&meta.loaded_addresses.writable)?,
+            loaded_readonly: pubkeys(&meta.loaded_addresses.readonly)?,
+            logs: meta.log_messages,
+        },
+    })
+}
+
+#[async_trait]
+impl SolanaRpc for ClusterRpc {
+    async fn get_latest_blockhash(&self) -> Result<LatestBlockhash, RpcError> {
+        let (blockhash, last_valid_block_height) = self
+            .client
+            .get_latest_blockhash_with_commitment(self.client.commitment())
+            .await
+            .map_err(rpc_error)?;
+
+        Ok(LatestBlockhash { blockhash, last_valid_block_height })
+    }
+
+    async fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>, RpcError> {
+        self.client
+            .get_account_with_commitment(pubkey, self.client.commitment())
+            .await
+            .map(|response| response.value)
+            .map_err(rpc_error)
+    }
+
+    async fn get_token_accounts_by_owner(
+        &self,
+        owner: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<Vec<(Pubkey, Account)>, RpcError> {
+        // Raw request for base64 data rather than the account-decoder's
+        // parsed JSON.
+        let params = json!([
+            owner.to_string(),
+            { "programId": program_id.to_string() },
+            { "encoding": "base64", "commitment": self.client.commitment().commitment },
+        ]);
+        let response: RawResponse<Vec<RawKeyedAccount>> = self
+            .client
+            .send(RpcRequest::GetTokenAccountsByOwner, params)
+            .await
+            .map_err(rpc_error)?;
+
+        response
+            .value
+            .into_iter()
+            .map(|keyed| Ok((pubkey(&keyed.pubkey)?, Account::try_from(keyed.account)?)))
+            .collect()
+    }
+
+    async fn get_token_balances_by_mint(
+        &self,
+        mint: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<Vec<(Pubkey, u64)>, RpcError> {
+        // The mint, owner and amount lead the base layout of both programs'
+        // token accounts. Only spl-token accounts have a fixed size to filter
+        // on as well.
+        let mut filters = vec![json!({ "memcmp": { "offset": 0, "bytes": mint.to_string() } })];
+        if *program_id == spl_token::id() {
+            filters.push(json!({ "dataSize": spl_token::state::Account::LEN }));
+        }
+        let params = json!([
+            program_id.to_string(),
+            {
+                "encoding": "base64",
+                "commitment": self.client.commitment().commitment,
+                "filters": filters,
+                "dataSlice": { "offset": 32, "length": 40 },
+            }
+        ]);
+        let accounts: Vec<RawKeyedAccount> = self
+            .client
+            .send(RpcRequest::GetProgramAccounts, params)
+            .await
+            .map_err(rpc_error)?;
+
+        accounts
+            .into_iter()
+            .map(|keyed| {
+                let data = Account::try_from(keyed.account)?.data;
+                let (Some(owner), Some(amount)) = (data.get(..32), data.get(32..40)) else {
+                    return Err(malformed("token account slice is short"));
+                };
+                let owner = Pubkey::try_from(owner).expect("32 bytes");
+                Ok((owner, u64::from_le_bytes(amount.try_into().expect("8 bytes"))))
+            })
+            .collect()
+    }
+
+    async fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: &[AccountFilter],
+    ) -> Result<Vec<(Pubkey, Account)>, RpcError> {
+        let filters: Vec<_> = filters
+            .iter()
+            .map(|filter| match filter {
+                AccountFilter::DataSize(size) => json!({ "dataSize": size }),
+                AccountFilter::Memcmp { offset, bytes } => {
+                    let bytes = general_purpose::STANDARD.encode(bytes);
+                    json!({ "memcmp": { "offset": offset, "bytes": bytes, "encoding": "base64" } })
+                }
+            })
+            .collect();
+        let params = json!([
+            program_id.to_string(),
+            {
+                "encoding": "base64",
+                "commitment": self.client.commitment().commitment,
+                "filters": filters,
+            }
+        ]);
+        let accounts: Vec<RawKeyedAccount> = self
+            .client
+            .send(RpcRequest::GetProgramAccounts, params)
+            .await
+            .map_err(rpc_error)?;
+
+        accounts
+            .into_iter()
+            .map(|keyed| Ok((pubkey(&keyed.pubkey)?, Account::try_from(keyed.account)?)))
+            .collect()
+    }
+
+    async fn simulate_transaction(&self, transaction: &VersionedTransaction) -> Result<Simulation, RpcError> {
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            ..RpcSimulateTransactionConfig::default()
+        };
+
+        let result = self
+            .client
+            .simulate_transaction_with_config(transaction, config)
+            .await
+            .map_err(rpc_error)?
+            .value;
+
+        Ok(Simulation {
+            err: result.err.map(|err| err.to_string()),
+            logs: result.logs.unwrap_or_default(),
+            units_consumed: result.units_consumed,
+            accounts: Vec::new(),
+        })
+    }
+
+    async fn send_transaction(&self, transaction: &VersionedTransaction) -> Result<Signature, RpcError> {
+        self.client.send_transaction(transaction).await.map_err(rpc_error)
+    }
+
+    async fn simulate_with_accounts(
+        &self,
+        transaction: &VersionedTransaction,
+        addresses: &[Pubkey],
+    ) -> Result<Simulation, RpcError> {
+        // Raw request so the returned accounts can be read without the
+        // account-decoder types.
+        let encoded = bincode::serialize(transaction).map_err(|err| RpcError(err.to_string()))?;
+        let params = json!([
+            general_purpose::STANDARD.encode(encoded),
+            {
+                "encoding": "base64",
+                "sigVerify": false,
+                "replaceRecentBlockhash": true,
+                "commitment": self.client.commitment().commitment,
+                "accounts": {
+                    "encoding": "base64",
+                    "addresses": addresses.iter().map(Pubkey::to_string).collect::<Vec<_>>(),
+                },
+            }
+        ]);
+        let response: RawResponse<RawSimulation> = self
+            .client
+            .send(RpcRequest::SimulateTransaction, params)
+            .await
+            .map_err(rpc_error)?;
+        let result = response.value;
+
+        Ok(Simulation {
+            err: result.err.filter(|err| !err.is_null()).map(|err| err.to_string()),
+            logs: result.logs.unwrap_or_default(),
+            units_consumed: result.units_consumed,
+            accounts: result
+                .accounts
+                .unwrap_or_default()
+                .into_iter()
+                .map(|account| account.map(Account::try_from).transpose())
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    async fn get_transaction_logs(&self, signature: &Signature) -> Result<Option<Vec<String>>, RpcError> {
+        // Only the logs are needed, so skip the typed response and its
+        // transaction-status dependency.
+        let params = self.transaction_params(signature, "json");
+        let transaction: Option<Value> = self
+            .client
+            .send(RpcRequest::GetTransaction, params)
+            .await
+            .map_err(rpc_error)?;
+
+        Ok(transaction.map(|transaction| {
+            transaction["meta"]["logMessages"]
+                .as_array()
+                .map(|logs| logs.iter().filter_map(|log| log.as_str().map(str::to_string)).collect())
+                .unwrap_or_default()
+        }))
+    }
+
+    async fn get_transaction(&self, signature: &Signature) -> Result<Option<ConfirmedTransaction>, RpcError> {
+        let params = self.transaction_params(signature, "base64");
+        let transaction: Option<RawConfirmedTransaction> = self
+            .client
+            .send(RpcRequest::GetTransaction, params)
+            .await
+            .map_err(rpc_error)?;
+
+        transaction.map(ConfirmedTransaction::try_from).transpose()
+    }
+
+    async fn get_block(&self, slot: u64) -> Result<Option<ConfirmedBlock>, RpcError> {
+        let params = json!([
+            slot,
+            {
+                "encoding": "base64",
+                "transactionDetails": "full",
+                "rewards": false,
+                "commitment": self.client.commitment().commitment,
+                "maxSupportedTransactionVersion": 0,
+            }
+        ]);
+        let block: Option<RawBlock> = match self.client.send(RpcRequest::GetBlock, params).await {
+            Ok(block) => block,
+            // Skipped and pruned slots are errors rather than null.
+            Err(err) if ["skipped", "not available"].iter().any(|reason| err.to_string().contains(reason)) => None,
+            Err(err) => return Err(rpc_error(err)),
+        };
+
+        block.map(|block| ConfirmedBlock::from_raw(slot, block)).transpose()
+    }
+
+    async fn get_epoch_info(&self) -> Result<EpochInfo, RpcError> {
+        self.client.get_epoch_info().await.map_err(rpc_error)
+    }
+
+    async fn get_slot(&self) -> Result<u64, RpcError> {
+        self.client.get_slot().await.map_err(rpc_error)
+    }
+
+    async fn get_block_height(&self) -> Result<u64, RpcError> {
+        self.client.get_block_height().await.map_err(rpc_error)
+    }
+
+    async fn is_blockhash_valid(&self, blockhash: &Hash) -> Result<bool, RpcError> {
+        self.client.is_blockhash_valid(blockhash, self.client.commitment()).await.map_err(rpc_error)
+    }
+
+    async fn get_version(&self) -> Result<ClusterVersion, RpcError> {
+        let version = self.client.get_version().await.map_err(rpc_error)?;
+        Ok(ClusterVersion { solana_core: version.solana_core, feature_set: version.feature_set })
+    }
+
+    async fn get_vote_accounts(&self) -> Result<Vec<VoteAccount>, RpcError> {
+        let status = self.client.get_vote_accounts().await.map_err(rpc_error)?;
+        let current = status.current.into_iter().map(|info| (info, false));
+        let delinquent = status.delinquent.into_iter().map(|info| (info, true));
+        current
+            .chain(delinquent)
+            .map(|(info, delinquent)| {
+                Ok(VoteAccount {
+                    vote_pubkey: pubkey(&info.vote_pubkey)?,
+                    node_pubkey: pubkey(&info.node_pubkey)?,
+                    activated_stake: info.activated_stake,
+                    commission: info.commission,
+                    last_vote: info.last_vote,
+                    root_slot: info.root_slot,
+                    delinquent,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_inflation_reward(&self, address: &Pubkey, epoch: u64) -> Result<Option<InflationReward>, RpcError> {
+        let rewards = self.client.get_inflation_reward(&[*address], Some(epoch)).await.map_err(rpc_error)?;
+        Ok(rewards.into_iter().next().flatten().map(|reward| InflationReward {
+            epoch: reward.epoch,
+            effective_slot: reward.effective_slot,
+            amount: reward.amount,
+            post_balance: reward.post_balance,
+            commission: reward.commission,
+        }))
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+use solana_sdk::{
+    account::Account,
+    epoch_info::EpochInfo,
+    hash::Hash,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+
+use spl_token::state::Account as TokenAccount;
+
+use super::{
+    AccountFilter, ClusterVersion, ConfirmedBlock, ConfirmedTransaction, InflationReward, LatestBlockhash, RpcError,
+    Simulation, SolanaRpc, VoteAccount,
+};
+
+/// In-memory `SolanaRpc` for tests and offline development. Every response is
+/// programmable; unset accounts read as missing, the blockhash is fixed and
+/// simulations succeed with no logs.
+pub struct MockRpc {
+    state: RwLock<MockState>,
+}
+
+struct MockState {
+    accounts: HashMap<Pubkey, Account>,
+    blockhash: LatestBlockhash,
+    epoch_info: EpochInfo,
+    version: ClusterVersion,
+    vote_accounts: Vec<VoteAccount>,
+    inflation_rewards: HashMap<(Pubkey, u64), InflationReward>,
+    simulation: Result<Simulation, RpcError>,
+    transaction_logs: HashMap<Signature, Vec<String>>,
+    transactions: HashMap<Signature, ConfirmedTransaction>,
+    blocks: HashMap<u64, ConfirmedBlock>,
+    simulated_accounts: HashMap<Pubkey, Option<Account>>,
+    simulated: Vec<VersionedTransaction>,
+    sent: Vec<VersionedTransaction>,
+    send_error: Option<RpcError>,
+}
+
+impl MockRpc {
+    pub fn new() -> Self {
+        Self {
+            state: RwLock::new(MockState {
+                accounts: HashMap::new(),
+                blockhash: LatestBlockhash {
+                    blockhash: Hash::new_from_array([1; 32]),
+                    last_valid_block_height: 400_150,
+                },
+                epoch_info: EpochInfo {
+                    epoch: 1,
+                    slot_index: 100,
+                    slots_in_epoch: 432_000,
+                    absolute_slot: 432_100,
+                    block_height: 400_000,
+                    transaction_count: None,
+                },
+                version: ClusterVersion { solana_core: "mock".to_string(), feature_set: None },
+                vote_accounts: Vec::new(),
+                inflation_rewards: HashMap::new(),
+                simulation: Ok(Simulation::default()),
+                transaction_logs: HashMap::new(),
+                transactions: HashMap::new(),
+                blocks: HashMap::new(),
+                simulated_accounts: HashMap::new(),
+                simulated: Vec::new(),
+                sent: Vec::new(),
+                send_error: None,
+            }),
+        }
+    }
+
+    pub fn set_account(&self, pubkey: Pubkey, account: Account) {
+        self.state.write().unwrap().accounts.insert(pubkey, account);
+    }
+
+    pub fn remove_account(&self, pubkey: &Pubkey) {
+        self.state.write().unwrap().accounts.remove(pubkey);
+    }
+
+    pub fn set_blockhash(&self, blockhash: LatestBlockhash) {
+        self.state.write().unwrap().blockhash = blockhash;
+    }
+
+    /// Also sets the slot `get_slot` reports, `absolute_slot`.
+    pub fn set_epoch_info(&self, epoch_info: EpochInfo) {
+        self.state.write().unwrap().epoch_info = epoch_info;
+    }
+
+    /// Also the `block_height` of the epoch info.
+    pub fn set_block_height(&self, block_height: u64) {
+        self.state.write().unwrap().epoch_info.block_height = block_height;
+    }
+
+    pub fn set_version(&self, version: ClusterVersion) {
+        self.state.write().unwrap().version = version;
+    }
+
+    pub fn set_vote_accounts(&self, vote_accounts: Vec<VoteAccount>) {
+        self.state.write().unwrap().vote_accounts = vote_accounts;
+    }
+
+    pub fn set_inflation_reward(&self, address: Pubkey, reward: InflationReward) {
+        self.state.write().unwrap().inflation_rewards.insert((address, reward.epoch), reward);
+    }
+
+    /// Result returned by every subsequent `simulate_transaction` call; pass
+    /// an `Err` to emulate the node rejecting the request.
+    pub fn set_simulation(&self, simulation: Result<Simulation, RpcError>) {
+        self.state.write().unwrap().simulation = simulation;
+    }
+
+    /// State `simulate_with_accounts` reports for `pubkey`; `None` means the
+    /// transaction closes it. Unset accounts come back unchanged.
+    pub fn set_simulated_account(&self, pubkey: Pubkey, account: Option<Account>) {
+        self.state.write().unwrap().simulated_accounts.insert(pubkey, account);
+    }
+
+    /// Every transaction passed to `simulate_transaction`, oldest first.
+    pub fn simulated_transactions(&self) -> Vec<VersionedTransaction> {
+        self.state.read().unwrap().simulated.clone()
+    }
+
+    /// Every transaction passed to `send_transaction`, oldest first.
+    pub fn sent_transactions(&self) -> Vec<VersionedTransaction> {
+        self.state.read().unwrap().sent.clone()
+    }
+
+    /// While set, `send_transaction` fails with this error and records nothing.
+    pub fn set_send_error(&self, error: Option<RpcError>) {
+        self.state.write().unwrap().send_error = error;
+    }
+
+    pub fn set_transaction_logs(&self, signature: Signature, logs: Vec<String>) {
+        self.state.write().unwrap().transaction_logs.insert(signature, logs);
+    }
+
+    pub fn set_transaction(&self, signature: Signature, transaction: ConfirmedTransaction) {
+        self.state.write().unwrap().transactions.insert(signature, transaction);
+    }
+
+    pub fn set_block(&self, block: ConfirmedBlock) {
+        self.state.write().unwrap().blocks.insert(block.slot, block);
+    }
+}
+
+impl MockRpc {
+    /// Token accounts of `program_id` whose base layout `matches`, by address.
+    fn token_accounts(&self, program_id: &Pubkey, matches: impl Fn(&[u8]) -> bool) -> Vec<(Pubkey, Account)> {
+        let state = self.state.read().unwrap();
+        let mut accounts: Vec<_> = state
+            .accounts
+            .iter()
+            .filter(|(_, account)| {
+                account.owner == *program_id && account.data.len() >= TokenAccount::LEN && matches(&account.data)
+            })
+            .map(|(address, account)| (*address, account.clone()))
+            .collect();
+        accounts.sort_by_key(|(address, _)| *address);
+        accounts
+    }
+}
+
+impl Default for MockRpc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SolanaRpc for MockRpc {
+    async fn get_latest_blockhash(&self) -> Result<LatestBlockhash, RpcError> {
+        Ok(self.state.read().unwrap().blockhash)
+    }
+
+    async fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>, RpcError> {
+        Ok(self.state.read().unwrap().accounts.get(pubkey).cloned())
+    }
+
+    async fn get_token_accounts_by_owner(
+        &self,
+        owner: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<Vec<(Pubkey, Account)>, RpcError> {
+        // Both token programs put the owner at the same offset; token-2022
+        // accounts only append extensions after the base layout.
+        Ok(self.token_accounts(program_id, |data| data[32..64] == owner.to_bytes()))
+    }
+
+    async fn get_token_balances_by_mint(
+        &self,
+        mint: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<Vec<(Pubkey, u64)>, RpcError> {
+        let accounts = self.token_accounts(program_id, |data| data[..32] == mint.to_bytes());
+        Ok(accounts
+            .into_iter()
+            .map(|(_, account)| {
+                let owner = Pubkey::try_from(&account.data[32..64]).unwrap();
+                (owner, u64::from_le_bytes(account.data[64..72].try_into().unwrap()))
+            })
+            .collect())
+    }
+
+    async fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: &[AccountFilter],
+    ) -> Result<Vec<(Pubkey, Account)>, RpcError> {
+        let state = self.state.read().unwrap();
+        let mut accounts: Vec<_> = state
+            .accounts
+            .iter()
+            .filter(|(_, account)| {
+                account.owner == *program_id && filters.iter().all(|filter| filter.matches(&account.data))
+            })
+            .map(|(address, account)| (*address, account.clone()))
+            .collect();
+        accounts.sort_by_key(|(address, _)| *address);
+        Ok(accounts)
+    }
+
+    async fn simulate_transaction(&self, transaction: &VersionedTransaction) -> Result<Simulation, RpcError> {
+        let mut state = self.state.write().unwrap();
+        state.simulated.push(transaction.clone());
+        state.simulation.clone()
+    }
+
+    async fn send_transaction(&self, transaction: &VersionedTransaction) -> Result<Signature, RpcError> {
+        let mut state = self.state.write().unwrap();
+        if let Some(error) = &state.send_error {
+            return Err(error.clone());
+        }
+        state.sent.push(transaction.clone());
+        Ok(transaction.signatures.first().copied().unwrap_or_default())
+    }
+
+    async fn simulate_with_accounts(
+        &self,
+        _transaction: &VersionedTransaction,
+        addresses: &[Pubkey],
+    ) -> Result<Simulation, RpcError> {
+        let state = self.state.read().unwrap();
+        let mut simulation = state.simulation.clone()?;
+        // Like a real node, a failed simulation reports no account state.
+        if simulation.err.is_none() {
+            simulation.accounts = addresses
+                .iter()
+                .map(|address| match state.simulated_accounts.get(address) {
+                    Some(account) => account.clone(),
+                    None => state.accounts.get(address).cloned(),
+                })
+                .collect();
+        }
+        Ok(simulation)
+    }
+
+    async fn get_transaction_logs(&self, signature: &Signature) -> Result<Option<Vec<String>>, RpcError> {
+        Ok(self.state.read().unwrap().transaction_logs.get(signature).cloned())
+    }
+
+    async fn get_transaction(&self, signature: &Signature) -> Result<Option<ConfirmedTransaction>, RpcError> {
+        Ok(self.state.read().unwrap().transactions.get(signature).cloned())
+    }
+
+    async fn get_block(&self, slot: u64) -> Result<Option<ConfirmedBlock>, RpcError> {
+        Ok(self.state.read().unwrap().blocks.get(&slot).cloned())
+    }
+
+    async fn get_epoch_info(&self) -> Result<EpochInfo, RpcError> {
+        Ok(self.state.read().unwrap().epoch_info.clone())
+    }
+
+    async fn get_slot(&self) -> Result<u64, RpcError> {
+        Ok(self.state.read().unwrap().epoch_info.absolute_slot)
+    }
+
+    async fn get_block_height(&self) -> Result<u64, RpcError> {
+        Ok(self.state.read().unwrap().epoch_info.block_height)
+    }
+
+    /// Only the current blockhash is valid, until the block height passes it.
+    async fn is_blockhash_valid(&self, blockhash: &Hash) -> Result<bool, RpcError> {
+        let state = self.state.read().unwrap();
+        Ok(*blockhash == state.blockhash.blockhash
+            && state.epoch_info.block_height <= state.blockhash.last_valid_block_height)
+    }
+
+    async fn get_version(&self) -> Result<ClusterVersion, RpcError> {
+        Ok(self.state.read().unwrap().version.clone())
+    }
+
+    async fn get_vote_accounts(&self) -> Result<Vec<VoteAccount>, RpcError> {
+        Ok(self.state.read().unwrap().vote_accounts.clone())
+    }
+
+    async fn get_inflation_reward(&self, address: &Pubkey, epoch: u64) -> Result<Option<InflationReward>, RpcError> {
+        Ok(self.state.read().unwrap().inflation_rewards.get(&(*address, epoch)).cloned())
+    }
+}
@@ -0,0 +1,121 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, Method, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use base64::{engine::general_purpose, Engine as _};
+use serde::Deserialize;
+use serde_json::Value;
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    transaction::VersionedTransaction,
+};
+
+use crate::errors::AppError;
+use crate::extract::Query;
+use crate::models::transaction::{DryRun, SimulateQuery};
+use crate::models::{InstructionResponse, Web3Instruction};
+use crate::state::AppState;
+use crate::tx;
+
+/// Instructions as builder endpoints return them.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Built {
+    One(InstructionResponse),
+    Many { instructions: Vec<InstructionResponse> },
+    Web3(Web3Instruction),
+}
+
+/// `?simulate=true` on POST builders: simulates what the endpoint built and
+/// adds the outcome as `simulation`, so mistakes surface before a client
+/// assembles and signs a transaction. Needs RPC.
+pub async fn dry_run(
+    State(state): State<AppState>,
+    Query(query): Query<SimulateQuery>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if request.method() != Method::POST || !query.simulate {
+        return Ok(next.run(request).await);
+    }
+    let rpc = state.rpc()?;
+
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return Ok(response);
+    }
+    let (mut parts, body) = response.into_parts();
+    let bytes = to_bytes(body, usize::MAX).await.map_err(|err| AppError::Internal(err.to_string()))?;
+    let mut body: Value = serde_json::from_slice(&bytes).map_err(|err| AppError::Internal(err.to_string()))?;
+    let instructions = serde_json::from_value(body["data"].clone()).ok().and_then(instructions).ok_or_else(|| {
+        AppError::InvalidField {
+            field: "simulate".to_string(),
+            message: "this endpoint doesn't return instructions to simulate".to_string(),
+        }
+    })?;
+
+    let fee_payer = instructions
+        .iter()
+        .flat_map(|instruction| &instruction.accounts)
+        .find(|meta| meta.is_signer && meta.is_writable)
+        .map_or_else(|| Keypair::new().pubkey(), |meta| meta.pubkey);
+    // The node swaps in a recent blockhash and skips signature checks.
+    let transaction = VersionedTransaction::from(tx::unsigned(&instructions, &fee_payer, Hash::default()));
+    let simulation = rpc.simulate_transaction(&transaction).await?;
+
+    body["simulation"] = serde_json::json!(DryRun {
+        fee_payer: fee_payer.to_string(),
+        error: simulation.err,
+        logs: simulation.logs,
+        units_consumed: simulation.units_consumed,
+    });
+    let bytes = serde_json::to_vec(&body).map_err(|err| AppError::Internal(err.to_string()))?;
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Ok(Response::from_parts(parts, Body::from(bytes)))
+}
+
+fn instructions(built: Built) -> Option<Vec<Instruction>> {
+    let instructions = match built {
+        Built::One(instruction) => vec![instruction],
+        Built::Many { instructions } => instructions,
+        Built::Web3(instruction) => vec![InstructionResponse {
+            program_id: instruction.program_id,
+            accounts: instruction
+                .keys
+                .into_iter()
+                .map(|key| crate::models::AccountMeta {
+                    pubkey: key.pubkey,
+                    is_signer: key.is_signer,
+                    is_writable: key.is_writable,
+                })
+                .collect(),
+            instruction_data: instruction.data,
+        }],
+    };
+    if instructions.is_empty() {
+        return None;
+    }
+    instructions.iter().map(instruction).collect()
+}
+
+fn instruction(response: &InstructionResponse) -> Option<Instruction> {
+    let accounts = response
+        .accounts
+        .iter()
+        .map(|meta| {
+            let pubkey: Pubkey = meta.pubkey.parse().ok()?;
+            Some(AccountMeta { pubkey, is_signer: meta.is_signer, is_writable: meta.is_writable })
+        })
+        .collect::<Option<_>>()?;
+    Some(Instruction {
+        program_id: response.program_id.parse().ok()?,
+        accounts,
+        data: general_purpose::STANDARD.decode(&response.instruction_data).ok()?,
+    })
+}
@@ -0,0 +1,204 @@
+use solana_sdk::{instruction::Instruction, pubkey, pubkey::Pubkey};
+use crate::amount;
+use crate::errors::FieldError;
+use crate::models::solana_pay::DecodeResponse;
+use crate::utils::parse_pubkey;
+
+pub const SCHEME: &str = "solana:";
+
+/// SPL Memo v2, which Solana Pay uses for the `memo` parameter.
+pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// A parsed `solana:` URL, per the Solana Pay specification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayUrl {
+    Transfer(TransferRequest),
+    /// Interactive request: the wallet fetches the transaction from `link`.
+    Transaction { link: String },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransferRequest {
+    pub recipient: Pubkey,
+    /// Decimal string in user units (SOL or whole tokens), never lamports.
+    pub amount: Option<String>,
+    pub spl_token: Option<Pubkey>,
+    pub reference: Vec<Pubkey>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+    pub memo: Option<String>,
+}
+
+impl PayUrl {
+    pub fn encode(&self) -> String {
+        match self {
+            PayUrl::Transaction { link } => {
+                // Links carrying their own query must be encoded so wallets
+                // don't mistake it for Solana Pay parameters.
+                if link.contains('?') {
+                    format!("{SCHEME}{}", escape(link))
+                } else {
+                    format!("{SCHEME}{link}")
+                }
+            }
+            PayUrl::Transfer(transfer) => {
+                let mut params = Vec::new();
+                if let Some(amount) = &transfer.amount {
+                    params.push(("amount", amount.clone()));
+                }
+                if let Some(mint) = &transfer.spl_token {
+                    params.push(("spl-token", mint.to_string()));
+                }
+                params.extend(transfer.reference.iter().map(|key| ("reference", key.to_string())));
+                for (name, value) in [("label", &transfer.label), ("message", &transfer.message), ("memo", &transfer.memo)] {
+                    if let Some(value) = value {
+                        params.push((name, value.clone()));
+                    }
+                }
+
+                let query: Vec<String> = params
+                    .into_iter()
+                    .map(|(name, value)| format!("{name}={}", escape(&value)))
+                    .collect();
+                if query.is_empty() {
+                    format!("{SCHEME}{}", transfer.recipient)
+                } else {
+                    format!("{SCHEME}{}?{}", transfer.recipient, query.join("&"))
+                }
+            }
+        }
+    }
+
+    pub fn parse(url: &str) -> Result<Self, FieldError> {
+        let invalid = |reason: &str| FieldError::InvalidPaymentUrl(reason.to_string());
+
+        let rest = url
+            .get(..SCHEME.len())
+            .filter(|scheme| scheme.eq_ignore_ascii_case(SCHEME))
+            .map(|_| &url[SCHEME.len()..])
+            .ok_or_else(|| invalid("expected the solana: scheme"))?;
+
+        let decoded = unescape(rest);
+        if decoded.to_ascii_lowercase().starts_with("https:") {
+            check_link(&decoded)?;
+            return Ok(PayUrl::Transaction { link: decoded });
+        }
+
+        let (recipient, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let mut transfer = TransferRequest {
+            recipient: parse_pubkey(recipient).map_err(|_| invalid("recipient is not a valid public key"))?,
+            ..TransferRequest::default()
+        };
+
+        for (name, value) in form_urlencoded::parse(query.as_bytes()) {
+            let value = value.into_owned();
+            match name.as_ref() {
+                "amount" => transfer.amount = Some(value),
+                "spl-token" => {
+                    transfer.spl_token = Some(parse_pubkey(&value).map_err(|_| invalid("spl-token is not a valid mint"))?);
+                }
+                "reference" => {
+                    transfer.reference.push(parse_pubkey(&value).map_err(|_| invalid("reference is not a valid public key"))?);
+                }
+                "label" => transfer.label = Some(value),
+                "message" => transfer.message = Some(value),
+                "memo" => transfer.memo = Some(value),
+                // The spec lets wallets ignore parameters they don't know.
+                _ => {}
+            }
+        }
+
+        // `amount` may precede `spl-token`, so it is checked once the asset is known.
+        if let Some(amount) = &transfer.amount {
+            check_amount(amount, transfer.spl_token.is_none())?;
+        }
+        Ok(PayUrl::Transfer(transfer))
+    }
+}
+
+impl From<PayUrl> for DecodeResponse {
+    fn from(url: PayUrl) -> Self {
+        match url {
+            PayUrl::Transfer(transfer) => DecodeResponse {
+                kind: "transfer".to_string(),
+                recipient: Some(transfer.recipient.to_string()),
+                amount: transfer.amount,
+                spl_token: transfer.spl_token.map(|mint| mint.to_string()),
+                reference: transfer.reference.iter().map(ToString::to_string).collect(),
+                label: transfer.label,
+                message: transfer.message,
+                memo: transfer.memo,
+                link: None,
+            },
+            PayUrl::Transaction { link } => DecodeResponse {
+                kind: "transaction".to_string(),
+                recipient: None,
+                amount: None,
+                spl_token: None,
+                reference: Vec::new(),
+                label: None,
+                message: None,
+                memo: None,
+                link: Some(link),
+            },
+        }
+    }
+}
+
+/// Memo instruction with no required signers; goes right before the transfer.
+pub fn memo_instruction(memo: &str) -> Instruction {
+    Instruction::new_with_bytes(MEMO_PROGRAM_ID, memo.as_bytes(), Vec::new())
+}
+
+/// Amounts are in user units; SOL amounts may not be finer than a lamport.
+/// Token precision depends on the mint, which the URL alone doesn't give.
+pub fn check_amount(amount: &str, is_sol: bool) -> Result<(), FieldError> {
+    if is_sol {
+        amount::sol_to_lamports(amount).map(|_| ())
+    } else {
+        amount::check_format(amount)
+    }
+}
+
+pub fn check_link(link: &str) -> Result<(), FieldError> {
+    let rest = link
+        .get(..8)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("https://"))
+        .map(|_| &link[8..])
+        .ok_or(FieldError::InvalidLink)?;
+
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if host.is_empty() || link.chars().any(char::is_whitespace) {
+        return Err(FieldError::InvalidLink);
+    }
+    Ok(())
+}
+
+/// `encodeURIComponent`-style escaping, which is what wallets decode.
+fn escape(value: &str) -> String {
+    form_urlencoded::byte_serialize(value.as_bytes())
+        .collect::<String>()
+        .replace('+', "%20")
+}
+
+fn unescape(value: &str) -> String {
+    let bytes: Vec<u8> = percent_decode(value.as_bytes());
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn percent_decode(input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        if input[i] == b'%'
+            && let Some(byte) = input.get(i + 1..i + 3).and_then(|hex| hex::decode(hex).ok())
+        {
+            output.push(byte[0]);
+            i += 3;
+            continue;
+        }
+        output.push(input[i]);
+        i += 1;
+    }
+    output
+}
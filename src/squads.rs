@@ -0,0 +1,354 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    pubkey,
+    pubkey::Pubkey,
+};
+use solana_system_interface::program as system_program;
+
+use crate::anchor::sighash;
+
+/// The Squads v4 multisig program.
+pub const PROGRAM_ID: Pubkey = pubkey!("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf");
+
+const SEED_PREFIX: &[u8] = b"multisig";
+
+/// Member permission bits, as stored in `Permissions::mask`.
+pub const PERMISSION_INITIATE: u8 = 1;
+pub const PERMISSION_VOTE: u8 = 2;
+pub const PERMISSION_EXECUTE: u8 = 4;
+
+pub fn program_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX, b"program_config"], &PROGRAM_ID).0
+}
+
+/// The multisig created with `create_key`, which must sign its creation.
+pub fn multisig_address(create_key: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX, b"multisig", create_key.as_ref()], &PROGRAM_ID).0
+}
+
+/// The PDA holding the multisig's funds; most multisigs only use index 0.
+pub fn vault_address(multisig: &Pubkey, index: u8) -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX, multisig.as_ref(), b"vault", &[index]], &PROGRAM_ID).0
+}
+
+pub fn transaction_address(multisig: &Pubkey, index: u64) -> Pubkey {
+    let seeds: &[&[u8]] = &[SEED_PREFIX, multisig.as_ref(), b"transaction", &index.to_le_bytes()];
+    Pubkey::find_program_address(seeds, &PROGRAM_ID).0
+}
+
+pub fn proposal_address(multisig: &Pubkey, index: u64) -> Pubkey {
+    let seeds: &[&[u8]] = &[SEED_PREFIX, multisig.as_ref(), b"transaction", &index.to_le_bytes(), b"proposal"];
+    Pubkey::find_program_address(seeds, &PROGRAM_ID).0
+}
+
+/// A member and their permission bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Member {
+    pub key: Pubkey,
+    pub mask: u8,
+}
+
+pub struct CreateMultisig<'a> {
+    pub create_key: Pubkey,
+    pub creator: Pubkey,
+    /// From the program config; creation fees are paid to it.
+    pub treasury: Pubkey,
+    pub config_authority: Option<Pubkey>,
+    pub threshold: u16,
+    pub members: &'a [Member],
+    pub time_lock: u32,
+    pub rent_collector: Option<Pubkey>,
+    pub memo: Option<&'a str>,
+}
+
+/// `multisig_create_v2`.
+pub fn create_multisig(args: &CreateMultisig) -> Instruction {
+    let mut data = sighash("global", "multisig_create_v2").to_vec();
+    option_pubkey(&mut data, args.config_authority);
+    data.extend_from_slice(&args.threshold.to_le_bytes());
+    data.extend_from_slice(&(args.members.len() as u32).to_le_bytes());
+    for member in args.members {
+        data.extend_from_slice(member.key.as_ref());
+        data.push(member.mask);
+    }
+    data.extend_from_slice(&args.time_lock.to_le_bytes());
+    option_pubkey(&mut data, args.rent_collector);
+    option_string(&mut data, args.memo);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(program_config_address(), false),
+        AccountMeta::new(args.treasury, false),
+        AccountMeta::new(multisig_address(&args.create_key), false),
+        AccountMeta::new_readonly(args.create_key, true),
+        AccountMeta::new(args.creator, true),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    Instruction { program_id: PROGRAM_ID, accounts, data }
+}
+
+/// `vault_transaction_create`, storing `message` (from `vault_message`) for
+/// the vault at `vault_index` to execute once approved.
+pub fn create_vault_transaction(
+    multisig: &Pubkey,
+    index: u64,
+    creator: &Pubkey,
+    rent_payer: &Pubkey,
+    vault_index: u8,
+    message: &[u8],
+    memo: Option<&str>,
+) -> Instruction {
+    let mut data = sighash("global", "vault_transaction_create").to_vec();
+    data.push(vault_index);
+    // Ephemeral signers aren't supported: the vault is the only signer.
+    data.push(0);
+    data.extend_from_slice(&(message.len() as u32).to_le_bytes());
+    data.extend_from_slice(message);
+    option_string(&mut data, memo);
+
+    let accounts = vec![
+        AccountMeta::new(*multisig, false),
+        AccountMeta::new(transaction_address(multisig, index), false),
+        AccountMeta::new_readonly(*creator, true),
+        AccountMeta::new(*rent_payer, true),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    Instruction { program_id: PROGRAM_ID, accounts, data }
+}
+
+/// `proposal_create`, opening the vote on transaction `index`.
+pub fn create_proposal(multisig: &Pubkey, index: u64, creator: &Pubkey, rent_payer: &Pubkey) -> Instruction {
+    let mut data = sighash("global", "proposal_create").to_vec();
+    data.extend_from_slice(&index.to_le_bytes());
+    // Not a draft: voting opens immediately.
+    data.push(0);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*multisig, false),
+        AccountMeta::new(proposal_address(multisig, index), false),
+        AccountMeta::new_readonly(*creator, true),
+        AccountMeta::new(*rent_payer, true),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    Instruction { program_id: PROGRAM_ID, accounts, data }
+}
+
+/// `proposal_approve` by `member`.
+pub fn approve_proposal(multisig: &Pubkey, index: u64, member: &Pubkey, memo: Option<&str>) -> Instruction {
+    let mut data = sighash("global", "proposal_approve").to_vec();
+    option_string(&mut data, memo);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*multisig, false),
+        AccountMeta::new(*member, true),
+        AccountMeta::new(proposal_address(multisig, index), false),
+    ];
+    Instruction { program_id: PROGRAM_ID, accounts, data }
+}
+
+/// `vault_transaction_execute` for an approved transaction whose stored
+/// message is `message`. Its accounts follow as remaining accounts, none of
+/// them signing: the program signs for the vault itself.
+pub fn execute_vault_transaction(
+    multisig: &Pubkey,
+    index: u64,
+    member: &Pubkey,
+    message: &VaultMessage,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*multisig, false),
+        AccountMeta::new(proposal_address(multisig, index), false),
+        AccountMeta::new_readonly(transaction_address(multisig, index), false),
+        AccountMeta::new_readonly(*member, true),
+    ];
+    accounts.extend(message.account_keys.iter().enumerate().map(|(i, key)| AccountMeta {
+        pubkey: *key,
+        is_signer: false,
+        is_writable: message.is_writable(i),
+    }));
+    Instruction { program_id: PROGRAM_ID, accounts, data: sighash("global", "vault_transaction_execute").to_vec() }
+}
+
+/// A vault transaction's message, as stored on chain and sent to
+/// `vault_transaction_create`. Accounts are ordered writable signers,
+/// readonly signers, writable non-signers, readonly non-signers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultMessage {
+    pub num_signers: u8,
+    pub num_writable_signers: u8,
+    pub num_writable_non_signers: u8,
+    pub account_keys: Vec<Pubkey>,
+    pub instructions: Vec<VaultInstruction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultInstruction {
+    pub program_id_index: u8,
+    pub account_indexes: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+impl VaultMessage {
+    /// Compiles `instructions` with `vault` as the only signer, or returns
+    /// the first other key that would have to sign.
+    pub fn compile(vault: &Pubkey, instructions: &[Instruction]) -> Result<Self, Pubkey> {
+        let message = Message::new(instructions, Some(vault));
+        let header = message.header;
+        if let Some(signer) = message.account_keys[1..usize::from(header.num_required_signatures)].first() {
+            return Err(*signer);
+        }
+        let num_signers = header.num_required_signatures;
+        Ok(VaultMessage {
+            num_signers,
+            num_writable_signers: num_signers - header.num_readonly_signed_accounts,
+            num_writable_non_signers: (message.account_keys.len()
+                - usize::from(num_signers)
+                - usize::from(header.num_readonly_unsigned_accounts)) as u8,
+            account_keys: message.account_keys,
+            instructions: message
+                .instructions
+                .into_iter()
+                .map(|ix| VaultInstruction {
+                    program_id_index: ix.program_id_index,
+                    account_indexes: ix.accounts,
+                    data: ix.data,
+                })
+                .collect(),
+        })
+    }
+
+    pub fn is_writable(&self, index: usize) -> bool {
+        let signers = usize::from(self.num_signers);
+        index < usize::from(self.num_writable_signers)
+            || (index >= signers && index < signers + usize::from(self.num_writable_non_signers))
+    }
+
+    /// The compact form `vault_transaction_create` takes: one-byte lengths,
+    /// except two bytes for instruction data. No lookup tables.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.num_signers, self.num_writable_signers, self.num_writable_non_signers];
+        bytes.push(self.account_keys.len() as u8);
+        for key in &self.account_keys {
+            bytes.extend_from_slice(key.as_ref());
+        }
+        bytes.push(self.instructions.len() as u8);
+        for ix in &self.instructions {
+            bytes.push(ix.program_id_index);
+            bytes.push(ix.account_indexes.len() as u8);
+            bytes.extend_from_slice(&ix.account_indexes);
+            bytes.extend_from_slice(&(ix.data.len() as u16).to_le_bytes());
+            bytes.extend_from_slice(&ix.data);
+        }
+        bytes.push(0);
+        bytes
+    }
+
+    /// Whether the compact form's one- and two-byte lengths can hold it.
+    pub fn fits(&self) -> bool {
+        self.account_keys.len() <= usize::from(u8::MAX)
+            && self.instructions.len() <= usize::from(u8::MAX)
+            && self.instructions.iter().all(|ix| ix.data.len() <= usize::from(u16::MAX))
+    }
+}
+
+/// The fields of a `Multisig` account this service reads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Multisig {
+    pub threshold: u16,
+    /// Index of the latest vault transaction; the next one is this plus one.
+    pub transaction_index: u64,
+}
+
+/// Decodes a `Multisig` account, or `None` if `data` isn't one.
+pub fn decode_multisig(data: &[u8]) -> Option<Multisig> {
+    let mut input = account(data, "Multisig")?;
+    // create_key and config_authority.
+    skip(&mut input, 64)?;
+    let threshold = u16::from_le_bytes(take(&mut input)?);
+    skip(&mut input, 4)?;
+    let transaction_index = u64::from_le_bytes(take(&mut input)?);
+    Some(Multisig { threshold, transaction_index })
+}
+
+/// The treasury creation fees go to, from the `ProgramConfig` account.
+pub fn decode_treasury(data: &[u8]) -> Option<Pubkey> {
+    let mut input = account(data, "ProgramConfig")?;
+    // authority and multisig_creation_fee.
+    skip(&mut input, 40)?;
+    Some(Pubkey::new_from_array(take(&mut input)?))
+}
+
+/// The message stored in a `VaultTransaction` account, or `None` if `data`
+/// isn't one or its message uses lookup tables.
+pub fn decode_vault_transaction(data: &[u8]) -> Option<VaultMessage> {
+    let mut input = account(data, "VaultTransaction")?;
+    // multisig, creator, index, bump, vault_index and vault_bump.
+    skip(&mut input, 32 + 32 + 8 + 3)?;
+    let bumps = len(&mut input)?;
+    skip(&mut input, bumps)?;
+
+    let [num_signers, num_writable_signers, num_writable_non_signers] = take(&mut input)?;
+    let account_keys = (0..len(&mut input)?)
+        .map(|_| take(&mut input).map(Pubkey::new_from_array))
+        .collect::<Option<Vec<_>>>()?;
+    let instructions = (0..len(&mut input)?)
+        .map(|_| {
+            let [program_id_index] = take(&mut input)?;
+            let account_indexes = bytes(&mut input)?;
+            let data = bytes(&mut input)?;
+            Some(VaultInstruction { program_id_index, account_indexes, data })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if len(&mut input)? != 0 {
+        return None;
+    }
+    Some(VaultMessage { num_signers, num_writable_signers, num_writable_non_signers, account_keys, instructions })
+}
+
+fn account<'a>(data: &'a [u8], name: &str) -> Option<&'a [u8]> {
+    data.strip_prefix(&sighash("account", name))
+}
+
+fn take<const N: usize>(input: &mut &[u8]) -> Option<[u8; N]> {
+    let (head, rest) = input.split_first_chunk::<N>()?;
+    *input = rest;
+    Some(*head)
+}
+
+fn skip(input: &mut &[u8], count: usize) -> Option<()> {
+    *input = input.get(count..)?;
+    Some(())
+}
+
+/// A Borsh `Vec` length.
+fn len(input: &mut &[u8]) -> Option<usize> {
+    usize::try_from(u32::from_le_bytes(take(input)?)).ok()
+}
+
+fn bytes(input: &mut &[u8]) -> Option<Vec<u8>> {
+    let len = len(input)?;
+    let bytes = input.get(..len)?.to_vec();
+    *input = &input[len..];
+    Some(bytes)
+}
+
+fn option_pubkey(data: &mut Vec<u8>, value: Option<Pubkey>) {
+    match value {
+        Some(key) => {
+            data.push(1);
+            data.extend_from_slice(key.as_ref());
+        }
+        None => data.push(0),
+    }
+}
+
+fn option_string(data: &mut Vec<u8>, value: Option<&str>) {
+    match value {
+        Some(text) => {
+            data.push(1);
+            data.extend_from_slice(&(text.len() as u32).to_le_bytes());
+            data.extend_from_slice(text.as_bytes());
+        }
+        None => data.push(0),
+    }
+}
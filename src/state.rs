@@ -0,0 +1,259 @@
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use solana_sdk::{pubkey::Pubkey, signer::keypair::Keypair};
+#[cfg(unix)]
+use tokio::signal::unix::{signal, SignalKind};
+
+use crate::address_book::AddressBook;
+use crate::anchor::IdlRegistry;
+use crate::approvals::Approvals;
+use crate::audit::AuditLog;
+use crate::blockhash::BlockhashCache;
+use crate::cache::AccountCache;
+use crate::config::{Config, ConfigError, ConfigHandle, FeaturesConfig, Mode};
+use crate::crypto::CryptoPool;
+use crate::das::{self, DasProvider};
+use crate::errors::{AppError, FieldError};
+use crate::jito::{self, BlockEngine};
+use crate::jobs::JobQueue;
+use crate::keystore::{KeyError, Keystore};
+use crate::metrics::Metrics;
+use crate::nft::NftMetadataCache;
+use crate::nonce_pool::NoncePool;
+use crate::policy::{Policy, PolicyError, Transfer};
+use crate::program_uploads::ProgramUploads;
+use crate::relayer::Relayer;
+use crate::reporting::ErrorReporter;
+use crate::rpc::{self, LatestBlockhash, RpcHandle, SolanaRpc};
+use crate::templates::TemplateStore;
+use crate::tenant;
+use crate::types::SignerRef;
+
+/// Everything handlers share, injected through axum's `State` extractor.
+/// Cheap to clone; every field is a handle.
+#[derive(Clone)]
+pub struct AppState {
+    pub config: ConfigHandle,
+    pub rpc: RpcHandle,
+    pub keystore: Arc<Keystore>,
+    pub crypto: Arc<CryptoPool>,
+    pub blockhash: Arc<BlockhashCache>,
+    pub accounts: Arc<AccountCache>,
+    pub nft: Arc<NftMetadataCache>,
+    pub metrics: Arc<Metrics>,
+    pub idls: Arc<IdlRegistry>,
+    /// Present when a relayer fee payer is configured.
+    pub relayer: Option<Arc<Relayer>>,
+    /// Present when Jito bundle submission is configured.
+    pub block_engine: Option<Arc<dyn BlockEngine>>,
+    /// Present when a DAS provider is configured.
+    pub das: Option<Arc<dyn DasProvider>>,
+    /// Present when a nonce pool authority is configured.
+    pub nonce_pool: Option<Arc<NoncePool>>,
+    pub jobs: Arc<JobQueue>,
+    pub policy: Arc<Policy>,
+    pub audit: Arc<AuditLog>,
+    pub approvals: Arc<Approvals>,
+    pub templates: Arc<TemplateStore>,
+    pub program_uploads: Arc<ProgramUploads>,
+    pub address_book: Arc<AddressBook>,
+    pub reporter: Arc<ErrorReporter>,
+    /// Identities whose API keys were revoked through `/admin`; kept across
+    /// config reloads.
+    pub revoked: Arc<RwLock<HashSet<String>>>,
+}
+
+impl AppState {
+    pub fn new(config: Config) -> Self {
+        let rpc = RpcHandle::new(rpc::connect(&config.rpc));
+        let blockhash = BlockhashCache::new(Duration::from_millis(config.rpc.blockhash_ttl_ms));
+        let accounts = AccountCache::new(rpc.clone(), &config.cache);
+        let nft = NftMetadataCache::new(&config.nft);
+        let keystore = Arc::new(Keystore::new());
+        let relayer = Relayer::load(&config.relayer, &keystore);
+        let nonce_pool = NoncePool::load(&config.nonce_pool, &keystore);
+        let block_engine = jito::connect(&config.jito, config.rpc.mock);
+        let das = das::connect(&config.das, config.rpc.mock);
+        let policy = Policy::new(config.policy.clone());
+        let crypto = CryptoPool::new(config.runtime.crypto_threads);
+
+        Self {
+            config: ConfigHandle::new(config),
+            rpc,
+            approvals: Arc::new(Approvals::new(keystore.clone())),
+            keystore,
+            crypto: Arc::new(crypto),
+            blockhash: Arc::new(blockhash),
+            accounts: Arc::new(accounts),
+            nft: Arc::new(nft),
+            metrics: Arc::new(Metrics::new()),
+            idls: Arc::new(IdlRegistry::new()),
+            relayer: relayer.map(Arc::new),
+            block_engine,
+            das,
+            nonce_pool: nonce_pool.map(Arc::new),
+            jobs: Arc::new(JobQueue::new()),
+            policy: Arc::new(policy),
+            audit: Arc::new(AuditLog::new()),
+            templates: Arc::new(TemplateStore::new()),
+            program_uploads: Arc::new(ProgramUploads::new()),
+            address_book: Arc::new(AddressBook::new()),
+            reporter: Arc::new(ErrorReporter::new()),
+            revoked: Arc::default(),
+        }
+    }
+
+    /// Re-reads the config file and environment and installs the result.
+    /// Policy rules and the RPC endpoint change with it, dropping what the
+    /// old endpoint cached; runtime, listener, cache, relayer, DAS and Jito settings
+    /// keep their startup values. An invalid config is rejected and the
+    /// active one kept.
+    pub async fn reload(&self) -> Result<Arc<Config>, ConfigError> {
+        let config = Config::load()?;
+        self.policy.set_config(config.policy.clone());
+        let previous = self.config.replace(config);
+        let config = self.config();
+        if previous.rpc.url != config.rpc.url || previous.rpc.mock != config.rpc.mock {
+            self.rpc.replace(rpc::connect(&config.rpc));
+            self.blockhash.invalidate().await;
+            self.accounts.invalidate_all();
+        }
+        Ok(config)
+    }
+
+    /// Reloads the configuration, like `/admin/reload`, whenever the process
+    /// receives SIGHUP. The handler is installed before this returns.
+    #[cfg(unix)]
+    pub fn reload_on_hangup(&self) -> std::io::Result<()> {
+        let mut hangups = signal(SignalKind::hangup())?;
+        let state = self.clone();
+        tokio::spawn(async move {
+            while hangups.recv().await.is_some() {
+                let outcome = state.reload().await.map_err(AppError::from);
+                state.audit.record("admin.reload", &[], outcome.as_ref().map(|_| ()));
+                match outcome {
+                    Ok(_) => eprintln!("configuration reloaded on SIGHUP"),
+                    Err(err) => eprintln!("SIGHUP reload rejected, keeping the active configuration: {err}"),
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Snapshot of the active configuration; hold it for the whole request
+    /// so a concurrent reload can't mix old and new settings.
+    pub fn config(&self) -> Arc<Config> {
+        self.config.get()
+    }
+
+    /// `FeatureDisabled` unless `enabled` picks a switched-on feature.
+    pub fn require_feature(
+        &self,
+        name: &'static str,
+        enabled: impl FnOnce(&FeaturesConfig) -> bool,
+    ) -> Result<(), AppError> {
+        if enabled(&self.config().features) { Ok(()) } else { Err(AppError::FeatureDisabled(name)) }
+    }
+
+    /// The active RPC backend, or `RpcUnavailable` for deployments running
+    /// only the offline builders.
+    pub fn rpc(&self) -> Result<Arc<dyn SolanaRpc>, AppError> {
+        self.rpc.get().ok_or(AppError::RpcUnavailable)
+    }
+
+    pub fn relayer(&self) -> Result<Arc<Relayer>, AppError> {
+        self.require_feature("relayer", |features| features.relayer)?;
+        self.relayer.clone().ok_or(AppError::RelayerUnavailable)
+    }
+
+    pub fn nonce_pool(&self) -> Result<Arc<NoncePool>, AppError> {
+        self.nonce_pool.clone().ok_or(AppError::NoncePoolUnavailable)
+    }
+
+    pub fn block_engine(&self) -> Result<Arc<dyn BlockEngine>, AppError> {
+        self.require_feature("bundles", |features| features.bundles)?;
+        self.block_engine.clone().ok_or(AppError::BlockEngineUnavailable)
+    }
+
+    pub fn das(&self) -> Result<Arc<dyn DasProvider>, AppError> {
+        self.das.clone().ok_or(AppError::DasUnavailable)
+    }
+
+    /// Resolves the signer named by request field `field`. Raw secrets are
+    /// refused in production mode, where keys must already be in the keystore.
+    pub fn signer(&self, field: String, signer: SignerRef) -> Result<Arc<Keypair>, AppError> {
+        match signer {
+            SignerRef::Secret(_) if self.config().mode == Mode::Production => {
+                Err(AppError::Field { field, error: FieldError::RawSecretDisabled })
+            }
+            SignerRef::Secret(secret) => Ok(Arc::new(secret.0)),
+            SignerRef::KeyId(key_id) => self.keystore.get_in(&tenant::current(), &key_id).map_err(|error| {
+                let error = match error {
+                    KeyError::Unknown => FieldError::UnknownKeyId,
+                    KeyError::Expired(expires_at) => FieldError::KeyExpired(expires_at),
+                };
+                AppError::Field { field, error }
+            }),
+        }
+    }
+
+    /// `signer` for each entry of a `signers` list.
+    pub fn signers(&self, signers: Vec<SignerRef>) -> Result<Vec<Arc<Keypair>>, AppError> {
+        signers
+            .into_iter()
+            .enumerate()
+            .map(|(i, signer)| self.signer(format!("signers[{i}]"), signer))
+            .collect()
+    }
+
+    /// Applies the full transfer policy to an operation about to sign or send
+    /// for `signers`, recording the decision in the audit log.
+    pub fn enforce_policy(
+        &self,
+        action: &'static str,
+        signers: &[Pubkey],
+        transfers: &[Transfer],
+    ) -> Result<(), AppError> {
+        self.audited(action, signers, self.policy.enforce(transfers))
+    }
+
+    /// `enforce_policy` for a transfer that collected its approvals.
+    pub fn enforce_approved_policy(
+        &self,
+        action: &'static str,
+        signers: &[Pubkey],
+        transfers: &[Transfer],
+    ) -> Result<(), AppError> {
+        self.audited(action, signers, self.policy.enforce_approved(transfers))
+    }
+
+    /// Like `enforce_policy` but only the rules that don't depend on usage,
+    /// for operations that will sign later.
+    pub fn review_policy(
+        &self,
+        action: &'static str,
+        signers: &[Pubkey],
+        transfers: &[Transfer],
+    ) -> Result<(), AppError> {
+        self.audited(action, signers, self.policy.review(transfers))
+    }
+
+    fn audited(
+        &self,
+        action: &'static str,
+        signers: &[Pubkey],
+        outcome: Result<(), PolicyError>,
+    ) -> Result<(), AppError> {
+        let outcome = outcome.map_err(AppError::from);
+        self.audit.record(action, signers, outcome.as_ref().map(|_| ()));
+        outcome
+    }
+
+    /// Recent blockhash for transaction builders, served from the shared cache.
+    pub async fn latest_blockhash(&self) -> Result<LatestBlockhash, AppError> {
+        let rpc = self.rpc()?;
+        Ok(self.blockhash.get(rpc.as_ref()).await?)
+    }
+}
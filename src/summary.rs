@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+
+use solana_sdk::{pubkey, pubkey::Pubkey, signature::Signature};
+
+use crate::amount;
+use crate::models::transaction::{SolChange, TokenChange, TransactionSummary};
+use crate::rpc::{ConfirmedTransaction, TokenBalance};
+use crate::solana_pay::MEMO_PROGRAM_ID;
+
+/// The original SPL Memo program, still used by older wallets and exchanges.
+pub const MEMO_V1_PROGRAM_ID: Pubkey = pubkey!("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+
+/// Summarizes `confirmed` into per-account SOL and per-owner token deltas.
+/// Accounts whose balance didn't change are left out.
+pub fn summarize(signature: &Signature, confirmed: &ConfirmedTransaction) -> TransactionSummary {
+    let message = &confirmed.transaction.message;
+    let meta = &confirmed.meta;
+    let keys: Vec<Pubkey> = message
+        .static_account_keys()
+        .iter()
+        .chain(&meta.loaded_writable)
+        .chain(&meta.loaded_readonly)
+        .copied()
+        .collect();
+
+    let sol_changes = keys
+        .iter()
+        .zip(meta.pre_balances.iter().zip(&meta.post_balances))
+        .filter(|(_, (pre, post))| pre != post)
+        .map(|(account, (&pre, &post))| SolChange {
+            account: account.to_string(),
+            pre: pre.to_string(),
+            post: post.to_string(),
+            change: (i128::from(post) - i128::from(pre)).to_string(),
+        })
+        .collect();
+
+    let memos = message
+        .instructions()
+        .iter()
+        .filter(|instruction| {
+            keys.get(usize::from(instruction.program_id_index))
+                .is_some_and(|program| *program == MEMO_PROGRAM_ID || *program == MEMO_V1_PROGRAM_ID)
+        })
+        .map(|instruction| String::from_utf8_lossy(&instruction.data).into_owned())
+        .collect();
+
+    TransactionSummary {
+        signature: signature.to_string(),
+        slot: confirmed.slot,
+        block_time: confirmed.block_time,
+        status: if meta.err.is_none() { "success" } else { "failed" }.to_string(),
+        error: meta.err.clone(),
+        fee_payer: keys.first().map(Pubkey::to_string).unwrap_or_default(),
+        fee: meta.fee.to_string(),
+        sol_changes,
+        token_changes: token_changes(&meta.pre_token_balances, &meta.post_token_balances),
+        memos,
+    }
+}
+
+/// Token accounts created or closed in the transaction only appear on one
+/// side, so a missing balance counts as zero.
+fn token_changes(pre: &[TokenBalance], post: &[TokenBalance]) -> Vec<TokenChange> {
+    let mut net: BTreeMap<(Option<Pubkey>, Pubkey), (u8, i128)> = BTreeMap::new();
+    for (balances, sign) in [(pre, -1), (post, 1)] {
+        for balance in balances {
+            let entry = net.entry((balance.owner, balance.mint)).or_insert((balance.decimals, 0));
+            entry.1 += sign * i128::from(balance.amount);
+        }
+    }
+
+    net.into_iter()
+        .filter(|(_, (_, change))| *change != 0)
+        .map(|((owner, mint), (decimals, change))| TokenChange {
+            owner: owner.map(|owner| owner.to_string()),
+            mint: mint.to_string(),
+            decimals,
+            change: change.to_string(),
+            // Only absurd decimals fail; fall back to the raw amount.
+            ui_change: amount::change_to_ui(change, decimals).map_or_else(|_| change.to_string(), |ui| ui.to_string()),
+        })
+        .collect()
+}
@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use base64::{Engine as _, engine::general_purpose};
+use chrono::Utc;
+use serde_json::{Map, Value};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use uuid::Uuid;
+
+use crate::borsh::{Schema, Type};
+use crate::errors::AppError;
+use crate::models::templates::{StoredTemplate, TemplateParameter, TemplateRequest};
+use crate::tenant;
+
+/// Instruction templates saved through `/templates`, visible to their
+/// tenant only. Kept in memory like jobs and proposals.
+#[derive(Default)]
+pub struct TemplateStore {
+    templates: Mutex<HashMap<String, Entry>>,
+}
+
+struct Entry {
+    tenant: String,
+    template: StoredTemplate,
+}
+
+impl TemplateStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Saves a template that passed `check` for the current tenant.
+    pub fn insert(&self, template: TemplateRequest) -> StoredTemplate {
+        let now = Utc::now();
+        let stored = StoredTemplate { id: Uuid::new_v4().to_string(), template, created_at: now, updated_at: now };
+        let entry = Entry { tenant: tenant::current(), template: stored.clone() };
+        self.templates.lock().unwrap().insert(stored.id.clone(), entry);
+        stored
+    }
+
+    /// The current tenant's templates, oldest first.
+    pub fn list(&self) -> Vec<StoredTemplate> {
+        let templates = self.templates.lock().unwrap();
+        let mut list: Vec<StoredTemplate> = templates
+            .values()
+            .filter(|entry| entry.tenant == tenant::current())
+            .map(|entry| entry.template.clone())
+            .collect();
+        list.sort_by_key(|template| template.created_at);
+        list
+    }
+
+    pub fn get(&self, id: &str) -> Result<StoredTemplate, AppError> {
+        let templates = self.templates.lock().unwrap();
+        templates
+            .get(id)
+            .filter(|entry| entry.tenant == tenant::current())
+            .map(|entry| entry.template.clone())
+            .ok_or_else(|| AppError::NotFound(format!("Template {id}")))
+    }
+
+    /// Replaces one of the current tenant's templates, keeping its id.
+    pub fn update(&self, id: &str, template: TemplateRequest) -> Result<StoredTemplate, AppError> {
+        let mut templates = self.templates.lock().unwrap();
+        let entry = templates
+            .get_mut(id)
+            .filter(|entry| entry.tenant == tenant::current())
+            .ok_or_else(|| AppError::NotFound(format!("Template {id}")))?;
+        entry.template.template = template;
+        entry.template.updated_at = Utc::now();
+        Ok(entry.template.clone())
+    }
+
+    pub fn remove(&self, id: &str) -> Result<StoredTemplate, AppError> {
+        let mut templates = self.templates.lock().unwrap();
+        match templates.get(id) {
+            Some(entry) if entry.tenant == tenant::current() => Ok(templates.remove(id).unwrap().template),
+            _ => Err(AppError::NotFound(format!("Template {id}"))),
+        }
+    }
+}
+
+/// The parameter name in a string that is exactly `{{name}}`.
+pub fn placeholder(text: &str) -> Option<&str> {
+    let name = text.strip_prefix("{{")?.strip_suffix("}}")?.trim();
+    (!name.is_empty()).then_some(name)
+}
+
+fn invalid(field: String, message: impl Into<String>) -> AppError {
+    AppError::InvalidField { field, message: message.into() }
+}
+
+/// Rejects templates that could never render: undeclared or mistyped
+/// placeholders, bad schemas, defaults that don't fit their type.
+pub fn check(template: &TemplateRequest) -> Result<(), AppError> {
+    let mut declared = HashMap::new();
+    for (index, parameter) in template.parameters.iter().enumerate() {
+        let field = format!("parameters[{index}]");
+        if declared.insert(parameter.name.as_str(), &parameter.ty).is_some() {
+            return Err(invalid(format!("{field}.name"), format!("`{}` is declared twice", parameter.name)));
+        }
+        if let Some(default) = &parameter.default {
+            Schema::default().encode(&parameter.ty, default, &format!("{field}.default"), &mut Vec::new())?;
+        }
+    }
+
+    for (index, instruction) in template.instructions.iter().enumerate() {
+        let field = format!("instructions[{index}]");
+        for (position, account) in instruction.accounts.iter().enumerate() {
+            let field = format!("{field}.accounts[{position}].pubkey");
+            match placeholder(&account.pubkey) {
+                Some(name) if declared.get(name) == Some(&&Type::Pubkey) => {}
+                Some(name) => return Err(invalid(field, format!("`{name}` is not a declared pubkey parameter"))),
+                None if Pubkey::from_str(&account.pubkey).is_ok() => {}
+                None => return Err(invalid(field, "expected a base58 public key or {{parameter}}")),
+            }
+        }
+        general_purpose::STANDARD.decode(&instruction.data).map_err(|_| invalid(format!("{field}.data"), "expected base64"))?;
+        if let Some(args) = &instruction.args {
+            Schema::from_decls(args.schema.types.clone())
+                .map_err(|message| invalid(format!("{field}.args.schema.types"), message))?;
+            let mut used = HashSet::new();
+            placeholders(&args.value, &mut used);
+            if let Some(name) = used.into_iter().find(|name| !declared.contains_key(name)) {
+                return Err(invalid(format!("{field}.args.value"), format!("`{name}` is not a declared parameter")));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn placeholders<'a>(value: &'a Value, used: &mut HashSet<&'a str>) {
+    match value {
+        Value::String(text) => used.extend(placeholder(text)),
+        Value::Array(items) => items.iter().for_each(|item| placeholders(item, used)),
+        Value::Object(fields) => fields.values().for_each(|item| placeholders(item, used)),
+        _ => {}
+    }
+}
+
+/// Each declared parameter's value, type-checked, from `given` or its
+/// default.
+fn resolve<'a>(
+    parameters: &'a [TemplateParameter],
+    given: &'a Map<String, Value>,
+) -> Result<HashMap<&'a str, &'a Value>, AppError> {
+    if let Some(name) = given.keys().find(|name| !parameters.iter().any(|parameter| parameter.name == **name)) {
+        return Err(invalid(format!("parameters.{name}"), "not a parameter of this template"));
+    }
+    parameters
+        .iter()
+        .map(|parameter| {
+            let field = format!("parameters.{}", parameter.name);
+            let value = given
+                .get(&parameter.name)
+                .or(parameter.default.as_ref())
+                .ok_or_else(|| invalid(field.clone(), "missing"))?;
+            Schema::default().encode(&parameter.ty, value, &field, &mut Vec::new())?;
+            Ok((parameter.name.as_str(), value))
+        })
+        .collect()
+}
+
+fn substitute(value: &Value, values: &HashMap<&str, &Value>) -> Value {
+    match value {
+        Value::String(text) => match placeholder(text).and_then(|name| values.get(name)) {
+            Some(value) => (*value).clone(),
+            None => value.clone(),
+        },
+        Value::Array(items) => Value::Array(items.iter().map(|item| substitute(item, values)).collect()),
+        Value::Object(fields) => {
+            Value::Object(fields.iter().map(|(key, item)| (key.clone(), substitute(item, values))).collect())
+        }
+        _ => value.clone(),
+    }
+}
+
+/// Fills `given` parameters into a template that passed `check`.
+pub fn render(template: &TemplateRequest, given: &Map<String, Value>) -> Result<Vec<Instruction>, AppError> {
+    let values = resolve(&template.parameters, given)?;
+    template
+        .instructions
+        .iter()
+        .enumerate()
+        .map(|(index, instruction)| {
+            let field = format!("instructions[{index}]");
+            let accounts = instruction
+                .accounts
+                .iter()
+                .map(|account| {
+                    let key = match placeholder(&account.pubkey) {
+                        Some(name) => values.get(name).and_then(|value| value.as_str()).unwrap_or_default(),
+                        None => &account.pubkey,
+                    };
+                    let pubkey = Pubkey::from_str(key)
+                        .map_err(|_| AppError::Internal(format!("{field} has an unchecked account key")))?;
+                    Ok(AccountMeta { pubkey, is_signer: account.is_signer, is_writable: account.is_writable })
+                })
+                .collect::<Result<Vec<_>, AppError>>()?;
+
+            let mut data = general_purpose::STANDARD
+                .decode(&instruction.data)
+                .map_err(|_| invalid(format!("{field}.data"), "expected base64"))?;
+            if let Some(args) = &instruction.args {
+                let schema = Schema::from_decls(args.schema.types.clone())
+                    .map_err(|message| invalid(format!("{field}.args.schema.types"), message))?;
+                let value = substitute(&args.value, &values);
+                schema.encode(&args.schema.ty, &value, &format!("{field}.args.value"), &mut data)?;
+            }
+            Ok(Instruction { program_id: *instruction.program_id, accounts, data })
+        })
+        .collect()
+}
@@ -0,0 +1,37 @@
+use std::future::Future;
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::extract::Identity;
+use crate::state::AppState;
+
+/// Tenant of anonymous callers and of identities no tenant lists.
+pub const DEFAULT_TENANT: &str = "default";
+
+tokio::task_local! {
+    static TENANT: String;
+}
+
+/// Tenant of the request or job being handled; `DEFAULT_TENANT` outside one.
+pub fn current() -> String {
+    TENANT.try_with(String::clone).unwrap_or_else(|_| DEFAULT_TENANT.to_string())
+}
+
+/// Runs `future` on behalf of `tenant`.
+pub async fn scope<F: Future>(tenant: String, future: F) -> F::Output {
+    TENANT.scope(tenant, future).await
+}
+
+/// Middleware that handles each request on behalf of its caller's tenant.
+pub async fn assign(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let (parts, body) = request.into_parts();
+    let tenant = match Identity::resolve(&parts, &state) {
+        Some(Identity(name)) => state.config().tenant_of(&name).to_string(),
+        None => DEFAULT_TENANT.to_string(),
+    };
+    scope(tenant, next.run(Request::from_parts(parts, body))).await
+}
@@ -0,0 +1,140 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{extract::Request, Router};
+use hyper::body::Incoming;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::{conn::auto, graceful::GracefulShutdown},
+    service::TowerToHyperService,
+};
+use thiserror::Error;
+use tokio::net::TcpListener;
+use tokio_rustls::{
+    rustls::{
+        self,
+        crypto::ring,
+        pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+        server::{VerifierBuilderError, WebPkiClientVerifier},
+        RootCertStore, ServerConfig,
+    },
+    TlsAcceptor,
+};
+use tower::ServiceExt;
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+use crate::config::TlsConfig;
+
+/// Subject common name of the verified client certificate the request's
+/// connection presented. Inserted as a request extension by `serve`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientSubject(pub String);
+
+#[derive(Error, Debug)]
+pub enum TlsError {
+    #[error("failed to load {path}: {source}")]
+    Pem {
+        path: String,
+        source: rustls::pki_types::pem::Error,
+    },
+    #[error("no certificates in {0}")]
+    Empty(String),
+    #[error("invalid client CA bundle: {0}")]
+    ClientCa(#[from] VerifierBuilderError),
+    #[error(transparent)]
+    Rustls(#[from] rustls::Error),
+}
+
+/// Builds the listener's TLS settings, or `None` when no certificate is
+/// configured. Client certificates are required when `client_ca` is set.
+pub fn server_config(config: &TlsConfig) -> Result<Option<Arc<ServerConfig>>, TlsError> {
+    let (Some(cert), Some(key)) = (&config.cert, &config.key) else {
+        return Ok(None);
+    };
+    let chain = certificates(cert)?;
+    let key = PrivateKeyDer::from_pem_file(key).map_err(|source| TlsError::Pem { path: key.clone(), source })?;
+
+    let provider = Arc::new(ring::default_provider());
+    let builder = ServerConfig::builder_with_provider(provider.clone()).with_safe_default_protocol_versions()?;
+    let builder = match &config.client_ca {
+        Some(path) => {
+            let mut roots = RootCertStore::empty();
+            for cert in certificates(path)? {
+                roots.add(cert)?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider).build()?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let mut tls = builder.with_single_cert(chain, key)?;
+    tls.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(Some(Arc::new(tls)))
+}
+
+fn certificates(path: &str) -> Result<Vec<CertificateDer<'static>>, TlsError> {
+    let pem = |source| TlsError::Pem { path: path.to_string(), source };
+    let certs = CertificateDer::pem_file_iter(path).map_err(pem)?.collect::<Result<Vec<_>, _>>().map_err(pem)?;
+    if certs.is_empty() {
+        return Err(TlsError::Empty(path.to_string()));
+    }
+    Ok(certs)
+}
+
+/// `axum::serve` over TLS. Connections that fail the handshake, including
+/// those without an acceptable client certificate, are dropped on their own.
+/// Once `shutdown` resolves no more are accepted, and in-flight requests get
+/// up to `drain` to finish, as with `listener::serve`.
+pub async fn serve(
+    listener: TcpListener,
+    tls: Arc<ServerConfig>,
+    app: Router,
+    shutdown: impl Future<Output = ()>,
+    drain: Duration,
+) {
+    let acceptor = TlsAcceptor::from(tls);
+    let graceful = GracefulShutdown::new();
+    tokio::pin!(shutdown);
+    loop {
+        let stream = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                // Typically out of file descriptors; back off rather than spin.
+                Err(_) => {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            },
+            _ = &mut shutdown => break,
+        };
+        let acceptor = acceptor.clone();
+        let app = app.clone();
+        let watcher = graceful.watcher();
+
+        tokio::spawn(async move {
+            let Ok(stream) = acceptor.accept(stream).await else {
+                return;
+            };
+            let subject = stream.get_ref().1.peer_certificates().and_then(|chain| chain.first()).and_then(common_name);
+            let app = app.map_request(move |mut request: Request<Incoming>| {
+                if let Some(subject) = &subject {
+                    request.extensions_mut().insert(ClientSubject(subject.clone()));
+                }
+                request
+            });
+            let builder = auto::Builder::new(TokioExecutor::new());
+            let connection = builder.serve_connection(TokioIo::new(stream), TowerToHyperService::new(app));
+            let _ = watcher.watch(connection).await;
+        });
+    }
+    drop(listener);
+    let _ = tokio::time::timeout(drain, graceful.shutdown()).await;
+}
+
+fn common_name(cert: &CertificateDer) -> Option<String> {
+    let (_, cert) = X509Certificate::from_der(cert).ok()?;
+    let name = cert.subject().iter_common_name().next()?;
+    name.as_str().ok().map(str::to_string)
+}
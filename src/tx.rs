@@ -0,0 +1,147 @@
+use std::ops::Range;
+
+use base64::{Engine as _, engine::general_purpose};
+use serde::Serialize;
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    message::{Message, VersionedMessage},
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+    transaction::{Transaction, VersionedTransaction},
+};
+use crate::errors::AppError;
+
+/// Largest serialized transaction, signatures included, that fits in one packet.
+pub const MAX_TRANSACTION_SIZE: usize = PACKET_DATA_SIZE;
+
+/// One transaction produced by `pack`, covering `groups` of the input.
+pub struct Packed {
+    pub transaction: Transaction,
+    pub groups: Range<usize>,
+    pub size: usize,
+}
+
+pub fn unsigned(instructions: &[Instruction], payer: &Pubkey, blockhash: Hash) -> Transaction {
+    Transaction::new_unsigned(Message::new_with_blockhash(instructions, Some(payer), &blockhash))
+}
+
+/// Wire size including placeholder signatures for every required signer.
+pub fn serialized_size(transaction: &Transaction) -> usize {
+    bincode::serialized_size(transaction).expect("transactions serialize") as usize
+}
+
+/// Base64 of the bincode wire format, as accepted by wallets and `sendTransaction`.
+pub fn encode(transaction: &impl Serialize) -> String {
+    general_purpose::STANDARD.encode(bincode::serialize(transaction).expect("transactions serialize"))
+}
+
+/// Parses and sanity-checks a base64 wire-format transaction, legacy or
+/// versioned, reporting failures against `field`.
+pub fn decode(encoded: &str, field: &str) -> Result<VersionedTransaction, AppError> {
+    let invalid = |message: &str| AppError::InvalidField { field: field.to_string(), message: message.to_string() };
+    let bytes = general_purpose::STANDARD.decode(encoded).map_err(|_| invalid("expected base64"))?;
+    let transaction: VersionedTransaction =
+        bincode::deserialize(&bytes).map_err(|_| invalid("not a serialized transaction"))?;
+    // Indices and signature count are trusted from here on.
+    transaction.sanitize().map_err(|err| invalid(&format!("malformed transaction: {err}")))?;
+    Ok(transaction)
+}
+
+/// `decode` for bare message bytes, as handed out for detached signing.
+/// Only the canonical encoding is accepted, so a signature over the input
+/// is a signature over the message that was checked.
+pub fn decode_message(encoded: &str, field: &str) -> Result<VersionedMessage, AppError> {
+    let invalid = |message: &str| AppError::InvalidField { field: field.to_string(), message: message.to_string() };
+    let bytes = general_purpose::STANDARD.decode(encoded).map_err(|_| invalid("expected base64"))?;
+    let message: VersionedMessage = bincode::deserialize(&bytes).map_err(|_| invalid("not a serialized message"))?;
+    message.sanitize().map_err(|err| invalid(&format!("malformed message: {err}")))?;
+    if message.serialize() != bytes {
+        return Err(invalid("trailing or non-canonical bytes"));
+    }
+    Ok(message)
+}
+
+/// Whether `bytes` start with a valid transaction message, so a signature
+/// over them could authorize a transaction.
+pub fn is_message(bytes: &[u8]) -> bool {
+    bincode::deserialize::<VersionedMessage>(bytes).is_ok_and(|message| message.sanitize().is_ok())
+}
+
+/// Greedily packs instruction groups into as few transactions as fit under
+/// the packet size limit. A group is never split across transactions, so
+/// e.g. an ATA creation always lands next to the transfer that needs it.
+pub fn pack(groups: Vec<Vec<Instruction>>, payer: &Pubkey, blockhash: Hash) -> Result<Vec<Packed>, AppError> {
+    packer(groups.into_iter().map(Ok), payer, blockhash).collect()
+}
+
+/// `pack`, one transaction at a time: groups are only pulled from `groups`
+/// as the next transaction needs them, so a caller streaming the result
+/// never holds more than one transaction's worth. Stops after the first
+/// error, whether from `groups` or an oversized group.
+pub fn packer<I>(groups: I, payer: &Pubkey, blockhash: Hash) -> Packer<I::IntoIter>
+where
+    I: IntoIterator<Item = Result<Vec<Instruction>, AppError>>,
+{
+    Packer { groups: groups.into_iter(), payer: *payer, blockhash, current: Vec::new(), start: 0, seen: 0, done: false }
+}
+
+pub struct Packer<I> {
+    groups: I,
+    payer: Pubkey,
+    blockhash: Hash,
+    current: Vec<Instruction>,
+    start: usize,
+    seen: usize,
+    done: bool,
+}
+
+impl<I: Iterator<Item = Result<Vec<Instruction>, AppError>>> Iterator for Packer<I> {
+    type Item = Result<Packed, AppError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let (payer, blockhash) = (self.payer, self.blockhash);
+
+        for group in self.groups.by_ref() {
+            let index = self.seen;
+            self.seen += 1;
+            let group = match group {
+                Ok(group) => group,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            };
+
+            let mut candidate = self.current.clone();
+            candidate.extend(group.iter().cloned());
+            if serialized_size(&unsigned(&candidate, &payer, blockhash)) <= MAX_TRANSACTION_SIZE {
+                self.current = candidate;
+                continue;
+            }
+
+            if self.current.is_empty() || serialized_size(&unsigned(&group, &payer, blockhash)) > MAX_TRANSACTION_SIZE {
+                self.done = true;
+                return Some(Err(AppError::InvalidInput(format!(
+                    "instructions for item {index} exceed the {MAX_TRANSACTION_SIZE}-byte transaction limit"
+                ))));
+            }
+
+            let packed = seal(&self.current, &payer, blockhash, self.start..index);
+            self.current = group;
+            self.start = index;
+            return Some(Ok(packed));
+        }
+
+        self.done = true;
+        (!self.current.is_empty()).then(|| Ok(seal(&self.current, &payer, blockhash, self.start..self.seen)))
+    }
+}
+
+fn seal(instructions: &[Instruction], payer: &Pubkey, blockhash: Hash, groups: Range<usize>) -> Packed {
+    let transaction = unsigned(instructions, payer, blockhash);
+    Packed { size: serialized_size(&transaction), transaction, groups }
+}
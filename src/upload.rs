@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use axum::{body::Bytes, extract::Multipart};
+use base64::{Engine as _, engine::general_purpose};
+use crate::errors::{AppError, FieldError};
+use crate::models::airdrop::{AirdropRecipient, BulkAirdropRequest};
+use crate::models::program::{ProgramUploadRequest, WriteBufferRequest};
+use crate::types::{HashStr, PubkeyStr};
+use crate::utils::{parse_hash, parse_pubkey};
+use crate::validation::{recipient_errors, Violations};
+
+/// Multipart part holding the CSV file on bulk endpoints.
+pub const FILE_FIELD: &str = "file";
+
+/// A `multipart/form-data` submission: one CSV file plus plain text fields
+/// carrying the rest of the request (payer, mint, column mapping, ...).
+pub struct Upload {
+    fields: HashMap<String, String>,
+    file: Option<Bytes>,
+}
+
+/// Bulk request types that can also be submitted as a CSV upload.
+pub trait FromUpload: Sized {
+    fn from_upload(upload: Upload) -> Result<Self, AppError>;
+}
+
+impl Upload {
+    pub async fn read(mut multipart: Multipart) -> Result<Self, AppError> {
+        let mut fields = HashMap::new();
+        let mut file = None;
+
+        while let Some(part) = multipart
+            .next_field()
+            .await
+            .map_err(|rejection| AppError::InvalidBody(rejection.body_text()))?
+        {
+            let name = part.name().unwrap_or_default().to_string();
+            let bytes = part
+                .bytes()
+                .await
+                .map_err(|rejection| AppError::InvalidBody(rejection.body_text()))?;
+
+            if name == FILE_FIELD {
+                file = Some(bytes);
+            } else {
+                let value = String::from_utf8(bytes.to_vec()).map_err(|_| AppError::InvalidField {
+                    field: name.clone(),
+                    message: "value must be UTF-8 text".to_string(),
+                })?;
+                fields.insert(name, value.trim().to_string());
+            }
+        }
+
+        Ok(Self { fields, file })
+    }
+
+    /// A text field parsed with one of the `utils::parse_*` helpers.
+    pub fn field<T>(&self, name: &str, parse: impl Fn(&str) -> Result<T, FieldError>) -> Result<Option<T>, AppError> {
+        match self.fields.get(name).filter(|value| !value.is_empty()) {
+            Some(value) => parse(value)
+                .map(Some)
+                .map_err(|error| AppError::Field { field: name.to_string(), error }),
+            None => Ok(None),
+        }
+    }
+
+    pub fn require<T>(&self, name: &str, parse: impl Fn(&str) -> Result<T, FieldError>) -> Result<T, AppError> {
+        self.field(name, parse)?
+            .ok_or_else(|| AppError::MissingField { field: name.to_string() })
+    }
+
+    pub fn flag(&self, name: &str) -> Result<bool, AppError> {
+        Ok(self.field(name, parse_bool)?.unwrap_or(false))
+    }
+
+    /// The uploaded file's bytes.
+    pub fn file(&self) -> Result<&Bytes, AppError> {
+        self.file.as_ref().ok_or_else(|| AppError::MissingField { field: FILE_FIELD.to_string() })
+    }
+
+    /// The uploaded file as CSV. `columns` are the logical fields the caller
+    /// needs; clients may rename them with a `<field>_column` text field when
+    /// their spreadsheet uses different headers.
+    pub fn csv(&self, columns: &[&'static str]) -> Result<CsvTable, AppError> {
+        let file = self.file()?;
+        let invalid = |message: String| AppError::InvalidField { field: FILE_FIELD.to_string(), message };
+
+        let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(file.as_ref());
+        let headers = reader.headers().map_err(|e| invalid(e.to_string()))?.clone();
+
+        let mut indices = HashMap::new();
+        for &column in columns {
+            let header = self
+                .fields
+                .get(&format!("{column}_column"))
+                .map_or(column, String::as_str);
+            let index = headers
+                .iter()
+                .position(|candidate| candidate.eq_ignore_ascii_case(header))
+                .ok_or_else(|| invalid(format!("missing column `{header}`")))?;
+            indices.insert(column, index);
+        }
+
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            let record = record.map_err(|e| invalid(e.to_string()))?;
+            let line = record.position().map_or(0, |position| position.line() as usize);
+            let values = indices
+                .iter()
+                .map(|(&column, &index)| (column, record.get(index).unwrap_or_default().to_string()))
+                .collect();
+            rows.push(CsvRow { line, values });
+        }
+
+        Ok(CsvTable { rows })
+    }
+}
+
+pub struct CsvTable {
+    pub rows: Vec<CsvRow>,
+}
+
+/// One record, keyed by logical column name.
+pub struct CsvRow {
+    /// 1-based line in the file; the header is line 1.
+    pub line: usize,
+    values: HashMap<&'static str, String>,
+}
+
+impl CsvRow {
+    pub fn get(&self, column: &str) -> &str {
+        self.values.get(column).map_or("", String::as_str)
+    }
+}
+
+/// Text fields `payer`, `mint`, `recent_blockhash` and `dry_run` mirror the
+/// JSON body; the file needs `recipient` and `amount` columns.
+impl FromUpload for BulkAirdropRequest {
+    fn from_upload(upload: Upload) -> Result<Self, AppError> {
+        let payer = PubkeyStr(upload.require("payer", parse_pubkey)?);
+        let mint = upload.field("mint", parse_pubkey)?.map(PubkeyStr);
+        let recent_blockhash = upload.field("recent_blockhash", parse_hash)?.map(HashStr);
+        let dry_run = upload.flag("dry_run")?;
+
+        let table = upload.csv(&["recipient", "amount"])?;
+        let mut violations = Violations::default();
+        let mut recipients = Vec::with_capacity(table.rows.len());
+
+        for row in &table.rows {
+            let recipient = parse_pubkey(row.get("recipient"));
+            let amount = row.get("amount").parse::<u64>().map_err(|_| FieldError::InvalidAmount);
+            if let Err(error) = &recipient {
+                violations.row(row.line, "recipient", error.clone());
+            }
+            if let Err(error) = &amount {
+                violations.row(row.line, "amount", error.clone());
+            }
+
+            if let (Ok(recipient), Ok(amount)) = (recipient, amount) {
+                let entry = AirdropRecipient { recipient: PubkeyStr(recipient), amount };
+                for (field, error) in recipient_errors(&payer, &entry) {
+                    violations.row(row.line, field, error);
+                }
+                recipients.push(entry);
+            }
+        }
+        violations.into_result()?;
+
+        Ok(BulkAirdropRequest { payer, mint, recipients, recent_blockhash, dry_run })
+    }
+}
+
+/// Text fields `payer`, `buffer` and `authority` mirror the JSON body; the
+/// file is the program's `.so`, uploaded as is.
+impl FromUpload for WriteBufferRequest {
+    fn from_upload(upload: Upload) -> Result<Self, AppError> {
+        Ok(WriteBufferRequest {
+            payer: PubkeyStr(upload.require("payer", parse_pubkey)?),
+            buffer: PubkeyStr(upload.require("buffer", parse_pubkey)?),
+            authority: upload.field("authority", parse_pubkey)?.map(PubkeyStr),
+            program: general_purpose::STANDARD.encode(upload.file()?),
+        })
+    }
+}
+
+/// As for `WriteBufferRequest`, plus an optional `recent_blockhash`.
+impl FromUpload for ProgramUploadRequest {
+    fn from_upload(upload: Upload) -> Result<Self, AppError> {
+        Ok(ProgramUploadRequest {
+            payer: PubkeyStr(upload.require("payer", parse_pubkey)?),
+            buffer: PubkeyStr(upload.require("buffer", parse_pubkey)?),
+            authority: upload.field("authority", parse_pubkey)?.map(PubkeyStr),
+            program: general_purpose::STANDARD.encode(upload.file()?),
+            recent_blockhash: upload.field("recent_blockhash", parse_hash)?.map(HashStr),
+        })
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool, FieldError> {
+    match value.to_ascii_lowercase().as_str() {
+        "1" | "true" => Ok(true),
+        "0" | "false" => Ok(false),
+        _ => Err(FieldError::InvalidBool),
+    }
+}
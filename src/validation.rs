@@ -0,0 +1,545 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use serde::Serialize;
+use crate::address_book::{valid_label, MAX_LABEL_LEN};
+use crate::amount;
+use crate::cron::Cron;
+use crate::errors::{AppError, FieldError};
+use crate::keystore::MAX_KEY_TAGS;
+use crate::models::address_book::CreateAddressRequest;
+use crate::models::airdrop::{AirdropRecipient, BulkAirdropRequest};
+use crate::models::alt::AltPlanRequest;
+use crate::models::compose::{ComposeOperation, ComposeRequest};
+use crate::models::jobs::ScheduleRequest;
+use crate::models::keys::KeyMetadata;
+use crate::merkle_tree::{self, MAX_CANOPY_DEPTH};
+use crate::metaplex::{MAX_NAME_LEN, MAX_SYMBOL_LEN, MAX_URI_LEN};
+use crate::models::merkle_tree::{CreateMerkleTreeRequest, MerkleTreeCostQuery};
+use crate::models::nft::{BurnNftRequest, PrintEditionRequest};
+use crate::models::nonce_pool::AddNonceAccountsRequest;
+use crate::models::program::{
+    LoaderAccountType, ProgramUploadRequest, SetProgramAuthorityRequest, WriteBufferRequest,
+};
+use crate::models::solana_pay::EncodeRequest;
+use crate::models::squads::{CreateMultisigRequest, MemberPermission, VaultTransactionRequest};
+use crate::models::templates::TemplateRequest;
+use crate::models::token::CreateMetadataRequest;
+use crate::jito::MAX_BUNDLE_TRANSACTIONS;
+use crate::models::transaction::{
+    BuildTransactionRequest, InspectRequest, SendBundleRequest, SendTransactionRequest, SignTransactionMessageRequest,
+};
+use crate::models::transfers::ProposeRequest;
+use crate::models::vote::VoteWithdrawRequest;
+use crate::solana_pay::{check_amount, check_link};
+use crate::types::PubkeyStr;
+use crate::models::{
+    BatchVerifyRequest, CreateTokenRequest, DeterministicKeypairRequest, ExportKeypairRequest, ImportKeypairRequest,
+    MintTokenRequest, SendSolBatchRequest, SendSolRequest, SendTokenRequest, SignMessageRequest, VerifyMessageRequest,
+};
+
+/// Upper bound on messages accepted by the sign/verify endpoints, in bytes.
+pub const MAX_MESSAGE_LEN: usize = 1024;
+/// Shortest passphrase accepted for encrypted keypair exports.
+pub const MIN_PASSPHRASE_LEN: usize = 12;
+/// SPL mints support more, but nothing real uses over 9 and clients that send
+/// larger values have almost always confused decimals with an amount.
+pub const MAX_DECIMALS: u8 = 9;
+/// Upper bound on entries in a single bulk request.
+pub const MAX_BATCH_ITEMS: usize = 1_000;
+/// Upper bound on sends per scheduled run.
+pub const MAX_JOB_ATTEMPTS: u32 = 10;
+/// Upper bound on fresh-blockhash rebuilds per `/transaction/send`.
+pub const MAX_REBUILDS: u32 = 5;
+/// Most nonce accounts one request adds to the pool.
+pub const MAX_NONCE_ACCOUNTS: usize = 32;
+
+#[derive(Debug, Serialize)]
+pub struct Violation {
+    pub field: String,
+    /// 1-based line of the offending record, for CSV uploads.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row: Option<usize>,
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// Collects every rule a request breaks so clients can fix them in one pass.
+#[derive(Default)]
+pub struct Violations(Vec<Violation>);
+
+impl Violations {
+    pub fn check(&mut self, ok: bool, field: &str, error: FieldError) {
+        if !ok {
+            self.push(None, field, error);
+        }
+    }
+
+    /// Records a failure in a row of an uploaded file.
+    pub fn row(&mut self, row: usize, field: &str, error: FieldError) {
+        self.push(Some(row), field, error);
+    }
+
+    fn push(&mut self, row: Option<usize>, field: &str, error: FieldError) {
+        self.0.push(Violation {
+            field: field.to_string(),
+            row,
+            code: error.code(),
+            message: error.to_string(),
+        });
+    }
+
+    /// Runs `item`'s rules, reporting its fields under `prefix`.
+    pub fn nested(&mut self, prefix: &str, item: &impl Validate) {
+        let mut inner = Violations::default();
+        item.validate(&mut inner);
+        for mut violation in inner.0 {
+            violation.field = format!("{prefix}.{}", violation.field);
+            self.0.push(violation);
+        }
+    }
+
+    pub fn into_result(self) -> Result<(), AppError> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::Validation(self.0))
+        }
+    }
+}
+
+/// Semantic checks that run after a request body deserialized successfully.
+pub trait Validate {
+    fn validate(&self, violations: &mut Violations);
+}
+
+impl Validate for CreateTokenRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(self.decimals <= MAX_DECIMALS, "decimals", FieldError::DecimalsOutOfRange(MAX_DECIMALS));
+    }
+}
+
+impl Validate for CreateMetadataRequest {
+    fn validate(&self, v: &mut Violations) {
+        let fields = [
+            ("name", &self.name, MAX_NAME_LEN),
+            ("symbol", &self.symbol, MAX_SYMBOL_LEN),
+            ("uri", &self.uri, MAX_URI_LEN),
+        ];
+        for (field, text, max) in fields {
+            v.check(text.len() <= max, field, FieldError::TooLong(max));
+        }
+    }
+}
+
+impl Validate for MintTokenRequest {
+    fn validate(&self, v: &mut Violations) {
+        check_token_amount(v, self.amount, self.ui_amount.as_deref(), self.decimals);
+    }
+}
+
+/// Exactly one of a raw `amount` and a `ui_amount`. Without `decimals` the
+/// handler converts the `ui_amount` once it has read them from the mint.
+fn check_token_amount(v: &mut Violations, amount: Option<u64>, ui_amount: Option<&str>, decimals: Option<u8>) {
+    match (amount, ui_amount, decimals) {
+        (Some(amount), None, _) => v.check(amount > 0, "amount", FieldError::AmountZero),
+        (None, Some(ui_amount), Some(decimals)) => match amount::from_ui(ui_amount, decimals) {
+            Ok(raw) => v.check(raw > 0, "ui_amount", FieldError::AmountZero),
+            Err(error) => v.check(false, "ui_amount", error),
+        },
+        (None, Some(ui_amount), None) => {
+            if let Err(error) = amount::check_format(ui_amount) {
+                v.check(false, "ui_amount", error);
+            }
+        }
+        _ => v.check(false, "amount", FieldError::ExactlyOne("amount or ui_amount")),
+    }
+}
+
+impl Validate for SignMessageRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(self.message.len() <= MAX_MESSAGE_LEN, "message", FieldError::MessageTooLong(MAX_MESSAGE_LEN));
+        let one_signer = self.secret.is_some() != self.key_id.is_some();
+        v.check(one_signer, "secret", FieldError::ExactlyOne("secret or key_id"));
+    }
+}
+
+impl Validate for ExportKeypairRequest {
+    fn validate(&self, v: &mut Violations) {
+        let one_signer = self.secret.is_some() != self.key_id.is_some();
+        v.check(one_signer, "secret", FieldError::ExactlyOne("secret or key_id"));
+        let long_enough = self.passphrase.chars().count() >= MIN_PASSPHRASE_LEN;
+        v.check(long_enough, "passphrase", FieldError::PassphraseTooShort(MIN_PASSPHRASE_LEN));
+    }
+}
+
+impl Validate for ImportKeypairRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(!self.passphrase.is_empty(), "passphrase", FieldError::Empty);
+        self.metadata.validate(v);
+    }
+}
+
+impl Validate for DeterministicKeypairRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(!self.seed.is_empty(), "seed", FieldError::Empty);
+    }
+}
+
+impl Validate for KeyMetadata {
+    fn validate(&self, v: &mut Violations) {
+        if let Some(label) = &self.label {
+            v.check(valid_label(label), "label", FieldError::InvalidLabel(MAX_LABEL_LEN));
+        }
+        v.check(self.tags.len() <= MAX_KEY_TAGS, "tags", FieldError::TooManyTags(MAX_KEY_TAGS));
+        for (i, tag) in self.tags.iter().enumerate() {
+            v.check(valid_label(tag), &format!("tags[{i}]"), FieldError::InvalidLabel(MAX_LABEL_LEN));
+        }
+    }
+}
+
+impl Validate for VerifyMessageRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(self.message.len() <= MAX_MESSAGE_LEN, "message", FieldError::MessageTooLong(MAX_MESSAGE_LEN));
+    }
+}
+
+impl Validate for BatchVerifyRequest {
+    fn validate(&self, v: &mut Violations) {
+        let len = self.items.len();
+        v.check((1..=MAX_BATCH_ITEMS).contains(&len), "items", FieldError::BatchSize(MAX_BATCH_ITEMS));
+    }
+}
+
+impl Validate for SendSolRequest {
+    fn validate(&self, v: &mut Violations) {
+        match (self.lamports, self.amount_sol.as_deref()) {
+            (Some(lamports), None) => v.check(lamports > 0, "lamports", FieldError::AmountZero),
+            (None, Some(sol)) => match amount::sol_to_lamports(sol) {
+                Ok(lamports) => v.check(lamports > 0, "amount_sol", FieldError::AmountZero),
+                Err(error) => v.check(false, "amount_sol", error),
+            },
+            _ => v.check(false, "lamports", FieldError::ExactlyOne("lamports or amount_sol")),
+        }
+        v.check(self.to.pubkey() != Some(*self.from), "to", FieldError::SelfTransfer);
+    }
+}
+
+impl Validate for VoteWithdrawRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(self.lamports > 0, "lamports", FieldError::AmountZero);
+        v.check(self.to.pubkey() != Some(*self.vote_account), "to", FieldError::SelfTransfer);
+    }
+}
+
+impl Validate for SendSolBatchRequest {
+    fn validate(&self, v: &mut Violations) {
+        let len = self.transfers.len();
+        v.check((1..=MAX_BATCH_ITEMS).contains(&len), "transfers", FieldError::BatchSize(MAX_BATCH_ITEMS));
+
+        let mut total = Some(0u64);
+        for (i, transfer) in self.transfers.iter().enumerate() {
+            v.check(transfer.lamports > 0, &format!("transfers[{i}].lamports"), FieldError::AmountZero);
+            let to_self = transfer.to.pubkey() == Some(*self.from);
+            v.check(!to_self, &format!("transfers[{i}].to"), FieldError::SelfTransfer);
+            total = total.and_then(|total| total.checked_add(transfer.lamports));
+        }
+        v.check(total.is_some(), "transfers", FieldError::AmountOverflow);
+    }
+}
+
+impl Validate for SendTokenRequest {
+    fn validate(&self, v: &mut Violations) {
+        check_token_amount(v, self.amount, self.ui_amount.as_deref(), self.decimals);
+        // Same owner and mint derive the same ATA: the transfer moves nothing.
+        v.check(self.destination.pubkey() != Some(*self.owner), "destination", FieldError::SameTokenAccount);
+        v.check(self.delegate != Some(self.owner), "delegate", FieldError::DelegateIsOwner);
+
+        let authority = self.delegate.unwrap_or(self.owner);
+        let signers = &self.multisig_signers;
+        let mut seen = HashSet::new();
+        let distinct = signers.iter().all(|signer| *signer != authority && seen.insert(*signer));
+        v.check(distinct, "multisig_signers", FieldError::DuplicateSigner);
+        let count_ok = signers.len() <= spl_token::instruction::MAX_SIGNERS;
+        v.check(count_ok, "multisig_signers", FieldError::TooManySigners(spl_token::instruction::MAX_SIGNERS));
+    }
+}
+
+impl Validate for BulkAirdropRequest {
+    fn validate(&self, v: &mut Violations) {
+        let len = self.recipients.len();
+        v.check((1..=MAX_BATCH_ITEMS).contains(&len), "recipients", FieldError::BatchSize(MAX_BATCH_ITEMS));
+
+        let mut total = Some(0u64);
+        for (i, entry) in self.recipients.iter().enumerate() {
+            for (field, error) in recipient_errors(&self.payer, entry) {
+                v.check(false, &format!("recipients[{i}].{field}"), error);
+            }
+            total = total.and_then(|total| total.checked_add(entry.amount));
+        }
+        v.check(total.is_some(), "recipients", FieldError::AmountOverflow);
+    }
+}
+
+impl Validate for EncodeRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(self.recipient.is_some() != self.link.is_some(), "recipient", FieldError::RecipientOrLink);
+
+        if let Some(link) = &self.link {
+            if let Err(error) = check_link(link) {
+                v.check(false, "link", error);
+            }
+            v.check(self.amount.is_none(), "amount", FieldError::TransferOnly);
+            v.check(self.spl_token.is_none(), "spl_token", FieldError::TransferOnly);
+            v.check(self.reference.is_empty(), "reference", FieldError::TransferOnly);
+            v.check(self.memo.is_none(), "memo", FieldError::TransferOnly);
+        }
+        if let Some(Err(error)) = self.amount.as_deref().map(|amount| check_amount(amount, self.spl_token.is_none())) {
+            v.check(false, "amount", error);
+        }
+    }
+}
+
+impl Validate for ComposeRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(!self.operations.is_empty(), "operations", FieldError::Empty);
+        for (index, operation) in self.operations.iter().enumerate() {
+            let prefix = format!("operations[{index}]");
+            match operation {
+                ComposeOperation::SendSol(request) => v.nested(&prefix, request),
+                ComposeOperation::SendToken(request) => v.nested(&prefix, request),
+                ComposeOperation::Memo(memo) => {
+                    v.check(!memo.memo.is_empty(), &format!("{prefix}.memo"), FieldError::Empty);
+                }
+                ComposeOperation::CreateAta(_) => {}
+            }
+        }
+    }
+}
+
+impl Validate for BuildTransactionRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(!self.instructions.is_empty(), "instructions", FieldError::Empty);
+    }
+}
+
+impl Validate for InspectRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(
+            (1..=100).contains(&self.drain_threshold_percent),
+            "drain_threshold_percent",
+            FieldError::PercentOutOfRange,
+        );
+    }
+}
+
+impl Validate for SignTransactionMessageRequest {
+    fn validate(&self, v: &mut Violations) {
+        let one_signer = self.secret.is_some() != self.key_id.is_some();
+        v.check(one_signer, "secret", FieldError::ExactlyOne("secret or key_id"));
+    }
+}
+
+impl Validate for SendBundleRequest {
+    fn validate(&self, v: &mut Violations) {
+        let len = self.transactions.len();
+        v.check(
+            (1..=MAX_BUNDLE_TRANSACTIONS).contains(&len),
+            "transactions",
+            FieldError::BatchSize(MAX_BUNDLE_TRANSACTIONS),
+        );
+    }
+}
+
+impl Validate for SendTransactionRequest {
+    fn validate(&self, v: &mut Violations) {
+        if let Some(rebuilds) = self.max_rebuilds {
+            v.check(rebuilds <= MAX_REBUILDS, "max_rebuilds", FieldError::TooManyRebuilds(MAX_REBUILDS));
+        }
+        if self.nonce_lease.is_some() {
+            let conflict = FieldError::NotAllowedWith("nonce_lease");
+            v.check(self.signers.is_empty(), "signers", conflict.clone());
+            v.check(self.max_rebuilds.is_none(), "max_rebuilds", conflict.clone());
+            v.check(self.last_valid_block_height.is_none(), "last_valid_block_height", conflict);
+        }
+    }
+}
+
+impl Validate for AltPlanRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(!self.instructions.is_empty(), "instructions", FieldError::Empty);
+    }
+}
+
+impl Validate for CreateMultisigRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(!self.members.is_empty(), "members", FieldError::Empty);
+        let mut seen = HashSet::new();
+        v.check(self.members.iter().all(|member| seen.insert(*member.key)), "members", FieldError::DuplicateMember);
+        for (i, member) in self.members.iter().enumerate() {
+            v.check(!member.permissions.is_empty(), &format!("members[{i}].permissions"), FieldError::Empty);
+        }
+        let holders = |permission| self.members.iter().filter(|m| m.permissions.contains(&permission)).count();
+        if !self.members.is_empty() {
+            v.check(holders(MemberPermission::Initiate) > 0, "members", FieldError::MissingPermission("initiate"));
+            v.check(holders(MemberPermission::Execute) > 0, "members", FieldError::MissingPermission("execute"));
+        }
+        let voters = holders(MemberPermission::Vote);
+        let in_range = (1..=voters).contains(&usize::from(self.threshold));
+        v.check(in_range, "threshold", FieldError::ThresholdRange(voters));
+    }
+}
+
+impl Validate for VaultTransactionRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(!self.instructions.is_empty(), "instructions", FieldError::Empty);
+    }
+}
+
+impl Validate for WriteBufferRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(!self.program.is_empty(), "program", FieldError::Empty);
+    }
+}
+
+impl Validate for ProgramUploadRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(!self.program.is_empty(), "program", FieldError::Empty);
+    }
+}
+
+impl Validate for SetProgramAuthorityRequest {
+    fn validate(&self, v: &mut Violations) {
+        if self.new_authority.is_none() {
+            let buffer = self.account_type == LoaderAccountType::Buffer;
+            v.check(!buffer, "new_authority", FieldError::RequiredWith("buffer"));
+            v.check(!self.checked, "new_authority", FieldError::RequiredWith("checked"));
+        }
+    }
+}
+
+impl Validate for CreateMerkleTreeRequest {
+    fn validate(&self, v: &mut Violations) {
+        check_tree_size(v, self.max_depth, self.max_buffer_size, self.canopy_depth);
+    }
+}
+
+impl Validate for MerkleTreeCostQuery {
+    fn validate(&self, v: &mut Violations) {
+        check_tree_size(v, self.max_depth, self.max_buffer_size, self.canopy_depth);
+    }
+}
+
+fn check_tree_size(v: &mut Violations, max_depth: u32, max_buffer_size: u32, canopy_depth: u32) {
+    let supported = merkle_tree::supported(max_depth, max_buffer_size);
+    v.check(supported, "max_buffer_size", FieldError::UnsupportedTreeSize);
+    let canopy = canopy_depth <= max_depth && canopy_depth <= MAX_CANOPY_DEPTH;
+    v.check(canopy, "canopy_depth", FieldError::CanopyDepth(MAX_CANOPY_DEPTH));
+}
+
+impl Validate for BurnNftRequest {
+    fn validate(&self, v: &mut Violations) {
+        if let Some(print) = &self.print_of {
+            v.check(print.edition > 0, "print_of.edition", FieldError::NotPositive);
+        }
+    }
+}
+
+impl Validate for PrintEditionRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(self.edition > 0, "edition", FieldError::NotPositive);
+    }
+}
+
+impl Validate for AddNonceAccountsRequest {
+    fn validate(&self, v: &mut Violations) {
+        match self.count {
+            Some(count) => {
+                v.check(self.accounts.is_empty(), "count", FieldError::ExactlyOne("count or accounts"));
+                let in_range = (1..=MAX_NONCE_ACCOUNTS).contains(&usize::from(count));
+                v.check(in_range, "count", FieldError::BatchSize(MAX_NONCE_ACCOUNTS));
+                v.check(self.payer.is_some(), "payer", FieldError::RequiredWith("count"));
+            }
+            None => {
+                v.check(!self.accounts.is_empty(), "count", FieldError::ExactlyOne("count or accounts"));
+                v.check(self.accounts.len() <= MAX_NONCE_ACCOUNTS, "accounts", FieldError::BatchSize(MAX_NONCE_ACCOUNTS));
+                v.check(self.payer.is_none(), "payer", FieldError::NotAllowedWith("accounts"));
+            }
+        }
+    }
+}
+
+impl Validate for CreateAddressRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(valid_label(&self.label), "label", FieldError::InvalidLabel(MAX_LABEL_LEN));
+    }
+}
+
+impl Validate for TemplateRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(!self.name.trim().is_empty(), "name", FieldError::Empty);
+        v.check(!self.instructions.is_empty(), "instructions", FieldError::Empty);
+        for (index, parameter) in self.parameters.iter().enumerate() {
+            v.check(!parameter.name.trim().is_empty(), &format!("parameters[{index}].name"), FieldError::Empty);
+        }
+    }
+}
+
+impl Validate for ScheduleRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(!self.signers.is_empty(), "signers", FieldError::Empty);
+        v.check(!self.instructions.is_empty(), "instructions", FieldError::Empty);
+        let one_schedule = self.execute_at.is_some() != self.cron.is_some();
+        v.check(one_schedule, "execute_at", FieldError::ExactlyOne("execute_at or cron"));
+        if let Some(expression) = &self.cron {
+            match expression.parse::<Cron>() {
+                Ok(cron) => v.check(
+                    cron.next_after(Utc::now()).is_some(),
+                    "cron",
+                    FieldError::InvalidCron("never fires".to_string()),
+                ),
+                Err(message) => v.check(false, "cron", FieldError::InvalidCron(message)),
+            }
+        }
+        if let Some(attempts) = self.retry.max_attempts {
+            v.check(
+                (1..=MAX_JOB_ATTEMPTS).contains(&attempts),
+                "retry.max_attempts",
+                FieldError::AttemptsOutOfRange(MAX_JOB_ATTEMPTS),
+            );
+        }
+        if let Some(fee) = &self.priority_fee {
+            let start = fee.initial_micro_lamports;
+            v.check(
+                start > 0 && start <= fee.max_micro_lamports,
+                "priority_fee.initial_micro_lamports",
+                FieldError::PriorityFeeRange,
+            );
+            if let Some(percent) = fee.bump_percent {
+                v.check((1..=100).contains(&percent), "priority_fee.bump_percent", FieldError::PercentOutOfRange);
+            }
+            if let Some(interval) = fee.interval_ms {
+                v.check(interval > 0, "priority_fee.interval_ms", FieldError::NotPositive);
+            }
+        }
+    }
+}
+
+impl Validate for ProposeRequest {
+    fn validate(&self, v: &mut Violations) {
+        v.check(!self.signers.is_empty(), "signers", FieldError::Empty);
+        v.check(!self.instructions.is_empty(), "instructions", FieldError::Empty);
+    }
+}
+
+/// Per-recipient rules, shared by JSON bodies and CSV rows.
+pub fn recipient_errors(payer: &PubkeyStr, entry: &AirdropRecipient) -> Vec<(&'static str, FieldError)> {
+    let mut errors = Vec::new();
+    if entry.amount == 0 {
+        errors.push(("amount", FieldError::AmountZero));
+    }
+    if entry.recipient == *payer {
+        errors.push(("recipient", FieldError::SelfTransfer));
+    }
+    errors
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::json;
+use solana_sdk::{
+    account::Account, hash::Hash, program_option::COption, program_pack::Pack, pubkey::Pubkey,
+    transaction::Transaction,
+};
+use spl_token::state::{Account as TokenAccount, AccountState};
+
+use solana_fellowship_server::rpc::MockRpc;
+
+use common::{assert_error, key, mock_app, post_json, post_json_to, pubkey};
+
+fn token_account(program_id: Pubkey, mint: u8, amount: u64, state: AccountState) -> Account {
+    let mut data = vec![0; TokenAccount::LEN];
+    TokenAccount {
+        mint: key(mint),
+        owner: key(1),
+        amount,
+        delegate: COption::None,
+        state,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    Account { lamports: 2_039_280, data, owner: program_id, executable: false, rent_epoch: 0 }
+}
+
+#[tokio::test]
+async fn cleanup_closes_empty_token_accounts() {
+    let mock = Arc::new(MockRpc::new());
+    mock.set_account(key(20), token_account(spl_token::id(), 9, 0, AccountState::Initialized));
+    mock.set_account(key(21), token_account(spl_token::id(), 8, 5, AccountState::Initialized));
+    mock.set_account(key(22), token_account(spl_token::id(), 7, 0, AccountState::Frozen));
+    mock.set_account(key(23), token_account(spl_token::id(), 6, 0, AccountState::Initialized));
+    mock.set_account(key(24), token_account(spl_token_2022::id(), 9, 0, AccountState::Initialized));
+    let app = mock_app(mock);
+
+    let blockhash = Hash::new_from_array([3; 32]).to_string();
+    let request = json!({ "keep_mints": [pubkey(6)], "recent_blockhash": blockhash });
+    let (status, body) = post_json_to(app, &format!("/account/{}/cleanup", pubkey(1)), request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let data = &body["data"];
+    let closed: Vec<_> = data["closed"].as_array().unwrap().iter().map(|account| account["address"].clone()).collect();
+    assert_eq!(closed, [json!(pubkey(20)), json!(pubkey(24))]);
+    assert_eq!(data["closed"][1]["token_program"], spl_token_2022::id().to_string());
+    assert_eq!(data["reclaimed_lamports"], 2 * 2_039_280);
+    assert_eq!(data["recent_blockhash"], blockhash);
+
+    let transactions = data["transactions"].as_array().unwrap();
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transactions[0]["accounts"], json!([0, 2]));
+    let bytes = general_purpose::STANDARD.decode(transactions[0]["transaction"].as_str().unwrap()).unwrap();
+    let transaction: Transaction = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(transaction.message.account_keys[0], key(1));
+    assert_eq!(transaction.message.instructions.len(), 2);
+}
+
+#[tokio::test]
+async fn cleanup_with_nothing_to_close_builds_no_transactions() {
+    let app = mock_app(Arc::new(MockRpc::new()));
+    let (status, body) = post_json_to(app, &format!("/account/{}/cleanup", pubkey(1)), json!({})).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["closed"], json!([]));
+    assert!(body["data"].get("recent_blockhash").is_none());
+}
+
+#[tokio::test]
+async fn cleanup_needs_rpc_and_a_valid_owner() {
+    let (status, body) = post_json(&format!("/account/{}/cleanup", pubkey(1)), json!({})).await;
+    assert_error(status, &body, StatusCode::SERVICE_UNAVAILABLE, "RPC_UNAVAILABLE");
+
+    let (status, body) = post_json_to(mock_app(Arc::new(MockRpc::new())), "/account/nope/cleanup", json!({})).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_PUBKEY");
+    assert_eq!(body["field"], "owner");
+}
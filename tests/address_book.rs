@@ -0,0 +1,95 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use serde_json::{json, Value};
+use spl_associated_token_account::get_associated_token_address;
+
+use solana_fellowship_server::rpc::MockRpc;
+
+use common::{assert_error, call, get_json_from, key, mint_account, mock_app, post_json_to, pubkey, test_app};
+
+async fn put(app: Router, path: &str, body: Value) -> (StatusCode, Value) {
+    let request = Request::put(path).header("content-type", "application/json").body(Body::from(body.to_string()));
+    let (status, _, bytes) = call(app, request.unwrap()).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+async fn delete(app: Router, path: &str) -> (StatusCode, Value) {
+    let (status, _, bytes) = call(app, Request::delete(path).body(Body::empty()).unwrap()).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+async fn add(app: Router, label: &str, seed: u8) {
+    let request = json!({ "label": label, "address": pubkey(seed), "note": "ops wallet" });
+    let (status, body) = post_json_to(app, "/address-book", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+}
+
+#[tokio::test]
+async fn manages_labelled_addresses() {
+    let app = test_app();
+    add(app.clone(), "treasury", 5).await;
+    add(app.clone(), "payroll", 6).await;
+
+    let request = json!({ "label": "treasury", "address": pubkey(7) });
+    let (status, body) = post_json_to(app.clone(), "/address-book", request).await;
+    assert_error(status, &body, StatusCode::CONFLICT, "CONFLICT");
+    let request = json!({ "label": "bad label!", "address": pubkey(7) });
+    let (status, body) = post_json_to(app.clone(), "/address-book", request).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "INVALID_LABEL");
+
+    let (status, body) = get_json_from(app.clone(), "/address-book").await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = body["data"].as_array().unwrap();
+    let labels: Vec<&str> = entries.iter().map(|entry| entry["label"].as_str().unwrap()).collect();
+    assert_eq!(labels, ["payroll", "treasury"]);
+
+    let (status, body) = put(app.clone(), "/address-book/treasury", json!({ "address": pubkey(8) })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["address"], pubkey(8));
+    assert_eq!(body["data"]["note"], Value::Null);
+
+    let (status, _) = delete(app.clone(), "/address-book/treasury").await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = get_json_from(app, "/address-book/treasury").await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+}
+
+#[tokio::test]
+async fn send_endpoints_resolve_labels() {
+    let mock = Arc::new(MockRpc::new());
+    mock.set_account(key(9), mint_account(spl_token::ID, 6));
+    let app = mock_app(mock);
+    add(app.clone(), "treasury", 5).await;
+    add(app.clone(), "self", 1).await;
+
+    let request = json!({ "from": pubkey(1), "to": "label:treasury", "lamports": 100_000 });
+    let (status, body) = post_json_to(app.clone(), "/send/sol", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["accounts"][1]["pubkey"], pubkey(5));
+
+    let request = json!({ "from": pubkey(1), "transfers": [{ "to": "label:treasury", "lamports": 10 }] });
+    let (status, body) = post_json_to(app.clone(), "/send/sol-batch", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["instructions"][0]["accounts"][1]["pubkey"], pubkey(5));
+
+    let request = json!({ "destination": "label:treasury", "mint": pubkey(9), "owner": pubkey(1), "amount": 10 });
+    let (status, body) = post_json_to(app.clone(), "/send/token", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["destination_ata"], get_associated_token_address(&key(5), &key(9)).to_string());
+
+    let request = json!({ "from": pubkey(1), "to": "label:unknown", "lamports": 100_000 });
+    let (status, body) = post_json_to(app.clone(), "/send/sol", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "UNKNOWN_LABEL");
+    assert_eq!(body["field"], "to");
+
+    // A label can't sneak a transfer to self past validation.
+    let request = json!({ "from": pubkey(1), "to": "label:self", "lamports": 100_000 });
+    let (status, body) = post_json_to(app, "/send/sol", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "SELF_TRANSFER");
+}
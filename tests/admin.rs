@@ -0,0 +1,179 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+
+use solana_fellowship_server::config::{AdminConfig, ApprovalsConfig, Config, Mode};
+use solana_fellowship_server::rpc::MockRpc;
+use solana_fellowship_server::types::Redacted;
+
+use common::{app_with, assert_error, call};
+
+fn admin_app(mode: Mode) -> Router {
+    let identities: HashMap<_, _> =
+        ["alice", "bob", "carol"].into_iter().map(|name| (name.to_string(), Redacted(format!("{name}-key")))).collect();
+    let config = Config {
+        mode,
+        approvals: ApprovalsConfig { required: 1, identities, ..ApprovalsConfig::default() },
+        admin: AdminConfig { identities: vec!["alice".to_string()] },
+        ..Config::default()
+    };
+    app_with(config, Arc::new(MockRpc::new()))
+}
+
+async fn send(app: Router, key: Option<&str>, method: &str, path: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let mut request = Request::builder().method(method).uri(path).header(header::CONTENT_TYPE, "application/json");
+    if let Some(key) = key {
+        request = request.header("x-api-key", key);
+    }
+    let body = body.map_or(Body::empty(), |body| Body::from(body.to_string()));
+    let (status, _, bytes) = call(app, request.body(body).unwrap()).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn admin_api_requires_an_admin_identity() {
+    let app = admin_app(Mode::Development);
+
+    let (status, body) = send(app.clone(), None, "GET", "/admin", None).await;
+    assert_error(status, &body, StatusCode::UNAUTHORIZED, "UNAUTHORIZED");
+    let (status, body) = send(app.clone(), Some("bob-key"), "POST", "/admin/keystore/rotate", None).await;
+    assert_error(status, &body, StatusCode::FORBIDDEN, "FORBIDDEN");
+
+    let (status, body) = send(app, Some("alice-key"), "GET", "/admin", None).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["mode"], "development");
+    assert_eq!(body["data"]["master_key_version"], 1);
+    assert_eq!(body["data"]["features"], json!({ "relayer": true, "bundles": true, "jobs": true }));
+}
+
+#[tokio::test]
+async fn revoked_api_keys_stop_authenticating() {
+    let app = admin_app(Mode::Development);
+    let approve = json!({ "id": "missing" });
+
+    let (status, body) = send(app.clone(), Some("carol-key"), "POST", "/transfers/approve", Some(approve.clone())).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+
+    let revoke = json!({ "identity": "carol" });
+    let (status, body) = send(app.clone(), Some("alice-key"), "POST", "/admin/api-keys/revoke", Some(revoke)).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["revoked"], json!(["carol"]));
+
+    let (status, body) = send(app.clone(), Some("carol-key"), "POST", "/transfers/approve", Some(approve)).await;
+    assert_error(status, &body, StatusCode::UNAUTHORIZED, "UNAUTHORIZED");
+
+    let revoke = json!({ "identity": "mallory" });
+    let (status, body) = send(app.clone(), Some("alice-key"), "POST", "/admin/api-keys/revoke", Some(revoke)).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+
+    let (_, body) = send(app, None, "GET", "/audit", None).await;
+    let entries = body["data"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["action"], "admin.revoke_api_key");
+    assert_eq!(entries[0]["identity"], "alice");
+    assert_eq!(entries[0]["allowed"], true);
+    assert_eq!(entries[1]["allowed"], false);
+    assert_eq!(entries[1]["code"], "NOT_FOUND");
+}
+
+#[tokio::test]
+async fn rotating_the_master_key_keeps_stored_keys_usable() {
+    let app = admin_app(Mode::Production);
+    let (status, body) = send(app.clone(), None, "POST", "/keypair", Some(json!({}))).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let pubkey = body["data"]["pubkey"].clone();
+    let key_id = body["data"]["key_id"].clone();
+
+    let (status, body) = send(app.clone(), Some("alice-key"), "POST", "/admin/keystore/rotate", None).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"], json!({ "master_key_version": 2, "reencrypted": 1 }));
+
+    let request = json!({ "message": "hi", "key_id": key_id });
+    let (status, body) = send(app, None, "POST", "/message/sign", Some(request)).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["public_key"], pubkey);
+}
+
+#[tokio::test]
+async fn features_can_be_switched_off_at_runtime() {
+    let app = admin_app(Mode::Development);
+
+    let (status, body) = send(app.clone(), None, "GET", "/relayer", None).await;
+    assert_error(status, &body, StatusCode::SERVICE_UNAVAILABLE, "RELAYER_UNAVAILABLE");
+
+    let request = json!({ "relayer": false });
+    let (status, body) = send(app.clone(), Some("alice-key"), "POST", "/admin/features", Some(request)).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["features"], json!({ "relayer": false, "bundles": true, "jobs": true }));
+
+    let (status, body) = send(app, None, "GET", "/relayer", None).await;
+    assert_error(status, &body, StatusCode::FORBIDDEN, "FEATURE_DISABLED");
+}
+
+#[tokio::test]
+async fn reload_applies_a_valid_config_and_keeps_the_old_one_otherwise() {
+    let path = std::env::temp_dir().join(format!("superdev-admin-{}.toml", uuid::Uuid::new_v4()));
+    // SAFETY: this is the only test in the binary that touches the environment.
+    unsafe { std::env::set_var("SUPERDEV_CONFIG", &path) };
+    let app = admin_app(Mode::Development);
+
+    let reloaded = r#"
+        mode = "production"
+
+        [features]
+        jobs = false
+
+        [approvals]
+        required = 1
+        identities = { alice = "alice-key", bob = "bob-key" }
+
+        [admin]
+        identities = ["alice"]
+    "#;
+    std::fs::write(&path, reloaded).unwrap();
+    let (status, body) = send(app.clone(), Some("alice-key"), "POST", "/admin/reload", None).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["mode"], "production");
+    assert_eq!(body["data"]["features"]["jobs"], false);
+
+    // carol's key is gone from the reloaded config.
+    let approve = json!({ "id": "missing" });
+    let (status, body) = send(app.clone(), Some("carol-key"), "POST", "/transfers/approve", Some(approve)).await;
+    assert_error(status, &body, StatusCode::UNAUTHORIZED, "UNAUTHORIZED");
+
+    std::fs::write(&path, "[admin]\nidentities = [\"nobody\"]\n").unwrap();
+    let (status, body) = send(app.clone(), Some("alice-key"), "POST", "/admin/reload", None).await;
+    assert_error(status, &body, StatusCode::INTERNAL_SERVER_ERROR, "INVALID_CONFIG");
+
+    let (status, body) = send(app, Some("alice-key"), "GET", "/admin", None).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["mode"], "production");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn debug_runtime_reports_tasks_jobs_and_rpc_health() {
+    let app = admin_app(Mode::Development);
+
+    let (status, body) = send(app.clone(), Some("bob-key"), "GET", "/debug/runtime", None).await;
+    assert_error(status, &body, StatusCode::FORBIDDEN, "FORBIDDEN");
+
+    let (status, body) = send(app, Some("alice-key"), "GET", "/debug/runtime", None).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data = &body["data"];
+    assert!(data["runtime"]["workers"].as_u64().unwrap() >= 1);
+    assert!(data["runtime"]["alive_tasks"].is_u64());
+    assert_eq!(data["jobs"], json!({ "scheduled": 0, "running": 0 }));
+    assert_eq!(data["blockhash_age_ms"], Value::Null);
+    assert_eq!(data["rpc"]["configured"], true);
+    assert!(data["rpc"]["slot"].is_u64(), "rpc: {}", data["rpc"]);
+    assert_eq!(data["rpc"]["error"], Value::Null);
+}
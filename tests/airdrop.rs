@@ -0,0 +1,157 @@
+mod common;
+
+use std::sync::Arc;
+
+use base64::{engine::general_purpose, Engine as _};
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use serde_json::{json, Value};
+use solana_sdk::{hash::Hash, pubkey::Pubkey, transaction::Transaction};
+
+use solana_fellowship_server::rpc::MockRpc;
+
+use common::{assert_error, call, mint_account, mock_app, post_json, post_json_to, pubkey};
+
+fn recipients(count: u8) -> Value {
+    (0..count)
+        .map(|i| json!({ "recipient": pubkey(i + 10), "amount": 1_000 + u64::from(i) }))
+        .collect()
+}
+
+fn decode(transaction: &Value) -> Transaction {
+    let bytes = general_purpose::STANDARD.decode(transaction.as_str().unwrap()).unwrap();
+    bincode::deserialize(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn sol_airdrop_packs_transfers_into_one_transaction() {
+    let blockhash = Hash::new_from_array([3; 32]).to_string();
+    let body = json!({ "payer": pubkey(1), "recipients": recipients(3), "recent_blockhash": blockhash });
+
+    let (status, body) = post_json("/airdrop/bulk", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let data = &body["data"];
+    assert_eq!(data["asset"], "SOL");
+    assert_eq!(data["recipient_count"], 3);
+    assert_eq!(data["total_amount"], 3_003);
+    assert_eq!(data["recent_blockhash"], blockhash);
+    assert_eq!(data["transactions"].as_array().unwrap().len(), 1);
+    assert_eq!(data["transactions"][0]["recipients"], json!([0, 3]));
+
+    let transaction = decode(&data["transactions"][0]["transaction"]);
+    assert_eq!(transaction.message.instructions.len(), 3);
+    assert_eq!(transaction.signatures.len(), 1);
+    assert_eq!(transaction.message.account_keys[0].to_string(), pubkey(1));
+}
+
+#[tokio::test]
+async fn large_airdrops_are_chunked_under_the_size_limit() {
+    let blockhash = Hash::new_from_array([3; 32]).to_string();
+    let body = json!({ "payer": pubkey(1), "recipients": recipients(100), "recent_blockhash": blockhash });
+
+    let (status, body) = post_json("/airdrop/bulk", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let transactions = body["data"]["transactions"].as_array().unwrap();
+    assert!(transactions.len() > 1);
+
+    let mut next = 0;
+    for entry in transactions {
+        assert!(entry["size"].as_u64().unwrap() <= 1232);
+        assert_eq!(entry["recipients"][0], next);
+        next = entry["recipients"][1].as_u64().unwrap();
+
+        let transaction = decode(&entry["transaction"]);
+        assert_eq!(transaction.message.instructions.len() as u64, next - entry["recipients"][0].as_u64().unwrap());
+    }
+    assert_eq!(next, 100);
+}
+
+#[tokio::test]
+async fn airdrops_stream_one_transaction_per_line() {
+    let body = json!({
+        "payer": pubkey(1),
+        "recipients": recipients(100),
+        "recent_blockhash": Hash::new_from_array([3; 32]).to_string(),
+    });
+    let request = Request::post("/airdrop/bulk")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::ACCEPT, "application/x-ndjson")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let (status, _, bytes) = call(mock_app(Arc::new(MockRpc::new())), request).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let lines: Vec<Value> = String::from_utf8(bytes.to_vec())
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(lines.len() > 1);
+    let mut next = 0;
+    for (index, line) in lines.iter().enumerate() {
+        assert_eq!(line["index"], index);
+        assert_eq!(line["recipients"][0], next);
+        next = line["recipients"][1].as_u64().unwrap();
+    }
+    assert_eq!(next, 100);
+}
+
+#[tokio::test]
+async fn token_airdrop_creates_atas_with_detected_program() {
+    let mock = Arc::new(MockRpc::new());
+    let mint = Pubkey::new_unique();
+    mock.set_account(mint, mint_account(spl_token_2022::id(), 6));
+
+    let body = json!({ "payer": pubkey(1), "mint": mint.to_string(), "recipients": recipients(2) });
+    let (status, body) = post_json_to(mock_app(mock), "/airdrop/bulk", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["token_program"], spl_token_2022::id().to_string());
+    assert_eq!(body["data"]["recent_blockhash"], Hash::new_from_array([1; 32]).to_string());
+
+    let transaction = decode(&body["data"]["transactions"][0]["transaction"]);
+    let programs: Vec<Pubkey> = transaction
+        .message
+        .instructions
+        .iter()
+        .map(|ix| *ix.program_id(&transaction.message.account_keys))
+        .collect();
+    assert_eq!(
+        programs,
+        [spl_associated_token_account::id(), spl_token_2022::id()].repeat(2)
+    );
+}
+
+#[tokio::test]
+async fn airdrop_reports_every_invalid_recipient() {
+    let body = json!({
+        "payer": pubkey(1),
+        "recipients": [
+            { "recipient": pubkey(2), "amount": 0 },
+            { "recipient": pubkey(1), "amount": 5 },
+        ],
+        "recent_blockhash": Hash::default().to_string(),
+    });
+
+    let (status, body) = post_json("/airdrop/bulk", body).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["field"], "recipients[0].amount");
+    assert_eq!(body["details"][1]["field"], "recipients[1].recipient");
+    assert_eq!(body["details"][1]["code"], "SELF_TRANSFER");
+}
+
+#[tokio::test]
+async fn token_airdrop_needs_rpc() {
+    let body = json!({
+        "payer": pubkey(1),
+        "mint": pubkey(2),
+        "recipients": recipients(1),
+        "recent_blockhash": Hash::default().to_string(),
+    });
+
+    let (status, body) = post_json("/airdrop/bulk", body).await;
+    assert_error(status, &body, StatusCode::SERVICE_UNAVAILABLE, "RPC_UNAVAILABLE");
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use solana_address_lookup_table_interface::{instruction::derive_lookup_table_address, program};
+use solana_sdk::pubkey::Pubkey;
+
+use solana_fellowship_server::rpc::MockRpc;
+
+use common::{assert_error, mock_app, post_json_to, pubkey};
+
+/// Program `pubkey(50)` reading `accounts` distinct accounts, none signing.
+fn wide_instruction(accounts: u8) -> Value {
+    json!({
+        "program_id": pubkey(50),
+        "accounts": (10..10 + accounts).map(|seed| json!({ "pubkey": pubkey(seed) })).collect::<Vec<_>>(),
+        "data": "AQ==",
+    })
+}
+
+#[tokio::test]
+async fn plans_table_for_non_signer_accounts() {
+    let app = mock_app(Arc::new(MockRpc::new()));
+    let request = json!({ "payer": pubkey(1), "instructions": [wide_instruction(30)], "recent_slot": 42 });
+
+    let (status, body) = post_json_to(app, "/alt/plan", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data = &body["data"];
+    let payer: Pubkey = pubkey(1).parse().unwrap();
+    assert_eq!(data["lookup_table"], derive_lookup_table_address(&payer, 42).0.to_string());
+    assert_eq!(data["authority"], pubkey(1));
+    assert_eq!(data["addresses"].as_array().unwrap().len(), 30);
+    assert_eq!(data["static_accounts"], json!([pubkey(1), pubkey(50)]));
+
+    // Create, then two extends of at most 20 addresses.
+    let instructions = data["instructions"].as_array().unwrap();
+    assert_eq!(instructions.len(), 3);
+    assert!(instructions.iter().all(|instruction| instruction["program_id"] == program::ID.to_string()));
+    // Each moved key shrinks from 32 bytes to a 1-byte index.
+    assert!(data["v0_size"].as_u64().unwrap() + 30 * 29 < data["legacy_size"].as_u64().unwrap());
+    assert_eq!(data["fits"], true);
+}
+
+#[tokio::test]
+async fn rejects_instructions_with_nothing_to_move() {
+    let app = mock_app(Arc::new(MockRpc::new()));
+
+    let request = json!({ "payer": pubkey(1), "instructions": [wide_instruction(0)] });
+    let (status, body) = post_json_to(app.clone(), "/alt/plan", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "instructions");
+
+    let (status, body) = post_json_to(app, "/alt/plan", json!({ "payer": pubkey(1), "instructions": [] })).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+}
@@ -0,0 +1,295 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::{http::StatusCode, Router};
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::{hash::hash, pubkey::Pubkey, signature::Signature};
+
+use solana_fellowship_server::rpc::MockRpc;
+
+use common::{assert_error, mock_app, post_json, post_json_to, pubkey, test_app};
+
+fn discriminator(preimage: &str) -> Vec<u8> {
+    hash(preimage.as_bytes()).to_bytes()[..8].to_vec()
+}
+
+/// A program in the 0.30+ IDL format: explicit discriminators, `pubkey`,
+/// nested account groups and `{"defined": {"name": ...}}`.
+fn spec_idl() -> Value {
+    json!({
+        "address": pubkey(9),
+        "metadata": { "name": "vault", "version": "0.1.0", "spec": "0.1.0" },
+        "instructions": [{
+            "name": "deposit",
+            "discriminator": [1, 2, 3, 4, 5, 6, 7, 8],
+            "accounts": [
+                { "name": "owner", "writable": true, "signer": true },
+                { "name": "vault", "accounts": [
+                    { "name": "state", "writable": true },
+                    { "name": "authority" }
+                ]},
+                { "name": "referrer", "optional": true },
+                { "name": "system_program", "address": "11111111111111111111111111111111" }
+            ],
+            "args": [
+                { "name": "amount", "type": "u64" },
+                { "name": "params", "type": { "defined": { "name": "Params" } } }
+            ]
+        }],
+        "types": [
+            { "name": "Params", "type": { "kind": "struct", "fields": [
+                { "name": "memo", "type": { "option": "string" } },
+                { "name": "tags", "type": { "vec": "u8" } },
+                { "name": "side", "type": { "defined": { "name": "Side" } } }
+            ]}},
+            { "name": "Side", "type": { "kind": "enum", "variants": [
+                { "name": "Bid" },
+                { "name": "Ask", "fields": [{ "name": "limit", "type": "u16" }] }
+            ]}}
+        ]
+    })
+}
+
+async fn register(app: &Router, idl: Value) -> (StatusCode, Value) {
+    post_json_to(app.clone(), "/anchor/idl", json!({ "idl": idl })).await
+}
+
+#[tokio::test]
+async fn builds_instruction_from_spec_idl() {
+    let app = test_app();
+    let (status, body) = register(&app, spec_idl()).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"], json!({ "program_id": pubkey(9), "name": "vault", "instructions": ["deposit"], "events": [] }));
+
+    let request = json!({
+        "program_id": pubkey(9),
+        "method": "deposit",
+        "args": {
+            "amount": "18446744073709551615",
+            "params": { "memo": "hi", "tags": [7, 8], "side": { "Ask": { "limit": 500 } } }
+        },
+        "accounts": {
+            "owner": pubkey(1),
+            "vault": { "state": pubkey(2), "authority": pubkey(3) }
+        },
+        "remaining_accounts": [{ "pubkey": pubkey(4), "is_writable": true }]
+    });
+    let (status, body) = post_json_to(app, "/anchor/instruction", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let mut expected = vec![1, 2, 3, 4, 5, 6, 7, 8];
+    expected.extend(u64::MAX.to_le_bytes());
+    expected.extend([1, 2, 0, 0, 0, b'h', b'i']);
+    expected.extend([2, 0, 0, 0, 7, 8]);
+    expected.extend([1, 0xf4, 0x01]);
+    let data = general_purpose::STANDARD.decode(body["data"]["instruction_data"].as_str().unwrap()).unwrap();
+    assert_eq!(data, expected);
+
+    assert_eq!(
+        body["data"]["accounts"],
+        json!([
+            { "pubkey": pubkey(1), "is_signer": true, "is_writable": true },
+            { "pubkey": pubkey(2), "is_signer": false, "is_writable": true },
+            { "pubkey": pubkey(3), "is_signer": false, "is_writable": false },
+            { "pubkey": pubkey(9), "is_signer": false, "is_writable": false },
+            { "pubkey": Pubkey::default().to_string(), "is_signer": false, "is_writable": false },
+            { "pubkey": pubkey(4), "is_signer": false, "is_writable": true },
+        ])
+    );
+}
+
+#[tokio::test]
+async fn legacy_idl_derives_discriminator_from_snake_case_name() {
+    let idl = json!({
+        "version": "0.1.0",
+        "name": "counter",
+        "instructions": [{
+            "name": "incrementBy",
+            "accounts": [
+                { "name": "counter", "isMut": true, "isSigner": false },
+                { "name": "authority", "isMut": false, "isSigner": true }
+            ],
+            "args": [{ "name": "step", "type": "u32" }, { "name": "owner", "type": "publicKey" }]
+        }]
+    });
+
+    let app = test_app();
+    let (status, body) = register(&app, idl.clone()).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "MISSING_FIELD");
+
+    let (status, body) = post_json_to(app.clone(), "/anchor/idl", json!({ "program_id": pubkey(7), "idl": idl })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let request = json!({
+        "program_id": pubkey(7),
+        "method": "increment_by",
+        "args": { "step": 3, "owner": pubkey(5) },
+        "accounts": { "counter": pubkey(1), "authority": pubkey(2) }
+    });
+    let (status, body) = post_json_to(app, "/anchor/instruction", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let mut expected = discriminator("global:increment_by");
+    expected.extend(3u32.to_le_bytes());
+    expected.extend(pubkey(5).parse::<Pubkey>().unwrap().to_bytes());
+    let data = general_purpose::STANDARD.decode(body["data"]["instruction_data"].as_str().unwrap()).unwrap();
+    assert_eq!(data, expected);
+    assert_eq!(body["data"]["accounts"][1], json!({ "pubkey": pubkey(2), "is_signer": true, "is_writable": false }));
+}
+
+#[tokio::test]
+async fn reports_argument_errors_by_path() {
+    let app = test_app();
+    register(&app, spec_idl()).await;
+    let accounts = json!({ "owner": pubkey(1), "vault": { "state": pubkey(2), "authority": pubkey(3) } });
+
+    let cases = [
+        (json!({ "amount": 1 }), "MISSING_FIELD", "args.params"),
+        (
+            json!({ "amount": -1, "params": { "memo": null, "tags": [], "side": "Bid" } }),
+            "INVALID_FIELD",
+            "args.amount",
+        ),
+        (
+            json!({ "amount": 1, "params": { "memo": null, "tags": [256], "side": "Bid" } }),
+            "INVALID_FIELD",
+            "args.params.tags[0]",
+        ),
+        (
+            json!({ "amount": 1, "params": { "memo": null, "tags": [], "side": "Hold" } }),
+            "INVALID_FIELD",
+            "args.params.side",
+        ),
+        (json!({ "amount": 1, "extra": 2 }), "INVALID_FIELD", "args.extra"),
+    ];
+
+    for (args, code, field) in cases {
+        let request = json!({ "program_id": pubkey(9), "method": "deposit", "args": args, "accounts": accounts });
+        let (status, body) = post_json_to(app.clone(), "/anchor/instruction", request).await;
+        assert_error(status, &body, StatusCode::BAD_REQUEST, code);
+        assert_eq!(body["field"], field, "body: {body}");
+    }
+
+    let request = json!({
+        "program_id": pubkey(9),
+        "method": "deposit",
+        "args": { "amount": 1, "params": { "memo": null, "tags": [], "side": "Bid" } },
+        "accounts": { "owner": pubkey(1) }
+    });
+    let (status, body) = post_json_to(app, "/anchor/instruction", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "MISSING_FIELD");
+    assert_eq!(body["field"], "accounts.vault.state");
+}
+
+#[tokio::test]
+async fn unknown_program_and_method_are_rejected() {
+    let request = json!({ "program_id": pubkey(9), "method": "deposit" });
+    let (status, body) = post_json("/anchor/instruction", request).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+
+    let app = test_app();
+    register(&app, spec_idl()).await;
+    let request = json!({ "program_id": pubkey(9), "method": "withdraw" });
+    let (status, body) = post_json_to(app.clone(), "/anchor/instruction", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "method");
+
+    let (status, body) = register(&app, json!({ "instructions": [{ "name": "x", "args": [{ "name": "a", "type": "u512" }] }] })).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+}
+
+/// Legacy IDL with inline event fields, registered under `pubkey(9)`.
+fn events_idl() -> Value {
+    json!({
+        "version": "0.1.0",
+        "name": "vault",
+        "metadata": { "address": pubkey(9) },
+        "instructions": [],
+        "events": [{
+            "name": "Deposited",
+            "fields": [
+                { "name": "user", "type": "publicKey", "index": false },
+                { "name": "amount", "type": "u64", "index": false }
+            ]
+        }]
+    })
+}
+
+fn deposited(user: u8, amount: u64) -> String {
+    let mut data = discriminator("event:Deposited");
+    data.extend(pubkey(user).parse::<Pubkey>().unwrap().to_bytes());
+    data.extend(amount.to_le_bytes());
+    format!("Program data: {}", general_purpose::STANDARD.encode(data))
+}
+
+fn transaction_logs() -> Vec<String> {
+    let program = pubkey(9);
+    let callee = pubkey(8);
+    vec![
+        format!("Program {program} invoke [1]"),
+        "Program log: Instruction: Deposit".to_string(),
+        format!("Program {callee} invoke [2]"),
+        deposited(3, 1),
+        format!("Program {callee} success"),
+        deposited(1, 500),
+        format!("Program data: {}", general_purpose::STANDARD.encode([0u8; 12])),
+        format!("Program {program} consumed 4000 of 200000 compute units"),
+        format!("Program {program} success"),
+    ]
+}
+
+#[tokio::test]
+async fn parse_logs_decodes_events_of_the_program() {
+    let app = test_app();
+    let (status, body) = register(&app, events_idl()).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["events"], json!(["Deposited"]));
+
+    let request = json!({ "program_id": pubkey(9), "logs": transaction_logs() });
+    let (status, body) = post_json_to(app, "/anchor/parse-logs", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(
+        body["data"]["events"],
+        json!([{ "name": "Deposited", "log_index": 5, "data": { "user": pubkey(1), "amount": "500" } }])
+    );
+}
+
+#[tokio::test]
+async fn parse_logs_fetches_logs_by_signature() {
+    let mock = Arc::new(MockRpc::new());
+    let signature = Signature::from([7; 64]);
+    mock.set_transaction_logs(signature, transaction_logs());
+    let app = mock_app(mock);
+    register(&app, events_idl()).await;
+
+    let request = json!({ "program_id": pubkey(9), "signature": signature.to_string() });
+    let (status, body) = post_json_to(app.clone(), "/anchor/parse-logs", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["events"][0]["data"]["amount"], "500");
+
+    let request = json!({ "program_id": pubkey(9), "signature": Signature::from([8; 64]).to_string() });
+    let (status, body) = post_json_to(app.clone(), "/anchor/parse-logs", request).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+
+    let request = json!({ "program_id": pubkey(9) });
+    let (status, body) = post_json_to(app, "/anchor/parse-logs", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "EXACTLY_ONE");
+}
+
+#[tokio::test]
+async fn parse_logs_reports_truncated_event_data() {
+    let app = test_app();
+    register(&app, events_idl()).await;
+
+    let mut data = discriminator("event:Deposited");
+    data.extend([1, 2, 3]);
+    let logs = vec![
+        format!("Program {} invoke [1]", pubkey(9)),
+        format!("Program data: {}", general_purpose::STANDARD.encode(data)),
+    ];
+    let (status, body) = post_json_to(app, "/anchor/parse-logs", json!({ "program_id": pubkey(9), "logs": logs })).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "logs[1].user");
+}
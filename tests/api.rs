@@ -0,0 +1,627 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::json;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::{Message, MessageHeader};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::{keypair::Keypair, Signer};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+use solana_fellowship_server::config::{Config, MessagesConfig, Mode};
+use solana_fellowship_server::rpc::MockRpc;
+
+use common::{
+    app_with, assert_error, assert_golden, call, keypair, mint_account, mock_app, post_json, post_json_to, post_raw, pubkey,
+    test_app,
+};
+
+#[tokio::test]
+async fn keypair_returns_matching_secret() {
+    let (status, body) = post_json("/keypair", json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["success"], true);
+
+    let secret = bs58::decode(body["data"]["secret"].as_str().unwrap()).into_vec().unwrap();
+    let keypair = Keypair::try_from(&secret[..]).unwrap();
+    assert_eq!(body["data"]["pubkey"], keypair.pubkey().to_string());
+}
+
+#[tokio::test]
+async fn encrypted_export_round_trips_through_the_keystore() {
+    let app = test_app();
+    let secret = keypair(7).to_base58_string();
+    let request = json!({ "secret": secret, "passphrase": "correct horse battery" });
+    let (status, body) = post_json_to(app.clone(), "/keypair/export-encrypted", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let encrypted = body["data"].clone();
+    assert_eq!(encrypted["pubkey"], keypair(7).pubkey().to_string());
+    assert!(!body.to_string().contains(&secret));
+
+    let request = json!({ "encrypted": encrypted, "passphrase": "wrong horse battery" });
+    let (status, body) = post_json_to(app.clone(), "/keypair/import-encrypted", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "DECRYPTION_FAILED");
+
+    // The pubkey is authenticated, so relabelling the backup breaks it.
+    let mut relabelled = encrypted.clone();
+    relabelled["pubkey"] = json!(pubkey(8));
+    let request = json!({ "encrypted": relabelled, "passphrase": "correct horse battery" });
+    let (status, body) = post_json_to(app.clone(), "/keypair/import-encrypted", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "DECRYPTION_FAILED");
+
+    let request = json!({ "encrypted": encrypted, "passphrase": "correct horse battery" });
+    let (status, body) = post_json_to(app.clone(), "/keypair/import-encrypted", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["pubkey"], keypair(7).pubkey().to_string());
+    assert!(body["data"].get("secret").is_none());
+
+    let request = json!({ "message": "hi", "key_id": body["data"]["key_id"] });
+    let (status, body) = post_json_to(app.clone(), "/message/sign", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["public_key"], keypair(7).pubkey().to_string());
+
+    let request = json!({ "secret": secret, "passphrase": "short" });
+    let (status, body) = post_json_to(app, "/keypair/export-encrypted", request).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "PASSPHRASE_TOO_SHORT");
+}
+
+#[tokio::test]
+async fn create_token_builds_initialize_mint() {
+    let (status, body) = post_json(
+        "/token/create",
+        json!({ "mintAuthority": pubkey(1), "mint": pubkey(2), "decimals": 6 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_golden("create_token", &body);
+}
+
+#[tokio::test]
+async fn create_token_rejects_invalid_authority() {
+    let (status, body) = post_json(
+        "/token/create",
+        json!({ "mintAuthority": "not-a-key", "mint": pubkey(2), "decimals": 6 }),
+    )
+    .await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_PUBKEY");
+    assert_eq!(body["field"], "mintAuthority");
+}
+
+#[tokio::test]
+async fn create_token_rejects_excess_decimals() {
+    let (status, body) = post_json(
+        "/token/create",
+        json!({ "mintAuthority": pubkey(1), "mint": pubkey(2), "decimals": 10 }),
+    )
+    .await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "DECIMALS_OUT_OF_RANGE");
+}
+
+#[tokio::test]
+async fn mint_token_builds_mint_to() {
+    let (status, body) = post_json(
+        "/token/mint",
+        json!({ "mint": pubkey(2), "destination": pubkey(3), "authority": pubkey(1), "amount": 1_000_000 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_golden("mint_token", &body);
+}
+
+#[tokio::test]
+async fn mint_token_reports_missing_field() {
+    let (status, body) = post_json(
+        "/token/mint",
+        json!({ "destination": pubkey(3), "authority": pubkey(1), "amount": 1 }),
+    )
+    .await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "MISSING_FIELD");
+    assert_eq!(body["field"], "mint");
+}
+
+#[tokio::test]
+async fn mint_token_needs_exactly_one_amount() {
+    for amount in [json!({}), json!({ "amount": 1, "ui_amount": "1" })] {
+        let mut request = json!({ "mint": pubkey(2), "destination": pubkey(3), "authority": pubkey(1) });
+        request.as_object_mut().unwrap().extend(amount.as_object().unwrap().clone());
+        let (status, body) = post_json("/token/mint", request).await;
+        assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+        assert_eq!(body["details"][0]["code"], "EXACTLY_ONE");
+    }
+}
+
+#[tokio::test]
+async fn mint_token_converts_ui_amount_with_checked_instruction() {
+    let (status, body) = post_json(
+        "/token/mint",
+        json!({ "mint": pubkey(2), "destination": pubkey(3), "authority": pubkey(1), "ui_amount": "1.5", "decimals": 6 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data = general_purpose::STANDARD.decode(body["data"]["instruction_data"].as_str().unwrap()).unwrap();
+    // MintToChecked: tag 14, amount, decimals.
+    assert_eq!(data[0], 14);
+    assert_eq!(data[1..9], 1_500_000u64.to_le_bytes());
+    assert_eq!(data[9], 6);
+
+    let (status, body) = post_json(
+        "/token/mint",
+        json!({ "mint": pubkey(2), "destination": pubkey(3), "authority": pubkey(1), "ui_amount": "1.0000001", "decimals": 6 }),
+    )
+    .await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["field"], "ui_amount");
+    assert_eq!(body["details"][0]["code"], "AMOUNT_PRECISION");
+}
+
+#[tokio::test]
+async fn mint_token_rejects_zero_amount() {
+    let (status, body) = post_json(
+        "/token/mint",
+        json!({ "mint": pubkey(2), "destination": pubkey(3), "authority": pubkey(1), "amount": 0 }),
+    )
+    .await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["field"], "amount");
+}
+
+#[tokio::test]
+async fn sign_message_is_deterministic() {
+    let (status, body) = post_json(
+        "/message/sign",
+        json!({ "message": "Hello, Solana!", "secret": keypair(7).to_base58_string() }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_golden("sign_message", &body);
+}
+
+#[tokio::test]
+async fn sign_message_accepts_secret_in_any_encoding() {
+    let signer = keypair(7);
+    let bytes = signer.to_bytes();
+    let expected = general_purpose::STANDARD.encode(signer.sign_message(b"hi").as_ref());
+    let secrets = [
+        json!(signer.to_base58_string()),
+        json!(hex::encode(bytes)),
+        json!(general_purpose::STANDARD.encode(bytes)),
+        json!(bytes.to_vec()),
+    ];
+    for secret in secrets {
+        let (status, body) = post_json("/message/sign", json!({ "message": "hi", "secret": secret })).await;
+        assert_eq!(status, StatusCode::OK, "secret {secret}: {body}");
+        assert_eq!(body["data"]["signature"], expected);
+    }
+
+    let (status, body) = post_json("/message/sign", json!({ "message": "hi", "secret": [1, 2, 3] })).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "SECRET_WRONG_LENGTH");
+}
+
+#[tokio::test]
+async fn sign_message_rejects_short_secret() {
+    let (status, body) = post_json(
+        "/message/sign",
+        json!({ "message": "hi", "secret": bs58::encode([1u8; 32]).into_string() }),
+    )
+    .await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "SECRET_WRONG_LENGTH");
+    assert_eq!(body["field"], "secret");
+}
+
+#[tokio::test]
+async fn sign_message_rejects_oversized_message() {
+    let (status, body) = post_json(
+        "/message/sign",
+        json!({ "message": "x".repeat(2048), "secret": keypair(7).to_base58_string() }),
+    )
+    .await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "MESSAGE_TOO_LONG");
+}
+
+#[tokio::test]
+async fn sign_message_needs_exactly_one_signer() {
+    let (status, body) = post_json("/message/sign", json!({ "message": "hi" })).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "EXACTLY_ONE");
+
+    let (status, body) = post_json("/message/sign", json!({ "message": "hi", "key_id": "missing" })).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "UNKNOWN_KEY_ID");
+    assert_eq!(body["field"], "key_id");
+}
+
+#[tokio::test]
+async fn production_mode_keeps_secrets_in_the_keystore() {
+    let config = Config { mode: Mode::Production, ..Config::default() };
+    let app = app_with(config, Arc::new(MockRpc::new()));
+
+    let (status, body) = post_json_to(app.clone(), "/keypair", json!({})).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert!(body["data"].get("secret").is_none());
+    let pubkey = body["data"]["pubkey"].clone();
+    let key_id = body["data"]["key_id"].as_str().unwrap().to_string();
+
+    let request = json!({ "message": "hi", "key_id": key_id });
+    let (status, body) = post_json_to(app.clone(), "/message/sign", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["public_key"], pubkey);
+
+    let request = json!({ "message": "hi", "secret": keypair(7).to_base58_string() });
+    let (status, body) = post_json_to(app.clone(), "/message/sign", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "RAW_SECRET_DISABLED");
+    assert_eq!(body["field"], "secret");
+
+    let request = json!({
+        "signers": [{ "key_id": key_id }, keypair(7).to_base58_string()],
+        "instructions": [{ "program_id": pubkey, "accounts": [], "data": "" }],
+        "execute_at": "2020-01-01T00:00:00Z",
+    });
+    let (status, body) = post_json_to(app, "/jobs", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "RAW_SECRET_DISABLED");
+    assert_eq!(body["field"], "signers[1]");
+}
+
+#[tokio::test]
+async fn domain_tag_separates_message_signatures() {
+    let messages = MessagesConfig { domain: Some("superdev:v1\n".to_string()) };
+    let app = app_with(Config { messages, ..Config::default() }, Arc::new(MockRpc::new()));
+    let signer = keypair(7);
+
+    let request = json!({ "message": "hi", "secret": signer.to_base58_string() });
+    let (status, body) = post_json_to(app.clone(), "/message/sign", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["domain"], "superdev:v1\n");
+    let expected = general_purpose::STANDARD.encode(signer.sign_message(b"superdev:v1\nhi").as_ref());
+    assert_eq!(body["data"]["signature"], expected);
+
+    let verify = json!({ "message": "hi", "signature": expected, "pubkey": signer.pubkey().to_string() });
+    let (_, body) = post_json_to(app.clone(), "/message/verify", verify.clone()).await;
+    assert_eq!(body["data"]["valid"], true);
+    let (_, body) = post_json("/message/verify", verify).await;
+    assert_eq!(body["data"]["valid"], false);
+
+    // A tag can't be made to line up with a transaction message either.
+    let message = Message {
+        header: MessageHeader {
+            num_required_signatures: 1,
+            num_readonly_signed_accounts: 0,
+            num_readonly_unsigned_accounts: 1,
+        },
+        account_keys: vec![Pubkey::new_from_array([b'a'; 32]), Pubkey::new_from_array([b'b'; 32])],
+        recent_blockhash: Hash::new_from_array([b'c'; 32]),
+        instructions: vec![],
+    };
+    let bytes = message.serialize();
+    let (tag, rest) = bytes.split_at(1);
+    let messages = MessagesConfig { domain: Some(String::from_utf8(tag.to_vec()).unwrap()) };
+    let app = app_with(Config { messages, ..Config::default() }, Arc::new(MockRpc::new()));
+    let request = json!({ "message": String::from_utf8(rest.to_vec()).unwrap(), "secret": signer.to_base58_string() });
+    let (status, body) = post_json_to(app, "/message/sign", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "TRANSACTION_MESSAGE");
+    assert_eq!(body["field"], "message");
+}
+
+#[tokio::test]
+async fn verify_message_accepts_valid_and_rejects_tampered() {
+    let signer = keypair(7);
+    let signature = general_purpose::STANDARD.encode(signer.sign_message(b"Hello, Solana!").as_ref());
+
+    let (status, body) = post_json(
+        "/message/verify",
+        json!({ "message": "Hello, Solana!", "signature": signature, "pubkey": signer.pubkey().to_string() }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["valid"], true);
+
+    let (status, body) = post_json(
+        "/message/verify",
+        json!({ "message": "Hello, Solana?", "signature": signature, "pubkey": signer.pubkey().to_string() }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["valid"], false);
+}
+
+#[tokio::test]
+async fn signatures_round_trip_as_base58() {
+    let signer = keypair(7);
+    let request = json!({ "message": "hi", "secret": signer.to_base58_string(), "signature_encoding": "base58" });
+    let (status, body) = post_json("/message/sign", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let signature = body["data"]["signature"].as_str().unwrap().to_string();
+    assert_eq!(signature, signer.sign_message(b"hi").to_string());
+
+    let request = json!({ "message": "hi", "signature": signature, "pubkey": signer.pubkey().to_string() });
+    let (status, body) = post_json("/message/verify", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["valid"], true);
+
+    let items = json!([{ "message": "hi", "signature": signature, "pubkey": signer.pubkey().to_string() }]);
+    let (status, body) = post_json("/message/verify-batch", json!({ "items": items })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["valid_count"], 1);
+}
+
+#[tokio::test]
+async fn verify_message_rejects_bad_signature_encoding() {
+    let (status, body) = post_json(
+        "/message/verify",
+        json!({ "message": "hi", "signature": "!!!", "pubkey": pubkey(1) }),
+    )
+    .await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "SIGNATURE_INVALID_BASE64");
+    assert_eq!(body["field"], "signature");
+}
+
+#[tokio::test]
+async fn send_sol_builds_system_transfer() {
+    let (status, body) = post_json(
+        "/send/sol",
+        json!({ "from": pubkey(1), "to": pubkey(2), "lamports": 100_000 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_golden("send_sol", &body);
+}
+
+#[tokio::test]
+async fn send_sol_keeps_its_old_shape_under_v1() {
+    let (status, body) = post_json(
+        "/v1/send/sol",
+        json!({ "from": pubkey(1), "to": pubkey(2), "lamports": 100_000 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["program_id"], "11111111111111111111111111111111");
+    assert_eq!(body["data"]["accounts"], json!([pubkey(1), pubkey(2)]));
+    assert_eq!(body["data"]["instruction_data"], "AgAAAKCGAQAAAAAA");
+}
+
+#[tokio::test]
+async fn send_sol_rejects_self_transfer() {
+    let (status, body) = post_json(
+        "/send/sol",
+        json!({ "from": pubkey(1), "to": pubkey(1), "lamports": 100_000 }),
+    )
+    .await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "SELF_TRANSFER");
+}
+
+#[tokio::test]
+async fn send_sol_accepts_decimal_sol() {
+    let (status, body) = post_json(
+        "/send/sol",
+        json!({ "from": pubkey(1), "to": pubkey(2), "amount_sol": "0.0001" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_golden("send_sol", &body);
+
+    let cases = [
+        (json!({ "lamports": 5, "amount_sol": "1" }), "lamports", "EXACTLY_ONE"),
+        (json!({}), "lamports", "EXACTLY_ONE"),
+        (json!({ "amount_sol": "0.0000000001" }), "amount_sol", "AMOUNT_PRECISION"),
+        (json!({ "amount_sol": "0.000" }), "amount_sol", "AMOUNT_ZERO"),
+        (json!({ "amount_sol": "18446744074" }), "amount_sol", "AMOUNT_TOO_LARGE"),
+        (json!({ "amount_sol": "-1" }), "amount_sol", "INVALID_DECIMAL_AMOUNT"),
+    ];
+    for (amount, field, code) in cases {
+        let mut request = json!({ "from": pubkey(1), "to": pubkey(2) });
+        request.as_object_mut().unwrap().extend(amount.as_object().unwrap().clone());
+        let (status, body) = post_json("/send/sol", request).await;
+        assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+        assert_eq!(body["details"][0]["field"], field, "{body}");
+        assert_eq!(body["details"][0]["code"], code, "{body}");
+    }
+}
+
+#[tokio::test]
+async fn builders_return_web3js_instructions_on_request() {
+    let (status, body) = post_json(
+        "/send/sol",
+        json!({ "from": pubkey(1), "to": pubkey(2), "lamports": 100_000, "output_format": "web3js" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["programId"], "11111111111111111111111111111111");
+    assert_eq!(
+        body["data"]["keys"],
+        json!([
+            { "pubkey": pubkey(1), "isSigner": true, "isWritable": true },
+            { "pubkey": pubkey(2), "isSigner": false, "isWritable": true },
+        ])
+    );
+    let data = general_purpose::STANDARD.decode(body["data"]["data"].as_str().unwrap()).unwrap();
+    assert_eq!(data[..4], [2, 0, 0, 0]);
+
+    let (status, body) = post_json(
+        "/token/mint",
+        json!({ "mint": pubkey(1), "destination": pubkey(2), "authority": pubkey(3), "amount": 5, "output_format": "web3js" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["keys"][2], json!({ "pubkey": pubkey(3), "isSigner": true, "isWritable": false }));
+
+    let (status, body) = post_json(
+        "/send/sol",
+        json!({ "from": pubkey(1), "to": pubkey(2), "lamports": 1, "output_format": "anchor" }),
+    )
+    .await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+}
+
+#[tokio::test]
+async fn send_sol_rejects_dust_below_the_configured_threshold() {
+    let mut config = Config::default();
+    config.transfers.dust_threshold_lamports = Some(1_000);
+    let app = app_with(config, Arc::new(MockRpc::new()));
+
+    let request = json!({ "from": pubkey(1), "to": pubkey(2), "amount_sol": "0.000000999" });
+    let (status, body) = post_json_to(app.clone(), "/send/sol", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "BELOW_DUST_THRESHOLD");
+    assert_eq!(body["field"], "amount_sol");
+
+    let request = json!({ "from": pubkey(1), "to": pubkey(2), "lamports": 1_000 });
+    let (status, _) = post_json_to(app, "/send/sol", request).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn send_token_rejects_same_token_account() {
+    let (status, body) = post_json(
+        "/send/token",
+        json!({ "destination": pubkey(1), "mint": pubkey(2), "owner": pubkey(1), "amount": 500 }),
+    )
+    .await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["field"], "destination");
+    assert_eq!(body["details"][0]["code"], "SAME_TOKEN_ACCOUNT");
+}
+
+#[tokio::test]
+async fn send_token_builds_ata_transfer() {
+    let (status, body) = post_json(
+        "/send/token",
+        json!({ "destination": pubkey(3), "mint": pubkey(2), "owner": pubkey(1), "amount": 500 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_golden("send_token", &body);
+}
+
+#[tokio::test]
+async fn send_token_reads_decimals_from_the_mint_for_ui_amounts() {
+    let mock = Arc::new(MockRpc::new());
+    let mint = Pubkey::new_unique();
+    mock.set_account(mint, mint_account(spl_token::id(), 2));
+    let app = mock_app(mock);
+
+    let request = json!({ "destination": pubkey(3), "mint": mint.to_string(), "owner": pubkey(1), "ui_amount": "12.34" });
+    let (status, body) = post_json_to(app.clone(), "/send/token", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    // TransferChecked: tag 12, amount, decimals; the mint sits between the two ATAs.
+    let data = general_purpose::STANDARD.decode(body["data"]["instruction_data"].as_str().unwrap()).unwrap();
+    assert_eq!(data[0], 12);
+    assert_eq!(data[1..9], 1234u64.to_le_bytes());
+    assert_eq!(data[9], 2);
+    assert_eq!(body["data"]["accounts"][1]["pubkey"], mint.to_string());
+
+    let request = json!({ "destination": pubkey(3), "mint": mint.to_string(), "owner": pubkey(1), "ui_amount": "0.001" });
+    let (status, body) = post_json_to(app, "/send/token", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "AMOUNT_PRECISION");
+    assert_eq!(body["field"], "ui_amount");
+}
+
+#[tokio::test]
+async fn token_builders_follow_the_mint_owner_when_rpc_is_configured() {
+    let mock = Arc::new(MockRpc::new());
+    let (legacy, token_2022) = (Pubkey::new_unique(), Pubkey::new_unique());
+    mock.set_account(legacy, mint_account(spl_token::id(), 6));
+    mock.set_account(token_2022, mint_account(spl_token_2022::id(), 6));
+    let app = mock_app(mock);
+
+    let request = json!({ "destination": pubkey(3), "mint": token_2022.to_string(), "owner": pubkey(1), "amount": 500 });
+    let (status, body) = post_json_to(app.clone(), "/send/token", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["program_id"], spl_token_2022::id().to_string());
+    let owner = Pubkey::new_from_array([1; 32]);
+    let source = get_associated_token_address_with_program_id(&owner, &token_2022, &spl_token_2022::id());
+    assert_eq!(body["data"]["source_ata"], source.to_string());
+    // Token-2022 only gets TransferChecked, with decimals read from the mint.
+    let data = general_purpose::STANDARD.decode(body["data"]["instruction_data"].as_str().unwrap()).unwrap();
+    assert_eq!((data[0], data[9]), (12, 6));
+
+    let request = json!({ "mint": legacy.to_string(), "destination": pubkey(3), "authority": pubkey(1), "amount": 5 });
+    let (status, body) = post_json_to(app, "/token/mint", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["program_id"], spl_token::id().to_string());
+    let data = general_purpose::STANDARD.decode(body["data"]["instruction_data"].as_str().unwrap()).unwrap();
+    assert_eq!(data[0], 7);
+}
+
+#[tokio::test]
+async fn send_token_lets_a_delegate_or_multisig_sign() {
+    let (status, body) = post_json(
+        "/send/token",
+        json!({ "destination": pubkey(3), "mint": pubkey(2), "owner": pubkey(1), "delegate": pubkey(4), "amount": 5 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["accounts"][2], json!({ "pubkey": pubkey(4), "isSigner": true }));
+    assert_eq!(body["data"]["required_signers"], json!([pubkey(4)]));
+
+    let request = json!({
+        "destination": pubkey(3), "mint": pubkey(2), "owner": pubkey(1), "amount": 5,
+        "multisig_signers": [pubkey(5), pubkey(6)],
+    });
+    let (status, body) = post_json("/send/token", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["accounts"][2], json!({ "pubkey": pubkey(1), "isSigner": false }));
+    assert_eq!(body["data"]["required_signers"], json!([pubkey(5), pubkey(6)]));
+
+    let cases = [
+        (json!({ "delegate": pubkey(1) }), "delegate", "DELEGATE_IS_OWNER"),
+        (json!({ "multisig_signers": [pubkey(5), pubkey(5)] }), "multisig_signers", "DUPLICATE_SIGNER"),
+        (json!({ "delegate": pubkey(4), "multisig_signers": [pubkey(4)] }), "multisig_signers", "DUPLICATE_SIGNER"),
+        (json!({ "multisig_signers": (10..22).map(pubkey).collect::<Vec<_>>() }), "multisig_signers", "TOO_MANY_SIGNERS"),
+    ];
+    for (extra, field, code) in cases {
+        let mut request = json!({ "destination": pubkey(3), "mint": pubkey(2), "owner": pubkey(1), "amount": 5 });
+        request.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+        let (status, body) = post_json("/send/token", request).await;
+        assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+        assert_eq!(body["details"][0]["field"], field, "{body}");
+        assert_eq!(body["details"][0]["code"], code, "{body}");
+    }
+}
+
+#[tokio::test]
+async fn send_token_rejects_invalid_owner() {
+    let (status, body) = post_json(
+        "/send/token",
+        json!({ "destination": pubkey(3), "mint": pubkey(2), "owner": "abc", "amount": 500 }),
+    )
+    .await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_PUBKEY");
+    assert_eq!(body["field"], "owner");
+}
+
+#[tokio::test]
+async fn malformed_json_uses_error_envelope() {
+    let (status, body) = post_raw("/send/sol", Some("application/json"), "{\"from\":").await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "MALFORMED_JSON");
+}
+
+#[tokio::test]
+async fn missing_content_type_uses_error_envelope() {
+    let (status, body) = post_raw("/send/sol", None, "{}").await;
+    assert_error(status, &body, StatusCode::UNSUPPORTED_MEDIA_TYPE, "UNSUPPORTED_MEDIA_TYPE");
+}
+
+#[tokio::test]
+async fn metrics_count_requests_by_route() {
+    let app = test_app();
+    let request = Request::post("/send/sol")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(json!({ "from": pubkey(1), "to": pubkey(2), "lamports": 1 }).to_string()))
+        .unwrap();
+    call(app.clone(), request).await;
+
+    let (status, _, body) = call(app, Request::get("/metrics").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+    let text = String::from_utf8(body.to_vec()).unwrap();
+    assert!(
+        text.contains(r#"superdev_http_requests_total{method="POST",route="/send/sol",status="200"} 1"#),
+        "{text}"
+    );
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::{http::StatusCode, Router};
+use serde_json::{json, Value};
+
+use solana_fellowship_server::config::Config;
+use solana_fellowship_server::das::MockDas;
+use solana_fellowship_server::state::AppState;
+
+use common::{assert_error, get_json_from, pubkey};
+
+fn app(das: Arc<MockDas>) -> Router {
+    let mut state = AppState::new(Config::default());
+    state.das = Some(das);
+    solana_fellowship_server::app(state)
+}
+
+/// A DAS asset object shaped like the providers' `getAsset` results.
+fn das_asset(id: u8, owner: u8, compressed: bool) -> Value {
+    let tree = if compressed { pubkey(60) } else { String::new() };
+    json!({
+        "id": pubkey(id),
+        "interface": "V1_NFT",
+        "content": {
+            "json_uri": format!("https://example.com/{id}.json"),
+            "metadata": { "name": format!("Asset #{id}"), "symbol": "AST" },
+            "files": [{ "uri": format!("https://example.com/{id}.png"), "mime": "image/png" }],
+        },
+        "grouping": [{ "group_key": "collection", "group_value": pubkey(50) }],
+        "royalty": { "basis_points": 250 },
+        "compression": { "compressed": compressed, "tree": tree, "leaf_id": 7 },
+        "ownership": { "owner": pubkey(owner), "delegate": null, "frozen": false },
+        "burnt": false,
+    })
+}
+
+fn das_mock() -> Arc<MockDas> {
+    let das = Arc::new(MockDas::new());
+    das.set_asset(das_asset(10, 1, false));
+    das.set_asset(das_asset(11, 1, true));
+    das.set_asset(das_asset(12, 2, false));
+    das
+}
+
+#[tokio::test]
+async fn asset_is_normalized() {
+    let (status, body) = get_json_from(app(das_mock()), &format!("/assets/{}", pubkey(11))).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(
+        body["data"],
+        json!({
+            "id": pubkey(11),
+            "interface": "V1_NFT",
+            "compressed": true,
+            "owner": pubkey(1),
+            "delegate": null,
+            "frozen": false,
+            "burnt": false,
+            "name": "Asset #11",
+            "symbol": "AST",
+            "uri": "https://example.com/11.json",
+            "image": "https://example.com/11.png",
+            "collection": pubkey(50),
+            "royalty_basis_points": 250,
+            "tree": pubkey(60),
+            "leaf_id": 7,
+        })
+    );
+
+    let (status, body) = get_json_from(app(das_mock()), &format!("/assets/{}", pubkey(13))).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+}
+
+#[tokio::test]
+async fn assets_by_owner_lists_regular_and_compressed() {
+    let app = app(das_mock());
+
+    let (status, body) = get_json_from(app.clone(), &format!("/assets/by-owner?owner={}", pubkey(1))).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data = &body["data"];
+    assert_eq!((data["page"].clone(), data["limit"].clone(), data["total"].clone()), (json!(1), json!(100), json!(2)));
+    let compressed: Vec<_> = data["items"].as_array().unwrap().iter().map(|item| item["compressed"].clone()).collect();
+    assert_eq!(compressed, [json!(false), json!(true)]);
+
+    let (_, body) = get_json_from(app.clone(), &format!("/assets/by-owner?owner={}&page=2&limit=1", pubkey(1))).await;
+    assert_eq!(body["data"]["items"][0]["id"], pubkey(11));
+
+    let (status, body) = get_json_from(app, &format!("/assets/by-owner?owner={}&limit=5000", pubkey(1))).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "BATCH_SIZE");
+}
+
+#[tokio::test]
+async fn assets_need_a_das_provider() {
+    let app = solana_fellowship_server::app(AppState::new(Config::default()));
+    let (status, body) = get_json_from(app, &format!("/assets/{}", pubkey(10))).await;
+    assert_error(status, &body, StatusCode::SERVICE_UNAVAILABLE, "DAS_UNAVAILABLE");
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::signer::Signer;
+
+use solana_fellowship_server::config::{Config, Mode};
+use solana_fellowship_server::crypto::CryptoPool;
+use solana_fellowship_server::rpc::MockRpc;
+use solana_fellowship_server::state::AppState;
+
+use common::{app_with, assert_error, call, keypair, post_json, pubkey, test_app};
+
+fn batch() -> Value {
+    let signer = keypair(7);
+    let signature = general_purpose::STANDARD.encode(signer.sign_message(b"hello").as_ref());
+    json!({
+        "items": [
+            { "message": "hello", "signature": signature, "pubkey": signer.pubkey().to_string() },
+            { "message": "tampered", "signature": signature, "pubkey": signer.pubkey().to_string() },
+            { "message": "hello", "signature": "not base64!", "pubkey": pubkey(1) },
+        ]
+    })
+}
+
+#[tokio::test]
+async fn verify_batch_reports_each_item() {
+    let (status, body) = post_json("/message/verify-batch", batch()).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let data = &body["data"];
+    assert_eq!(data["total"], 3);
+    assert_eq!(data["valid_count"], 1);
+    assert_eq!(data["results"][0], json!({ "index": 0, "valid": true }));
+    assert_eq!(data["results"][1], json!({ "index": 1, "valid": false }));
+    assert_eq!(data["results"][2]["valid"], false);
+    assert_eq!(data["results"][2]["error"]["code"], "SIGNATURE_INVALID_BASE64");
+}
+
+#[tokio::test]
+async fn verify_batch_streams_ndjson_when_requested() {
+    let request = Request::post("/message/verify-batch")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::ACCEPT, "application/x-ndjson")
+        .body(Body::from(batch().to_string()))
+        .unwrap();
+
+    let (status, headers, bytes) = call(test_app(), request).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::CONTENT_TYPE], "application/x-ndjson");
+
+    let lines: Vec<Value> = std::str::from_utf8(&bytes)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["valid"], true);
+    assert_eq!(lines[2]["index"], 2);
+}
+
+#[tokio::test]
+async fn verify_batch_rejects_empty_batch() {
+    let (status, body) = post_json("/message/verify-batch", json!({ "items": [] })).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "BATCH_SIZE");
+}
+
+fn keypair_batch(count: usize, accept: &str) -> Request<Body> {
+    Request::post(format!("/keypair?count={count}")).header(header::ACCEPT, accept).body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn keypair_count_returns_distinct_keypairs() {
+    let (status, _, bytes) = call(test_app(), keypair_batch(130, "application/json")).await;
+    assert_eq!(status, StatusCode::OK);
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    let keypairs = body["data"].as_array().unwrap();
+    assert_eq!(keypairs.len(), 130);
+    let pubkeys: HashSet<_> = keypairs.iter().map(|keypair| keypair["pubkey"].as_str().unwrap()).collect();
+    assert_eq!(pubkeys.len(), 130);
+    assert!(keypairs.iter().all(|keypair| keypair["secret"].is_string()));
+
+    for count in [0, 1_001] {
+        let (status, _, bytes) = call(test_app(), keypair_batch(count, "application/json")).await;
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_error(status, &body, StatusCode::BAD_REQUEST, "BATCH_SIZE");
+    }
+}
+
+#[tokio::test]
+async fn keypair_count_streams_ndjson_and_keeps_production_keys_in_the_keystore() {
+    let config = Config { mode: Mode::Production, ..Config::default() };
+    let app = app_with(config, Arc::new(MockRpc::new()));
+    let (status, headers, bytes) = call(app, keypair_batch(70, "application/x-ndjson")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::CONTENT_TYPE], "application/x-ndjson");
+    let lines: Vec<Value> =
+        std::str::from_utf8(&bytes).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(lines.len(), 70);
+    assert!(lines.iter().all(|line| line["key_id"].is_string() && line.get("secret").is_none()));
+}
+
+#[tokio::test]
+async fn keypair_stream_reports_chunks_that_fail() {
+    let mut state = AppState::new(Config::default());
+    // A pool without threads refuses every task.
+    state.crypto = Arc::new(CryptoPool::new(Some(0)));
+    let app = solana_fellowship_server::app(state);
+    let (status, _, bytes) = call(app, keypair_batch(70, "application/x-ndjson")).await;
+    assert_eq!(status, StatusCode::OK);
+    let lines: Vec<Value> =
+        std::str::from_utf8(&bytes).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    // One error line for each chunk of up to 64 keypairs.
+    assert_eq!(lines.len(), 2);
+    assert!(lines.iter().all(|line| line["error"]["code"] == "INTERNAL" && line.get("pubkey").is_none()));
+}
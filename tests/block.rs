@@ -0,0 +1,93 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use solana_sdk::{
+    hash::Hash,
+    message::{Message, VersionedMessage},
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+use solana_system_interface::instruction as system_instruction;
+
+use solana_fellowship_server::rpc::{ConfirmedBlock, ConfirmedTransaction, MockRpc, TransactionMeta};
+
+use common::{assert_error, get_json_from, key, mock_app, pubkey};
+
+/// `from` sends `to` one lamport, signed as `signature`.
+fn transfer(signature: u8, from: u8, to: u8) -> ConfirmedTransaction {
+    let instruction = system_instruction::transfer(&key(from), &key(to), 1);
+    let message = Message::new_with_blockhash(&[instruction], Some(&key(from)), &Hash::new_from_array([3; 32]));
+    let keys = message.account_keys.len();
+    let pre_balances = vec![10_000; keys];
+    let mut post_balances = pre_balances.clone();
+    post_balances[0] -= 5_001;
+    post_balances[1] += 1;
+
+    ConfirmedTransaction {
+        slot: 500,
+        block_time: Some(1_700_000_000),
+        transaction: VersionedTransaction {
+            signatures: vec![Signature::from([signature; 64])],
+            message: VersionedMessage::Legacy(message),
+        },
+        meta: TransactionMeta { fee: 5_000, pre_balances, post_balances, ..TransactionMeta::default() },
+    }
+}
+
+fn block_mock() -> Arc<MockRpc> {
+    let mock = Arc::new(MockRpc::new());
+    mock.set_block(ConfirmedBlock {
+        slot: 500,
+        blockhash: Hash::new_from_array([5; 32]),
+        parent_slot: 499,
+        block_time: Some(1_700_000_000),
+        block_height: Some(480),
+        transactions: vec![transfer(1, 1, 2), transfer(2, 3, 4), transfer(3, 2, 5)],
+    });
+    mock
+}
+
+#[tokio::test]
+async fn block_transactions_are_summarized_and_paged() {
+    let app = mock_app(block_mock());
+
+    let (status, body) = get_json_from(app.clone(), "/block/500?limit=2").await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data = &body["data"];
+    assert_eq!(data["blockhash"], Hash::new_from_array([5; 32]).to_string());
+    assert_eq!(data["parent_slot"], 499);
+    assert_eq!(data["transaction_count"], 3);
+    assert_eq!(data["next_offset"], 2);
+    assert_eq!(data["transactions"][0]["signature"], Signature::from([1; 64]).to_string());
+    assert_eq!(
+        data["transactions"][0]["sol_changes"][1],
+        json!({ "account": pubkey(2), "pre": "10000", "post": "10001", "change": "1" })
+    );
+
+    let (_, body) = get_json_from(app, "/block/500?offset=2").await;
+    assert_eq!(body["data"]["transactions"].as_array().unwrap().len(), 1);
+    assert!(body["data"].get("next_offset").is_none());
+}
+
+#[tokio::test]
+async fn block_transactions_filter_by_address() {
+    let (status, body) = get_json_from(mock_app(block_mock()), &format!("/block/500?address={}", pubkey(2))).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let signatures: Vec<_> =
+        body["data"]["transactions"].as_array().unwrap().iter().map(|tx| tx["signature"].clone()).collect();
+    assert_eq!(signatures, [json!(Signature::from([1; 64]).to_string()), json!(Signature::from([3; 64]).to_string())]);
+    assert_eq!(body["data"]["transaction_count"], 2);
+}
+
+#[tokio::test]
+async fn missing_blocks_are_not_found() {
+    let app = mock_app(block_mock());
+    let (status, body) = get_json_from(app.clone(), "/block/501").await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+
+    let (status, body) = get_json_from(app, "/block/latest").await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+}
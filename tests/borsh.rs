@@ -0,0 +1,114 @@
+mod common;
+
+use axum::http::StatusCode;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+
+use common::{assert_error, post_json, pubkey};
+
+fn order_schema() -> Value {
+    json!({
+        "type": { "defined": "Order" },
+        "types": [
+            { "name": "Order", "type": { "kind": "struct", "fields": [
+                { "name": "owner", "type": "pubkey" },
+                { "name": "size", "type": "u128" },
+                { "name": "price", "type": "i32" },
+                { "name": "note", "type": { "option": "string" } },
+                { "name": "fills", "type": { "vec": { "defined": "Fill" } } },
+                { "name": "side", "type": { "defined": "Side" } },
+                { "name": "seed", "type": { "array": ["u8", 2] } }
+            ]}},
+            { "name": "Fill", "type": { "kind": "struct", "fields": ["u16", "bool"] } },
+            { "name": "Side", "type": { "kind": "enum", "variants": [
+                { "name": "Bid" },
+                { "name": "Ask", "fields": ["u8"] }
+            ]}}
+        ]
+    })
+}
+
+#[tokio::test]
+async fn encode_and_decode_round_trip() {
+    let value = json!({
+        "owner": pubkey(1),
+        "size": "340282366920938463463374607431768211455",
+        "price": -5,
+        "note": null,
+        "fills": [[7, true], [9, false]],
+        "side": { "Ask": [3] },
+        "seed": [1, 2]
+    });
+
+    let (status, body) = post_json("/borsh/encode", json!({ "schema": order_schema(), "value": value })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data = body["data"]["data"].as_str().unwrap().to_string();
+    let bytes = general_purpose::STANDARD.decode(&data).unwrap();
+    assert_eq!(body["data"]["size"], bytes.len());
+    assert_eq!(body["data"]["hex"], hex::encode(&bytes));
+
+    let mut expected = pubkey(1).parse::<solana_sdk::pubkey::Pubkey>().unwrap().to_bytes().to_vec();
+    expected.extend(u128::MAX.to_le_bytes());
+    expected.extend((-5i32).to_le_bytes());
+    expected.push(0);
+    expected.extend([2, 0, 0, 0, 7, 0, 1, 9, 0, 0]);
+    expected.extend([1, 3, 1, 2]);
+    assert_eq!(bytes, expected);
+
+    let (status, body) = post_json("/borsh/decode", json!({ "schema": order_schema(), "data": data })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["value"], value);
+}
+
+#[tokio::test]
+async fn decode_rejects_trailing_and_missing_bytes() {
+    let schema = json!({ "type": "u16" });
+
+    let data = general_purpose::STANDARD.encode([1, 0, 0]);
+    let (status, body) = post_json("/borsh/decode", json!({ "schema": schema, "data": data })).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "data");
+
+    let data = general_purpose::STANDARD.encode([1]);
+    let (status, body) = post_json("/borsh/decode", json!({ "schema": schema, "data": data })).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+}
+
+#[tokio::test]
+async fn encode_reports_value_errors_by_path() {
+    let value = json!({
+        "owner": pubkey(1),
+        "size": 1,
+        "price": 0,
+        "note": "hi",
+        "fills": [[7, true], [70000, false]],
+        "side": "Bid",
+        "seed": [1, 2]
+    });
+    let (status, body) = post_json("/borsh/encode", json!({ "schema": order_schema(), "value": value })).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "value.fills[1][0]");
+}
+
+#[tokio::test]
+async fn schema_errors_are_rejected() {
+    let cases = [
+        json!({ "type": "u512" }),
+        json!({ "type": "u8", "types": [
+            { "name": "A", "type": { "kind": "struct" } },
+            { "name": "A", "type": { "kind": "struct" } }
+        ]}),
+    ];
+    for schema in cases {
+        let (status, body) = post_json("/borsh/encode", json!({ "schema": schema, "value": 1 })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "body: {body}");
+    }
+
+    // A type that contains itself must fail cleanly rather than recurse forever.
+    let schema = json!({
+        "type": { "defined": "Loop" },
+        "types": [{ "name": "Loop", "type": { "kind": "type", "alias": { "defined": "Loop" } } }]
+    });
+    let (status, body) = post_json("/borsh/decode", json!({ "schema": schema, "data": "AA==" })).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use std::sync::Arc;
+
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+use solana_fellowship_server::cache::AccountCache;
+use solana_fellowship_server::config::CacheConfig;
+use solana_fellowship_server::errors::AppError;
+use solana_fellowship_server::rpc::{MockRpc, RpcHandle};
+
+use common::mint_account;
+
+fn cache_with(mock: &Arc<MockRpc>) -> AccountCache {
+    AccountCache::new(RpcHandle::new(Some(mock.clone())), &CacheConfig::default())
+}
+
+#[tokio::test]
+async fn mint_lookup_detects_program_and_decimals() {
+    let mock = Arc::new(MockRpc::new());
+    let classic = Pubkey::new_unique();
+    let token_2022 = Pubkey::new_unique();
+    mock.set_account(classic, mint_account(spl_token::id(), 6));
+    mock.set_account(token_2022, mint_account(spl_token_2022::id(), 9));
+
+    let cache = cache_with(&mock);
+    let info = cache.mint(&classic).await.unwrap();
+    assert_eq!((info.program_id, info.decimals, info.supply), (spl_token::id(), 6, 1_000));
+    assert_eq!(info.mint_authority, Some(Pubkey::new_from_array([9; 32])));
+    assert_eq!(cache.token_program(&token_2022).await.unwrap(), spl_token_2022::id());
+}
+
+#[tokio::test]
+async fn cached_accounts_survive_until_invalidated() {
+    let mock = Arc::new(MockRpc::new());
+    let mint = Pubkey::new_unique();
+    mock.set_account(mint, mint_account(spl_token::id(), 6));
+
+    let cache = cache_with(&mock);
+    cache.mint(&mint).await.unwrap();
+
+    mock.remove_account(&mint);
+    assert!(cache.mint(&mint).await.is_ok());
+
+    cache.invalidate_all();
+    assert!(matches!(cache.mint(&mint).await, Err(AppError::AccountNotFound(_))));
+}
+
+#[tokio::test]
+async fn non_mint_accounts_are_rejected() {
+    let mock = Arc::new(MockRpc::new());
+    let wallet = Pubkey::new_unique();
+    mock.set_account(wallet, Account { lamports: 5, ..Account::default() });
+
+    let result = cache_with(&mock).mint(&wallet).await;
+    assert!(matches!(result, Err(AppError::InvalidAccount { .. })));
+}
+
+#[tokio::test]
+async fn missing_rpc_is_reported() {
+    let cache = AccountCache::new(RpcHandle::default(), &CacheConfig::default());
+    let result = cache.account(&Pubkey::new_unique()).await;
+    assert!(matches!(result, Err(AppError::RpcUnavailable)));
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::Router;
+use serde_json::json;
+use tokio::net::TcpListener;
+
+use solana_fellowship_server::config::Config;
+use solana_fellowship_server::routes::{Availability, RouteGroup};
+use solana_fellowship_server::rpc::MockRpc;
+use superdev_client::models::borsh::{BorshDecodeRequest, BorshEncodeRequest, SchemaDescription};
+use superdev_client::models::derive::AtaQuery;
+use superdev_client::models::parse::parse_secret_key;
+use superdev_client::models::types::{PubkeyStr, SecretKeyStr, SignatureEncoding::Base64, SignatureStr};
+use superdev_client::models::{OutputFormat, SendSolRequest, SignMessageRequest, VerifyMessageRequest};
+use superdev_client::{Client, Error};
+
+use common::{app_with, key, pubkey, test_app};
+
+async fn serve(app: Router) -> Client {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    Client::new(format!("http://{address}"))
+}
+
+fn key_str(seed: u8) -> PubkeyStr {
+    PubkeyStr(key(seed))
+}
+
+#[tokio::test]
+async fn typed_requests_round_trip_through_the_server() {
+    let client = serve(test_app()).await;
+
+    let keypair = client.generate_keypair().await.unwrap();
+    let secret = keypair.secret.as_ref().map(|secret| SecretKeyStr(parse_secret_key(secret).unwrap()));
+    let request = SignMessageRequest { message: "hello".to_string(), secret, key_id: None, signature_encoding: Base64 };
+    let signed = client.sign_message(&request).await.unwrap();
+    assert_eq!(signed.public_key, keypair.pubkey);
+
+    let verify = VerifyMessageRequest {
+        message: "hello".to_string(),
+        signature: serde_json::from_value::<SignatureStr>(json!(signed.signature)).unwrap(),
+        pubkey: PubkeyStr(keypair.pubkey.parse().unwrap()),
+    };
+    assert!(client.verify_message(&verify).await.unwrap().valid);
+
+    let ata = client.derive_ata(&AtaQuery { owner: key_str(1), mint: key_str(2), token_program: None }).await.unwrap();
+    assert_eq!(ata.owner, pubkey(1));
+
+    let send = client.send_sol(&SendSolRequest { from: key_str(1), to: key_str(2).into(), lamports: Some(5), amount_sol: None, output_format: OutputFormat::Superdev }).await.unwrap();
+    let accounts: Vec<_> = send.accounts.iter().map(|meta| (meta.pubkey.clone(), meta.is_signer, meta.is_writable)).collect();
+    assert_eq!(accounts, [(pubkey(1), true, true), (pubkey(2), false, true)]);
+}
+
+#[tokio::test]
+async fn borsh_schemas_serialize_back_to_idl_syntax() {
+    let client = serve(test_app()).await;
+    let schema = json!({
+        "type": { "defined": "Order" },
+        "types": [
+            { "name": "Order", "type": { "kind": "struct", "fields": [
+                { "name": "size", "type": "u64" },
+                { "name": "fills", "type": { "vec": { "array": ["u8", 2] } } },
+                { "name": "side", "type": { "defined": "Side" } }
+            ]}},
+            { "name": "Side", "type": { "kind": "enum", "variants": [
+                { "name": "Bid" },
+                { "name": "Ask", "fields": [{ "option": "pubkey" }] }
+            ]}}
+        ]
+    });
+    let schema = || serde_json::from_value::<SchemaDescription>(schema.clone()).unwrap();
+    let value = json!({ "size": "7", "fills": [[1, 2]], "side": { "Ask": [pubkey(3)] } });
+
+    let encoded = client.borsh_encode(&BorshEncodeRequest { schema: schema(), value: value.clone() }).await.unwrap();
+    let decoded = client.borsh_decode(&BorshDecodeRequest { schema: schema(), data: encoded.data }).await.unwrap();
+    assert_eq!(decoded.value, value);
+}
+
+#[tokio::test]
+async fn errors_carry_the_server_code_and_field() {
+    let routes = HashMap::from([(RouteGroup::Keypair, Availability::Hidden)]);
+    let client = serve(app_with(Config { routes, ..Config::default() }, Arc::new(MockRpc::new()))).await;
+
+    let key_id = Some("nope".to_string());
+    let request = SignMessageRequest { message: "hi".to_string(), secret: None, key_id, signature_encoding: Base64 };
+    match client.sign_message(&request).await {
+        Err(Error::Api { status, code, field, .. }) => {
+            assert_eq!(status, 400);
+            assert_eq!(code, "UNKNOWN_KEY_ID");
+            assert_eq!(field.as_deref(), Some("key_id"));
+        }
+        other => panic!("expected an API error, got {other:?}"),
+    }
+
+    match client.generate_keypair().await {
+        Err(Error::Api { status, code, .. }) => assert_eq!((status, code.as_str()), (404, "NOT_FOUND")),
+        other => panic!("expected an API error, got {other:?}"),
+    }
+}
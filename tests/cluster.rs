@@ -0,0 +1,95 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use solana_sdk::{epoch_info::EpochInfo, pubkey::Pubkey};
+
+use solana_fellowship_server::config::Config;
+use solana_fellowship_server::rpc::{ClusterVersion, MockRpc, VoteAccount};
+use solana_fellowship_server::state::AppState;
+
+use common::{assert_error, get_json_from, mock_app, pubkey};
+
+#[tokio::test]
+async fn cluster_endpoints_wrap_rpc() {
+    let mock = Arc::new(MockRpc::new());
+    mock.set_epoch_info(EpochInfo {
+        epoch: 700,
+        slot_index: 431_000,
+        slots_in_epoch: 432_000,
+        absolute_slot: 302_831_000,
+        block_height: 281_000_000,
+        transaction_count: Some(9),
+    });
+    mock.set_version(ClusterVersion { solana_core: "2.1.0".to_string(), feature_set: Some(42) });
+    let app = mock_app(mock);
+
+    let (status, body) = get_json_from(app.clone(), "/cluster/epoch-info").await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(
+        body["data"],
+        json!({
+            "epoch": 700,
+            "slot_index": 431_000,
+            "slots_in_epoch": 432_000,
+            "slots_remaining": 1_000,
+            "absolute_slot": 302_831_000,
+            "block_height": 281_000_000,
+            "transaction_count": 9,
+        })
+    );
+
+    let (_, body) = get_json_from(app.clone(), "/cluster/slot").await;
+    assert_eq!(body["data"], json!({ "slot": 302_831_000 }));
+
+    let (_, body) = get_json_from(app, "/cluster/version").await;
+    assert_eq!(body["data"], json!({ "solana_core": "2.1.0", "feature_set": 42 }));
+}
+
+#[tokio::test]
+async fn cluster_endpoints_need_rpc() {
+    let app = solana_fellowship_server::app(AppState::new(Config::default()));
+    let (status, body) = get_json_from(app, "/cluster/slot").await;
+    assert_error(status, &body, StatusCode::SERVICE_UNAVAILABLE, "RPC_UNAVAILABLE");
+}
+
+fn vote_account(seed: u8, activated_stake: u64, commission: u8, delinquent: bool) -> VoteAccount {
+    VoteAccount {
+        vote_pubkey: Pubkey::new_from_array([seed; 32]),
+        node_pubkey: Pubkey::new_from_array([seed + 100; 32]),
+        activated_stake,
+        commission,
+        last_vote: 1_000,
+        root_slot: 968,
+        delinquent,
+    }
+}
+
+#[tokio::test]
+async fn validators_are_filtered_and_sorted() {
+    let mock = Arc::new(MockRpc::new());
+    mock.set_vote_accounts(vec![
+        vote_account(1, 5_000_000_000, 10, false),
+        vote_account(2, 9_000_000_000, 5, false),
+        vote_account(3, 1_000_000_000, 0, false),
+        vote_account(4, 2_000_000_000, 0, true),
+    ]);
+    let app = mock_app(mock);
+
+    let (status, body) = get_json_from(app.clone(), "/cluster/validators?delinquent=false&min_stake=2000000000").await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data = &body["data"];
+    assert_eq!(data["total_stake"], 17_000_000_000u64);
+    assert_eq!(data["delinquent_stake"], 2_000_000_000u64);
+    assert_eq!(data["validator_count"], 2);
+    assert_eq!(data["validators"][0]["vote_pubkey"], pubkey(2));
+    assert_eq!(data["validators"][0]["node_pubkey"], pubkey(102));
+    assert_eq!(data["validators"][0]["activated_stake_sol"], "9");
+
+    let (_, body) = get_json_from(app, "/cluster/validators?sort=commission&max_commission=5&limit=2").await;
+    let votes: Vec<_> = body["data"]["validators"].as_array().unwrap().iter().map(|v| v["vote_pubkey"].clone()).collect();
+    assert_eq!(votes, [json!(pubkey(4)), json!(pubkey(3))]);
+    assert_eq!(body["data"]["validator_count"], 3);
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use serde_json::{json, Value};
+
+use common::{call, post_json, pubkey, test_app};
+
+fn send_sol() -> Value {
+    json!({ "from": pubkey(1), "to": pubkey(2), "lamports": 1_000 })
+}
+
+async fn post(path: &str, content_type: &str, accept: &str, body: Vec<u8>) -> (StatusCode, String, Vec<u8>) {
+    let request = Request::post(path)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::ACCEPT, accept)
+        .body(Body::from(body))
+        .unwrap();
+
+    let (status, headers, bytes) = call(test_app(), request).await;
+    let content_type = headers[header::CONTENT_TYPE].to_str().unwrap().to_string();
+    (status, content_type, bytes.to_vec())
+}
+
+fn cbor(value: &Value) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(value, &mut bytes).unwrap();
+    bytes
+}
+
+#[tokio::test]
+async fn cbor_round_trip_matches_json() {
+    let (_, expected) = post_json("/send/sol", send_sol()).await;
+
+    let (status, content_type, bytes) = post("/send/sol", "application/cbor", "application/cbor", cbor(&send_sol())).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/cbor");
+
+    let body: Value = ciborium::from_reader(bytes.as_slice()).unwrap();
+    assert_eq!(body, expected);
+}
+
+#[tokio::test]
+async fn msgpack_round_trip_matches_json() {
+    let (_, expected) = post_json("/send/sol", send_sol()).await;
+
+    let request = rmp_serde::to_vec_named(&send_sol()).unwrap();
+    let (status, content_type, bytes) = post("/send/sol", "application/msgpack", "application/msgpack", request).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/msgpack");
+
+    let body: Value = rmp_serde::from_slice(&bytes).unwrap();
+    assert_eq!(body, expected);
+}
+
+#[tokio::test]
+async fn cbor_request_reports_field_errors() {
+    let request = cbor(&json!({ "from": pubkey(1), "to": "not-a-key", "lamports": 1 }));
+    let (status, content_type, bytes) = post("/send/sol", "application/cbor", "application/json", request).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(content_type, "application/json");
+
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["code"], "INVALID_PUBKEY");
+    assert_eq!(body["field"], "to");
+}
+
+#[tokio::test]
+async fn truncated_msgpack_is_malformed() {
+    let mut request = rmp_serde::to_vec_named(&send_sol()).unwrap();
+    request.truncate(request.len() / 2);
+
+    let (status, _, bytes) = post("/send/sol", "application/msgpack", "*/*", request).await;
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "MALFORMED_BODY");
+}
+
+#[tokio::test]
+async fn etag_differs_per_encoding() {
+    let path = format!("/derive/ata?owner={}&mint={}", pubkey(1), pubkey(2));
+    let get = |accept: &'static str| Request::get(&path).header(header::ACCEPT, accept).body(Body::empty()).unwrap();
+
+    let (_, json_headers, _) = call(test_app(), get("application/json")).await;
+    let (status, cbor_headers, _) = call(test_app(), get("application/cbor")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cbor_headers[header::CONTENT_TYPE], "application/cbor");
+    assert_eq!(cbor_headers[header::VARY], "accept");
+    assert_ne!(json_headers[header::ETAG], cbor_headers[header::ETAG]);
+}
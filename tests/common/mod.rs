@@ -0,0 +1,150 @@
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::{
+    body::{Body, Bytes},
+    http::{header, HeaderMap, Request, StatusCode},
+    Router,
+};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use solana_sdk::{
+    account::Account, program_option::COption, program_pack::Pack, pubkey::Pubkey, signer::keypair::Keypair,
+};
+use spl_token::state::Mint;
+use tower::ServiceExt;
+
+use solana_fellowship_server::metaplex::METADATA_PROGRAM_ID;
+use solana_fellowship_server::{config::Config, rpc::MockRpc, state::AppState};
+
+pub fn key(seed: u8) -> Pubkey {
+    Pubkey::new_from_array([seed; 32])
+}
+
+/// Deterministic pubkey so golden files stay stable across runs.
+pub fn pubkey(seed: u8) -> String {
+    key(seed).to_string()
+}
+
+pub fn keypair(seed: u8) -> Keypair {
+    Keypair::new_from_array([seed; 32])
+}
+
+pub fn test_app() -> Router {
+    solana_fellowship_server::app(AppState::new(Config::default()))
+}
+
+/// App whose RPC backend is `mock`; keep the `Arc` to seed accounts.
+pub fn mock_app(mock: Arc<MockRpc>) -> Router {
+    app_with(Config::default(), mock)
+}
+
+pub fn app_with(config: Config, mock: Arc<MockRpc>) -> Router {
+    let state = AppState::new(config);
+    state.rpc.replace(Some(mock));
+    solana_fellowship_server::app(state)
+}
+
+/// Initialized mint account owned by `owner` (spl-token or token-2022).
+pub fn mint_account(owner: Pubkey, decimals: u8) -> Account {
+    let mint = Mint {
+        mint_authority: COption::Some(Pubkey::new_from_array([9; 32])),
+        supply: 1_000,
+        decimals,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+    let mut data = vec![0; Mint::LEN];
+    mint.pack_into_slice(&mut data);
+    Account { lamports: 1, data, owner, executable: false, rent_epoch: 0 }
+}
+
+/// A Metaplex metadata account updated by `pubkey(7)`, which is also its
+/// only creator, with strings padded the way the program stores them.
+pub fn metadata_account(mint: Pubkey, name: &str, symbol: &str, uri: &str) -> Account {
+    let creator = Pubkey::new_from_array([7; 32]);
+    let mut data = vec![4];
+    data.extend_from_slice(creator.as_ref());
+    data.extend_from_slice(mint.as_ref());
+    for (text, max) in [(name, 32), (symbol, 10), (uri, 200)] {
+        data.extend_from_slice(&(max as u32).to_le_bytes());
+        data.extend_from_slice(text.as_bytes());
+        data.resize(data.len() + max - text.len(), 0);
+    }
+    data.extend_from_slice(&500u16.to_le_bytes());
+    data.extend_from_slice(&[1, 1, 0, 0, 0]);
+    data.extend_from_slice(creator.as_ref());
+    data.extend_from_slice(&[1, 100, 0, 1]);
+    Account { lamports: 1, data, owner: METADATA_PROGRAM_ID, executable: false, rent_epoch: 0 }
+}
+
+/// Sends one request through `app`; clone the router to reuse its state.
+pub async fn call(app: Router, request: Request<Body>) -> (StatusCode, HeaderMap, Bytes) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, headers, bytes)
+}
+
+pub async fn get_json(path: &str) -> (StatusCode, Value) {
+    let (status, _, bytes) = call(test_app(), Request::get(path).body(Body::empty()).unwrap()).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+pub async fn get_json_from(app: Router, path: &str) -> (StatusCode, Value) {
+    let (status, _, bytes) = call(app, Request::get(path).body(Body::empty()).unwrap()).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+pub async fn post_json(path: &str, body: Value) -> (StatusCode, Value) {
+    post_raw(path, Some("application/json"), body.to_string()).await
+}
+
+pub async fn post_json_to(app: Router, path: &str, body: Value) -> (StatusCode, Value) {
+    let request = Request::post(path)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+
+    let (status, _, bytes) = call(app, request).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+pub async fn post_raw(path: &str, content_type: Option<&str>, body: impl Into<Body>) -> (StatusCode, Value) {
+    let mut request = Request::post(path);
+    if let Some(content_type) = content_type {
+        request = request.header(header::CONTENT_TYPE, content_type);
+    }
+
+    let (status, _, bytes) = call(test_app(), request.body(body.into()).unwrap()).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+/// Asserts an error envelope and returns its body for further checks.
+pub fn assert_error(status: StatusCode, body: &Value, expected_status: StatusCode, code: &str) {
+    assert_eq!(status, expected_status, "unexpected status, body: {body}");
+    assert_eq!(body["success"], false);
+    assert_eq!(body["code"], code, "unexpected code, body: {body}");
+}
+
+/// Compares `actual` against `tests/golden/<name>.json`. Run with
+/// `UPDATE_GOLDEN=1` to rewrite the file after an intentional wire change.
+pub fn assert_golden(name: &str, actual: &Value) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.json"));
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        let pretty = serde_json::to_string_pretty(actual).unwrap();
+        std::fs::write(&path, pretty + "\n").unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("missing golden file {}, run with UPDATE_GOLDEN=1", path.display()));
+    let expected: Value = serde_json::from_str(&expected).unwrap();
+    assert_eq!(&expected, actual, "wire output drifted from {}", path.display());
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use axum::http::StatusCode;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::json;
+use solana_sdk::{hash::Hash, message::Message, transaction::Transaction};
+use solana_system_interface::instruction as system_instruction;
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+
+use solana_fellowship_server::solana_pay::memo_instruction;
+
+use common::{assert_error, key, post_json, pubkey};
+
+#[tokio::test]
+async fn compiles_operations_into_one_transaction() {
+    let blockhash = Hash::new_from_array([5; 32]);
+    let body = json!({
+        "payer": pubkey(1),
+        "operations": [
+            { "type": "create_ata", "owner": pubkey(2), "mint": pubkey(9) },
+            { "type": "send_token", "owner": pubkey(1), "destination": pubkey(2), "mint": pubkey(9), "amount": 50 },
+            { "type": "send_sol", "from": pubkey(1), "to": pubkey(2), "lamports": 100_000 },
+            { "type": "memo", "memo": "invoice 42" },
+        ],
+        "recent_blockhash": blockhash.to_string(),
+    });
+
+    let (status, body) = post_json("/compose", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["signers"], json!([pubkey(1)]));
+    let bytes = general_purpose::STANDARD.decode(body["data"]["transaction"].as_str().unwrap()).unwrap();
+    let transaction: Transaction = bincode::deserialize(&bytes).unwrap();
+
+    let transfer = spl_token::instruction::transfer(
+        &spl_token::id(),
+        &get_associated_token_address(&key(1), &key(9)),
+        &get_associated_token_address(&key(2), &key(9)),
+        &key(1),
+        &[],
+        50,
+    )
+    .unwrap();
+    let instructions = [
+        create_associated_token_account_idempotent(&key(1), &key(2), &key(9), &spl_token::id()),
+        transfer,
+        system_instruction::transfer(&key(1), &key(2), 100_000),
+        memo_instruction("invoice 42"),
+    ];
+    assert_eq!(transaction.message, Message::new_with_blockhash(&instructions, Some(&key(1)), &blockhash));
+}
+
+#[tokio::test]
+async fn reports_errors_against_the_operation() {
+    let body = json!({
+        "payer": pubkey(1),
+        "operations": [
+            { "type": "memo", "memo": "" },
+            { "type": "send_sol", "from": pubkey(1), "to": pubkey(1), "lamports": 100_000 },
+        ],
+        "recent_blockhash": Hash::new_from_array([5; 32]).to_string(),
+    });
+
+    let (status, body) = post_json("/compose", body).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["field"], "operations[0].memo");
+    assert_eq!(body["details"][1]["field"], "operations[1].to");
+    assert_eq!(body["details"][1]["code"], "SELF_TRANSFER");
+
+    let body = json!({
+        "payer": pubkey(1),
+        "operations": [{ "type": "send_sol", "from": pubkey(1), "to": "label:nobody", "lamports": 100_000 }],
+        "recent_blockhash": Hash::new_from_array([5; 32]).to_string(),
+    });
+    let (status, body) = post_json("/compose", body).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "UNKNOWN_LABEL");
+    assert_eq!(body["field"], "operations[0].to");
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use std::io::Read;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use flate2::read::GzDecoder;
+use serde_json::Value;
+
+use common::{call, get_json, test_app};
+
+fn get(path: &str, accept_encoding: &str) -> Request<Body> {
+    Request::get(path).header(header::ACCEPT_ENCODING, accept_encoding).body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn compresses_responses_the_client_accepts() {
+    let (_, expected) = get_json("/schemas").await;
+
+    let (status, headers, bytes) = call(test_app(), get("/schemas", "gzip")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::CONTENT_ENCODING], "gzip");
+    let mut json = String::new();
+    GzDecoder::new(&bytes[..]).read_to_string(&mut json).unwrap();
+    assert_eq!(serde_json::from_str::<Value>(&json).unwrap(), expected);
+
+    for encoding in ["br", "zstd"] {
+        let (status, headers, _) = call(test_app(), get("/schemas", encoding)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers[header::CONTENT_ENCODING], encoding);
+    }
+
+    let (_, headers, bytes) = call(test_app(), get("/schemas", "identity")).await;
+    assert!(!headers.contains_key(header::CONTENT_ENCODING));
+    assert_eq!(serde_json::from_slice::<Value>(&bytes).unwrap(), expected);
+}
+
+#[tokio::test]
+async fn compressed_reads_keep_working_etags() {
+    let (_, headers, _) = call(test_app(), get("/schemas", "gzip")).await;
+    let gzip_tag = headers[header::ETAG].clone();
+    let (_, headers, _) = call(test_app(), get("/schemas", "identity")).await;
+    assert_ne!(headers[header::ETAG], gzip_tag);
+
+    let mut request = get("/schemas", "gzip");
+    request.headers_mut().insert(header::IF_NONE_MATCH, gzip_tag);
+    let (status, _, _) = call(test_app(), request).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+}
+
+#[tokio::test]
+async fn compresses_error_bodies_and_posts_too() {
+    let request = Request::post("/send/sol")
+        .header(header::ACCEPT_ENCODING, "gzip")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(r#"{"from":"not-a-key","to":"nope","lamports":1}"#))
+        .unwrap();
+    let (status, headers, bytes) = call(test_app(), request).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(headers[header::CONTENT_ENCODING], "gzip");
+    let mut json = String::new();
+    GzDecoder::new(&bytes[..]).read_to_string(&mut json).unwrap();
+    assert_eq!(serde_json::from_str::<Value>(&json).unwrap()["success"], false);
+}
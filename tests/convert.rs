@@ -0,0 +1,75 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+
+use solana_fellowship_server::rpc::MockRpc;
+
+use common::{assert_error, call, get_json, mint_account, mock_app};
+
+#[tokio::test]
+async fn converts_between_lamports_and_sol() {
+    let (status, body) = get_json("/convert/sol?lamports=1500000001").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!({ "lamports": "1500000001", "sol": "1.500000001" }));
+
+    let (_, body) = get_json("/convert/sol?sol=0.1").await;
+    assert_eq!(body["data"], json!({ "lamports": "100000000", "sol": "0.1" }));
+
+    // 18446744073.709551615 SOL is exactly u64::MAX lamports; floats can't say that.
+    let (_, body) = get_json("/convert/sol?sol=18446744073.709551615").await;
+    assert_eq!(body["data"]["lamports"], u64::MAX.to_string());
+}
+
+#[tokio::test]
+async fn sol_conversion_rejects_lossy_input() {
+    let (status, body) = get_json("/convert/sol?sol=0.0000000001").await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "AMOUNT_PRECISION");
+    assert_eq!(body["field"], "sol");
+
+    let (status, body) = get_json("/convert/sol?sol=18446744073.709551616").await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "AMOUNT_TOO_LARGE");
+
+    let (status, body) = get_json("/convert/sol?sol=1e9").await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_DECIMAL_AMOUNT");
+
+    let (status, body) = get_json("/convert/sol?sol=1&lamports=1").await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "EXACTLY_ONE");
+}
+
+#[tokio::test]
+async fn converts_token_amounts_with_given_decimals() {
+    let (status, body) = get_json("/convert/token?decimals=6&ui_amount=12.50").await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"], json!({ "decimals": 6, "amount": "12500000", "ui_amount": "12.5" }));
+
+    let (_, body) = get_json("/convert/token?decimals=0&amount=42").await;
+    assert_eq!(body["data"]["ui_amount"], "42");
+
+    let (status, body) = get_json("/convert/token?decimals=2&ui_amount=0.001").await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "AMOUNT_PRECISION");
+    assert_eq!(body["field"], "ui_amount");
+}
+
+#[tokio::test]
+async fn token_conversion_reads_decimals_from_mint() {
+    let mock = Arc::new(MockRpc::new());
+    let mint = Pubkey::new_unique();
+    mock.set_account(mint, mint_account(spl_token::id(), 3));
+
+    let request = Request::get(format!("/convert/token?mint={mint}&amount=1234")).body(Body::empty()).unwrap();
+    let (status, _, bytes) = call(mock_app(mock), request).await;
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"], json!({ "mint": mint.to_string(), "decimals": 3, "amount": "1234", "ui_amount": "1.234" }));
+
+    let (status, body) = get_json("/convert/token?amount=1").await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "EXACTLY_ONE");
+    assert_eq!(body["field"], "mint");
+}
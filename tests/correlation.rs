@@ -0,0 +1,67 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{HeaderMap, Request, StatusCode},
+    routing::post,
+    Json, Router,
+};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+
+use solana_fellowship_server::config::{Config, RpcConfig};
+use solana_fellowship_server::correlation::CORRELATION_ID;
+use solana_fellowship_server::state::AppState;
+
+use common::{assert_error, call, test_app};
+
+fn get(path: &str, correlation_id: Option<&str>) -> Request<Body> {
+    let mut request = Request::get(path);
+    if let Some(id) = correlation_id {
+        request = request.header(CORRELATION_ID, id);
+    }
+    request.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn echoes_or_makes_up_a_correlation_id() {
+    let (status, headers, bytes) = call(test_app(), get("/no/such/route", Some("checkout-7f3a"))).await;
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+    assert_eq!(headers[CORRELATION_ID], "checkout-7f3a");
+    assert_eq!(body["correlation_id"], "checkout-7f3a");
+
+    let (_, headers, bytes) = call(test_app(), get("/cluster/slot", Some("spaces are not ok"))).await;
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    let made_up = headers[CORRELATION_ID].to_str().unwrap();
+    assert_ne!(made_up, "spaces are not ok");
+    assert_eq!(body["correlation_id"], made_up);
+
+    let (status, headers, bytes) = call(test_app(), get("/convert/sol?sol=1", None)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(headers.contains_key(CORRELATION_ID));
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert!(body.get("correlation_id").is_none());
+}
+
+#[tokio::test]
+async fn forwards_the_correlation_id_to_the_rpc_node() {
+    let (sender, mut seen) = mpsc::unbounded_channel();
+    let node = Router::new().route(
+        "/",
+        post(move |headers: HeaderMap, Json(request): Json<Value>| async move {
+            sender.send(headers.get(CORRELATION_ID).map(|id| id.to_str().unwrap().to_string())).unwrap();
+            Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": 4242 }))
+        }),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, node).await.unwrap() });
+
+    let rpc = RpcConfig { url: Some(format!("http://{address}")), ..RpcConfig::default() };
+    let app = solana_fellowship_server::app(AppState::new(Config { rpc, ..Config::default() }));
+    let (status, _, bytes) = call(app, get("/cluster/slot", Some("checkout-7f3a"))).await;
+    assert_eq!(status, StatusCode::OK, "body: {}", String::from_utf8_lossy(&bytes));
+    assert_eq!(seen.recv().await.unwrap().as_deref(), Some("checkout-7f3a"));
+}
@@ -0,0 +1,226 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_nonce::{
+    state::{Data as NonceData, DurableNonce, State as NonceState},
+    versions::Versions,
+};
+use solana_sdk::{account::Account, hash::Hash, program_option::COption, program_pack::Pack, pubkey::Pubkey};
+use solana_stake_interface::{
+    stake_flags::StakeFlags,
+    state::{Authorized, Delegation, Lockup, Meta, Stake, StakeStateV2},
+};
+use spl_token_2022::{
+    extension::{
+        metadata_pointer::MetadataPointer, transfer_fee::TransferFeeConfig, BaseStateWithExtensionsMut,
+        ExtensionType, StateWithExtensionsMut,
+    },
+    state::{Account as TokenAccount, AccountState, Mint},
+};
+
+use solana_fellowship_server::rpc::MockRpc;
+
+use common::{assert_error, key, mint_account, mock_app, post_json, post_json_to, pubkey};
+
+/// A Token-2022 mint with a transfer fee and a metadata pointer.
+fn mint_with_extensions() -> Vec<u8> {
+    let space = ExtensionType::try_calculate_account_len::<Mint>(&[
+        ExtensionType::TransferFeeConfig,
+        ExtensionType::MetadataPointer,
+    ])
+    .unwrap();
+    let mut data = vec![0; space];
+    let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+
+    let fee = state.init_extension::<TransferFeeConfig>(true).unwrap();
+    fee.transfer_fee_config_authority = Some(key(3)).try_into().unwrap();
+    fee.withheld_amount = 12.into();
+    fee.newer_transfer_fee.transfer_fee_basis_points = 50.into();
+    fee.newer_transfer_fee.maximum_fee = 5_000.into();
+
+    let pointer = state.init_extension::<MetadataPointer>(true).unwrap();
+    pointer.metadata_address = Some(key(4)).try_into().unwrap();
+
+    state.base = Mint {
+        mint_authority: COption::Some(key(1)),
+        supply: 1_000_000,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+    state.pack_base();
+    state.init_account_type().unwrap();
+    data
+}
+
+fn encode(data: &[u8]) -> String {
+    general_purpose::STANDARD.encode(data)
+}
+
+#[tokio::test]
+async fn decodes_token_2022_mint_extensions() {
+    let (status, body) = post_json("/decode/mint", json!({ "data": encode(&mint_with_extensions()) })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let data = &body["data"];
+    assert_eq!(data["mint_authority"], pubkey(1));
+    assert_eq!(data["freeze_authority"], Value::Null);
+    assert_eq!(data["supply"], "1000000");
+    assert_eq!(data["decimals"], 6);
+    assert!(data.get("address").is_none());
+
+    let extensions = data["extensions"].as_array().unwrap();
+    assert_eq!(extensions.len(), 2);
+    assert_eq!(extensions[0]["extension"], "transfer_fee_config");
+    assert_eq!(extensions[0]["transfer_fee_config_authority"], pubkey(3));
+    assert_eq!(extensions[0]["withheld_amount"], "12");
+    assert_eq!(extensions[0]["newer_transfer_fee"]["transfer_fee_basis_points"], 50);
+    assert_eq!(extensions[0]["newer_transfer_fee"]["maximum_fee"], "5000");
+    assert_eq!(
+        extensions[1],
+        json!({ "extension": "metadata_pointer", "authority": null, "metadata_address": pubkey(4) })
+    );
+}
+
+#[tokio::test]
+async fn decodes_legacy_token_account() {
+    let mut data = vec![0; TokenAccount::LEN];
+    TokenAccount {
+        mint: key(1),
+        owner: key(2),
+        amount: 42,
+        delegate: COption::Some(key(3)),
+        state: AccountState::Frozen,
+        is_native: COption::None,
+        delegated_amount: 7,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+
+    let (status, body) = post_json("/decode/token-account", json!({ "data": encode(&data) })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(
+        body["data"],
+        json!({
+            "mint": pubkey(1),
+            "owner": pubkey(2),
+            "amount": "42",
+            "delegate": pubkey(3),
+            "delegated_amount": "7",
+            "state": "frozen",
+            "is_native": false,
+            "close_authority": null,
+            "extensions": [],
+        })
+    );
+}
+
+#[tokio::test]
+async fn fetches_account_by_pubkey_and_checks_owner() {
+    let mock = Arc::new(MockRpc::new());
+    mock.set_account(key(10), mint_account(spl_token::ID, 9));
+    mock.set_account(
+        key(11),
+        Account { lamports: 1, data: mint_with_extensions(), owner: key(99), executable: false, rent_epoch: 0 },
+    );
+    let app = mock_app(mock);
+
+    let (status, body) = post_json_to(app.clone(), "/decode/mint", json!({ "pubkey": pubkey(10) })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["address"], pubkey(10));
+    assert_eq!(body["data"]["program_id"], spl_token::ID.to_string());
+    assert_eq!(body["data"]["decimals"], 9);
+
+    let (status, body) = post_json_to(app.clone(), "/decode/mint", json!({ "pubkey": pubkey(11) })).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_ACCOUNT");
+
+    let (status, body) = post_json_to(app.clone(), "/decode/token-account", json!({ "pubkey": pubkey(10) })).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_ACCOUNT");
+
+    let (status, body) = post_json_to(app, "/decode/mint", json!({ "pubkey": pubkey(12) })).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "ACCOUNT_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn rejects_bad_sources() {
+    let (status, body) = post_json("/decode/mint", json!({})).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "EXACTLY_ONE");
+
+    let (status, body) = post_json("/decode/mint", json!({ "data": encode(&[1, 2, 3]) })).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "data");
+}
+
+#[tokio::test]
+async fn decodes_nonce_account() {
+    let blockhash = Hash::new_from_array([5; 32]);
+    let state = NonceState::Initialized(NonceData::new(key(1), DurableNonce::from_blockhash(&blockhash), 5_000));
+    let data = bincode::serialize(&Versions::new(state)).unwrap();
+
+    let (status, body) = post_json("/decode/nonce", json!({ "data": encode(&data) })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["version"], "current");
+    assert_eq!(body["data"]["state"], "initialized");
+    assert_eq!(body["data"]["authority"], pubkey(1));
+    assert_eq!(body["data"]["nonce"], DurableNonce::from_blockhash(&blockhash).as_hash().to_string());
+    assert_eq!(body["data"]["lamports_per_signature"], 5_000);
+
+    // A mint owned by the token program is not a nonce account.
+    let mock = Arc::new(MockRpc::new());
+    mock.set_account(key(10), mint_account(spl_token::ID, 9));
+    let (status, body) = post_json_to(mock_app(mock), "/decode/nonce", json!({ "pubkey": pubkey(10) })).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_ACCOUNT");
+}
+
+#[tokio::test]
+async fn decodes_delegated_stake_account() {
+    let meta = Meta {
+        rent_exempt_reserve: 2_282_880,
+        authorized: Authorized { staker: key(1), withdrawer: key(2) },
+        lockup: Lockup::default(),
+    };
+    let stake = Stake {
+        delegation: Delegation {
+            voter_pubkey: key(3),
+            stake: 10_000_000_000,
+            activation_epoch: 400,
+            deactivation_epoch: u64::MAX,
+            ..Delegation::default()
+        },
+        credits_observed: 77,
+    };
+    let mut data = bincode::serialize(&StakeStateV2::Stake(meta, stake, StakeFlags::empty())).unwrap();
+    data.resize(StakeStateV2::size_of(), 0);
+
+    let mock = Arc::new(MockRpc::new());
+    mock.set_account(
+        key(20),
+        Account { lamports: 1, data, owner: solana_stake_interface::program::ID, executable: false, rent_epoch: 0 },
+    );
+    let (status, body) = post_json_to(mock_app(mock), "/decode/stake", json!({ "pubkey": pubkey(20) })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(
+        body["data"],
+        json!({
+            "address": pubkey(20),
+            "state": "delegated",
+            "meta": {
+                "rent_exempt_reserve": "2282880",
+                "staker": pubkey(1),
+                "withdrawer": pubkey(2),
+                "lockup": { "unix_timestamp": 0, "epoch": 0, "custodian": Pubkey::default().to_string(), "in_force": false },
+            },
+            "delegation": {
+                "voter": pubkey(3),
+                "stake": "10000000000",
+                "activation_epoch": 400,
+                "deactivation_epoch": null,
+                "credits_observed": 77,
+            },
+        })
+    );
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+use common::{assert_error, call, get_json, pubkey, test_app};
+
+#[tokio::test]
+async fn ata_matches_library_derivation() {
+    let owner = Pubkey::new_from_array([1; 32]);
+    let mint = Pubkey::new_from_array([2; 32]);
+
+    let (status, body) = get_json(&format!("/derive/ata?owner={owner}&mint={mint}")).await;
+    assert_eq!(status, StatusCode::OK);
+    let expected = get_associated_token_address_with_program_id(&owner, &mint, &spl_token::id());
+    assert_eq!(body["data"]["address"], expected.to_string());
+
+    let (_, body) = get_json(&format!(
+        "/derive/ata?owner={owner}&mint={mint}&token_program={}",
+        spl_token_2022::id()
+    ))
+    .await;
+    let expected = get_associated_token_address_with_program_id(&owner, &mint, &spl_token_2022::id());
+    assert_eq!(body["data"]["address"], expected.to_string());
+}
+
+#[tokio::test]
+async fn ata_rejects_unknown_token_program() {
+    let (status, body) =
+        get_json(&format!("/derive/ata?owner={}&mint={}&token_program={}", pubkey(1), pubkey(2), pubkey(3))).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "UNSUPPORTED_TOKEN_PROGRAM");
+    assert_eq!(body["field"], "token_program");
+}
+
+#[tokio::test]
+async fn ata_reports_missing_query_field() {
+    let (status, body) = get_json(&format!("/derive/ata?owner={}", pubkey(1))).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "MISSING_FIELD");
+    assert_eq!(body["field"], "mint");
+}
+
+#[tokio::test]
+async fn pda_matches_library_derivation() {
+    let program_id = Pubkey::new_from_array([5; 32]);
+    let owner = Pubkey::new_from_array([1; 32]);
+
+    let (status, body) =
+        get_json(&format!("/derive/pda?program_id={program_id}&seeds=utf8:vault,base58:{owner},hex:01ff")).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (expected, bump) =
+        Pubkey::find_program_address(&[b"vault", owner.as_ref(), &[0x01, 0xff]], &program_id);
+    assert_eq!(body["data"]["address"], expected.to_string());
+    assert_eq!(body["data"]["bump"], bump);
+}
+
+#[tokio::test]
+async fn pda_rejects_oversized_seed() {
+    let seed = "a".repeat(33);
+    let (status, body) = get_json(&format!("/derive/pda?program_id={}&seeds=utf8:{seed}", pubkey(5))).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_SEEDS");
+}
+
+#[tokio::test]
+async fn reads_honour_if_none_match() {
+    let app = test_app();
+    let uri = format!("/derive/ata?owner={}&mint={}", pubkey(1), pubkey(2));
+
+    let (status, headers, _) = call(app.clone(), Request::get(&uri).body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+    let etag = headers.get(header::ETAG).expect("etag header").clone();
+
+    let request = Request::get(&uri)
+        .header(header::IF_NONE_MATCH, etag.clone())
+        .body(Body::empty())
+        .unwrap();
+    let (status, headers, body) = call(app.clone(), request).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert_eq!(headers.get(header::ETAG), Some(&etag));
+    assert!(body.is_empty());
+
+    let request = Request::get(&uri)
+        .header(header::IF_NONE_MATCH, "\"stale\"")
+        .body(Body::empty())
+        .unwrap();
+    let (status, _, _) = call(app, request).await;
+    assert_eq!(status, StatusCode::OK);
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "dev-tools")]
+
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use solana_sdk::signer::{keypair::Keypair, Signer};
+
+use common::{assert_error, post_json, post_json_to, test_app};
+
+#[tokio::test]
+async fn deterministic_keypairs_are_stable_per_seed_and_index() {
+    let app = test_app();
+    let request = json!({ "seed": "demo", "index": 3 });
+    let (status, first) = post_json_to(app.clone(), "/keypair/deterministic", request.clone()).await;
+    assert_eq!(status, StatusCode::OK, "body: {first}");
+    assert_eq!(first["data"]["insecure"], true);
+    let (_, again) = post_json_to(app.clone(), "/keypair/deterministic", request).await;
+    assert_eq!(again["data"], first["data"]);
+
+    let secret = bs58::decode(first["data"]["secret"].as_str().unwrap()).into_vec().unwrap();
+    let keypair = Keypair::try_from(&secret[..]).unwrap();
+    assert_eq!(first["data"]["pubkey"], keypair.pubkey().to_string());
+
+    let (_, other) = post_json_to(app.clone(), "/keypair/deterministic", json!({ "seed": "demo", "index": 4 })).await;
+    assert_ne!(other["data"]["pubkey"], first["data"]["pubkey"]);
+    let (_, other) = post_json_to(app, "/keypair/deterministic", json!({ "seed": "demo!", "index": 3 })).await;
+    assert_ne!(other["data"]["pubkey"], first["data"]["pubkey"]);
+
+    let (status, body) = post_json("/keypair/deterministic", json!({ "seed": "" })).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+}
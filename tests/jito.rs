@@ -0,0 +1,95 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::{http::StatusCode, Router};
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::json;
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    transaction::Transaction,
+};
+use solana_system_interface::instruction as system_instruction;
+
+use solana_fellowship_server::config::Config;
+use solana_fellowship_server::jito::{MockBlockEngine, TIP_ACCOUNTS};
+use solana_fellowship_server::solana_pay::memo_instruction;
+use solana_fellowship_server::state::AppState;
+
+use common::{assert_error, get_json, keypair, post_json, post_json_to, pubkey};
+
+fn app(engine: Arc<MockBlockEngine>) -> Router {
+    let mut state = AppState::new(Config::default());
+    state.block_engine = Some(engine);
+    solana_fellowship_server::app(state)
+}
+
+fn signed(instructions: &[Instruction], payer: &Keypair) -> String {
+    let transaction =
+        Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &[payer], Hash::new_from_array([4; 32]));
+    general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap())
+}
+
+fn tip(lamports: u64) -> Instruction {
+    system_instruction::transfer(&keypair(1).pubkey(), &TIP_ACCOUNTS[3], lamports)
+}
+
+#[tokio::test]
+async fn sends_tipped_bundle() {
+    let engine = Arc::new(MockBlockEngine::new());
+    let payer = keypair(1);
+    let to: Pubkey = pubkey(2).parse().unwrap();
+    let transactions = [
+        signed(&[system_instruction::transfer(&payer.pubkey(), &to, 1)], &payer),
+        signed(&[memo_instruction("second"), tip(1_000)], &payer),
+    ];
+
+    let request = json!({ "transactions": transactions });
+    let (status, body) = post_json_to(app(engine.clone()), "/transaction/send-bundle", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["bundle_id"], "bundle-1");
+    assert_eq!(body["data"]["tip_lamports"], "1000");
+
+    let bundles = engine.bundles();
+    assert_eq!(bundles.len(), 1);
+    assert_eq!(bundles[0].len(), 2);
+    assert_eq!(body["data"]["signatures"][1], bundles[0][1].signatures[0].to_string());
+}
+
+#[tokio::test]
+async fn rejects_untipped_or_unsigned_bundles() {
+    let engine = Arc::new(MockBlockEngine::new());
+    let app = app(engine.clone());
+    let payer = keypair(1);
+
+    let request = json!({ "transactions": [signed(&[tip(999)], &payer)] });
+    let (status, body) = post_json_to(app.clone(), "/transaction/send-bundle", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "transactions");
+
+    let unsigned = Transaction::new_unsigned(solana_sdk::message::Message::new(&[tip(5_000)], Some(&payer.pubkey())));
+    let unsigned = general_purpose::STANDARD.encode(bincode::serialize(&unsigned).unwrap());
+    let request = json!({ "transactions": [signed(&[tip(5_000)], &payer), unsigned] });
+    let (status, body) = post_json_to(app.clone(), "/transaction/send-bundle", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "transactions[1]");
+
+    let (status, body) = post_json_to(app, "/transaction/send-bundle", json!({ "transactions": [] })).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert!(engine.bundles().is_empty());
+}
+
+#[tokio::test]
+async fn reports_tip_accounts_and_missing_block_engine() {
+    let (status, body) = get_json("/transaction/bundle-tip").await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["min_tip_lamports"], "1000");
+    assert_eq!(body["data"]["tip_accounts"].as_array().unwrap().len(), TIP_ACCOUNTS.len());
+
+    let request = json!({ "transactions": [signed(&[tip(5_000)], &keypair(1))] });
+    let (status, body) = post_json("/transaction/send-bundle", request).await;
+    assert_error(status, &body, StatusCode::SERVICE_UNAVAILABLE, "BLOCK_ENGINE_UNAVAILABLE");
+}
@@ -0,0 +1,353 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, TimeZone, Timelike, Utc};
+use serde_json::{json, Value};
+use solana_sdk::{hash::Hash, pubkey::Pubkey, signer::Signer, transaction::VersionedTransaction};
+use solana_system_interface::instruction as system_instruction;
+
+use solana_fellowship_server::cron::Cron;
+use solana_fellowship_server::rpc::{
+    ConfirmedTransaction, LatestBlockhash, MockRpc, RpcError, SolanaRpc, TransactionMeta,
+};
+
+use common::{assert_error, call, get_json_from, keypair, mock_app, post_json_to, pubkey};
+
+fn secret(seed: u8) -> String {
+    bs58::encode(keypair(seed).to_bytes()).into_string()
+}
+
+/// Template for a transfer of `lamports` from `keypair(1)` to `pubkey(2)`.
+fn transfer(lamports: u64) -> Value {
+    let to: Pubkey = pubkey(2).parse().unwrap();
+    let instruction = system_instruction::transfer(&keypair(1).pubkey(), &to, lamports);
+    json!({
+        "program_id": instruction.program_id.to_string(),
+        "accounts": instruction.accounts.iter().map(|meta| json!({
+            "pubkey": meta.pubkey.to_string(),
+            "is_signer": meta.is_signer,
+            "is_writable": meta.is_writable,
+        })).collect::<Vec<_>>(),
+        "data": general_purpose::STANDARD.encode(&instruction.data),
+    })
+}
+
+/// Polls the job until it leaves `scheduled`/`running`.
+async fn finished(app: Router, id: &str) -> Value {
+    for _ in 0..200 {
+        let (status, body) = get_json_from(app.clone(), &format!("/jobs/{id}")).await;
+        assert_eq!(status, StatusCode::OK, "body: {body}");
+        if !matches!(body["data"]["status"].as_str(), Some("scheduled" | "running")) {
+            return body["data"].clone();
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("job {id} never finished");
+}
+
+async fn delete(app: Router, path: &str) -> (StatusCode, Value) {
+    let (status, _, bytes) = call(app, Request::delete(path).body(Body::empty()).unwrap()).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, minute, 0).unwrap()
+}
+
+#[tokio::test]
+async fn sends_due_job_with_fresh_blockhash() {
+    let mock = Arc::new(MockRpc::new());
+    let blockhash = Hash::new_from_array([7; 32]);
+    mock.set_blockhash(LatestBlockhash { blockhash, last_valid_block_height: 500 });
+    let app = mock_app(mock.clone());
+
+    let request = json!({
+        "signers": [secret(1)],
+        "instructions": [transfer(5_000)],
+        "execute_at": "2020-01-01T00:00:00Z",
+    });
+    let (status, body) = post_json_to(app.clone(), "/jobs", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["fee_payer"], keypair(1).pubkey().to_string());
+
+    let job = finished(app, body["data"]["id"].as_str().unwrap()).await;
+    assert_eq!(job["status"], "succeeded", "job: {job}");
+    assert_eq!(job["runs"], 1);
+    assert_eq!(job["attempts"], 1);
+    assert_eq!(job["next_run"], Value::Null);
+
+    let sent = mock.sent_transactions();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(*sent[0].message.recent_blockhash(), blockhash);
+    assert!(sent[0].verify_with_results().iter().all(|valid| *valid));
+    assert_eq!(job["signatures"], json!([sent[0].signatures[0].to_string()]));
+}
+
+#[tokio::test]
+async fn retries_failed_sends_then_gives_up() {
+    let mock = Arc::new(MockRpc::new());
+    mock.set_send_error(Some(RpcError("blockhash not found".to_string())));
+    let app = mock_app(mock.clone());
+
+    let request = json!({
+        "signers": [secret(1)],
+        "instructions": [transfer(1)],
+        "execute_at": Utc::now().to_rfc3339(),
+        "retry": { "max_attempts": 2, "backoff_ms": 1 },
+    });
+    let (status, body) = post_json_to(app.clone(), "/jobs", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let job = finished(app, body["data"]["id"].as_str().unwrap()).await;
+    assert_eq!(job["status"], "failed", "job: {job}");
+    assert_eq!(job["attempts"], 2);
+    assert_eq!(job["signatures"], json!([]));
+    assert!(job["last_error"].as_str().unwrap().contains("blockhash not found"));
+    assert!(mock.sent_transactions().is_empty());
+}
+
+/// Compute-unit price a send was made at, from its leading instruction.
+fn price(transaction: &VersionedTransaction) -> u64 {
+    let data = &transaction.message.instructions()[0].data;
+    u64::from_le_bytes(data[1..9].try_into().unwrap())
+}
+
+/// Replaces the mock's blockhash with one valid for another 150 blocks, and
+/// moves the chain past the old one so nothing signed with it can land.
+async fn expire_blockhash(mock: &MockRpc) {
+    let latest = mock.get_latest_blockhash().await.unwrap();
+    let next = latest.last_valid_block_height + 1;
+    let blockhash = Hash::new_from_array([(next % 251) as u8; 32]);
+    mock.set_blockhash(LatestBlockhash { blockhash, last_valid_block_height: next + 150 });
+    mock.set_block_height(next);
+}
+
+/// Stands in for the cluster: sends `lands` accepts confirm straight away;
+/// any other send sits until its blockhash expires.
+fn cluster(mock: Arc<MockRpc>, lands: fn(&VersionedTransaction) -> bool) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut seen = 0;
+        loop {
+            let sent = mock.sent_transactions();
+            for transaction in sent.into_iter().skip(seen) {
+                seen += 1;
+                if lands(&transaction) {
+                    let meta = TransactionMeta { fee: 5_030, ..TransactionMeta::default() };
+                    let signature = transaction.signatures[0];
+                    let confirmed = ConfirmedTransaction { slot: 9, block_time: None, transaction, meta };
+                    mock.set_transaction(signature, confirmed);
+                } else {
+                    expire_blockhash(&mock).await;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+}
+
+#[tokio::test]
+async fn bumps_priority_fee_until_confirmed() {
+    let mock = Arc::new(MockRpc::new());
+    let app = mock_app(mock.clone());
+    // The cluster only includes sends priced at 150 or more.
+    let cluster = cluster(mock.clone(), |sent| price(sent) >= 150);
+
+    let request = json!({
+        "signers": [secret(1)],
+        "instructions": [transfer(1)],
+        "execute_at": Utc::now().to_rfc3339(),
+        "priority_fee": {
+            "initial_micro_lamports": 100,
+            "max_micro_lamports": 1_000,
+            "bump_percent": 50,
+            "interval_ms": 10,
+        },
+    });
+    let (status, body) = post_json_to(app.clone(), "/jobs", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let job = finished(app, body["data"]["id"].as_str().unwrap()).await;
+    cluster.abort();
+    assert_eq!(job["status"], "succeeded", "job: {job}");
+    assert_eq!(job["attempts"], 2);
+    assert_eq!(job["priority_fee"], 150);
+    assert_eq!(job["fee_paid"], 5_030);
+
+    let sent = mock.sent_transactions();
+    assert_eq!(sent.iter().map(price).collect::<Vec<_>>(), [100, 150]);
+    assert_eq!(job["signatures"], json!([sent[1].signatures[0].to_string()]));
+}
+
+#[tokio::test]
+async fn bumps_only_once_the_previous_send_expires() {
+    let mock = Arc::new(MockRpc::new());
+    let app = mock_app(mock.clone());
+    let first_blockhash = mock.get_latest_blockhash().await.unwrap();
+
+    let request = json!({
+        "signers": [secret(1)],
+        "instructions": [transfer(1)],
+        "execute_at": Utc::now().to_rfc3339(),
+        "priority_fee": {
+            "initial_micro_lamports": 100,
+            "max_micro_lamports": 1_000,
+            "bump_percent": 50,
+            "interval_ms": 10,
+        },
+    });
+    let (status, body) = post_json_to(app.clone(), "/jobs", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    // Many intervals pass unconfirmed, but the first send could still land,
+    // so nothing else is signed.
+    while mock.sent_transactions().is_empty() {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(mock.sent_transactions().len(), 1);
+
+    // Once it can't, the bump goes out. Even with both reported as
+    // confirmed, the run lands once, with the live one.
+    expire_blockhash(&mock).await;
+    while mock.sent_transactions().len() < 2 {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    for transaction in mock.sent_transactions() {
+        let meta = TransactionMeta { fee: 5_030, ..TransactionMeta::default() };
+        let signature = transaction.signatures[0];
+        mock.set_transaction(signature, ConfirmedTransaction { slot: 9, block_time: None, transaction, meta });
+    }
+    let job = finished(app, body["data"]["id"].as_str().unwrap()).await;
+
+    let sent = mock.sent_transactions();
+    assert_eq!(sent.iter().map(price).collect::<Vec<_>>(), [100, 150]);
+    assert_eq!(*sent[0].message.recent_blockhash(), first_blockhash.blockhash);
+    assert_ne!(*sent[1].message.recent_blockhash(), first_blockhash.blockhash);
+    assert_eq!(job["status"], "succeeded", "job: {job}");
+    assert_eq!(job["signatures"], json!([sent[1].signatures[0].to_string()]));
+}
+
+#[tokio::test]
+async fn gives_up_at_maximum_priority_fee() {
+    let mock = Arc::new(MockRpc::new());
+    let app = mock_app(mock.clone());
+    let cluster = cluster(mock.clone(), |_| false);
+
+    let request = json!({
+        "signers": [secret(1)],
+        "instructions": [transfer(1)],
+        "execute_at": Utc::now().to_rfc3339(),
+        "priority_fee": {
+            "initial_micro_lamports": 100,
+            "max_micro_lamports": 120,
+            "bump_percent": 50,
+            "interval_ms": 10,
+        },
+    });
+    let (status, body) = post_json_to(app.clone(), "/jobs", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let job = finished(app.clone(), body["data"]["id"].as_str().unwrap()).await;
+    cluster.abort();
+    assert_eq!(job["status"], "failed", "job: {job}");
+    assert_eq!(job["attempts"], 2);
+    assert_eq!(job["priority_fee"], 120);
+    assert!(job["last_error"].as_str().unwrap().contains("maximum priority fee"));
+    assert_eq!(mock.sent_transactions().iter().map(price).collect::<Vec<_>>(), [100, 120]);
+
+    let request = json!({
+        "signers": [secret(1)],
+        "instructions": [transfer(1)],
+        "cron": "* * * * *",
+        "priority_fee": { "initial_micro_lamports": 200, "max_micro_lamports": 100 },
+    });
+    let (status, body) = post_json_to(app, "/jobs", request).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "PRIORITY_FEE_RANGE");
+}
+
+#[tokio::test]
+async fn schedules_and_cancels_cron_job() {
+    let app = mock_app(Arc::new(MockRpc::new()));
+    let request = json!({ "signers": [secret(1)], "instructions": [transfer(1)], "cron": "*/5 * * * *" });
+    let (status, body) = post_json_to(app.clone(), "/jobs", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let job = &body["data"];
+    assert_eq!(job["status"], "scheduled");
+    assert_eq!(job["cron"], "*/5 * * * *");
+    let next_run: DateTime<Utc> = job["next_run"].as_str().unwrap().parse().unwrap();
+    assert!(next_run > Utc::now());
+    assert_eq!(next_run.minute() % 5, 0);
+
+    let path = format!("/jobs/{}", job["id"].as_str().unwrap());
+    let (status, body) = delete(app.clone(), &path).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["status"], "cancelled");
+    assert_eq!(body["data"]["next_run"], Value::Null);
+
+    let (status, body) = delete(app.clone(), &path).await;
+    assert_error(status, &body, StatusCode::CONFLICT, "CONFLICT");
+
+    let (status, body) = get_json_from(app, "/jobs/unknown").await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+}
+
+#[tokio::test]
+async fn rejects_bad_schedules() {
+    let app = mock_app(Arc::new(MockRpc::new()));
+
+    let request = json!({
+        "signers": [secret(1)],
+        "instructions": [transfer(1)],
+        "execute_at": "2020-01-01T00:00:00Z",
+        "cron": "* * * * *",
+    });
+    let (status, body) = post_json_to(app.clone(), "/jobs", request).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "EXACTLY_ONE");
+
+    let request = json!({ "signers": [secret(1)], "instructions": [transfer(1)], "cron": "0 0 31 2 *" });
+    let (status, body) = post_json_to(app.clone(), "/jobs", request).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "INVALID_CRON");
+
+    // keypair(1) must sign the transfer, but only keypair(3) was given.
+    let request = json!({ "signers": [secret(3)], "instructions": [transfer(1)], "cron": "* * * * *" });
+    let (status, body) = post_json_to(app, "/jobs", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "signers");
+}
+
+#[test]
+fn cron_finds_next_match() {
+    let weekdays: Cron = "30 9 * * 1-5".parse().unwrap();
+    // Saturday 2024-06-01 rolls over to Monday morning.
+    assert_eq!(weekdays.next_after(utc(2024, 6, 1, 12, 0)), Some(utc(2024, 6, 3, 9, 30)));
+    assert_eq!(weekdays.next_after(utc(2024, 6, 3, 9, 29)), Some(utc(2024, 6, 3, 9, 30)));
+    assert_eq!(weekdays.next_after(utc(2024, 6, 3, 9, 30)), Some(utc(2024, 6, 4, 9, 30)));
+
+    let leap: Cron = "0 0 29 2 *".parse().unwrap();
+    assert_eq!(leap.next_after(utc(2024, 3, 1, 0, 0)), Some(utc(2028, 2, 29, 0, 0)));
+
+    // Day of month and day of week both restricted: either one fires.
+    let either: Cron = "0 12 1 * 7".parse().unwrap();
+    assert_eq!(either.next_after(utc(2024, 6, 3, 0, 0)), Some(utc(2024, 6, 9, 12, 0)));
+    assert_eq!(either.next_after(utc(2024, 6, 30, 13, 0)), Some(utc(2024, 7, 1, 12, 0)));
+
+    let stepped: Cron = "5/20 */6 * 1,7 *".parse().unwrap();
+    assert_eq!(stepped.next_after(utc(2024, 6, 15, 0, 0)), Some(utc(2024, 7, 1, 0, 5)));
+
+    assert!("0 0 31 2 *".parse::<Cron>().unwrap().next_after(utc(2024, 1, 1, 0, 0)).is_none());
+    for bad in ["* * * *", "60 * * * *", "* * 0 * *", "*/0 * * * *", "5-1 * * * *", "a * * * *"] {
+        assert!(bad.parse::<Cron>().is_err(), "{bad}");
+    }
+}
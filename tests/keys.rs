@@ -0,0 +1,142 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use serde_json::{json, Value};
+
+use solana_fellowship_server::config::{AdminConfig, ApprovalsConfig, Config, Mode};
+use solana_fellowship_server::rpc::MockRpc;
+use solana_fellowship_server::types::Redacted;
+
+use common::{app_with, assert_error, call, get_json_from, post_json_to};
+
+/// Production app where `alice-key` authenticates an admin and `bob-key`
+/// someone else.
+fn production_app() -> Router {
+    let identities: HashMap<_, _> =
+        ["alice", "bob"].into_iter().map(|name| (name.to_string(), Redacted(format!("{name}-key")))).collect();
+    let config = Config {
+        mode: Mode::Production,
+        approvals: ApprovalsConfig { identities, ..ApprovalsConfig::default() },
+        admin: AdminConfig { identities: vec!["alice".to_string()] },
+        ..Config::default()
+    };
+    app_with(config, Arc::new(MockRpc::new()))
+}
+
+async fn export(app: Router, api_key: Option<&str>, body: Value) -> (StatusCode, Value) {
+    let mut request = Request::post("/keypair/export-encrypted").header("content-type", "application/json");
+    if let Some(key) = api_key {
+        request = request.header("x-api-key", key);
+    }
+    let (status, _, bytes) = call(app, request.body(Body::from(body.to_string())).unwrap()).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+async fn put(app: Router, path: &str, body: Value) -> (StatusCode, Value) {
+    let request = Request::put(path).header("content-type", "application/json").body(Body::from(body.to_string()));
+    let (status, _, bytes) = call(app, request.unwrap()).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+async fn generate(app: Router) -> String {
+    let (status, body) = post_json_to(app, "/keypair", json!({})).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    body["data"]["key_id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn lists_keys_by_tag_with_usage() {
+    let app = production_app();
+    let payer = generate(app.clone()).await;
+    let other = generate(app.clone()).await;
+
+    let request = json!({ "label": "fee-payer-1", "tags": ["fee-payer", "eu"] });
+    let (status, body) = put(app.clone(), &format!("/keys/{payer}"), request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["tags"], json!(["eu", "fee-payer"]));
+
+    for _ in 0..2 {
+        let request = json!({ "message": "hi", "key_id": payer });
+        let (status, body) = post_json_to(app.clone(), "/message/sign", request).await;
+        assert_eq!(status, StatusCode::OK, "body: {body}");
+    }
+
+    let (status, body) = get_json_from(app.clone(), "/keys?tag=fee-payer").await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let keys = body["data"].as_array().unwrap();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0]["key_id"], payer);
+    assert_eq!(keys[0]["label"], "fee-payer-1");
+    assert_eq!(keys[0]["uses"], 2);
+    assert!(keys[0]["last_used_at"].is_string());
+    assert_eq!(keys[0]["expired"], false);
+
+    let (_, body) = get_json_from(app.clone(), "/keys").await;
+    let ids: Vec<&str> = body["data"].as_array().unwrap().iter().map(|key| key["key_id"].as_str().unwrap()).collect();
+    assert_eq!(ids, [payer.as_str(), other.as_str()]);
+
+    let (status, body) = put(app.clone(), &format!("/keys/{payer}"), json!({ "tags": ["not a tag"] })).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "INVALID_LABEL");
+    assert_eq!(body["details"][0]["field"], "tags[0]");
+
+    let (status, body) = put(app, "/keys/missing", json!({})).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+}
+
+#[tokio::test]
+async fn refuses_to_sign_with_expired_keys() {
+    let app = production_app();
+    let key_id = generate(app.clone()).await;
+
+    let request = json!({ "key_id": key_id, "passphrase": "correct horse battery" });
+    let (status, body) = export(app.clone(), Some("alice-key"), request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let request = json!({
+        "encrypted": body["data"],
+        "passphrase": "correct horse battery",
+        "tags": ["retired"],
+        "expires_at": "2020-01-01T00:00:00Z",
+    });
+    let (status, body) = post_json_to(app.clone(), "/keypair/import-encrypted", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let imported = body["data"]["key_id"].as_str().unwrap().to_string();
+
+    let request = json!({ "message": "hi", "key_id": imported });
+    let (status, body) = post_json_to(app.clone(), "/message/sign", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "KEY_EXPIRED");
+    assert_eq!(body["field"], "key_id");
+
+    let (_, body) = get_json_from(app.clone(), "/keys?tag=retired").await;
+    assert_eq!(body["data"][0]["key_id"], imported);
+    assert_eq!(body["data"][0]["expired"], true);
+    assert_eq!(body["data"][0]["uses"], 0);
+
+    // Lifting the expiry brings the key back.
+    let (status, _) = put(app.clone(), &format!("/keys/{imported}"), json!({ "tags": ["retired"] })).await;
+    assert_eq!(status, StatusCode::OK);
+    let request = json!({ "message": "hi", "key_id": imported });
+    let (status, body) = post_json_to(app, "/message/sign", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+}
+
+#[tokio::test]
+async fn only_admins_export_keystore_keys_in_production() {
+    let app = production_app();
+    let key_id = generate(app.clone()).await;
+    let request = json!({ "key_id": key_id, "passphrase": "correct horse battery" });
+
+    let (status, body) = export(app.clone(), None, request.clone()).await;
+    assert_error(status, &body, StatusCode::UNAUTHORIZED, "UNAUTHORIZED");
+    let (status, body) = export(app.clone(), Some("bob-key"), request.clone()).await;
+    assert_error(status, &body, StatusCode::FORBIDDEN, "FORBIDDEN");
+
+    let (status, body) = export(app, Some("alice-key"), request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert!(body["data"]["ciphertext"].is_string());
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::Value;
+use tokio::sync::oneshot;
+
+use solana_fellowship_server::config::{BindConfig, ListenerConfig};
+use solana_fellowship_server::listener;
+use solana_fellowship_server::routes::RouteGroup;
+
+use common::{assert_error, call, test_app};
+
+#[tokio::test]
+async fn reuse_port_lets_a_second_process_share_the_port() {
+    let first = listener::bind("127.0.0.1:0", true).unwrap();
+    let address = first.local_addr().unwrap().to_string();
+    let second = listener::bind(&address, true).unwrap();
+    assert_eq!(second.local_addr().unwrap(), first.local_addr().unwrap());
+
+    // Without it the port stays exclusive.
+    let taken = listener::bind("127.0.0.1:0", false).unwrap();
+    let address = taken.local_addr().unwrap().to_string();
+    assert!(listener::bind(&address, false).is_err());
+}
+
+#[tokio::test]
+async fn binds_ipv6_and_rejects_unparsable_addresses() {
+    if let Ok(listener) = listener::bind("[::1]:0", false) {
+        assert!(listener.local_addr().unwrap().is_ipv6());
+    }
+    assert!(listener::bind("localhost", false).is_err());
+}
+
+#[tokio::test]
+async fn drains_in_flight_requests_on_shutdown() {
+    let app = Router::new().route(
+        "/slow",
+        get(|| async {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            "done"
+        }),
+    );
+    let listener = listener::bind("127.0.0.1:0", false).unwrap();
+    let url = format!("http://{}/slow", listener.local_addr().unwrap());
+    let (stop, stopped) = oneshot::channel::<()>();
+    let server = tokio::spawn(listener::serve(
+        listener,
+        app,
+        async {
+            stopped.await.ok();
+        },
+        Duration::from_secs(5),
+    ));
+
+    let request = tokio::spawn(reqwest::get(url.clone()));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    stop.send(()).unwrap();
+
+    let response = request.await.unwrap().unwrap();
+    assert_eq!(response.text().await.unwrap(), "done");
+    server.await.unwrap().unwrap();
+    assert!(reqwest::get(url).await.is_err());
+}
+
+#[tokio::test]
+async fn binds_the_main_address_then_each_extra_one() {
+    let config = ListenerConfig {
+        address: "127.0.0.1:0".to_string(),
+        extra: vec![BindConfig { address: "127.0.0.1:0".to_string(), groups: vec![RouteGroup::Admin] }],
+        ..ListenerConfig::default()
+    };
+    let listeners = listener::listeners(&config).unwrap();
+    assert_eq!(listeners.len(), 2);
+    assert!(listeners[0].groups.is_empty());
+    assert_eq!(listeners[1].groups, [RouteGroup::Admin]);
+    assert_ne!(listeners[0].socket.local_addr().unwrap(), listeners[1].socket.local_addr().unwrap());
+}
+
+#[tokio::test]
+async fn a_listener_hides_the_groups_it_does_not_serve() {
+    let get = |path: &str| Request::get(path).body(Body::empty()).unwrap();
+    let private = listener::restrict(test_app(), &[RouteGroup::Convert]);
+
+    let (status, _, _) = call(private.clone(), get("/convert/sol?sol=1")).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _, bytes) = call(private, get("/schemas")).await;
+    assert_error(status, &serde_json::from_slice::<Value>(&bytes).unwrap(), StatusCode::NOT_FOUND, "NOT_FOUND");
+
+    let (status, _, _) = call(listener::restrict(test_app(), &[]), get("/schemas")).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn moves_operations_routes_to_the_admin_address() {
+    let config = ListenerConfig {
+        address: "127.0.0.1:0".to_string(),
+        admin_address: Some("127.0.0.1:0".to_string()),
+        ..ListenerConfig::default()
+    };
+    let listeners = listener::listeners(&config).unwrap();
+    let [public, admin] = &listeners[..] else { panic!("expected two listeners") };
+    assert_eq!(admin.groups, listener::OPERATIONS);
+    assert!(public.groups.contains(&RouteGroup::Keypair));
+    assert!(!public.groups.iter().any(|group| listener::OPERATIONS.contains(group)));
+
+    let get = |path: &str| Request::get(path).body(Body::empty()).unwrap();
+    let (status, _, bytes) = call(listener::restrict(test_app(), &admin.groups), get("/health")).await;
+    assert_eq!(status, StatusCode::OK);
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["data"]["mode"], "development");
+    let (status, _, _) = call(listener::restrict(test_app(), &public.groups), get("/metrics")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _, _) = call(listener::restrict(test_app(), &admin.groups), get("/keypair")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
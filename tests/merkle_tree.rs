@@ -0,0 +1,104 @@
+mod common;
+
+use axum::http::StatusCode;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::rent::Rent;
+
+use solana_fellowship_server::anchor::sighash;
+use solana_fellowship_server::merkle_tree::{self, NOOP_PROGRAM_ID, PROGRAM_ID};
+
+use common::{assert_error, get_json_from, post_json, pubkey, test_app};
+
+fn data(instruction: &Value) -> Vec<u8> {
+    general_purpose::STANDARD.decode(instruction["instruction_data"].as_str().unwrap()).unwrap()
+}
+
+#[test]
+fn account_size_matches_the_program() {
+    // What the compression SDK's getConcurrentMerkleTreeAccountSize reports.
+    assert_eq!(merkle_tree::account_size(14, 64, 0), 31_800);
+    assert_eq!(merkle_tree::account_size(14, 64, 3), 31_800 + 14 * 32);
+}
+
+#[tokio::test]
+async fn creates_and_initializes_a_tree_account() {
+    let body = json!({
+        "payer": pubkey(1),
+        "tree": pubkey(2),
+        "max_depth": 14,
+        "max_buffer_size": 64,
+        "canopy_depth": 3,
+    });
+
+    let (status, body) = post_json("/merkle-tree/create", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let space = merkle_tree::account_size(14, 64, 3);
+    let cost = &body["data"]["cost"];
+    assert_eq!(cost["space"], space);
+    assert_eq!(cost["lamports"], Rent::default().minimum_balance(space));
+    assert_eq!(cost["max_leaves"], 16_384);
+    assert_eq!(cost["proof_length"], 11);
+    assert_eq!(body["data"]["authority"], pubkey(1));
+
+    let [allocate, init] = [&body["data"]["instructions"][0], &body["data"]["instructions"][1]];
+    let allocate_data = data(allocate);
+    assert_eq!(allocate_data[..4], 0u32.to_le_bytes());
+    assert_eq!(allocate_data[12..20], (space as u64).to_le_bytes());
+    assert_eq!(allocate_data[20..], *PROGRAM_ID.as_ref());
+
+    assert_eq!(init["program_id"], PROGRAM_ID.to_string());
+    assert_eq!(init["accounts"][0]["pubkey"], pubkey(2));
+    assert_eq!(init["accounts"][1]["is_signer"], true);
+    assert_eq!(init["accounts"][2]["pubkey"], NOOP_PROGRAM_ID.to_string());
+    let mut expected = sighash("global", "init_empty_merkle_tree").to_vec();
+    expected.extend_from_slice(&14u32.to_le_bytes());
+    expected.extend_from_slice(&64u32.to_le_bytes());
+    assert_eq!(data(init), expected);
+}
+
+#[tokio::test]
+async fn rejects_shapes_the_program_does_not_support() {
+    let body = json!({
+        "payer": pubkey(1),
+        "tree": pubkey(2),
+        "max_depth": 14,
+        "max_buffer_size": 100,
+        "canopy_depth": 15,
+    });
+
+    let (status, body) = post_json("/merkle-tree/create", body).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "UNSUPPORTED_TREE_SIZE");
+    assert_eq!(body["details"][1]["field"], "canopy_depth");
+    assert_eq!(body["details"][1]["code"], "CANOPY_DEPTH");
+
+    let (status, body) = get_json_from(test_app(), "/merkle-tree/cost?max_depth=15&max_buffer_size=2048").await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+}
+
+#[tokio::test]
+async fn quotes_the_cost_of_a_tree() {
+    let (status, body) = get_json_from(test_app(), "/merkle-tree/cost?max_depth=20&max_buffer_size=256").await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let space = merkle_tree::account_size(20, 256, 0);
+    assert_eq!(body["data"]["space"], space);
+    assert_eq!(body["data"]["lamports"], Rent::default().minimum_balance(space));
+    assert_eq!(body["data"]["max_leaves"], 1 << 20);
+}
+
+#[tokio::test]
+async fn appends_a_leaf() {
+    let leaf = [7u8; 32];
+    let body = json!({ "tree": pubkey(2), "authority": pubkey(1), "leaf": bs58::encode(leaf).into_string() });
+
+    let (status, body) = post_json("/merkle-tree/append", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let mut expected = sighash("global", "append").to_vec();
+    expected.extend_from_slice(&leaf);
+    assert_eq!(data(&body["data"]), expected);
+
+    let body = json!({ "tree": pubkey(2), "authority": pubkey(1), "leaf": "abc" });
+    let (status, body) = post_json("/merkle-tree/append", body).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+}
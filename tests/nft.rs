@@ -0,0 +1,229 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use axum::{routing::get, Json, Router};
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use solana_sdk::{pubkey::Pubkey, sysvar};
+use spl_associated_token_account::get_associated_token_address;
+use tokio::net::TcpListener;
+
+use solana_fellowship_server::config::{Config, NftConfig};
+use solana_fellowship_server::metaplex::{
+    edition_address, edition_marker_address, edition_marker_v2_address, metadata_address, token_record_address,
+    METADATA_PROGRAM_ID,
+};
+use solana_fellowship_server::rpc::MockRpc;
+
+use common::{app_with, assert_error, get_json_from, key, metadata_account, mint_account, mock_app, post_json, pubkey};
+
+/// Serves `/sup.json` and a too-large `/big.json`, counting requests.
+async fn serve_json() -> (String, Arc<AtomicUsize>) {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    let app = Router::new()
+        .route(
+            "/sup.json",
+            get(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async {
+                    Json(json!({
+                        "name": "Ignored",
+                        "description": "A super NFT",
+                        "image": "https://example.com/sup.png",
+                        "attributes": [{ "trait_type": "Power", "value": 9 }],
+                    }))
+                }
+            }),
+        )
+        .route("/big.json", get(|| async { Json(json!({ "padding": "x".repeat(4_096) })) }));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (format!("http://{address}"), hits)
+}
+
+fn nft_mock(uri: &str) -> Arc<MockRpc> {
+    let mock = Arc::new(MockRpc::new());
+    let mint = Pubkey::new_from_array([9; 32]);
+    mock.set_account(mint, mint_account(spl_token::id(), 0));
+    mock.set_account(metadata_address(&mint), metadata_account(mint, "Super #1", "SUP", uri));
+    mock
+}
+
+#[tokio::test]
+async fn metadata_merges_both_layers_and_is_cached() {
+    let (base, hits) = serve_json().await;
+    let app = mock_app(nft_mock(&format!("{base}/sup.json")));
+    let path = format!("/nft/{}/metadata", pubkey(9));
+
+    let (status, body) = get_json_from(app.clone(), &path).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data = &body["data"];
+    assert_eq!(data["name"], "Super #1");
+    assert_eq!(data["symbol"], "SUP");
+    assert_eq!(data["description"], "A super NFT");
+    assert_eq!(data["image"], "https://example.com/sup.png");
+    assert_eq!(data["attributes"], json!([{ "trait_type": "Power", "value": 9 }]));
+    assert_eq!(
+        data["on_chain"],
+        json!({
+            "update_authority": pubkey(7),
+            "seller_fee_basis_points": 500,
+            "creators": [{ "address": pubkey(7), "verified": true, "share": 100 }],
+            "primary_sale_happened": false,
+            "is_mutable": true,
+        })
+    );
+    assert_eq!(data["off_chain"]["name"], "Ignored");
+
+    let (_, again) = get_json_from(app, &path).await;
+    assert_eq!(again, body);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn unreadable_off_chain_json_is_reported_not_fatal() {
+    let (base, _) = serve_json().await;
+    let config = Config { nft: NftConfig { max_json_bytes: 1_024, ..NftConfig::default() }, ..Config::default() };
+    let app = app_with(config, nft_mock(&format!("{base}/big.json")));
+
+    let (status, body) = get_json_from(app, &format!("/nft/{}/metadata", pubkey(9))).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["name"], "Super #1");
+    assert!(body["data"]["off_chain"].is_null());
+    assert_eq!(body["data"]["off_chain_error"], "document exceeds 1024 bytes");
+}
+
+#[tokio::test]
+async fn mints_without_metadata_are_not_found() {
+    let (status, body) = get_json_from(mock_app(Arc::new(MockRpc::new())), &format!("/nft/{}/metadata", pubkey(9))).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "ACCOUNT_NOT_FOUND");
+}
+
+/// Each account's address and whether it is writable.
+fn accounts(instruction: &Value) -> Vec<(String, bool)> {
+    let accounts = instruction["accounts"].as_array().unwrap();
+    accounts.iter().map(|meta| (meta["pubkey"].as_str().unwrap().to_string(), meta["is_writable"] == true)).collect()
+}
+
+#[tokio::test]
+async fn burns_a_master_edition_from_the_owners_token_account() {
+    let (owner, mint) = (key(1), key(2));
+    let (status, body) = post_json("/nft/burn", json!({ "owner": owner.to_string(), "mint": mint.to_string() })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let instruction = &body["data"];
+    assert_eq!(instruction["program_id"], METADATA_PROGRAM_ID.to_string());
+    assert_eq!(instruction["accounts"][0]["is_signer"], true);
+    let unused = (METADATA_PROGRAM_ID.to_string(), false);
+    let expected = [
+        (owner.to_string(), true),
+        unused.clone(),
+        (metadata_address(&mint).to_string(), true),
+        (edition_address(&mint).to_string(), true),
+        (mint.to_string(), true),
+        (get_associated_token_address(&owner, &mint).to_string(), true),
+        unused.clone(),
+        unused.clone(),
+        unused.clone(),
+        unused.clone(),
+        unused,
+        ("11111111111111111111111111111111".to_string(), false),
+        (sysvar::instructions::ID.to_string(), false),
+        (spl_token::id().to_string(), false),
+    ];
+    assert_eq!(accounts(instruction), expected);
+    // `Burn`, `BurnArgs::V1 { amount: 1 }`.
+    assert_eq!(instruction["instruction_data"], "KQABAAAAAAAAAA==");
+}
+
+#[tokio::test]
+async fn burns_prints_with_their_marker_collection_and_token_record() {
+    let (owner, mint, token, collection, master) = (key(1), key(2), key(3), key(4), key(5));
+    let body = json!({
+        "owner": owner.to_string(),
+        "mint": mint.to_string(),
+        "token_account": token.to_string(),
+        "collection_mint": collection.to_string(),
+        "print_of": { "master_mint": master.to_string(), "master_token_account": pubkey(6), "edition": 300 },
+    });
+    let (status, response) = post_json("/nft/burn", body.clone()).await;
+    assert_eq!(status, StatusCode::OK, "body: {response}");
+    let burned = accounts(&response["data"]);
+    assert_eq!(burned[1], (metadata_address(&collection).to_string(), true));
+    assert_eq!(burned[5], (token.to_string(), true));
+    assert_eq!(burned[6], (edition_address(&master).to_string(), true));
+    assert_eq!(burned[7], (master.to_string(), false));
+    assert_eq!(burned[8], (pubkey(6), false));
+    assert_eq!(burned[9], (edition_marker_address(&master, 300).to_string(), true));
+    assert_eq!(burned[10], (METADATA_PROGRAM_ID.to_string(), false));
+    // Editions 248 to 495 share a marker.
+    assert_eq!(edition_marker_address(&master, 248), edition_marker_address(&master, 495));
+    assert_ne!(edition_marker_address(&master, 247), edition_marker_address(&master, 248));
+
+    let mut programmable = body.clone();
+    programmable["programmable"] = json!(true);
+    let (_, response) = post_json("/nft/burn", programmable).await;
+    let burned = accounts(&response["data"]);
+    assert_eq!(burned[9], (edition_marker_v2_address(&master).to_string(), true));
+    assert_eq!(burned[10], (token_record_address(&mint, &token).to_string(), true));
+
+    let mut first = body;
+    first["print_of"]["edition"] = json!(0);
+    let (status, response) = post_json("/nft/burn", first).await;
+    assert_error(status, &response, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(response["details"][0]["field"], "print_of.edition");
+}
+
+#[tokio::test]
+async fn prints_an_edition_with_its_marker() {
+    let (owner, master, new_mint) = (key(1), key(5), key(7));
+    let body = json!({
+        "master_mint": master.to_string(),
+        "owner": owner.to_string(),
+        "new_mint": new_mint.to_string(),
+        "new_mint_authority": pubkey(8),
+        "edition": 249,
+    });
+    let (status, response) = post_json("/nft/print-edition", body.clone()).await;
+    assert_eq!(status, StatusCode::OK, "body: {response}");
+
+    let printed = &response["data"];
+    let marker = edition_marker_address(&master, 249).to_string();
+    assert_eq!(printed["metadata"], metadata_address(&new_mint).to_string());
+    assert_eq!(printed["edition"], edition_address(&new_mint).to_string());
+    assert_eq!(printed["edition_marker"], marker);
+
+    let instruction = &printed["instruction"];
+    assert_eq!(instruction["program_id"], METADATA_PROGRAM_ID.to_string());
+    let expected = [
+        (metadata_address(&new_mint).to_string(), true),
+        (edition_address(&new_mint).to_string(), true),
+        (edition_address(&master).to_string(), true),
+        (new_mint.to_string(), true),
+        (marker, true),
+        (pubkey(8), false),
+        (owner.to_string(), true),
+        (owner.to_string(), false),
+        (get_associated_token_address(&owner, &master).to_string(), false),
+        (owner.to_string(), false),
+        (metadata_address(&master).to_string(), false),
+        (spl_token::id().to_string(), false),
+        ("11111111111111111111111111111111".to_string(), false),
+    ];
+    assert_eq!(accounts(instruction), expected);
+    let metas = instruction["accounts"].as_array().unwrap();
+    let signers: Vec<_> = (0..metas.len()).filter(|&i| metas[i]["is_signer"] == true).collect();
+    assert_eq!(signers, [5, 6, 7]);
+    // Discriminator 11, then the edition number.
+    assert_eq!(instruction["instruction_data"], "C/kAAAAAAAAA");
+
+    let mut first = body;
+    first["edition"] = json!(0);
+    let (status, response) = post_json("/nft/print-edition", first).await;
+    assert_error(status, &response, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(response["details"][0]["field"], "edition");
+}
@@ -0,0 +1,193 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_nonce::{
+    state::{Data as NonceData, DurableNonce, State as NonceState},
+    versions::Versions,
+};
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    message::Message,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    transaction::Transaction,
+};
+use solana_system_interface::{instruction as system_instruction, program as system_program};
+
+use solana_fellowship_server::config::{Config, NoncePoolConfig};
+use solana_fellowship_server::rpc::{ConfirmedTransaction, MockRpc, RpcError, TransactionMeta};
+use solana_fellowship_server::types::Redacted;
+
+use common::{app_with, assert_error, get_json_from, key, keypair, mock_app, post_json_to, pubkey};
+
+fn authority() -> Keypair {
+    keypair(60)
+}
+
+fn nonce_account(authority: Pubkey, seed: u8) -> Account {
+    let nonce = DurableNonce::from_blockhash(&Hash::new_from_array([seed; 32]));
+    let state = NonceState::Initialized(NonceData::new(authority, nonce, 5_000));
+    Account {
+        lamports: 1_447_680,
+        data: bincode::serialize(&Versions::new(state)).unwrap(),
+        owner: system_program::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// App with a pool of the nonce accounts at `key(20)` and `key(21)`.
+async fn setup() -> (axum::Router, Arc<MockRpc>) {
+    let config = Config {
+        nonce_pool: NoncePoolConfig {
+            authority: Some(Redacted(bs58::encode(authority().to_bytes()).into_string())),
+            ..NoncePoolConfig::default()
+        },
+        ..Config::default()
+    };
+    let mock = Arc::new(MockRpc::new());
+    mock.set_account(key(20), nonce_account(authority().pubkey(), 20));
+    mock.set_account(key(21), nonce_account(authority().pubkey(), 21));
+    let app = app_with(config, mock.clone());
+
+    let request = json!({ "accounts": [pubkey(20), pubkey(21)] });
+    let (status, body) = post_json_to(app.clone(), "/nonce-pool/accounts", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["added"], json!([pubkey(20), pubkey(21)]));
+    assert_eq!(body["data"]["total"], 2);
+    (app, mock)
+}
+
+#[tokio::test]
+async fn requires_a_configured_authority() {
+    let app = mock_app(Arc::new(MockRpc::new()));
+    let (status, body) = get_json_from(app, "/nonce-pool").await;
+    assert_error(status, &body, StatusCode::SERVICE_UNAVAILABLE, "NONCE_POOL_UNAVAILABLE");
+}
+
+#[tokio::test]
+async fn audit_entries_redact_an_inline_payer_secret() {
+    let (app, mock) = setup().await;
+    mock.set_send_error(Some(RpcError("node unavailable".to_string())));
+    let payer = bs58::encode(keypair(1).to_bytes()).into_string();
+    let request = json!({ "count": 1, "payer": payer });
+    let (status, _) = post_json_to(app.clone(), "/nonce-pool/accounts", request).await;
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+
+    let (status, body) = get_json_from(app, "/audit").await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let entry = &body["data"][0];
+    assert_eq!(entry["action"], "nonce_pool.create");
+    assert_eq!(entry["request"]["payer"], "[REDACTED]");
+    assert_eq!(entry["request"]["count"], 1);
+    assert!(!body.to_string().contains(&payer));
+}
+
+#[tokio::test]
+async fn leases_each_account_once_until_released() {
+    let (app, mock) = setup().await;
+
+    let mut leases = Vec::new();
+    for _ in 0..2 {
+        let (status, body) = post_json_to(app.clone(), "/nonce-pool/acquire", json!({})).await;
+        assert_eq!(status, StatusCode::OK, "body: {body}");
+        leases.push(body["data"].clone());
+    }
+    assert_ne!(leases[0]["nonce_account"], leases[1]["nonce_account"]);
+    let lease = &leases[0];
+    let nonce = DurableNonce::from_blockhash(&Hash::new_from_array([20; 32]));
+    assert_eq!(lease["nonce_account"], pubkey(20));
+    assert_eq!(lease["nonce"], nonce.as_hash().to_string());
+    assert_eq!(lease["authority"], authority().pubkey().to_string());
+    assert_eq!(lease["advance_instruction"]["program_id"], system_program::ID.to_string());
+
+    let (status, body) = post_json_to(app.clone(), "/nonce-pool/acquire", json!({})).await;
+    assert_error(status, &body, StatusCode::CONFLICT, "CONFLICT");
+
+    let request = json!({ "lease_id": lease["lease_id"] });
+    let (status, body) = post_json_to(app.clone(), "/nonce-pool/release", request.clone()).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["available"], 1);
+    let (status, body) = post_json_to(app.clone(), "/nonce-pool/release", request).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+
+    // An account whose authority changed can't be leased.
+    mock.set_account(key(20), nonce_account(key(3), 20));
+    let (status, body) = post_json_to(app, "/nonce-pool/acquire", json!({})).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_ACCOUNT");
+}
+
+#[tokio::test]
+async fn rejects_accounts_the_authority_does_not_control() {
+    let (app, mock) = setup().await;
+    mock.set_account(key(22), nonce_account(key(3), 22));
+
+    let (status, body) = post_json_to(app.clone(), "/nonce-pool/accounts", json!({ "accounts": [pubkey(22)] })).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_ACCOUNT");
+
+    let (status, body) = post_json_to(app, "/nonce-pool/accounts", json!({ "count": 2 })).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+}
+
+#[tokio::test]
+async fn send_advances_leased_nonce_and_releases_it() {
+    let (app, mock) = setup().await;
+    let (_, body) = post_json_to(app.clone(), "/nonce-pool/acquire", json!({ "ttl_secs": 30 })).await;
+    let lease = &body["data"];
+    let account: Pubkey = lease["nonce_account"].as_str().unwrap().parse().unwrap();
+    let nonce: Hash = lease["nonce"].as_str().unwrap().parse().unwrap();
+
+    let payer = keypair(1);
+    let encode = |blockhash: &Hash| {
+        let instructions = [
+            system_instruction::advance_nonce_account(&account, &authority().pubkey()),
+            system_instruction::transfer(&payer.pubkey(), &key(2), 1_000),
+        ];
+        let message = Message::new_with_blockhash(&instructions, Some(&payer.pubkey()), blockhash);
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction.partial_sign(&[&payer], *blockhash);
+        (general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap()), transaction.signatures[0])
+    };
+
+    // A transaction on any other blockhash isn't accepted for the lease.
+    let (stale, _) = encode(&Hash::new_from_array([1; 32]));
+    let request = json!({ "transaction": stale, "nonce_lease": lease["lease_id"] });
+    let (status, body) = post_json_to(app.clone(), "/transaction/send", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+
+    let secret = bs58::encode(payer.to_bytes()).into_string();
+    let request = json!({ "transaction": stale, "nonce_lease": lease["lease_id"], "signers": [secret] });
+    let (status, body) = post_json_to(app.clone(), "/transaction/send", request).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+
+    let (transaction, signature) = encode(&nonce);
+    let cluster = mock.clone();
+    let chain = tokio::spawn(async move {
+        while cluster.sent_transactions().is_empty() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let transaction = cluster.sent_transactions().pop().unwrap();
+        let meta = TransactionMeta { fee: 10_000, ..TransactionMeta::default() };
+        cluster.set_transaction(signature, ConfirmedTransaction { slot: 88, block_time: None, transaction, meta });
+    });
+    let request = json!({ "transaction": transaction, "nonce_lease": lease["lease_id"] });
+    let (status, body) = post_json_to(app.clone(), "/transaction/send", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    chain.await.unwrap();
+
+    let sent = mock.sent_transactions();
+    assert_eq!(sent.len(), 1);
+    assert!(sent[0].verify_with_results().iter().all(|valid| *valid));
+    assert_eq!(body["data"]["signature"], signature.to_string());
+    assert_eq!(body["data"]["slot"], 88);
+    assert_eq!(body["data"]["last_valid_block_height"], Value::Null);
+
+    let (_, body) = get_json_from(app, "/nonce-pool").await;
+    assert_eq!(body["data"]["available"], 2);
+}
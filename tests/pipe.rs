@@ -0,0 +1,50 @@
+mod common;
+
+use serde_json::{json, Value};
+
+use solana_fellowship_server::pipe;
+
+use common::{pubkey, test_app};
+
+async fn run(input: &str) -> Vec<Value> {
+    let mut output = Vec::new();
+    pipe::run(test_app(), input.as_bytes(), &mut output).await.unwrap();
+    String::from_utf8(output).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+}
+
+#[tokio::test]
+async fn pipe_answers_each_command_in_order() {
+    let send = json!({
+        "id": "a",
+        "path": "/send/sol",
+        "body": { "from": pubkey(1), "to": pubkey(2), "lamports": 1000 },
+    });
+    let derive = json!({
+        "id": 7,
+        "method": "get",
+        "path": format!("/derive/ata?owner={}&mint={}", pubkey(1), pubkey(2)),
+    });
+    let replies = run(&format!("{send}\n\n{derive}\n")).await;
+
+    assert_eq!(replies.len(), 2);
+    assert_eq!(replies[0]["id"], "a");
+    assert_eq!(replies[0]["status"], 200, "reply: {}", replies[0]);
+    assert_eq!(replies[0]["body"]["success"], true);
+    assert_eq!(replies[1]["id"], 7);
+    assert_eq!(replies[1]["status"], 200, "reply: {}", replies[1]);
+}
+
+#[tokio::test]
+async fn pipe_reports_errors_without_stopping() {
+    let bad_body = json!({ "id": 1, "path": "/send/sol", "body": { "from": "nope" } });
+    let replies = run(&format!("not json\n{{\"id\": 2}}\n{bad_body}\n")).await;
+
+    assert_eq!(replies.len(), 3);
+    assert!(replies[0].get("id").is_none());
+    assert_eq!(replies[0]["status"], 400);
+    assert_eq!(replies[0]["body"]["success"], false);
+    assert_eq!(replies[1]["status"], 400);
+    assert_eq!(replies[2]["id"], 1);
+    assert_eq!(replies[2]["status"], 400);
+    assert_eq!(replies[2]["body"]["success"], false);
+}
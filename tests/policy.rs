@@ -0,0 +1,263 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{http::StatusCode, Router};
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::{Message, VersionedMessage},
+    signer::Signer,
+    transaction::Transaction,
+};
+use solana_system_interface::instruction as system_instruction;
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::instruction::AuthorityType::AccountOwner;
+
+use solana_fellowship_server::config::{Config, PolicyConfig, TokenPolicy};
+use solana_fellowship_server::jito::{MockBlockEngine, TIP_ACCOUNTS};
+use solana_fellowship_server::policy::{self, Policy, PolicyError, Transfer};
+use solana_fellowship_server::rpc::MockRpc;
+use solana_fellowship_server::solana_pay::MEMO_PROGRAM_ID;
+use solana_fellowship_server::state::AppState;
+use solana_fellowship_server::types::PubkeyStr;
+
+use common::{app_with, assert_error, get_json_from, key, keypair, post_json_to};
+
+fn secret(seed: u8) -> String {
+    bs58::encode(keypair(seed).to_bytes()).into_string()
+}
+
+fn sol(to: u8, lamports: u64) -> Instruction {
+    system_instruction::transfer(&keypair(1).pubkey(), &key(to), lamports)
+}
+
+fn token(to: u8, amount: u64, checked: bool) -> Instruction {
+    let owner = keypair(1).pubkey();
+    let source = get_associated_token_address(&owner, &key(9));
+    let destination = get_associated_token_address(&key(to), &key(9));
+    if checked {
+        spl_token::instruction::transfer_checked(&spl_token::ID, &source, &key(9), &destination, &owner, &[], amount, 6)
+    } else {
+        spl_token::instruction::transfer(&spl_token::ID, &source, &destination, &owner, &[], amount)
+    }
+    .unwrap()
+}
+
+fn transfers(instructions: &[Instruction]) -> Vec<Transfer> {
+    let message = VersionedMessage::Legacy(Message::new(instructions, Some(&keypair(1).pubkey())));
+    policy::transfers(&message, message.static_account_keys())
+}
+
+fn template(instruction: &Instruction) -> Value {
+    json!({
+        "program_id": instruction.program_id.to_string(),
+        "accounts": instruction.accounts.iter().map(|meta| json!({
+            "pubkey": meta.pubkey.to_string(),
+            "is_signer": meta.is_signer,
+            "is_writable": meta.is_writable,
+        })).collect::<Vec<_>>(),
+        "data": general_purpose::STANDARD.encode(&instruction.data),
+    })
+}
+
+async fn run_job(app: Router, instruction: &Instruction) -> Value {
+    let request = json!({
+        "signers": [secret(1)],
+        "instructions": [template(instruction)],
+        "execute_at": "2020-01-01T00:00:00Z",
+    });
+    let (status, body) = post_json_to(app.clone(), "/jobs", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let path = format!("/jobs/{}", body["data"]["id"].as_str().unwrap());
+    for _ in 0..200 {
+        let (_, body) = get_json_from(app.clone(), &path).await;
+        if !matches!(body["data"]["status"].as_str(), Some("scheduled" | "running")) {
+            return body["data"].clone();
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("job never finished");
+}
+
+#[test]
+fn extracts_transfers() {
+    let found = transfers(&[sol(2, 5), token(3, 7, true), token(3, 8, false)]);
+    assert_eq!(found.len(), 3);
+    assert_eq!((found[0].authority, found[0].destination, found[0].amount), (keypair(1).pubkey(), key(2), 5));
+    assert_eq!(found[1].destination, get_associated_token_address(&key(3), &key(9)));
+    assert_eq!(found[1].authority, keypair(1).pubkey());
+    assert_eq!(found[2].amount, 8);
+}
+
+#[test]
+fn checks_destinations_and_mints() {
+    let policy = Policy::new(PolicyConfig {
+        allowed_destinations: vec![PubkeyStr(key(2)), PubkeyStr(key(3))],
+        denied_destinations: vec![PubkeyStr(key(3))],
+        ..PolicyConfig::default()
+    });
+    assert_eq!(policy.review(&transfers(&[sol(2, 1)])), Ok(()));
+    // An allowed wallet's associated token account counts as the wallet.
+    assert_eq!(policy.review(&transfers(&[token(2, 1, true)])), Ok(()));
+    assert_eq!(policy.review(&transfers(&[sol(3, 1)])), Err(PolicyError::DestinationDenied(key(3))));
+    assert_eq!(policy.review(&transfers(&[sol(4, 1)])), Err(PolicyError::DestinationNotAllowed(key(4))));
+
+    let policy = Policy::new(PolicyConfig { allowed_mints: vec![PubkeyStr(key(8))], ..PolicyConfig::default() });
+    assert_eq!(policy.review(&transfers(&[sol(2, 1)])), Ok(()));
+    assert_eq!(policy.review(&transfers(&[token(2, 1, true)])), Err(PolicyError::MintNotAllowed(key(9))));
+    assert_eq!(policy.review(&transfers(&[token(2, 1, false)])), Err(PolicyError::UnknownMint(0)));
+}
+
+#[test]
+fn enforces_daily_limits_and_approval_thresholds() {
+    let policy = Policy::new(PolicyConfig {
+        daily_lamport_limit: Some(1_000),
+        approval_threshold_lamports: Some(600),
+        tokens: vec![TokenPolicy { mint: PubkeyStr(key(9)), daily_limit: Some(10), approval_threshold: None }],
+        ..PolicyConfig::default()
+    });
+
+    // Thresholds apply to the total one key moves in one operation.
+    let error = policy.enforce(&transfers(&[sol(2, 400), sol(3, 400)])).unwrap_err();
+    assert_eq!(error.code(), "APPROVAL_REQUIRED");
+
+    assert_eq!(policy.enforce(&transfers(&[sol(2, 600)])), Ok(()));
+    assert_eq!(policy.enforce(&transfers(&[sol(2, 400), token(2, 10, true)])), Ok(()));
+    assert_eq!(policy.enforce(&transfers(&[sol(2, 1)])).unwrap_err().code(), "DAILY_LIMIT_EXCEEDED");
+    assert_eq!(policy.enforce(&transfers(&[token(2, 1, true)])).unwrap_err().code(), "DAILY_LIMIT_EXCEEDED");
+}
+
+#[test]
+fn counts_account_funding_and_delegation() {
+    let owner = keypair(1).pubkey();
+    let policy = Policy::new(PolicyConfig {
+        daily_lamport_limit: Some(1_000),
+        tokens: vec![TokenPolicy { mint: PubkeyStr(key(9)), daily_limit: Some(10), approval_threshold: None }],
+        ..PolicyConfig::default()
+    });
+
+    let create = system_instruction::create_account(&owner, &key(5), 2_000, 0, &key(6));
+    let found = transfers(&[create]);
+    assert_eq!((found[0].authority, found[0].destination, found[0].amount), (owner, key(5), 2_000));
+    assert_eq!(policy.enforce(&found).unwrap_err().code(), "DAILY_LIMIT_EXCEEDED");
+
+    // A delegate can move whatever it is approved for.
+    let source = get_associated_token_address(&owner, &key(9));
+    let approve =
+        spl_token::instruction::approve_checked(&spl_token::ID, &source, &key(9), &key(4), &owner, &[], 11, 6).unwrap();
+    let found = transfers(&[approve]);
+    assert_eq!((found[0].authority, found[0].destination, found[0].amount), (owner, key(4), 11));
+    assert_eq!(policy.enforce(&found).unwrap_err().code(), "DAILY_LIMIT_EXCEEDED");
+}
+
+#[test]
+fn refuses_instructions_it_cannot_read() {
+    let owner = keypair(1).pubkey();
+    let foreign = || Instruction::new_with_bytes(key(77), &[1], vec![AccountMeta::new(owner, true)]);
+    assert_eq!(Policy::new(PolicyConfig::default()).review(&transfers(&[foreign()])), Ok(()));
+
+    let policy = Policy::new(PolicyConfig { denied_destinations: vec![PubkeyStr(key(3))], ..PolicyConfig::default() });
+    assert_eq!(policy.review(&transfers(&[sol(2, 1), foreign()])), Err(PolicyError::Unclassified(1)));
+
+    let source = get_associated_token_address(&owner, &key(9));
+    let new_owner = Some(&key(4));
+    let hand_over =
+        spl_token::instruction::set_authority(&spl_token::ID, &source, new_owner, AccountOwner, &owner, &[]).unwrap();
+    assert_eq!(policy.review(&transfers(&[hand_over])), Err(PolicyError::Unclassified(0)));
+
+    // Closing into the owner's own wallet and memos move nothing.
+    let close = spl_token::instruction::close_account(&spl_token::ID, &source, &owner, &owner, &[]).unwrap();
+    let memo = Instruction::new_with_bytes(MEMO_PROGRAM_ID, b"hi", vec![AccountMeta::new_readonly(owner, true)]);
+    assert_eq!(transfers(&[close, memo]), []);
+}
+
+#[tokio::test]
+async fn refuses_scheduled_sends_and_records_audit_entries() {
+    let config = Config {
+        policy: PolicyConfig {
+            daily_lamport_limit: Some(1_000),
+            allowed_destinations: vec![PubkeyStr(key(2))],
+            ..PolicyConfig::default()
+        },
+        ..Config::default()
+    };
+    let mock = Arc::new(MockRpc::new());
+    let app = app_with(config, mock.clone());
+
+    let job = run_job(app.clone(), &sol(2, 800)).await;
+    assert_eq!(job["status"], "succeeded", "job: {job}");
+
+    // Over the daily limit: refused at run time, without retries.
+    let job = run_job(app.clone(), &sol(2, 800)).await;
+    assert_eq!(job["status"], "failed", "job: {job}");
+    assert_eq!(job["attempts"], 1);
+    assert!(job["last_error"].as_str().unwrap().contains("daily limit"));
+    assert_eq!(mock.sent_transactions().len(), 1);
+
+    // Not on the allowlist: refused when scheduling.
+    let request = json!({ "signers": [secret(1)], "instructions": [template(&sol(4, 1))], "cron": "* * * * *" });
+    let (status, body) = post_json_to(app.clone(), "/jobs", request).await;
+    assert_error(status, &body, StatusCode::FORBIDDEN, "DESTINATION_NOT_ALLOWED");
+
+    let (status, body) = get_json_from(app, "/audit").await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let entries = body["data"].as_array().unwrap();
+    let summary: Vec<_> =
+        entries.iter().map(|entry| (entry["action"].as_str().unwrap(), entry["allowed"].clone())).collect();
+    assert_eq!(
+        summary,
+        [
+            ("jobs.schedule", json!(true)),
+            ("jobs.run", json!(true)),
+            ("jobs.schedule", json!(true)),
+            ("jobs.run", json!(false)),
+            ("jobs.schedule", json!(false)),
+        ]
+    );
+    assert_eq!(entries[3]["code"], "DAILY_LIMIT_EXCEEDED");
+    assert_eq!(entries[3]["signers"], json!([keypair(1).pubkey().to_string()]));
+
+    // Entries keep the request behind them, minus the secret keys.
+    assert_eq!(entries[0]["request"]["signers"], "[REDACTED]");
+    assert_eq!(entries[0]["request"]["execute_at"], "2020-01-01T00:00:00Z");
+    assert!(entries[1].get("request").is_none());
+    assert!(!body.to_string().contains(&secret(1)));
+}
+
+#[tokio::test]
+async fn refuses_bundles_over_approval_threshold() {
+    let config = Config {
+        policy: PolicyConfig { approval_threshold_lamports: Some(10_000), ..PolicyConfig::default() },
+        ..Config::default()
+    };
+    let engine = Arc::new(MockBlockEngine::new());
+    let mut state = AppState::new(config);
+    state.block_engine = Some(engine.clone());
+    let app = solana_fellowship_server::app(state);
+
+    let payer = keypair(1);
+    let tip = || system_instruction::transfer(&payer.pubkey(), &TIP_ACCOUNTS[0], 1_000);
+    let signed = |instructions: &[Instruction]| {
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::new_from_array([4; 32]),
+        );
+        general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap())
+    };
+
+    // 9_500 plus the 1_000 tip crosses the threshold.
+    let request = json!({ "transactions": [signed(&[sol(2, 9_500)]), signed(&[tip()])] });
+    let (status, body) = post_json_to(app.clone(), "/transaction/send-bundle", request).await;
+    assert_error(status, &body, StatusCode::FORBIDDEN, "APPROVAL_REQUIRED");
+    assert!(engine.bundles().is_empty());
+
+    let request = json!({ "transactions": [signed(&[sol(2, 9_000), tip()])] });
+    let (status, body) = post_json_to(app, "/transaction/send-bundle", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+}
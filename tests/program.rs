@@ -0,0 +1,239 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_loader_v3_interface::{get_program_data_address, instruction as loader};
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    transaction::Transaction,
+};
+use spl_token::state::{Account as TokenAccount, AccountState};
+
+use solana_fellowship_server::rpc::MockRpc;
+use solana_fellowship_server::tx::{self, MAX_TRANSACTION_SIZE};
+
+use common::{
+    assert_error, call, get_json_from, key, mint_account, mock_app, post_json, post_json_to, pubkey, test_app,
+};
+
+fn holding(mint: u8, owner: u8, amount: u64) -> Account {
+    let mut data = vec![0; TokenAccount::LEN];
+    TokenAccount {
+        mint: key(mint),
+        owner: key(owner),
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    Account { lamports: 2_039_280, data, owner: spl_token::id(), executable: false, rent_epoch: 0 }
+}
+
+fn program_mock() -> Arc<MockRpc> {
+    let mock = Arc::new(MockRpc::new());
+    mock.set_account(key(9), mint_account(spl_token::id(), 2));
+    mock.set_account(key(20), holding(9, 1, 500));
+    mock.set_account(key(21), holding(9, 2, 250));
+    mock.set_account(key(22), holding(8, 1, 7));
+    let data = [[0xAB; 8], [3; 8]].concat();
+    mock.set_account(key(30), Account { lamports: 1, data, owner: key(40), executable: false, rent_epoch: 0 });
+    mock
+}
+
+#[tokio::test]
+async fn token_presets_filter_and_decode() {
+    let app = mock_app(program_mock());
+    let path = format!("/program/{}/accounts?mint={}&owner={}", spl_token::id(), pubkey(9), pubkey(1));
+
+    let (status, body) = get_json_from(app, &path).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data = &body["data"];
+    assert_eq!(data["count"], 1);
+    let account = &data["accounts"][0];
+    assert_eq!(account["pubkey"], pubkey(20));
+    assert_eq!(account["layout"], "token_account");
+    assert_eq!(account["parsed"]["amount"], "500");
+    assert!(account.get("data").is_none());
+}
+
+#[tokio::test]
+async fn memcmp_matches_raw_bytes_of_unknown_layouts() {
+    let app = mock_app(program_mock());
+    let bytes = bs58::encode([3; 4]).into_string();
+
+    let (status, body) = get_json_from(app, &format!("/program/{}/accounts?memcmp=8:{bytes}&data_size=16", pubkey(40))).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let account = &body["data"]["accounts"][0];
+    assert_eq!(account["pubkey"], pubkey(30));
+    assert_eq!(account["data_len"], 16);
+    assert!(account.get("layout").is_none());
+    assert_eq!(account["data"], "q6urq6urq6sDAwMDAwMDAw==");
+}
+
+#[tokio::test]
+async fn program_accounts_are_limited_and_need_filters() {
+    let app = mock_app(program_mock());
+
+    let path = format!("/program/{}/accounts?mint={}&limit=1", spl_token::id(), pubkey(9));
+    let (status, body) = get_json_from(app.clone(), &path).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "TOO_MANY_ACCOUNTS");
+
+    let (status, body) = get_json_from(app.clone(), &format!("/program/{}/accounts", spl_token::id())).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "filters");
+
+    let (status, body) = get_json_from(app, &format!("/program/{}/accounts?mint={}", pubkey(40), pubkey(9))).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "UNSUPPORTED_TOKEN_PROGRAM");
+}
+
+fn instruction(response: &Value) -> Instruction {
+    let key = |value: &Value| value.as_str().unwrap().parse::<Pubkey>().unwrap();
+    let accounts = response["accounts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|meta| AccountMeta {
+            pubkey: key(&meta["pubkey"]),
+            is_signer: meta["is_signer"].as_bool().unwrap(),
+            is_writable: meta["is_writable"].as_bool().unwrap(),
+        })
+        .collect();
+    let data = general_purpose::STANDARD.decode(response["instruction_data"].as_str().unwrap()).unwrap();
+    Instruction { program_id: key(&response["program_id"]), accounts, data }
+}
+
+#[tokio::test]
+async fn buffer_writes_cover_the_program_and_each_fit_a_transaction() {
+    let program: Vec<u8> = (0..3_000u32).map(|i| i as u8).collect();
+    let body = json!({
+        "payer": pubkey(1),
+        "buffer": pubkey(2),
+        "authority": pubkey(3),
+        "program": general_purpose::STANDARD.encode(&program),
+    });
+
+    let (status, body) = post_json("/program/buffer", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data = &body["data"];
+    assert_eq!(data["buffer"], pubkey(2));
+    assert_eq!(data["program_len"], 3_000);
+    let create = loader::create_buffer(&key(1), &key(2), &key(3), data["lamports"].as_u64().unwrap(), 3_000).unwrap();
+    let setup: Vec<_> = data["instructions"].as_array().unwrap().iter().map(instruction).collect();
+    assert_eq!(setup, create);
+
+    let mut written = Vec::new();
+    for write in data["writes"].as_array().unwrap() {
+        let write = instruction(write);
+        let size = tx::serialized_size(&tx::unsigned(std::slice::from_ref(&write), &key(1), Hash::default()));
+        assert!(size <= MAX_TRANSACTION_SIZE, "{size} bytes");
+        let offset = u32::from_le_bytes(write.data[4..8].try_into().unwrap()) as usize;
+        assert_eq!(offset, written.len());
+        written.extend_from_slice(&write.data[16..]);
+    }
+    assert_eq!(written, program);
+    assert!(data["writes"].as_array().unwrap().len() <= 4);
+}
+
+#[tokio::test]
+async fn deploy_reads_the_program_length_from_the_buffer() {
+    let mock = Arc::new(MockRpc::new());
+    let data = vec![0; 37 + 5_000];
+    let owner = solana_sdk::bpf_loader_upgradeable::ID;
+    mock.set_account(key(2), Account { lamports: 1, data, owner, executable: false, rent_epoch: 0 });
+    let body = json!({ "payer": pubkey(1), "program_id": pubkey(4), "buffer": pubkey(2) });
+
+    let (status, body) = post_json_to(mock_app(mock), "/program/deploy", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data = &body["data"];
+    assert_eq!(data["max_data_len"], 5_000);
+    assert_eq!(data["program_data"], get_program_data_address(&key(4)).to_string());
+    let instructions: Vec<_> = data["instructions"].as_array().unwrap().iter().map(instruction).collect();
+    let lamports = instructions[0].data[4..12].try_into().map(u64::from_le_bytes).unwrap();
+    #[allow(deprecated)]
+    let expected = loader::deploy_with_max_program_len(&key(1), &key(4), &key(2), &key(1), lamports, 5_000).unwrap();
+    assert_eq!(instructions, expected);
+}
+
+#[tokio::test]
+async fn authority_changes_need_a_new_authority_unless_freezing_a_program() {
+    let body = json!({ "account": pubkey(4), "account_type": "program", "authority": pubkey(1) });
+    let (status, body) = post_json("/program/set-authority", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(instruction(&body["data"]), loader::set_upgrade_authority(&key(4), &key(1), None));
+
+    let body = json!({ "account": pubkey(2), "account_type": "buffer", "authority": pubkey(1), "checked": true });
+    let (status, body) = post_json("/program/set-authority", body).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "REQUIRED_WITH");
+    assert_eq!(body["details"].as_array().unwrap().len(), 2);
+}
+
+async fn delete(app: Router, path: &str) -> (StatusCode, Value) {
+    let (status, _, bytes) = call(app, Request::delete(path).body(Body::empty()).unwrap()).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn uploads_pack_buffer_transactions_and_estimate_the_deploy() {
+    let app = test_app();
+    let program = vec![7u8; 2_000];
+    let body = json!({
+        "payer": pubkey(1),
+        "buffer": pubkey(2),
+        "authority": pubkey(3),
+        "program": general_purpose::STANDARD.encode(&program),
+        "recent_blockhash": Hash::new_from_array([5; 32]).to_string(),
+    });
+
+    let (status, body) = post_json_to(app.clone(), "/program/uploads", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data = &body["data"];
+    assert_eq!(data["sha256"], hex::encode(solana_sdk::hash::hash(&program).to_bytes()));
+    assert_eq!(data["program_len"], 2_000);
+    assert_eq!(data["authority"], pubkey(3));
+    let transactions: Vec<Transaction> = data["transactions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|encoded| bincode::deserialize(&general_purpose::STANDARD.decode(encoded.as_str().unwrap()).unwrap()))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(transactions[0].message.header.num_required_signatures, 2);
+    assert!(transactions.iter().all(|transaction| tx::serialized_size(transaction) <= MAX_TRANSACTION_SIZE));
+    let blockhash = Hash::new_from_array([5; 32]);
+    assert!(transactions.iter().all(|transaction| transaction.message.recent_blockhash == blockhash));
+
+    let cost = &data["cost"];
+    let writes = transactions.len() as u64 - 1;
+    assert_eq!(cost["transactions"], transactions.len() + 1);
+    assert_eq!(cost["fee_lamports"], (2 + 2 * writes + 3) * 5_000);
+    let buffer = cost["buffer_lamports"].as_u64().unwrap();
+    let total = cost["total_lamports"].as_u64().unwrap();
+    assert_eq!(cost["net_lamports"], total - buffer);
+
+    let id = data["id"].as_str().unwrap();
+    let path = format!("/program/uploads/{id}?recent_blockhash={}", Hash::new_from_array([6; 32]));
+    let (status, body) = get_json_from(app.clone(), &path).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["id"], id);
+    assert_eq!(body["data"]["recent_blockhash"], Hash::new_from_array([6; 32]).to_string());
+    assert_eq!(body["data"]["transactions"].as_array().unwrap().len(), transactions.len());
+
+    let (status, _) = delete(app.clone(), &format!("/program/uploads/{id}")).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = get_json_from(app, &path).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use serde_json::Value;
+
+use common::{assert_error, call, get_json, pubkey, test_app};
+
+async fn get(path: &str) -> (StatusCode, String, Vec<u8>) {
+    let (status, headers, bytes) = call(test_app(), Request::get(path).body(Body::empty()).unwrap()).await;
+    let content_type = headers[header::CONTENT_TYPE].to_str().unwrap().to_string();
+    (status, content_type, bytes.to_vec())
+}
+
+fn escape(value: &str) -> String {
+    form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+#[tokio::test]
+async fn qr_renders_png_at_requested_size() {
+    let (status, content_type, bytes) = get("/qr?data=hello&size=300").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "image/png");
+
+    let image = image::load_from_memory(&bytes).unwrap();
+    assert!(image.width() >= 300 && image.width() == image.height());
+}
+
+#[tokio::test]
+async fn qr_renders_svg() {
+    let (status, content_type, bytes) = get("/qr?data=hello&format=svg&ec=H").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "image/svg+xml");
+    assert!(String::from_utf8(bytes).unwrap().contains("<svg"));
+}
+
+#[tokio::test]
+async fn qr_rejects_bad_options() {
+    let (status, body) = get_json("/qr?data=hello&size=10").await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "QR_SIZE");
+    assert_eq!(body["field"], "size");
+
+    let (status, body) = get_json("/qr?data=hello&format=gif").await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "format");
+
+    let (status, body) = get_json(&format!("/qr?data={}&ec=H", "x".repeat(2000))).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "QR_DATA_TOO_LONG");
+}
+
+#[tokio::test]
+async fn solana_pay_qr_validates_the_url() {
+    let url = format!("solana:{}?amount=1", pubkey(1));
+    let (status, content_type, _) = get(&format!("/solana-pay/qr?url={}", escape(&url))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "image/png");
+
+    let (status, body): (StatusCode, Value) = get_json("/solana-pay/qr?url=https%3A%2F%2Fexample.com").await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_PAYMENT_URL");
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::get,
+    Router,
+};
+use serde_json::Value;
+
+use solana_fellowship_server::config::Config;
+use solana_fellowship_server::recover;
+use solana_fellowship_server::request_id::{self, REQUEST_ID};
+use solana_fellowship_server::state::AppState;
+
+use common::{assert_error, call};
+
+async fn boom() -> StatusCode {
+    panic!("ledger exploded")
+}
+
+#[tokio::test]
+async fn panics_become_json_500s_with_the_request_id() {
+    let state = AppState::new(Config::default());
+    let app = Router::new()
+        .route("/boom", get(boom))
+        .route("/fine", get(|| async { StatusCode::NO_CONTENT }))
+        .layer(middleware::from_fn_with_state(state.clone(), recover::catch_panic))
+        .layer(middleware::from_fn(request_id::assign))
+        .with_state(state.clone());
+
+    let (status, headers, bytes) = call(app.clone(), Request::get("/boom").body(Body::empty()).unwrap()).await;
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_error(status, &body, StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL");
+    assert_eq!(body["request_id"], headers[REQUEST_ID].to_str().unwrap());
+    assert!(!body["error"].as_str().unwrap().contains("ledger"), "panic messages stay server-side");
+
+    let (status, _, _) = call(app, Request::get("/fine").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let metrics = state.metrics.render();
+    assert!(metrics.contains(r#"superdev_http_panics_total{method="GET",route="/boom"} 1"#), "{metrics}");
+}
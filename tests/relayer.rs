@@ -0,0 +1,163 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    transaction::{Transaction, VersionedTransaction},
+};
+use solana_system_interface::instruction as system_instruction;
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::instruction::transfer_checked;
+
+use solana_fellowship_server::config::{Config, RelayerConfig, RelayerFee};
+use solana_fellowship_server::rpc::MockRpc;
+use solana_fellowship_server::solana_pay::memo_instruction;
+use solana_fellowship_server::types::{PubkeyStr, Redacted, SecretKeyStr};
+
+use common::{app_with, assert_error, get_json_from, key, keypair, mint_account, mock_app, post_json_to, pubkey};
+
+const FEE: u64 = 10_000;
+
+fn relayer() -> Keypair {
+    keypair(50)
+}
+
+fn setup() -> (axum::Router, Arc<MockRpc>) {
+    let config = Config {
+        relayer: RelayerConfig {
+            secret: Some(Redacted(bs58::encode(relayer().to_bytes()).into_string())),
+            fee: Some(RelayerFee { mint: PubkeyStr(key(9)), amount: FEE }),
+            ..RelayerConfig::default()
+        },
+        ..Config::default()
+    };
+    let mock = Arc::new(MockRpc::new());
+    mock.set_account(key(9), mint_account(spl_token::ID, 6));
+    (app_with(config, mock.clone()), mock)
+}
+
+fn fee_instruction(amount: u64) -> Instruction {
+    let user = keypair(1).pubkey();
+    transfer_checked(
+        &spl_token::ID,
+        &get_associated_token_address(&user, &key(9)),
+        &key(9),
+        &get_associated_token_address(&relayer().pubkey(), &key(9)),
+        &user,
+        &[],
+        amount,
+        6,
+    )
+    .unwrap()
+}
+
+/// A transaction paid for by `payer`, signed by everyone except the payer.
+fn user_signed(instructions: &[Instruction], payer: &Pubkey, signers: &[&Keypair]) -> String {
+    let message = Message::new_with_blockhash(instructions, Some(payer), &Hash::new_from_array([4; 32]));
+    let mut transaction = Transaction::new_unsigned(message);
+    transaction.partial_sign(signers, transaction.message.recent_blockhash);
+    general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap())
+}
+
+#[tokio::test]
+async fn info_describes_fee_payer_and_fee() {
+    let (app, _) = setup();
+    let (status, body) = get_json_from(app, "/relayer").await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(
+        body["data"],
+        json!({
+            "fee_payer": relayer().pubkey().to_string(),
+            "fee": {
+                "mint": pubkey(9),
+                "amount": "10000",
+                "account": get_associated_token_address(&relayer().pubkey(), &key(9)).to_string(),
+            },
+            "max_signatures": 2,
+            "allowed_programs": [],
+        })
+    );
+}
+
+#[tokio::test]
+async fn cosigns_and_submits_paid_transactions() {
+    let (app, mock) = setup();
+    let user = keypair(1);
+    let transaction = user_signed(&[fee_instruction(FEE), memo_instruction("gm")], &relayer().pubkey(), &[&user]);
+
+    let (status, body) = post_json_to(app.clone(), "/relayer/sign", json!({ "transaction": transaction })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let bytes = general_purpose::STANDARD.decode(body["data"]["transaction"].as_str().unwrap()).unwrap();
+    let signed: VersionedTransaction = bincode::deserialize(&bytes).unwrap();
+    assert!(signed.verify_with_results().iter().all(|valid| *valid));
+    assert_eq!(body["data"]["signature"], signed.signatures[0].to_string());
+    assert!(mock.sent_transactions().is_empty());
+
+    let (status, body) = post_json_to(app, "/relayer/submit", json!({ "transaction": transaction })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let sent = mock.sent_transactions();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(body["data"]["signature"], sent[0].signatures[0].to_string());
+    assert_eq!(sent[0], signed);
+}
+
+#[tokio::test]
+async fn rejects_transactions_outside_policy() {
+    let (app, mock) = setup();
+    let user = keypair(1);
+    let payer = relayer().pubkey();
+    let cases: [(&str, String); 5] = [
+        ("unpaid", user_signed(&[fee_instruction(FEE - 1)], &payer, &[&user])),
+        ("unsigned", user_signed(&[fee_instruction(FEE)], &payer, &[])),
+        ("other payer", user_signed(&[fee_instruction(FEE)], &user.pubkey(), &[&user])),
+        (
+            "drains relayer",
+            user_signed(&[fee_instruction(FEE), system_instruction::transfer(&payer, &key(2), 1)], &payer, &[&user]),
+        ),
+        (
+            "too many signers",
+            user_signed(
+                &[fee_instruction(FEE), memo_instruction("x")].map(|mut instruction| {
+                    instruction.accounts.push(solana_sdk::instruction::AccountMeta::new_readonly(keypair(2).pubkey(), true));
+                    instruction
+                }),
+                &payer,
+                &[&user, &keypair(2)],
+            ),
+        ),
+    ];
+
+    for (name, transaction) in cases {
+        let (status, body) = post_json_to(app.clone(), "/relayer/submit", json!({ "transaction": transaction })).await;
+        assert_eq!(body["code"], "POLICY_VIOLATION", "{name}: {body}");
+        assert_eq!(status, StatusCode::FORBIDDEN, "{name}");
+    }
+    assert!(mock.sent_transactions().is_empty());
+}
+
+#[tokio::test]
+async fn unavailable_without_configured_fee_payer() {
+    let app = mock_app(Arc::new(MockRpc::new()));
+    let (status, body): (StatusCode, Value) = get_json_from(app, "/relayer").await;
+    assert_error(status, &body, StatusCode::SERVICE_UNAVAILABLE, "RELAYER_UNAVAILABLE");
+}
+
+#[test]
+fn secrets_are_redacted_in_debug_output() {
+    let secret = bs58::encode(relayer().to_bytes()).into_string();
+    let config = RelayerConfig { secret: Some(Redacted(secret.clone())), ..RelayerConfig::default() };
+    let debug = format!("{config:?}");
+    assert!(!debug.contains(&secret), "{debug}");
+    assert!(debug.contains("[REDACTED]"));
+
+    let parsed: SecretKeyStr = serde_json::from_value(json!(secret)).unwrap();
+    assert_eq!(format!("{parsed:?}"), "[REDACTED]");
+}
@@ -0,0 +1,28 @@
+use std::process::Command;
+use std::time::Duration;
+
+use solana_fellowship_server::config::{Config, Mode};
+use solana_fellowship_server::state::AppState;
+
+#[tokio::test]
+async fn sighup_reloads_the_config_and_swaps_the_rpc_endpoint() {
+    let path = std::env::temp_dir().join(format!("superdev-reload-{}.toml", uuid::Uuid::new_v4()));
+    // SAFETY: this is the only test in the binary.
+    unsafe { std::env::set_var("SUPERDEV_CONFIG", &path) };
+    let state = AppState::new(Config::default());
+    assert!(state.rpc().is_err());
+    state.reload_on_hangup().unwrap();
+
+    std::fs::write(&path, "mode = \"production\"\n\n[rpc]\nmock = true\n").unwrap();
+    let status = Command::new("kill").args(["-HUP", &std::process::id().to_string()]).status().unwrap();
+    assert!(status.success());
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    while state.config().mode != Mode::Production {
+        assert!(tokio::time::Instant::now() < deadline, "config was not reloaded");
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert!(state.config().rpc.mock);
+    assert!(state.rpc().is_ok());
+    std::fs::remove_file(path).unwrap();
+}
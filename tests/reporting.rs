@@ -0,0 +1,115 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    middleware,
+    routing::post,
+    Json, Router,
+};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+
+use solana_fellowship_server::config::Config;
+use solana_fellowship_server::correlation::CORRELATION_ID;
+use solana_fellowship_server::rpc::{MockRpc, RpcError};
+use solana_fellowship_server::state::AppState;
+use solana_fellowship_server::types::Redacted;
+use solana_fellowship_server::request_id::{self, REQUEST_ID};
+use solana_fellowship_server::{audit, recover, reporting};
+
+use common::{app_with, call, pubkey};
+
+/// Serves a webhook that forwards every report it receives.
+async fn receiver() -> (Config, mpsc::UnboundedReceiver<Value>) {
+    let (sender, reports) = mpsc::unbounded_channel();
+    let app = Router::new().route(
+        "/reports",
+        post(move |Json(report): Json<Value>| async move {
+            sender.send(report).unwrap();
+        }),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let mut config = Config::default();
+    config.error_reporting.webhook_url = Some(Redacted(format!("http://{address}/reports")));
+    config.error_reporting.environment = Some("staging".to_string());
+    (config, reports)
+}
+
+async fn next_report(reports: &mut mpsc::UnboundedReceiver<Value>) -> Value {
+    tokio::time::timeout(Duration::from_secs(5), reports.recv()).await.unwrap().unwrap()
+}
+
+fn post_json(path: &str, body: Value) -> Request<Body> {
+    Request::post(path).header(header::CONTENT_TYPE, "application/json").body(Body::from(body.to_string())).unwrap()
+}
+
+#[tokio::test]
+async fn reports_server_errors_with_their_request_id() {
+    let (config, mut reports) = receiver().await;
+    let mock = Arc::new(MockRpc::new());
+    mock.set_simulation(Err(RpcError("node unavailable".to_string())));
+    let app = app_with(config, mock);
+    let body = json!({ "from": pubkey(1), "to": pubkey(2), "lamports": 100_000 });
+
+    let (status, headers, _) = call(app.clone(), post_json("/send/sol?simulate=true", body.clone())).await;
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    let request_id = headers[REQUEST_ID].to_str().unwrap();
+
+    let report = next_report(&mut reports).await;
+    assert_eq!(report["request_id"], request_id);
+    assert_eq!(report["correlation_id"], headers[CORRELATION_ID].to_str().unwrap());
+    assert_eq!(report["environment"], "staging");
+    assert_eq!(report["method"], "POST");
+    assert_eq!(report["path"], "/send/sol");
+    assert_eq!(report["status"], 502);
+    assert_eq!(report["code"], "RPC_ERROR");
+    assert_eq!(report["tenant"], "default");
+    assert_eq!(report["request"], body);
+
+    // Client errors aren't reported, and a caller's own id is echoed back.
+    let mut request = post_json("/send/sol", json!({ "from": pubkey(1) }));
+    request.headers_mut().insert(REQUEST_ID, "client-42".parse().unwrap());
+    let (status, headers, _) = call(app, request).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(headers[REQUEST_ID], "client-42");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(reports.try_recv().is_err());
+}
+
+async fn boom() -> StatusCode {
+    panic!("ledger exploded")
+}
+
+#[tokio::test]
+async fn reports_panics_with_redacted_context() {
+    let (config, mut reports) = receiver().await;
+    let state = AppState::new(config);
+    let app = Router::new()
+        .route("/boom", post(boom))
+        .layer(middleware::from_fn_with_state(state.clone(), recover::catch_panic))
+        .layer(middleware::from_fn_with_state(state.clone(), reporting::report))
+        .layer(middleware::from_fn_with_state(state.clone(), audit::capture))
+        .layer(middleware::from_fn(request_id::assign))
+        .with_state(state);
+
+    let body = json!({ "secret": "hunter2", "lamports": 5 });
+    let (status, headers, bytes) = call(app, post_json("/boom", body)).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    let response: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(response["code"], "INTERNAL");
+
+    let report = next_report(&mut reports).await;
+    assert_eq!(report["request_id"], headers[REQUEST_ID].to_str().unwrap());
+    assert_eq!(report["status"], 500);
+    assert_eq!(report["code"], "PANIC");
+    assert_eq!(report["message"], "ledger exploded");
+    assert_eq!(report["request"], json!({ "secret": "[REDACTED]", "lamports": 5 }));
+}
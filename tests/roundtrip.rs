@@ -0,0 +1,122 @@
+mod common;
+
+use axum::http::StatusCode;
+use base64::{engine::general_purpose, Engine as _};
+use proptest::prelude::*;
+use serde_json::{json, Value};
+use solana_sdk::signer::{keypair::Keypair, Signer};
+use solana_system_interface::instruction::SystemInstruction;
+use spl_token::instruction::TokenInstruction;
+
+use solana_fellowship_server::utils::{parse_secret_key, parse_signature};
+use common::{post_json, pubkey};
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+fn instruction_data(body: &Value) -> Vec<u8> {
+    general_purpose::STANDARD
+        .decode(body["data"]["instruction_data"].as_str().unwrap())
+        .unwrap()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn secret_key_base58_round_trips(seed in any::<[u8; 32]>()) {
+        let keypair = Keypair::new_from_array(seed);
+        let parsed = parse_secret_key(&keypair.to_base58_string()).unwrap();
+        prop_assert_eq!(parsed.pubkey(), keypair.pubkey());
+        prop_assert_eq!(parsed.to_bytes(), keypair.to_bytes());
+    }
+
+    #[test]
+    fn signature_base64_round_trips(seed in any::<[u8; 32]>(), message in any::<Vec<u8>>()) {
+        let signature = Keypair::new_from_array(seed).sign_message(&message);
+        let encoded = general_purpose::STANDARD.encode(signature.as_ref());
+        prop_assert_eq!(parse_signature(&encoded).unwrap(), signature);
+    }
+
+    #[test]
+    fn sign_then_verify_round_trips(seed in any::<[u8; 32]>(), message in "\\PC{0,256}") {
+        let keypair = Keypair::new_from_array(seed);
+
+        let (status, signed) = block_on(post_json(
+            "/message/sign",
+            json!({ "message": message, "secret": keypair.to_base58_string() }),
+        ));
+        prop_assert_eq!(status, StatusCode::OK);
+        prop_assert_eq!(&signed["data"]["public_key"], &json!(keypair.pubkey().to_string()));
+
+        let (status, verified) = block_on(post_json(
+            "/message/verify",
+            json!({
+                "message": message,
+                "signature": signed["data"]["signature"],
+                "pubkey": signed["data"]["public_key"],
+            }),
+        ));
+        prop_assert_eq!(status, StatusCode::OK);
+        prop_assert_eq!(&verified["data"]["valid"], &json!(true));
+    }
+
+    #[test]
+    fn send_sol_data_decodes_to_transfer(lamports in 1u64..) {
+        let (status, body) = block_on(post_json(
+            "/send/sol",
+            json!({ "from": pubkey(1), "to": pubkey(2), "lamports": lamports }),
+        ));
+        prop_assert_eq!(status, StatusCode::OK);
+
+        let decoded: SystemInstruction = bincode::deserialize(&instruction_data(&body)).unwrap();
+        prop_assert_eq!(decoded, SystemInstruction::Transfer { lamports });
+    }
+
+    #[test]
+    fn mint_token_data_decodes_to_mint_to(amount in 1u64..) {
+        let (status, body) = block_on(post_json(
+            "/token/mint",
+            json!({ "mint": pubkey(2), "destination": pubkey(3), "authority": pubkey(1), "amount": amount }),
+        ));
+        prop_assert_eq!(status, StatusCode::OK);
+
+        let data = instruction_data(&body);
+        prop_assert_eq!(TokenInstruction::unpack(&data).unwrap(), TokenInstruction::MintTo { amount });
+    }
+
+    #[test]
+    fn send_token_data_decodes_to_transfer(amount in 1u64..) {
+        let (status, body) = block_on(post_json(
+            "/send/token",
+            json!({ "destination": pubkey(3), "mint": pubkey(2), "owner": pubkey(1), "amount": amount }),
+        ));
+        prop_assert_eq!(status, StatusCode::OK);
+
+        let data = instruction_data(&body);
+        prop_assert_eq!(TokenInstruction::unpack(&data).unwrap(), TokenInstruction::Transfer { amount });
+    }
+
+    #[test]
+    fn create_token_data_decodes_to_initialize_mint(decimals in 0u8..=9) {
+        let (status, body) = block_on(post_json(
+            "/token/create",
+            json!({ "mintAuthority": pubkey(1), "mint": pubkey(2), "decimals": decimals }),
+        ));
+        prop_assert_eq!(status, StatusCode::OK);
+
+        let data = instruction_data(&body);
+        match TokenInstruction::unpack(&data).unwrap() {
+            TokenInstruction::InitializeMint { decimals: decoded, mint_authority, freeze_authority } => {
+                prop_assert_eq!(decoded, decimals);
+                prop_assert_eq!(mint_authority.to_string(), pubkey(1));
+                prop_assert!(Option::<solana_sdk::pubkey::Pubkey>::from(freeze_authority).is_none());
+            }
+            other => prop_assert!(false, "unexpected instruction {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use serde_json::{json, Value};
+
+use solana_fellowship_server::config::Config;
+use solana_fellowship_server::request_id::REQUEST_ID;
+use solana_fellowship_server::routes::{Availability, RouteGroup};
+use solana_fellowship_server::rpc::MockRpc;
+
+use common::{app_with, assert_error, call, get_json_from, post_json_to, pubkey, test_app};
+
+#[tokio::test]
+async fn disabled_route_groups_are_hidden_or_forbidden() {
+    let routes = HashMap::from([
+        (RouteGroup::Keypair, Availability::Hidden),
+        (RouteGroup::Message, Availability::Forbidden),
+        (RouteGroup::Send, Availability::Forbidden),
+    ]);
+    let app = app_with(Config { routes, ..Config::default() }, Arc::new(MockRpc::new()));
+
+    let request = Request::post("/keypair").body(Body::empty()).unwrap();
+    let (status, _, bytes) = call(app.clone(), request).await;
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+    assert_eq!(body["error"], "Route /keypair not found");
+
+    let (status, body) = post_json_to(app.clone(), "/message/sign", json!({ "message": "hi" })).await;
+    assert_error(status, &body, StatusCode::FORBIDDEN, "FEATURE_DISABLED");
+    assert_eq!(body["error"], "The message feature is disabled");
+
+    let (status, body) = post_json_to(app.clone(), "/v1/send/sol", json!({})).await;
+    assert_error(status, &body, StatusCode::FORBIDDEN, "FEATURE_DISABLED");
+
+    let (status, body) = get_json_from(app, &format!("/derive/ata?owner={}&mint={}", pubkey(1), pubkey(2))).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+}
+
+#[tokio::test]
+async fn route_groups_are_named_by_their_path_segment() {
+    let config: Config = toml::from_str("[routes]\nsolana-pay = \"hidden\"\ntoken = \"forbidden\"\n").unwrap();
+    assert_eq!(config.routes[&RouteGroup::SolanaPay], Availability::Hidden);
+    assert_eq!(config.routes[&RouteGroup::Token], Availability::Forbidden);
+    assert!(toml::from_str::<Config>("[routes]\nsolana_pay = \"hidden\"\n").is_err());
+
+    assert_eq!(RouteGroup::of("/solana-pay/tx/shop"), Some(RouteGroup::SolanaPay));
+    assert_eq!(RouteGroup::of("/keypair/export-encrypted"), Some(RouteGroup::Keypair));
+    assert_eq!(RouteGroup::of("/keypairs"), None);
+    assert_eq!(RouteGroup::of("/"), None);
+}
+
+#[tokio::test]
+async fn unknown_paths_and_methods_get_the_error_envelope() {
+    let (status, body) = get_json_from(test_app(), "/no/such/route").await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+    assert_eq!(body["error"], "Route /no/such/route not found");
+
+    let request = Request::delete("/send/sol").body(Body::empty()).unwrap();
+    let (status, headers, bytes) = call(test_app(), request).await;
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_error(status, &body, StatusCode::METHOD_NOT_ALLOWED, "METHOD_NOT_ALLOWED");
+    assert_eq!(body["error"], "Method DELETE not allowed");
+    assert_eq!(body["allowed"], json!(["POST"]));
+    assert_eq!(headers[header::ALLOW], "POST");
+    assert!(headers.contains_key(REQUEST_ID));
+}
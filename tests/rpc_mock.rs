@@ -0,0 +1,40 @@
+use solana_sdk::{account::Account, hash::Hash, pubkey::Pubkey};
+
+use solana_fellowship_server::config::RpcConfig;
+use solana_fellowship_server::rpc::{self, LatestBlockhash, MockRpc, RpcError, Simulation, SolanaRpc};
+
+#[tokio::test]
+async fn mock_serves_programmed_accounts() {
+    let mock = MockRpc::new();
+    let pubkey = Pubkey::new_unique();
+    assert_eq!(mock.get_account(&pubkey).await.unwrap(), None);
+
+    let account = Account { lamports: 42, ..Account::default() };
+    mock.set_account(pubkey, account.clone());
+    assert_eq!(mock.get_account(&pubkey).await.unwrap(), Some(account));
+
+    mock.remove_account(&pubkey);
+    assert_eq!(mock.get_account(&pubkey).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn mock_serves_programmed_blockhash_and_simulation() {
+    let mock = MockRpc::new();
+    let blockhash = LatestBlockhash { blockhash: Hash::new_unique(), last_valid_block_height: 9 };
+    mock.set_blockhash(blockhash);
+    assert_eq!(mock.get_latest_blockhash().await.unwrap(), blockhash);
+
+    let simulation = Simulation { logs: vec!["Program log: hi".into()], ..Simulation::default() };
+    mock.set_simulation(Ok(simulation.clone()));
+    let transaction = Default::default();
+    assert_eq!(mock.simulate_transaction(&transaction).await.unwrap(), simulation);
+
+    mock.set_simulation(Err(RpcError("node is behind".into())));
+    assert!(mock.simulate_transaction(&transaction).await.is_err());
+}
+
+#[test]
+fn connect_honours_mock_flag() {
+    assert!(rpc::connect(&RpcConfig::default()).is_none());
+    assert!(rpc::connect(&RpcConfig { mock: true, ..RpcConfig::default() }).is_some());
+}
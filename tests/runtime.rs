@@ -0,0 +1,43 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use serde_json::json;
+
+use solana_fellowship_server::config::{Config, RuntimeConfig};
+use solana_fellowship_server::crypto::CryptoPool;
+use solana_fellowship_server::rpc::MockRpc;
+
+use common::{app_with, post_json_to};
+
+#[tokio::test]
+async fn dedicated_crypto_threads_run_the_work_and_survive_panics() {
+    let pool = CryptoPool::new(Some(2));
+    let name = pool.run(|| std::thread::current().name().map(str::to_string)).await.unwrap();
+    assert!(name.unwrap().starts_with("crypto-"));
+
+    let panicked = pool.run(|| -> u8 { panic!("bad key") }).await;
+    assert!(panicked.is_err());
+    for _ in 0..4 {
+        assert_eq!(pool.run(|| 7).await.unwrap(), 7);
+    }
+
+    let shared = CryptoPool::new(None);
+    let name = shared.run(|| std::thread::current().name().map(str::to_string)).await.unwrap();
+    assert!(!name.unwrap_or_default().starts_with("crypto-"));
+}
+
+#[tokio::test]
+async fn handlers_sign_on_the_dedicated_pool() {
+    let runtime = RuntimeConfig { crypto_threads: Some(1), ..RuntimeConfig::default() };
+    let config = Config { runtime, ..Config::default() };
+    let app = app_with(config, Arc::new(MockRpc::new()));
+
+    let (status, body) = post_json_to(app.clone(), "/keypair", json!({})).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let secret = body["data"]["secret"].clone();
+    let (status, body) = post_json_to(app, "/message/sign", json!({ "message": "hi", "secret": secret })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert!(body["data"]["signature"].is_string());
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use common::get_json;
+
+#[tokio::test]
+async fn schemas_describe_every_model() {
+    let (status, body) = get_json("/schemas").await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["$schema"], "https://json-schema.org/draft/2020-12/schema");
+
+    let defs = &body["$defs"];
+    for name in ["SendSolRequest", "SendTokenResponse", "ScheduleRequest", "Proposal", "InspectResponse", "Job"] {
+        assert!(defs[name].is_object(), "missing {name}");
+    }
+
+    let send_sol = &defs["SendSolRequest"];
+    assert_eq!(send_sol["properties"]["from"]["$ref"], "#/$defs/Pubkey");
+    assert_eq!(send_sol["required"], json!(["from", "to"]));
+    assert_eq!(defs["Pubkey"]["type"], "string");
+    assert_eq!(defs["JobStatus"]["enum"], json!(["scheduled", "running", "succeeded", "failed", "cancelled"]));
+    assert!(defs["SignerRef"]["oneOf"].is_array());
+    assert!(defs["Type"]["oneOf"].is_array());
+}
+
+#[tokio::test]
+async fn golden_responses_are_schema_examples() {
+    let (status, body) = get_json("/schemas").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let golden = |name: &str| {
+        let path = format!("{}/tests/golden/{name}.json", env!("CARGO_MANIFEST_DIR"));
+        serde_json::from_str::<Value>(&std::fs::read_to_string(path).unwrap()).unwrap()["data"].clone()
+    };
+    let defs = &body["$defs"];
+    assert_eq!(defs["SendTokenResponse"]["examples"], json!([golden("send_token")]));
+    assert_eq!(defs["SignMessageResponse"]["examples"], json!([golden("sign_message")]));
+    assert_eq!(defs["InstructionResponse"]["examples"].as_array().unwrap().len(), 3);
+    assert_eq!(defs["InstructionResponse"]["examples"][2], golden("send_sol"));
+}
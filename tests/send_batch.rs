@@ -0,0 +1,67 @@
+mod common;
+
+use axum::http::StatusCode;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::{hash::Hash, transaction::Transaction};
+
+use common::{assert_error, post_json, pubkey};
+
+fn transfers(count: u8) -> Vec<Value> {
+    (0..count).map(|i| json!({ "to": pubkey(10 + i), "lamports": 1_000 + u64::from(i) })).collect()
+}
+
+#[tokio::test]
+async fn sol_batch_returns_instructions_and_totals() {
+    let (status, body) = post_json("/send/sol-batch", json!({ "from": pubkey(1), "transfers": transfers(3) })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let data = &body["data"];
+    assert_eq!(data["transfer_count"], 3);
+    assert_eq!(data["total_lamports"], 3_003);
+    assert_eq!(data["total_sol"], "0.000003003");
+    assert_eq!(data["instructions"].as_array().unwrap().len(), 3);
+    assert_eq!(data["instructions"][2]["accounts"][1]["pubkey"], pubkey(12));
+    assert!(data.get("transactions").is_none());
+}
+
+#[tokio::test]
+async fn sol_batch_packs_unsigned_transactions_on_request() {
+    let blockhash = Hash::new_from_array([3; 32]).to_string();
+    let request = json!({ "from": pubkey(1), "transfers": transfers(60), "pack": true, "recent_blockhash": blockhash });
+    let (status, body) = post_json("/send/sol-batch", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let data = &body["data"];
+    assert_eq!(data["recent_blockhash"], blockhash);
+    assert!(data.get("instructions").is_none());
+    let transactions = data["transactions"].as_array().unwrap();
+    assert!(transactions.len() > 1);
+    assert_eq!(transactions.last().unwrap()["transfers"][1], 60);
+
+    let bytes = general_purpose::STANDARD.decode(transactions[0]["transaction"].as_str().unwrap()).unwrap();
+    let transaction: Transaction = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(transaction.message.account_keys[0].to_string(), pubkey(1));
+    assert_eq!(transaction.message.header.num_required_signatures, 1);
+}
+
+#[tokio::test]
+async fn sol_batch_reports_every_invalid_transfer() {
+    let request = json!({ "from": pubkey(1), "transfers": [
+        { "to": pubkey(2), "lamports": 0 },
+        { "to": pubkey(1), "lamports": 5 },
+        { "to": pubkey(3), "lamports": u64::MAX },
+    ]});
+    let (status, body) = post_json("/send/sol-batch", request).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    let details: Vec<(&str, &str)> = body["details"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|detail| (detail["field"].as_str().unwrap(), detail["code"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        details,
+        [("transfers[0].lamports", "AMOUNT_ZERO"), ("transfers[1].to", "SELF_TRANSFER"), ("transfers", "AMOUNT_OVERFLOW")]
+    );
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use serde_json::json;
+
+use solana_fellowship_server::rpc::{MockRpc, Simulation};
+
+use common::{assert_error, key, keypair, mock_app, post_json, post_json_to, pubkey};
+
+#[tokio::test]
+async fn simulates_built_instructions_on_request() {
+    let mock = Arc::new(MockRpc::new());
+    let logs = vec!["Program 11111111111111111111111111111111 success".to_string()];
+    mock.set_simulation(Ok(Simulation { logs: logs.clone(), units_consumed: Some(150), ..Simulation::default() }));
+    let app = mock_app(mock.clone());
+    let body = json!({ "from": pubkey(1), "to": pubkey(2), "lamports": 100_000 });
+
+    let (status, plain) = post_json_to(app.clone(), "/send/sol", body.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(plain.get("simulation").is_none());
+    assert!(mock.simulated_transactions().is_empty());
+
+    let (status, simulated) = post_json_to(app, "/send/sol?simulate=true", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {simulated}");
+    assert_eq!(simulated["data"], plain["data"]);
+    assert_eq!(simulated["simulation"]["fee_payer"], pubkey(1));
+    assert_eq!(simulated["simulation"]["error"], json!(null));
+    assert_eq!(simulated["simulation"]["logs"], json!(logs));
+    assert_eq!(simulated["simulation"]["units_consumed"], 150);
+    let transactions = mock.simulated_transactions();
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transactions[0].message.static_account_keys()[..2], [key(1), key(2)]);
+}
+
+#[tokio::test]
+async fn simulates_web3js_output_and_instruction_lists() {
+    let mock = Arc::new(MockRpc::new());
+    mock.set_simulation(Ok(Simulation { err: Some("InsufficientFundsForFee".to_string()), ..Simulation::default() }));
+    let app = mock_app(mock.clone());
+
+    let body = json!({ "from": pubkey(1), "to": pubkey(2), "lamports": 100_000, "output_format": "web3js" });
+    let (status, body) = post_json_to(app.clone(), "/send/sol?simulate=true", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["simulation"]["error"], "InsufficientFundsForFee");
+
+    let body = json!({ "payer": pubkey(1), "tree": pubkey(2), "max_depth": 3, "max_buffer_size": 8 });
+    let (status, body) = post_json_to(app, "/merkle-tree/create?simulate=true", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["simulation"]["fee_payer"], pubkey(1));
+    assert_eq!(mock.simulated_transactions()[1].message.instructions().len(), 2);
+}
+
+#[tokio::test]
+async fn simulation_needs_rpc_and_instructions() {
+    let body = json!({ "from": pubkey(1), "to": pubkey(2), "lamports": 100_000 });
+    let (status, body) = post_json("/send/sol?simulate=true", body).await;
+    assert_error(status, &body, StatusCode::SERVICE_UNAVAILABLE, "RPC_UNAVAILABLE");
+
+    let body = json!({ "message": "hello", "secret": keypair(7).to_base58_string() });
+    let (status, body) = post_json_to(mock_app(Arc::new(MockRpc::new())), "/message/sign?simulate=true", body).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "simulate");
+}
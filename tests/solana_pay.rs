@@ -0,0 +1,198 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::{pubkey::Pubkey, transaction::Transaction};
+
+use solana_fellowship_server::{config::Config, rpc::MockRpc, solana_pay::MEMO_PROGRAM_ID};
+
+use common::{app_with, assert_error, call, get_json, mint_account, post_json, post_json_to, pubkey};
+
+async fn decode(url: &str) -> (StatusCode, Value) {
+    let query: String = form_urlencoded::byte_serialize(url.as_bytes()).collect();
+    get_json(&format!("/solana-pay/decode?url={query}")).await
+}
+
+#[tokio::test]
+async fn encode_transfer_request_with_all_fields() {
+    let request = json!({
+        "recipient": pubkey(1),
+        "amount": "1.5",
+        "spl_token": pubkey(2),
+        "reference": [pubkey(3), pubkey(4)],
+        "label": "Coffee Shop",
+        "message": "Thanks for your order!",
+        "memo": "order#42",
+    });
+
+    let (status, body) = post_json("/solana-pay/encode", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(
+        body["data"]["url"],
+        format!(
+            "solana:{}?amount=1.5&spl-token={}&reference={}&reference={}&label=Coffee%20Shop\
+             &message=Thanks%20for%20your%20order%21&memo=order%2342",
+            pubkey(1),
+            pubkey(2),
+            pubkey(3),
+            pubkey(4)
+        )
+    );
+}
+
+#[tokio::test]
+async fn decode_round_trips_encoded_transfer() {
+    let request = json!({ "recipient": pubkey(1), "amount": "0.25", "label": "A & B", "reference": [pubkey(5)] });
+    let (_, encoded) = post_json("/solana-pay/encode", request).await;
+
+    let (status, body) = decode(encoded["data"]["url"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(
+        body["data"],
+        json!({
+            "kind": "transfer",
+            "recipient": pubkey(1),
+            "amount": "0.25",
+            "reference": [pubkey(5)],
+            "label": "A & B",
+        })
+    );
+}
+
+#[tokio::test]
+async fn transaction_request_links_with_query_are_escaped() {
+    let link = "https://pay.example.com/tx?order=42&tip=1";
+    let (status, body) = post_json("/solana-pay/encode", json!({ "link": link })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let url = body["data"]["url"].as_str().unwrap();
+    assert_eq!(url, "solana:https%3A%2F%2Fpay.example.com%2Ftx%3Forder%3D42%26tip%3D1");
+
+    let (_, body) = decode(url).await;
+    assert_eq!(body["data"], json!({ "kind": "transaction", "link": link }));
+}
+
+#[tokio::test]
+async fn encode_requires_exactly_one_target() {
+    let (status, body) = post_json("/solana-pay/encode", json!({ "amount": "1" })).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "RECIPIENT_OR_LINK");
+
+    let request = json!({ "link": "http://insecure.example.com", "amount": "1" });
+    let (status, body) = post_json("/solana-pay/encode", request).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    let codes: Vec<&Value> = body["details"].as_array().unwrap().iter().map(|d| &d["code"]).collect();
+    assert_eq!(codes, [&json!("INVALID_LINK"), &json!("TRANSFER_ONLY")]);
+}
+
+#[tokio::test]
+async fn amounts_are_plain_decimals_with_sol_precision() {
+    for amount in ["-1", "1e3", "1.", ".5", "1,000"] {
+        let (status, body) = post_json("/solana-pay/encode", json!({ "recipient": pubkey(1), "amount": amount })).await;
+        assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+        assert_eq!(body["details"][0]["code"], "INVALID_DECIMAL_AMOUNT", "amount {amount}");
+    }
+
+    let (status, body) = decode(&format!("solana:{}?amount=0.0000000001", pubkey(1))).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "AMOUNT_PRECISION");
+
+    // Token amounts aren't bound by lamport precision.
+    let (status, _) = decode(&format!("solana:{}?amount=0.0000000001&spl-token={}", pubkey(1), pubkey(2))).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn decode_rejects_foreign_urls() {
+    let (status, body) = decode("bitcoin:abc").await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_PAYMENT_URL");
+    assert_eq!(body["field"], "url");
+
+    let (status, body) = decode("solana:not-a-key?amount=1").await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_PAYMENT_URL");
+}
+
+fn pay_app(mint: Option<Pubkey>) -> Router {
+    let mock = Arc::new(MockRpc::new());
+    let spl_token = mint.map_or(String::new(), |mint| {
+        mock.set_account(mint, mint_account(spl_token::id(), 6));
+        format!("spl_token = \"{mint}\"")
+    });
+
+    let config: Config = toml::from_str(&format!(
+        r#"
+        [solana_pay.requests.coffee]
+        label = "Coffee Shop"
+        icon = "https://example.com/icon.svg"
+        recipient = "{recipient}"
+        amount = 2500
+        reference = ["{reference}"]
+        memo = "order-7"
+        message = "Thanks!"
+        {spl_token}
+        "#,
+        recipient = pubkey(1),
+        reference = pubkey(3),
+    ))
+    .unwrap();
+    app_with(config, mock)
+}
+
+fn decode_transaction(body: &Value) -> Transaction {
+    let bytes = general_purpose::STANDARD.decode(body["transaction"].as_str().unwrap()).unwrap();
+    bincode::deserialize(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn transaction_request_get_returns_label_and_icon() {
+    let request = Request::get("/solana-pay/tx/coffee").body(Body::empty()).unwrap();
+    let (status, _, bytes) = call(pay_app(None), request).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body, json!({ "label": "Coffee Shop", "icon": "https://example.com/icon.svg" }));
+}
+
+#[tokio::test]
+async fn transaction_request_post_builds_sol_payment_for_wallet() {
+    let wallet = pubkey(2);
+    let (status, body) = post_json_to(pay_app(None), "/solana-pay/tx/coffee", json!({ "account": wallet })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["message"], "Thanks!");
+
+    let transaction = decode_transaction(&body);
+    let message = &transaction.message;
+    assert_eq!(message.account_keys[0].to_string(), wallet);
+    assert_eq!(message.instructions.len(), 2);
+    assert_eq!(*message.instructions[0].program_id(&message.account_keys), MEMO_PROGRAM_ID);
+    assert_eq!(message.instructions[0].data, b"order-7");
+
+    let transfer = &message.instructions[1];
+    let accounts: Vec<String> = transfer.accounts.iter().map(|&i| message.account_keys[i as usize].to_string()).collect();
+    assert_eq!(accounts, [wallet, pubkey(1), pubkey(3)]);
+}
+
+#[tokio::test]
+async fn transaction_request_post_uses_mint_program_for_tokens() {
+    let mint = Pubkey::new_unique();
+    let (status, body) = post_json_to(pay_app(Some(mint)), "/solana-pay/tx/coffee", json!({ "account": pubkey(2) })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let transaction = decode_transaction(&body);
+    let transfer = &transaction.message.instructions[1];
+    assert_eq!(*transfer.program_id(&transaction.message.account_keys), spl_token::id());
+    // transfer_checked: source, mint, destination, owner, then the reference.
+    assert_eq!(transfer.accounts.len(), 5);
+}
+
+#[tokio::test]
+async fn unknown_transaction_request_is_not_found() {
+    let (status, body) = post_json_to(pay_app(None), "/solana-pay/tx/tea", json!({ "account": pubkey(2) })).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+}
@@ -0,0 +1,241 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use solana_system_interface::program as system_program;
+
+use solana_fellowship_server::anchor::sighash;
+use solana_fellowship_server::rpc::MockRpc;
+use solana_fellowship_server::squads::{self, PROGRAM_ID};
+
+use common::{assert_error, key, mock_app, post_json_to, pubkey};
+
+fn squads_account(name: &str, fields: &[u8]) -> Account {
+    let mut data = sighash("account", name).to_vec();
+    data.extend_from_slice(fields);
+    Account { lamports: 1_000_000, data, owner: PROGRAM_ID, executable: false, rent_epoch: 0 }
+}
+
+fn program_config(treasury: Pubkey) -> Account {
+    let mut fields = key(90).to_bytes().to_vec();
+    fields.extend_from_slice(&0u64.to_le_bytes());
+    fields.extend_from_slice(treasury.as_ref());
+    squads_account("ProgramConfig", &fields)
+}
+
+/// A multisig whose latest transaction is `transaction_index`.
+fn multisig_account(transaction_index: u64) -> Account {
+    let mut fields = key(91).to_bytes().to_vec();
+    fields.extend_from_slice(&[0; 32]);
+    fields.extend_from_slice(&1u16.to_le_bytes());
+    fields.extend_from_slice(&0u32.to_le_bytes());
+    fields.extend_from_slice(&transaction_index.to_le_bytes());
+    fields.extend_from_slice(&0u64.to_le_bytes());
+    squads_account("Multisig", &fields)
+}
+
+/// Re-encodes the compact message sent to `vault_transaction_create` the
+/// way the program stores it, with four-byte lengths.
+fn vault_transaction_account(multisig: Pubkey, index: u64, compact: &[u8]) -> Account {
+    let mut fields = multisig.to_bytes().to_vec();
+    fields.extend_from_slice(&[3; 32]);
+    fields.extend_from_slice(&index.to_le_bytes());
+    fields.extend_from_slice(&[255, 0, 254]);
+    fields.extend_from_slice(&0u32.to_le_bytes());
+
+    let mut input = compact;
+    let mut take = |count: usize| {
+        let (head, rest) = input.split_at(count);
+        input = rest;
+        head.to_vec()
+    };
+    fields.extend(take(3));
+    let keys = take(1)[0];
+    fields.extend_from_slice(&u32::from(keys).to_le_bytes());
+    fields.extend(take(32 * usize::from(keys)));
+    let instructions = take(1)[0];
+    fields.extend_from_slice(&u32::from(instructions).to_le_bytes());
+    for _ in 0..instructions {
+        fields.extend(take(1));
+        let accounts = take(1)[0];
+        fields.extend_from_slice(&u32::from(accounts).to_le_bytes());
+        fields.extend(take(usize::from(accounts)));
+        let len = u16::from_le_bytes(take(2).try_into().unwrap());
+        fields.extend_from_slice(&u32::from(len).to_le_bytes());
+        fields.extend(take(usize::from(len)));
+    }
+    fields.extend_from_slice(&0u32.to_le_bytes());
+    squads_account("VaultTransaction", &fields)
+}
+
+fn data(instruction: &Value) -> Vec<u8> {
+    general_purpose::STANDARD.decode(instruction["instruction_data"].as_str().unwrap()).unwrap()
+}
+
+fn meta(pubkey: Pubkey, is_signer: bool, is_writable: bool) -> Value {
+    json!({ "pubkey": pubkey.to_string(), "is_signer": is_signer, "is_writable": is_writable })
+}
+
+fn member(seed: u8, permissions: &[&str]) -> Value {
+    json!({ "key": pubkey(seed), "permissions": permissions })
+}
+
+#[tokio::test]
+async fn creates_a_multisig_paying_the_configured_treasury() {
+    let mock = Arc::new(MockRpc::new());
+    let treasury = key(80);
+    mock.set_account(squads::program_config_address(), program_config(treasury));
+    let body = json!({
+        "creator": pubkey(1),
+        "create_key": pubkey(2),
+        "members": [member(1, &["initiate", "vote", "execute"]), member(4, &["vote"])],
+        "threshold": 2,
+    });
+
+    let (status, body) = post_json_to(mock_app(mock), "/squads/multisig", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let multisig = squads::multisig_address(&key(2));
+    assert_eq!(body["data"]["multisig"], multisig.to_string());
+    assert_eq!(body["data"]["vault"], squads::vault_address(&multisig, 0).to_string());
+
+    let instruction = &body["data"]["instructions"][0];
+    assert_eq!(instruction["program_id"], PROGRAM_ID.to_string());
+    assert_eq!(
+        instruction["accounts"],
+        json!([
+            meta(squads::program_config_address(), false, false),
+            meta(treasury, false, true),
+            meta(multisig, false, true),
+            meta(key(2), true, false),
+            meta(key(1), true, true),
+            meta(system_program::ID, false, false),
+        ])
+    );
+    let mut expected = sighash("global", "multisig_create_v2").to_vec();
+    expected.push(0);
+    expected.extend_from_slice(&2u16.to_le_bytes());
+    expected.extend_from_slice(&2u32.to_le_bytes());
+    expected.extend_from_slice(key(1).as_ref());
+    expected.push(7);
+    expected.extend_from_slice(key(4).as_ref());
+    expected.push(2);
+    expected.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+    assert_eq!(data(instruction), expected);
+}
+
+#[tokio::test]
+async fn multisig_threshold_must_be_reachable() {
+    let body = json!({
+        "creator": pubkey(1),
+        "create_key": pubkey(2),
+        "members": [member(1, &["initiate", "vote"]), member(4, &["vote"])],
+        "threshold": 3,
+    });
+
+    let (status, body) = post_json_to(mock_app(Arc::new(MockRpc::new())), "/squads/multisig", body).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "MISSING_PERMISSION");
+    assert_eq!(body["details"][1]["field"], "threshold");
+    assert_eq!(body["details"][1]["code"], "THRESHOLD_RANGE");
+}
+
+#[tokio::test]
+async fn proposes_approves_and_executes_a_vault_transfer() {
+    let mock = Arc::new(MockRpc::new());
+    let multisig = key(10);
+    mock.set_account(multisig, multisig_account(4));
+    let app = mock_app(mock.clone());
+    let vault = squads::vault_address(&multisig, 0);
+    let recipient = key(20);
+    let mut transfer = vec![2, 0, 0, 0];
+    transfer.extend_from_slice(&5_000u64.to_le_bytes());
+    let body = json!({
+        "multisig": multisig.to_string(),
+        "creator": pubkey(1),
+        "instructions": [{
+            "program_id": system_program::ID.to_string(),
+            "accounts": [
+                { "pubkey": vault.to_string(), "is_signer": true, "is_writable": true },
+                { "pubkey": recipient.to_string(), "is_writable": true },
+            ],
+            "data": general_purpose::STANDARD.encode(&transfer),
+        }],
+    });
+
+    let (status, body) = post_json_to(app.clone(), "/squads/vault-transaction", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data_out = &body["data"];
+    assert_eq!(data_out["transaction_index"], 5);
+    assert_eq!(data_out["vault"], vault.to_string());
+    let transaction = squads::transaction_address(&multisig, 5);
+    let proposal = squads::proposal_address(&multisig, 5);
+    assert_eq!(data_out["transaction"], transaction.to_string());
+    assert_eq!(data_out["proposal"], proposal.to_string());
+
+    let [create, propose] = [&data_out["instructions"][0], &data_out["instructions"][1]];
+    assert_eq!(create["accounts"][1], meta(transaction, false, true));
+    let create_data = data(create);
+    assert_eq!(create_data[..8], sighash("global", "vault_transaction_create"));
+    assert_eq!(create_data[8..10], [0, 0]);
+    let len = u32::from_le_bytes(create_data[10..14].try_into().unwrap()) as usize;
+    let message = &create_data[14..14 + len];
+    assert_eq!(message[..4], [1, 1, 1, 3]);
+    assert_eq!(message[4..36], *vault.as_ref());
+    assert_eq!(create_data[14 + len..], [0]);
+    let mut expected = sighash("global", "proposal_create").to_vec();
+    expected.extend_from_slice(&5u64.to_le_bytes());
+    expected.push(0);
+    assert_eq!(data(propose), expected);
+    assert_eq!(propose["accounts"][1], meta(proposal, false, true));
+
+    let approve = json!({ "multisig": multisig.to_string(), "transaction_index": 5, "member": pubkey(1) });
+    let (status, body) = post_json_to(app.clone(), "/squads/approve", approve).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let instruction = &body["data"]["instructions"][0];
+    assert_eq!(
+        instruction["accounts"],
+        json!([meta(multisig, false, false), meta(key(1), true, true), meta(proposal, false, true)])
+    );
+    assert_eq!(data(instruction), [sighash("global", "proposal_approve").as_slice(), &[0]].concat());
+
+    mock.set_account(transaction, vault_transaction_account(multisig, 5, message));
+    let execute = json!({ "multisig": multisig.to_string(), "transaction_index": 5, "member": pubkey(1) });
+    let (status, body) = post_json_to(app, "/squads/execute", execute).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let instruction = &body["data"]["instructions"][0];
+    assert_eq!(
+        instruction["accounts"],
+        json!([
+            meta(multisig, false, false),
+            meta(proposal, false, true),
+            meta(transaction, false, false),
+            meta(key(1), true, false),
+            meta(vault, false, true),
+            meta(recipient, false, true),
+            meta(system_program::ID, false, false),
+        ])
+    );
+    assert_eq!(data(instruction), sighash("global", "vault_transaction_execute"));
+}
+
+#[tokio::test]
+async fn vault_transactions_cannot_need_other_signers() {
+    let multisig = key(10);
+    let body = json!({
+        "multisig": multisig.to_string(),
+        "creator": pubkey(1),
+        "transaction_index": 1,
+        "instructions": [{
+            "program_id": system_program::ID.to_string(),
+            "accounts": [{ "pubkey": pubkey(1), "is_signer": true, "is_writable": true }],
+            "data": "",
+        }],
+    });
+
+    let (status, body) = post_json_to(mock_app(Arc::new(MockRpc::new())), "/squads/vault-transaction", body).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+}
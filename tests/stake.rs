@@ -0,0 +1,70 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use solana_sdk::{epoch_info::EpochInfo, pubkey::Pubkey};
+
+use solana_fellowship_server::rpc::{InflationReward, MockRpc};
+
+use common::{assert_error, get_json_from, mock_app, pubkey};
+
+fn rewards_mock() -> Arc<MockRpc> {
+    let mock = Arc::new(MockRpc::new());
+    mock.set_epoch_info(EpochInfo {
+        epoch: 20,
+        slot_index: 0,
+        slots_in_epoch: 432_000,
+        absolute_slot: 8_640_000,
+        block_height: 8_000_000,
+        transaction_count: None,
+    });
+    let account = Pubkey::new_from_array([5; 32]);
+    for (epoch, amount, post_balance) in [(17, 1_000_000, 10_001_000_000), (19, 2_000_000, 10_003_000_000)] {
+        let effective_slot = (epoch + 1) * 432_000;
+        let reward = InflationReward { epoch, effective_slot, amount, post_balance, commission: Some(7) };
+        mock.set_inflation_reward(account, reward);
+    }
+    mock
+}
+
+#[tokio::test]
+async fn rewards_cover_the_last_completed_epochs() {
+    let (status, body) = get_json_from(mock_app(rewards_mock()), &format!("/stake/{}/rewards", pubkey(5))).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data = &body["data"];
+    assert_eq!((data["start_epoch"].clone(), data["end_epoch"].clone()), (json!(10), json!(19)));
+    assert_eq!(data["total_rewards"], 3_000_000);
+    assert_eq!(data["total_rewards_sol"], "0.003");
+    assert_eq!(
+        data["rewards"][0],
+        json!({
+            "epoch": 17,
+            "effective_slot": 7_776_000,
+            "amount": 1_000_000,
+            "amount_sol": "0.001",
+            "post_balance": 10_001_000_000u64,
+            "rate": "0.0001",
+            "commission": 7,
+        })
+    );
+    assert_eq!(data["rewards"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn rewards_range_is_checked() {
+    let app = mock_app(rewards_mock());
+
+    let (status, body) = get_json_from(app.clone(), &format!("/stake/{}/rewards?start_epoch=18", pubkey(5))).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["rewards"].as_array().unwrap().len(), 1);
+
+    let (status, body) = get_json_from(app.clone(), &format!("/stake/{}/rewards?end_epoch=20", pubkey(5))).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "EPOCH_RANGE");
+    assert_eq!(body["field"], "end_epoch");
+
+    let (status, body) = get_json_from(app, &format!("/stake/{}/rewards?start_epoch=19&end_epoch=18", pubkey(5))).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "EPOCH_RANGE");
+    assert_eq!(body["field"], "start_epoch");
+}
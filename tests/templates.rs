@@ -0,0 +1,131 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_system_interface::{instruction as system_instruction, program as system_program};
+
+use common::{assert_error, call, get_json_from, key, post_json_to, pubkey, test_app};
+
+/// A system transfer with the sender, recipient and amount left open.
+fn transfer_template() -> Value {
+    json!({
+        "name": "Pay vendor",
+        "parameters": [
+            { "name": "from", "type": "pubkey" },
+            { "name": "to", "type": "pubkey", "default": pubkey(2) },
+            { "name": "lamports", "type": "u64", "description": "Amount to send" },
+        ],
+        "instructions": [{
+            "program_id": system_program::ID.to_string(),
+            "accounts": [
+                { "pubkey": "{{from}}", "is_signer": true, "is_writable": true },
+                { "pubkey": "{{ to }}", "is_writable": true },
+            ],
+            "data": general_purpose::STANDARD.encode(2u32.to_le_bytes()),
+            "args": { "schema": { "type": "u64" }, "value": "{{lamports}}" },
+        }],
+    })
+}
+
+async fn put(app: Router, path: &str, body: Value) -> (StatusCode, Value) {
+    let request = Request::put(path).header("content-type", "application/json").body(Body::from(body.to_string()));
+    let (status, _, bytes) = call(app, request.unwrap()).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+async fn delete(app: Router, path: &str) -> (StatusCode, Value) {
+    let (status, _, bytes) = call(app, Request::delete(path).body(Body::empty()).unwrap()).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn renders_saved_template_into_instructions() {
+    let app = test_app();
+    let (status, body) = post_json_to(app.clone(), "/templates", transfer_template()).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let id = body["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(body["data"]["name"], "Pay vendor");
+
+    let request = json!({ "parameters": { "from": pubkey(1), "lamports": 5_000 } });
+    let (status, body) = post_json_to(app.clone(), &format!("/templates/{id}/render"), request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let expected = system_instruction::transfer(&key(1), &key(2), 5_000);
+    let instruction = &body["data"]["instructions"][0];
+    assert_eq!(body["data"]["template_id"], id);
+    assert_eq!(instruction["program_id"], expected.program_id.to_string());
+    assert_eq!(instruction["accounts"][0]["pubkey"], pubkey(1));
+    assert_eq!(instruction["accounts"][0]["is_signer"], true);
+    assert_eq!(instruction["accounts"][1]["pubkey"], pubkey(2));
+    assert_eq!(instruction["instruction_data"], general_purpose::STANDARD.encode(&expected.data));
+
+    // Values are checked against the declared types.
+    let request = json!({ "parameters": { "from": pubkey(1), "lamports": -1 } });
+    let (status, body) = post_json_to(app.clone(), &format!("/templates/{id}/render"), request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "parameters.lamports");
+
+    let request = json!({ "parameters": { "lamports": 1 } });
+    let (status, body) = post_json_to(app.clone(), &format!("/templates/{id}/render"), request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "parameters.from");
+
+    let request = json!({ "parameters": { "from": pubkey(1), "lamports": 1, "memo": "hi" } });
+    let (status, body) = post_json_to(app, &format!("/templates/{id}/render"), request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "parameters.memo");
+}
+
+#[tokio::test]
+async fn updates_lists_and_deletes_templates() {
+    let app = test_app();
+    let (_, body) = post_json_to(app.clone(), "/templates", transfer_template()).await;
+    let id = body["data"]["id"].as_str().unwrap().to_string();
+    let path = format!("/templates/{id}");
+
+    let mut template = transfer_template();
+    template["name"] = json!("Pay supplier");
+    let (status, body) = put(app.clone(), &path, template).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["id"], id);
+    assert_eq!(body["data"]["name"], "Pay supplier");
+
+    let (_, body) = get_json_from(app.clone(), "/templates").await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+    assert_eq!(body["data"][0]["name"], "Pay supplier");
+
+    let (status, _) = delete(app.clone(), &path).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = get_json_from(app, &path).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+}
+
+#[tokio::test]
+async fn rejects_templates_that_cannot_render() {
+    let app = test_app();
+
+    let mut template = transfer_template();
+    template["instructions"][0]["accounts"][1]["pubkey"] = json!("{{recipient}}");
+    let (status, body) = post_json_to(app.clone(), "/templates", template).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "instructions[0].accounts[1].pubkey");
+
+    // Account placeholders must be pubkey parameters.
+    let mut template = transfer_template();
+    template["instructions"][0]["accounts"][1]["pubkey"] = json!("{{lamports}}");
+    let (status, body) = post_json_to(app.clone(), "/templates", template).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+
+    let mut template = transfer_template();
+    template["parameters"][1]["default"] = json!("not a key");
+    let (status, body) = post_json_to(app.clone(), "/templates", template).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "parameters[1].default");
+
+    let mut template = transfer_template();
+    template["instructions"] = json!([]);
+    let (status, body) = post_json_to(app, "/templates", template).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+}
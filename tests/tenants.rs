@@ -0,0 +1,126 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use solana_system_interface::instruction as system_instruction;
+
+use solana_fellowship_server::config::{ApprovalsConfig, Config, Mode, PolicyConfig};
+use solana_fellowship_server::rpc::MockRpc;
+use solana_fellowship_server::types::Redacted;
+
+use common::{app_with, assert_error, call, pubkey};
+
+fn tenants_app(mock: Arc<MockRpc>) -> Router {
+    let identities: HashMap<_, _> = ["alice", "bob", "carol", "dave"]
+        .into_iter()
+        .map(|name| (name.to_string(), Redacted(format!("{name}-key"))))
+        .collect();
+    let tenants = HashMap::from([
+        ("treasury".to_string(), vec!["alice".to_string(), "bob".to_string()]),
+        ("payroll".to_string(), vec!["carol".to_string(), "dave".to_string()]),
+    ]);
+    let config = Config {
+        mode: Mode::Production,
+        policy: PolicyConfig { approval_threshold_lamports: Some(10_000), ..PolicyConfig::default() },
+        approvals: ApprovalsConfig { required: 1, identities, ..ApprovalsConfig::default() },
+        tenants,
+        ..Config::default()
+    };
+    app_with(config, mock)
+}
+
+async fn send(app: Router, key: Option<&str>, method: &str, path: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let mut request = Request::builder().method(method).uri(path).header(header::CONTENT_TYPE, "application/json");
+    if let Some(key) = key {
+        request = request.header("x-api-key", key);
+    }
+    let body = body.map_or(Body::empty(), |body| Body::from(body.to_string()));
+    let (status, _, bytes) = call(app, request.body(body).unwrap()).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+fn transfer(key_id: &Value, from: &str, lamports: u64) -> Value {
+    let from: Pubkey = from.parse().unwrap();
+    let destination: Pubkey = pubkey(2).parse().unwrap();
+    let instruction: Instruction = system_instruction::transfer(&from, &destination, lamports);
+    json!({
+        "signers": [{ "key_id": key_id }],
+        "instructions": [{
+            "program_id": instruction.program_id.to_string(),
+            "accounts": instruction.accounts.iter().map(|meta| json!({
+                "pubkey": meta.pubkey.to_string(),
+                "is_signer": meta.is_signer,
+                "is_writable": meta.is_writable,
+            })).collect::<Vec<_>>(),
+            "data": general_purpose::STANDARD.encode(&instruction.data),
+        }],
+    })
+}
+
+#[tokio::test]
+async fn keystore_entries_are_private_to_their_tenant() {
+    let app = tenants_app(Arc::new(MockRpc::new()));
+    let (status, body) = send(app.clone(), Some("alice-key"), "POST", "/keypair", Some(json!({}))).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let key_id = body["data"]["key_id"].clone();
+
+    let sign = json!({ "message": "hi", "key_id": key_id });
+    let (status, body) = send(app.clone(), Some("bob-key"), "POST", "/message/sign", Some(sign.clone())).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    for key in [Some("carol-key"), None] {
+        let (status, body) = send(app.clone(), key, "POST", "/message/sign", Some(sign.clone())).await;
+        assert_error(status, &body, StatusCode::BAD_REQUEST, "UNKNOWN_KEY_ID");
+    }
+
+    // The anonymous default tenant is a tenant like any other.
+    let (_, body) = send(app.clone(), None, "POST", "/keypair", Some(json!({}))).await;
+    let sign = json!({ "message": "hi", "key_id": body["data"]["key_id"] });
+    let (status, body) = send(app, Some("alice-key"), "POST", "/message/sign", Some(sign)).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "UNKNOWN_KEY_ID");
+}
+
+#[tokio::test]
+async fn proposals_and_audit_entries_stay_within_the_tenant() {
+    let mock = Arc::new(MockRpc::new());
+    let app = tenants_app(mock.clone());
+    let (_, body) = send(app.clone(), Some("alice-key"), "POST", "/keypair", Some(json!({}))).await;
+    let key_id = body["data"]["key_id"].clone();
+    let from = body["data"]["pubkey"].as_str().unwrap().to_string();
+
+    let request = transfer(&key_id, &from, 50_000);
+    let (status, body) = send(app.clone(), Some("alice-key"), "POST", "/transfers/propose", Some(request)).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["status"], "pending");
+    let id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let path = format!("/transfers/{id}");
+    let (status, body) = send(app.clone(), Some("carol-key"), "GET", &path, None).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+    let approve = json!({ "id": id });
+    let (status, body) =
+        send(app.clone(), Some("carol-key"), "POST", "/transfers/approve", Some(approve.clone())).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+    assert!(mock.sent_transactions().is_empty());
+
+    let (status, body) = send(app.clone(), Some("bob-key"), "POST", "/transfers/approve", Some(approve)).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["status"], "submitted");
+
+    let (_, body) = send(app.clone(), Some("bob-key"), "GET", "/audit", None).await;
+    let actions: Vec<_> = body["data"].as_array().unwrap().iter().map(|entry| entry["action"].clone()).collect();
+    assert_eq!(actions, [json!("transfers.propose"), json!("transfers.approve")]);
+    for key in [Some("dave-key"), None] {
+        let (_, body) = send(app.clone(), key, "GET", "/audit", None).await;
+        assert_eq!(body["data"], json!([]));
+    }
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64::{engine::general_purpose, Engine as _};
+use rcgen::{BasicConstraints, Certificate, CertificateParams, CertifiedIssuer, DnType, IsCa, KeyPair};
+use serde_json::{json, Value};
+use solana_sdk::signer::Signer;
+use solana_system_interface::instruction as system_instruction;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::{
+    rustls::{
+        pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer, ServerName},
+        ClientConfig, RootCertStore,
+    },
+    TlsConnector,
+};
+
+use solana_fellowship_server::config::{Config, TlsConfig};
+use solana_fellowship_server::rpc::MockRpc;
+use solana_fellowship_server::tls;
+
+use common::{app_with, keypair};
+
+struct Issued {
+    cert: Certificate,
+    key: KeyPair,
+}
+
+fn ca(name: &str) -> CertifiedIssuer<'static, KeyPair> {
+    let mut params = CertificateParams::new(Vec::new()).unwrap();
+    params.distinguished_name.push(DnType::CommonName, name);
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    CertifiedIssuer::self_signed(params, KeyPair::generate().unwrap()).unwrap()
+}
+
+fn issue(ca: &CertifiedIssuer<'static, KeyPair>, common_name: &str, names: &[&str]) -> Issued {
+    let mut params = CertificateParams::new(names.iter().map(ToString::to_string).collect::<Vec<_>>()).unwrap();
+    params.distinguished_name.push(DnType::CommonName, common_name);
+    let key = KeyPair::generate().unwrap();
+    let cert = params.signed_by(&key, ca).unwrap();
+    Issued { cert, key }
+}
+
+/// Writes the server certificate, its key and the client CA bundle to a
+/// fresh directory and returns the matching config.
+fn write_pems(server_ca: &CertifiedIssuer<'static, KeyPair>, client_ca: &CertifiedIssuer<'static, KeyPair>) -> TlsConfig {
+    let dir = std::env::temp_dir().join(format!("superdev-tls-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let server = issue(server_ca, "superdev", &["localhost"]);
+    let write = |name: &str, contents: String| -> String {
+        let path: PathBuf = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        path.display().to_string()
+    };
+    TlsConfig {
+        cert: Some(write("server.pem", server.cert.pem())),
+        key: Some(write("server.key", server.key.serialize_pem())),
+        client_ca: Some(write("clients.pem", client_ca.pem())),
+        subjects: HashMap::from([("treasury-ops".to_string(), "alice".to_string())]),
+    }
+}
+
+async fn start(tls: TlsConfig) -> (u16, Arc<MockRpc>) {
+    let server_config = tls::server_config(&tls).unwrap().unwrap();
+    let mock = Arc::new(MockRpc::new());
+    let app = app_with(Config { tls, ..Config::default() }, mock.clone());
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(tls::serve(listener, server_config, app, std::future::pending(), Duration::ZERO));
+    (port, mock)
+}
+
+/// Sends one HTTP/1.1 request over TLS, presenting `client` if given.
+/// Returns the response body, or `None` if the server dropped the connection.
+async fn request(
+    port: u16,
+    server_ca: &CertifiedIssuer<'static, KeyPair>,
+    client: Option<Issued>,
+    head: &str,
+    body: &str,
+) -> Option<(u16, Value)> {
+    let mut roots = RootCertStore::empty();
+    roots.add(server_ca.der().clone()).unwrap();
+    let builder = ClientConfig::builder().with_root_certificates(roots);
+    let config = match client {
+        Some(Issued { cert, key }) => {
+            let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key.serialize_der()));
+            builder.with_client_auth_cert(vec![cert.der().clone()], key).unwrap()
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    let name = ServerName::try_from("localhost").unwrap();
+    let mut stream = TlsConnector::from(Arc::new(config)).connect(name, stream).await.ok()?;
+    let message = format!(
+        "{head} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(message.as_bytes()).await.ok()?;
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).await.ok()?;
+
+    let response = String::from_utf8(response).ok()?;
+    let status = response.split(' ').nth(1)?.parse().ok()?;
+    let (_, body) = response.split_once("\r\n\r\n")?;
+    Some((status, serde_json::from_str(body).unwrap()))
+}
+
+fn transfer(lamports: u64) -> String {
+    let from = keypair(1);
+    let instruction = system_instruction::transfer(&from.pubkey(), &keypair(2).pubkey(), lamports);
+    json!({
+        "signers": [bs58::encode(from.to_bytes()).into_string()],
+        "instructions": [{
+            "program_id": instruction.program_id.to_string(),
+            "accounts": instruction.accounts.iter().map(|meta| json!({
+                "pubkey": meta.pubkey.to_string(),
+                "is_signer": meta.is_signer,
+                "is_writable": meta.is_writable,
+            })).collect::<Vec<_>>(),
+            "data": general_purpose::STANDARD.encode(&instruction.data),
+        }],
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn maps_client_certificate_subjects_to_identities() {
+    let (server_ca, client_ca) = (ca("server-ca"), ca("client-ca"));
+    let (port, mock) = start(write_pems(&server_ca, &client_ca)).await;
+
+    let ops = issue(&client_ca, "treasury-ops", &[]);
+    let (status, body) = request(port, &server_ca, Some(ops), "POST /transfers/propose", &transfer(5_000)).await.unwrap();
+    assert_eq!(status, 200, "body: {body}");
+    assert_eq!(body["data"]["proposer"], "alice");
+    assert_eq!(mock.sent_transactions().len(), 1);
+
+    let ops = issue(&client_ca, "treasury-ops", &[]);
+    let (status, body) = request(port, &server_ca, Some(ops), "GET /audit", "").await.unwrap();
+    assert_eq!(status, 200, "body: {body}");
+    assert_eq!(body["data"][0]["action"], "transfers.propose");
+    assert_eq!(body["data"][0]["identity"], "alice");
+
+    // A trusted certificate with no mapped identity authenticates nobody.
+    let stranger = issue(&client_ca, "stranger", &[]);
+    let (status, body) =
+        request(port, &server_ca, Some(stranger), "POST /transfers/propose", &transfer(5_000)).await.unwrap();
+    assert_eq!(status, 401, "body: {body}");
+    assert_eq!(body["code"], "UNAUTHORIZED");
+}
+
+#[tokio::test]
+async fn refuses_connections_without_a_trusted_client_certificate() {
+    let (server_ca, client_ca) = (ca("server-ca"), ca("client-ca"));
+    let (port, _) = start(write_pems(&server_ca, &client_ca)).await;
+
+    assert!(request(port, &server_ca, None, "GET /relayer", "").await.is_none());
+
+    let rogue_ca = ca("rogue-ca");
+    let forged = issue(&rogue_ca, "treasury-ops", &[]);
+    assert!(request(port, &server_ca, Some(forged), "GET /relayer", "").await.is_none());
+}
@@ -0,0 +1,214 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::{account::Account, program_option::COption, program_pack::Pack, pubkey::Pubkey};
+use spl_token::state::{Account as TokenAccount, AccountState};
+
+use solana_fellowship_server::metaplex::{metadata_address, METADATA_PROGRAM_ID};
+use solana_fellowship_server::rpc::MockRpc;
+
+use common::{assert_error, call, get_json_from, key, metadata_account, mint_account, mock_app, post_json, pubkey};
+
+fn holding(mint: Pubkey, owner: u8, amount: u64) -> Account {
+    let mut data = vec![0; TokenAccount::LEN];
+    TokenAccount {
+        mint,
+        owner: key(owner),
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    Account { lamports: 2_039_280, data, owner: spl_token::id(), executable: false, rent_epoch: 0 }
+}
+
+fn holders_mock() -> Arc<MockRpc> {
+    let mock = Arc::new(MockRpc::new());
+    let mint = key(9);
+    mock.set_account(mint, mint_account(spl_token::id(), 2));
+    mock.set_account(key(20), holding(mint, 1, 500));
+    mock.set_account(key(21), holding(mint, 1, 250));
+    mock.set_account(key(22), holding(mint, 2, 1_000));
+    mock.set_account(key(23), holding(mint, 3, 5));
+    mock.set_account(key(24), holding(key(8), 4, 9_999));
+    mock
+}
+
+#[tokio::test]
+async fn holders_are_summed_per_owner_and_paged() {
+    let app = mock_app(holders_mock());
+
+    let (status, body) = get_json_from(app.clone(), &format!("/token/{}/holders?min_balance=10&limit=1", pubkey(9))).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data = &body["data"];
+    assert_eq!(data["holder_count"], 2);
+    assert_eq!(data["total_balance"], 1_750);
+    assert_eq!(data["holders"], json!([{ "owner": pubkey(2), "balance": 1_000, "ui_balance": "10", "accounts": 1 }]));
+    assert_eq!(data["next_offset"], 1);
+
+    let (_, body) = get_json_from(app, &format!("/token/{}/holders?min_balance=10&limit=1&offset=1", pubkey(9))).await;
+    let data = &body["data"];
+    assert_eq!(data["holders"], json!([{ "owner": pubkey(1), "balance": 750, "ui_balance": "7.5", "accounts": 2 }]));
+    assert!(data.get("next_offset").is_none());
+}
+
+#[tokio::test]
+async fn holders_stream_as_ndjson_with_a_summary() {
+    let request = Request::get(format!("/token/{}/holders?limit=2", pubkey(9)))
+        .header(header::ACCEPT, "application/x-ndjson")
+        .body(Body::empty())
+        .unwrap();
+    let (status, _, bytes) = call(mock_app(holders_mock()), request).await;
+    assert_eq!(status, StatusCode::OK);
+    let lines: Vec<Value> = String::from_utf8(bytes.to_vec())
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            json!({ "owner": pubkey(2), "balance": 1_000, "ui_balance": "10", "accounts": 1 }),
+            json!({ "owner": pubkey(1), "balance": 750, "ui_balance": "7.5", "accounts": 2 }),
+            json!({
+                "mint": pubkey(9),
+                "token_program": spl_token::id().to_string(),
+                "decimals": 2,
+                "holder_count": 3,
+                "total_balance": 1_755,
+                "next_offset": 2,
+            }),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn holders_rejects_bad_page_size() {
+    let (status, body) = get_json_from(mock_app(holders_mock()), &format!("/token/{}/holders?limit=0", pubkey(9))).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "BATCH_SIZE");
+    assert_eq!(body["field"], "limit");
+}
+
+#[tokio::test]
+async fn info_combines_supply_authorities_and_metadata() {
+    let mock = Arc::new(MockRpc::new());
+    let mint = key(9);
+    mock.set_account(mint, mint_account(spl_token::id(), 2));
+    mock.set_account(metadata_address(&mint), metadata_account(mint, "Super Token", "SUP", "https://example.com/sup.json"));
+
+    let (status, body) = get_json_from(mock_app(mock), &format!("/token/{}/info", pubkey(9))).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(
+        body["data"],
+        json!({
+            "mint": pubkey(9),
+            "token_program": spl_token::id().to_string(),
+            "supply": 1_000,
+            "ui_supply": "10",
+            "decimals": 2,
+            "mint_authority": pubkey(9),
+            "freeze_authority": null,
+            "extensions": [],
+            "metadata": {
+                "source": "metaplex",
+                "address": metadata_address(&mint).to_string(),
+                "name": "Super Token",
+                "symbol": "SUP",
+                "uri": "https://example.com/sup.json",
+                "update_authority": pubkey(7),
+            },
+        })
+    );
+}
+
+#[tokio::test]
+async fn info_omits_missing_metadata_and_rejects_non_mints() {
+    let mock = Arc::new(MockRpc::new());
+    mock.set_account(key(9), mint_account(spl_token::id(), 0));
+    mock.set_account(key(20), holding(key(9), 1, 5));
+    let app = mock_app(mock);
+
+    let (status, body) = get_json_from(app.clone(), &format!("/token/{}/info", pubkey(9))).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert!(body["data"].get("metadata").is_none());
+
+    let (status, body) = get_json_from(app.clone(), &format!("/token/{}/info", pubkey(20))).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_ACCOUNT");
+
+    let (status, body) = get_json_from(app, &format!("/token/{}/info", pubkey(30))).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "ACCOUNT_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn create_metadata_builds_create_metadata_account_v3() {
+    let body = json!({
+        "mint": key(9).to_string(),
+        "mint_authority": pubkey(1),
+        "name": "Superdev",
+        "symbol": "SDV",
+        "uri": "https://example.com/sdv.json",
+    });
+
+    let (status, body) = post_json("/token/create-metadata", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let instruction = &body["data"];
+    assert_eq!(instruction["program_id"], METADATA_PROGRAM_ID.to_string());
+    let accounts: Vec<_> = instruction["accounts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|meta| (meta["pubkey"].as_str().unwrap(), meta["is_signer"] == true, meta["is_writable"] == true))
+        .collect();
+    let metadata = metadata_address(&key(9)).to_string();
+    let (mint, authority) = (key(9).to_string(), pubkey(1));
+    assert_eq!(
+        accounts,
+        [
+            (metadata.as_str(), false, true),
+            (mint.as_str(), false, false),
+            (authority.as_str(), true, false),
+            (authority.as_str(), true, true),
+            (authority.as_str(), true, false),
+            ("11111111111111111111111111111111", false, false),
+        ]
+    );
+
+    let mut expected = vec![33];
+    for text in ["Superdev", "SDV", "https://example.com/sdv.json"] {
+        expected.extend_from_slice(&(text.len() as u32).to_le_bytes());
+        expected.extend_from_slice(text.as_bytes());
+    }
+    // No seller fee, creators, collection or uses; mutable; no collection details.
+    expected.extend_from_slice(&[0, 0, 0, 0, 0, 1, 0]);
+    let data = general_purpose::STANDARD.decode(instruction["instruction_data"].as_str().unwrap()).unwrap();
+    assert_eq!(data, expected);
+}
+
+#[tokio::test]
+async fn create_metadata_rejects_oversized_fields() {
+    let body = json!({
+        "mint": key(9).to_string(),
+        "mint_authority": pubkey(1),
+        "update_authority": pubkey(2),
+        "name": "n".repeat(33),
+        "symbol": "TOOLONGSYMBOL",
+        "uri": "",
+    });
+
+    let (status, body) = post_json("/token/create-metadata", body).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["field"], "name");
+    assert_eq!(body["details"][0]["code"], "TOO_LONG");
+    assert_eq!(body["details"][1]["field"], "symbol");
+    assert_eq!(body["details"].as_array().unwrap().len(), 2);
+}
@@ -0,0 +1,475 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::{Message, VersionedMessage},
+    program_option::COption,
+    program_pack::Pack,
+    signature::Signature,
+    signer::Signer,
+    transaction::{Transaction, VersionedTransaction},
+};
+use solana_system_interface::instruction as system_instruction;
+use spl_token::{
+    instruction::{approve, set_authority, transfer_checked, AuthorityType},
+    state::{Account as TokenAccount, AccountState},
+};
+
+use solana_fellowship_server::rpc::{
+    ConfirmedTransaction, LatestBlockhash, MockRpc, Simulation, TokenBalance, TransactionMeta,
+};
+use solana_fellowship_server::solana_pay::memo_instruction;
+
+use common::{assert_error, get_json_from, key, keypair, mint_account, mock_app, post_json, post_json_to, pubkey};
+
+fn token_balance(account_index: usize, owner: u8, amount: u64) -> TokenBalance {
+    TokenBalance { account_index, mint: key(9), owner: Some(key(owner)), amount, decimals: 6 }
+}
+
+/// key(1) pays key(2) 1.5 SOL with a memo, while key(2) also receives
+/// 30 tokens of mint key(9) from key(1) into a freshly created account.
+fn deposit() -> ConfirmedTransaction {
+    let instructions = [
+        system_instruction::transfer(&key(1), &key(2), 1_500_000_000),
+        memo_instruction("invoice-42"),
+    ];
+    let message = Message::new_with_blockhash(&instructions, Some(&key(1)), &Hash::new_from_array([3; 32]));
+    let keys = message.account_keys.len();
+    let mut pre_balances = vec![1; keys];
+    let mut post_balances = pre_balances.clone();
+    pre_balances[0] = 5_000_000_000;
+    post_balances[0] = 5_000_000_000 - 1_500_000_000 - 5_000;
+    post_balances[1] += 1_500_000_000;
+
+    ConfirmedTransaction {
+        slot: 321,
+        block_time: Some(1_700_000_000),
+        transaction: VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::Legacy(message),
+        },
+        meta: TransactionMeta {
+            fee: 5_000,
+            pre_balances,
+            post_balances,
+            pre_token_balances: vec![token_balance(5, 1, 100_000_000)],
+            post_token_balances: vec![token_balance(5, 1, 70_000_000), token_balance(6, 2, 30_000_000)],
+            ..TransactionMeta::default()
+        },
+    }
+}
+
+#[tokio::test]
+async fn parses_confirmed_transaction_into_deltas() {
+    let signature = Signature::from([7; 64]);
+    let mock = Arc::new(MockRpc::new());
+    mock.set_transaction(signature, deposit());
+
+    let (status, body) = get_json_from(mock_app(mock), &format!("/transaction/parse/{signature}")).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let data = &body["data"];
+    assert_eq!(data["signature"], signature.to_string());
+    assert_eq!(data["slot"], 321);
+    assert_eq!(data["status"], "success");
+    assert_eq!(data["error"], json!(null));
+    assert_eq!(data["fee_payer"], pubkey(1));
+    assert_eq!(data["fee"], "5000");
+    assert_eq!(data["memos"], json!(["invoice-42"]));
+    assert_eq!(
+        data["sol_changes"],
+        json!([
+            { "account": pubkey(1), "pre": "5000000000", "post": "3499995000", "change": "-1500005000" },
+            { "account": pubkey(2), "pre": "1", "post": "1500000001", "change": "1500000000" },
+        ])
+    );
+
+    let changes = data["token_changes"].as_array().unwrap();
+    assert_eq!(changes.len(), 2);
+    let change_for = |owner: u8| changes.iter().find(|change| change["owner"] == pubkey(owner)).unwrap();
+    assert_eq!(change_for(1)["change"], "-30000000");
+    assert_eq!(change_for(1)["ui_change"], "-30");
+    assert_eq!(change_for(2)["mint"], pubkey(9));
+    assert_eq!(change_for(2)["change"], "30000000");
+    assert_eq!(change_for(2)["ui_change"], "30");
+}
+
+#[tokio::test]
+async fn failed_transaction_reports_error_and_fee_only() {
+    let signature = Signature::from([8; 64]);
+    let mut confirmed = deposit();
+    confirmed.meta.err = Some("{\"InstructionError\":[0,{\"Custom\":1}]}".to_string());
+    confirmed.meta.post_balances = confirmed.meta.pre_balances.clone();
+    confirmed.meta.post_balances[0] -= 5_000;
+    confirmed.meta.post_token_balances = confirmed.meta.pre_token_balances.clone();
+    let mock = Arc::new(MockRpc::new());
+    mock.set_transaction(signature, confirmed);
+
+    let (status, body) = get_json_from(mock_app(mock), &format!("/transaction/parse/{signature}")).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["status"], "failed");
+    assert!(body["data"]["error"].as_str().unwrap().contains("InstructionError"));
+    assert_eq!(body["data"]["sol_changes"].as_array().unwrap().len(), 1);
+    assert_eq!(body["data"]["sol_changes"][0]["change"], "-5000");
+    assert_eq!(body["data"]["token_changes"], json!([]));
+}
+
+#[tokio::test]
+async fn rejects_bad_and_unknown_signatures() {
+    let app = mock_app(Arc::new(MockRpc::new()));
+
+    let (status, body) = get_json_from(app.clone(), "/transaction/parse/not-a-signature").await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "signature");
+
+    let (status, body) = get_json_from(app, &format!("/transaction/parse/{}", Signature::from([9; 64]))).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+}
+
+fn encode(instructions: &[Instruction], blockhash: Hash) -> String {
+    let transaction = Transaction::new_unsigned(Message::new_with_blockhash(instructions, Some(&key(1)), &blockhash));
+    general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap())
+}
+
+fn codes(body: &Value) -> Vec<&str> {
+    body["data"]["findings"].as_array().unwrap().iter().map(|finding| finding["code"].as_str().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn inspect_flags_authority_delegate_and_unknown_program() {
+    let token_account = key(5);
+    let instructions = [
+        set_authority(&spl_token::ID, &token_account, Some(&key(6)), AuthorityType::AccountOwner, &key(1), &[]).unwrap(),
+        approve(&spl_token::ID, &token_account, &key(7), &key(1), &[], 10).unwrap(),
+        approve(&spl_token::ID, &token_account, &key(8), &key(1), &[], 10).unwrap(),
+        Instruction::new_with_bytes(key(30), &[1], vec![]),
+        Instruction::new_with_bytes(key(31), &[1], vec![]),
+    ];
+    let request = json!({
+        "transaction": encode(&instructions, Hash::default()),
+        "trusted_programs": [pubkey(31)],
+        "trusted_delegates": [pubkey(8)],
+    });
+
+    // Without RPC the structural checks still run; drains are skipped.
+    let (status, body) = post_json("/transaction/inspect", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["risk"], "high");
+    assert_eq!(body["data"]["fee_payer"], pubkey(1));
+    assert_eq!(body["data"]["balances_checked"], false);
+    assert_eq!(codes(&body), ["MISSING_BLOCKHASH", "OWNER_CHANGE", "TOKEN_DELEGATE", "UNKNOWN_PROGRAM"]);
+
+    let findings = &body["data"]["findings"];
+    assert_eq!(findings[0]["instruction_index"], Value::Null);
+    assert_eq!(findings[2]["instruction_index"], 1);
+    assert_eq!(findings[3]["severity"], "medium");
+    assert!(findings[3]["message"].as_str().unwrap().contains(&pubkey(30)));
+}
+
+/// Token account of mint key(9) holding `amount`.
+fn token_account(owner: u8, amount: u64) -> Account {
+    let mut data = vec![0; TokenAccount::LEN];
+    TokenAccount {
+        mint: key(9),
+        owner: key(owner),
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    Account { lamports: 2_039_280, data, owner: spl_token::ID, executable: false, rent_epoch: 0 }
+}
+
+#[tokio::test]
+async fn inspect_checks_transfers_against_balances() {
+    let mock = Arc::new(MockRpc::new());
+    mock.set_account(key(1), Account { lamports: 1_000_000_000, ..Account::default() });
+    mock.set_account(key(5), token_account(1, 1_000));
+    let app = mock_app(mock);
+
+    let instructions = [
+        system_instruction::transfer(&key(1), &key(2), 400_000_000),
+        system_instruction::transfer(&key(1), &key(3), 200_000_000),
+        transfer_checked(&spl_token::ID, &key(5), &key(9), &key(6), &key(1), &[], 100, 6).unwrap(),
+    ];
+    let request = json!({ "transaction": encode(&instructions, Hash::new_from_array([4; 32])) });
+    let (status, body) = post_json_to(app.clone(), "/transaction/inspect", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["balances_checked"], true);
+    assert_eq!(codes(&body), ["BALANCE_DRAIN"]);
+    assert_eq!(body["data"]["findings"][0]["instruction_index"], 0);
+    assert!(body["data"]["findings"][0]["message"].as_str().unwrap().contains("(60%)"));
+
+    // A lower threshold catches the 10% token transfer too.
+    let request = json!({ "transaction": encode(&instructions, Hash::new_from_array([4; 32])), "drain_threshold_percent": 10 });
+    let (_, body) = post_json_to(app.clone(), "/transaction/inspect", request).await;
+    assert_eq!(codes(&body), ["BALANCE_DRAIN", "BALANCE_DRAIN"]);
+
+    let (status, body) = post_json_to(app, "/transaction/inspect", json!({ "transaction": "AAAA" })).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "transaction");
+
+    let (status, body) = post_json("/transaction/inspect", json!({ "transaction": "", "drain_threshold_percent": 0 })).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+}
+
+#[tokio::test]
+async fn verify_signatures_reports_each_required_signer() {
+    let (payer, owner) = (keypair(1), keypair(2));
+    let instruction = system_instruction::transfer(&owner.pubkey(), &key(3), 1_000);
+    let message = Message::new_with_blockhash(&[instruction], Some(&payer.pubkey()), &Hash::new_from_array([4; 32]));
+    let mut transaction = Transaction::new_unsigned(message);
+    transaction.partial_sign(&[&payer], transaction.message.recent_blockhash);
+    let encoded = general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap());
+
+    let (status, body) = post_json("/transaction/verify-signatures", json!({ "transaction": encoded })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["complete"], false);
+    assert_eq!(body["data"]["valid_count"], 1);
+    let signers = &body["data"]["signers"];
+    assert_eq!(signers[0]["pubkey"], payer.pubkey().to_string());
+    assert_eq!(signers[0]["status"], "valid");
+    assert_eq!(signers[0]["signature"], transaction.signatures[0].to_string());
+    assert_eq!(signers[1]["pubkey"], owner.pubkey().to_string());
+    assert_eq!(signers[1]["status"], "missing");
+    assert!(signers[1].get("signature").is_none());
+
+    transaction.signatures[1] = Signature::from([9; 64]);
+    let encoded = general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap());
+    let (_, body) = post_json("/transaction/verify-signatures", json!({ "transaction": encoded })).await;
+    assert_eq!(body["data"]["signers"][1]["status"], "invalid");
+
+    transaction.partial_sign(&[&owner], transaction.message.recent_blockhash);
+    let encoded = general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap());
+    let (_, body) = post_json("/transaction/verify-signatures", json!({ "transaction": encoded })).await;
+    assert_eq!(body["data"]["complete"], true);
+
+    let (status, body) = post_json("/transaction/verify-signatures", json!({ "transaction": "!!" })).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+}
+
+#[tokio::test]
+async fn sign_message_signs_detached_message_bytes() {
+    let (payer, owner) = (keypair(1), keypair(2));
+    let instruction = system_instruction::transfer(&owner.pubkey(), &key(3), 1_000);
+    let message = Message::new_with_blockhash(&[instruction], Some(&payer.pubkey()), &Hash::new_from_array([4; 32]));
+    let bytes = message.serialize();
+    let encoded = general_purpose::STANDARD.encode(&bytes);
+
+    let request = json!({ "message": encoded, "secret": owner.to_base58_string(), "signature_encoding": "base58" });
+    let (status, body) = post_json("/transaction/sign-message", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["signature"], owner.sign_message(&bytes).to_string());
+    assert_eq!(body["data"]["public_key"], owner.pubkey().to_string());
+    assert_eq!(body["data"]["signer_index"], 1);
+
+    let request = json!({ "message": encoded, "secret": keypair(3).to_base58_string() });
+    let (status, body) = post_json("/transaction/sign-message", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "message");
+
+    let padded = general_purpose::STANDARD.encode([bytes.as_slice(), &[0]].concat());
+    let request = json!({ "message": padded, "secret": owner.to_base58_string() });
+    let (status, body) = post_json("/transaction/sign-message", request).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+}
+
+#[tokio::test]
+async fn preview_reports_simulated_balance_changes() {
+    let mock = Arc::new(MockRpc::new());
+    mock.set_account(key(1), Account { lamports: 10_000_000_000, ..Account::default() });
+    mock.set_account(key(5), token_account(1, 100_000_000));
+    mock.set_account(key(6), token_account(2, 0));
+    mock.set_account(key(9), mint_account(spl_token::ID, 6));
+    mock.set_simulated_account(key(1), Some(Account { lamports: 8_499_995_000, ..Account::default() }));
+    mock.set_simulated_account(key(2), Some(Account { lamports: 1_500_000_000, ..Account::default() }));
+    mock.set_simulated_account(key(5), Some(token_account(1, 70_000_000)));
+    mock.set_simulated_account(key(6), Some(token_account(2, 30_000_000)));
+    mock.set_simulation(Ok(Simulation { units_consumed: Some(450), ..Simulation::default() }));
+    let app = mock_app(mock.clone());
+
+    let instructions = [
+        system_instruction::transfer(&key(1), &key(2), 1_500_000_000),
+        transfer_checked(&spl_token::ID, &key(5), &key(9), &key(6), &key(1), &[], 30_000_000, 6).unwrap(),
+    ];
+    let request = json!({ "transaction": encode(&instructions, Hash::new_from_array([4; 32])) });
+    let (status, body) = post_json_to(app.clone(), "/transaction/preview", request.clone()).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["status"], "success");
+    assert_eq!(body["data"]["units_consumed"], 450);
+
+    let accounts = body["data"]["accounts"].as_array().unwrap();
+    let preview = |seed: u8| accounts.iter().find(|account| account["address"] == pubkey(seed)).unwrap();
+    // The mint and programs are read-only, so only the four writable accounts show up.
+    assert_eq!(accounts.len(), 4);
+    assert_eq!(preview(1)["lamports"]["ui_change"], "-1.500005");
+    assert_eq!(preview(1)["token"], Value::Null);
+    assert_eq!(
+        preview(2)["lamports"],
+        json!({ "pre": "0", "post": "1500000000", "change": "1500000000", "ui_change": "1.5" })
+    );
+    assert_eq!(
+        preview(6)["token"],
+        json!({
+            "mint": pubkey(9),
+            "owner": pubkey(2),
+            "decimals": 6,
+            "amount": { "pre": "0", "post": "30000000", "change": "30000000", "ui_change": "30" },
+        })
+    );
+    assert_eq!(preview(5)["token"]["amount"]["ui_change"], "-30");
+
+    mock.set_simulation(Ok(Simulation { err: Some("InsufficientFundsForFee".to_string()), ..Simulation::default() }));
+    let (status, body) = post_json_to(app, "/transaction/preview", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["status"], "failed");
+    assert_eq!(body["data"]["error"], "InsufficientFundsForFee");
+    assert_eq!(body["data"]["accounts"], json!([]));
+}
+
+#[tokio::test]
+async fn send_rebuilds_on_fresh_blockhash_after_expiry() {
+    let mock = Arc::new(MockRpc::new());
+    let app = mock_app(mock.clone());
+    let fresh = Hash::new_from_array([5; 32]);
+
+    // The first send expires unconfirmed; the rebuilt one lands.
+    let cluster = mock.clone();
+    let chain = tokio::spawn(async move {
+        while cluster.sent_transactions().is_empty() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        cluster.set_block_height(400_151);
+        cluster.set_blockhash(LatestBlockhash { blockhash: fresh, last_valid_block_height: 400_300 });
+        while cluster.sent_transactions().len() < 2 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let transaction = cluster.sent_transactions().pop().unwrap();
+        let meta = TransactionMeta { fee: 5_000, ..TransactionMeta::default() };
+        let confirmed = ConfirmedTransaction { slot: 77, block_time: None, transaction: transaction.clone(), meta };
+        cluster.set_transaction(transaction.signatures[0], confirmed);
+    });
+
+    let payer = keypair(1).pubkey();
+    let message = Message::new_with_blockhash(
+        &[system_instruction::transfer(&payer, &key(2), 1_000)],
+        Some(&payer),
+        &Hash::new_from_array([1; 32]),
+    );
+    let unsigned = Transaction::new_unsigned(message);
+    let request = json!({
+        "transaction": general_purpose::STANDARD.encode(bincode::serialize(&unsigned).unwrap()),
+        "signers": [bs58::encode(keypair(1).to_bytes()).into_string()],
+    });
+    let (status, body) = post_json_to(app, "/transaction/send", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    chain.await.unwrap();
+
+    let sent = mock.sent_transactions();
+    assert_eq!(sent.len(), 2);
+    assert_eq!(*sent[1].message.recent_blockhash(), fresh);
+    assert!(sent[1].verify_with_results().iter().all(|valid| *valid));
+    let data = &body["data"];
+    assert_eq!(data["signature"], sent[1].signatures[0].to_string());
+    assert_eq!(data["slot"], 77);
+    assert_eq!(data["fee_lamports"], 5_000);
+    assert_eq!(data["rebuilds"], 1);
+    assert_eq!(data["last_valid_block_height"], 400_300);
+}
+
+#[tokio::test]
+async fn send_without_signers_reports_expired_blockhash() {
+    let mock = Arc::new(MockRpc::new());
+    let app = mock_app(mock.clone());
+    let payer = keypair(1).pubkey();
+    let transfer = system_instruction::transfer(&payer, &key(2), 1_000);
+
+    let (status, body) = post_json_to(
+        app.clone(),
+        "/transaction/send",
+        json!({ "transaction": encode(std::slice::from_ref(&transfer), Hash::new_from_array([1; 32])) }),
+    )
+    .await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "transaction");
+
+    // Signed on a blockhash the cluster no longer accepts.
+    let stale = Hash::new_from_array([2; 32]);
+    let message = Message::new_with_blockhash(&[transfer], Some(&payer), &stale);
+    let transaction = Transaction::new(&[&keypair(1)], message, stale);
+    let encoded = general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap());
+    let (status, body) = post_json_to(app, "/transaction/send", json!({ "transaction": encoded })).await;
+    assert_error(status, &body, StatusCode::CONFLICT, "BLOCKHASH_EXPIRED");
+    let hint = &body["rebuild"];
+    assert_eq!(hint["signature"], transaction.signatures[0].to_string());
+    assert_eq!(hint["expired_blockhash"], stale.to_string());
+    assert_eq!(hint["last_valid_block_height"], Value::Null);
+    assert_eq!(hint["recent_blockhash"], Hash::new_from_array([1; 32]).to_string());
+    assert_eq!(hint["recent_last_valid_block_height"], 400_150);
+    assert!(mock.sent_transactions().is_empty());
+}
+
+#[tokio::test]
+async fn builds_a_transaction_from_service_and_third_party_instructions() {
+    let transfer = json!({ "from": pubkey(1), "to": pubkey(2), "lamports": 100_000 });
+    let (status, transfer) = post_json("/send/sol", transfer).await;
+    assert_eq!(status, StatusCode::OK);
+    let third_party = json!({
+        "program_id": pubkey(30),
+        "accounts": [{ "pubkey": pubkey(31), "is_signer": true }, { "pubkey": pubkey(2), "is_writable": true }],
+        "data": general_purpose::STANDARD.encode([1, 2, 3]),
+    });
+    let blockhash = Hash::new_from_array([5; 32]);
+    let body = json!({
+        "payer": pubkey(1),
+        "instructions": [transfer["data"], third_party],
+        "recent_blockhash": blockhash.to_string(),
+    });
+
+    let (status, body) = post_json("/transaction/build", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["signers"], json!([pubkey(1), pubkey(31)]));
+    let bytes = general_purpose::STANDARD.decode(body["data"]["transaction"].as_str().unwrap()).unwrap();
+    assert_eq!(body["data"]["size"], bytes.len());
+    let transaction: Transaction = bincode::deserialize(&bytes).unwrap();
+    let expected = Message::new_with_blockhash(
+        &[
+            system_instruction::transfer(&key(1), &key(2), 100_000),
+            Instruction::new_with_bytes(
+                key(30),
+                &[1, 2, 3],
+                vec![
+                    AccountMeta::new_readonly(key(31), true),
+                    AccountMeta::new(key(2), false),
+                ],
+            ),
+        ],
+        Some(&key(1)),
+        &blockhash,
+    );
+    assert_eq!(transaction.message, expected);
+    assert_eq!(body["data"]["message"], general_purpose::STANDARD.encode(expected.serialize()));
+}
+
+#[tokio::test]
+async fn build_rejects_instruction_data_that_is_not_base64() {
+    let body = json!({
+        "payer": pubkey(1),
+        "instructions": [{ "program_id": pubkey(30), "accounts": [], "data": "not base64!" }],
+        "recent_blockhash": Hash::new_from_array([5; 32]).to_string(),
+    });
+
+    let (status, body) = post_json("/transaction/build", body).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "instructions[0].data");
+}
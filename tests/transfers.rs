@@ -0,0 +1,159 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::Signer};
+use solana_system_interface::instruction as system_instruction;
+
+use solana_fellowship_server::config::{ApprovalsConfig, Config, PolicyConfig};
+use solana_fellowship_server::rpc::MockRpc;
+use solana_fellowship_server::state::AppState;
+use solana_fellowship_server::types::Redacted;
+
+use common::{app_with, assert_error, call, get_json_from, keypair, pubkey};
+
+fn secret(seed: u8) -> String {
+    bs58::encode(keypair(seed).to_bytes()).into_string()
+}
+
+fn transfer(lamports: u64) -> Value {
+    let destination: Pubkey = pubkey(2).parse().unwrap();
+    let instruction: Instruction = system_instruction::transfer(&keypair(1).pubkey(), &destination, lamports);
+    json!({
+        "signers": [secret(1)],
+        "instructions": [{
+            "program_id": instruction.program_id.to_string(),
+            "accounts": instruction.accounts.iter().map(|meta| json!({
+                "pubkey": meta.pubkey.to_string(),
+                "is_signer": meta.is_signer,
+                "is_writable": meta.is_writable,
+            })).collect::<Vec<_>>(),
+            "data": general_purpose::STANDARD.encode(&instruction.data),
+        }],
+    })
+}
+
+fn treasury_app(mock: Arc<MockRpc>) -> Router {
+    let identities: HashMap<_, _> =
+        ["alice", "bob", "carol"].into_iter().map(|name| (name.to_string(), Redacted(format!("{name}-key")))).collect();
+    let config = Config {
+        policy: PolicyConfig { approval_threshold_lamports: Some(10_000), ..PolicyConfig::default() },
+        approvals: ApprovalsConfig { required: 2, identities, ..ApprovalsConfig::default() },
+        ..Config::default()
+    };
+    app_with(config, mock)
+}
+
+async fn post_as(app: Router, key: Option<&str>, path: &str, body: Value) -> (StatusCode, Value) {
+    let mut request = Request::post(path).header(header::CONTENT_TYPE, "application/json");
+    if let Some(key) = key {
+        request = request.header("x-api-key", key);
+    }
+    let (status, _, bytes) = call(app, request.body(Body::from(body.to_string())).unwrap()).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn submits_transfers_under_the_threshold_immediately() {
+    let mock = Arc::new(MockRpc::new());
+    let app = treasury_app(mock.clone());
+
+    let (status, body) = post_as(app, Some("alice-key"), "/transfers/propose", transfer(5_000)).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["status"], "submitted");
+    assert_eq!(body["data"]["proposer"], "alice");
+    assert!(body["data"]["signature"].is_string());
+    assert_eq!(mock.sent_transactions().len(), 1);
+}
+
+#[tokio::test]
+async fn holds_large_transfers_until_enough_identities_approve() {
+    let mock = Arc::new(MockRpc::new());
+    let app = treasury_app(mock.clone());
+
+    let (status, body) = post_as(app.clone(), Some("alice-key"), "/transfers/propose", transfer(50_000)).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["status"], "pending");
+    assert_eq!(body["data"]["fee_payer"], keypair(1).pubkey().to_string());
+    let id = body["data"]["id"].as_str().unwrap().to_string();
+    assert!(mock.sent_transactions().is_empty());
+
+    // Proposers can't approve their own transfers.
+    let (status, body) = post_as(app.clone(), Some("alice-key"), "/transfers/approve", json!({ "id": id })).await;
+    assert_error(status, &body, StatusCode::CONFLICT, "CONFLICT");
+
+    let (status, body) = post_as(app.clone(), Some("bob-key"), "/transfers/approve", json!({ "id": id })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["status"], "pending");
+    assert_eq!(body["data"]["approvals"], json!(["bob"]));
+
+    let (status, body) = post_as(app.clone(), Some("bob-key"), "/transfers/approve", json!({ "id": id })).await;
+    assert_error(status, &body, StatusCode::CONFLICT, "CONFLICT");
+    assert!(mock.sent_transactions().is_empty());
+
+    let (status, body) = post_as(app.clone(), Some("carol-key"), "/transfers/approve", json!({ "id": id })).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["status"], "submitted");
+    assert_eq!(mock.sent_transactions().len(), 1);
+
+    let (status, body) = get_json_from(app.clone(), &format!("/transfers/{id}")).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["approvals"], json!(["bob", "carol"]));
+
+    // Submitted proposals take no further approvals.
+    let (status, body) = post_as(app, Some("alice-key"), "/transfers/approve", json!({ "id": id })).await;
+    assert_error(status, &body, StatusCode::CONFLICT, "CONFLICT");
+    assert_eq!(mock.sent_transactions().len(), 1);
+}
+
+#[tokio::test]
+async fn requires_a_known_api_key() {
+    let app = treasury_app(Arc::new(MockRpc::new()));
+
+    let (status, body) = post_as(app.clone(), None, "/transfers/propose", transfer(5_000)).await;
+    assert_error(status, &body, StatusCode::UNAUTHORIZED, "UNAUTHORIZED");
+    let (status, body) = post_as(app.clone(), Some("mallory-key"), "/transfers/propose", transfer(5_000)).await;
+    assert_error(status, &body, StatusCode::UNAUTHORIZED, "UNAUTHORIZED");
+    let (status, body) = post_as(app.clone(), Some("bob-key"), "/transfers/approve", json!({ "id": "missing" })).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+
+    let empty = json!({ "signers": [], "instructions": [] });
+    let (status, body) = post_as(app, Some("bob-key"), "/transfers/propose", empty).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+}
+
+#[tokio::test]
+async fn expired_proposals_are_dropped_with_their_signers() {
+    let identities: HashMap<_, _> =
+        ["alice", "bob"].into_iter().map(|name| (name.to_string(), Redacted(format!("{name}-key")))).collect();
+    let config = Config {
+        policy: PolicyConfig { approval_threshold_lamports: Some(10_000), ..PolicyConfig::default() },
+        approvals: ApprovalsConfig { required: 1, ttl_secs: 1, identities },
+        ..Config::default()
+    };
+    let state = AppState::new(config);
+    state.rpc.replace(Some(Arc::new(MockRpc::new())));
+    let app = solana_fellowship_server::app(state.clone());
+
+    let (status, body) = post_as(app.clone(), Some("alice-key"), "/transfers/propose", transfer(50_000)).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let id = body["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(state.keystore.list("default", None).len(), 1);
+
+    tokio::time::sleep(std::time::Duration::from_millis(1_100)).await;
+    state.approvals.sweep();
+    assert!(state.keystore.list("default", None).is_empty());
+
+    let (status, body) = get_json_from(app.clone(), &format!("/transfers/{id}")).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+    let (status, body) = post_as(app, Some("bob-key"), "/transfers/approve", json!({ "id": id })).await;
+    assert_error(status, &body, StatusCode::NOT_FOUND, "NOT_FOUND");
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use solana_sdk::hash::Hash;
+
+use common::{assert_error, post_json, post_raw, pubkey};
+
+const BOUNDARY: &str = "superdev-test-boundary";
+
+fn multipart(fields: &[(&str, &str)], csv: &str) -> String {
+    let mut body = String::new();
+    for (name, value) in fields {
+        body += &format!("--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n");
+    }
+    body += &format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"recipients.csv\"\r\n\
+         Content-Type: text/csv\r\n\r\n{csv}\r\n--{BOUNDARY}--\r\n"
+    );
+    body
+}
+
+async fn upload(fields: &[(&str, &str)], csv: &str) -> (StatusCode, Value) {
+    let content_type = format!("multipart/form-data; boundary={BOUNDARY}");
+    post_raw("/airdrop/bulk", Some(&content_type), multipart(fields, csv)).await
+}
+
+#[tokio::test]
+async fn csv_upload_builds_airdrop_with_mapped_columns() {
+    let payer = pubkey(1);
+    let blockhash = Hash::new_from_array([3; 32]).to_string();
+    let csv = format!("Wallet,Lamports\n{},500\n{},700\n", pubkey(2), pubkey(3));
+    let fields = [
+        ("payer", payer.as_str()),
+        ("recent_blockhash", blockhash.as_str()),
+        ("recipient_column", "wallet"),
+        ("amount_column", "lamports"),
+    ];
+
+    let (status, body) = upload(&fields, &csv).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["recipient_count"], 2);
+    assert_eq!(body["data"]["total_amount"], 1_200);
+    assert_eq!(body["data"]["transactions"][0]["recipients"], json!([0, 2]));
+}
+
+#[tokio::test]
+async fn csv_upload_reports_row_and_field() {
+    let payer = pubkey(1);
+    let csv = format!("recipient,amount\n{},10\nnot-a-key,abc\n{payer},5\n", pubkey(2));
+
+    let (status, body) = upload(&[("payer", payer.as_str())], &csv).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+
+    let details = body["details"].as_array().unwrap();
+    assert_eq!(details.len(), 3);
+    assert_eq!(details[0], json!({
+        "field": "recipient", "row": 3, "code": "INVALID_PUBKEY", "message": "Invalid public key"
+    }));
+    assert_eq!((&details[1]["field"], &details[1]["row"], &details[1]["code"]), (&json!("amount"), &json!(3), &json!("INVALID_AMOUNT")));
+    assert_eq!((&details[2]["row"], &details[2]["code"]), (&json!(4), &json!("SELF_TRANSFER")));
+}
+
+#[tokio::test]
+async fn csv_upload_requires_mapped_columns() {
+    let payer = pubkey(1);
+    let csv = format!("address,amount\n{},10\n", pubkey(2));
+
+    let (status, body) = upload(&[("payer", payer.as_str())], &csv).await;
+    assert_error(status, &body, StatusCode::BAD_REQUEST, "INVALID_FIELD");
+    assert_eq!(body["field"], "file");
+}
+
+#[tokio::test]
+async fn dry_run_reports_totals_without_transactions() {
+    let payer = pubkey(1);
+    let csv = format!("recipient,amount\n{0},10\n{0},15\n{1},5\n", pubkey(2), pubkey(3));
+
+    let (status, body) = upload(&[("payer", payer.as_str()), ("dry_run", "true")], &csv).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(
+        body["data"],
+        json!({ "asset": "SOL", "recipient_count": 3, "unique_recipients": 2, "total_amount": 30 })
+    );
+
+    // Token dry runs skip the mint lookup, so they work without RPC too.
+    let request = json!({
+        "payer": payer,
+        "mint": pubkey(9),
+        "recipients": [{ "recipient": pubkey(2), "amount": 4 }],
+        "dry_run": true,
+    });
+    let (status, body) = post_json("/airdrop/bulk", request).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["asset"], pubkey(9));
+    assert!(body["data"].get("transactions").is_none());
+}
+
+#[tokio::test]
+async fn program_upload_builds_buffer_writes() {
+    let content_type = format!("multipart/form-data; boundary={BOUNDARY}");
+    let (payer, buffer) = (pubkey(1), pubkey(2));
+    let body = multipart(&[("payer", &payer), ("buffer", &buffer)], "not really an ELF");
+    let (status, body) = post_raw("/program/buffer", Some(&content_type), body).await;
+
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(body["data"]["program_len"], 17);
+    assert_eq!(body["data"]["writes"].as_array().unwrap().len(), 1);
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use axum::http::StatusCode;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::json;
+use solana_vote_interface::{instruction as vote_instruction, state::VoteAuthorize};
+
+use common::{assert_error, key, post_json, pubkey};
+
+#[tokio::test]
+async fn withdraw_builds_the_vote_program_instruction() {
+    let body = json!({
+        "vote_account": pubkey(1),
+        "authorized_withdrawer": pubkey(2),
+        "to": pubkey(3),
+        "lamports": 1_500_000,
+    });
+
+    let (status, body) = post_json("/vote/withdraw", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let expected = vote_instruction::withdraw(&key(1), &key(2), 1_500_000, &key(3));
+    assert_eq!(body["data"]["program_id"], expected.program_id.to_string());
+    assert_eq!(
+        body["data"]["accounts"],
+        json!([
+            { "pubkey": pubkey(1), "is_signer": false, "is_writable": true },
+            { "pubkey": pubkey(3), "is_signer": false, "is_writable": true },
+            { "pubkey": pubkey(2), "is_signer": true, "is_writable": false },
+        ])
+    );
+    assert_eq!(body["data"]["instruction_data"], general_purpose::STANDARD.encode(&expected.data));
+}
+
+#[tokio::test]
+async fn withdraw_rejects_zero_and_the_vote_account_itself() {
+    let body = json!({
+        "vote_account": pubkey(1),
+        "authorized_withdrawer": pubkey(2),
+        "to": pubkey(1),
+        "lamports": 0,
+    });
+
+    let (status, body) = post_json("/vote/withdraw", body).await;
+    assert_error(status, &body, StatusCode::UNPROCESSABLE_ENTITY, "VALIDATION_FAILED");
+    assert_eq!(body["details"][0]["code"], "AMOUNT_ZERO");
+    assert_eq!(body["details"][1]["code"], "SELF_TRANSFER");
+}
+
+#[tokio::test]
+async fn authorize_checked_needs_the_new_authority_to_sign() {
+    let body = json!({
+        "vote_account": pubkey(1),
+        "authority": pubkey(2),
+        "new_authority": pubkey(4),
+        "authority_type": "withdrawer",
+        "checked": true,
+    });
+
+    let (status, body) = post_json("/vote/authorize", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let expected = vote_instruction::authorize_checked(&key(1), &key(2), &key(4), VoteAuthorize::Withdrawer);
+    assert_eq!(body["data"]["instruction_data"], general_purpose::STANDARD.encode(&expected.data));
+    assert_eq!(body["data"]["accounts"][3], json!({ "pubkey": pubkey(4), "is_signer": true, "is_writable": false }));
+
+    let body = json!({
+        "vote_account": pubkey(1),
+        "authority": pubkey(2),
+        "new_authority": pubkey(4),
+        "authority_type": "voter",
+    });
+    let (status, body) = post_json("/vote/authorize", body).await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    let expected = vote_instruction::authorize(&key(1), &key(2), &key(4), VoteAuthorize::Voter);
+    assert_eq!(body["data"]["instruction_data"], general_purpose::STANDARD.encode(&expected.data));
+    assert_eq!(body["data"]["accounts"].as_array().unwrap().len(), 3);
+}